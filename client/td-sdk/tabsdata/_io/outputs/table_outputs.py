@@ -11,8 +11,12 @@ from tabsdata._io.plugin import DestinationPlugin
 from tabsdata._tabsserver.function.native_tables_utils import sink_lf_to_location
 from tabsdata._tabsserver.function.store_results_utils import (
     get_table_meta_info_from_lf,
+    get_table_stats_query,
 )
 from tabsdata._tabsserver.function.yaml_parsing import Table
+
+# noinspection PyProtectedMember
+from tabsdata._utils.tableframe._common import drop_system_columns
 from tabsdata.exceptions import (
     DestinationConfigurationError,
     ErrorCode,
@@ -162,8 +166,15 @@ def _store_results_in_table(
                 idx=result_value._idx,
                 properties=result_value._properties,
             )
-            sink_lf_to_location(tf._to_lazy(), execution_context, table.location)
-            table_meta_info = get_table_meta_info_from_lf(lf)
+            written_lf = tf._to_lazy()
+            # The statistics are computed in the same pass that writes the table.
+            [stats] = sink_lf_to_location(
+                written_lf,
+                execution_context,
+                table.location,
+                along=[get_table_stats_query(drop_system_columns(written_lf))],
+            )
+            table_meta_info = get_table_meta_info_from_lf(lf, stats)
             table_info = {"name": table.name, "meta_info": table_meta_info}
             modified_tables.append(table_info)
             logger.debug(
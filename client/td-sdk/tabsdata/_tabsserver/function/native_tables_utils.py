@@ -85,7 +85,15 @@ def sink_lf_to_location(
     lf: pl.LazyFrame,
     execution_context: ExecutionContext,
     location: Location,
-):
+    along: list[pl.LazyFrame] | None = None,
+) -> list[pl.DataFrame]:
+    """
+    Sinks a LazyFrame to the given location as a Parquet file.
+
+    The queries in along, derived from lf, are run in the same pass as the sink, sharing
+    the scan of the data with it, so it is read only once. Their results are returned in
+    the same order.
+    """
     if location.uri is None:
         raise ValueError(
             "Location URI must be specified to sink a LazyFrame. Got "
@@ -122,6 +130,13 @@ def sink_lf_to_location(
     ]
     if columns_to_drop:
         lf = lf.drop(columns_to_drop)
-    lf.sink_parquet(uri, storage_options=storage_options, maintain_order=True)
+    if not along:
+        lf.sink_parquet(uri, storage_options=storage_options, maintain_order=True)
+        logger.debug("LazyFrame sunk successfully.")
+        return []
+    sink = lf.sink_parquet(
+        uri, storage_options=storage_options, maintain_order=True, lazy=True
+    )
+    results = pl.collect_all([sink, *along])
     logger.debug("LazyFrame sunk successfully.")
-    return
+    return results[1:]
@@ -56,6 +56,7 @@ def create_response(
                     column_count=table_cols,
                     row_count=table_rows,
                     schema_hash=table_schema_hash,
+                    column_stats=table_info.get("column_stats"),
                 )
             )
         except KeyError as e:
@@ -81,6 +82,7 @@ def create_response(
                     column_count=initial_values_meta_info["column_count"],
                     row_count=initial_values_meta_info["row_count"],
                     schema_hash=initial_values_meta_info["schema_hash"],
+                    column_stats=initial_values_meta_info.get("column_stats"),
                 )
             )
             modified_tables.append(
@@ -7,6 +7,7 @@ from __future__ import annotations
 import glob
 import hashlib
 import logging
+import math
 import os
 import re
 from functools import partial
@@ -52,19 +53,87 @@ def store_results(
     logger.debug("Destination plugin run completed successfully")
 
 
-def get_table_meta_info_from_lf(lf: pl.LazyFrame) -> dict:
+def get_table_meta_info_from_lf(
+    lf: pl.LazyFrame, stats: pl.DataFrame | None = None
+) -> dict:
     """
     Extracts table information from a Polars LazyFrame.
     This function retrieves the schema and other metadata from the LazyFrame.
 
+    The row count and the column statistics require reading the data. When the table is
+    written, the query of get_table_stats_query should be run along with the write, and its
+    result given as stats, so the data is not read again to compute them.
+
     :param lf: Polars LazyFrame
+    :param stats: Result of the query of get_table_stats_query, if already run
     :return: Dictionary containing table information
     """
     lf = drop_system_columns(lf)
     columns = lf.width
-    rows = lf.select(pl.len()).collect().to_series().item()
+    schema = lf.collect_schema()
+    if stats is None:
+        stats = get_table_stats_query(lf).collect()
+    rows, column_stats = get_table_stats_from_df(schema, stats)
     schema_hash = arrow_schema_hash(get_arrow_schema(lf), sort_schema=True)
-    return {"column_count": columns, "row_count": rows, "schema_hash": schema_hash}
+    return {
+        "column_count": columns,
+        "row_count": rows,
+        "schema_hash": schema_hash,
+        "column_stats": column_stats,
+    }
+
+
+def get_table_stats_query(lf: pl.LazyFrame) -> pl.LazyFrame:
+    """
+    Builds the query computing the row count and the per-column statistics (null count,
+    and min and max for numeric columns) of a Polars LazyFrame. All of them are aggregated
+    in a single row, so the data is scanned only once, and the query can be run along with
+    the sink of the LazyFrame.
+
+    :param lf: Polars LazyFrame, without system columns
+    :return: Polars LazyFrame with the aggregated statistics
+    """
+    aggregations = [pl.len().alias("__len")]
+    for index, (name, dtype) in enumerate(lf.collect_schema().items()):
+        aggregations.append(pl.col(name).null_count().alias(f"__nulls_{index}"))
+        if dtype.is_integer():
+            column = pl.col(name)
+        elif dtype.is_numeric():
+            # decimals are not serializable to JSON or YAML
+            column = pl.col(name).cast(pl.Float64)
+        else:
+            continue
+        aggregations.append(column.min().alias(f"__min_{index}"))
+        aggregations.append(column.max().alias(f"__max_{index}"))
+    return lf.select(aggregations)
+
+
+def get_table_stats_from_df(
+    schema: pl.Schema, df: pl.DataFrame
+) -> tuple[int, list[dict]]:
+    """
+    Extracts the row count and the per-column statistics from the result of the query of
+    get_table_stats_query. Min and max of integer columns are integers, and of other
+    numeric columns floats. The server stores all of them as floats, so integers beyond
+    2^53 are recorded with the precision of a float.
+
+    :param schema: Schema of the LazyFrame the statistics were computed on
+    :param df: Result of the query of get_table_stats_query
+    :return: Tuple with the row count and the statistics of each column, in column order
+    """
+    aggregated = df.row(0, named=True)
+    column_stats = []
+    for index, name in enumerate(schema.names()):
+        stats = {"name": name, "null_count": aggregated[f"__nulls_{index}"]}
+        for stat in ("min", "max"):
+            value = aggregated.get(f"__{stat}_{index}")
+            # NaN is not a valid statistic, and it can't be serialized to JSON or YAML
+            if value is not None and not (
+                isinstance(value, float) and math.isnan(value)
+            ):
+                stats[stat] = value
+        column_stats.append(stats)
+    return aggregated["__len"], column_stats
 
 
 # Get the user's schema of a Tabsdata Parquet file as an Arrow schema
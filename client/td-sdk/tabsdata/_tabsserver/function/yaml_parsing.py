@@ -334,6 +334,7 @@ class Data:
         column_count: int = None,
         row_count: int = None,
         schema_hash: str = None,
+        column_stats: list[dict] = None,
     ):
         self.content = {
             "table": table,
@@ -348,6 +349,9 @@ class Data:
         if schema_hash is not None:
             self.content["info"] = self.content.get("info", {})
             self.content["info"]["schema_hash"] = schema_hash
+        if column_stats is not None:
+            self.content["info"] = self.content.get("info", {})
+            self.content["info"]["column_stats"] = column_stats
 
     def __repr__(self):
         return f"{self.__class__.__name__}(content={self.content})"
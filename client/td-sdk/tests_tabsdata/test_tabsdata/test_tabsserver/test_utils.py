@@ -6,12 +6,17 @@ import logging
 import os
 import pathlib
 
+import polars as pl
 import pytest
 
+from tabsdata._tabsserver.function.native_tables_utils import sink_lf_to_location
 from tabsdata._tabsserver.function.store_results_utils import (
     _extract_index,
     _get_matching_files,
+    get_table_meta_info_from_lf,
+    get_table_stats_query,
 )
+from tabsdata._tabsserver.function.yaml_parsing import Location
 from tabsdata._tabsserver.utils import convert_uri_to_path
 
 # noinspection PyUnresolvedReferences
@@ -57,3 +62,44 @@ def test_get_matching_files(tmp_path):
         _get_matching_files(os.path.join(tmp_path, "example_file_*.jsonl"))
         == files_generated
     )
+
+
+def test_get_table_meta_info_column_stats():
+    lf = pl.LazyFrame(
+        {
+            "a": [3, None, -2, 5],
+            "b": ["x", "y", None, "z"],
+            "c": [None, None, None, None],
+            "d": [1.5, float("nan"), 0.5, None],
+        },
+        schema={"a": pl.Int64, "b": pl.String, "c": pl.Float64, "d": pl.Float64},
+    )
+    meta_info = get_table_meta_info_from_lf(lf)
+    assert meta_info["row_count"] == 4
+    assert meta_info["column_count"] == 4
+    assert meta_info["column_stats"] == [
+        {"name": "a", "null_count": 1, "min": -2, "max": 5},
+        {"name": "b", "null_count": 1},
+        {"name": "c", "null_count": 4},
+        {"name": "d", "null_count": 1, "min": 0.5, "max": 1.5},
+    ]
+    # integer statistics are not converted to floats
+    assert isinstance(meta_info["column_stats"][0]["min"], int)
+    assert isinstance(meta_info["column_stats"][0]["max"], int)
+
+
+def test_get_table_meta_info_column_stats_written(tmp_path):
+    lf = pl.LazyFrame({"a": [3, None, -2, 5]}, schema={"a": pl.Int64})
+    path = os.path.join(tmp_path, "table.parquet")
+    location = Location({"uri": pathlib.Path(path).as_uri()})
+    # the statistics are computed in the same pass that writes the table
+    [stats] = sink_lf_to_location(
+        lf, None, location, along=[get_table_stats_query(lf)]
+    )
+    meta_info = get_table_meta_info_from_lf(lf, stats)
+    assert meta_info["row_count"] == 4
+    assert meta_info["column_count"] == 1
+    assert meta_info["column_stats"] == [
+        {"name": "a", "null_count": 1, "min": -2, "max": 5}
+    ]
+    assert pl.read_parquet(path).equals(lf.collect())
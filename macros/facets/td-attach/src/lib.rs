@@ -23,6 +23,9 @@ const TABSDATALIB: &str = "tabsdatalib";
 /// - Start your process as normal.
 /// - Attach a debugger to your process.
 /// - When the breakpoint is hit, change the variable condition to exit the loop.
+///
+/// An optional `timeout_secs` argument (e.g. `#[attach(signal = "apiserver", timeout_secs = 30)]`)
+/// makes the program resume, with a warning, if no debugger releases the loop within that time.
 #[proc_macro_attribute]
 pub fn attach(args: TokenStream, item: TokenStream) -> TokenStream {
     let attr_args = match NestedMeta::parse_meta_list(args.into()) {
@@ -41,19 +44,30 @@ pub fn attach(args: TokenStream, item: TokenStream) -> TokenStream {
     let func_body = &input.block;
     let func_sig = &input.sig;
     let signal = args.signal.to_string();
-    let attach_path = match crate_name(TABSDATALIB) {
-        Ok(FoundCrate::Itself) => quote!(crate::attach::wait_for_attach),
+    let attach_module = match crate_name(TABSDATALIB) {
+        Ok(FoundCrate::Itself) => quote!(crate::attach),
         Ok(FoundCrate::Name(external_name)) => {
             let external_name = syn::Ident::new(&external_name, proc_macro2::Span::call_site());
-            quote!(#external_name::attach::wait_for_attach)
+            quote!(#external_name::attach)
         }
-        Err(_) => quote!(td_common::attach::wait_for_attach),
+        Err(_) => quote!(td_common::attach),
+    };
+    let wait_for_attach = match args.timeout_secs {
+        Some(timeout_secs) => quote! {
+            #attach_module::wait_for_attach_timeout(
+                #signal,
+                ::std::time::Duration::from_secs(#timeout_secs),
+            );
+        },
+        None => quote! {
+            #attach_module::wait_for_attach(#signal);
+        },
     };
 
     let expanded = quote! {
         #func_sig {
             #[cfg(debug_assertions)] {
-                #attach_path(#signal);
+                #wait_for_attach
             }
             #func_body
         }
@@ -64,4 +78,6 @@ pub fn attach(args: TokenStream, item: TokenStream) -> TokenStream {
 #[derive(Debug, FromMeta)]
 struct Arguments {
     signal: String,
+    #[darling(default)]
+    timeout_secs: Option<u64>,
 }
@@ -30,6 +30,7 @@ pub struct DaoArguments {
     order_by: Option<String>,
     versioned: Option<VersionedArguments>,
     recursive: Option<DaoRecursiveArguments>,
+    audit: Option<DaoAuditArguments>,
     #[darling(default)]
     states: HashMap<Ident, Expr>,
 }
@@ -53,6 +54,16 @@ impl ToTokens for DaoArguments {
             let down = &recursive.down;
             args.push(quote! { recursive(up = #up, down = #down) });
         }
+        if let Some(audit) = &self.audit {
+            let mut columns = Vec::new();
+            if let Some(created_on) = &audit.created_on {
+                columns.push(quote! { created_on = #created_on });
+            }
+            if let Some(created_by) = &audit.created_by {
+                columns.push(quote! { created_by = #created_by });
+            }
+            args.push(quote! { audit(#(#columns),*) });
+        }
         let states = self
             .states
             .iter()
@@ -77,6 +88,7 @@ impl DaoArguments {
             order_by: other.order_by.or(self.order_by),
             versioned: other.versioned.or(self.versioned),
             recursive: other.recursive.or(self.recursive),
+            audit: other.audit.or(self.audit),
             states: other.states.into_iter().chain(self.states).collect(),
         }
     }
@@ -95,6 +107,14 @@ struct DaoRecursiveArguments {
     down: String,
 }
 
+/// Audit columns (the fields holding the creation time and the creating user) filled from an
+/// audit context by the Dao builder, when not explicitly set.
+#[derive(Debug, FromMeta)]
+struct DaoAuditArguments {
+    created_on: Option<String>,
+    created_by: Option<String>,
+}
+
 pub fn dao_type(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let parsed_args = DaoArguments::from_derive_input(&input).unwrap();
@@ -111,6 +131,11 @@ pub fn dao_type(input: TokenStream) -> TokenStream {
     let field_names = gen_fields_as_list(fields);
     let field_types = gen_field_types_as_list(fields);
     let immutable_field_names = gen_immutable_fields_as_list(fields);
+    let read_only_field_names = gen_read_only_fields_as_list(fields);
+    let writable_field_names = field_names
+        .iter()
+        .filter(|f| !read_only_field_names.contains(f))
+        .collect::<Vec<_>>();
 
     // Dao specifics
     let sql_table = match parsed_args.sql_table {
@@ -230,6 +255,41 @@ pub fn dao_type(input: TokenStream) -> TokenStream {
     };
 
     let builder_type = format_ident!("{}Builder", &item.ident);
+
+    let audit = match &parsed_args.audit {
+        Some(audit) => {
+            let columns = [
+                (&audit.created_on, quote! { created_on }),
+                (&audit.created_by, quote! { created_by }),
+            ]
+            .into_iter()
+            .filter_map(|(column, value)| {
+                let column = column.as_ref()?;
+                // Panics if the audit column is not a field of the Dao.
+                type_for_field(fields, column);
+                let column = format_ident!("{}", column);
+                Some(quote! {
+                    if self.#column.is_none() {
+                        self.#column = Some(audit.#value.clone().into());
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+            quote! {
+                impl #impl_generics #builder_type #ty_generics #where_clause {
+                    /// Fills the audit fields not explicitly set from the given audit context.
+                    pub fn audit(&mut self, audit: &crate::dxo::crudl::AuditContext) -> &mut Self {
+                        #(#columns)*
+                        self
+                    }
+                }
+            }
+        }
+        None => {
+            quote! {}
+        }
+    };
+
     let expanded = quote! {
         impl #impl_generics crate::types::DataAccessObject for #ident #ty_generics #where_clause {
             type Builder = #builder_type #ty_generics;
@@ -250,6 +310,10 @@ pub fn dao_type(input: TokenStream) -> TokenStream {
                 &[#(stringify!(#immutable_field_names)),*]
             }
 
+            fn writable_fields() -> &'static [&'static str] {
+                &[#(stringify!(#writable_field_names)),*]
+            }
+
             fn sql_field_for_type(val: std::any::TypeId) -> Result<&'static str, td_error::TdError> {
                 match val {
                     #(
@@ -307,6 +371,8 @@ pub fn dao_type(input: TokenStream) -> TokenStream {
             }
         }
 
+        #audit
+
         #td_type
         #versioned
         #all_state
@@ -369,6 +435,20 @@ fn gen_immutable_fields_as_list(fields: &Fields) -> Vec<&Ident> {
         .collect()
 }
 
+fn gen_read_only_fields_as_list(fields: &Fields) -> Vec<&Ident> {
+    fields
+        .iter()
+        .filter(|f| {
+            // Check if the field does have the `#[dao(read_only)]` attribute
+            f.attrs.iter().any(|attr| {
+                attr.path().is_ident("dao")
+                    && attr.to_token_stream().to_string().contains("read_only")
+            })
+        })
+        .filter_map(|f| f.ident.as_ref())
+        .collect()
+}
+
 fn gen_field_types_as_list(fields: &Fields) -> Vec<&Type> {
     fields
         .iter()
@@ -3,22 +3,109 @@
 //
 
 use crate::type_builder::{parse_input_item_struct, td_type};
+use darling::FromField;
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
-use syn::{DeriveInput, ItemStruct, parse_macro_input};
+use syn::{Attribute, DeriveInput, ItemStruct, parse_macro_input, parse_quote};
 
 pub fn dlo(_args: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as ItemStruct);
+    let mut input = parse_macro_input!(item as ItemStruct);
+
+    let ident = &input.ident;
+    let builder_type = format_ident!("{}Builder", ident);
+    let serde = derives_serde(&input.attrs);
+
+    // Fields with `#[dlo(flatten)]` are inlined into the parent, both for serde and for the
+    // builder, which holds the builder of the nested DLO and delegates to it.
+    let mut flatten_setters = Vec::new();
+    for field in input.fields.iter_mut() {
+        let field_args = DloFieldArguments::from_field(field).unwrap();
+        if !field_args.flatten {
+            continue;
+        }
+
+        let field_name = field.ident.as_ref().unwrap();
+        let field_type = &field.ty;
+        let field_builder_type = match field_type {
+            syn::Type::Path(type_path) => {
+                let mut builder_path = type_path.clone();
+                let last = builder_path.path.segments.last_mut().unwrap();
+                last.ident = format_ident!("{}Builder", last.ident);
+                builder_path
+            }
+            _ => panic!("Flattened field '{field_name}' must be a DLO type"),
+        };
+        let builder_ty = quote!(#field_builder_type).to_string();
+        let build = format!("self.{field_name}.build().map_err(|e| e.to_string())?");
+        let with_field = format_ident!("with_{}", field_name);
+
+        flatten_setters.push(quote! {
+            pub fn #field_name<VALUE: Into<#field_type>>(&mut self, value: VALUE) -> &mut Self {
+                self.#field_name = value.into().to_builder();
+                self
+            }
+
+            /// Sets the fields of the flattened DLO with its builder.
+            pub fn #with_field(
+                &mut self,
+                with: impl FnOnce(&mut #field_builder_type) -> &mut #field_builder_type,
+            ) -> &mut Self {
+                with(&mut self.#field_name);
+                self
+            }
+        });
+
+        field.attrs.push(parse_quote! {
+            #[builder(setter(custom), field(ty = #builder_ty, build = #build))]
+        });
+        if serde {
+            field.attrs.push(parse_quote! { #[serde(flatten)] });
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let flatten_setters = if flatten_setters.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics #builder_type #ty_generics #where_clause {
+                #(#flatten_setters)*
+            }
+        }
+    };
 
     let expanded = quote! {
         #[derive(Debug, Clone, td_type::DloType, derive_builder::Builder)]
         #[builder(try_setter, setter(into))]
         #input
+
+        #flatten_setters
     };
 
     expanded.into()
 }
 
+#[derive(FromField)]
+#[darling(attributes(dlo))]
+struct DloFieldArguments {
+    /// Inlines the fields of the nested DLO into the parent.
+    #[darling(default)]
+    flatten: bool,
+}
+
+/// Whether the DLO also derives serde serialization or deserialization.
+fn derives_serde(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .any(|attr| {
+            let derives = attr.meta.require_list().map(|list| list.tokens.to_string());
+            derives.is_ok_and(|derives| {
+                derives.contains("Serialize") || derives.contains("Deserialize")
+            })
+        })
+}
+
 pub fn dlo_type(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let item = parse_input_item_struct(&input);
@@ -8,7 +8,7 @@ use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::{format_ident, quote};
 use std::collections::HashMap;
-use syn::{DeriveInput, ItemStruct, parse_macro_input};
+use syn::{Attribute, DeriveInput, ItemStruct, parse_macro_input};
 
 pub fn dto(_args: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemStruct);
@@ -18,12 +18,43 @@ pub fn dto(_args: TokenStream, item: TokenStream) -> TokenStream {
     let attrs = &input.attrs;
     let ident = &input.ident;
     let fields = &input.fields;
-    let (impl_generics, _ty_generics, where_clause) = input.generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+
+    // Default value functions for fields with `#[dto(default = "expr")]`, as serde requires a
+    // function path instead of an expression. They are associated functions, so they can use the
+    // generics of the DTO.
+    let mut default_fns = Vec::new();
 
     // Generate doc comments for fields
     let fields_with_docs = fields.iter().map(|field| {
         let field_args = DtoFieldArguments::from_field(field).unwrap();
 
+        let serde_default = field_args.default.map(|default| {
+            let default: syn::Expr = syn::parse_str(&default)
+                .unwrap_or_else(|e| panic!("Invalid dto default expression '{default}': {e}"));
+            let field_ty = &field.ty;
+            let default_fn = format_ident!("__dto_default_{}", field.ident.as_ref().unwrap());
+            let default_fn_name = quote! { #ident #turbofish :: #default_fn }.to_string();
+            // String literals are converted into the field type, anything else is used as is.
+            let default = match &default {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(_),
+                    ..
+                }) => quote! { #default.into() },
+                _ => quote! { #default },
+            };
+            default_fns.push(quote! {
+                #[doc(hidden)]
+                fn #default_fn() -> #field_ty {
+                    #default
+                }
+            });
+            quote! {
+                #[serde(default = #default_fn_name)]
+            }
+        });
+
         // Generate doc comments based on field arguments
         let doc_comments = field_args.list.into_iter().map(|arg| {
             let mut field_doc = String::new();
@@ -48,9 +79,21 @@ pub fn dto(_args: TokenStream, item: TokenStream) -> TokenStream {
 
         quote! {
             #(#doc_comments)*
+            #serde_default
             #field,
         }
     });
+    let fields_with_docs = fields_with_docs.collect::<Vec<_>>();
+
+    let default_fns = if default_fns.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#default_fns)*
+            }
+        }
+    };
 
     let expanded = quote! {
         #[derive(Debug, Clone, td_type::DtoType, derive_builder::Builder, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
@@ -59,6 +102,8 @@ pub fn dto(_args: TokenStream, item: TokenStream) -> TokenStream {
         #vis #struct_token #ident #impl_generics #where_clause {
             #(#fields_with_docs)*
         }
+
+        #default_fns
     };
 
     expanded.into()
@@ -81,6 +126,9 @@ struct ListArguments {
 struct DtoFieldArguments {
     #[darling(multiple)]
     list: Vec<FieldListArguments>,
+    /// Expression used as value when the field is absent on deserialization.
+    #[darling(default)]
+    default: Option<String>,
 }
 
 #[derive(FromMeta)]
@@ -201,6 +249,22 @@ pub fn dto_type(input: TokenStream) -> TokenStream {
             panic!("A field must be marked as pagination_by");
         }
 
+        // Selected by their serialized names, as they are selected on the serialized response.
+        let rename_all = serde_attr_value(&item.attrs, "rename_all");
+        let select_fields = fields
+            .iter()
+            .filter(|f| !serde_attr_flag(&f.attrs, &["skip", "skip_serializing", "flatten"]))
+            .filter_map(|f| {
+                let name = f.ident.as_ref()?.to_string();
+                let name = name.trim_start_matches("r#");
+                Some(
+                    serde_attr_value(&f.attrs, "rename").unwrap_or_else(|| match &rename_all {
+                        Some(rule) => rename_field(name, rule),
+                        None => name.to_string(),
+                    }),
+                )
+            });
+
         let field_names = field_type_map.keys();
         let field_types = field_type_map.values();
 
@@ -274,6 +338,10 @@ pub fn dto_type(input: TokenStream) -> TokenStream {
                     &[#(stringify!(#filter_like_fields)),*]
                 }
 
+                fn select_fields() -> &'static [&'static str] {
+                    &[#(#select_fields),*]
+                }
+
             }
         }
     } else {
@@ -292,3 +360,78 @@ pub fn dto_type(input: TokenStream) -> TokenStream {
 
     expanded.into()
 }
+
+/// Parses the arguments of the `#[serde(...)]` attributes, calling `f` with each argument name
+/// and its string value, if any.
+fn parse_serde_attrs(attrs: &[Attribute], mut f: impl FnMut(&syn::Path, Option<String>)) {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            let mut value = None;
+            if meta.input.peek(syn::Token![=]) {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }) = meta.value()?.parse()?
+                {
+                    value = Some(lit.value());
+                }
+            } else if meta.input.peek(syn::token::Paren) {
+                let _content;
+                syn::parenthesized!(_content in meta.input);
+            }
+            f(&meta.path, value);
+            Ok(())
+        });
+    }
+}
+
+/// String value of the given `#[serde(name = "value")]` argument, if any.
+fn serde_attr_value(attrs: &[Attribute], name: &str) -> Option<String> {
+    let mut found = None;
+    parse_serde_attrs(attrs, |path, value| {
+        if path.is_ident(name) && value.is_some() {
+            found = value;
+        }
+    });
+    found
+}
+
+/// Whether any of the given `#[serde(name)]` arguments is present.
+fn serde_attr_flag(attrs: &[Attribute], names: &[&str]) -> bool {
+    let mut found = false;
+    parse_serde_attrs(attrs, |path, _| {
+        found |= names.iter().any(|name| path.is_ident(name));
+    });
+    found
+}
+
+/// Renames a snake case field name as serde `rename_all` does with the given rule.
+fn rename_field(name: &str, rule: &str) -> String {
+    let pascal_case = || {
+        name.split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+    match rule {
+        "lowercase" | "snake_case" => name.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_uppercase(),
+        "PascalCase" => pascal_case(),
+        "camelCase" => {
+            let pascal_case = pascal_case();
+            let mut chars = pascal_case.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.replace('_', "-").to_uppercase(),
+        _ => panic!("Unsupported serde rename_all rule {rule}"),
+    }
+}
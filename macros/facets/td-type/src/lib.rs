@@ -60,7 +60,7 @@ pub fn UrlParam(args: TokenStream, item: TokenStream) -> TokenStream {
 #[proc_macro_attribute]
 #[allow(non_snake_case)]
 pub fn QueryParam(args: TokenStream, item: TokenStream) -> TokenStream {
-    url::url_param(args, item)
+    url::query_param(args, item)
 }
 
 #[proc_macro_attribute]
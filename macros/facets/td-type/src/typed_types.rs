@@ -115,6 +115,8 @@ pub struct TypedString {
     min_len: Option<SynMetaOrLit>,
     max_len: Option<SynMetaOrLit>,
     regex: Option<SynMetaOrLit>,
+    /// Alias of `regex`.
+    pattern: Option<SynMetaOrLit>,
     parser: Option<SynMetaOrLit>,
 }
 
@@ -140,6 +142,11 @@ pub fn typed_string(input: &ItemStruct, typed: Option<TypedString>) -> proc_macr
             _ => {}
         };
 
+        assert!(
+            typed.regex.is_none() || typed.pattern.is_none(),
+            "regex and pattern cannot be used together"
+        );
+
         let default = downcast_option!(typed.default, String);
         if let Some(default) = default {
             if let Some(len) = len {
@@ -168,7 +175,7 @@ pub fn typed_string(input: &ItemStruct, typed: Option<TypedString>) -> proc_macr
             typed.len,
             typed.min_len,
             typed.max_len,
-            typed.regex,
+            typed.regex.or(typed.pattern),
             typed.parser,
         )
     } else {
@@ -204,6 +211,15 @@ pub fn typed_string(input: &ItemStruct, typed: Option<TypedString>) -> proc_macr
         quote! { None }
     };
 
+    // Error messages carry the typed name, so failures deep in a request point to the field.
+    let len_error = format!("{name}: string value '{{0}}' must be of length {{1}}");
+    let min_len_error =
+        format!("{name}: string value '{{0}}' cannot be shorter than {{1}} characters");
+    let max_len_error =
+        format!("{name}: string value '{{0}}' cannot be longer than {{1}} characters");
+    let regex_error = format!("{name}: string value '{{0}}' does not match regex '{{1}}'");
+    let parse_error = format!("{name}: error parsing string value: {{0}}");
+
     let expanded = quote! {
         #(#attrs)*
         #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, sqlx::Type, utoipa::ToSchema)]
@@ -212,15 +228,15 @@ pub fn typed_string(input: &ItemStruct, typed: Option<TypedString>) -> proc_macr
 
         #[td_error::td_error]
         pub enum #error_name {
-            #[error("String value '{0}' must be of length {1}")]
+            #[error(#len_error)]
             Len(String, usize),
-            #[error("String value '{0}' cannot be shorter than {1} characters")]
+            #[error(#min_len_error)]
             MinLen(String, usize),
-            #[error("String value '{0}' cannot be longer than {1} characters")]
+            #[error(#max_len_error)]
             MaxLen(String, usize),
-            #[error("String value '{0}' does not match regex '{1}'")]
+            #[error(#regex_error)]
             Regex(String, String),
-            #[error("Error parsing string value: {0}")]
+            #[error(#parse_error)]
             Parse(#[source] td_error::TdError),
         }
 
@@ -443,17 +459,21 @@ pub fn typed_numeric<T: FromStr + ToTokens + PartialOrd>(
     let min = some_or_none(min);
     let max = some_or_none(max);
 
+    let min_error = format!("{name}: value '{{0}}' cannot be lower than '{{1}}'");
+    let max_error = format!("{name}: value '{{0}}' cannot be higher than '{{1}}'");
+    let parse_error_msg = format!("{name}: error parsing numeric value: {{0}}");
+
     let expanded = quote! {
         #(#attrs)*
         pub struct #name(#int_type);
 
         #[td_error::td_error]
         pub enum #error_name {
-            #[error("Value '{0}' cannot be lower than '{1}'")]
+            #[error(#min_error)]
             Min(#int_type, #int_type),
-            #[error("Value '{0}' cannot be higher than '{1}'")]
+            #[error(#max_error)]
             Max(#int_type, #int_type),
-            #[error("Error parsing numeric value: {0}")]
+            #[error(#parse_error_msg)]
             Parse(#[source] #parse_error),
         }
 
@@ -1057,6 +1077,10 @@ pub fn typed_timestamp(
             fn as_any(&self) -> &dyn std::any::Any {
                 self
             }
+
+            fn is_timestamp(&self) -> bool {
+                true
+            }
         }
     };
 
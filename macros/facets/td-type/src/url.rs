@@ -4,7 +4,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{ItemStruct, parse_macro_input};
+use syn::{Attribute, Field, ItemStruct, Type, parse_macro_input, parse_quote};
 
 pub fn url_param(_args: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as ItemStruct);
@@ -21,3 +21,82 @@ pub fn url_param(_args: TokenStream, item: TokenStream) -> TokenStream {
 
     expanded.into()
 }
+
+/// Same as [`url_param`], but optional (`Option<T>`) and repeated (`Vec<T>`) fields are handled
+/// as such in the query string: absent keys deserialize to `None` or an empty `Vec`, and repeated
+/// keys (`?tag=a&tag=b`) are collected into the `Vec` and documented as exploded form params.
+pub fn query_param(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(item as ItemStruct);
+
+    for field in input.fields.iter_mut() {
+        match QueryFieldKind::of(&field.ty) {
+            QueryFieldKind::Optional => default_field(field),
+            QueryFieldKind::Repeated => {
+                default_field(field);
+                if !has_attr(&field.attrs, "param") {
+                    field
+                        .attrs
+                        .push(parse_quote!(#[param(style = Form, explode)]));
+                }
+            }
+            QueryFieldKind::Single => {}
+        }
+    }
+
+    url_param(args, quote!(#input).into())
+}
+
+enum QueryFieldKind {
+    Single,
+    Optional,
+    Repeated,
+}
+
+impl QueryFieldKind {
+    fn of(ty: &Type) -> Self {
+        match ty {
+            Type::Path(type_path) => match type_path.path.segments.last() {
+                Some(segment) if segment.ident == "Option" => QueryFieldKind::Optional,
+                Some(segment) if segment.ident == "Vec" => QueryFieldKind::Repeated,
+                _ => QueryFieldKind::Single,
+            },
+            _ => QueryFieldKind::Single,
+        }
+    }
+}
+
+/// Adds serde and builder defaults to the field, unless it already declares its own.
+fn default_field(field: &mut Field) {
+    if !has_attr_with(&field.attrs, "serde", "default") {
+        field.attrs.push(parse_quote!(#[serde(default)]));
+    }
+    if !has_attr_with(&field.attrs, "builder", "default") {
+        field.attrs.push(parse_quote!(#[builder(default)]));
+    }
+}
+
+fn has_attr(attrs: &[Attribute], name: &str) -> bool {
+    attrs.iter().any(|attr| attr.path().is_ident(name))
+}
+
+fn has_attr_with(attrs: &[Attribute], name: &str, arg: &str) -> bool {
+    attrs.iter().any(|attr| {
+        let mut found = false;
+        if attr.path().is_ident(name) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident(arg) {
+                    found = true;
+                }
+                // Skip values (e.g. `default = "fn_name"` or `setter(into)`), only names matter.
+                if meta.input.peek(syn::Token![=]) {
+                    let _: syn::Expr = meta.value()?.parse()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let _content;
+                    syn::parenthesized!(_content in meta.input);
+                }
+                Ok(())
+            });
+        }
+        found
+    })
+}
@@ -8,6 +8,7 @@ use proc_macro::TokenStream;
 mod derive_field_accessor;
 mod derive_service_factory;
 mod service_factory;
+mod service_type;
 
 #[proc_macro_attribute]
 pub fn service_factory(args: TokenStream, item: TokenStream) -> TokenStream {
@@ -15,6 +16,14 @@ pub fn service_factory(args: TokenStream, item: TokenStream) -> TokenStream {
     service_factory::service_factory(args, item)
 }
 
+/// Annotates a service type with its `request` and `response` types, asserting at compile time
+/// that they are `Send + 'static`, so a mismatch fails with an error pointing at them rather
+/// than at the code using the service.
+#[proc_macro_attribute]
+pub fn service_type(args: TokenStream, item: TokenStream) -> TokenStream {
+    service_type::service_type(args, item)
+}
+
 #[proc_macro_derive(ServiceFactory)]
 pub fn derive_service_factory(input: TokenStream) -> TokenStream {
     derive_service_factory::derive_service_factory(input)
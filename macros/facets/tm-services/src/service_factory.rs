@@ -17,6 +17,7 @@ struct ProviderArgs {
     response: Type,
     connection: Option<Type>,
     context: Vec<Type>,
+    audit: Option<Type>,
 }
 
 impl Parse for ProviderArgs {
@@ -26,6 +27,7 @@ impl Parse for ProviderArgs {
         let mut response = None;
         let mut connection = None;
         let mut context = Vec::new();
+        let mut audit = None;
 
         while !input.is_empty() {
             let key: Ident = input.parse()?;
@@ -36,6 +38,7 @@ impl Parse for ProviderArgs {
                 "request" => request = Some(input.parse()?),
                 "response" => response = Some(input.parse()?),
                 "connection" => connection = Some(input.parse()?),
+                "audit" => audit = Some(input.parse()?),
                 "context" => {
                     let ctx: Type = input.parse()?;
                     context.push(ctx);
@@ -55,10 +58,24 @@ impl Parse for ProviderArgs {
                 .ok_or_else(|| syn::Error::new(input.span(), "Missing `response`"))?,
             connection,
             context,
+            audit,
         })
     }
 }
 
+/// Returns if the request is a create, update or delete request.
+fn is_mutating_request(request: &Type) -> bool {
+    match request {
+        Type::Path(type_path) => type_path.path.segments.last().is_some_and(|segment| {
+            matches!(
+                segment.ident.to_string().as_str(),
+                "CreateRequest" | "UpdateRequest" | "DeleteRequest"
+            )
+        }),
+        _ => false,
+    }
+}
+
 pub fn service_factory(attr: TokenStream, item: TokenStream) -> TokenStream {
     let args = parse_macro_input!(attr as ProviderArgs);
     let mut func = parse_macro_input!(item as ItemFn);
@@ -87,7 +104,7 @@ pub fn service_factory(attr: TokenStream, item: TokenStream) -> TokenStream {
     // Inject In's bounds into the where clause
     let where_clause = func.sig.generics.make_where_clause();
     where_clause.predicates.push(parse_quote! {
-        Req: Send + Sync + 'static
+        Req: ::td_tower::trace::TracedRequest
     });
     where_clause.predicates.push(parse_quote! {
         Res: Send + Sync + 'static
@@ -106,6 +123,7 @@ pub fn service_factory(attr: TokenStream, item: TokenStream) -> TokenStream {
     let name = &args.name;
     let req_ty = &args.request;
     let res_ty = &args.response;
+    let service = name.to_string();
 
     let (mut factory_args, mut factory_types) = (vec![], vec![]);
     let (db_input, db_arg, db_provider) = match &args.connection {
@@ -120,6 +138,35 @@ pub fn service_factory(attr: TokenStream, item: TokenStream) -> TokenStream {
             )
         }
     };
+    // Create, update and delete services, and services declaring the entity they change, record
+    // their outcome in the audit log: changes in their transaction, and failures once it is
+    // rolled back.
+    let (audit_failure_provider, audit_provider) = match (&args.audit, &args.connection) {
+        (Some(entity), None) => {
+            return syn::Error::new_spanned(entity, "Audited services must have a `connection`")
+                .to_compile_error()
+                .into();
+        }
+        (None, None) => (vec![], vec![]),
+        (None, Some(_)) if !is_mutating_request(req_ty) => (vec![], vec![]),
+        (entity, Some(_)) => {
+            let entity = match entity {
+                Some(entity) => quote! { ::td_tower::audit::entity::<#entity> },
+                None => quote! { ::td_tower::audit::no_entity },
+            };
+            func.sig
+                .generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote! { Req: ::td_tower::audit::AuditedRequest });
+            (
+                vec![
+                    quote! { ::td_tower::audit::AuditFailureProvider::<Req>::new(db.clone(), #service) },
+                ],
+                vec![quote! { ::td_tower::audit::AuditProvider::<Req>::new(#service, #entity) }],
+            )
+        }
+    };
     let (ctx_input, ctx_arg, ctx_ty, ctx_provider): (Vec<_>, Vec<_>, Vec<_>, Vec<_>) = args
         .context
         .iter()
@@ -159,13 +206,19 @@ pub fn service_factory(attr: TokenStream, item: TokenStream) -> TokenStream {
     func.block = parse_quote!({
         use ::td_tower::service_provider::IntoServiceProvider;
         tower::builder::ServiceBuilder::new()
-            .layer(td_tower::default_services::ServiceEntry::default())
+            .layer(td_tower::default_services::ServiceEntry::new(#service))
             #(
                 .layer(#ctx_provider)
             )*
+            #(
+                .layer(#audit_failure_provider)
+            )*
             #(
                 .layer(#db_provider)
             )*
+            #(
+                .layer(#audit_provider)
+            )*
             .layer(#original_block)
             .map_err(td_error::TdError::from)
             .service(td_tower::default_services::ServiceReturn)
@@ -174,6 +227,7 @@ pub fn service_factory(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     // Generate provider struct
     TokenStream::from(quote! {
+        #[::ta_services::factory::service_type(request = #req_ty, response = #res_ty)]
         pub struct #name {
             provider: ::td_tower::service_provider::ServiceProvider<#req_ty, #res_ty, td_error::TdError>,
             #[cfg(feature = "test_tower_metadata")]
@@ -0,0 +1,63 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, Item, Token, Type, parse_macro_input};
+
+struct ServiceTypeArgs {
+    request: Type,
+    response: Type,
+}
+
+impl Parse for ServiceTypeArgs {
+    fn parse(input: ParseStream) -> Result<Self, syn::Error> {
+        let mut request = None;
+        let mut response = None;
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            match key.to_string().as_str() {
+                "request" => request = Some(input.parse()?),
+                "response" => response = Some(input.parse()?),
+                _ => return Err(syn::Error::new(key.span(), "Unknown attribute key")),
+            }
+
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(ServiceTypeArgs {
+            request: request.ok_or_else(|| syn::Error::new(input.span(), "Missing `request`"))?,
+            response: response
+                .ok_or_else(|| syn::Error::new(input.span(), "Missing `response`"))?,
+        })
+    }
+}
+
+pub fn service_type(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as ServiceTypeArgs);
+    let item = parse_macro_input!(item as Item);
+
+    let req_ty = &args.request;
+    let res_ty = &args.response;
+
+    // Static assertions on request and response types, failing with a descriptive bound name.
+    TokenStream::from(quote! {
+        #item
+
+        const _: fn() = || {
+            fn service_request_must_be_send_static<T: Send + 'static>() {}
+            fn service_response_must_be_send_static<T: Send + 'static>() {}
+            service_request_must_be_send_static::<#req_ty>();
+            service_response_must_be_send_static::<#res_ty>();
+        };
+    })
+}
@@ -9,6 +9,8 @@
 //! is passed.
 //! Migrator should contain DB definitions, and fixtures can contain eitehr definitions or default data.
 //! The function must have a DbPool parameter.
+//! Fixtures can reference `${var}` variables, given with `vars(var = "value")`. Referencing a
+//! variable without value fails the test setup.
 //!
 //! By default, the schema is loaded from [`td_schema::schema()`].
 //!
@@ -17,6 +19,7 @@
 //! #[test(sqlx(fixture = "table"))]
 //! #[test(sqlx(migrator = td_schema::schema(), fixture = "table"))]
 //! #[test(sqlx(migrator = td_schema::schema(), fixture = "table"))]
+//! #[test(sqlx(fixture = "table", vars(collection = "c1")))]
 
 extern crate proc_macro;
 mod scoped_test;
@@ -8,6 +8,7 @@ use darling::FromMeta;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::quote;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use syn::{FnArg, ItemFn, LitStr, Signature, Type, TypePath, parse_macro_input};
 use td_shared::meta_parser::{OptionWrapper, SynMetaOrLit, some_or_none};
@@ -96,10 +97,12 @@ pub struct SqlxArguments {
     migrator: Option<SynMetaOrLit>,
     #[darling(multiple, rename = "fixture")]
     fixtures: Vec<LitStr>,
+    #[darling(default)]
+    vars: HashMap<Ident, LitStr>,
 }
 
 fn sqlx_test(func_sig: &Signature, args: Option<SqlxArguments>) -> InnerFnSetup {
-    let (migrator, fixtures) = match args {
+    let (migrator, fixtures, vars) = match args {
         Some(args) => {
             let migrator = some_or_none(args.migrator);
             let fixtures = args
@@ -116,9 +119,17 @@ fn sqlx_test(func_sig: &Signature, args: Option<SqlxArguments>) -> InnerFnSetup
                     quote! { include_str!(#f) }
                 })
                 .collect::<Vec<_>>();
-            (migrator, fixtures)
+            let vars = args
+                .vars
+                .iter()
+                .map(|(name, value)| {
+                    let name = name.to_string();
+                    quote! { (#name, #value) }
+                })
+                .collect::<Vec<_>>();
+            (migrator, fixtures, vars)
         }
-        None => (quote! { None }, vec![]),
+        None => (quote! { None }, vec![], vec![]),
     };
 
     // Check if the function has a DbPool parameter
@@ -126,7 +137,10 @@ fn sqlx_test(func_sig: &Signature, args: Option<SqlxArguments>) -> InnerFnSetup
     let (name, ty) = get_fn_args(func_sig, &Ident::new("DbPool", Span::call_site()));
 
     let setup = quote! {
-        td_test::sqlx::SqlxTestSetup::new(#migrator, vec![#(#fixtures),*]).setup().await
+        td_test::sqlx::SqlxTestSetup::new(#migrator, vec![#(#fixtures),*])
+            .with_vars(vec![#(#vars),*])
+            .setup()
+            .await
     };
 
     InnerFnSetup {
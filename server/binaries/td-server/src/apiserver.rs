@@ -9,7 +9,7 @@ use td_apiserver::config::{Config, DbSchema, Params};
 use td_apiserver::scheduler_server::SchedulerBuilder;
 use td_common::attach::attach;
 use td_common::server::FileWorkerMessageQueue;
-use td_common::signal::terminate;
+use td_common::signal::shutdown_signal;
 use td_common::status::ExitStatus;
 use td_common::{about, logging};
 use td_database::sql::DbError;
@@ -17,6 +17,7 @@ use td_objects::sql::DaoQueries;
 use td_process::launcher::cli::Cli;
 use td_process::launcher::hooks;
 use td_services::execution::services::runtime_info::RuntimeContext;
+use td_services::function_run::webhook::WebhookClient;
 use td_storage::Storage;
 use tracing::{Level, error, info};
 
@@ -222,12 +223,21 @@ fn main() {
                 }
             };
 
+            let webhook_client = match WebhookClient::try_default() {
+                Ok(client) => Arc::new(client),
+                Err(e) => {
+                    error!("Error creating webhook client: {}", e);
+                    return ExitStatus::GeneralError;
+                }
+            };
+
             let execution_server = SchedulerBuilder::new(
                 db.clone(),
                 queries.clone(),
                 storage.clone(),
                 worker_message_queue.clone(),
                 internal_addresses,
+                webhook_client,
             )
             .build()
             .await;
@@ -237,7 +247,7 @@ fn main() {
             tokio::spawn({
                 let shutdown_tx = shutdown_tx.clone();
                 async move {
-                    terminate().await;
+                    shutdown_signal().await;
                     info!("Terminate signal received in apiserver, shutting down...");
                     let _ = shutdown_tx.send(());
                 }
@@ -9,7 +9,8 @@
 //! and the actual response might differ. For example, if the response has a NOT_FOUND, but the
 //! status does not allow that, it has to be converted to another status such as BAD_REQUEST.
 //!
-//! Default errors -> BAD_REQUEST(400), UNAUTHORIZED(401) and INTERNAL_SERVER_ERROR(500)
+//! Default errors -> BAD_REQUEST(400), UNAUTHORIZED(401), CONFLICT(409),
+//! INTERNAL_SERVER_ERROR(500) and SERVICE_UNAVAILABLE(503)
 //! Not found with default -> NOT_FOUND(404) and default errors.
 
 #![allow(non_camel_case_types, clippy::upper_case_acronyms)]
@@ -42,8 +43,12 @@ pub enum ErrorStatus {
     UNAUTHORIZED(ErrorResponse),
     #[response(status = StatusCode::FORBIDDEN, description = "FORBIDDEN")]
     FORBIDDEN(ErrorResponse),
+    #[response(status = StatusCode::CONFLICT, description = "CONFLICT")]
+    CONFLICT(ErrorResponse),
     #[response(status = StatusCode::INTERNAL_SERVER_ERROR, description = "INTERNAL_SERVER_ERROR")]
     INTERNAL_SERVER_ERROR(ErrorResponse),
+    #[response(status = StatusCode::SERVICE_UNAVAILABLE, description = "SERVICE_UNAVAILABLE")]
+    SERVICE_UNAVAILABLE(ErrorResponse),
 }
 
 impl axum::response::IntoResponse for ErrorStatus {
@@ -53,7 +58,9 @@ impl axum::response::IntoResponse for ErrorStatus {
             ErrorStatus::BAD_REQUEST(e) => (StatusCode::BAD_REQUEST, e),
             ErrorStatus::UNAUTHORIZED(e) => (StatusCode::UNAUTHORIZED, e),
             ErrorStatus::FORBIDDEN(e) => (StatusCode::FORBIDDEN, e),
+            ErrorStatus::CONFLICT(e) => (StatusCode::CONFLICT, e),
             ErrorStatus::INTERNAL_SERVER_ERROR(e) => (StatusCode::INTERNAL_SERVER_ERROR, e),
+            ErrorStatus::SERVICE_UNAVAILABLE(e) => (StatusCode::SERVICE_UNAVAILABLE, e),
         };
         (status, axum::Json(serde_json::json!(error))).into_response()
     }
@@ -66,6 +73,8 @@ impl From<ErrorResponse> for ErrorStatus {
             StatusCode::BAD_REQUEST => ErrorStatus::BAD_REQUEST(error),
             StatusCode::UNAUTHORIZED => ErrorStatus::UNAUTHORIZED(error),
             StatusCode::FORBIDDEN => ErrorStatus::FORBIDDEN(error),
+            StatusCode::CONFLICT => ErrorStatus::CONFLICT(error),
+            StatusCode::SERVICE_UNAVAILABLE => ErrorStatus::SERVICE_UNAVAILABLE(error),
             _ => ErrorStatus::INTERNAL_SERVER_ERROR(error),
         }
     }
@@ -54,15 +54,30 @@ where
 {
     fn into_response(self) -> axum::response::Response {
         match self {
-            Self::OK(response) => (
-                http::StatusCode::OK,
-                axum::Json(serde_json::json!(response)),
-            )
-                .into_response(),
+            Self::OK(response) => {
+                let mut json = serde_json::json!(response);
+                if let Some(fields) = response.list_params.selected_fields() {
+                    select_fields(&mut json, &fields);
+                }
+                (http::StatusCode::OK, axum::Json(json)).into_response()
+            }
         }
     }
 }
 
+/// Keeps only the given fields in each item of the data of a serialized list response.
+fn select_fields(response: &mut serde_json::Value, fields: &[&str]) {
+    if let Some(items) = response
+        .pointer_mut("/data/data")
+        .and_then(serde_json::Value::as_array_mut)
+    {
+        items
+            .iter_mut()
+            .filter_map(serde_json::Value::as_object_mut)
+            .for_each(|item| item.retain(|field, _| fields.contains(&field.as_str())));
+    }
+}
+
 #[derive(utoipa::ToSchema, utoipa::IntoResponses, serde::Serialize)]
 pub enum GetStatus<T>
 where
@@ -158,3 +173,78 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use td_objects::dxo::crudl::{ListParamsBuilder, ListResponseBuilder};
+    use td_tower::ctx_service::{CtxMap, CtxResponse};
+
+    #[derive(Debug, Clone, Serialize, ToSchema)]
+    struct Item {
+        id: String,
+        name: String,
+        description: String,
+    }
+
+    async fn list_items(fields: Option<&str>) -> Vec<serde_json::Value> {
+        let list_params = ListParamsBuilder::default()
+            .fields(fields.map(str::to_string))
+            .build()
+            .unwrap();
+        let item = Item {
+            id: "ID".to_string(),
+            name: "NAME".to_string(),
+            description: "DESCRIPTION".to_string(),
+        };
+        let response = ListResponseBuilder::default()
+            .list_params(list_params)
+            .data(vec![item.clone(), item])
+            .previous_page(None, None, None)
+            .next_page(None, None, None)
+            .build()
+            .unwrap();
+        let response =
+            ListStatus::OK(CtxResponse::new(response, CtxMap::default())).into_response();
+        assert_eq!(response.status(), http::StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        json["data"]["data"].as_array().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn test_list_status_all_fields() {
+        let items = list_items(None).await;
+        assert_eq!(items.len(), 2);
+        for item in items {
+            let keys: Vec<_> = item.as_object().unwrap().keys().cloned().collect();
+            assert_eq!(keys.len(), 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_status_empty_fields() {
+        let items = list_items(Some("")).await;
+        assert_eq!(items.len(), 2);
+        for item in items {
+            assert_eq!(item.as_object().unwrap().len(), 3);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_status_selected_fields() {
+        let items = list_items(Some("id, name")).await;
+        assert_eq!(items.len(), 2);
+        for item in items {
+            let item = item.as_object().unwrap();
+            assert_eq!(item.len(), 2);
+            assert_eq!(item["id"], "ID");
+            assert_eq!(item["name"], "NAME");
+            assert!(!item.contains_key("description"));
+        }
+    }
+}
@@ -68,6 +68,20 @@ where
             .error_description(Some(error.to_string()))
             .build()
             .unwrap(),
+        ApiError::Unavailable => ErrorResponseBuilder::default()
+            .status(StatusCode::SERVICE_UNAVAILABLE)
+            .code(error.code())
+            .error(Some(String::from("unavailable")))
+            .error_description(Some(error.to_string()))
+            .build()
+            .unwrap(),
+        ApiError::Conflict => ErrorResponseBuilder::default()
+            .status(StatusCode::CONFLICT)
+            .code(error.code())
+            .error(Some(String::from("conflict")))
+            .error_description(Some(error.to_string()))
+            .build()
+            .unwrap(),
         ApiError::Unexpected => ErrorResponseBuilder::default()
             .status(StatusCode::IM_A_TEAPOT)
             .code(error.code())
@@ -4,11 +4,12 @@
 
 use crate::transaction::TransactionMapper;
 use getset::Getters;
+use petgraph::Direction;
 use petgraph::Graph;
 use petgraph::algo::toposort;
 use petgraph::dot::{Config, Dot};
 use petgraph::prelude::{DiGraph, EdgeRef, NodeIndex};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 use std::ops::Deref;
@@ -179,6 +180,58 @@ impl<V> ExecutionGraph<V> {
         Ok(())
     }
 
+    /// Returns the functions in execution order (topological order, trigger wise). When multiple
+    /// functions are ready at the same time, they are ordered by collection and function name, so
+    /// planning the same graph always yields the same order.
+    pub fn execution_order(&self) -> Result<Vec<&FunctionNode>, TdError> {
+        let partial = PartialGraph::trigger_graph(self)?;
+        let graph = partial.inner();
+
+        let sort_key = |index: NodeIndex| {
+            let function = graph[index];
+            (
+                function.collection.to_string(),
+                function.name.to_string(),
+                index,
+            )
+        };
+
+        let mut in_degree: HashMap<_, _> = graph
+            .node_indices()
+            .map(|index| {
+                let degree = graph.edges_directed(index, Direction::Incoming).count();
+                (index, degree)
+            })
+            .collect();
+        let mut ready: BTreeSet<_> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(index, _)| sort_key(*index))
+            .collect();
+
+        let mut order = Vec::with_capacity(graph.node_count());
+        while let Some((_, _, index)) = ready.pop_first() {
+            order.push(graph[index]);
+            for edge in graph.edges(index) {
+                let degree = in_degree.get_mut(&edge.target()).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.insert(sort_key(edge.target()));
+                }
+            }
+        }
+
+        if order.len() < graph.node_count() {
+            let (index, _) = in_degree
+                .iter()
+                .filter(|(_, degree)| **degree > 0)
+                .min_by_key(|(index, _)| sort_key(**index))
+                .unwrap();
+            Err(GraphError::Cyclic(graph[*index].name.clone()))?
+        }
+        Ok(order)
+    }
+
     /// Validates the graph to ensure it is a Direct Acyclic Graph (DAG) transaction wise.
     pub fn validate_transaction(
         &self,
@@ -327,9 +380,13 @@ mod tests {
     use crate::test_utils::graph::test_graph;
     use crate::test_utils::transaction::TestTransactionBy;
     use std::collections::HashSet;
+    use td_objects::execution::graph::TableNode;
     use td_objects::test_utils::graph::{
         FUNCTION_NAMES, TABLE_NAMES, dependency, function_node, table, table_node, trigger,
     };
+    use td_objects::types::basic::{
+        CollectionId, CollectionName, FunctionVersionId, TableId, TableName, TableVersionId,
+    };
 
     #[tokio::test]
     async fn test_graph_builder_build() {
@@ -601,4 +658,86 @@ mod tests {
                 .is_ok()
         );
     }
+
+    #[test]
+    fn test_execution_order_stable() {
+        let function = |name: &str| {
+            GraphNode::Function(
+                FunctionNode::builder()
+                    .collection_id(CollectionId::default())
+                    .collection(CollectionName::try_from("test").unwrap())
+                    .function_version_id(FunctionVersionId::default())
+                    .name(FunctionName::try_from(name).unwrap())
+                    .build()
+                    .unwrap(),
+            )
+        };
+        let table = |name: &str| {
+            GraphNode::Table(
+                TableNode::builder()
+                    .collection_id(CollectionId::default())
+                    .collection(CollectionName::try_from("test").unwrap())
+                    .function_version_id(FunctionVersionId::default())
+                    .table_id(TableId::default())
+                    .table_version_id(TableVersionId::default())
+                    .name(TableName::try_from(name).unwrap())
+                    .system(false)
+                    .build()
+                    .unwrap(),
+            )
+        };
+        let output = || {
+            GraphEdge::output(
+                Versions::None,
+                GraphOutput::builder().output_pos(None).build().unwrap(),
+            )
+        };
+        let trigger = || GraphEdge::trigger(Versions::None);
+
+        // Diamond: a -> (b, c) -> d. Nodes are added in reverse order on purpose.
+        let mut graph = DiGraph::new();
+        let d = graph.add_node(function("d"));
+        let c = graph.add_node(function("c"));
+        let b = graph.add_node(function("b"));
+        let a = graph.add_node(function("a"));
+        let table_c = graph.add_node(table("table_c"));
+        let table_b = graph.add_node(table("table_b"));
+        let table_a = graph.add_node(table("table_a"));
+        graph.add_edge(a, table_a, output());
+        graph.add_edge(table_a, c, trigger());
+        graph.add_edge(table_a, b, trigger());
+        graph.add_edge(c, table_c, output());
+        graph.add_edge(b, table_b, output());
+        graph.add_edge(table_c, d, trigger());
+        graph.add_edge(table_b, d, trigger());
+        let graph = ExecutionGraph::new(graph, a);
+
+        for _ in 0..10 {
+            let order: Vec<_> = graph
+                .execution_order()
+                .unwrap()
+                .iter()
+                .map(|f| f.name.to_string())
+                .collect();
+            assert_eq!(order, vec!["a", "b", "c", "d"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execution_order_cyclic() {
+        let output_tables = vec![
+            table(&FUNCTION_NAMES[0], &TABLE_NAMES[0]).await,
+            table(&FUNCTION_NAMES[1], &TABLE_NAMES[1]).await,
+        ];
+        let input_tables = vec![];
+        let trigger_graph = [
+            trigger(&TABLE_NAMES[0], &FUNCTION_NAMES[1]).await,
+            trigger(&TABLE_NAMES[1], &FUNCTION_NAMES[0]).await,
+        ];
+        let trigger_graph = trigger_graph.iter().map(|t| (&t.0, &t.1, &t.2)).collect();
+
+        let builder = GraphBuilder::new(&output_tables, &trigger_graph, &input_tables);
+        let graph = builder.build(function_node(&FUNCTION_NAMES[0])).unwrap();
+        assert!(graph.execution_order().is_err());
+    }
 }
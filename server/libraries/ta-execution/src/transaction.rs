@@ -70,6 +70,20 @@ impl<T: TransactionMapper> TransactionMap<T> {
         Ok(())
     }
 
+    /// Inserts an already existing transaction value, keyed by its transaction key.
+    /// Returns false, leaving the map untouched, if the key is already present.
+    pub fn insert(&mut self, value: TransactionValue) -> bool {
+        if self.map.contains_key(&value.transaction_key) {
+            return false;
+        }
+        self.map.insert(value.transaction_key.clone(), value);
+        true
+    }
+
+    pub fn contains_key(&self, key: &TransactionKey) -> bool {
+        self.map.contains_key(key)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &TransactionKey> {
         self.map.keys()
     }
@@ -128,6 +142,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_insert() -> Result<(), TdError> {
+        let transaction_by = TestTransactionBy::default();
+        let mut transaction_map = TransactionMap::empty(transaction_by);
+
+        let key = TransactionKey::try_from(FUNCTION_NAMES[0].to_string())?;
+        let value = TransactionValue {
+            id: TransactionId::default(),
+            collection_id: Default::default(),
+            execution_id: Default::default(),
+            transaction_by: TransactionByStr::try_from("test")?,
+            transaction_key: key.clone(),
+        };
+        assert!(!transaction_map.contains_key(&key));
+        assert!(transaction_map.insert(value.clone()));
+        assert!(transaction_map.contains_key(&key));
+
+        let other = TransactionValue {
+            id: TransactionId::default(),
+            ..value.clone()
+        };
+        assert!(!transaction_map.insert(other));
+        assert_eq!(transaction_map.get(&key)?.id, value.id);
+        Ok(())
+    }
+
     #[test]
     fn test_mapper() {
         let transaction_by = TestTransactionBy::default();
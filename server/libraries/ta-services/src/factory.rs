@@ -2,7 +2,7 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-pub use tm_services::{FieldAccessors, ServiceFactory, service_factory};
+pub use tm_services::{FieldAccessors, ServiceFactory, service_factory, service_type};
 
 use std::sync::Arc;
 
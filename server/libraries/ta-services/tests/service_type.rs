@@ -0,0 +1,9 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[test]
+fn test_service_type_ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/service_type_*.rs");
+}
@@ -0,0 +1,11 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use std::sync::MutexGuard;
+use ta_services::factory::service_type;
+
+#[service_type(request = MutexGuard<'static, ()>, response = ())]
+pub struct NotSendService;
+
+fn main() {}
@@ -32,9 +32,17 @@
 //! ```
 
 use crate::config::Config;
+use crate::layers::access_log::AccessLogService;
 use crate::layers::authorization::authorization_layer;
 use crate::layers::compression::CompressionService;
+use crate::layers::concurrency_limit::ConcurrencyLimitService;
+use crate::layers::conditional::ConditionalService;
 use crate::layers::cors::CorsService;
+use crate::layers::deadline::deadline_layer;
+use crate::layers::idempotency::idempotency_layer;
+use crate::layers::read_consistency::{
+    MAX_CONSISTENT_READS, ReadConsistencyState, read_consistency_layer,
+};
 use crate::layers::tracing::TraceService;
 use crate::layers::uri_filter::LoopbackIpFilterService;
 use crate::router::auth::{SecureAuthRouter, UnsecureAuthRouter};
@@ -65,6 +73,7 @@ use td_authz::AuthzContext;
 use td_database::sql::DbPool;
 use td_error::{ApiError, api_error};
 use td_objects::sql::DaoQueries;
+use td_objects::sql::list::Cursor;
 use td_objects::types::addresses::{
     ApiServerAddresses, InternalServerAddresses, NonEmptyAddresses,
 };
@@ -76,6 +85,12 @@ use te_apiserver::{AuthenticatedExtendedRouter, UnauthenticatedExtendedRouter};
 use te_services::{ExtendedContext, ExtendedServices};
 use tower_http::timeout::TimeoutLayer;
 
+/// Retries binding the server addresses still in use, i.e. by the previous instance still shutting
+/// down on a quick restart, and delay before the first retry (doubled on each retry, capped at 5
+/// seconds). It waits for them for under 30 seconds before failing.
+const BIND_RETRY_ATTEMPTS: u32 = 10;
+const BIND_RETRY_DELAY: Duration = Duration::from_millis(100);
+
 pub struct ApiServerInstance {
     internal: Box<dyn Server>,
     api_v1: Box<dyn Server>,
@@ -134,6 +149,10 @@ impl ApiServerInstanceBuilder {
         storage: Arc<Storage>,
         runtime_context: Arc<RuntimeContext>,
     ) -> Self {
+        // pagination cursors are signed with a key derived from the JWT secret
+        if let Some(secret) = &config.jwt.secret {
+            Cursor::init_secret(secret.as_bytes());
+        }
         let context = Context {
             db: db.clone(),
             queries: queries.clone(),
@@ -151,6 +170,7 @@ impl ApiServerInstanceBuilder {
             storage: storage.clone(),
             runtime_context: runtime_context.clone(),
             transaction_by: Arc::new(config.transaction_by.clone()),
+            storage_quota: Arc::new(config.storage_quota.clone()),
         };
         let services = Services::build(&context);
         let extended_context = ExtendedContext::build(&context, &config.extended_config);
@@ -200,6 +220,16 @@ impl ApiServerInstanceBuilder {
                         .merge(AuthenticatedExtendedRouter::router(
                             self.extended_services.clone(),
                         ))
+                        // read-your-writes layer, for requests with the consistent param
+                        .layer(from_fn_with_state(
+                            ReadConsistencyState::new(
+                                self.context.db.clone(),
+                                MAX_CONSISTENT_READS,
+                            ),
+                            read_consistency_layer,
+                        ))
+                        // idempotency layer, it requires the request context
+                        .layer(from_fn_with_state(self.context.clone(), idempotency_layer))
                         // authorization layer
                         .layer(from_fn_with_state(
                             self.context.clone(),
@@ -251,17 +281,44 @@ impl ApiServerInstanceBuilder {
                 ));
             }
 
+            // Add metrics layer if the feature is enabled, the endpoint is served by the
+            // internal server.
+            #[cfg(feature = "metrics")]
+            let router = router.layer(crate::layers::metrics::MetricsService::layer());
+
             // Default layers
             let router = router
+                .layer(ConditionalService::layer())
+                .layer(from_fn_with_state(
+                    Duration::from_secs(self.config.request_timeout as u64),
+                    deadline_layer,
+                ))
                 .layer(TimeoutLayer::new(Duration::from_secs(
                     self.config.request_timeout as u64,
                 )))
-                .layer(CorsService::layer())
+                .layer(CorsService::layer());
+            // Propagate the trace context if the feature is enabled, inside the request span.
+            #[cfg(feature = "otel")]
+            let router = router.layer(crate::layers::trace_context::TraceContextService::layer());
+            let router = router
                 .layer(TraceService::layer())
+                .layer(AccessLogService::layer())
                 .layer(CompressionService::layer());
 
+            // Load shedding, only if configured
+            let router = match self.config.max_concurrent_requests {
+                Some(max_concurrent_requests) => router.layer(ConcurrencyLimitService::layer(
+                    max_concurrent_requests,
+                    self.config
+                        .concurrency_queue_timeout
+                        .map(Duration::from_millis),
+                )),
+                None => router,
+            };
+
             ServerBuilder::new(self.config.addresses.clone(), router)
                 .tls(&self.config.ssl_folder)
+                .bind_retry(BIND_RETRY_ATTEMPTS, BIND_RETRY_DELAY)
                 .build()
                 .await
         }?;
@@ -281,8 +338,17 @@ impl ApiServerInstanceBuilder {
                 // Everything going to /api and is not found, is a not found. Only non /api calls get through.
                 .fallback(api_not_found_handler);
             // Nest the router in the base API URL.
-            let router = utoipa_axum::router::OpenApiRouter::default()
-                .nest(td_objects::rest_urls::BASE_API_URL, router);
+            let router: axum::Router = utoipa_axum::router::OpenApiRouter::default()
+                .nest(td_objects::rest_urls::BASE_API_URL, router)
+                .into();
+
+            // Add metrics endpoint if the feature is enabled, only accessible from loopback IPs
+            // as the internal API, not exposed with the API.
+            #[cfg(feature = "metrics")]
+            let router = router.merge(
+                crate::metrics::MetricsRouter::router(self.context.db.clone())?
+                    .layer(from_fn(LoopbackIpFilterService::layer)),
+            );
 
             // Default layers
             let router = router
@@ -290,9 +356,11 @@ impl ApiServerInstanceBuilder {
                     self.config.request_timeout as u64,
                 )))
                 .layer(CorsService::layer())
-                .layer(TraceService::layer());
+                .layer(TraceService::layer())
+                .layer(AccessLogService::layer());
 
-            ServerBuilder::new(self.config.internal_addresses.clone(), router.into())
+            ServerBuilder::new(self.config.internal_addresses.clone(), router)
+                .bind_retry(BIND_RETRY_ATTEMPTS, BIND_RETRY_DELAY)
                 .build()
                 .await
         }?;
@@ -18,6 +18,7 @@ use td_objects::types::addresses::{
 };
 use td_security::config::PasswordHashingConfig;
 use td_services::auth::jwt::JwtConfig;
+use td_storage::quota::StorageQuota;
 use td_storage::{MountDef, StorageError};
 use te_apiserver::config::{ExtendedConfig, ExtendedParams};
 use te_execution::transaction::TransactionBy;
@@ -31,12 +32,18 @@ pub struct Config {
     pub password: PasswordHashingConfig,
     pub jwt: JwtConfig,
     pub request_timeout: i64, // in seconds
+    #[serde(default)]
+    pub max_concurrent_requests: Option<usize>,
+    #[serde(default)]
+    pub concurrency_queue_timeout: Option<u64>, // in milliseconds
     pub ssl_folder: PathBuf,
     pub database: SqliteConfig,
     #[serde(default)]
     pub storage: Option<StorageConfig>,
     #[serde(default)]
     pub transaction_by: TransactionBy,
+    #[serde(default)]
+    pub storage_quota: StorageQuota,
     #[serde(flatten)]
     pub extended_config: ExtendedConfig,
 }
@@ -83,10 +90,13 @@ impl Default for Config {
             password: PasswordHashingConfig::default(),
             jwt: JwtConfig::default(),
             request_timeout: 60,
+            max_concurrent_requests: None,
+            concurrency_queue_timeout: None,
             ssl_folder: PathBuf::default(),
             database: SqliteConfig::default(),
             storage: Some(StorageConfig::default()),
             transaction_by: TransactionBy::default(),
+            storage_quota: StorageQuota::default(),
             extended_config: ExtendedConfig::default(),
         }
     }
@@ -216,6 +226,8 @@ impl Params {
                 JwtConfig::new(secret, expiration)
             },
             request_timeout: self.request_timeout.unwrap_or(config.request_timeout),
+            max_concurrent_requests: config.max_concurrent_requests,
+            concurrency_queue_timeout: config.concurrency_queue_timeout,
             ssl_folder: self
                 .ssl_folder
                 .clone()
@@ -234,6 +246,7 @@ impl Params {
                 .transaction_by
                 .clone()
                 .unwrap_or_else(|| config.transaction_by.clone()),
+            storage_quota: config.storage_quota.clone(),
             extended_config: self
                 .extended_params
                 .resolve(config.extended_config.clone())?,
@@ -0,0 +1,297 @@
+//
+//  Copyright 2025 Tabs Data Inc.
+//
+
+use http::{HeaderName, HeaderValue, Request, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use td_common::id::id;
+use td_objects::rest_urls::{BASE_URL_V1, SERVER_STATUS};
+use tower::{Layer, Service};
+use tracing::{Level, event};
+
+/// Header holding the request id, set by the [`AccessLogLayer`] if not present or not valid.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Id of a request generated by the server, in the request extensions. Unlike the
+/// [`REQUEST_ID_HEADER`], clients cannot set it, so it is the one recorded in audit records.
+#[derive(Debug, Clone)]
+pub struct ServerRequestId(pub String);
+
+/// Maximum length of a request id given by a client.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+const ACCESS_LOG_TARGET: &str = "access_log";
+
+#[derive(Default)]
+pub struct AccessLogService;
+
+impl AccessLogService {
+    /// Creates an [`AccessLogLayer`] logging at `INFO` level, excluding the server status
+    /// (health check) endpoint.
+    pub fn layer() -> AccessLogLayer {
+        AccessLogLayer::new(Level::INFO).exclude(format!("{BASE_URL_V1}{SERVER_STATUS}"))
+    }
+}
+
+/// Layer emitting one access log event per request, with method, path, status, latency and
+/// request id.
+#[derive(Debug, Clone)]
+pub struct AccessLogLayer {
+    level: Level,
+    excluded_paths: Arc<Vec<String>>,
+}
+
+impl AccessLogLayer {
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            excluded_paths: Arc::new(vec![]),
+        }
+    }
+
+    /// Requests to the given path are not logged.
+    pub fn exclude(mut self, path: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.excluded_paths).push(path.into());
+        self
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLog<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLog {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessLog<S> {
+    inner: S,
+    layer: AccessLogLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for AccessLog<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // The request id given by the client ends up in logs, so it is replaced by the server
+        // generated one unless it is valid.
+        let server_request_id = id().to_string();
+        let request_id = match request
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|request_id| request_id.to_str().ok())
+            .filter(|request_id| is_valid_request_id(request_id))
+        {
+            Some(request_id) => request_id.to_string(),
+            None => {
+                if let Ok(value) = HeaderValue::from_str(&server_request_id) {
+                    request.headers_mut().insert(REQUEST_ID_HEADER, value);
+                }
+                server_request_id.clone()
+            }
+        };
+        request
+            .extensions_mut()
+            .insert(ServerRequestId(server_request_id.clone()));
+
+        let path = request.uri().path().to_string();
+        let excluded = self.layer.excluded_paths.contains(&path);
+        let method = request.method().clone();
+        let level = self.layer.level;
+        let start = Instant::now();
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let response = future.await?;
+            if !excluded {
+                let status = response.status().as_u16();
+                let latency_us = start.elapsed().as_micros() as u64;
+                macro_rules! access_log {
+                    ($level:expr) => {
+                        event!(
+                            target: ACCESS_LOG_TARGET,
+                            $level,
+                            request_id = %request_id,
+                            server_request_id = %server_request_id,
+                            method = %method,
+                            path = %path,
+                            status,
+                            latency_us,
+                            "access"
+                        )
+                    };
+                }
+                match level {
+                    Level::ERROR => access_log!(Level::ERROR),
+                    Level::WARN => access_log!(Level::WARN),
+                    Level::INFO => access_log!(Level::INFO),
+                    Level::DEBUG => access_log!(Level::DEBUG),
+                    _ => access_log!(Level::TRACE),
+                }
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Request ids given by clients must be short and use only alphanumeric characters, `-`, `_`
+/// and `.`.
+fn is_valid_request_id(request_id: &str) -> bool {
+    !request_id.is_empty()
+        && request_id.len() <= MAX_REQUEST_ID_LEN
+        && request_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::StatusCode;
+    use std::convert::Infallible;
+    use std::io::{self, Write};
+    use std::sync::Mutex;
+    use tower::{ServiceBuilder, ServiceExt, service_fn};
+    use tracing::subscriber::set_default;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::{fmt, registry};
+
+    struct WriterGuard {
+        buffer: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Write for WriterGuard {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let mut lock = self.buffer.lock().unwrap();
+            lock.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn logs_for(paths: &[&str]) -> String {
+        let logs = Arc::new(Mutex::new(Vec::new()));
+        let logs_clone = logs.clone();
+        let layer = fmt::layer()
+            .with_writer(move || WriterGuard {
+                buffer: logs_clone.clone(),
+            })
+            .with_ansi(false)
+            .with_level(true);
+        let _guard = set_default(registry().with(layer));
+
+        let service = ServiceBuilder::new()
+            .layer(AccessLogService::layer())
+            .service(service_fn(|_: Request<()>| async {
+                Ok::<_, Infallible>(
+                    Response::builder()
+                        .status(StatusCode::NOT_FOUND)
+                        .body(())
+                        .unwrap(),
+                )
+            }));
+
+        for path in paths {
+            let request = Request::builder()
+                .method("GET")
+                .uri(*path)
+                .header(REQUEST_ID_HEADER, "test-request-id")
+                .body(())
+                .unwrap();
+            service.clone().oneshot(request).await.unwrap();
+        }
+
+        let logs = logs.lock().unwrap().to_vec();
+        String::from_utf8_lossy(&logs).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_access_log() {
+        let logs = logs_for(&["/api/v1/collections"]).await;
+        assert!(logs.contains("INFO"));
+        assert!(logs.contains("access"));
+        assert!(logs.contains("request_id=test-request-id"));
+        assert!(logs.contains("method=GET"));
+        assert!(logs.contains("path=/api/v1/collections"));
+        assert!(logs.contains("status=404"));
+        assert!(logs.contains("latency_us="));
+    }
+
+    #[tokio::test]
+    async fn test_access_log_replaces_invalid_request_id() {
+        let service = ServiceBuilder::new()
+            .layer(AccessLogService::layer())
+            .service(service_fn(|request: Request<()>| async move {
+                let request_id = request.headers().get(REQUEST_ID_HEADER).cloned();
+                Ok::<_, Infallible>(Response::new(request_id))
+            }));
+
+        let too_long = "a".repeat(MAX_REQUEST_ID_LEN + 1);
+        for invalid in ["", "id with spaces", "id\"quoted\"", too_long.as_str()] {
+            let request = Request::builder()
+                .uri("/api/v1/collections")
+                .header(REQUEST_ID_HEADER, invalid)
+                .body(())
+                .unwrap();
+            let response = service.clone().oneshot(request).await.unwrap();
+            let request_id = response.into_body().unwrap();
+            let request_id = request_id.to_str().unwrap();
+            assert_ne!(request_id, invalid);
+            assert!(is_valid_request_id(request_id));
+        }
+
+        let request = Request::builder()
+            .uri("/api/v1/collections")
+            .header(REQUEST_ID_HEADER, "client-request.id_1")
+            .body(())
+            .unwrap();
+        let response = service.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.into_body().unwrap(), "client-request.id_1");
+    }
+
+    #[tokio::test]
+    async fn test_access_log_server_request_id() {
+        let service = ServiceBuilder::new()
+            .layer(AccessLogService::layer())
+            .service(service_fn(|request: Request<()>| async move {
+                let server_request_id = request.extensions().get::<ServerRequestId>().cloned();
+                Ok::<_, Infallible>(Response::new(server_request_id))
+            }));
+
+        let request = Request::builder()
+            .uri("/api/v1/collections")
+            .header(REQUEST_ID_HEADER, "client-request.id_1")
+            .body(())
+            .unwrap();
+        let response = service.clone().oneshot(request).await.unwrap();
+        let ServerRequestId(server_request_id) = response.into_body().unwrap();
+        assert_ne!(server_request_id, "client-request.id_1");
+        assert!(is_valid_request_id(&server_request_id));
+    }
+
+    #[tokio::test]
+    async fn test_access_log_excludes_health_check() {
+        let logs = logs_for(&[&format!("{BASE_URL_V1}{SERVER_STATUS}")]).await;
+        assert!(!logs.contains("access"));
+    }
+}
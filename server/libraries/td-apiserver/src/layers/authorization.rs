@@ -2,6 +2,7 @@
 // Copyright 2025. Tabs Data Inc.
 //
 
+use crate::layers::access_log::ServerRequestId;
 use axum::extract::{Request, State};
 use axum::http;
 use axum::middleware::Next;
@@ -11,15 +12,32 @@ use std::sync::Arc;
 use ta_apiserver::status::error_status::ErrorStatus;
 use td_database::sql::DbPool;
 use td_error::TdError;
+use td_objects::dxo::api_key::ApiKeyDBWithNames;
 use td_objects::dxo::crudl::RequestContext;
-use td_objects::types::basic::{AccessToken, AccessTokenId};
+use td_objects::sql::DaoQueries;
+use td_objects::types::basic::{AccessToken, AccessTokenId, ApiKeyToken, RequestId};
 use td_services::auth::AuthError;
+use td_services::auth::api_key::{API_KEY_SCHEME, authenticate_api_key};
 use td_services::auth::jwt::{JwtConfig, decode_token};
 use td_services::auth::session::{Session, SessionError, SessionProvider, Sessions};
 use tracing::{Instrument, Level, Span, error, span};
 
+/// Sets the request ID, generated by the access log layer, in the request context. The request ID
+/// header is not used, as clients can set it.
+fn with_request_id(request_context: RequestContext, request: &Request) -> RequestContext {
+    let request_id = request
+        .extensions()
+        .get::<ServerRequestId>()
+        .and_then(|ServerRequestId(request_id)| RequestId::try_from(request_id.as_str()).ok());
+    match request_id {
+        Some(request_id) => request_context.with_request_id(request_id),
+        None => request_context,
+    }
+}
+
 pub async fn authorization_layer(
     State(db): State<DbPool>,
+    State(queries): State<Arc<DaoQueries>>,
     State(jwt_config): State<Arc<JwtConfig>>,
     State(sessions): State<Arc<Sessions>>,
     request: Request,
@@ -43,62 +61,88 @@ pub async fn authorization_layer(
     })?;
     //        .log_warn_err(|_| format!("Invalid authorization header: {:?}", auth_header))?;
     // Check if the Authorization header is a Bearer token
-    let auth_header: Vec<_> = auth_header.split_whitespace().collect();
+    let auth_header: Vec<_> = auth_header
+        .split_whitespace()
+        .map(ToString::to_string)
+        .collect();
     if auth_header.len() != 2 {
         error!(
             "Invalid authorization header, not 2 words: {:?}",
             auth_header
         );
         Err(TdError::from(AuthError::InvalidAuthorizationHeaderValue(
-            "It should be 2 words: Bearer <ACCESS_TOKEN>".to_string(),
+            "It should be 2 words: Bearer <ACCESS_TOKEN> or ApiKey <API_KEY>".to_string(),
         )))?;
         //        .log_err_warn(|e| e.to_string())?;
     }
-    if auth_header[0] != "Bearer" {
-        error!(
-            "Invalid authorization header, not a Bearer token: {:?}",
-            auth_header
-        );
-        Err(TdError::from(AuthError::InvalidAuthorizationHeaderValue(
-            "Not a Bearer token".to_string(),
-        )))?;
-        //        .log_err_warn(|e| e.to_string())?;
-    }
-    let access_token = AccessToken::try_from(auth_header[1])?;
-
-    // Check if the token is valid
-    let token = decode_token(&jwt_config, access_token.as_str())
-        //        .log_err_warn(|e| e.to_string())
-        .map_err(|e| {
-            error!("Could not decode token: {}", e);
-            TdError::from(AuthError::AuthenticationFailed)
-        })?;
-    let access_token_id: AccessTokenId = token.jti().into();
-
-    // Get user_id/role_id from session
+
     let mut conn = db
         .acquire()
         .await
         .map_err(|e| TdError::from(SessionError::CouldNotGetDbConn(e)))?;
-    let session = sessions
-        .get_session(&mut conn, &access_token_id)
-        .await
-        //        .log_err_warn(ToString::to_string)
-        .map_err(|e| {
-            error!("Could not get session: {}", e);
-            TdError::from(AuthError::AuthenticationFailed)
-        })?;
-
-    // Insert the context into the request extensions
-    let request_context =
-        RequestContext::with(&session.access_token_id, &session.user_id, &session.role_id);
     let mut request = request;
-    request.extensions_mut().insert(request_context);
-    request.extensions_mut().insert(access_token);
+    let log_span = match auth_header[0].as_str() {
+        "Bearer" => {
+            let access_token = AccessToken::try_from(auth_header[1].as_str())?;
+
+            // Check if the token is valid
+            let token = decode_token(&jwt_config, access_token.as_str())
+                //        .log_err_warn(|e| e.to_string())
+                .map_err(|e| {
+                    error!("Could not decode token: {}", e);
+                    TdError::from(AuthError::AuthenticationFailed)
+                })?;
+            let access_token_id: AccessTokenId = token.jti().into();
+
+            // Get user_id/role_id from session
+            let session = sessions
+                .get_session(&mut conn, &access_token_id)
+                .await
+                //        .log_err_warn(ToString::to_string)
+                .map_err(|e| {
+                    error!("Could not get session: {}", e);
+                    TdError::from(AuthError::AuthenticationFailed)
+                })?;
+
+            // Insert the context into the request extensions
+            let request_context =
+                RequestContext::with(&session.access_token_id, &session.user_id, &session.role_id);
+            let request_context = with_request_id(request_context, &request);
+            request.extensions_mut().insert(request_context);
+            request.extensions_mut().insert(access_token);
+            log_span(&session)
+        }
+        API_KEY_SCHEME => {
+            let api_key = ApiKeyToken::try_from(auth_header[1].as_str())?;
+
+            // Get user_id/role_id from the API key, the key id is used as access token id
+            let api_key = authenticate_api_key(&queries, &mut conn, &api_key).await?;
+            let access_token_id = AccessTokenId::try_from(api_key.id)?;
+
+            // Insert the context into the request extensions, scoped keys are restricted to
+            // their scope
+            let request_context =
+                RequestContext::with(access_token_id, &api_key.user_id, &api_key.role_id)
+                    .with_scoped(api_key.scoped.clone());
+            let request_context = with_request_id(request_context, &request);
+            request.extensions_mut().insert(request_context);
+            api_key_log_span(&api_key)
+        }
+        _ => {
+            error!(
+                "Invalid authorization header, not a Bearer token or an API key: {:?}",
+                auth_header
+            );
+            Err(TdError::from(AuthError::InvalidAuthorizationHeaderValue(
+                "Not a Bearer token or an API key".to_string(),
+            )))?
+            //        .log_err_warn(|e| e.to_string())?;
+        }
+    };
+    drop(conn);
 
     // Let the request continue
     // TODO this could be a separate layer
-    let log_span = log_span(&session);
     let future = next.run(request).instrument(log_span);
     Ok(future.await)
 }
@@ -113,6 +157,16 @@ fn log_span(session: &Session) -> Span {
     )
 }
 
+fn api_key_log_span(api_key: &ApiKeyDBWithNames) -> Span {
+    span!(
+        Level::INFO,
+        "authorized",
+        user_name = %api_key.user_name,
+        role_name = %api_key.role_name,
+        api_key_prefix = %api_key.prefix,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
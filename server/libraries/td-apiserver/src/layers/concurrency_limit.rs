@@ -0,0 +1,311 @@
+//
+//  Copyright 2025 Tabs Data Inc.
+//
+
+use http::{Request, Response, StatusCode};
+use http_body::{Body, Frame, SizeHint};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use td_objects::rest_urls::{BASE_URL_V1, SERVER_STATUS};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::{Layer, Service};
+use tracing::warn;
+
+#[derive(Default)]
+pub struct ConcurrencyLimitService;
+
+impl ConcurrencyLimitService {
+    /// Creates a [`ConcurrencyLimitLayer`] allowing `max_in_flight` requests, waiting up to
+    /// `queue_timeout` for a free slot, if given. The server status (health check) endpoint
+    /// bypasses the limit.
+    pub fn layer(max_in_flight: usize, queue_timeout: Option<Duration>) -> ConcurrencyLimitLayer {
+        ConcurrencyLimitLayer::new(max_in_flight)
+            .queue_timeout(queue_timeout)
+            .bypass(format!("{BASE_URL_V1}{SERVER_STATUS}"))
+    }
+}
+
+/// Layer limiting the number of in-flight requests, shedding the excess with a
+/// `503 Service Unavailable` response.
+///
+/// A request is in flight until its response body has been sent, so requests streaming their
+/// response hold their slot while streaming. Request bodies are counted as long as they are read
+/// before the response is returned, as the handlers do.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimitLayer {
+    semaphore: Arc<Semaphore>,
+    queue_timeout: Option<Duration>,
+    bypass_paths: Arc<Vec<String>>,
+}
+
+impl ConcurrencyLimitLayer {
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_in_flight)),
+            queue_timeout: None,
+            bypass_paths: Arc::new(vec![]),
+        }
+    }
+
+    /// Time requests wait for a free slot before being shed. If `None`, they are shed right away.
+    pub fn queue_timeout(mut self, queue_timeout: Option<Duration>) -> Self {
+        self.queue_timeout = queue_timeout;
+        self
+    }
+
+    /// Requests to the given path are not limited.
+    pub fn bypass(mut self, path: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.bypass_paths).push(path.into());
+        self
+    }
+
+    async fn acquire(
+        semaphore: Arc<Semaphore>,
+        queue_timeout: Option<Duration>,
+    ) -> Option<OwnedSemaphorePermit> {
+        match semaphore.clone().try_acquire_owned() {
+            Ok(permit) => Some(permit),
+            Err(_) => match queue_timeout {
+                Some(queue_timeout) => {
+                    tokio::time::timeout(queue_timeout, semaphore.acquire_owned())
+                        .await
+                        .ok()?
+                        .ok()
+                }
+                None => None,
+            },
+        }
+    }
+}
+
+impl<S> Layer<S> for ConcurrencyLimitLayer {
+    type Service = ConcurrencyLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConcurrencyLimit {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimit<S> {
+    inner: S,
+    layer: ConcurrencyLimitLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ConcurrencyLimit<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+    ResBody: Default,
+{
+    type Response = Response<PermitBody<ResBody>>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        // Use the service that was polled ready, leaving a clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        if self
+            .layer
+            .bypass_paths
+            .iter()
+            .any(|path| path == request.uri().path())
+        {
+            return Box::pin(async move {
+                let response = inner.call(request).await;
+                response.map(|response| response.map(|body| PermitBody::new(body, None)))
+            });
+        }
+
+        let semaphore = self.layer.semaphore.clone();
+        let queue_timeout = self.layer.queue_timeout;
+        Box::pin(async move {
+            match ConcurrencyLimitLayer::acquire(semaphore, queue_timeout).await {
+                Some(permit) => {
+                    // On errors there is no body, and the permit is dropped right away.
+                    let response = inner.call(request).await;
+                    response
+                        .map(|response| response.map(|body| PermitBody::new(body, Some(permit))))
+                }
+                None => {
+                    warn!(
+                        "Too many in-flight requests, shedding request to [{}]",
+                        request.uri().path()
+                    );
+                    let mut response = Response::new(PermitBody::new(ResBody::default(), None));
+                    *response.status_mut() = StatusCode::SERVICE_UNAVAILABLE;
+                    Ok(response)
+                }
+            }
+        })
+    }
+}
+
+/// Response body holding the in-flight slot of its request until it is fully sent, or dropped.
+pub struct PermitBody<B> {
+    inner: B,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl<B> PermitBody<B> {
+    fn new(inner: B, permit: Option<OwnedSemaphorePermit>) -> Self {
+        Self { inner, permit }
+    }
+}
+
+impl<B> Body for PermitBody<B>
+where
+    B: Body + Unpin,
+{
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = Pin::new(&mut self.inner).poll_frame(cx);
+        if let Poll::Ready(None) = frame {
+            self.permit.take();
+        }
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use std::convert::Infallible;
+    use tokio::sync::Notify;
+    use tower::{ServiceBuilder, ServiceExt, service_fn};
+
+    fn request(path: &str) -> Request<()> {
+        Request::builder().uri(path).body(()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit() {
+        let release = Arc::new(Notify::new());
+        let service = {
+            let release = release.clone();
+            ServiceBuilder::new()
+                .layer(ConcurrencyLimitService::layer(2, None))
+                .service(service_fn(move |request: Request<()>| {
+                    let release = release.clone();
+                    async move {
+                        if request.uri().path() == "/slow" {
+                            release.notified().await;
+                        }
+                        Ok::<_, Infallible>(Response::new(()))
+                    }
+                }))
+        };
+
+        // Saturate the limit with slow requests
+        let slow_1 = tokio::spawn(service.clone().oneshot(request("/slow")));
+        let slow_2 = tokio::spawn(service.clone().oneshot(request("/slow")));
+        tokio::task::yield_now().await;
+        while service.layer.semaphore.available_permits() > 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // Excess requests are shed
+        let response = service.clone().oneshot(request("/fast")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Health checks bypass the limit
+        let response = service
+            .clone()
+            .oneshot(request(&format!("{BASE_URL_V1}{SERVER_STATUS}")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Freeing a slot lets a new request through
+        release.notify_one();
+        let _ = slow_1.await.unwrap();
+        let response = service.clone().oneshot(request("/fast")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        release.notify_one();
+        let _ = slow_2.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit_queue_timeout() {
+        let release = Arc::new(Notify::new());
+        let service = {
+            let release = release.clone();
+            ServiceBuilder::new()
+                .layer(ConcurrencyLimitService::layer(
+                    1,
+                    Some(Duration::from_secs(5)),
+                ))
+                .service(service_fn(move |request: Request<()>| {
+                    let release = release.clone();
+                    async move {
+                        if request.uri().path() == "/slow" {
+                            release.notified().await;
+                        }
+                        Ok::<_, Infallible>(Response::new(()))
+                    }
+                }))
+        };
+
+        let slow = tokio::spawn(service.clone().oneshot(request("/slow")));
+        while service.layer.semaphore.available_permits() > 0 {
+            tokio::task::yield_now().await;
+        }
+
+        // Queued until the slot is freed
+        let queued = tokio::spawn(service.clone().oneshot(request("/fast")));
+        tokio::task::yield_now().await;
+        release.notify_one();
+        let _ = slow.await.unwrap();
+        let response = queued.await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit_streaming_response() {
+        let service = ServiceBuilder::new()
+            .layer(ConcurrencyLimitService::layer(1, None))
+            .service(service_fn(|_request: Request<()>| async move {
+                Ok::<_, Infallible>(Response::new(axum::body::Body::from("streamed")))
+            }));
+
+        // The slot is held until the response body is sent
+        let response = service.clone().oneshot(request("/stream")).await.unwrap();
+        assert_eq!(service.layer.semaphore.available_permits(), 0);
+        let shed = service.clone().oneshot(request("/stream")).await.unwrap();
+        assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let body = to_bytes(axum::body::Body::new(response.into_body()), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"streamed");
+        assert_eq!(service.layer.semaphore.available_permits(), 1);
+    }
+}
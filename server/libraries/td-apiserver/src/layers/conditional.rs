@@ -0,0 +1,241 @@
+//
+//  Copyright 2025 Tabs Data Inc.
+//
+
+use axum::body::{Body, HttpBody, to_bytes};
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use http::{HeaderValue, Method, Request, Response, StatusCode};
+use ring::digest::{SHA256, digest};
+use std::fmt::Write;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+#[derive(Default)]
+pub struct ConditionalService;
+
+impl ConditionalService {
+    /// Creates a [`ConditionalLayer`].
+    pub fn layer() -> ConditionalLayer {
+        ConditionalLayer
+    }
+}
+
+/// Layer handling conditional `GET` requests.
+///
+/// `200 OK` JSON responses get an `ETag` header, a hash of the response body (which includes
+/// the entity ids and `modified_on`/version fields, so it changes whenever the entity does).
+/// If the request `If-None-Match` header matches it, the body is dropped and a
+/// `304 Not Modified` is returned instead.
+///
+/// Only responses already held in memory are handled, streamed responses are left as they are.
+#[derive(Debug, Clone)]
+pub struct ConditionalLayer;
+
+impl<S> Layer<S> for ConditionalLayer {
+    type Service = Conditional<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Conditional { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Conditional<S> {
+    inner: S,
+}
+
+/// Strong entity tag of a body.
+fn etag(body: &[u8]) -> String {
+    let hash = digest(&SHA256, body);
+    let mut etag = String::with_capacity(2 + 2 * hash.as_ref().len());
+    etag.push('"');
+    for byte in hash.as_ref() {
+        let _ = write!(etag, "{byte:02x}");
+    }
+    etag.push('"');
+    etag
+}
+
+/// Whether the `If-None-Match` header value matches the entity tag, using weak comparison.
+fn none_match(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+fn is_json(response: &Response<Body>) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for Conditional<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let conditional = request.method() == Method::GET;
+        let if_none_match = request
+            .headers()
+            .get(IF_NONE_MATCH)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let response = future.await?;
+            if !conditional
+                || response.status() != StatusCode::OK
+                || !is_json(&response)
+                || response.body().size_hint().exact().is_none()
+            {
+                return Ok(response);
+            }
+
+            let (mut parts, body) = response.into_parts();
+            let Ok(body) = to_bytes(body, usize::MAX).await else {
+                // The body is in memory, it cannot fail to be read.
+                return Ok(Response::from_parts(parts, Body::empty()));
+            };
+            let etag = etag(&body);
+            if let Ok(value) = HeaderValue::from_str(&etag) {
+                parts.headers.insert(ETAG, value);
+            }
+
+            match if_none_match {
+                Some(if_none_match) if none_match(&if_none_match, &etag) => {
+                    parts.status = StatusCode::NOT_MODIFIED;
+                    parts.headers.remove(CONTENT_TYPE);
+                    parts.headers.remove(CONTENT_LENGTH);
+                    Ok(Response::from_parts(parts, Body::empty()))
+                }
+                _ => Ok(Response::from_parts(parts, Body::from(body))),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::functions::FunctionsRouter;
+    use axum::{Extension, Router};
+    use std::sync::Arc;
+    use ta_apiserver::router::RouterExtension;
+    use ta_services::factory::ServiceFactory;
+    use td_database::sql::DbPool;
+    use td_objects::dxo::crudl::RequestContext;
+    use td_objects::dxo::function::FunctionRegister;
+    use td_objects::rest_urls::{FUNCTION_GET, FUNCTION_UPDATE};
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_function::{function_register, seed_function};
+    use td_objects::types::basic::{AccessTokenId, CollectionName, Description, RoleId, UserId};
+    use td_services::Context;
+    use td_services::function::services::FunctionServices;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_none_match() {
+        let etag = etag(b"{}");
+        assert!(none_match(&etag, &etag));
+        assert!(none_match(&format!("W/{etag}"), &etag));
+        assert!(none_match(&format!("\"other\", {etag}"), &etag));
+        assert!(none_match("*", &etag));
+        assert!(!none_match("\"other\"", &etag));
+    }
+
+    fn function(description: &str) -> FunctionRegister {
+        let mut function = function_register("function", &[], &[], &[]).unwrap();
+        function.description = Description::try_from(description).unwrap();
+        function
+    }
+
+    async fn get(router: &Router, uri: &str, if_none_match: Option<&str>) -> Response<Body> {
+        let mut request = Request::builder().method(Method::GET).uri(uri);
+        if let Some(if_none_match) = if_none_match {
+            request = request.header(IF_NONE_MATCH, if_none_match);
+        }
+        router
+            .clone()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_conditional_read(db: DbPool) {
+        let collection = seed_collection(
+            &db,
+            &CollectionName::try_from("collection").unwrap(),
+            &UserId::admin(),
+        )
+        .await;
+        seed_function(&db, &collection, &function("description")).await;
+
+        let context = Context::with_defaults(db);
+        let router: Router =
+            FunctionsRouter::router(Arc::new(FunctionServices::build(&context))).into();
+        let router =
+            router
+                .layer(ConditionalService::layer())
+                .layer(Extension(RequestContext::with(
+                    AccessTokenId::default(),
+                    UserId::admin(),
+                    RoleId::sys_admin(),
+                )));
+        let uri = FUNCTION_GET
+            .replace("{collection}", "collection")
+            .replace("{function}", "function");
+
+        let response = get(&router, &uri, None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers().get(ETAG).unwrap().to_str().unwrap();
+        let etag = etag.to_string();
+
+        // unchanged
+        let response = get(&router, &uri, Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(ETAG).unwrap(), etag.as_str());
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        // updated
+        let update = serde_json::to_string(&function("updated description")).unwrap();
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(
+                        FUNCTION_UPDATE
+                            .replace("{collection}", "collection")
+                            .replace("{function}", "function"),
+                    )
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(update))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = get(&router, &uri, Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers().get(ETAG).unwrap(), etag.as_str());
+    }
+}
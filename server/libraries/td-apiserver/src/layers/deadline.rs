@@ -0,0 +1,80 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::time::Duration;
+use td_tower::deadline::with_deadline;
+use tokio::time::Instant;
+
+/// Header with the client timeout of a request, in milliseconds.
+pub const REQUEST_TIMEOUT_HEADER: &str = "X-Request-Timeout";
+
+/// Sets the deadline of the request, propagated to its database and storage operations.
+///
+/// The deadline is the `X-Request-Timeout` header timeout, if given and valid, capped by the
+/// server request timeout (the state of the layer).
+pub async fn deadline_layer(
+    State(request_timeout): State<Duration>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let timeout = request
+        .headers()
+        .get(REQUEST_TIMEOUT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_millis)
+        .map_or(request_timeout, |timeout| timeout.min(request_timeout));
+    with_deadline(Instant::now() + timeout, next.run(request)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::body::{Body, to_bytes};
+    use axum::middleware::from_fn_with_state;
+    use axum::routing::get;
+    use td_tower::deadline::remaining;
+    use tower::ServiceExt;
+
+    async fn remaining_ms(timeout: Option<&str>) -> u64 {
+        let router = Router::new()
+            .route(
+                "/",
+                get(|| async { remaining().unwrap().as_millis().to_string() }),
+            )
+            .layer(from_fn_with_state(Duration::from_secs(60), deadline_layer));
+        let mut request = Request::builder().uri("/");
+        if let Some(timeout) = timeout {
+            request = request.header(REQUEST_TIMEOUT_HEADER, timeout);
+        }
+        let response = router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap().parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_deadline_layer() {
+        // server default
+        let remaining = remaining_ms(None).await;
+        assert!(remaining > 1_000 && remaining <= 60_000);
+
+        // client timeout
+        assert!(remaining_ms(Some("1000")).await <= 1_000);
+
+        // capped by the server timeout
+        let remaining = remaining_ms(Some("3600000")).await;
+        assert!(remaining > 1_000 && remaining <= 60_000);
+
+        // invalid timeouts are ignored
+        let remaining = remaining_ms(Some("soon")).await;
+        assert!(remaining > 1_000 && remaining <= 60_000);
+    }
+}
@@ -0,0 +1,277 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use axum::Json;
+use axum::body::{Body, HttpBody, to_bytes};
+use axum::extract::{MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::header::CONTENT_TYPE;
+use http::{HeaderValue, Method, StatusCode};
+use std::sync::Arc;
+use ta_apiserver::status::error_status::{ErrorResponseBuilder, ErrorStatus};
+use td_database::sql::DbPool;
+use td_error::TdError;
+use td_objects::dxo::crudl::RequestContext;
+use td_objects::dxo::idempotency::{IdempotencyKeyDB, IdempotencyKeyResponseDB};
+use td_objects::rest_urls::{FUNCTION_UPLOAD, FUNCTION_UPLOAD_CHUNK};
+use td_objects::sql::DaoQueries;
+use td_objects::types::basic::{AtTime, IdempotencyKey, ResponseBody, ResponseStatus};
+use td_services::idempotency::{
+    IDEMPOTENCY_KEY_HEADER, IDEMPOTENCY_KEY_LEASE, IDEMPOTENCY_KEY_TTL, IdempotencyError,
+    idempotency_key_expiration, release_idempotency_key, request_hash, reserve_idempotency_key,
+    store_idempotency_response,
+};
+
+/// Header set on responses replayed for an idempotency key.
+const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Max size of request bodies hashed, the same as the default limit of the JSON extractor.
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// Max size of responses stored, larger ones are not replayed.
+const MAX_RESPONSE_SIZE: usize = 2 * 1024 * 1024;
+
+/// Routes with streamed request bodies, which are not buffered to hash them.
+const STREAMED_ROUTES: [&str; 2] = [FUNCTION_UPLOAD, FUNCTION_UPLOAD_CHUNK];
+
+/// Honors the `Idempotency-Key` header on create (`POST`) requests.
+///
+/// The key is reserved before running the request, and the first `201 Created` response for it
+/// is stored and replayed for later requests of the same user with the same key and request.
+/// Reusing the key with a different request, or while its request is still in progress, is
+/// rejected with a `409 Conflict`. Other responses, and responses too large to be stored,
+/// release the key. Requests without the header, and the routes streaming their bodies, are
+/// not buffered and pass through.
+///
+/// It must be inside the authorization layer, as keys are scoped to the user making the
+/// request.
+pub async fn idempotency_layer(
+    State(db): State<DbPool>,
+    State(queries): State<Arc<DaoQueries>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ErrorStatus> {
+    if request.method() != Method::POST || is_streamed(&request) {
+        return Ok(next.run(request).await);
+    }
+    let (Some(key), Some(context)) = (
+        request.headers().get(IDEMPOTENCY_KEY_HEADER),
+        request.extensions().get::<RequestContext>(),
+    ) else {
+        return Ok(next.run(request).await);
+    };
+
+    let key = key
+        .to_str()
+        .map_err(|e| TdError::from(IdempotencyError::InvalidKey(e.to_string())))?;
+    let key = IdempotencyKey::try_from(key)?;
+    let user_id = context.user_id;
+
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|e| TdError::from(IdempotencyError::InvalidRequestBody(e.to_string())))?;
+    let hash = request_hash(parts.method.as_str(), parts.uri.path(), &body)?;
+
+    let reserved = IdempotencyKeyDB::builder()
+        .idempotency_key(key.clone())
+        .user_id(user_id)
+        .request_hash(hash.clone())
+        .response_status(None)
+        .response_body(None)
+        .created_on(AtTime::now())
+        .expires_on(idempotency_key_expiration(IDEMPOTENCY_KEY_LEASE)?)
+        .build()?;
+    match reserve_idempotency_key(&queries, &db, &reserved).await? {
+        Some(stored) if stored.request_hash != hash => {
+            return Ok(conflict(IdempotencyError::KeyReused(key).into()));
+        }
+        Some(stored) => {
+            return match (stored.response_status, stored.response_body) {
+                (Some(status), Some(body)) => Ok(replay(status, body)),
+                _ => Ok(conflict(IdempotencyError::KeyInProgress(key).into())),
+            };
+        }
+        None => {}
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let storable = response
+        .body()
+        .size_hint()
+        .exact()
+        .is_some_and(|size| size <= MAX_RESPONSE_SIZE as u64);
+    if response.status() != StatusCode::CREATED || !storable {
+        release_idempotency_key(&queries, &db, &key, &user_id).await?;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, MAX_RESPONSE_SIZE)
+        .await
+        .map_err(|e| TdError::from(IdempotencyError::InvalidRequestBody(e.to_string())))?;
+    let stored = IdempotencyKeyResponseDB::builder()
+        .response_status(Some(
+            ResponseStatus::try_from(parts.status.as_u16() as i16)?,
+        ))
+        .response_body(Some(ResponseBody::try_from(
+            String::from_utf8_lossy(&body).into_owned(),
+        )?))
+        .expires_on(idempotency_key_expiration(IDEMPOTENCY_KEY_TTL)?)
+        .build()?;
+    // Otherwise the key would stay reserved, and retries rejected as in progress.
+    store_idempotency_response(&queries, &db, &key, &user_id, &stored).await?;
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+fn is_streamed(request: &Request) -> bool {
+    request
+        .extensions()
+        .get::<MatchedPath>()
+        .is_some_and(|path| {
+            STREAMED_ROUTES
+                .iter()
+                .any(|route| path.as_str().ends_with(route))
+        })
+}
+
+fn replay(status: ResponseStatus, body: ResponseBody) -> Response {
+    let status = StatusCode::from_u16(*status as u16).unwrap_or(StatusCode::CREATED);
+    let mut response = (status, body.to_string()).into_response();
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+fn conflict(error: TdError) -> Response {
+    let error = ErrorResponseBuilder::default()
+        .status(StatusCode::CONFLICT)
+        .code(error.code())
+        .error(Some(String::from("conflict")))
+        .error_description(Some(error.to_string()))
+        .build()
+        .unwrap();
+    (StatusCode::CONFLICT, Json(error)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::collections::CollectionsRouter;
+    use axum::middleware::from_fn_with_state;
+    use axum::routing::post;
+    use axum::{Extension, Router};
+    use serde_json::json;
+    use ta_apiserver::router::RouterExtension;
+    use ta_services::factory::ServiceFactory;
+    use td_objects::rest_urls::{CREATE_COLLECTION, LIST_COLLECTIONS};
+    use td_objects::types::basic::{AccessTokenId, RoleId, UserId};
+    use td_services::Context;
+    use td_services::collection::service::CollectionServices;
+    use tower::ServiceExt;
+
+    fn router(db: DbPool) -> Router {
+        let context = Context::with_defaults(db);
+        let router: Router =
+            CollectionsRouter::router(Arc::new(CollectionServices::build(&context))).into();
+        router
+            .layer(from_fn_with_state(context, idempotency_layer))
+            .layer(Extension(RequestContext::with(
+                AccessTokenId::default(),
+                UserId::admin(),
+                RoleId::sys_admin(),
+            )))
+    }
+
+    async fn create(router: &Router, key: &str, name: &str) -> Response {
+        let body = json!({ "name": name, "description": "idempotent" });
+        router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(CREATE_COLLECTION)
+                    .header(CONTENT_TYPE, "application/json")
+                    .header(IDEMPOTENCY_KEY_HEADER, key)
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_idempotent_create(db: DbPool) {
+        let router = router(db);
+
+        let first = create(&router, "key", "idempotent_collection").await;
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(first.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        let first = body_json(first).await;
+
+        // the retry returns the stored response
+        let second = create(&router, "key", "idempotent_collection").await;
+        assert_eq!(second.status(), StatusCode::CREATED);
+        assert!(second.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_some());
+        assert_eq!(body_json(second).await, first);
+
+        // and a single collection was created
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(LIST_COLLECTIONS)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(body_json(response).await["data"]["len"], 1);
+
+        // reusing the key with a different request is a conflict
+        let third = create(&router, "key", "other_collection").await;
+        assert_eq!(third.status(), StatusCode::CONFLICT);
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_streamed_route_not_buffered(db: DbPool) {
+        let context = Context::with_defaults(db);
+        let router = Router::new()
+            .route(FUNCTION_UPLOAD, post(|| async { StatusCode::CREATED }))
+            .layer(from_fn_with_state(context, idempotency_layer))
+            .layer(Extension(RequestContext::with(
+                AccessTokenId::default(),
+                UserId::admin(),
+                RoleId::sys_admin(),
+            )));
+
+        // the key is ignored, different requests with it are not conflicts
+        let uri = FUNCTION_UPLOAD.replace("{collection}", "collection");
+        for body in ["first", "second"] {
+            let response = router
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method(Method::POST)
+                        .uri(&uri)
+                        .header(IDEMPOTENCY_KEY_HEADER, "key")
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            assert!(response.headers().get(IDEMPOTENT_REPLAYED_HEADER).is_none());
+        }
+    }
+}
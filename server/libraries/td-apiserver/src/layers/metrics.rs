@@ -0,0 +1,82 @@
+//
+//  Copyright 2025 Tabs Data Inc.
+//
+
+use axum::extract::MatchedPath;
+use http::{Request, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+/// Label used for requests not matching any route, to keep the route label cardinality bounded.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+#[derive(Default)]
+pub struct MetricsService;
+
+impl MetricsService {
+    /// Creates a [`MetricsLayer`].
+    pub fn layer() -> MetricsLayer {
+        MetricsLayer
+    }
+}
+
+/// Layer recording the count and latency of requests, by method, route and status.
+///
+/// The route is the matched route template (i.e. `/collections/{collection}`), not the request
+/// path.
+#[derive(Debug, Clone)]
+pub struct MetricsLayer;
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = Metrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Metrics { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Metrics<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Metrics<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| path.as_str().to_string())
+            .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+        let method = request.method().to_string();
+        let start = Instant::now();
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let response = future.await?;
+            let labels = [
+                ("method", method),
+                ("route", route),
+                ("status", response.status().as_u16().to_string()),
+            ];
+            metrics::counter!("http_requests_total", &labels).increment(1);
+            metrics::histogram!("http_request_duration_seconds", &labels)
+                .record(start.elapsed().as_secs_f64());
+            Ok(response)
+        })
+    }
+}
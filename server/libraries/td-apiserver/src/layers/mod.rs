@@ -2,8 +2,18 @@
 //  Copyright 2024 Tabs Data Inc.
 //
 
+pub mod access_log;
 pub mod authorization;
 pub mod compression;
+pub mod concurrency_limit;
+pub mod conditional;
 pub mod cors;
+pub mod deadline;
+pub mod idempotency;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod read_consistency;
+#[cfg(feature = "otel")]
+pub mod trace_context;
 pub mod tracing;
 pub mod uri_filter;
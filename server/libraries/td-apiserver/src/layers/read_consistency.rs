@@ -0,0 +1,209 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use axum::extract::{Query, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::header::RETRY_AFTER;
+use http::{Method, StatusCode};
+use serde::Deserialize;
+use std::sync::Arc;
+use td_database::sql::DbPool;
+use td_objects::dxo::crudl::RequestContext;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+/// Consistent reads in flight, they are serialized with the writes in the read-write pool.
+pub const MAX_CONSISTENT_READS: usize = 4;
+
+/// Query param asking for read-your-writes consistency.
+#[derive(Debug, Default, Deserialize)]
+struct ReadConsistency {
+    #[serde(default)]
+    consistent: bool,
+}
+
+/// State of the [`read_consistency_layer`], limiting the consistent reads in flight.
+#[derive(Clone)]
+pub struct ReadConsistencyState {
+    db: DbPool,
+    permits: Arc<Semaphore>,
+}
+
+impl ReadConsistencyState {
+    pub fn new(db: DbPool, max_consistent_reads: usize) -> Self {
+        Self {
+            db,
+            permits: Arc::new(Semaphore::new(max_consistent_reads)),
+        }
+    }
+}
+
+/// Honors the `consistent` query param on authenticated `GET` requests.
+///
+/// Reads are served from the read-only database pool, which may lag behind a just committed
+/// write. With `consistent=true` the request is handled within a [`DbPool::read_write_scope`],
+/// so its reads go to the read-write pool and always see the committed writes, at a latency
+/// cost (they are serialized with the writes).
+///
+/// As they hold the read-write connection, the consistent reads in flight are limited (see
+/// [`ReadConsistencyState::new`]), further ones get a `429 Too Many Requests` response. Requests
+/// without a [`RequestContext`] (not authenticated) ignore the param.
+pub async fn read_consistency_layer(
+    State(state): State<ReadConsistencyState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let consistent = request.method() == Method::GET
+        && request.extensions().get::<RequestContext>().is_some()
+        && Query::<ReadConsistency>::try_from_uri(request.uri())
+            .map(|Query(params)| params.consistent)
+            .unwrap_or_default();
+    if !consistent {
+        return next.run(request).await;
+    }
+
+    let Ok(_permit) = state.permits.try_acquire() else {
+        warn!(
+            "Too many consistent reads in flight, rejecting request to [{}]",
+            request.uri().path()
+        );
+        return (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, "1")]).into_response();
+    };
+    state.db.read_write_scope(next.run(request)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::collections::CollectionsRouter;
+    use axum::body::{Body, to_bytes};
+    use axum::middleware::from_fn_with_state;
+    use axum::routing::get;
+    use axum::{Extension, Router};
+    use serde_json::json;
+    use ta_apiserver::router::RouterExtension;
+    use ta_services::factory::ServiceFactory;
+    use td_objects::rest_urls::{CREATE_COLLECTION, LIST_COLLECTIONS};
+    use td_objects::types::basic::{AccessTokenId, RoleId, UserId};
+    use td_services::Context;
+    use td_services::collection::service::CollectionServices;
+    use tower::ServiceExt;
+
+    fn request_context() -> RequestContext {
+        RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )
+    }
+
+    fn router(db: DbPool, max_consistent_reads: usize) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(|| async { DbPool::in_read_write_scope().to_string() }),
+            )
+            .layer(from_fn_with_state(
+                ReadConsistencyState::new(db, max_consistent_reads),
+                read_consistency_layer,
+            ))
+    }
+
+    async fn send(router: &Router, request: Request) -> (StatusCode, Vec<u8>) {
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec();
+        (status, body)
+    }
+
+    async fn in_read_write_scope(router: &Router, uri: &str) -> String {
+        let request = Request::builder()
+            .uri(uri)
+            .extension(request_context())
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(router, request).await;
+        assert_eq!(status, StatusCode::OK);
+        String::from_utf8(body).unwrap()
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_read_consistency_layer(db: DbPool) {
+        let router = router(db, MAX_CONSISTENT_READS);
+        assert_eq!(in_read_write_scope(&router, "/").await, "false");
+        assert_eq!(
+            in_read_write_scope(&router, "/?consistent=false").await,
+            "false"
+        );
+        assert_eq!(
+            in_read_write_scope(&router, "/?len=10&consistent=true").await,
+            "true"
+        );
+
+        // not authenticated requests ignore the param
+        let request = Request::builder()
+            .uri("/?consistent=true")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(send(&router, request).await.1, b"false");
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_read_consistency_layer_limit(db: DbPool) {
+        let router = router(db, 0);
+
+        // without permits consistent reads are rejected, other reads are not affected
+        let request = Request::builder()
+            .uri("/?consistent=true")
+            .extension(request_context())
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "1");
+        assert_eq!(in_read_write_scope(&router, "/").await, "false");
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_consistent_write_then_read(db: DbPool) {
+        let context = Context::with_defaults(db.clone());
+        let router: Router =
+            CollectionsRouter::router(Arc::new(CollectionServices::build(&context))).into();
+        let router = router
+            .layer(from_fn_with_state(
+                ReadConsistencyState::new(db, MAX_CONSISTENT_READS),
+                read_consistency_layer,
+            ))
+            .layer(Extension(request_context()));
+
+        // write through the API
+        let create = json!({"name": "collection", "description": "description"});
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(CREATE_COLLECTION)
+            .header("content-type", "application/json")
+            .body(Body::from(create.to_string()))
+            .unwrap();
+        let (status, _) = send(&router, request).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        // and read it right away
+        let request = Request::builder()
+            .uri(format!("{LIST_COLLECTIONS}?consistent=true"))
+            .body(Body::empty())
+            .unwrap();
+        let (status, body) = send(&router, request).await;
+        assert_eq!(status, StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["len"], 1);
+        assert_eq!(body["data"]["data"][0]["name"], "collection");
+    }
+}
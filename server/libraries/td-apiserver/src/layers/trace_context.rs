@@ -0,0 +1,168 @@
+//
+//  Copyright 2025 Tabs Data Inc.
+//
+
+use http::{HeaderMap, HeaderName, HeaderValue, Request, Response};
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+#[derive(Default)]
+pub struct TraceContextService;
+
+impl TraceContextService {
+    /// Creates a [`TraceContextLayer`] using W3C trace context (`traceparent`) headers.
+    pub fn layer() -> TraceContextLayer {
+        TraceContextLayer {
+            propagator: TraceContextPropagator::new(),
+        }
+    }
+}
+
+/// Layer propagating the OpenTelemetry span context.
+///
+/// The span context of the incoming request headers, if any, is set as the parent of the
+/// current span (the request span, so this layer must be inside the
+/// [`TraceService`](crate::layers::tracing::TraceService) layer). The span context of the
+/// request span is written to the response headers.
+#[derive(Debug, Clone)]
+pub struct TraceContextLayer {
+    propagator: TraceContextPropagator,
+}
+
+impl<S> Layer<S> for TraceContextLayer {
+    type Service = TraceContext<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceContext {
+            inner,
+            propagator: self.propagator.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceContext<S> {
+    inner: S,
+    propagator: TraceContextPropagator,
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for TraceContext<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let span = Span::current();
+        let parent = self.propagator.extract(&HeaderExtractor(request.headers()));
+        // An invalid or missing 'traceparent' yields an empty context, leaving a root span.
+        let _ = span.set_parent(parent);
+
+        let propagator = self.propagator.clone();
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let mut response = future.await?;
+            propagator.inject_context(&span.context(), &mut HeaderInjector(response.headers_mut()));
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::tracing::TraceService;
+    use axum::body::Body;
+    use opentelemetry::trace::{SpanContext, TraceContextExt, TracerProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use std::convert::Infallible;
+    use tower::{ServiceBuilder, ServiceExt, service_fn};
+    use tracing::subscriber::set_default;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::registry;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+    #[tokio::test]
+    async fn test_trace_context_propagation() {
+        let tracer_provider = SdkTracerProvider::builder().build();
+        let subscriber = registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("test")));
+        let _guard = set_default(subscriber);
+
+        let service = ServiceBuilder::new()
+            .layer(TraceService::layer())
+            .layer(TraceContextService::layer())
+            .service(service_fn(|_: Request<Body>| async {
+                // the span context seen by the request handler
+                let context = Span::current().context();
+                let span_context = context.span().span_context().clone();
+                let mut response = Response::new(Body::empty());
+                response.extensions_mut().insert(span_context);
+                Ok::<_, Infallible>(response)
+            }));
+
+        let request = Request::builder()
+            .uri("/api/v1/collections")
+            .header("traceparent", format!("00-{TRACE_ID}-{PARENT_SPAN_ID}-01"))
+            .body(Body::empty())
+            .unwrap();
+        let response = service.oneshot(request).await.unwrap();
+
+        // the request span is a child of the propagated one
+        let span_context = response.extensions().get::<SpanContext>().unwrap();
+        assert_eq!(span_context.trace_id().to_string(), TRACE_ID);
+        assert_ne!(span_context.span_id().to_string(), PARENT_SPAN_ID);
+
+        // and its context is written to the response
+        let traceparent = response
+            .headers()
+            .get("traceparent")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert_eq!(
+            traceparent,
+            format!("00-{TRACE_ID}-{}-01", span_context.span_id())
+        );
+    }
+}
@@ -2,9 +2,10 @@
 //  Copyright 2024 Tabs Data Inc.
 //
 
+use crate::layers::access_log::REQUEST_ID_HEADER;
 use http::{HeaderName, HeaderValue, Request};
-use std::fmt::Debug;
-use td_common::id::{Id, id};
+use std::fmt::{Debug, Display};
+use td_common::id::id;
 use tower_http::LatencyUnit;
 use tower_http::trace::{
     DefaultOnEos, DefaultOnFailure, DefaultOnRequest, DefaultOnResponse, HttpMakeClassifier,
@@ -38,17 +39,25 @@ impl TraceService {
     }
 }
 
-/// Similar to [`tower_http::trace::DefaultMakeSpan`], but with a unique ID per span.
+/// Similar to [`tower_http::trace::DefaultMakeSpan`], but with a unique ID per span. The ID is
+/// the request id header, if set (i.e. by the access log layer), so both can be correlated.
 #[derive(Debug, Clone, Default)]
 pub struct RequestMakeSpan;
 
 impl<B: Debug> MakeSpan<B> for RequestMakeSpan {
     fn make_span(&mut self, request: &Request<B>) -> Span {
-        log_span(&id(), request)
+        match request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+        {
+            Some(request_id) => log_span(request_id, request),
+            None => log_span(id(), request),
+        }
     }
 }
 
-fn log_span<B>(id: &Id, request: &Request<B>) -> Span {
+fn log_span<B>(id: impl Display, request: &Request<B>) -> Span {
     fn header<B>(request: &Request<B>, header: HeaderName) -> String {
         const UNKNOWN_HEADER: HeaderValue = HeaderValue::from_static("unknown");
         format!(
@@ -5,6 +5,8 @@
 pub mod apiserver;
 pub mod config;
 mod layers;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod router;
 pub mod scheduler_server;
 
@@ -15,14 +17,14 @@ use axum_server::tls_rustls::RustlsConfig;
 use futures::StreamExt;
 use futures::stream::FuturesUnordered;
 use http::uri::Scheme;
-use rustls::crypto::{aws_lc_rs, ring};
+use rustls::crypto::{CryptoProvider, aws_lc_rs, ring};
 use std::fmt::Debug;
 use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use td_common::server::{SSL_CERT_PEM_FILE, SSL_KEY_PEM_FILE};
 use td_objects::types::addresses::NonEmptyAddresses;
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, TcpSocket};
 use tokio::task::{JoinError, JoinHandle};
 use tracing::{debug, error, info, warn};
 
@@ -38,6 +40,22 @@ pub enum ServerError {
     JoinHandle(JoinError),
     #[error("Failed to get the loopback port: {0}")]
     LoopbackPort(std::io::Error),
+    #[error("Invalid server addresses: {0}")]
+    InvalidAddresses(td_error::TdError),
+    #[error("TLS is required but not available: {0}")]
+    TlsRequired(String),
+    #[error("Failed to install the metrics recorder: {0}")]
+    Metrics(String),
+}
+
+const LISTENER_BACKLOG: u32 = 1024;
+const MAX_BIND_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Retry policy for binding addresses still in use, i.e. by a previous instance on a restart.
+#[derive(Debug, Clone, Copy)]
+struct BindRetry {
+    attempts: u32,
+    delay: Duration,
 }
 
 /// Builder for [`Server`]. It will bind the addresses used and create the server on build.
@@ -46,6 +64,8 @@ pub struct ServerBuilder {
     addresses: NonEmptyAddresses,
     router: Router,
     ssl_folder: Option<PathBuf>,
+    require_tls: bool,
+    bind_retry: Option<BindRetry>,
 }
 
 impl ServerBuilder {
@@ -54,6 +74,8 @@ impl ServerBuilder {
             addresses: addresses.into(),
             router,
             ssl_folder: None,
+            require_tls: false,
+            bind_retry: None,
         }
     }
 
@@ -62,18 +84,97 @@ impl ServerBuilder {
         self
     }
 
+    /// Fails the build, instead of falling back to plain, if a ssl folder is configured but tls
+    /// cannot be loaded from it.
+    pub fn require_tls(mut self) -> Self {
+        self.require_tls = true;
+        self
+    }
+
+    /// Retries binding addresses in use up to `attempts` more times, doubling the `delay`
+    /// between attempts, capped at 5 seconds.
+    pub fn bind_retry(mut self, attempts: u32, delay: Duration) -> Self {
+        self.bind_retry = Some(BindRetry { attempts, delay });
+        self
+    }
+
+    fn bind(addr: &SocketAddr) -> std::io::Result<TcpListener> {
+        let socket = if addr.is_ipv4() {
+            TcpSocket::new_v4()?
+        } else {
+            TcpSocket::new_v6()?
+        };
+        // On Windows SO_REUSEADDR allows binding ports actively in use, so it is not set.
+        #[cfg(not(windows))]
+        socket.set_reuseaddr(true)?;
+        socket.bind(*addr)?;
+        socket.listen(LISTENER_BACKLOG)
+    }
+
+    async fn bind_with_retry(&self, addr: &SocketAddr) -> Result<TcpListener, ServerError> {
+        let (mut attempts, mut delay) = match self.bind_retry {
+            Some(retry) => (retry.attempts, retry.delay),
+            None => (0, Duration::ZERO),
+        };
+        loop {
+            match Self::bind(addr) {
+                Ok(listener) => return Ok(listener),
+                Err(e) if attempts > 0 && e.kind() == std::io::ErrorKind::AddrInUse => {
+                    warn!("Address [{addr}] in use, retrying bind in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    attempts -= 1;
+                    delay = (delay * 2).min(MAX_BIND_RETRY_DELAY);
+                }
+                Err(e) => return Err(ServerError::Bind(*addr, e)),
+            }
+        }
+    }
+
     async fn bind_listeners(&self) -> Result<Vec<TcpListener>, ServerError> {
         let mut listeners = Vec::new();
         for addr in self.addresses.iter() {
-            match TcpListener::bind(addr).await {
-                Ok(listener) => listeners.push(listener),
-                Err(e) => Err(ServerError::Bind(*addr, e))?,
-            }
+            listeners.push(self.bind_with_retry(addr).await?);
         }
         Ok(listeners)
     }
 
-    async fn load_tls(&self) -> Option<RustlsConfig> {
+    /// Returns `Ok(None)` if tls is not available, or an error if it is required.
+    fn tls_unavailable(&self, reason: String) -> Result<Option<RustlsConfig>, ServerError> {
+        if self.require_tls {
+            error!("{reason}. Protocol tls is required.");
+            Err(ServerError::TlsRequired(reason))
+        } else {
+            warn!("{reason}. Protocol tls will not be available.");
+            Ok(None)
+        }
+    }
+
+    fn install_crypto_provider() -> Result<(), String> {
+        if CryptoProvider::get_default().is_some() {
+            debug!("A tls cryptographic provider is already installed");
+            return Ok(());
+        }
+
+        if let Err(e) = aws_lc_rs::default_provider().install_default() {
+            warn!(
+                "Failed to install the aws-lc-rs tls cryptographic provider: {e:?}. Falling back to ring tls cryptographic provider."
+            );
+            if let Err(e) = ring::default_provider().install_default() {
+                return Err(format!(
+                    "Failed to install the ring tls cryptographic provider: {e:?}"
+                ));
+            } else {
+                info!("Successfully installed the ring tls cryptographic provider!");
+                println!("Successfully installed the ring tls cryptographic provider!");
+            }
+        } else {
+            info!("Successfully installed the aws-lc-rs tls cryptographic provider!");
+            println!("Successfully installed the aws-lc-rs tls cryptographic provider!");
+        }
+        Ok(())
+    }
+
+    async fn load_tls(&self) -> Result<Option<RustlsConfig>, ServerError> {
         let ssl_folder = match &self.ssl_folder {
             Some(folder) => {
                 debug!("A ssl folder was provided: '{}'", folder.display());
@@ -81,7 +182,7 @@ impl ServerBuilder {
             }
             None => {
                 warn!("A ssl folder was not provided. Protocol tls will not be available.");
-                return None;
+                return Ok(None);
             }
         };
 
@@ -95,42 +196,29 @@ impl ServerBuilder {
             );
             (key_path, cert_path)
         } else {
-            warn!(
-                "The ssl folder does no exist: '{:?}'. Protocol tls will not be available.",
-                self.ssl_folder
-            );
-            return None;
+            return self.tls_unavailable(format!(
+                "The ssl folder does no exist: '{}'",
+                ssl_folder.display()
+            ));
         };
 
-        if let Err(e) = aws_lc_rs::default_provider().install_default() {
-            warn!(
-                "Failed to install the aws-lc-rs tls cryptographic provider: {e:?}. Falling back to ring tls cryptographic provider."
-            );
-            if let Err(e) = ring::default_provider().install_default() {
-                info!(
-                    "Failed to install the ring tls cryptographic provider: {e:?}. Protocol tls will not be available."
-                );
-                return None;
-            } else {
-                info!("Successfully installed the ring tls cryptographic provider!");
-                println!("Successfully installed the ring tls cryptographic provider!");
-            }
-        } else {
-            info!("Successfully installed the aws-lc-rs tls cryptographic provider!");
-            println!("Successfully installed the aws-lc-rs tls cryptographic provider!");
+        if let Err(reason) = Self::install_crypto_provider() {
+            return self.tls_unavailable(reason);
         }
 
-        RustlsConfig::from_pem_file(cert_path, key_path)
-            .await
-            .map_err(|e| {
-                warn!("Error loading the tls certificates: {e}");
-            })
-            .ok()
+        match RustlsConfig::from_pem_file(cert_path, key_path).await {
+            Ok(config) => Ok(Some(config)),
+            Err(e) => self.tls_unavailable(format!("Error loading the tls certificates: {e}")),
+        }
     }
 
-    pub async fn build(self) -> Result<Box<dyn Server>, ServerError> {
+    pub async fn build(mut self) -> Result<Box<dyn Server>, ServerError> {
+        self.addresses = self
+            .addresses
+            .validated()
+            .map_err(ServerError::InvalidAddresses)?;
         let listeners = self.bind_listeners().await?;
-        match self.load_tls().await {
+        match self.load_tls().await? {
             Some(tls_config) => Ok(Box::new(TlsServer {
                 listeners,
                 tls_config,
@@ -383,6 +471,38 @@ mod tests {
         server_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_bind_retry_when_port_frees() {
+        let occupied = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = occupied.local_addr().unwrap();
+
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            drop(occupied);
+        });
+
+        let server = ServerBuilder::new(NonEmptyAddresses::new(nonempty![addr]), Router::new())
+            .bind_retry(20, Duration::from_millis(50))
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(server.listeners()[0].local_addr().unwrap(), addr);
+        release.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_bind_retry_gives_up() {
+        let occupied = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = occupied.local_addr().unwrap();
+
+        let result = ServerBuilder::new(NonEmptyAddresses::new(nonempty![addr]), Router::new())
+            .bind_retry(2, Duration::from_millis(10))
+            .build()
+            .await;
+        assert!(matches!(result, Err(ServerError::Bind(a, _)) if a == addr));
+        drop(occupied);
+    }
+
     #[tokio::test]
     async fn test_tls_config_missing_files() {
         let tls_path = testdir!();
@@ -391,10 +511,31 @@ mod tests {
             Router::new(),
         )
         .tls(tls_path);
-        let config = builder.load_tls().await;
+        let config = builder.load_tls().await.unwrap();
         assert!(config.is_none());
     }
 
+    #[tokio::test]
+    async fn test_require_tls_invalid_certificate() {
+        let tls_path = testdir!();
+        fs::write(tls_path.join(SSL_KEY_PEM_FILE), "not a key")
+            .await
+            .unwrap();
+        fs::write(tls_path.join(SSL_CERT_PEM_FILE), "not a certificate")
+            .await
+            .unwrap();
+
+        let result = ServerBuilder::new(
+            NonEmptyAddresses::new(nonempty![SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0)]),
+            Router::new(),
+        )
+        .tls(tls_path)
+        .require_tls()
+        .build()
+        .await;
+        assert!(matches!(result, Err(ServerError::TlsRequired(_))));
+    }
+
     #[tokio::test]
     async fn test_tls_config_success() {
         let tls_path = testdir!();
@@ -471,7 +612,7 @@ vRcg70teydPmY1fiAURFk3gl/g==
         )
         .tls(tls_path);
         eprintln!("Builder is {builder:?}");
-        let config = builder.load_tls().await;
+        let config = builder.load_tls().await.unwrap();
         eprintln!("Config is {config:?}");
         if check_flag_env(TD_CROSS_BUILD) {
             eprintln!("Identified a cross runtime.");
@@ -0,0 +1,124 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Prometheus metrics endpoint.
+//!
+//! It is served by the internal server, at its own bind addresses, and only to loopback IPs, as
+//! the internal API. It is not exposed with the API.
+//!
+//! Metrics are recorded through the `metrics` crate facade, by the [`MetricsLayer`] for HTTP
+//! requests and by the storage for its operations. Database pool usage is sampled on each scrape.
+//!
+//! [`MetricsLayer`]: crate::layers::metrics::MetricsLayer
+
+use crate::ServerError;
+use axum::Router;
+use axum::extract::State;
+use axum::routing::get;
+use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
+use std::sync::Mutex;
+use td_database::sql::DbPool;
+
+/// URL of the metrics endpoint, in Prometheus text format.
+pub const METRICS_URL: &str = "/metrics";
+
+/// Returns the handle of the Prometheus recorder, installing it as the global recorder on first
+/// use. It fails if another global recorder was installed by someone else.
+fn prometheus_handle() -> Result<PrometheusHandle, BuildError> {
+    static HANDLE: Mutex<Option<PrometheusHandle>> = Mutex::new(None);
+    let mut handle = HANDLE.lock().unwrap_or_else(|e| e.into_inner());
+    match handle.as_ref() {
+        Some(handle) => Ok(handle.clone()),
+        None => {
+            let installed = PrometheusBuilder::new().install_recorder()?;
+            *handle = Some(installed.clone());
+            Ok(installed)
+        }
+    }
+}
+
+#[derive(Clone)]
+struct MetricsState {
+    handle: PrometheusHandle,
+    db: DbPool,
+}
+
+pub struct MetricsRouter;
+
+impl MetricsRouter {
+    /// Router serving the metrics endpoint. It fails if the Prometheus recorder cannot be
+    /// installed.
+    pub fn router(db: DbPool) -> Result<Router, ServerError> {
+        let handle = prometheus_handle().map_err(|e| ServerError::Metrics(e.to_string()))?;
+        let state = MetricsState { handle, db };
+        Ok(Router::new()
+            .route(METRICS_URL, get(metrics))
+            .with_state(state))
+    }
+}
+
+fn record_pool(name: &'static str, size: u32, idle: usize) {
+    let in_use = (size as usize).saturating_sub(idle);
+    metrics::gauge!("db_pool_connections", "pool" => name, "state" => "in_use").set(in_use as f64);
+    metrics::gauge!("db_pool_connections", "pool" => name, "state" => "idle").set(idle as f64);
+}
+
+async fn metrics(State(state): State<MetricsState>) -> String {
+    let ro_pool = &state.db.ro_pool;
+    record_pool("ro", ro_pool.size(), ro_pool.num_idle());
+    if let Some(rw_pool) = &state.db.rw_pool {
+        record_pool("rw", rw_pool.size(), rw_pool.num_idle());
+    }
+    state.handle.render()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::metrics::MetricsService;
+    use axum::body::{Body, to_bytes};
+    use http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    async fn get_body(router: &Router, uri: &str) -> String {
+        let response = router
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_metrics_endpoint(db: DbPool) {
+        let router = Router::new()
+            .route("/hello/{name}", get(|| async { "hello" }))
+            .merge(MetricsRouter::router(db).unwrap())
+            .layer(MetricsService::layer());
+
+        get_body(&router, "/hello/world").await;
+        let metrics = get_body(&router, METRICS_URL).await;
+
+        // every sample line is `<name>{<labels>} <value>`
+        for line in metrics
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        {
+            let (name, value) = line.rsplit_once(' ').unwrap();
+            assert!(!name.is_empty(), "invalid line: {line}");
+            assert!(value.parse::<f64>().is_ok(), "invalid line: {line}");
+        }
+
+        assert!(metrics.lines().any(|l| {
+            l.starts_with("http_requests_total{")
+                && l.contains(r#"route="/hello/{name}""#)
+                && l.contains(r#"status="200""#)
+        }));
+        assert!(metrics.contains("http_request_duration_seconds"));
+        assert!(metrics.contains(r#"db_pool_connections{pool="rw",state="idle"}"#));
+    }
+}
@@ -9,18 +9,24 @@ const AUTH_TAG: &str = "Authentication";
 #[router_ext(SecureAuthRouter)]
 mod secure_routes {
     use crate::router::auth::AUTH_TAG;
-    use axum::extract::State;
+    use axum::extract::{Path, Query, State};
     use axum::{Extension, Form};
     use std::sync::Arc;
     use ta_apiserver::status::error_status::ErrorStatus;
     use ta_apiserver::status::extractors::Json;
-    use ta_apiserver::status::ok_status::{GetStatus, NoContent, RawStatus, UpdateStatus};
+    use ta_apiserver::status::ok_status::{
+        CreateStatus, GetStatus, ListStatus, NoContent, RawStatus, UpdateStatus,
+    };
     use ta_services::service::TdService;
     use td_apiforge::apiserver_path;
+    use td_objects::dxo::api_key::{ApiKey, ApiKeyCreate, ApiKeyCreated};
     use td_objects::dxo::auth::{RefreshRequestX, RoleChange, TokenResponseX};
-    use td_objects::dxo::crudl::RequestContext;
+    use td_objects::dxo::crudl::{ListParams, RequestContext};
     use td_objects::dxo::user::UserInfo;
-    use td_objects::rest_urls::{AUTH_LOGOUT, AUTH_REFRESH, AUTH_ROLE_CHANGE, AUTH_USER_INFO};
+    use td_objects::rest_urls::{
+        AUTH_LOGOUT, AUTH_REFRESH, AUTH_ROLE_CHANGE, AUTH_USER_INFO, ApiKeyParam, CREATE_API_KEY,
+        LIST_API_KEYS, REVOKE_API_KEY,
+    };
     use td_services::auth::services::AuthServices;
     use td_tower::ctx_service::RawOneshot;
     use tower::ServiceExt;
@@ -75,6 +81,52 @@ mod secure_routes {
         let response = state.user_info.service().await.oneshot(request).await?;
         Ok(GetStatus::OK(response))
     }
+
+    #[apiserver_path(method = post, path = CREATE_API_KEY, tag = AUTH_TAG)]
+    #[doc = "Create an API key for the current user and role, the key is only returned once"]
+    pub async fn create_api_key(
+        State(state): State<Arc<AuthServices>>,
+        Extension(context): Extension<RequestContext>,
+        Json(request): Json<ApiKeyCreate>,
+    ) -> Result<CreateStatus<ApiKeyCreated>, ErrorStatus> {
+        let request = context.create((), request);
+        let response = state
+            .create_api_key
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(CreateStatus::CREATED(response))
+    }
+
+    #[apiserver_path(method = get, path = LIST_API_KEYS, tag = AUTH_TAG)]
+    #[doc = "List the API keys of the current user"]
+    pub async fn list_api_keys(
+        State(state): State<Arc<AuthServices>>,
+        Extension(context): Extension<RequestContext>,
+        Query(query_params): Query<ListParams>,
+    ) -> Result<ListStatus<ApiKey>, ErrorStatus> {
+        let request = context.list((), query_params);
+        let response = state.list_api_keys.service().await.oneshot(request).await?;
+        Ok(ListStatus::OK(response))
+    }
+
+    #[apiserver_path(method = post, path = REVOKE_API_KEY, tag = AUTH_TAG)]
+    #[doc = "Revoke an API key of the current user"]
+    pub async fn revoke_api_key(
+        State(state): State<Arc<AuthServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(param): Path<ApiKeyParam>,
+    ) -> Result<UpdateStatus<NoContent>, ErrorStatus> {
+        let request = context.update(param, ());
+        let response = state
+            .revoke_api_key
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(UpdateStatus::OK(response))
+    }
 }
 
 #[router_ext(UnsecureAuthRouter)]
@@ -17,11 +17,14 @@ mod routes {
     };
     use ta_services::service::TdService;
     use td_apiforge::apiserver_path;
-    use td_objects::dxo::collection::{CollectionCreate, CollectionRead, CollectionUpdate};
+    use td_objects::dxo::collection::{
+        CollectionCreate, CollectionDefinition, CollectionImport, CollectionRead, CollectionUpdate,
+    };
     use td_objects::dxo::crudl::{ListParams, RequestContext};
+    use td_objects::rest_urls::params::CollectionCascadeName;
     use td_objects::rest_urls::{
-        CREATE_COLLECTION, CollectionParam, DELETE_COLLECTION, GET_COLLECTION, LIST_COLLECTIONS,
-        UPDATE_COLLECTION,
+        CREATE_COLLECTION, CascadeParam, CollectionParam, DELETE_COLLECTION, EXPORT_COLLECTION,
+        GET_COLLECTION, IMPORT_COLLECTION, LIST_COLLECTIONS, UPDATE_COLLECTION,
     };
     use td_services::collection::service::CollectionServices;
     use tower::ServiceExt;
@@ -103,8 +106,10 @@ mod routes {
         State(collection_state): State<Arc<CollectionServices>>,
         Extension(context): Extension<RequestContext>,
         Path(collection_param): Path<CollectionParam>,
+        Query(cascade_param): Query<CascadeParam>,
     ) -> Result<DeleteStatus<NoContent>, ErrorStatus> {
-        let request = context.delete(collection_param);
+        let name = CollectionCascadeName::new(collection_param, cascade_param);
+        let request = context.delete(name);
         let response = collection_state
             .delete
             .service()
@@ -113,6 +118,40 @@ mod routes {
             .await?;
         Ok(DeleteStatus::OK(response))
     }
+
+    #[apiserver_path(method = get, path = EXPORT_COLLECTION, tag = COLLECTIONS_TAG)]
+    #[doc = "Export the definition of a collection"]
+    pub async fn export_collection(
+        State(collection_state): State<Arc<CollectionServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(collection_param): Path<CollectionParam>,
+    ) -> Result<GetStatus<CollectionDefinition>, ErrorStatus> {
+        let request = context.read(collection_param);
+        let response = collection_state
+            .export
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(GetStatus::OK(response))
+    }
+
+    #[apiserver_path(method = post, path = IMPORT_COLLECTION, tag = COLLECTIONS_TAG)]
+    #[doc = "Import a collection definition"]
+    pub async fn import_collection(
+        State(collection_state): State<Arc<CollectionServices>>,
+        Extension(context): Extension<RequestContext>,
+        Json(request): Json<CollectionDefinition>,
+    ) -> Result<CreateStatus<CollectionImport>, ErrorStatus> {
+        let request = context.create((), request);
+        let response = collection_state
+            .import
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(CreateStatus::CREATED(response))
+    }
 }
 
 #[cfg(test)]
@@ -8,17 +8,33 @@ use td_apiforge::router_ext;
 mod routes {
     use axum::Extension;
     use axum::extract::{Path, State};
+    use axum::response::IntoResponse;
+    use axum::response::sse::{Event, KeepAlive, Sse};
     use axum_extra::extract::Query;
+    use futures::StreamExt;
+    use futures::stream::BoxStream;
+    use std::convert::Infallible;
     use std::sync::Arc;
     use ta_apiserver::status::error_status::ErrorStatus;
-    use ta_apiserver::status::ok_status::{GetStatus, ListStatus};
+    use ta_apiserver::status::extractors::Json;
+    use ta_apiserver::status::ok_status::{
+        CreateStatus, DeleteStatus, GetStatus, ListStatus, NoContent,
+    };
     use ta_services::service::TdService;
     use td_apiforge::apiserver_path;
     use td_objects::dxo::crudl::{ListParams, RequestContext};
     use td_objects::dxo::function_run::FunctionRun;
-    use td_objects::rest_urls::{FUNCTION_RUN_GET, FUNCTION_RUN_LIST, FunctionRunParam};
+    use td_objects::dxo::webhook::{Webhook, WebhookCreate};
+    use td_objects::rest_urls::{
+        CREATE_FUNCTION_RUN_WEBHOOK, CollectionParam, DELETE_FUNCTION_RUN_WEBHOOK,
+        FUNCTION_RUN_EVENTS, FUNCTION_RUN_GET, FUNCTION_RUN_LIST, FunctionRunParam,
+        LIST_FUNCTION_RUN_WEBHOOKS, WebhookParam,
+    };
+    use td_services::function_run::events::{EventsPolling, function_run_status_events};
     use td_services::function_run::services::FunctionRunServices;
+    use td_tower::ctx_service::RawOneshot;
     use tower::ServiceExt;
+    use utoipa::IntoResponses;
 
     const FUNCTION_RUNS_TAG: &str = "Function Runs";
 
@@ -45,4 +61,257 @@ mod routes {
         let response = state.read.service().await.oneshot(request).await?;
         Ok(GetStatus::OK(response))
     }
+
+    /// This struct is just used to document FunctionRunEvents in the OpenAPI schema.
+    /// The server is streaming server-sent `status` events, each one with the function run after
+    /// a status transition, and a final `error` event if it can no longer be read.
+    #[allow(dead_code)]
+    #[derive(utoipa::ToSchema, IntoResponses)]
+    #[response(status = 200, description = "OK", content_type = "text/event-stream")]
+    pub struct FunctionRunEvents(
+        #[schema(value_type = FunctionRun)] Sse<BoxStream<'static, Result<Event, Infallible>>>,
+    );
+
+    impl IntoResponse for FunctionRunEvents {
+        fn into_response(self) -> axum::response::Response {
+            self.0.into_response()
+        }
+    }
+
+    #[apiserver_path(method = get, path = FUNCTION_RUN_EVENTS, tag = FUNCTION_RUNS_TAG)]
+    #[doc = "Stream the status transitions of a function run, until it reaches a final status or the stream times out"]
+    pub async fn run_events(
+        State(state): State<Arc<FunctionRunServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(param): Path<FunctionRunParam>,
+    ) -> Result<FunctionRunEvents, ErrorStatus> {
+        // Fail before streaming if the function run cannot be read.
+        let request = context.clone().read(param.clone());
+        state.read.service().await.raw_oneshot(request).await?;
+
+        let read = move || {
+            let state = state.clone();
+            let request = context.clone().read(param.clone());
+            async move { state.read.service().await.raw_oneshot(request).await }
+        };
+        let events = function_run_status_events(read, EventsPolling::default())
+            .map(|function_run| {
+                let event = match function_run {
+                    Ok(function_run) => Event::default()
+                        .event("status")
+                        .json_data(function_run)
+                        .unwrap_or_else(|e| Event::default().event("error").data(e.to_string())),
+                    Err(e) => Event::default().event("error").data(e.to_string()),
+                };
+                Ok(event)
+            })
+            .boxed();
+        Ok(FunctionRunEvents(
+            Sse::new(events).keep_alive(KeepAlive::default()),
+        ))
+    }
+
+    #[apiserver_path(method = post, path = CREATE_FUNCTION_RUN_WEBHOOK, tag = FUNCTION_RUNS_TAG)]
+    #[doc = "Create a function run webhook"]
+    pub async fn create_webhook(
+        State(state): State<Arc<FunctionRunServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(param): Path<CollectionParam>,
+        Json(request): Json<WebhookCreate>,
+    ) -> Result<CreateStatus<Webhook>, ErrorStatus> {
+        let request = context.create(param, request);
+        let response = state
+            .create_webhook
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(CreateStatus::CREATED(response))
+    }
+
+    #[apiserver_path(method = get, path = LIST_FUNCTION_RUN_WEBHOOKS, tag = FUNCTION_RUNS_TAG)]
+    #[doc = "List function run webhooks"]
+    pub async fn list_webhooks(
+        State(state): State<Arc<FunctionRunServices>>,
+        Extension(context): Extension<RequestContext>,
+        Query(query_params): Query<ListParams>,
+        Path(path_params): Path<CollectionParam>,
+    ) -> Result<ListStatus<Webhook>, ErrorStatus> {
+        let request = context.list(path_params, query_params);
+        let response = state.list_webhooks.service().await.oneshot(request).await?;
+        Ok(ListStatus::OK(response))
+    }
+
+    #[apiserver_path(method = delete, path = DELETE_FUNCTION_RUN_WEBHOOK, tag = FUNCTION_RUNS_TAG)]
+    #[doc = "Delete a function run webhook"]
+    pub async fn delete_webhook(
+        State(state): State<Arc<FunctionRunServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(param): Path<WebhookParam>,
+    ) -> Result<DeleteStatus<NoContent>, ErrorStatus> {
+        let request = context.delete(param);
+        let response = state
+            .delete_webhook
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(DeleteStatus::OK(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::router::function_runs::FunctionRunsRouter;
+    use axum::body::{Body, BodyDataStream};
+    use axum::http::{Request, StatusCode};
+    use axum::{Extension, Router};
+    use futures::StreamExt;
+    use http::header::CONTENT_TYPE;
+    use http::method::Method;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use ta_apiserver::router::RouterExtension;
+    use ta_services::factory::ServiceFactory;
+    use td_database::sql::DbPool;
+    use td_objects::dxo::crudl::RequestContext;
+    use td_objects::dxo::function_run::{FunctionRun, FunctionRunDB, UpdateFunctionRunDB};
+    use td_objects::rest_urls::FUNCTION_RUN_EVENTS;
+    use td_objects::sql::{DaoQueries, UpdateBy};
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_execution::seed_execution;
+    use td_objects::test_utils::seed_function::{function_register, seed_function};
+    use td_objects::test_utils::seed_function_run::seed_function_run;
+    use td_objects::test_utils::seed_transaction::seed_transaction;
+    use td_objects::types::basic::{
+        AccessTokenId, CollectionName, FunctionRunId, FunctionRunStatus, RoleId, TransactionKey,
+        UserId,
+    };
+    use td_services::Context;
+    use td_services::function_run::services::FunctionRunServices;
+    use tower::ServiceExt;
+
+    async fn update_status(db: &DbPool, function_run: &FunctionRunId, status: FunctionRunStatus) {
+        let update = UpdateFunctionRunDB::builder()
+            .status(status)
+            .build()
+            .unwrap();
+        DaoQueries::default()
+            .update_by::<_, FunctionRunDB>(&update, &(function_run))
+            .unwrap()
+            .build()
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    /// Reads the next event of the stream, returning its name and data.
+    async fn next_event(
+        body: &mut BodyDataStream,
+        buffer: &mut String,
+    ) -> Option<(String, String)> {
+        loop {
+            if let Some(end) = buffer.find("\n\n") {
+                let frame: String = buffer.drain(..end + 2).collect();
+                let mut event = None;
+                let mut data = None;
+                for line in frame.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        event = Some(value.trim().to_string());
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data = Some(value.trim().to_string());
+                    }
+                }
+                // Keep-alive comments have neither.
+                if let (Some(event), Some(data)) = (event, data) {
+                    return Some((event, data));
+                }
+                continue;
+            }
+            let chunk = tokio::time::timeout(Duration::from_secs(10), body.next())
+                .await
+                .expect("timed out waiting for an event")?
+                .unwrap();
+            buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        }
+    }
+
+    async fn next_status(body: &mut BodyDataStream, buffer: &mut String) -> FunctionRunStatus {
+        let (event, data) = next_event(body, buffer).await.unwrap();
+        assert_eq!(event, "status");
+        let function_run: FunctionRun = serde_json::from_str(&data).unwrap();
+        function_run.status
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_function_run_events(db: DbPool) {
+        let collection = seed_collection(
+            &db,
+            &CollectionName::try_from("collection").unwrap(),
+            &UserId::admin(),
+        )
+        .await;
+        let create = function_register("function", &[], &[], &[]).unwrap();
+        let function_version = seed_function(&db, &collection, &create).await;
+        let execution = seed_execution(&db, &function_version).await;
+        let transaction =
+            seed_transaction(&db, &execution, &TransactionKey::try_from("ANY").unwrap()).await;
+        let function_run = seed_function_run(
+            &db,
+            &collection,
+            &function_version,
+            &execution,
+            &transaction,
+            &FunctionRunStatus::Scheduled,
+        )
+        .await;
+
+        let router: Router = FunctionRunsRouter::router(Arc::new(FunctionRunServices::build(
+            &Context::with_defaults(db.clone()),
+        )))
+        .into();
+        let router = router.layer(Extension(RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::user(),
+        )));
+
+        let uri = FUNCTION_RUN_EVENTS
+            .replace("{collection}", &collection.name.to_string())
+            .replace("{function}", &function_version.name.to_string())
+            .replace("{execution}", &format!("~{}", execution.id));
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/event-stream");
+
+        let mut body = response.into_body().into_data_stream();
+        let mut buffer = String::new();
+
+        // Current status first, then every transition until a final one.
+        assert_eq!(
+            next_status(&mut body, &mut buffer).await,
+            FunctionRunStatus::Scheduled
+        );
+        for status in [
+            FunctionRunStatus::Running,
+            FunctionRunStatus::Done,
+            FunctionRunStatus::Committed,
+        ] {
+            update_status(&db, &function_run.id, status.clone()).await;
+            assert_eq!(next_status(&mut body, &mut buffer).await, status);
+        }
+
+        // The stream is closed after the final status.
+        assert!(next_event(&mut body, &mut buffer).await.is_none());
+    }
 }
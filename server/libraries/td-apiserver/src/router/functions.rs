@@ -17,17 +17,23 @@ mod routes {
     };
     use ta_services::service::TdService;
     use td_apiforge::apiserver_path;
-    use td_objects::dxo::bundle::Bundle;
+    use td_objects::dxo::bundle::{Bundle, BundleUpload, BundleUploadCommit};
     use td_objects::dxo::crudl::{ListParams, RequestContext};
     use td_objects::dxo::function::{
-        Function, FunctionRegister, FunctionUpdate, FunctionWithTables,
+        Function, FunctionBatch, FunctionDiff, FunctionRegister, FunctionRegisterBatch,
+        FunctionSnippet, FunctionUpdate, FunctionWithTables,
     };
     use td_objects::dxo::function_upload::FunctionUpload;
-    use td_objects::rest_urls::params::{CollectionAtName, FunctionAtIdName};
+    use td_objects::rest_urls::params::{
+        CollectionAtName, FunctionAtIdName, FunctionDiffName, FunctionExpectedVersionName,
+        FunctionListName,
+    };
     use td_objects::rest_urls::{
-        AtTimeParam, CollectionParam, FUNCTION_CREATE, FUNCTION_DELETE, FUNCTION_GET,
-        FUNCTION_HISTORY, FUNCTION_LIST, FUNCTION_LIST_BY_COLL, FUNCTION_UPDATE, FUNCTION_UPLOAD,
-        FunctionParam,
+        AtTimeParam, BundleUploadChunkParam, BundleUploadParam, CollectionParam, FUNCTION_CREATE,
+        FUNCTION_CREATE_BATCH, FUNCTION_DELETE, FUNCTION_DIFF, FUNCTION_GET, FUNCTION_HISTORY,
+        FUNCTION_LIST, FUNCTION_LIST_BY_COLL, FUNCTION_SNIPPET, FUNCTION_UPDATE, FUNCTION_UPLOAD,
+        FUNCTION_UPLOAD_CHUNK, FUNCTION_UPLOAD_COMMIT, FUNCTION_UPLOAD_START, FunctionDiffParam,
+        FunctionExpectedVersionParam, FunctionParam, FunctionTagsParam,
     };
     use td_services::function::services::FunctionServices;
     use tower::ServiceExt;
@@ -66,8 +72,10 @@ mod routes {
         Extension(context): Extension<RequestContext>,
         Query(query_params): Query<ListParams>,
         Query(at_param): Query<AtTimeParam>,
+        Query(tags_param): Query<FunctionTagsParam>,
     ) -> Result<ListStatus<Function>, ErrorStatus> {
-        let request = context.list(at_param, query_params);
+        let name = FunctionListName::new(at_param, tags_param);
+        let request = context.list(name, query_params);
         let response = state.list.service().await.oneshot(request).await?;
         Ok(ListStatus::OK(response))
     }
@@ -104,6 +112,32 @@ mod routes {
         Ok(GetStatus::OK(response))
     }
 
+    #[apiserver_path(method = get, path = FUNCTION_SNIPPET, tag = FUNCTIONS_TAG)]
+    #[doc = "Show the source snippet of a function"]
+    pub async fn read_snippet(
+        State(state): State<Arc<FunctionServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(param): Path<FunctionParam>,
+    ) -> Result<GetStatus<FunctionSnippet>, ErrorStatus> {
+        let request = context.read(param);
+        let response = state.read_snippet.service().await.oneshot(request).await?;
+        Ok(GetStatus::OK(response))
+    }
+
+    #[apiserver_path(method = get, path = FUNCTION_DIFF, tag = FUNCTIONS_TAG)]
+    #[doc = "Diff two versions of a function"]
+    pub async fn diff(
+        State(state): State<Arc<FunctionServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(function_param): Path<FunctionParam>,
+        Query(diff_param): Query<FunctionDiffParam>,
+    ) -> Result<GetStatus<FunctionDiff>, ErrorStatus> {
+        let name = FunctionDiffName::new(function_param, diff_param);
+        let request = context.read(name);
+        let response = state.diff.service().await.oneshot(request).await?;
+        Ok(GetStatus::OK(response))
+    }
+
     #[apiserver_path(method = post, path = FUNCTION_CREATE, tag = FUNCTIONS_TAG)]
     #[doc = "Register a function"]
     pub async fn register(
@@ -117,15 +151,35 @@ mod routes {
         Ok(CreateStatus::CREATED(response))
     }
 
+    #[apiserver_path(method = post, path = FUNCTION_CREATE_BATCH, tag = FUNCTIONS_TAG)]
+    #[doc = "Register a batch of functions, all or none of them"]
+    pub async fn register_batch(
+        State(state): State<Arc<FunctionServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(collection_param): Path<CollectionParam>,
+        Json(request): Json<FunctionRegisterBatch>,
+    ) -> Result<CreateStatus<FunctionBatch>, ErrorStatus> {
+        let request = context.create(collection_param, request);
+        let response = state
+            .register_batch
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(CreateStatus::CREATED(response))
+    }
+
     #[apiserver_path(method = post, path = FUNCTION_UPDATE, tag = FUNCTIONS_TAG)]
     #[doc = "Update a function"]
     pub async fn update(
         State(state): State<Arc<FunctionServices>>,
         Extension(context): Extension<RequestContext>,
         Path(function_param): Path<FunctionParam>,
+        Query(expected_version_param): Query<FunctionExpectedVersionParam>,
         Json(request): Json<FunctionUpdate>,
     ) -> Result<UpdateStatus<Function>, ErrorStatus> {
-        let request = context.update(function_param, request);
+        let name = FunctionExpectedVersionName::new(function_param, expected_version_param);
+        let request = context.update(name, request);
         let response = state.update.service().await.oneshot(request).await?;
         Ok(UpdateStatus::OK(response))
     }
@@ -149,4 +203,141 @@ mod routes {
         let response = state.upload.service().await.oneshot(request).await?;
         Ok(CreateStatus::CREATED(response))
     }
+
+    #[apiserver_path(method = post, path = FUNCTION_UPLOAD_START, tag = FUNCTIONS_TAG)]
+    #[doc = "Start a chunked upload of a function bundle"]
+    pub async fn upload_start(
+        State(state): State<Arc<FunctionServices>>,
+        Extension(request_context): Extension<RequestContext>,
+        Path(param): Path<CollectionParam>,
+    ) -> Result<CreateStatus<BundleUpload>, ErrorStatus> {
+        let request = request_context.create(param, ());
+        let response = state.upload_start.service().await.oneshot(request).await?;
+        Ok(CreateStatus::CREATED(response))
+    }
+
+    #[apiserver_path(method = post, path = FUNCTION_UPLOAD_CHUNK, tag = FUNCTIONS_TAG)]
+    #[doc = "Upload a chunk of a function bundle, at its offset"]
+    pub async fn upload_chunk(
+        State(state): State<Arc<FunctionServices>>,
+        Extension(request_context): Extension<RequestContext>,
+        Path(param): Path<BundleUploadChunkParam>,
+        request: Request,
+    ) -> Result<CreateStatus<BundleUpload>, ErrorStatus> {
+        let request = FunctionUpload::new(request);
+        let request = request_context.create(param, request);
+        let response = state.upload_chunk.service().await.oneshot(request).await?;
+        Ok(CreateStatus::CREATED(response))
+    }
+
+    #[apiserver_path(method = post, path = FUNCTION_UPLOAD_COMMIT, tag = FUNCTIONS_TAG)]
+    #[doc = "Commit a chunked upload of a function bundle"]
+    pub async fn upload_commit(
+        State(state): State<Arc<FunctionServices>>,
+        Extension(request_context): Extension<RequestContext>,
+        Path(param): Path<BundleUploadParam>,
+        Json(request): Json<BundleUploadCommit>,
+    ) -> Result<CreateStatus<Bundle>, ErrorStatus> {
+        let request = request_context.create(param, request);
+        let response = state.upload_commit.service().await.oneshot(request).await?;
+        Ok(CreateStatus::CREATED(response))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::router::functions::FunctionsRouter;
+    use crate::{Server, ServerBuilder};
+    use axum::{Extension, Router};
+    use nonempty::nonempty;
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+    use ta_apiserver::router::RouterExtension;
+    use ta_services::factory::ServiceFactory;
+    use td_client::Client;
+    use td_database::sql::DbPool;
+    use td_error::{ApiError, TdError};
+    use td_objects::dxo::crudl::{ListParams, RequestContext};
+    use td_objects::rest_urls::{BASE_URL_V1, CollectionParam};
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_function::function_register;
+    use td_objects::types::addresses::NonEmptyAddresses;
+    use td_objects::types::basic::{AccessTokenId, CollectionName, RoleId, UserId};
+    use td_services::Context;
+    use td_services::function::services::FunctionServices;
+    use tokio::sync::watch;
+
+    /// Runs an in-process server with the functions routes, returning a client to it and the
+    /// server shutdown sender.
+    async fn client(db: DbPool) -> (Client, watch::Sender<()>) {
+        let context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        let router: Router = FunctionsRouter::router(Arc::new(FunctionServices::build(
+            &Context::with_defaults(db),
+        )))
+        .into();
+        let router = Router::new().nest(BASE_URL_V1, router.layer(Extension(context)));
+
+        let server = ServerBuilder::new(
+            NonEmptyAddresses::new(nonempty![SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0)]),
+            router,
+        )
+        .build()
+        .await
+        .unwrap();
+        let addr = server.listeners().first().unwrap().local_addr().unwrap();
+        let url = format!("{}://{}", server.scheme(), addr);
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(());
+        tokio::spawn(async move {
+            server.run(shutdown_rx).await.unwrap();
+        });
+
+        let client = Client::builder(url)
+            .access_token("access_token")
+            .build()
+            .unwrap();
+        (client, shutdown_tx)
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_client_register_and_list(db: DbPool) -> Result<(), TdError> {
+        seed_collection(&db, &CollectionName::try_from("joaquin")?, &UserId::admin()).await;
+        let (client, shutdown) = client(db).await;
+
+        let collection = CollectionParam::builder()
+            .try_collection("joaquin")?
+            .build()?;
+        let register = function_register("function_foo", &[], &[], &[])?;
+        let function = client.register_function(&collection, &register).await?;
+        assert_eq!(function.name, register.name);
+        assert_eq!(function.collection, CollectionName::try_from("joaquin")?);
+
+        let list = client.list_functions(&ListParams::default()).await?;
+        assert_eq!(list.len, 1);
+        assert_eq!(list.data[0].id, function.id);
+        assert_eq!(list.data[0].name, register.name);
+
+        shutdown.send(()).unwrap();
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_client_error_response(db: DbPool) -> Result<(), TdError> {
+        let (client, shutdown) = client(db).await;
+
+        let list_params = ListParams::builder()
+            .filter(vec!["bogus:eq:value".to_string()])
+            .build()?;
+        let err = client.list_functions(&list_params).await.unwrap_err();
+        assert_eq!(err.code(), "ListError::0003");
+        assert_eq!(err.domain(), "ListError");
+        assert_eq!(err.api_error(), ApiError::InputError);
+        assert!(err.to_string().contains("Undefined filter field 'bogus'"));
+
+        shutdown.send(()).unwrap();
+        Ok(())
+    }
 }
@@ -21,7 +21,8 @@ mod routes {
     };
     use td_objects::rest_urls::{
         CREATE_INTER_COLLECTION_PERMISSION, CollectionParam, DELETE_INTER_COLLECTION_PERMISSION,
-        InterCollectionPermissionParam, LIST_INTER_COLLECTION_PERMISSIONS,
+        InterCollectionPermissionParam, InterCollectionPermissionToParam,
+        LIST_INTER_COLLECTION_PERMISSIONS, REVOKE_INTER_COLLECTION_PERMISSION,
     };
     use td_services::inter_coll_permission::services::InterCollectionPermissionServices;
     use tower::ServiceExt;
@@ -53,6 +54,18 @@ mod routes {
         Ok(DeleteStatus::OK(response))
     }
 
+    #[apiserver_path(method = delete, path = REVOKE_INTER_COLLECTION_PERMISSION, tag = INTER_COLLECTION_PERMISSIONS_TAG)]
+    #[doc = "Revoke the inter collection permission of a collection to read from another collection"]
+    pub async fn revoke(
+        State(state): State<Arc<InterCollectionPermissionServices>>,
+        Extension(context): Extension<RequestContext>,
+        Path(param): Path<InterCollectionPermissionToParam>,
+    ) -> Result<DeleteStatus<NoContent>, ErrorStatus> {
+        let request = context.delete(param);
+        let response = state.revoke.service().await.oneshot(request).await?;
+        Ok(DeleteStatus::OK(response))
+    }
+
     #[apiserver_path(method = get, path = LIST_INTER_COLLECTION_PERMISSIONS, tag = INTER_COLLECTION_PERMISSIONS_TAG)]
     #[doc = "List permissions"]
     pub async fn list(
@@ -10,13 +10,13 @@ mod routes {
     use axum::extract::State;
     use std::sync::Arc;
     use ta_apiserver::status::error_status::ErrorStatus;
-    use ta_apiserver::status::ok_status::GetStatus;
+    use ta_apiserver::status::ok_status::{GetStatus, UpdateStatus};
     use ta_services::service::TdService;
     use td_apiforge::apiserver_path;
     use td_objects::dxo::crudl::RequestContext;
     use td_objects::dxo::runtime_info::RuntimeInfo;
-    use td_objects::dxo::system::ApiStatus;
-    use td_objects::rest_urls::{RUNTIME_INFO, SERVER_STATUS};
+    use td_objects::dxo::system::{ApiStatus, AuthzCacheStatus};
+    use td_objects::rest_urls::{AUTHZ_CACHE_REFRESH, RUNTIME_INFO, SERVER_STATUS};
     use td_services::execution::services::ExecutionServices;
     use td_services::system::services::SystemServices;
     use tower::ServiceExt;
@@ -42,6 +42,22 @@ mod routes {
         let response = executions.info.service().await.oneshot(request).await?;
         Ok(GetStatus::OK(response))
     }
+
+    #[apiserver_path(method = post, path = AUTHZ_CACHE_REFRESH, tag = STATUS_TAG)]
+    #[doc = "Refresh the authorization cache, returning the roles and permissions now cached"]
+    pub async fn refresh_authz(
+        State(status_state): State<Arc<SystemServices>>,
+        Extension(context): Extension<RequestContext>,
+    ) -> Result<UpdateStatus<AuthzCacheStatus>, ErrorStatus> {
+        let request = context.update((), ());
+        let response = status_state
+            .refresh_authz
+            .service()
+            .await
+            .oneshot(request)
+            .await?;
+        Ok(UpdateStatus::OK(response))
+    }
 }
 
 #[cfg(test)]
@@ -55,9 +71,14 @@ mod tests {
     use ta_services::factory::ServiceFactory;
     use td_database::sql::DbPool;
     use td_objects::dxo::crudl::RequestContext;
-    use td_objects::dxo::system::{ApiStatus, HealthStatus};
-    use td_objects::rest_urls::SERVER_STATUS;
-    use td_objects::types::basic::{AccessTokenId, RoleId, UserId};
+    use td_objects::dxo::system::{ApiStatus, AuthzCacheStatus, HealthStatus};
+    use td_objects::rest_urls::{AUTHZ_CACHE_REFRESH, SERVER_STATUS};
+    use td_objects::test_utils::seed_permission::seed_permission;
+    use td_objects::test_utils::seed_role::seed_role;
+    use td_objects::tower_service::authz::AuthzContextT;
+    use td_objects::types::basic::{
+        AccessTokenId, Description, PermissionType, RoleId, RoleName, UserId,
+    };
     use td_services::{Context, Services};
     use tower::ServiceExt;
 
@@ -94,4 +115,67 @@ mod tests {
         assert!(matches!(database_status.status, HealthStatus::OK));
         assert!(database_status.latency_as_nanos > 0);
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_refresh_authz(db: DbPool) {
+        let context = Context::with_defaults(db.clone());
+        let authz_context = context.auth_context.clone();
+        let router: Router = ServerStatusRouter::router(Services::build(&context)).into();
+        let router = router.layer(Extension(RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )));
+
+        let role = seed_role(
+            &db,
+            RoleName::try_from("r0").unwrap(),
+            Description::try_from("role").unwrap(),
+        )
+        .await;
+        let mut conn = db.acquire().await.unwrap();
+        assert!(
+            authz_context
+                .role_permissions(&mut conn, &role.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // edit permissions directly in the database, the cache is not aware of it
+        seed_permission(&db, PermissionType::CollectionDev, None, None, &role).await;
+        assert!(
+            authz_context
+                .role_permissions(&mut conn, &role.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(AUTHZ_CACHE_REFRESH)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let status: AuthzCacheStatus =
+            serde_json::from_value(body["data"].clone()).expect("Failed to parse authz status");
+        assert!(status.roles > 0);
+        assert!(status.permissions > 0);
+
+        let permissions = authz_context
+            .role_permissions(&mut conn, &role.id)
+            .await
+            .unwrap();
+        assert_eq!(permissions.unwrap().len(), 1);
+    }
 }
@@ -12,6 +12,7 @@ use td_database::sql::DbPool;
 use td_objects::sql::DaoQueries;
 use td_objects::types::addresses::InternalServerAddresses;
 use td_services::SchedulerContext;
+use td_services::function_run::webhook::WebhookClient;
 use td_services::scheduler::services::ScheduleServices;
 use td_storage::Storage;
 use td_tower::service_provider::{IntoServiceProvider, ServiceProvider};
@@ -21,6 +22,8 @@ use tracing::{Instrument, Level, debug, error, span, trace};
 pub struct Scheduler {
     request_service: ServiceProvider<(), (), BoxError>,
     commit_service: ServiceProvider<(), (), BoxError>,
+    webhooks_service: ServiceProvider<(), (), BoxError>,
+    bundles_service: ServiceProvider<(), (), BoxError>,
 }
 
 impl Scheduler {
@@ -36,6 +39,18 @@ impl Scheduler {
         Ok(())
     }
 
+    async fn webhooks(&self) -> Result<(), BoxError> {
+        let service = self.webhooks_service.make().await;
+        let _ = service.oneshot(()).await?;
+        Ok(())
+    }
+
+    async fn bundles(&self) -> Result<(), BoxError> {
+        let service = self.bundles_service.make().await;
+        let _ = service.oneshot(()).await?;
+        Ok(())
+    }
+
     pub async fn run(
         self,
         shutdown: tokio::sync::watch::Receiver<()>,
@@ -84,7 +99,52 @@ impl Scheduler {
         }
         .instrument(log_span.clone());
 
-        tokio::join!(request_future, commit_future);
+        let scheduler = this.clone();
+        let mut shutdown_clone = shutdown.clone();
+        let webhooks_future = async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_clone.changed() => {
+                        debug!("Webhooks scheduler loop shutting down...");
+                        break;
+                    }
+                    res = scheduler.webhooks() => {
+                        match res {
+                            Ok(_) => trace!("Webhooks scheduler executed successfully"),
+                            Err(e) => error!("Error executing webhooks scheduler: {}", e),
+                        }
+                    }
+                }
+            }
+        }
+        .instrument(log_span.clone());
+
+        let scheduler = this.clone();
+        let mut shutdown_clone = shutdown.clone();
+        let bundles_future = async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_clone.changed() => {
+                        debug!("Bundles scheduler loop shutting down...");
+                        break;
+                    }
+                    res = scheduler.bundles() => {
+                        match res {
+                            Ok(_) => trace!("Bundles scheduler executed successfully"),
+                            Err(e) => error!("Error executing bundles scheduler: {}", e),
+                        }
+                    }
+                }
+            }
+        }
+        .instrument(log_span.clone());
+
+        tokio::join!(
+            request_future,
+            commit_future,
+            webhooks_future,
+            bundles_future
+        );
         Ok(())
     }
 }
@@ -101,6 +161,7 @@ impl SchedulerBuilder {
         storage: Arc<Storage>,
         worker_queue: Arc<FileWorkerMessageQueue>,
         internal_addresses: Arc<InternalServerAddresses>,
+        webhook_client: Arc<WebhookClient>,
     ) -> Self {
         let context = SchedulerContext {
             db,
@@ -108,6 +169,7 @@ impl SchedulerBuilder {
             storage,
             worker_queue,
             internal_addresses,
+            webhook_client,
         };
 
         let services = ScheduleServices::build(&context);
@@ -134,9 +196,33 @@ impl SchedulerBuilder {
             .service(self.services.commit().service().await)
             .into_service_provider();
 
+        // Webhooks are delivered outside the execution loops, as endpoints can be slow to respond.
+        const WEBHOOKS_CHECK_FREQUENCY: Duration = Duration::from_secs(1);
+
+        let webhooks_service = ServiceBuilder::new()
+            .buffer(1)
+            .concurrency_limit(1)
+            .rate_limit(1, WEBHOOKS_CHECK_FREQUENCY)
+            .timeout(Duration::from_secs(300))
+            .service(self.services.webhooks().service().await)
+            .into_service_provider();
+
+        // Released function bundle blobs are purged from storage once their release is committed.
+        const BUNDLES_CHECK_FREQUENCY: Duration = Duration::from_secs(60);
+
+        let bundles_service = ServiceBuilder::new()
+            .buffer(1)
+            .concurrency_limit(1)
+            .rate_limit(1, BUNDLES_CHECK_FREQUENCY)
+            .timeout(Duration::from_secs(300))
+            .service(self.services.bundles().service().await)
+            .into_service_provider();
+
         Scheduler {
             request_service,
             commit_service,
+            webhooks_service,
+            bundles_service,
         }
     }
 }
@@ -2,34 +2,56 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use crate::sql::SqlAuthzDataProvider;
+use crate::sql::{SqlAuthzDataProvider, latest_permission_change, record_permission_change};
 use async_trait::async_trait;
 use sqlx::SqliteConnection;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use td_common::provider::{CachedProvider, Provider};
 use td_error::TdError;
+use td_objects::dxo::system::AuthzCacheStatus;
 use td_objects::tower_service::authz::{AuthzContextT, NoPermissions, Permission};
-use td_objects::types::basic::{CollectionId, RoleId, ToCollectionId};
+use td_objects::types::basic::{
+    AccessTokenId, CollectionId, PermissionChangeToken, RoleId, ToCollectionId,
+};
 use td_tower::extractors::{Connection, IntoMutSqlConnection, SrvCtx};
+use tokio::sync::Mutex;
+use tracing::debug;
 
 mod sql;
 
 /// Authorization context for permissions check
 pub type AuthzContext = AuthzContextImplWithCache<'static>;
 
+/// Interval permission changes, made by any node, are polled at to invalidate the cache.
+pub const PERMISSION_CHANGES_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Layer to refresh the authz context. It must be called every time
 /// permissions and inter_collection_permissions change.
+///
+/// The change is recorded, so the authz contexts of other nodes invalidate their cache too.
 pub async fn refresh_authz_context(
     SrvCtx(context): SrvCtx<AuthzContext>,
     Connection(conn): Connection,
 ) -> Result<(), TdError> {
     let mut conn_ = conn.lock().await;
     let conn = conn_.get_mut_connection()?;
+    record_permission_change(conn).await?;
     context.refresh(conn).await?;
     Ok(())
 }
 
+/// Layer returning the roles and permissions cached by the authz context.
+pub async fn authz_cache_status(
+    SrvCtx(context): SrvCtx<AuthzContext>,
+    Connection(conn): Connection,
+) -> Result<AuthzCacheStatus, TdError> {
+    let mut conn_ = conn.lock().await;
+    let conn = conn_.get_mut_connection()?;
+    context.cache_status(conn).await
+}
+
 #[derive(Debug)]
 struct AuthzData {
     permissions: HashMap<RoleId, Arc<Vec<Permission>>>,
@@ -39,17 +61,72 @@ struct AuthzData {
     // Given a ToCollectionId, which CollectionIds it has read to
     inter_collections_permissions_key_can_read_value:
         HashMap<ToCollectionId, Arc<Vec<CollectionId>>>,
+    // Given an AccessTokenId of a scoped API key, its scope permissions
+    access_token_permissions: HashMap<AccessTokenId, Arc<Vec<Permission>>>,
 }
 
 pub struct AuthzContextImplWithCache<'a> {
     provider: CachedProvider<'a, AuthzData, &'a mut SqliteConnection, SqlAuthzDataProvider>,
+    poll_interval: Duration,
+    poll: Mutex<PermissionChangesPoll>,
+}
+
+#[derive(Debug, Default)]
+struct PermissionChangesPoll {
+    /// The latest permission change seen, `None` if permissions never changed.
+    token: Option<PermissionChangeToken>,
+    /// When permission changes were last polled, `None` if never.
+    polled_on: Option<Instant>,
 }
 
 impl Default for AuthzContextImplWithCache<'_> {
     fn default() -> Self {
+        Self::with_poll_interval(PERMISSION_CHANGES_POLL_INTERVAL)
+    }
+}
+
+impl AuthzContextImplWithCache<'_> {
+    /// Creates an authz context polling permission changes at the given interval.
+    pub fn with_poll_interval(poll_interval: Duration) -> Self {
         let provider = SqlAuthzDataProvider;
         let provider = CachedProvider::cache(provider);
-        Self { provider }
+        Self {
+            provider,
+            poll_interval,
+            poll: Mutex::new(PermissionChangesPoll::default()),
+        }
+    }
+
+    /// Returns the roles and permissions in the cache, loading it if necessary.
+    pub async fn cache_status(
+        &self,
+        conn: &mut SqliteConnection,
+    ) -> Result<AuthzCacheStatus, TdError> {
+        let data = self.provider.get(conn).await?;
+        Ok(AuthzCacheStatus {
+            roles: data.permissions.len(),
+            permissions: data.permissions.values().map(|p| p.len()).sum(),
+        })
+    }
+
+    /// Invalidates the cache if permissions changed since the last poll, polling at most once
+    /// per poll interval.
+    async fn poll_changes(&self, conn: &mut SqliteConnection) -> Result<(), TdError> {
+        let mut poll = self.poll.lock().await;
+        if poll
+            .polled_on
+            .is_some_and(|polled_on| polled_on.elapsed() < self.poll_interval)
+        {
+            return Ok(());
+        }
+        let token = latest_permission_change(conn).await?;
+        if token != poll.token {
+            debug!("Permissions changed, invalidating the authz cache");
+            self.provider.purge(conn).await?;
+            poll.token = token;
+        }
+        poll.polled_on = Some(Instant::now());
+        Ok(())
     }
 }
 
@@ -60,6 +137,7 @@ impl AuthzContextT for AuthzContextImplWithCache<'_> {
         conn: &mut SqliteConnection,
         role: &RoleId,
     ) -> Result<Option<Arc<Vec<Permission>>>, TdError> {
+        self.poll_changes(conn).await?;
         Ok(self
             .provider
             .get(conn)
@@ -69,11 +147,27 @@ impl AuthzContextT for AuthzContextImplWithCache<'_> {
             .cloned())
     }
 
+    async fn access_token_permissions(
+        &self,
+        conn: &mut SqliteConnection,
+        access_token: &AccessTokenId,
+    ) -> Result<Option<Arc<Vec<Permission>>>, TdError> {
+        self.poll_changes(conn).await?;
+        Ok(self
+            .provider
+            .get(conn)
+            .await?
+            .access_token_permissions
+            .get(access_token)
+            .cloned())
+    }
+
     async fn inter_collections_permissions_value_can_read_key(
         &self,
         conn: &mut SqliteConnection,
         collection_id: &CollectionId,
     ) -> Result<Option<Arc<Vec<ToCollectionId>>>, TdError> {
+        self.poll_changes(conn).await?;
         Ok(self
             .provider
             .get(conn)
@@ -88,6 +182,7 @@ impl AuthzContextT for AuthzContextImplWithCache<'_> {
         conn: &mut SqliteConnection,
         collection_id: &ToCollectionId,
     ) -> Result<Option<Arc<Vec<CollectionId>>>, TdError> {
+        self.poll_changes(conn).await?;
         Ok(self
             .provider
             .get(conn)
@@ -115,3 +210,64 @@ pub type Authz<
     C6 = NoPermissions,
     C7 = NoPermissions,
 > = td_objects::tower_service::authz::Authz<AuthzContext, C1, C2, C3, C4, C5, C6, C7>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use td_database::sql::DbPool;
+    use td_objects::test_utils::seed_permission::seed_permission;
+    use td_objects::test_utils::seed_role::seed_role;
+    use td_objects::types::basic::{Description, PermissionType, RoleName};
+
+    #[td_test::test(sqlx(migrator = td_schema::schema()))]
+    #[tokio::test]
+    async fn test_permission_changes_across_contexts(db: DbPool) -> Result<(), TdError> {
+        let poll_interval = Duration::from_millis(200);
+        // two nodes sharing the db
+        let node_a = AuthzContext::with_poll_interval(poll_interval);
+        let node_b = AuthzContext::with_poll_interval(poll_interval);
+
+        let role = seed_role(
+            &db,
+            RoleName::try_from("r0")?,
+            Description::try_from("role")?,
+        )
+        .await;
+        let mut conn = db.acquire().await.unwrap();
+        assert!(
+            node_a
+                .role_permissions(&mut conn, &role.id)
+                .await?
+                .is_none()
+        );
+        assert!(
+            node_b
+                .role_permissions(&mut conn, &role.id)
+                .await?
+                .is_none()
+        );
+
+        // change permissions on node a, as refresh_authz_context does
+        seed_permission(&db, PermissionType::CollectionDev, None, None, &role).await;
+        record_permission_change(&mut conn).await?;
+        node_a.refresh(&mut conn).await?;
+        assert!(
+            node_a
+                .role_permissions(&mut conn, &role.id)
+                .await?
+                .is_some()
+        );
+
+        // node b keeps its cache until the next poll tick
+        assert!(
+            node_b
+                .role_permissions(&mut conn, &role.id)
+                .await?
+                .is_none()
+        );
+        tokio::time::sleep(poll_interval).await;
+        let permissions = node_b.role_permissions(&mut conn, &role.id).await?;
+        assert_eq!(permissions.unwrap().len(), 1);
+        Ok(())
+    }
+}
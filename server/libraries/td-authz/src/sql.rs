@@ -9,12 +9,44 @@ use sqlx::SqliteConnection;
 use std::collections::HashMap;
 use std::sync::Arc;
 use td_error::TdError;
+use td_objects::dxo::api_key::{ApiKeyDB, ApiKeyScopeDB};
 use td_objects::dxo::crudl::handle_sql_err;
 use td_objects::dxo::inter_collection_permission::InterCollectionPermissionDB;
-use td_objects::dxo::permission::PermissionDB;
-use td_objects::sql::{DaoQueries, SelectBy};
-use td_objects::tower_service::authz::{AuthzEntity, Permission};
-use td_objects::types::basic::{CollectionId, PermissionType, RoleId, ToCollectionId};
+use td_objects::dxo::permission::{PermissionChangeDB, PermissionDB};
+use td_objects::sql::{DaoQueries, Insert, SelectBy};
+use td_objects::tower_service::authz::Permission;
+use td_objects::types::DataAccessObject;
+use td_objects::types::basic::{
+    AccessTokenId, AtTime, CollectionId, PermissionChangeToken, RoleId, ToCollectionId,
+};
+use tracing::warn;
+
+/// Records a permission change.
+pub(crate) async fn record_permission_change(conn: &mut SqliteConnection) -> Result<(), TdError> {
+    let change = PermissionChangeDB::builder()
+        .changed_on(AtTime::now())
+        .build()?;
+    DaoQueries::default()
+        .insert(&change)?
+        .build()
+        .execute(conn)
+        .await
+        .map_err(handle_sql_err)?;
+    Ok(())
+}
+
+/// Returns the token of the latest permission change, `None` if permissions never changed.
+pub(crate) async fn latest_permission_change(
+    conn: &mut SqliteConnection,
+) -> Result<Option<PermissionChangeToken>, TdError> {
+    let token: Option<i64> = sqlx::QueryBuilder::new("SELECT MAX(id) FROM ")
+        .push(PermissionChangeDB::sql_table())
+        .build_query_scalar()
+        .fetch_one(conn)
+        .await
+        .map_err(handle_sql_err)?;
+    token.map(PermissionChangeToken::try_from).transpose()
+}
 
 /// Provider that gets permissions and inter-permissions mapping from the database on every `get` call.
 pub struct SqlAuthzDataProvider;
@@ -22,23 +54,7 @@ pub struct SqlAuthzDataProvider;
 impl SqlAuthzDataProvider {
     //TODO: This try_to_permission should be converted into a `TryFrom<&PermissionDB> for Permission`
     fn try_to_permission(perm_db: &PermissionDB) -> Result<Permission, TdError> {
-        fn authz_entity(perm_db: &PermissionDB) -> Result<AuthzEntity<CollectionId>, TdError> {
-            if perm_db.entity_id.is_all_entities() {
-                Ok(AuthzEntity::All)
-            } else {
-                Ok(AuthzEntity::On(CollectionId::try_from(&perm_db.entity_id)?))
-            }
-        }
-
-        let perm = match &perm_db.permission_type {
-            PermissionType::SysAdmin => Permission::SysAdmin,
-            PermissionType::SecAdmin => Permission::SecAdmin,
-            PermissionType::CollectionAdmin => Permission::CollectionAdmin(authz_entity(perm_db)?),
-            PermissionType::CollectionDev => Permission::CollectionDev(authz_entity(perm_db)?),
-            PermissionType::CollectionExec => Permission::CollectionExec(authz_entity(perm_db)?),
-            PermissionType::CollectionRead => Permission::CollectionRead(authz_entity(perm_db)?),
-        };
-        Ok(perm)
+        Permission::try_from_entity(&perm_db.permission_type, &perm_db.entity_id)
     }
 
     async fn get_permissions<'a>(
@@ -61,6 +77,49 @@ impl SqlAuthzDataProvider {
         Ok(role_permissions_map)
     }
 
+    /// Scoped API keys get their scope permissions, if any. Unscoped keys and other access
+    /// tokens are not in the map, and have all the permissions of their role.
+    async fn get_access_token_permissions<'a>(
+        &'a self,
+        conn: &'a mut SqliteConnection,
+    ) -> Result<HashMap<AccessTokenId, Arc<Vec<Permission>>>, TdError> {
+        let api_keys: Vec<ApiKeyDB> = DaoQueries::default()
+            .select_by::<ApiKeyDB>(&())?
+            .build_query_as()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+        let scopes: Vec<ApiKeyScopeDB> = DaoQueries::default()
+            .select_by::<ApiKeyScopeDB>(&())?
+            .build_query_as()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+
+        // Scoped keys without scopes have no permissions.
+        let mut access_token_permissions_map: HashMap<_, Vec<Permission>> = api_keys
+            .iter()
+            .filter(|api_key| *api_key.scoped)
+            .map(|api_key| Ok((AccessTokenId::try_from(api_key.id)?, Vec::new())))
+            .collect::<Result<_, TdError>>()?;
+        for scope in scopes {
+            // Keys with scopes are scoped, even if not marked. Scopes that cannot be read grant
+            // nothing.
+            let permissions = access_token_permissions_map
+                .entry(AccessTokenId::try_from(scope.api_key_id)?)
+                .or_default();
+            match Permission::try_from_entity(&scope.permission_type, &scope.entity_id) {
+                Ok(permission) => permissions.push(permission),
+                Err(e) => warn!("Ignoring API key scope {}: {e}", scope.id),
+            }
+        }
+        let access_token_permissions_map = access_token_permissions_map
+            .into_iter()
+            .map(|(access_token, perms)| (access_token, Arc::new(perms)))
+            .collect();
+        Ok(access_token_permissions_map)
+    }
+
     async fn get_inter_collection_permissions<'a>(
         &'a self,
         conn: &'a mut SqliteConnection,
@@ -86,6 +145,7 @@ impl SqlAuthzDataProvider {
 impl<'a> Provider<'a, AuthzData, &'a mut SqliteConnection> for SqlAuthzDataProvider {
     async fn get(&'a self, context: &'a mut SqliteConnection) -> Result<Arc<AuthzData>, TdError> {
         let permissions = self.get_permissions(context).await?;
+        let access_token_permissions = self.get_access_token_permissions(context).await?;
         let inter_collections_permissions_value_can_read_key =
             self.get_inter_collection_permissions(context).await?;
         let inter_collections_permissions_key_can_read_value =
@@ -107,6 +167,7 @@ impl<'a> Provider<'a, AuthzData, &'a mut SqliteConnection> for SqlAuthzDataProvi
             permissions,
             inter_collections_permissions_value_can_read_key,
             inter_collections_permissions_key_can_read_value,
+            access_token_permissions,
         };
         Ok(Arc::new(authz_data))
     }
@@ -114,13 +175,20 @@ impl<'a> Provider<'a, AuthzData, &'a mut SqliteConnection> for SqlAuthzDataProvi
 
 #[cfg(test)]
 mod tests {
-    use crate::sql::SqlAuthzDataProvider;
+    use crate::sql::{SqlAuthzDataProvider, latest_permission_change, record_permission_change};
     use td_common::provider::Provider;
     use td_database::sql::DbPool;
     use td_error::TdError;
+    use td_objects::dxo::api_key::{ApiKeyDB, ApiKeyDBBuilder, ApiKeyScopeDB};
+    use td_objects::dxo::crudl::RequestContext;
+    use td_objects::sql::{DaoQueries, Insert};
     use td_objects::test_utils::seed_collection::seed_collection;
     use td_objects::test_utils::seed_inter_collection_permission::seed_inter_collection_permission;
-    use td_objects::types::basic::{CollectionName, RoleId, ToCollectionId, UserId};
+    use td_objects::tower_service::authz::{AuthzEntity, Permission};
+    use td_objects::types::basic::{
+        AccessTokenId, ApiKeyHash, ApiKeyName, ApiKeyPrefix, ApiKeyScoped, CollectionName,
+        EntityId, PermissionType, RoleId, ToCollectionId, UserId,
+    };
 
     #[td_test::test(sqlx(migrator = td_schema::schema()))]
     #[tokio::test]
@@ -136,6 +204,90 @@ mod tests {
         Ok(())
     }
 
+    #[td_test::test(sqlx(migrator = td_schema::schema()))]
+    #[tokio::test]
+    async fn test_get_access_token_permissions(db: DbPool) -> Result<(), TdError> {
+        let c0 = seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+
+        let request_context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        let mut builder = ApiKeyDB::builder();
+        builder
+            .name(ApiKeyName::try_from("ci")?)
+            .prefix(ApiKeyPrefix::try_from("0123abcd")?)
+            .hash(ApiKeyHash::try_from("hash")?)
+            .scoped(ApiKeyScoped::from(true));
+        let api_key = ApiKeyDBBuilder::try_from((&request_context, builder))?.build()?;
+        let scope = ApiKeyScopeDB::builder()
+            .api_key_id(api_key.id)
+            .permission_type(PermissionType::CollectionRead)
+            .entity_type(PermissionType::CollectionRead.on_entity_type())
+            .entity_id(EntityId::try_from(c0.id)?)
+            .build()?;
+        let queries = DaoQueries::default();
+        queries
+            .insert(&api_key)?
+            .build()
+            .execute(&db)
+            .await
+            .unwrap();
+        queries.insert(&scope)?.build().execute(&db).await.unwrap();
+
+        let provider = SqlAuthzDataProvider;
+        let permissions = provider
+            .get_access_token_permissions(&mut db.acquire().await.unwrap())
+            .await?;
+        assert_eq!(permissions.len(), 1);
+        assert_eq!(
+            permissions
+                .get(&AccessTokenId::try_from(api_key.id)?)
+                .unwrap()
+                .as_slice(),
+            &[Permission::CollectionRead(AuthzEntity::On(c0.id))]
+        );
+        Ok(())
+    }
+
+    #[td_test::test(sqlx(migrator = td_schema::schema()))]
+    #[tokio::test]
+    async fn test_get_access_token_permissions_scope_absent(db: DbPool) -> Result<(), TdError> {
+        let request_context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        let queries = DaoQueries::default();
+        let mut api_keys = vec![];
+        for (prefix, scoped) in [("0123abcd", true), ("4567abcd", false)] {
+            let mut builder = ApiKeyDB::builder();
+            builder
+                .name(ApiKeyName::try_from("ci")?)
+                .prefix(ApiKeyPrefix::try_from(prefix)?)
+                .hash(ApiKeyHash::try_from("hash")?)
+                .scoped(ApiKeyScoped::from(scoped));
+            let api_key = ApiKeyDBBuilder::try_from((&request_context, builder))?.build()?;
+            queries
+                .insert(&api_key)?
+                .build()
+                .execute(&db)
+                .await
+                .unwrap();
+            api_keys.push(api_key);
+        }
+
+        let provider = SqlAuthzDataProvider;
+        let permissions = provider
+            .get_access_token_permissions(&mut db.acquire().await.unwrap())
+            .await?;
+        // the scoped key without scopes has no permissions, the unscoped one is not restricted
+        assert_eq!(permissions.len(), 1);
+        assert!(
+            permissions
+                .get(&AccessTokenId::try_from(api_keys[0].id)?)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(!permissions.contains_key(&AccessTokenId::try_from(api_keys[1].id)?));
+        Ok(())
+    }
+
     #[td_test::test(sqlx(migrator = td_schema::schema()))]
     #[tokio::test]
     async fn test_get_inter_collection_permissions(db: DbPool) -> Result<(), TdError> {
@@ -157,6 +309,20 @@ mod tests {
         Ok(())
     }
 
+    #[td_test::test(sqlx(migrator = td_schema::schema()))]
+    #[tokio::test]
+    async fn test_permission_changes(db: DbPool) -> Result<(), TdError> {
+        let mut conn = db.acquire().await.unwrap();
+        assert_eq!(latest_permission_change(&mut conn).await?, None);
+
+        record_permission_change(&mut conn).await?;
+        let first = latest_permission_change(&mut conn).await?.unwrap();
+        record_permission_change(&mut conn).await?;
+        let second = latest_permission_change(&mut conn).await?.unwrap();
+        assert!(*second > *first);
+        Ok(())
+    }
+
     #[td_test::test(sqlx(migrator = td_schema::schema()))]
     #[tokio::test]
     async fn test_provider_get(db: DbPool) -> Result<(), TdError> {
@@ -0,0 +1,461 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Typed client of the apiserver REST API.
+//!
+//! Requests and responses are the same DTOs used by the apiserver, and error responses are mapped
+//! back into [`TdError`]s with the code and API error of the server error.
+//!
+//! Requests rejected with `429 Too Many Requests` or `503 Service Unavailable` carrying a
+//! `Retry-After` header (in seconds) are retried after the indicated delay, capped to the
+//! configured maximum delay, up to the configured number of retries. Only idempotent requests
+//! (`GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS`) and requests carrying an `Idempotency-Key` header
+//! are retried, other requests return the error response of the server.
+
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue, InvalidHeaderValue, RETRY_AFTER,
+};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use td_error::{ApiError, InlineError, TdError, td_error};
+use td_objects::dxo::crudl::{ListParams, ListResponse};
+use td_objects::dxo::function::{Function, FunctionRegister};
+use td_objects::rest_urls::reverse::reverse_url;
+use td_objects::rest_urls::{BASE_URL_V1, CollectionParam, FUNCTION_CREATE, FUNCTION_LIST};
+
+#[td_error]
+pub enum ClientError {
+    #[error("Invalid access token: {0}")]
+    InvalidAccessToken(#[source] InvalidHeaderValue) = 0,
+    #[error("Could not serialize request to '{0}': {1}")]
+    CouldNotSerializeRequest(String, String) = 1,
+
+    #[error("Could not build HTTP client: {0}")]
+    CouldNotBuildClient(#[source] reqwest::Error) = 5000,
+    #[error("Could not send request to '{0}': {1}")]
+    CouldNotSendRequest(String, #[source] reqwest::Error) = 5001,
+    #[error("Invalid response from '{0}' with status '{1}': {2}")]
+    InvalidResponse(String, StatusCode, String) = 5002,
+    #[error("Request to '{0}' still rejected with status '{1}' after {2} retries")]
+    RetriesExhausted(String, StatusCode, u32) = 5003,
+}
+
+/// Default number of retries of requests rejected with a `Retry-After` header.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default maximum delay before retrying a request, longer `Retry-After` delays are capped to it.
+pub const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Header with the idempotency key of a request, making non idempotent requests retryable.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Builder for [`Client`].
+#[derive(Debug)]
+pub struct ClientBuilder {
+    url: String,
+    access_token: Option<String>,
+    timeout: Option<Duration>,
+    max_retries: u32,
+    max_retry_delay: Duration,
+}
+
+impl ClientBuilder {
+    /// Creates a builder for a client of the apiserver at the given URL (scheme, host and port).
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            access_token: None,
+            timeout: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+        }
+    }
+
+    /// Access token sent as bearer token with every request.
+    pub fn access_token(mut self, access_token: impl Into<String>) -> Self {
+        self.access_token = Some(access_token.into());
+        self
+    }
+
+    /// Timeout of each request, from connecting until the response body is read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum number of retries of a request rejected with `429` or `503` and a `Retry-After`
+    /// header. Zero disables retries.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Maximum delay before retrying a request, longer `Retry-After` delays are capped to it.
+    pub fn max_retry_delay(mut self, max_retry_delay: Duration) -> Self {
+        self.max_retry_delay = max_retry_delay;
+        self
+    }
+
+    pub fn build(self) -> Result<Client, TdError> {
+        let mut headers = HeaderMap::new();
+        if let Some(access_token) = self.access_token {
+            let mut authorization = HeaderValue::from_str(&format!("Bearer {access_token}"))
+                .map_err(ClientError::InvalidAccessToken)?;
+            authorization.set_sensitive(true);
+            headers.insert(AUTHORIZATION, authorization);
+        }
+
+        let mut http = reqwest::Client::builder().default_headers(headers);
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        let http = http.build().map_err(ClientError::CouldNotBuildClient)?;
+
+        Ok(Client {
+            http,
+            base_url: format!("{}{}", self.url.trim_end_matches('/'), BASE_URL_V1),
+            max_retries: self.max_retries,
+            max_retry_delay: self.max_retry_delay,
+        })
+    }
+}
+
+/// Client of the apiserver REST API. Cloning it is cheap, clones share the connection pool.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    max_retries: u32,
+    max_retry_delay: Duration,
+}
+
+/// Successful apiserver response, only its data is used.
+#[derive(Deserialize)]
+struct DataResponse<T> {
+    data: T,
+}
+
+/// Error apiserver response.
+#[derive(Deserialize)]
+struct ErrorResponse {
+    code: String,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+impl Client {
+    pub fn builder(url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder::new(url)
+    }
+
+    /// Registers a function in a collection.
+    pub async fn register_function(
+        &self,
+        collection: &CollectionParam,
+        function: &FunctionRegister,
+    ) -> Result<Function, TdError> {
+        let path = reverse_url(FUNCTION_CREATE, collection)?;
+        self.send(Method::POST, &path, Some(function), None).await
+    }
+
+    /// Registers a function in a collection with an idempotency key. The server runs it once
+    /// per key, so it is retried like idempotent requests.
+    pub async fn register_function_idempotent(
+        &self,
+        collection: &CollectionParam,
+        function: &FunctionRegister,
+        idempotency_key: &str,
+    ) -> Result<Function, TdError> {
+        let path = reverse_url(FUNCTION_CREATE, collection)?;
+        self.send(Method::POST, &path, Some(function), Some(idempotency_key))
+            .await
+    }
+
+    /// Lists the functions of all collections.
+    pub async fn list_functions(
+        &self,
+        list_params: &ListParams,
+    ) -> Result<ListResponse<Function>, TdError> {
+        let query = serde_html_form::to_string(list_params).map_err(|e| {
+            ClientError::CouldNotSerializeRequest(FUNCTION_LIST.to_string(), e.to_string())
+        })?;
+        let path = format!("{FUNCTION_LIST}?{query}");
+        self.send(Method::GET, &path, None::<&()>, None).await
+    }
+
+    async fn send<B, T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+        idempotency_key: Option<&str>,
+    ) -> Result<T, TdError>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        let url = format!("{}{path}", self.base_url);
+        let body = body
+            .map(serde_json::to_vec)
+            .transpose()
+            .map_err(|e| ClientError::CouldNotSerializeRequest(url.clone(), e.to_string()))?;
+        let idempotency_key = idempotency_key
+            .map(HeaderValue::from_str)
+            .transpose()
+            .map_err(|e| ClientError::CouldNotSerializeRequest(url.clone(), e.to_string()))?;
+        let max_retries = if is_idempotent(&method) || idempotency_key.is_some() {
+            self.max_retries
+        } else {
+            0
+        };
+
+        let mut retries = 0;
+        let response = loop {
+            let mut request = self.http.request(method.clone(), &url);
+            if let Some(body) = &body {
+                request = request
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body.clone());
+            }
+            if let Some(idempotency_key) = &idempotency_key {
+                request = request.header(IDEMPOTENCY_KEY_HEADER, idempotency_key.clone());
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| ClientError::CouldNotSendRequest(url.clone(), e))?;
+
+            match retry_after(&response) {
+                Some(delay) if retries < max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(delay.min(self.max_retry_delay)).await;
+                }
+                Some(_) if max_retries > 0 => Err(ClientError::RetriesExhausted(
+                    url,
+                    response.status(),
+                    retries,
+                ))?,
+                _ => break response,
+            }
+        };
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| ClientError::CouldNotSendRequest(url.clone(), e))?;
+        let invalid =
+            |e: serde_json::Error| ClientError::InvalidResponse(url, status, e.to_string());
+
+        if status.is_success() {
+            let response: DataResponse<T> = serde_json::from_slice(&body).map_err(invalid)?;
+            Ok(response.data)
+        } else {
+            let response: ErrorResponse = serde_json::from_slice(&body).map_err(invalid)?;
+            Err(into_td_error(status, response))
+        }
+    }
+}
+
+/// Whether repeating a request with the given method has the same effect as sending it once.
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    )
+}
+
+/// Delay requested by a `429` or `503` response with a `Retry-After` header in seconds, if any.
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    match response.status() {
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE => response
+            .headers()
+            .get(RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+/// Maps an error response into the [`TdError`] of the server. Error codes are
+/// `<DOMAIN>::<DISCRIMINANT>`, and the discriminant determines the API error.
+fn into_td_error(status: StatusCode, response: ErrorResponse) -> TdError {
+    let (domain, api_error) = match response.code.rsplit_once("::") {
+        Some((domain, discriminant)) => {
+            let api_error = discriminant
+                .parse::<u16>()
+                .map(ApiError::from)
+                .unwrap_or(ApiError::Unexpected);
+            (domain.to_string(), api_error)
+        }
+        None => (response.code.clone(), ApiError::Unexpected),
+    };
+    let msg = response
+        .error_description
+        .or(response.error)
+        .unwrap_or_else(|| status.to_string());
+    TdError::new(InlineError::new(msg, domain, response.code, api_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::http::header;
+    use axum::response::{IntoResponse, Response};
+    use axum::routing::any;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Serves `/api/v1/retry`, rejecting the first `rejections` requests with `429` and the
+    /// given `Retry-After`. Returns the server URL and the number of requests received.
+    async fn mock_server(rejections: u32, retry_after: &'static str) -> (String, Arc<AtomicU32>) {
+        let requests = Arc::new(AtomicU32::new(0));
+        let counter = requests.clone();
+        let router = Router::new().route(
+            "/api/v1/retry",
+            any(move || async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < rejections {
+                    (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [(header::RETRY_AFTER, retry_after)],
+                        r#"{"code":"RateLimit::0000","error":"too_many_requests"}"#,
+                    )
+                        .into_response()
+                } else {
+                    Response::new(r#"{"data":"ok"}"#.into())
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (url, requests)
+    }
+
+    #[test]
+    fn test_into_td_error() {
+        let response: ErrorResponse = serde_json::from_str(
+            r#"{"code":"ListError::0003","error":"invalid_request","error_description":"Undefined filter field 'bogus'"}"#,
+        )
+        .unwrap();
+        let err = into_td_error(StatusCode::BAD_REQUEST, response);
+        assert_eq!(err.domain(), "ListError");
+        assert_eq!(err.code(), "ListError::0003");
+        assert_eq!(err.api_error(), ApiError::InputError);
+        assert!(err.to_string().contains("Undefined filter field 'bogus'"));
+
+        let response: ErrorResponse =
+            serde_json::from_str(r#"{"code":"SqlError::1000","error":"not_found"}"#).unwrap();
+        let err = into_td_error(StatusCode::NOT_FOUND, response);
+        assert_eq!(err.api_error(), ApiError::NotFound);
+        assert!(err.to_string().contains("not_found"));
+
+        let response: ErrorResponse =
+            serde_json::from_str(r#"{"code":"unknown","error":null}"#).unwrap();
+        let err = into_td_error(StatusCode::INTERNAL_SERVER_ERROR, response);
+        assert_eq!(err.api_error(), ApiError::Unexpected);
+        assert!(err.to_string().contains("500 Internal Server Error"));
+    }
+
+    #[test]
+    fn test_client_base_url() -> Result<(), TdError> {
+        let client = Client::builder("http://localhost:2457/")
+            .access_token("token")
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        assert_eq!(client.base_url, "http://localhost:2457/api/v1");
+
+        let err = Client::builder("http://localhost:2457")
+            .access_token("invalid\ntoken")
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err.domain_err::<ClientError>(),
+            ClientError::InvalidAccessToken(_)
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_retry_after() -> Result<(), TdError> {
+        let (url, requests) = mock_server(1, "0").await;
+        let client = Client::builder(url).build()?;
+        let data: String = client
+            .send(Method::GET, "/retry", None::<&()>, None)
+            .await?;
+        assert_eq!(data, "ok");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_retry_after_exhausted() -> Result<(), TdError> {
+        let (url, requests) = mock_server(u32::MAX, "0").await;
+        let client = Client::builder(&url).max_retries(2).build()?;
+        let err = client
+            .send::<(), String>(Method::GET, "/retry", None, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.domain_err::<ClientError>(),
+            ClientError::RetriesExhausted(_, StatusCode::TOO_MANY_REQUESTS, 2)
+        ));
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        // Without retries the error response of the server is returned.
+        let client = Client::builder(url).max_retries(0).build()?;
+        let err = client
+            .send::<(), String>(Method::GET, "/retry", None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "RateLimit::0000");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_retry_after_capped() -> Result<(), TdError> {
+        let (url, requests) = mock_server(1, "3600").await;
+        let client = Client::builder(url)
+            .max_retry_delay(Duration::from_millis(10))
+            .build()?;
+        let data: String = tokio::time::timeout(
+            Duration::from_secs(10),
+            client.send(Method::GET, "/retry", None::<&()>, None),
+        )
+        .await
+        .expect("Retry-After delay not capped")?;
+        assert_eq!(data, "ok");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_retry_after_not_idempotent() -> Result<(), TdError> {
+        // Without idempotency key non idempotent requests are not retried.
+        let (url, requests) = mock_server(1, "0").await;
+        let client = Client::builder(url).build()?;
+        let err = client
+            .send::<(), String>(Method::POST, "/retry", None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "RateLimit::0000");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // With idempotency key they are.
+        let (url, requests) = mock_server(1, "0").await;
+        let client = Client::builder(url).build()?;
+        let data: String = client
+            .send(Method::POST, "/retry", None::<&()>, Some("key"))
+            .await?;
+        assert_eq!(data, "ok");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+}
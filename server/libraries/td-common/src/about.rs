@@ -4,10 +4,68 @@
 
 use chrono::FixedOffset;
 use colored::Colorize;
+use serde::Serialize;
 use std::env;
 use std::process::Command;
+use std::sync::LazyLock;
 #[cfg(not(windows))]
 use supports_color;
+use td_build::version::TABSDATA_VERSION;
+
+#[cfg(feature = "enterprise")]
+const EDITION: &str = "Enterprise";
+#[cfg(not(feature = "enterprise"))]
+const EDITION: &str = "Open Source";
+
+/// Build metadata of the running binary, captured at compile time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    /// Tabsdata version.
+    pub version: &'static str,
+    /// Short hash of the commit the binary was built from, if known.
+    pub git_commit: Option<&'static str>,
+    /// Build timestamp (RFC 3339, UTC).
+    pub build_timestamp: &'static str,
+    /// Tabsdata edition (Open Source or Enterprise).
+    pub edition: &'static str,
+}
+
+macro_rules! first_git_commit {
+    ($dummy1:expr, $dummy2:expr, $($prefix:literal),* $(,)?) => {
+        [$(option_env!(concat!("VERGEN_GIT_", $prefix, "_SHA"))),*]
+            .into_iter()
+            .flatten()
+            .next()
+    };
+}
+
+static BUILD_INFO: LazyLock<BuildInfo> = LazyLock::new(|| BuildInfo {
+    version: TABSDATA_VERSION.trim(),
+    git_commit: td_build::invoke_add_git_sections!((), (), first_git_commit),
+    build_timestamp: env!("VERGEN_BUILD_TIMESTAMP"),
+    edition: EDITION,
+});
+
+static LONG_VERSION: LazyLock<String> = LazyLock::new(|| {
+    let build_info = build_info();
+    format!(
+        "{} ({}, commit {}, built {})",
+        build_info.version,
+        build_info.edition,
+        build_info.git_commit.unwrap_or("-"),
+        build_info.build_timestamp
+    )
+});
+
+/// Returns the build metadata of the running binary.
+pub fn build_info() -> &'static BuildInfo {
+    &BUILD_INFO
+}
+
+/// Returns the version of the running binary with its build metadata, as shown by `--version`.
+pub fn long_version() -> &'static str {
+    &LONG_VERSION
+}
 
 pub fn tdabout(version: &str) {
     // Setting env vars is not thread-safe; use with care.
@@ -215,11 +273,7 @@ pub fn show_build_metadata() {
     let header_line_about = "About";
     let header_line_version = format!("Tabsdata Version {}", version);
 
-    #[cfg(feature = "enterprise")]
-    let edition = "Enterprise";
-    #[cfg(not(feature = "enterprise"))]
-    let edition = "Open Source";
-    let header_line_edition = format!("Edition: {}", edition);
+    let header_line_edition = format!("Edition: {}", build_info().edition);
 
     #[cfg(any(test, feature = "mock-env"))]
     let mode = "Development (mock-env feature enabled)";
@@ -562,3 +616,26 @@ pub fn show_build_metadata() {
 
     println!("{bottom_border}\n");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_version() {
+        assert_eq!(build_info().version, TABSDATA_VERSION.trim());
+    }
+
+    #[test]
+    fn test_build_info_edition() {
+        assert_eq!(build_info().edition, EDITION);
+        assert!(!build_info().build_timestamp.is_empty());
+    }
+
+    #[test]
+    fn test_long_version() {
+        assert!(long_version().starts_with(build_info().version));
+        assert!(long_version().contains(build_info().edition));
+        assert!(long_version().contains(build_info().build_timestamp));
+    }
+}
@@ -17,19 +17,39 @@ pub use td_attach::attach;
 #[allow(dead_code)]
 pub fn wait_for_attach(signal: &str) {
     const MAX_WAIT: u64 = 300;
-    const SLEEP_TIME: u64 = 5;
 
+    wait_for_attach_timeout(signal, Duration::from_secs(MAX_WAIT));
+}
+
+/// Same as [`wait_for_attach`], but giving up once `timeout` has elapsed without a debugger
+/// releasing the wait loop.
+// This function is called by the macro generated code. Therefor, identifying it as dead code is fine.
+#[allow(dead_code)]
+pub fn wait_for_attach_timeout(signal: &str, timeout: Duration) {
     if !check_attach_env() && !check_attach_config(signal) {
         return;
     }
+    wait_loop(signal, timeout);
+}
+
+fn wait_loop(signal: &str, timeout: Duration) {
+    const SLEEP_TIME: u64 = 5;
+
     println!("Entering into Wait for Attach function... '{signal}'");
     println!("Waiting for debugger to attach...: '{}'", id());
     let mut condition = false;
-    let max_wait = Duration::from_secs(MAX_WAIT);
     let sleep_time = Duration::from_secs(SLEEP_TIME);
     let start_time = Instant::now();
-    while !condition && start_time.elapsed() < max_wait {
-        sleep(sleep_time);
+    while !condition {
+        let elapsed = start_time.elapsed();
+        if elapsed >= timeout {
+            println!(
+                "WARNING: No debugger attached after {} seconds. Resuming execution... '{signal}'",
+                timeout.as_secs()
+            );
+            break;
+        }
+        sleep(sleep_time.min(timeout - elapsed));
         condition = false;
     }
     println!("Exiting from Wait for Attach function...");
@@ -163,6 +183,23 @@ mod tests {
         assert!(!check_attach_env());
     }
 
+    #[test]
+    fn test_wait_loop_timeout() {
+        let start = Instant::now();
+        wait_loop("test", Duration::from_millis(100));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_wait_for_attach_timeout() {
+        let (_guard, _env_guard) = setup_environment("true");
+        let start = Instant::now();
+        wait_for_attach_timeout("test", Duration::from_secs(1));
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_secs(1));
+        assert!(elapsed < Duration::from_secs(3));
+    }
+
     #[test]
     fn test_check_attach_flag_not_set() {
         let (_guard, _env_guard) = setup_environment("");
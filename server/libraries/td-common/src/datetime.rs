@@ -2,7 +2,9 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
+use regex::Regex;
+use std::sync::LazyLock;
 use td_error::td_error;
 
 #[td_error]
@@ -11,6 +13,10 @@ pub enum IntoDateTimeError {
     InvalidTimestampMillis(i64) = 0,
     #[error("Invalid timestamp: {0}")]
     InvalidTimestamp(String) = 1,
+    #[error(
+        "Invalid relative timestamp '{0}', it must be now, <+/-><N><UNIT> or now<+/-><N><UNIT> (units are s, m, h, d, w)"
+    )]
+    InvalidRelativeTimestamp(String) = 2,
 }
 
 pub trait IntoDateTimeUtc {
@@ -41,3 +47,120 @@ impl IntoDateTimeUtc for &str {
             .map_err(|_| IntoDateTimeError::InvalidTimestamp(self.to_string()))
     }
 }
+
+const RELATIVE_PATTERN: &str = r"^(now)?((?<sign>[+-])(?<amount>\d+)(?<unit>[smhdw]))?$";
+
+static RELATIVE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(RELATIVE_PATTERN).unwrap());
+
+/// Relative timestamp expressions, resolved against a given `now`.
+///
+/// An expression is `now`, or a signed offset from now, optionally prefixed with `now`, with a
+/// `s`, `m`, `h`, `d` or `w` unit (`-7d` and `now-7d` are 7 days ago).
+pub trait IntoRelativeDateTimeUtc {
+    /// Returns `None` if it is not a relative expression, and an error if it looks like one
+    /// but it is invalid.
+    fn relative_datetime_utc(
+        self,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, IntoDateTimeError>;
+}
+
+impl IntoRelativeDateTimeUtc for &str {
+    fn relative_datetime_utc(
+        self,
+        now: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>, IntoDateTimeError> {
+        // timestamps in milliseconds can be signed, offsets always have a unit
+        let relative = self.starts_with("now")
+            || (self.starts_with(['+', '-']) && self.parse::<i64>().is_err());
+        if !relative {
+            return Ok(None);
+        }
+
+        let invalid = || IntoDateTimeError::InvalidRelativeTimestamp(self.to_string());
+        let captures = RELATIVE_REGEX.captures(self).ok_or_else(invalid)?;
+        let (Some(sign), Some(amount), Some(unit)) = (
+            captures.name("sign"),
+            captures.name("amount"),
+            captures.name("unit"),
+        ) else {
+            return Ok(Some(now));
+        };
+
+        let amount = amount.as_str().parse::<i64>().map_err(|_| invalid())?;
+        let offset = match unit.as_str() {
+            "s" => TimeDelta::try_seconds(amount),
+            "m" => TimeDelta::try_minutes(amount),
+            "h" => TimeDelta::try_hours(amount),
+            "d" => TimeDelta::try_days(amount),
+            "w" => TimeDelta::try_weeks(amount),
+            _ => None,
+        }
+        .ok_or_else(invalid)?;
+        let resolved = match sign.as_str() {
+            "-" => now.checked_sub_signed(offset),
+            _ => now.checked_add_signed(offset),
+        }
+        .ok_or_else(invalid)?;
+        Ok(Some(resolved))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_datetime_utc() -> Result<(), IntoDateTimeError> {
+        let now = Utc::now();
+        assert_eq!("now".relative_datetime_utc(now)?, Some(now));
+        assert_eq!(
+            "-7d".relative_datetime_utc(now)?,
+            Some(now - TimeDelta::days(7))
+        );
+        assert_eq!(
+            "now-7d".relative_datetime_utc(now)?,
+            Some(now - TimeDelta::days(7))
+        );
+        assert_eq!(
+            "+2h".relative_datetime_utc(now)?,
+            Some(now + TimeDelta::hours(2))
+        );
+        assert_eq!(
+            "-30m".relative_datetime_utc(now)?,
+            Some(now - TimeDelta::minutes(30))
+        );
+        assert_eq!(
+            "-1w".relative_datetime_utc(now)?,
+            Some(now - TimeDelta::weeks(1))
+        );
+        assert_eq!(
+            "-10s".relative_datetime_utc(now)?,
+            Some(now - TimeDelta::seconds(10))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_datetime_utc_not_relative() -> Result<(), IntoDateTimeError> {
+        let now = Utc::now();
+        assert_eq!("1234".relative_datetime_utc(now)?, None);
+        assert_eq!("-1234".relative_datetime_utc(now)?, None);
+        assert_eq!("2025-01-01T00:00:00Z".relative_datetime_utc(now)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relative_datetime_utc_invalid() {
+        let now = Utc::now();
+        for expression in ["-7x", "-d", "now-", "now7d", "nowish", "-99999999999999w"] {
+            assert!(
+                matches!(
+                    expression.relative_datetime_utc(now),
+                    Err(IntoDateTimeError::InvalidRelativeTimestamp(_))
+                ),
+                "expected '{expression}' to be invalid"
+            );
+        }
+    }
+}
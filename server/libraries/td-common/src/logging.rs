@@ -52,6 +52,11 @@ pub const LOG_FILE: &str = "td.log";
 pub const LOG_EXTENSION: &str = "log";
 
 pub const WORK_ENV: &str = "TD_URI_WORK";
+
+/// OTLP (HTTP) endpoint traces are exported to. Export is disabled if not set, and requires the
+/// `otel` feature.
+pub const OTEL_ENDPOINT_ENV: &str = "TD_OTEL_EXPORTER_OTLP_ENDPOINT";
+pub const OTEL_DEFAULT_SERVICE_NAME: &str = "tabsdata";
 pub const PYTEST_VERSION: &str = "PYTEST_VERSION";
 
 use tracing_subscriber::reload::{Handle, Layer as ReloadLayer};
@@ -71,6 +76,8 @@ pub enum LogOutput {
 // Struct to hold the logger provider and ensure proper shutdown.
 struct LoggerGuard {
     provider: SdkLoggerProvider,
+    #[cfg(feature = "otel")]
+    tracer_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
 }
 
 // Destructor for the log provider.
@@ -79,6 +86,11 @@ impl Drop for LoggerGuard {
         self.provider
             .shutdown()
             .expect("Error shutting down the logging system");
+        // Best effort, the traces endpoint might not be reachable anymore.
+        #[cfg(feature = "otel")]
+        if let Some(tracer_provider) = &self.tracer_provider {
+            let _ = tracer_provider.shutdown();
+        }
     }
 }
 
@@ -170,14 +182,66 @@ fn init<W: for<'a> MakeWriter<'a> + Send + Sync + 'static>(
         None
     });
 
+    #[cfg(feature = "otel")]
+    let (registry, tracer_provider, otel_error) = {
+        use opentelemetry::trace::TracerProvider;
+        let (tracer_provider, otel_error) = match otel_tracer_provider() {
+            Ok(tracer_provider) => (tracer_provider, None),
+            Err(e) => (None, Some(e)),
+        };
+        let otel_layer = tracer_provider.as_ref().map(|tracer_provider| {
+            tracing_opentelemetry::layer()
+                .with_tracer(tracer_provider.tracer(OTEL_DEFAULT_SERVICE_NAME))
+        });
+        (registry.with(otel_layer), tracer_provider, otel_error)
+    };
+
     registry.init();
 
+    // Reported once logging is initialized, tracing export is disabled but the server goes on.
+    #[cfg(feature = "otel")]
+    if let Some(e) = otel_error {
+        error!("{e}");
+    }
+
     let exporter = LogExporter::default();
     let provider = SdkLoggerProvider::builder()
         .with_simple_exporter(exporter)
         .build();
 
-    LoggerGuard { provider }
+    LoggerGuard {
+        provider,
+        #[cfg(feature = "otel")]
+        tracer_provider,
+    }
+}
+
+// Create the OpenTelemetry tracer provider exporting to the OTLP endpoint, if configured. It also
+// sets the W3C trace context as the global propagator, so the span context can be propagated with
+// 'traceparent' headers. Logging is not initialized yet, so errors are returned to be logged later.
+#[cfg(feature = "otel")]
+fn otel_tracer_provider() -> Result<Option<opentelemetry_sdk::trace::SdkTracerProvider>, String> {
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::Resource;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    let Ok(endpoint) = env::var(OTEL_ENDPOINT_ENV) else {
+        return Ok(None);
+    };
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&endpoint)
+        .build()
+        .map_err(|e| format!("Unable to create OpenTelemetry exporter for `{endpoint}`: {e}"))?;
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    let service_name = obtain_name().unwrap_or(OTEL_DEFAULT_SERVICE_NAME.to_string());
+    Ok(Some(
+        SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(Resource::builder().with_service_name(service_name).build())
+            .build(),
+    ))
 }
 
 // Start the logger with the specified max level and output type.
@@ -13,14 +13,21 @@ use std::os::unix::fs::MetadataExt;
 use crate::env::check_flag_env;
 #[cfg(target_os = "windows")]
 use crate::server::TD_DETACHED_SUBPROCESSES;
+use derive_builder::Builder;
+use getset::Getters;
 #[cfg(not(target_os = "windows"))]
 use libc::pid_t;
 #[cfg(not(target_os = "windows"))]
 use libc::{S_IXGRP, S_IXOTH, S_IXUSR};
+use serde::{Deserialize, Serialize};
 use std::env::consts::OS;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process;
-use sysinfo::{Pid, Process, Signal, System};
+use std::process::ExitStatus;
+use std::time::Duration;
+use sysinfo::{Pid, Process, ProcessesToUpdate, Signal, System};
+use tokio::process::{Child, Command};
 use tracing::{info, warn};
 
 #[allow(dead_code)]
@@ -38,6 +45,9 @@ const EXECUTABLE_MASK: u32 = S_IXUSR | S_IXGRP | S_IXOTH;
 #[cfg(target_os = "macos")]
 const EXECUTABLE_MASK: u32 = (S_IXUSR | S_IXGRP | S_IXOTH) as u32;
 
+/// CPU shares of a process with the default CPU weight.
+pub const DEFAULT_CPU_SHARES: u64 = 1024;
+
 #[derive(Debug, thiserror::Error)]
 pub enum OsProcessError {
     #[error("Process '{0}' does not exist")]
@@ -214,6 +224,206 @@ pub fn is_executable(path: &Path) -> bool {
     }
 }
 
+/// Function to get the space (in bytes) available to the current user in the filesystem that
+/// contains the given path. The path must exist.
+#[cfg(target_os = "windows")]
+pub fn available_space(path: &Path) -> std::io::Result<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+    use windows::core::PCWSTR;
+
+    let wide_path: Vec<u16> = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut free_bytes: u64 = 0;
+    unsafe {
+        GetDiskFreeSpaceExW(
+            PCWSTR(wide_path.as_ptr()),
+            Some(&mut free_bytes as *mut u64),
+            None,
+            None,
+        )
+    }
+    .map_err(std::io::Error::other)?;
+    Ok(free_bytes)
+}
+
+/// Function to get the space (in bytes) available to the current user in the filesystem that
+/// contains the given path. The path must exist.
+#[cfg(not(target_os = "windows"))]
+#[allow(clippy::unnecessary_cast)]
+pub fn available_space(path: &Path) -> std::io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(std::io::Error::other)?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // Field widths differ between Linux and macOS, hence the casts.
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+/// Interval between checks of the memory used by a process with a memory limit.
+const MEMORY_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Resource limits of a process. Unset limits are not enforced.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize, Getters, Builder)]
+#[builder(setter(into, strip_option), default)]
+#[getset(get = "pub")]
+pub struct ResourceLimits {
+    /// Maximum memory (resident set size of the process and its children), in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_memory: Option<u64>,
+    /// Relative CPU weight, as cgroups CPU shares ([`DEFAULT_CPU_SHARES`] being the default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cpu_shares: Option<u64>,
+    /// Maximum wall-clock time, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+}
+
+impl ResourceLimits {
+    pub fn builder() -> ResourceLimitsBuilder {
+        ResourceLimitsBuilder::default()
+    }
+
+    /// Maximum wall-clock time, if any.
+    pub fn timeout_duration(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// Niceness increment giving a process the CPU weight of the CPU shares. The scheduler
+    /// weights each niceness level 1.25 times less than the previous one, niceness 0 having
+    /// the default weight. Niceness can only be increased, so shares above the default have
+    /// no effect.
+    pub fn niceness(&self) -> Option<i32> {
+        self.cpu_shares.map(|shares| {
+            let ratio = DEFAULT_CPU_SHARES as f64 / shares.max(1) as f64;
+            (ratio.ln() / 1.25f64.ln()).round().clamp(0.0, 19.0) as i32
+        })
+    }
+}
+
+/// Exit of a process waited for with [`wait_with_limits`].
+#[derive(Debug)]
+pub enum LimitedExit {
+    /// The process exited by itself.
+    Exited(ExitStatus),
+    /// The process was killed for exceeding its wall-clock limit.
+    TimedOut(Duration),
+    /// The process was killed for exceeding its memory limit, using `used` bytes.
+    OutOfMemory { used: u64, limit: u64 },
+}
+
+/// Function to apply the CPU limits to the process spawned by the given command, and to its
+/// own children. Memory and wall-clock limits are enforced waiting for the process, with
+/// [`wait_with_limits`].
+#[cfg(not(target_os = "windows"))]
+pub fn apply_resource_limits(command: &mut Command, limits: &ResourceLimits) {
+    let Some(niceness) = limits.niceness() else {
+        return;
+    };
+    // SAFETY: the closure runs in the child process, between fork and exec, and it only calls
+    // async-signal-safe functions.
+    unsafe {
+        command.pre_exec(move || {
+            // The new niceness can legitimately be -1, so failures are ignored.
+            libc::nice(niceness);
+            Ok(())
+        });
+    }
+}
+
+/// Function to apply the CPU limits to the process spawned by the given command. They are not
+/// supported on Windows, where only memory and wall-clock limits are enforced, with
+/// [`wait_with_limits`].
+#[cfg(target_os = "windows")]
+pub fn apply_resource_limits(_command: &mut Command, limits: &ResourceLimits) {
+    if limits.cpu_shares.is_some() {
+        warn!("CPU limits are not supported on Windows, ignoring them: {limits:?}");
+    }
+}
+
+/// Function to wait for a child process to exit, enforcing its memory and wall-clock limits.
+/// If the process, along with its children, uses more memory than allowed, or it does not exit
+/// in time, it is killed, along with its children, and the exceeded limit is returned.
+///
+/// Memory is checked periodically instead of limiting the address space of the process, so
+/// that exceeding it can be told apart from any other failure of the process.
+pub async fn wait_with_limits(
+    child: &mut Child,
+    limits: Option<&ResourceLimits>,
+) -> io::Result<LimitedExit> {
+    let timeout = limits.and_then(|l| l.timeout_duration());
+    let max_memory = limits.and_then(|l| *l.max_memory());
+    let deadline = tokio::time::Instant::now() + timeout.unwrap_or_default();
+    let mut system = System::new();
+    loop {
+        tokio::select! {
+            exit_status = child.wait() => return exit_status.map(LimitedExit::Exited),
+            _ = tokio::time::sleep_until(deadline), if timeout.is_some() => {
+                let timeout = timeout.unwrap_or_default();
+                if let Some(pid) = child.id() {
+                    warn!(
+                        "Process with pid '{}' exceeded its time limit of {:?}, killing it",
+                        pid, timeout
+                    );
+                }
+                kill_child(child).await?;
+                return Ok(LimitedExit::TimedOut(timeout));
+            }
+            _ = tokio::time::sleep(MEMORY_CHECK_INTERVAL), if max_memory.is_some() => {
+                let limit = max_memory.unwrap_or_default();
+                let Some(pid) = child.id() else {
+                    continue;
+                };
+                let used = process_tree_memory(&mut system, Pid::from_u32(pid));
+                if used > limit {
+                    warn!(
+                        "Process with pid '{}' exceeded its memory limit of {} bytes, killing it",
+                        pid, limit
+                    );
+                    kill_child(child).await?;
+                    return Ok(LimitedExit::OutOfMemory { used, limit });
+                }
+            }
+        }
+    }
+}
+
+/// Resident memory used by a process and all its descendants, in bytes.
+fn process_tree_memory(system: &mut System, pid: Pid) -> u64 {
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    let mut memory = 0;
+    let mut pending = vec![pid];
+    while let Some(pid) = pending.pop() {
+        if let Some(process) = system.process(pid) {
+            memory += process.memory();
+        }
+        pending.extend(system.processes().iter().filter_map(|(child, process)| {
+            (process.parent() == Some(pid) && process.thread_kind().is_none()).then_some(*child)
+        }));
+    }
+    memory
+}
+
+/// Function to kill a child process, along with its children, and reap it.
+async fn kill_child(child: &mut Child) -> io::Result<()> {
+    if let Some(pid) = child.id()
+        && let Err(e) = terminate_process(pid as i32, Signal::Kill)
+    {
+        warn!("Unable to kill process with pid '{}': {}", pid, e);
+    }
+    // Ensure the process is killed and reaped, even if it had no children.
+    let _ = child.start_kill();
+    child.wait().await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::process::Command;
@@ -320,6 +530,123 @@ mod tests {
         panic!("The dummy process should be terminated after '{signal}' signal");
     }
 
+    #[test]
+    fn test_resource_limits_niceness() {
+        let niceness = |shares: u64| {
+            ResourceLimits::builder()
+                .cpu_shares(shares)
+                .build()
+                .unwrap()
+                .niceness()
+        };
+        assert_eq!(ResourceLimits::default().niceness(), None);
+        assert_eq!(niceness(DEFAULT_CPU_SHARES), Some(0));
+        assert_eq!(niceness(4096), Some(0));
+        assert_eq!(niceness(512), Some(3));
+        assert_eq!(niceness(0), Some(19));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_apply_resource_limits() {
+        let limits = ResourceLimits::builder()
+            .max_memory(512u64 * 1024 * 1024)
+            .cpu_shares(512u64)
+            .build()
+            .unwrap();
+        let mut command = tokio::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg("nice")
+            .stdout(std::process::Stdio::piped());
+        apply_resource_limits(&mut command, &limits);
+        let output = command.output().await.unwrap();
+        assert!(output.status.success());
+
+        let output = String::from_utf8(output.stdout).unwrap();
+        let niceness: i32 = output.trim().parse().unwrap();
+        assert!(niceness >= 3);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[tokio::test]
+    async fn test_wait_with_limits_timeout() {
+        let limits = ResourceLimits::builder().timeout(1u64).build().unwrap();
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("600")
+            .spawn()
+            .unwrap();
+        let pid = child.id().unwrap() as i32;
+        let exit = wait_with_limits(&mut child, Some(&limits)).await.unwrap();
+        assert!(
+            matches!(exit, LimitedExit::TimedOut(timeout) if timeout == Duration::from_secs(1))
+        );
+        assert!(!check_process(pid));
+
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("0")
+            .spawn()
+            .unwrap();
+        let exit = wait_with_limits(&mut child, Some(&limits)).await.unwrap();
+        assert!(matches!(exit, LimitedExit::Exited(status) if status.success()));
+
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("0")
+            .spawn()
+            .unwrap();
+        let exit = wait_with_limits(&mut child, None).await.unwrap();
+        assert!(matches!(exit, LimitedExit::Exited(status) if status.success()));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[tokio::test]
+    async fn test_wait_with_limits_memory() {
+        // Any process uses more than a single page of memory.
+        let limits = ResourceLimits::builder()
+            .max_memory(4096u64)
+            .timeout(60u64)
+            .build()
+            .unwrap();
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("600")
+            .spawn()
+            .unwrap();
+        let pid = child.id().unwrap() as i32;
+        let exit = wait_with_limits(&mut child, Some(&limits)).await.unwrap();
+        match exit {
+            LimitedExit::OutOfMemory { used, limit } => {
+                assert_eq!(limit, 4096);
+                assert!(used > limit);
+            }
+            exit => panic!("Expected the process to exceed its memory limit, got {exit:?}"),
+        }
+        assert!(!check_process(pid));
+
+        let limits = ResourceLimits::builder()
+            .max_memory(1024u64 * 1024 * 1024)
+            .build()
+            .unwrap();
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("1")
+            .spawn()
+            .unwrap();
+        let exit = wait_with_limits(&mut child, Some(&limits)).await.unwrap();
+        assert!(matches!(exit, LimitedExit::Exited(status) if status.success()));
+    }
+
+    #[test]
+    fn test_available_space() {
+        let dir = testdir::testdir!();
+        let space = available_space(&dir).unwrap();
+        assert!(space > 0);
+    }
+
+    #[test]
+    fn test_available_space_missing_path() {
+        let dir = testdir::testdir!().join("missing");
+        assert!(available_space(&dir).is_err());
+    }
+
     // Test program extension with no previous extension.
     #[test]
     #[cfg(target_os = "linux")]
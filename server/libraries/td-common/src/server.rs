@@ -10,6 +10,7 @@ use crate::execution_status::WorkerCallbackStatus;
 use crate::files::{LOCK_EXTENSION, YAML_EXTENSION, get_files_in_folder_sorted_by_name};
 use crate::logging::LOG_LOCATION;
 use crate::manifest::{Inf, WORKER_INF_FILE};
+use crate::os::ResourceLimits;
 use crate::server::EtcError::EtcStoreLocationCreationError;
 use crate::server::QueueError::{
     MessageAlreadyExisting, MessageNonExisting, QueuePlannedCreationError, QueueRootCreationError,
@@ -31,6 +32,7 @@ use regex::Regex;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_yaml::{Mapping, Value};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
@@ -206,6 +208,34 @@ pub enum MessageAction {
     Notify,
 }
 
+/// Dispatch priority of a worker message. Higher priority messages are dispatched first, and
+/// messages with the same priority are dispatched in arrival order.
+#[derive(
+    ToSchema,
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum MessagePriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, new, Setters, Serialize, Deserialize)]
 pub struct SupervisorMessage<T = Value>
 where
@@ -217,6 +247,25 @@ where
     pub payload: SupervisorMessagePayload<T>,
 }
 
+impl<T> SupervisorMessage<T>
+where
+    T: Clone,
+{
+    /// Priority of the message. Only request messages carry one, others have the default priority.
+    pub fn priority(&self) -> MessagePriority {
+        match &self.payload {
+            SupervisorRequestMessagePayload(payload) => payload.priority,
+            _ => MessagePriority::default(),
+        }
+    }
+}
+
+/// Sorts messages by descending priority. The sort is stable, so messages with the same priority
+/// keep their relative (arrival) order.
+pub fn sort_by_priority<T: Clone>(messages: &mut [SupervisorMessage<T>]) {
+    messages.sort_by_key(|message| Reverse(message.priority()));
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SupervisorMessagePayload<T = Value>
 where
@@ -243,6 +292,12 @@ where
     callback: Option<Callback>,
     #[serde(skip_serializing_if = "Option::is_none")]
     context: Option<T>,
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    limits: Option<ResourceLimits>,
+    #[builder(default)]
+    #[serde(default)]
+    priority: MessagePriority,
 }
 
 impl<T> RequestMessagePayload<T>
@@ -435,6 +490,8 @@ where
             arguments,
             callback,
             context,
+            limits: None,
+            priority: MessagePriority::default(),
         }
     }
 }
@@ -649,6 +706,8 @@ pub trait WorkerMessageQueue: Send + Sync + Sized + 'static {
     /// Rollbacks a message in the queue.
     async fn rollback(&self, id: &str) -> Result<(), QueueError>;
 
+    /// Returns the locked messages in dispatch order: by descending priority, and in arrival
+    /// order within the same priority.
     async fn locked_messages<T: DeserializeOwned + Clone + Send + Sync>(
         &self,
     ) -> Vec<SupervisorMessage<T>>;
@@ -707,19 +766,22 @@ impl WorkerMessageQueue for FileWorkerMessageQueue {
     async fn locked_messages<T: DeserializeOwned + Clone + Send + Sync>(
         &self,
     ) -> Vec<SupervisorMessage<T>> {
-        get_files_in_folder_sorted_by_name(&self.location, Some(LOCK_EXTENSION))
-            .unwrap_or_else(|_| Vec::new())
-            .into_iter()
-            .filter_map(|file| {
-                match SupervisorMessage::<T>::try_from((file.clone(), PayloadType::Request)) {
-                    Ok(msg) => Some(msg),
-                    Err(e) => {
-                        error!("Failed to extract message from file {:?}: {:?}", file, e);
-                        None
+        let mut messages: Vec<_> =
+            get_files_in_folder_sorted_by_name(&self.location, Some(LOCK_EXTENSION))
+                .unwrap_or_else(|_| Vec::new())
+                .into_iter()
+                .filter_map(|file| {
+                    match SupervisorMessage::<T>::try_from((file.clone(), PayloadType::Request)) {
+                        Ok(msg) => Some(msg),
+                        Err(e) => {
+                            error!("Failed to extract message from file {:?}: {:?}", file, e);
+                            None
+                        }
                     }
-                }
-            })
-            .collect()
+                })
+                .collect();
+        sort_by_priority(&mut messages);
+        messages
     }
 }
 
@@ -759,6 +821,8 @@ mod tests_queue {
             arguments: vec![String::from("arg1")],
             callback: None,
             context: Some(Value::Null),
+            limits: None,
+            priority: MessagePriority::default(),
         };
 
         let lock_message_path = get_current_dir()
@@ -812,6 +876,8 @@ mod tests_queue {
             arguments: vec![String::from("arg2")],
             callback: None,
             context: Some(Value::Null),
+            limits: None,
+            priority: MessagePriority::default(),
         };
 
         let lock_message_path = get_current_dir()
@@ -865,6 +931,8 @@ mod tests_queue {
             arguments: vec![String::from("arg3")],
             callback: None,
             context: None,
+            limits: None,
+            priority: MessagePriority::default(),
         };
 
         let _ = queue.put(id.to_string(), payload.clone()).await.unwrap();
@@ -890,6 +958,40 @@ mod tests_queue {
         assert!(matches!(result, Err(MessageNonExisting { .. })));
     }
 
+    #[tokio::test]
+    async fn test_locked_messages_by_priority() {
+        let queue = FileWorkerMessageQueue {
+            location: testdir::testdir!(),
+        };
+
+        let runs = [
+            ("run0", MessagePriority::Low),
+            ("run1", MessagePriority::High),
+            ("run2", MessagePriority::Normal),
+            ("run3", MessagePriority::Low),
+            ("run4", MessagePriority::High),
+        ];
+        for (id, priority) in runs {
+            let payload = RequestMessagePayload::<Value>::builder()
+                .class(WorkerClass::EPHEMERAL)
+                .worker("worker")
+                .action(MessageAction::Start)
+                .arguments(vec![])
+                .callback(None)
+                .context(None)
+                .priority(priority)
+                .build()
+                .unwrap();
+            queue.put(id.to_string(), payload).await.unwrap();
+        }
+
+        let messages = queue.locked_messages::<Value>().await;
+        let ids: Vec<_> = messages.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["run1", "run4", "run2", "run0", "run3"]);
+        assert_eq!(messages[0].priority(), MessagePriority::High);
+        assert_eq!(messages[4].priority(), MessagePriority::Low);
+    }
+
     #[test]
     fn test_valid_counter() {
         let path = PathBuf::from("/a/b/work_3");
@@ -6,20 +6,75 @@ use sysinfo::Signal;
 use tokio::select;
 use tracing::info;
 
-/// Termination signals that can be received by the program. This method is platform-specific,
-/// returning the received signal, or None if the monitor ended.
-pub async fn terminate() -> Option<Signal> {
+/// Source of the termination signals received by the program.
+pub trait SignalSource: Send {
+    /// Waits for the next termination signal, returning None if the source ended.
+    fn recv(&mut self) -> impl Future<Output = Option<Signal>> + Send;
+}
+
+/// Termination signals of the operating system. This source is platform-specific.
+///
+/// Signal handlers are installed when the source is created (not when it is first polled), so no
+/// signal delivered after its creation is missed. It must be created from within a Tokio runtime.
+pub struct OsSignals {
+    #[cfg(not(windows))]
+    interrupt: tokio::signal::unix::Signal,
+    #[cfg(not(windows))]
+    terminate: tokio::signal::unix::Signal,
+    #[cfg(not(windows))]
+    quit: tokio::signal::unix::Signal,
+
+    #[cfg(windows)]
+    ctrl_c: tokio::signal::windows::CtrlC,
+    #[cfg(windows)]
+    ctrl_break: tokio::signal::windows::CtrlBreak,
+    #[cfg(windows)]
+    ctrl_close: tokio::signal::windows::CtrlClose,
+    #[cfg(windows)]
+    ctrl_logoff: tokio::signal::windows::CtrlLogoff,
+    #[cfg(windows)]
+    ctrl_shutdown: tokio::signal::windows::CtrlShutdown,
+}
+
+impl OsSignals {
     #[cfg(not(windows))]
     // https://www.gnu.org/software/libc/manual/html_node/Termination-Signals.html
-    {
+    pub fn new() -> Self {
         use tokio::signal::unix::{SignalKind, signal};
 
-        let mut signal_interrupt_handle = signal(SignalKind::interrupt()).unwrap();
-        let mut signal_terminate_handle = signal(SignalKind::terminate()).unwrap();
-        let mut signal_quit_handle = signal(SignalKind::quit()).unwrap();
+        Self {
+            interrupt: signal(SignalKind::interrupt()).unwrap(),
+            terminate: signal(SignalKind::terminate()).unwrap(),
+            quit: signal(SignalKind::quit()).unwrap(),
+        }
+    }
+
+    #[cfg(windows)]
+    // https://learn.microsoft.com/en-us/windows/console/handlerroutine
+    pub fn new() -> Self {
+        use tokio::signal::windows;
 
+        Self {
+            ctrl_c: windows::ctrl_c().unwrap(),
+            ctrl_break: windows::ctrl_break().unwrap(),
+            ctrl_close: windows::ctrl_close().unwrap(),
+            ctrl_logoff: windows::ctrl_logoff().unwrap(),
+            ctrl_shutdown: windows::ctrl_shutdown().unwrap(),
+        }
+    }
+}
+
+impl Default for OsSignals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalSource for OsSignals {
+    #[cfg(not(windows))]
+    async fn recv(&mut self) -> Option<Signal> {
         select! {
-            result = signal_interrupt_handle.recv() => {
+            result = self.interrupt.recv() => {
                 if result.is_some() {
                     info!("Received SIGINT (Ctrl+C). Initiating graceful stop...");
                     Some(Signal::Interrupt)
@@ -28,7 +83,7 @@ pub async fn terminate() -> Option<Signal> {
                     None
                 }
             },
-            result = signal_terminate_handle.recv() => {
+            result = self.terminate.recv() => {
                 if result.is_some() {
                     info!("Received SIGTERM. Initiating graceful stop...");
                     Some(Signal::Term)
@@ -37,7 +92,7 @@ pub async fn terminate() -> Option<Signal> {
                     None
                 }
             },
-            result = signal_quit_handle.recv() => {
+            result = self.quit.recv() => {
                 if result.is_some() {
                     info!("Received SIGQUIT. Initiating forceful stop...");
                     Some(Signal::Kill)
@@ -50,18 +105,9 @@ pub async fn terminate() -> Option<Signal> {
     }
 
     #[cfg(windows)]
-    // https://learn.microsoft.com/en-us/windows/console/handlerroutine
-    {
-        use tokio::signal::windows;
-
-        let mut signal_c_handle = windows::ctrl_c().unwrap();
-        let mut signal_break_handle = windows::ctrl_break().unwrap();
-        let mut signal_close_handle = windows::ctrl_close().unwrap();
-        let mut signal_logoff_handle = windows::ctrl_logoff().unwrap();
-        let mut signal_shutdown_handle = windows::ctrl_shutdown().unwrap();
-
+    async fn recv(&mut self) -> Option<Signal> {
         select! {
-            result = signal_c_handle.recv() => {
+            result = self.ctrl_c.recv() => {
                 if result.is_some() {
                     info!("Received Ctrl+C. Initiating graceful stop...");
                     Some(Signal::Kill)
@@ -70,7 +116,7 @@ pub async fn terminate() -> Option<Signal> {
                     None
                 }
             },
-            result = signal_break_handle.recv() => {
+            result = self.ctrl_break.recv() => {
                 if result.is_some() {
                     info!("Received Ctrl+Break. Initiating graceful stop...");
                     Some(Signal::Kill)
@@ -79,7 +125,7 @@ pub async fn terminate() -> Option<Signal> {
                     None
                 }
             },
-            result = signal_close_handle.recv() => {
+            result = self.ctrl_close.recv() => {
                 if result.is_some() {
                     info!("Received Ctrl+Close. Initiating graceful stop...");
                     Some(Signal::Kill)
@@ -88,7 +134,7 @@ pub async fn terminate() -> Option<Signal> {
                     None
                 }
             },
-            result = signal_logoff_handle.recv() => {
+            result = self.ctrl_logoff.recv() => {
                 if result.is_some() {
                     info!("Received Ctrl+Logoff. Initiating graceful stop...");
                     Some(Signal::Kill)
@@ -97,7 +143,7 @@ pub async fn terminate() -> Option<Signal> {
                     None
                 }
             },
-            result = signal_shutdown_handle.recv() => {
+            result = self.ctrl_shutdown.recv() => {
                 if result.is_some() {
                     info!("Received Ctrl+Shutdown. Initiating graceful stop...");
                     Some(Signal::Kill)
@@ -109,3 +155,62 @@ pub async fn terminate() -> Option<Signal> {
         }
     }
 }
+
+/// Signals sent within the program, to trigger a termination without an operating system signal.
+impl SignalSource for tokio::sync::mpsc::Receiver<Signal> {
+    async fn recv(&mut self) -> Option<Signal> {
+        tokio::sync::mpsc::Receiver::recv(self).await
+    }
+}
+
+/// Termination signals that can be received by the program, returning the received signal, or
+/// None if the monitor ended.
+pub async fn terminate() -> Option<Signal> {
+    OsSignals::new().recv().await
+}
+
+/// Shutdown signal shared by all servers. It resolves once a termination signal of the operating
+/// system is received (see [`OsSignals`]), returning it, or None if the monitor ended.
+///
+/// Signal handlers are installed when this function is called (not when the returned future is
+/// first polled), so no signal delivered after the call is missed. It must be called from within
+/// a Tokio runtime.
+pub fn shutdown_signal() -> impl Future<Output = Option<Signal>> {
+    shutdown_signal_from(OsSignals::new())
+}
+
+/// Shutdown signal resolving once the given source delivers a termination signal.
+pub fn shutdown_signal_from<S: SignalSource>(
+    mut source: S,
+) -> impl Future<Output = Option<Signal>> {
+    async move { source.recv().await }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_shutdown_signal_resolves_on_signal() {
+        let (sender, receiver) = mpsc::channel(1);
+        let handle = tokio::spawn(shutdown_signal_from(receiver));
+
+        sender.send(Signal::Term).await.unwrap();
+
+        let signal = tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("shutdown signal did not resolve")
+            .unwrap();
+        assert_eq!(signal, Some(Signal::Term));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_signal_ends_with_source() {
+        let (sender, receiver) = mpsc::channel(1);
+        drop(sender);
+
+        assert_eq!(shutdown_signal_from(receiver).await, None);
+    }
+}
@@ -4,6 +4,76 @@
 
 use itertools::Itertools;
 
+/// Marker appended to strings truncated by [`truncate_chars`] and [`truncate_bytes_on_boundary`].
+pub const ELLIPSIS: &str = "...";
+
 pub fn comma_separated(values: &[String]) -> String {
     values.iter().join(",")
 }
+
+/// Truncates `s` to at most `max_chars` characters, appending [`ELLIPSIS`] if anything was cut.
+///
+/// Characters are Unicode scalar values, so multi-byte characters are never split.
+pub fn truncate_chars(s: &str, max_chars: usize) -> String {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}{}", &s[..end], ELLIPSIS),
+        None => s.to_string(),
+    }
+}
+
+/// Truncates `s` to at most `max_bytes` bytes, appending [`ELLIPSIS`] if anything was cut.
+///
+/// The cut is moved back to the closest char boundary, so the result is always valid UTF-8 and
+/// it never panics, even if `max_bytes` falls in the middle of a multi-byte character.
+pub fn truncate_bytes_on_boundary(s: &str, max_bytes: usize) -> String {
+    if s.len() <= max_bytes {
+        return s.to_string();
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &s[..end], ELLIPSIS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("", 3), "");
+        assert_eq!(truncate_chars("abc", 3), "abc");
+        assert_eq!(truncate_chars("abcd", 3), "abc...");
+        assert_eq!(truncate_chars("abcd", 0), "...");
+        assert_eq!(truncate_chars("ñañaña", 3), "ñañ...");
+        assert_eq!(truncate_chars("日本語テキスト", 3), "日本語...");
+        assert_eq!(truncate_chars("🦀🦀", 2), "🦀🦀");
+    }
+
+    #[test]
+    fn test_truncate_bytes_on_boundary() {
+        assert_eq!(truncate_bytes_on_boundary("", 3), "");
+        assert_eq!(truncate_bytes_on_boundary("abc", 3), "abc");
+        assert_eq!(truncate_bytes_on_boundary("abcd", 3), "abc...");
+        assert_eq!(truncate_bytes_on_boundary("abcd", 0), "...");
+        // 'ñ' is 2 bytes, cutting at 3 would split the second one.
+        assert_eq!(truncate_bytes_on_boundary("ñañ", 3), "ña...");
+        // '日' is 3 bytes, any cut below 3 drops it entirely.
+        assert_eq!(truncate_bytes_on_boundary("日本", 2), "...");
+        assert_eq!(truncate_bytes_on_boundary("日本", 4), "日...");
+        assert_eq!(truncate_bytes_on_boundary("🦀🦀", 7), "🦀...");
+    }
+
+    #[test]
+    fn test_truncate_never_panics() {
+        let s = "a日ñ🦀b";
+        for max in 0..=s.len() + 1 {
+            let truncated = truncate_bytes_on_boundary(s, max);
+            assert!(truncated.len() <= max + ELLIPSIS.len());
+        }
+        for max in 0..=s.chars().count() + 1 {
+            let _ = truncate_chars(s, max);
+        }
+    }
+}
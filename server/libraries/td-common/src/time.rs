@@ -5,6 +5,7 @@
 use chrono::{DateTime, Utc};
 use std::marker::PhantomData;
 use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
 /// Type holder for [`UniqueUtc::now_millis`] function.
 pub struct UniqueUtc {
@@ -97,6 +98,52 @@ impl ThreadSafeElasticUniqueEpoch {
 static ELASTIC_UNIQUE_EPOCH: LazyLock<ThreadSafeElasticUniqueEpoch> =
     LazyLock::new(ThreadSafeElasticUniqueEpoch::new);
 
+/// Monotonic stopwatch to time services and operations.
+///
+/// It is based on [`Instant`], so it is not affected by system clock adjustments. Durations are
+/// meant to be attached to tracing spans, e.g. `span.record("elapsed_ms", stopwatch.elapsed_millis())`.
+#[derive(Debug, Clone)]
+pub struct Stopwatch {
+    start: Instant,
+    last_lap: Instant,
+}
+
+impl Stopwatch {
+    /// Creates a new [`Stopwatch`], started at the current instant.
+    pub fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            last_lap: now,
+        }
+    }
+
+    /// Returns the time elapsed since the previous lap (or since the start for the first lap) and
+    /// starts a new lap.
+    pub fn lap(&mut self) -> Duration {
+        let now = Instant::now();
+        let lap = now.duration_since(self.last_lap);
+        self.last_lap = now;
+        lap
+    }
+
+    /// Returns the time elapsed since the start, regardless of laps.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns the time elapsed since the start in milliseconds.
+    pub fn elapsed_millis(&self) -> u128 {
+        self.elapsed().as_millis()
+    }
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Self::start()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,4 +171,22 @@ mod tests {
         let uniq = times.into_iter().all(|t| uniq.insert(t));
         assert!(uniq);
     }
+
+    #[test]
+    fn test_stopwatch_monotonic() {
+        let mut stopwatch = Stopwatch::start();
+        let mut previous = stopwatch.elapsed();
+        let mut laps = Duration::ZERO;
+        for _ in 0..5 {
+            std::thread::sleep(Duration::from_millis(2));
+            let lap = stopwatch.lap();
+            assert!(lap >= Duration::from_millis(2));
+            laps += lap;
+
+            let elapsed = stopwatch.elapsed();
+            assert!(elapsed > previous);
+            assert!(elapsed >= laps);
+            previous = elapsed;
+        }
+    }
 }
@@ -13,7 +13,7 @@ use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions, SqliteRow};
 use sqlx::{
     ConnectOptions, Database, Describe, Error, Execute, Executor, FromRow, Pool, Sqlite,
-    Transaction,
+    SqliteConnection, Transaction,
 };
 use std::cmp::Ordering;
 use std::fmt::Display;
@@ -23,9 +23,34 @@ use td_error::td_error;
 use td_schema::{DB_EDITION_NAME, DB_VERSION_NAME, DB_VERSION_VALUE};
 use te_system::edition::{Compatible, Edition, TabsdataEdition};
 use tracing::log::LevelFilter;
+use tracing::{error, warn};
 
 const SLOW_QUERIES_THRESHOLD: u64 = 5000;
-const PRAGMA_TEMP_STORE: (&str, &str) = ("temp_store", "MEMORY");
+const PRAGMA_TEMP_STORE: &str = "temp_store";
+const PRAGMA_CACHE_SIZE: &str = "cache_size";
+
+// SQLite primary result codes of a database being locked by another connection.
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+// SQLite primary result code of a write failing because the database disk is full.
+const SQLITE_FULL: i32 = 13;
+
+tokio::task_local! {
+    // Set while running a logical read-write unit, see [`DbPool::read_write_scope`].
+    static READ_WRITE_SCOPE: ();
+}
+
+/// Where SQLite stores its temporary tables and indices (`PRAGMA temp_store`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum SqliteTempStore {
+    /// As defined at SQLite compile time.
+    Default,
+    File,
+    #[default]
+    Memory,
+}
 
 /// Configuration for a SQLite database.
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
@@ -46,6 +71,20 @@ pub struct SqliteConfig {
     idle_timeout: u64,
     /// Whether to test the connection before acquiring it, defaults to `true`.
     test_before_acquire: bool,
+    /// The page cache size of each database connection in KiB, defaults to SQLite's (`2000`).
+    #[serde(default)]
+    cache_size_kb: Option<u32>,
+    /// Where temporary tables and indices are stored, defaults to `memory`.
+    #[serde(default)]
+    temp_store: SqliteTempStore,
+    /// Whether to start in read-only mode if the database cannot be opened for writing,
+    /// defaults to `false`.
+    #[serde(default)]
+    read_only_fallback: bool,
+    /// Whether to open the minimum number of read-only connections before returning from
+    /// [`DbPool::connect`] and [`DbPool::create`], defaults to `false`.
+    #[serde(default)]
+    warm_up: bool,
 }
 
 impl Default for SqliteConfig {
@@ -58,6 +97,10 @@ impl Default for SqliteConfig {
             max_lifetime: 60 * 60,
             idle_timeout: 60,
             test_before_acquire: true,
+            cache_size_kb: None,
+            temp_store: SqliteTempStore::default(),
+            read_only_fallback: false,
+            warm_up: false,
         }
     }
 }
@@ -85,6 +128,10 @@ impl SqliteConfig {
         builder.max_lifetime(self.max_lifetime);
         builder.idle_timeout(self.idle_timeout);
         builder.test_before_acquire(self.test_before_acquire);
+        builder.cache_size_kb(self.cache_size_kb);
+        builder.temp_store(self.temp_store);
+        builder.read_only_fallback(self.read_only_fallback);
+        builder.warm_up(self.warm_up);
         builder
     }
 
@@ -165,6 +212,34 @@ pub enum DbError {
     FailedToCreateOrUpgradeDatabaseEdition(#[source] Error) = 5013,
     #[error("Failed to upgrade the database tabsdata edition: {0}")]
     CannotUpgradeEdition(String) = 5014,
+    #[error("Database is in read-only mode, writes are not allowed")]
+    ReadOnlyMode = 7000,
+    #[error("Database disk is full, writes will fail until space is freed: {0}")]
+    DiskFull(#[source] Error) = 5016,
+}
+
+impl DbError {
+    /// Maps a Sqlx error, to [`DbError::DiskFull`] if the database disk is full, logging it, and
+    /// to [`DbError::SqlError`] otherwise.
+    pub fn from_sql(err: Error) -> Self {
+        if is_disk_full(&err) {
+            error!("Database disk is full: {err}");
+            #[cfg(feature = "metrics")]
+            metrics::counter!("database_disk_full_errors_total").increment(1);
+            DbError::DiskFull(err)
+        } else {
+            DbError::SqlError(err)
+        }
+    }
+}
+
+/// Whether a [`DbPool`] can write to the database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DbMode {
+    #[default]
+    ReadWrite,
+    /// Degraded mode, the database could not be opened for writing.
+    ReadOnly,
 }
 
 /// Sqlite database connection provider using Sqlx.
@@ -221,7 +296,14 @@ impl Db {
                 LevelFilter::Warn,
                 Duration::from_millis(SLOW_QUERIES_THRESHOLD),
             )
-            .pragma(PRAGMA_TEMP_STORE.0, PRAGMA_TEMP_STORE.1);
+            .pragma(PRAGMA_TEMP_STORE, config.temp_store.to_string());
+        // Negative cache sizes are in KiB, positive ones in pages.
+        let db_options = match config.cache_size_kb {
+            Some(cache_size_kb) => {
+                db_options.pragma(PRAGMA_CACHE_SIZE, format!("-{cache_size_kb}"))
+            }
+            None => db_options,
+        };
 
         let db_options = if !cfg!(feature = "sqlx_log") {
             db_options.clone().log_statements(LevelFilter::Trace)
@@ -255,11 +337,61 @@ impl Db {
     }
 }
 
+/// Retries of [`DbPool::with_transaction`] when the database is locked.
+#[derive(Debug, Clone)]
+pub struct TransactionRetry {
+    /// Maximum number of attempts, including the first one.
+    attempts: u32,
+    /// Wait before the first retry, doubled on every retry.
+    backoff: Duration,
+}
+
+impl TransactionRetry {
+    pub fn new(attempts: u32, backoff: Duration) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            backoff,
+        }
+    }
+}
+
+impl Default for TransactionRetry {
+    fn default() -> Self {
+        Self::new(5, Duration::from_millis(50))
+    }
+}
+
+/// Returns if the error is because the database is locked (`SQLITE_BUSY` or `SQLITE_LOCKED`,
+/// including their extended result codes).
+fn is_database_locked(err: &Error) -> bool {
+    sqlite_result_code(err).is_some_and(|code| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED))
+}
+
+/// Returns if the error is because the database disk is full (`SQLITE_FULL`, including its
+/// extended result codes).
+pub fn is_disk_full(err: &Error) -> bool {
+    sqlite_result_code(err).is_some_and(|code| code & 0xff == SQLITE_FULL)
+}
+
+/// Returns the SQLite result code of a database error.
+fn sqlite_result_code(err: &Error) -> Option<i32> {
+    match err {
+        Error::Database(err) => err.code().and_then(|code| code.parse::<i32>().ok()),
+        _ => None,
+    }
+}
+
+/// Error returned by the Sqlx API of a [`DbPool`] on writes in [`DbMode::ReadOnly`] mode.
+fn read_only_mode_error() -> Error {
+    Error::Configuration(Box::new(DbError::ReadOnlyMode))
+}
+
 #[derive(Debug, Clone)]
 pub struct DbPool {
     pub schema: &'static DbSchema,
     pub ro_pool: Pool<Sqlite>,
-    pub rw_pool: Pool<Sqlite>,
+    /// Absent in [`DbMode::ReadOnly`] mode.
+    pub rw_pool: Option<Pool<Sqlite>>,
 }
 
 /// Specialized Sqlx Sqlite [`Pool`] that uses two pools, one for read-only operations and one for
@@ -268,12 +400,30 @@ impl DbPool {
     /// Connects to a database using the given configuration.
     ///
     /// The schema is assumed to be up to date.
+    ///
+    /// If the database cannot be opened for writing and the configuration allows a read-only
+    /// fallback, it connects in [`DbMode::ReadOnly`] mode.
     pub async fn connect(
         config: &SqliteConfig,
         schema: &'static DbSchema,
     ) -> Result<Self, DbError> {
-        let rw_pool = Db::schema().rw_pool(config).await?;
+        let rw_pool = if config.read_only_fallback {
+            match Self::writable_rw_pool(config).await {
+                Ok(rw_pool) => Some(rw_pool),
+                Err(err) => {
+                    warn!(
+                        "Database cannot be opened for writing, starting in read-only mode: {err}"
+                    );
+                    None
+                }
+            }
+        } else {
+            Some(Db::schema().rw_pool(config).await?)
+        };
         let ro_pool = Db::schema().ro_connect(config).await?;
+        if config.warm_up {
+            Self::warm_up(&ro_pool, config.min_connections).await?;
+        }
         Ok(Self {
             schema,
             ro_pool,
@@ -281,21 +431,74 @@ impl DbPool {
         })
     }
 
+    /// Opens up to the given number of connections in the pool, checking each one is live, so
+    /// they are idle in the pool when this returns.
+    async fn warm_up(pool: &Pool<Sqlite>, connections: u32) -> Result<(), DbError> {
+        let mut conns = Vec::with_capacity(connections as usize);
+        for _ in 0..connections {
+            let mut conn = pool
+                .acquire()
+                .await
+                .map_err(DbError::FailedToConnectToDatabase)?;
+            sqlx::query("SELECT 1")
+                .execute(&mut *conn)
+                .await
+                .map_err(DbError::FailedToConnectToDatabase)?;
+            // Held until all are open, otherwise the same connection would be acquired again.
+            conns.push(conn);
+        }
+        Ok(())
+    }
+
+    /// Opens the read-write pool, failing if the database cannot be written to.
+    async fn writable_rw_pool(config: &SqliteConfig) -> Result<Pool<Sqlite>, DbError> {
+        let rw_pool = Db::schema().rw_pool(config).await?;
+        // SQLite opens write protected files as read-only, taking the write lock fails on them.
+        let mut conn = rw_pool
+            .acquire()
+            .await
+            .map_err(DbError::FailedToConnectToDatabase)?;
+        let tx = sqlx::Connection::begin_with(&mut *conn, "BEGIN IMMEDIATE")
+            .await
+            .map_err(DbError::FailedToConnectToDatabase)?;
+        tx.rollback()
+            .await
+            .map_err(DbError::FailedToConnectToDatabase)?;
+        drop(conn);
+        Ok(rw_pool)
+    }
+
     /// Creates a database using the given configuration.
     ///
     /// Creates the schema.
     pub async fn create(config: &SqliteConfig, schema: &'static DbSchema) -> Result<Self, DbError> {
         let rw_pool = Db::schema().rw_pool(config).await?;
         let ro_pool = Db::schema().ro_connect(config).await?;
+        if config.warm_up {
+            Self::warm_up(&ro_pool, config.min_connections).await?;
+        }
         let db = Self {
             schema,
             ro_pool,
-            rw_pool,
+            rw_pool: Some(rw_pool),
         };
         db.upgrade().await?;
         Ok(db)
     }
 
+    /// Returns if the pool can write to the database.
+    pub fn mode(&self) -> DbMode {
+        match self.rw_pool {
+            Some(_) => DbMode::ReadWrite,
+            None => DbMode::ReadOnly,
+        }
+    }
+
+    /// Returns the read-write pool, failing in [`DbMode::ReadOnly`] mode.
+    pub fn read_write_pool(&self) -> Result<&Pool<Sqlite>, DbError> {
+        self.rw_pool.as_ref().ok_or(DbError::ReadOnlyMode)
+    }
+
     pub async fn check(&self) -> Result<(), DbError> {
         self.check_db_version().await?;
         self.check_tabsdata_edition().await?;
@@ -317,10 +520,10 @@ impl DbPool {
                 if database_err.message().contains("no such table") {
                     DbError::DatabaseSchemaDoesNotExist
                 } else {
-                    DbError::SqlError(err)
+                    DbError::from_sql(err)
                 }
             }
-            _ => DbError::SqlError(err),
+            _ => DbError::from_sql(err),
         }
     }
 
@@ -354,7 +557,7 @@ impl DbPool {
 
     async fn upgrade_db_version(&self) -> Result<(), DbError> {
         self.schema
-            .run(&self.rw_pool)
+            .run(self.read_write_pool()?)
             .await
             .map_err(DbError::FailedToCreateOrUpgradeDatabaseSchema)?;
         Ok(())
@@ -409,7 +612,7 @@ impl DbPool {
                     .push_bind(runtime_edition.label())
                     .push(")")
                     .build()
-                    .execute(&self.rw_pool)
+                    .execute(self.read_write_pool()?)
                     .await
                     .map_err(DbError::FailedToCreateOrUpgradeDatabaseEdition)?;
                 Ok(())
@@ -426,7 +629,7 @@ impl DbPool {
                         .push(" WHERE name = ")
                         .push_bind(DB_EDITION_NAME)
                         .build()
-                        .execute(&self.rw_pool)
+                        .execute(self.read_write_pool()?)
                         .await
                         .map_err(DbError::FailedToCreateOrUpgradeDatabaseEdition)?;
                     Ok(())
@@ -437,21 +640,104 @@ impl DbPool {
         }
     }
 
-    /// Delegates to the read-only pool's [`Pool::acquire`] method.
+    /// Delegates to the read-only pool's [`Pool::acquire`] method, or to the read-write pool's
+    /// within a [`DbPool::read_write_scope`].
     pub fn acquire(
         &self,
     ) -> impl Future<Output = Result<PoolConnection<Sqlite>, Error>> + 'static + use<> {
-        self.ro_pool.acquire()
+        self.read_pool().acquire()
     }
 
     /// Delegates to the read-write pool's [`Pool::begin`] method.
+    ///
+    /// Fails with [`DbError::ReadOnlyMode`] in [`DbMode::ReadOnly`] mode.
     pub async fn begin(&self) -> Result<Transaction<'static, Sqlite>, Error> {
-        self.rw_pool.begin().await
+        match &self.rw_pool {
+            Some(rw_pool) => rw_pool.begin().await,
+            None => Err(read_only_mode_error()),
+        }
+    }
+
+    /// Runs the given function in a read-write transaction, committing it on success, with the
+    /// default [`TransactionRetry`].
+    ///
+    /// See [`DbPool::with_transaction_retry`].
+    pub async fn with_transaction<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        F: for<'c> FnMut(&'c mut SqliteConnection) -> BoxFuture<'c, Result<T, Error>>,
+    {
+        self.with_transaction_retry(&TransactionRetry::default(), f)
+            .await
+    }
+
+    /// Runs the given function in a read-write transaction, committing it on success.
+    ///
+    /// If the database is locked (`SQLITE_BUSY`/`SQLITE_LOCKED`) the transaction is rolled back
+    /// and the whole function is retried, with exponential backoff, up to the given attempts.
+    /// Any other error rolls back the transaction and it is returned.
+    ///
+    /// The function may run more than once, it must be idempotent. It must not have side effects
+    /// outside of the transaction (or they must be safe to repeat).
+    pub async fn with_transaction_retry<T, F>(
+        &self,
+        retry: &TransactionRetry,
+        mut f: F,
+    ) -> Result<T, Error>
+    where
+        F: for<'c> FnMut(&'c mut SqliteConnection) -> BoxFuture<'c, Result<T, Error>>,
+    {
+        let mut attempt = 1;
+        loop {
+            let res = async {
+                let mut tx = self.begin().await?;
+                let res = f(&mut *tx).await?;
+                tx.commit().await?;
+                Ok(res)
+            }
+            .await;
+            match res {
+                Err(err) if is_database_locked(&err) && attempt < retry.attempts => {
+                    let backoff = retry.backoff * 2u32.pow(attempt - 1);
+                    warn!(
+                        "Database locked, retrying transaction in {backoff:?} (attempt {attempt} of {}): {err}",
+                        retry.attempts
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Runs the given future as a logical read-write unit.
+    ///
+    /// Within it, reads through the [`DbPool`] [`Executor`] go to the read-write pool instead of
+    /// the read-only pool, so they see the writes done within the unit (a read-only connection
+    /// may still be reading an older WAL snapshot).
+    ///
+    /// The scope is bound to the current task, futures spawned within it are not in it.
+    pub async fn read_write_scope<F: Future>(&self, f: F) -> F::Output {
+        READ_WRITE_SCOPE.scope((), f).await
+    }
+
+    /// Returns if running within a [`DbPool::read_write_scope`].
+    pub fn in_read_write_scope() -> bool {
+        READ_WRITE_SCOPE.try_with(|_| ()).is_ok()
+    }
+
+    /// Returns the pool for reads, the read-write pool within a [`DbPool::read_write_scope`], the
+    /// read-only pool otherwise (or in [`DbMode::ReadOnly`] mode).
+    fn read_pool(&self) -> &Pool<Sqlite> {
+        match &self.rw_pool {
+            Some(rw_pool) if Self::in_read_write_scope() => rw_pool,
+            _ => &self.ro_pool,
+        }
     }
 
     /// Returns if the pool is closed.
     pub fn is_closed(&self) -> bool {
-        self.ro_pool.is_closed() && self.rw_pool.is_closed()
+        self.ro_pool.is_closed() && self.rw_pool.as_ref().is_none_or(Pool::is_closed)
     }
 }
 
@@ -469,6 +755,10 @@ pub trait DbData: for<'a> FromRow<'a, SqliteRow> + Send + Unpin {}
 /// The implementation delegates to the corresponding methods of the read-only pool for
 /// transactions with only read operations, and to the read-write pool for transactions
 /// with read (optional) & write operations.
+///
+/// Reads within a [`DbPool::read_write_scope`] are delegated to the read-write pool.
+///
+/// Writes fail with [`DbError::ReadOnlyMode`] in [`DbMode::ReadOnly`] mode.
 //TODO Joaquin please check lifetimes here
 impl<'c> Executor<'c> for &'_ DbPool {
     type Database = Sqlite;
@@ -481,7 +771,10 @@ impl<'c> Executor<'c> for &'_ DbPool {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
-        self.rw_pool.execute(query)
+        match &self.rw_pool {
+            Some(rw_pool) => rw_pool.execute(query),
+            None => Box::pin(async { Err(read_only_mode_error()) }),
+        }
     }
 
     fn execute_many<'e, 'q: 'e, E>(
@@ -492,7 +785,12 @@ impl<'c> Executor<'c> for &'_ DbPool {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
-        self.rw_pool.execute_many(query)
+        match &self.rw_pool {
+            Some(rw_pool) => rw_pool.execute_many(query),
+            None => Box::pin(futures_util::stream::once(async {
+                Err(read_only_mode_error())
+            })),
+        }
     }
 
     fn fetch<'e, 'q: 'e, E>(
@@ -503,7 +801,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
-        self.ro_pool.fetch(query)
+        self.read_pool().fetch(query)
     }
 
     fn fetch_many<'e, 'q: 'e, E>(
@@ -520,7 +818,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
-        self.ro_pool.fetch_many(query)
+        self.read_pool().fetch_many(query)
     }
 
     fn fetch_all<'e, 'q: 'e, E>(
@@ -531,7 +829,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
-        self.ro_pool.fetch_all(query)
+        self.read_pool().fetch_all(query)
     }
 
     fn fetch_one<'e, 'q: 'e, E>(
@@ -542,7 +840,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
-        self.ro_pool.fetch_one(query)
+        self.read_pool().fetch_one(query)
     }
 
     fn fetch_optional<'e, 'q: 'e, E>(
@@ -553,7 +851,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
         'c: 'e,
         E: 'q + Execute<'q, Self::Database>,
     {
-        self.ro_pool.fetch_optional(query)
+        self.read_pool().fetch_optional(query)
     }
 
     fn prepare<'e, 'q: 'e>(
@@ -563,7 +861,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
     where
         'c: 'e,
     {
-        self.ro_pool.prepare(query)
+        self.read_pool().prepare(query)
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -574,7 +872,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
     where
         'c: 'e,
     {
-        self.ro_pool.prepare_with(sql, parameters)
+        self.read_pool().prepare_with(sql, parameters)
     }
 
     fn describe<'e, 'q: 'e>(
@@ -584,7 +882,7 @@ impl<'c> Executor<'c> for &'_ DbPool {
     where
         'c: 'e,
     {
-        self.rw_pool.describe(sql)
+        self.rw_pool.as_ref().unwrap_or(&self.ro_pool).describe(sql)
     }
 }
 
@@ -603,7 +901,12 @@ fn remove_leading_slash(url: &str) -> String {
 #[cfg(test)]
 mod tests {
     use crate::sql;
-    use crate::sql::{Db, DbError, DbPool, remove_leading_file_protocol, remove_leading_slash};
+    use crate::sql::{
+        Db, DbError, DbMode, DbPool, TransactionRetry, remove_leading_file_protocol,
+        remove_leading_slash,
+    };
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
     use std::time::Duration;
     use te_system::edition::{Edition, TabsdataEdition};
     use testdir::testdir;
@@ -643,6 +946,32 @@ mod tests {
         assert_eq!(config.max_lifetime(), Duration::from_secs(60 * 60));
         assert_eq!(config.idle_timeout(), Duration::from_secs(60));
         assert!(config.test_before_acquire);
+        assert_eq!(config.cache_size_kb, None);
+        assert_eq!(config.temp_store, sql::SqliteTempStore::Memory);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_config_pragmas() {
+        let db_file = testdir!().join("test.db");
+        let config = sql::SqliteConfigBuilder::default()
+            .url(db_file.to_str().map(str::to_string))
+            .cache_size_kb(Some(8192))
+            .temp_store(sql::SqliteTempStore::File)
+            .build()
+            .unwrap();
+        let pool = Db::schema().rw_pool(&config).await.unwrap();
+
+        let cache_size: i64 = sqlx::query_scalar("PRAGMA cache_size")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(cache_size, -8192);
+        // 0 is default, 1 is file and 2 is memory.
+        let temp_store: i64 = sqlx::query_scalar("PRAGMA temp_store")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(temp_store, 1);
     }
 
     //TODO
@@ -885,4 +1214,223 @@ mod tests {
         let res = db.check_tabsdata_edition().await;
         assert!(matches!(res, Err(DbError::InvalidEdition(_, _))));
     }
+
+    #[tokio::test]
+    async fn test_read_write_scope_reads_own_writes() {
+        let schema = td_schema::test_schema();
+        let db_file = testdir!().join("test.db");
+        let config = sql::SqliteConfigBuilder::default()
+            .url(db_file.to_str().map(str::to_string))
+            .min_connections(1)
+            .max_connections(1)
+            .acquire_timeout(1)
+            .build()
+            .unwrap();
+        let db = DbPool::connect(&config, schema).await.unwrap();
+        db.upgrade_db_version().await.unwrap();
+
+        // hold the only read-only connection, reads through the read-only pool would time out
+        let ro_conn = db.acquire().await.unwrap();
+
+        assert!(!DbPool::in_read_write_scope());
+        let rows = db
+            .read_write_scope(async {
+                assert!(DbPool::in_read_write_scope());
+                sqlx::query("INSERT INTO foo values('a', 'A')")
+                    .execute(&db)
+                    .await?;
+                sqlx::query("SELECT * FROM foo WHERE id = 'a'")
+                    .fetch_all(&db)
+                    .await
+            })
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(!DbPool::in_read_write_scope());
+
+        // outside of the scope, reads go to the read-only pool
+        let res = sqlx::query("SELECT * FROM foo").fetch_all(&db).await;
+        assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+        drop(ro_conn);
+        let rows = sqlx::query("SELECT * FROM foo")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_with_transaction_retries_when_locked() {
+        let schema = td_schema::test_schema();
+        let db_file = testdir!().join("test.db");
+        let config = sql::SqliteConfigBuilder::default()
+            .url(db_file.to_str().map(str::to_string))
+            .build()
+            .unwrap();
+        let db = DbPool::connect(&config, schema).await.unwrap();
+        db.upgrade_db_version().await.unwrap();
+        // another pool, so its transactions contend with the ones of db
+        let other = DbPool::connect(&config, schema).await.unwrap();
+
+        let attempts = Arc::new(AtomicU32::new(0));
+        let retry = TransactionRetry::new(3, Duration::from_millis(1));
+        let res = db
+            .with_transaction_retry(&retry, |conn| {
+                let attempts = attempts.clone();
+                let other = other.clone();
+                Box::pin(async move {
+                    let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+                    sqlx::query("SELECT * FROM foo")
+                        .fetch_all(&mut *conn)
+                        .await?;
+                    if attempt == 0 {
+                        // a concurrent transaction commits after this one started reading,
+                        // so this one cannot write anymore
+                        sqlx::query("INSERT INTO foo values('b', 'B')")
+                            .execute(&other)
+                            .await?;
+                    }
+                    sqlx::query("INSERT INTO foo values('a', 'A')")
+                        .execute(&mut *conn)
+                        .await?;
+                    Ok(attempt)
+                })
+            })
+            .await
+            .unwrap();
+
+        // the first attempt was rolled back, the retry committed
+        assert_eq!(res, 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        let rows = sqlx::query("SELECT * FROM foo")
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+
+        // without retries the locked transaction fails
+        let res = db
+            .with_transaction_retry(&TransactionRetry::new(1, Duration::ZERO), |conn| {
+                let other = other.clone();
+                Box::pin(async move {
+                    sqlx::query("SELECT * FROM foo")
+                        .fetch_all(&mut *conn)
+                        .await?;
+                    sqlx::query("INSERT INTO foo values('d', 'D')")
+                        .execute(&other)
+                        .await?;
+                    sqlx::query("INSERT INTO foo values('c', 'C')")
+                        .execute(&mut *conn)
+                        .await?;
+                    Ok(())
+                })
+            })
+            .await;
+        assert!(super::is_database_locked(&res.unwrap_err()));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_fallback() {
+        let schema = td_schema::test_schema();
+        let db_file = testdir!().join("test.db");
+        let config = sql::SqliteConfigBuilder::default()
+            .url(db_file.to_str().map(str::to_string))
+            .read_only_fallback(true)
+            .build()
+            .unwrap();
+        let db = DbPool::connect(&config, schema).await.unwrap();
+        assert_eq!(db.mode(), DbMode::ReadWrite);
+        db.upgrade_db_version().await.unwrap();
+        sqlx::query("INSERT INTO foo values('a', 'A')")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        // another writer holding the write lock makes the database not writable
+        let mut writer = db.read_write_pool().unwrap().acquire().await.unwrap();
+        let _lock = sqlx::Connection::begin_with(&mut *writer, "BEGIN IMMEDIATE")
+            .await
+            .unwrap();
+
+        let read_only_db = DbPool::connect(&config, schema).await.unwrap();
+        assert_eq!(read_only_db.mode(), DbMode::ReadOnly);
+
+        // reads are served by the read-only pool
+        let rows = sqlx::query("SELECT * FROM foo")
+            .fetch_all(&read_only_db)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+
+        // writes fail with the read-only mode error
+        let is_read_only_mode = |err: &sqlx::Error| match err {
+            sqlx::Error::Configuration(err) => {
+                matches!(err.downcast_ref::<DbError>(), Some(DbError::ReadOnlyMode))
+            }
+            _ => false,
+        };
+        let res = sqlx::query("INSERT INTO foo values('b', 'B')")
+            .execute(&read_only_db)
+            .await;
+        assert!(matches!(res, Err(ref err) if is_read_only_mode(err)));
+        assert!(matches!(read_only_db.begin().await, Err(ref err) if is_read_only_mode(err)));
+        assert!(matches!(
+            read_only_db.upgrade().await,
+            Err(DbError::ReadOnlyMode)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_warm_up() {
+        let schema = td_schema::test_schema();
+        let db_file = testdir!().join("test.db");
+        let config = sql::SqliteConfigBuilder::default()
+            .url(db_file.to_str().map(str::to_string))
+            .min_connections(3)
+            .warm_up(true)
+            .build()
+            .unwrap();
+        let db = DbPool::create(&config, schema).await.unwrap();
+        assert_eq!(db.ro_pool.size(), 3);
+        assert_eq!(db.ro_pool.num_idle(), 3);
+        db.ro_pool.close().await;
+        db.read_write_pool().unwrap().close().await;
+
+        let db = DbPool::connect(&config, schema).await.unwrap();
+        assert_eq!(db.ro_pool.size(), 3);
+        assert_eq!(db.ro_pool.num_idle(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_disk_full() {
+        let config = sql::SqliteConfigBuilder::default()
+            .url(testdir!().join("test.db").to_str().map(str::to_string))
+            .build()
+            .unwrap();
+        let pool = Db::schema().rw_pool(&config).await.unwrap();
+        sqlx::query("CREATE TABLE foo (value TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // The maximum is capped to the current size of the database, so it cannot grow.
+        sqlx::query("PRAGMA max_page_count = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let err = sqlx::query("INSERT INTO foo VALUES (?)")
+            .bind("x".repeat(64 * 1024))
+            .execute(&pool)
+            .await
+            .unwrap_err();
+        assert!(sql::is_disk_full(&err));
+        assert!(matches!(DbError::from_sql(err), DbError::DiskFull(_)));
+
+        let err = sqlx::query("SELECT * FROM bar")
+            .execute(&pool)
+            .await
+            .unwrap_err();
+        assert!(!sql::is_disk_full(&err));
+        assert!(matches!(DbError::from_sql(err), DbError::SqlError(_)));
+    }
 }
@@ -13,19 +13,44 @@ macro_rules! display_vec {
     };
 }
 
+const DEFAULT_SEPARATOR: &str = ", ";
+
 /// A wrapper around `Vec<T>` that implements `Display` and `Debug` traits so inner types
 /// can be printed using `Display` and not `Debug`.
-pub struct DisplayVec<T>(Vec<T>);
+///
+/// Items are joined with `, ` unless another separator is set. If a max items cap is set, only
+/// that many items are printed, followed by `... (N more)`.
+pub struct DisplayVec<T> {
+    vec: Vec<T>,
+    separator: &'static str,
+    max_items: Option<usize>,
+}
 
 impl<T> DisplayVec<T> {
     pub fn new(vec: Vec<T>) -> Self {
-        Self(vec)
+        Self {
+            vec,
+            separator: DEFAULT_SEPARATOR,
+            max_items: None,
+        }
+    }
+
+    /// Sets the separator used between items.
+    pub fn with_separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets the maximum number of items printed.
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = Some(max_items);
+        self
     }
 }
 
 impl<T> From<Vec<T>> for DisplayVec<T> {
     fn from(vec: Vec<T>) -> Self {
-        Self(vec)
+        Self::new(vec)
     }
 }
 
@@ -33,7 +58,7 @@ impl<T> Deref for DisplayVec<T> {
     type Target = Vec<T>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.vec
     }
 }
 
@@ -45,7 +70,18 @@ impl<T: Display> Debug for DisplayVec<T> {
 
 impl<T: Display> Display for DisplayVec<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.iter().join(", "))
+        match self.max_items {
+            Some(max_items) if self.len() > max_items => {
+                write!(
+                    f,
+                    "{}{}... ({} more)",
+                    self.iter().take(max_items).join(self.separator),
+                    if max_items > 0 { self.separator } else { "" },
+                    self.len() - max_items
+                )
+            }
+            _ => write!(f, "{}", self.iter().join(self.separator)),
+        }
     }
 }
 
@@ -100,6 +136,32 @@ mod tests {
         assert_eq!(sum, 6);
     }
 
+    #[test]
+    fn test_display_separator() {
+        let vec = DisplayVec::new(vec![1, 2, 3]).with_separator(" | ");
+        assert_eq!(format!("{vec}"), "1 | 2 | 3");
+    }
+
+    #[test]
+    fn test_display_max_items() {
+        let vec = DisplayVec::new((0..500).collect()).with_max_items(3);
+        assert_eq!(format!("{vec}"), "0, 1, 2, ... (497 more)");
+
+        let vec = DisplayVec::new(vec![1, 2, 3])
+            .with_separator("; ")
+            .with_max_items(2);
+        assert_eq!(format!("{vec:?}"), "1; 2; ... (1 more)");
+
+        let vec = DisplayVec::new(vec![1, 2, 3]).with_max_items(0);
+        assert_eq!(format!("{vec}"), "... (3 more)");
+    }
+
+    #[test]
+    fn test_display_max_items_not_exceeded() {
+        let vec = DisplayVec::new(vec![1, 2, 3]).with_max_items(3);
+        assert_eq!(format!("{vec}"), "1, 2, 3");
+    }
+
     #[test]
     fn test_display_uses_display_trait() {
         struct CustomStruct;
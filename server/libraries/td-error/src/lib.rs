@@ -28,7 +28,11 @@ pub enum ApiError {
     InternalError = 5000,
     /// Discriminants from 6000 to 6999 are reserved for not implemented errors
     NotImplemented = 6000,
-    /// Discriminants from 7000 to u16::MAX are unexpected
+    /// Discriminants from 7000 to 7999 are reserved for unavailable errors (retryable)
+    Unavailable = 7000,
+    /// Discriminants from 8000 to 8999 are reserved for conflict errors
+    Conflict = 8000,
+    /// Discriminants from 9000 to u16::MAX are unexpected
     Unexpected = u16::MAX as isize,
 }
 
@@ -42,6 +46,8 @@ impl From<u16> for ApiError {
             i if i < Self::NotAuthorized as u16 + 1000 => Self::NotAuthorized,
             i if i < Self::InternalError as u16 + 1000 => Self::InternalError,
             i if i < Self::NotImplemented as u16 + 1000 => Self::NotImplemented,
+            i if i < Self::Unavailable as u16 + 1000 => Self::Unavailable,
+            i if i < Self::Conflict as u16 + 1000 => Self::Conflict,
             _i => Self::Unexpected,
         }
     }
@@ -236,6 +242,26 @@ pub async fn assert_service_error<Req, Res, Err: TdDomainError + 'static>(
     }
 }
 
+/// Asserts a service invocation error code.
+///
+/// The invocation of the given `service` with the given `request` is expected to fail with a
+/// [`TdError`] with the `expected_code` code, regardless of its [`TdDomainError`] type.
+pub async fn assert_service_error_code<Req, Res>(
+    service: tower::util::BoxService<Req, Res, TdError>,
+    request: Req,
+    expected_code: &str,
+) {
+    match tower::ServiceExt::oneshot(service, request).await {
+        Ok(_) => panic!("Service is expected to error"),
+        Err(err) => assert_eq!(
+            err.code(),
+            expected_code,
+            "error code expected '{expected_code}', but got '{}': {err}",
+            err.code()
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate as td_error;
@@ -253,6 +279,8 @@ mod tests {
         assert_eq!(ApiError::NotAuthorized as u16, 4000);
         assert_eq!(ApiError::InternalError as u16, 5000);
         assert_eq!(ApiError::NotImplemented as u16, 6000);
+        assert_eq!(ApiError::Unavailable as u16, 7000);
+        assert_eq!(ApiError::Conflict as u16, 8000);
         assert_eq!(ApiError::Unexpected as u16, u16::MAX);
 
         assert_eq!(ApiError::from(0), ApiError::InputError);
@@ -269,7 +297,11 @@ mod tests {
         assert_eq!(ApiError::from(5999), ApiError::InternalError);
         assert_eq!(ApiError::from(6000), ApiError::NotImplemented);
         assert_eq!(ApiError::from(6999), ApiError::NotImplemented);
-        assert_eq!(ApiError::from(7000), ApiError::Unexpected);
+        assert_eq!(ApiError::from(7000), ApiError::Unavailable);
+        assert_eq!(ApiError::from(7999), ApiError::Unavailable);
+        assert_eq!(ApiError::from(8000), ApiError::Conflict);
+        assert_eq!(ApiError::from(8999), ApiError::Conflict);
+        assert_eq!(ApiError::from(9000), ApiError::Unexpected);
         assert_eq!(ApiError::from(u16::MAX), ApiError::Unexpected);
     }
 
@@ -325,4 +357,29 @@ mod tests {
             .downcast_ref::<MyErrorB>()
             .unwrap();
     }
+
+    fn failing_service() -> tower::util::BoxService<(), (), TdError> {
+        tower::util::BoxService::new(tower::service_fn(|_: ()| async {
+            Err::<(), _>(TdError::new(MyErrorA::A1("foo".to_string())))
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_assert_service_error_code() {
+        assert_service_error_code(failing_service(), (), "MyErrorA::1000").await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "error code expected 'MyErrorA::0000', but got 'MyErrorA::1000'")]
+    async fn test_assert_service_error_code_mismatch() {
+        assert_service_error_code(failing_service(), (), "MyErrorA::0000").await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Service is expected to error")]
+    async fn test_assert_service_error_code_no_error() {
+        let service =
+            tower::util::BoxService::new(tower::service_fn(|_: ()| async { Ok::<_, TdError>(()) }));
+        assert_service_error_code(service, (), "MyErrorA::1000").await;
+    }
 }
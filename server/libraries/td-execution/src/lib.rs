@@ -2,5 +2,7 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+pub mod link;
+pub mod parameters;
 pub mod planner;
 pub mod version_resolver;
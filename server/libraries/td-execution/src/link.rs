@@ -0,0 +1,113 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use std::collections::HashSet;
+use td_error::{TdError, td_error};
+use td_objects::execution::graph::GraphNode;
+
+#[td_error]
+pub enum LinkError {
+    #[error("Node [{0}] cannot be linked to itself.")]
+    SelfLink(GraphNode) = 0,
+    #[error("Invalid link from [{0}] to [{1}], links must go between a function and a table.")]
+    InvalidDirection(GraphNode, GraphNode),
+}
+
+/// Directed link between two graph nodes, validated before planning. Functions link to the
+/// tables they output, and tables link to the functions they trigger or are a dependency of.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Link {
+    source: GraphNode,
+    target: GraphNode,
+}
+
+impl Link {
+    pub fn new(source: GraphNode, target: GraphNode) -> Self {
+        Self { source, target }
+    }
+
+    pub fn source(&self) -> &GraphNode {
+        &self.source
+    }
+
+    pub fn target(&self) -> &GraphNode {
+        &self.target
+    }
+
+    /// Validates the link is not a self-link, and that it goes between a function and a table.
+    pub fn validate(&self) -> Result<(), TdError> {
+        if self.source == self.target {
+            Err(LinkError::SelfLink(self.source.clone()))?
+        }
+        match (&self.source, &self.target) {
+            (GraphNode::Function(_), GraphNode::Table(_))
+            | (GraphNode::Table(_), GraphNode::Function(_)) => Ok(()),
+            (source, target) => Err(LinkError::InvalidDirection(source.clone(), target.clone()))?,
+        }
+    }
+
+    /// Validates all links, collapsing duplicates and keeping the first occurrence order.
+    pub fn validate_all(links: impl IntoIterator<Item = Link>) -> Result<Vec<Link>, TdError> {
+        let mut seen = HashSet::new();
+        let mut deduped = vec![];
+        for link in links {
+            link.validate()?;
+            if seen.insert(link.clone()) {
+                deduped.push(link);
+            }
+        }
+        Ok(deduped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use td_objects::test_utils::graph::{FUNCTION_NAMES, TABLE_NAMES, function_node, table_node};
+
+    fn function(index: usize) -> GraphNode {
+        GraphNode::Function(function_node(&FUNCTION_NAMES[index]))
+    }
+
+    fn table(index: usize) -> GraphNode {
+        GraphNode::Table(table_node(&TABLE_NAMES[index]))
+    }
+
+    #[test]
+    fn test_valid_link() {
+        assert!(Link::new(function(0), table(0)).validate().is_ok());
+        assert!(Link::new(table(0), function(1)).validate().is_ok());
+    }
+
+    #[test]
+    fn test_self_link() {
+        let err = Link::new(table(0), table(0)).validate().unwrap_err();
+        let err = err.domain_err::<LinkError>();
+        assert!(matches!(err, LinkError::SelfLink(_)));
+    }
+
+    #[test]
+    fn test_invalid_direction() {
+        let err = Link::new(function(0), function(1)).validate().unwrap_err();
+        let err = err.domain_err::<LinkError>();
+        assert!(matches!(err, LinkError::InvalidDirection(_, _)));
+    }
+
+    #[test]
+    fn test_duplicate_links_collapsed() {
+        let links = vec![
+            Link::new(function(0), table(0)),
+            Link::new(table(0), function(1)),
+            Link::new(function(0), table(0)),
+        ];
+        let links = Link::validate_all(links).unwrap();
+        assert_eq!(
+            links,
+            vec![
+                Link::new(function(0), table(0)),
+                Link::new(table(0), function(1)),
+            ]
+        );
+    }
+}
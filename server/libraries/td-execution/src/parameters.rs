@@ -0,0 +1,248 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use derive_builder::Builder;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use td_error::display_vec::DisplayVec;
+use td_error::{TdError, td_error};
+
+#[td_error]
+pub enum ParamError {
+    #[error("Invalid function parameters: [{0}]")]
+    InvalidParameters(DisplayVec<ParamViolation>) = 0,
+}
+
+/// A single parameter validation failure. All of them are collected into
+/// [`ParamError::InvalidParameters`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ParamViolation {
+    #[error("parameter '{0}' is unknown")]
+    Unknown(String),
+    #[error("parameter '{0}' is required")]
+    Missing(String),
+    #[error("parameter '{0}' must be of type {1}, got '{2}'")]
+    InvalidType(String, ParamType, ParamValue),
+    #[error("parameter '{0}' value '{1}' is not one of [{2}]")]
+    NotAllowed(String, ParamValue, String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ParamType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl ParamValue {
+    pub fn param_type(&self) -> ParamType {
+        match self {
+            ParamValue::String(_) => ParamType::String,
+            ParamValue::Integer(_) => ParamType::Integer,
+            ParamValue::Float(_) => ParamType::Float,
+            ParamValue::Boolean(_) => ParamType::Boolean,
+        }
+    }
+}
+
+impl Display for ParamValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamValue::String(v) => write!(f, "{v}"),
+            ParamValue::Integer(v) => write!(f, "{v}"),
+            ParamValue::Float(v) => write!(f, "{v}"),
+            ParamValue::Boolean(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// Declaration of a function parameter. Optional parameters without a default are left out of
+/// the resolved parameters if not given.
+#[derive(Debug, Clone, Builder)]
+#[builder(setter(into), build_fn(error = "TdError"))]
+pub struct ParamDef {
+    name: String,
+    param_type: ParamType,
+    #[builder(default = "false")]
+    required: bool,
+    #[builder(default, setter(strip_option))]
+    default: Option<ParamValue>,
+    #[builder(default)]
+    allowed_values: Vec<ParamValue>,
+}
+
+impl ParamDef {
+    pub fn builder() -> ParamDefBuilder {
+        ParamDefBuilder::default()
+    }
+
+    fn check(&self, value: &ParamValue, violations: &mut Vec<ParamViolation>) {
+        if value.param_type() != self.param_type {
+            violations.push(ParamViolation::InvalidType(
+                self.name.clone(),
+                self.param_type,
+                value.clone(),
+            ));
+        } else if !self.allowed_values.is_empty() && !self.allowed_values.contains(value) {
+            violations.push(ParamViolation::NotAllowed(
+                self.name.clone(),
+                value.clone(),
+                DisplayVec::from(self.allowed_values.clone()).to_string(),
+            ));
+        }
+    }
+}
+
+/// Parameters after validation, with defaults applied.
+pub type ResolvedParameters = HashMap<String, ParamValue>;
+
+/// Set of parameter declarations of a function.
+#[derive(Debug, Clone, Default)]
+pub struct ParamSchema(Vec<ParamDef>);
+
+impl ParamSchema {
+    pub fn new(params: Vec<ParamDef>) -> Self {
+        Self(params)
+    }
+
+    /// Validates the given inputs, applying defaults to missing parameters. All violations are
+    /// collected and returned in a single [`ParamError::InvalidParameters`] error.
+    pub fn validate(
+        &self,
+        inputs: &HashMap<String, ParamValue>,
+    ) -> Result<ResolvedParameters, TdError> {
+        let mut violations = vec![];
+
+        let mut unknown: Vec<_> = inputs
+            .keys()
+            .filter(|name| !self.0.iter().any(|p| &p.name == *name))
+            .cloned()
+            .collect();
+        unknown.sort();
+        violations.extend(unknown.into_iter().map(ParamViolation::Unknown));
+
+        let mut resolved = ResolvedParameters::new();
+        for param in &self.0 {
+            match inputs.get(&param.name).or(param.default.as_ref()) {
+                Some(value) => {
+                    param.check(value, &mut violations);
+                    resolved.insert(param.name.clone(), value.clone());
+                }
+                None if param.required => {
+                    violations.push(ParamViolation::Missing(param.name.clone()))
+                }
+                None => {}
+            }
+        }
+
+        if !violations.is_empty() {
+            Err(ParamError::InvalidParameters(violations.into()))?
+        }
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema() -> ParamSchema {
+        ParamSchema::new(vec![
+            ParamDef::builder()
+                .name("mode")
+                .param_type(ParamType::String)
+                .default(ParamValue::String("full".to_string()))
+                .allowed_values(vec![
+                    ParamValue::String("full".to_string()),
+                    ParamValue::String("incremental".to_string()),
+                ])
+                .build()
+                .unwrap(),
+            ParamDef::builder()
+                .name("limit")
+                .param_type(ParamType::Integer)
+                .required(true)
+                .build()
+                .unwrap(),
+        ])
+    }
+
+    fn violations(err: TdError) -> Vec<ParamViolation> {
+        match err.domain_err::<ParamError>() {
+            ParamError::InvalidParameters(violations) => violations.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_default_missing_optional_param() -> Result<(), TdError> {
+        let inputs = HashMap::from([("limit".to_string(), ParamValue::Integer(10))]);
+        let resolved = schema().validate(&inputs)?;
+        assert_eq!(
+            resolved.get("mode"),
+            Some(&ParamValue::String("full".to_string()))
+        );
+        assert_eq!(resolved.get("limit"), Some(&ParamValue::Integer(10)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_param() {
+        let inputs = HashMap::from([
+            ("limit".to_string(), ParamValue::Integer(10)),
+            ("other".to_string(), ParamValue::Boolean(true)),
+        ]);
+        let err = schema().validate(&inputs).unwrap_err();
+        assert_eq!(
+            violations(err),
+            vec![ParamViolation::Unknown("other".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_not_allowed_value() {
+        let inputs = HashMap::from([
+            ("limit".to_string(), ParamValue::Integer(10)),
+            (
+                "mode".to_string(),
+                ParamValue::String("partial".to_string()),
+            ),
+        ]);
+        let err = schema().validate(&inputs).unwrap_err();
+        let violations = violations(err);
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(
+            &violations[0],
+            ParamViolation::NotAllowed(name, ParamValue::String(value), _)
+                if name == "mode" && value == "partial"
+        ));
+    }
+
+    #[test]
+    fn test_collects_all_violations() {
+        let inputs = HashMap::from([
+            ("mode".to_string(), ParamValue::Integer(1)),
+            ("other".to_string(), ParamValue::Boolean(true)),
+        ]);
+        let err = schema().validate(&inputs).unwrap_err();
+        let violations = violations(err);
+        assert_eq!(violations.len(), 3);
+        assert!(violations.contains(&ParamViolation::Unknown("other".to_string())));
+        assert!(violations.contains(&ParamViolation::Missing("limit".to_string())));
+        assert!(violations.contains(&ParamViolation::InvalidType(
+            "mode".to_string(),
+            ParamType::String,
+            ParamValue::Integer(1)
+        )));
+    }
+}
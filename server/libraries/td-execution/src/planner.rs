@@ -9,7 +9,11 @@ use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::hash::Hash;
 use ta_execution::graphs::ExecutionGraph;
-use td_objects::execution::graph::{FunctionNode, GraphEdge, GraphNode, TableNode};
+use td_error::TdError;
+use td_objects::execution::graph::{
+    FunctionNode, GraphEdge, GraphNode, ResolvedVersion, TableNode,
+};
+use td_objects::types::basic::RowCount;
 use te_execution::planner::TriggerPlanner;
 
 /// The `ExecutionPlanner` trait is used to define the execution plan for the type implementing it.
@@ -207,15 +211,69 @@ impl<V: Eq + Hash> ExecutionPlanner<V> for ExecutionGraph<V> {
     }
 }
 
+/// The `PlanEstimator` trait estimates the work a resolved plan entails, using the table stats
+/// stored when the resolved table data versions were written.
+pub trait PlanEstimator {
+    /// Returns the estimated input row count of each function in the plan (including the main
+    /// function), adding up the rows of all its resolved dependencies. Versions that do not exist
+    /// add no rows, and it is `None` (unknown) if any existing version has no stats.
+    fn estimated_input_rows(&self) -> Result<HashMap<&FunctionNode, Option<RowCount>>, TdError>;
+
+    /// Returns the estimated total row count of the plan, `None` (unknown) if any function
+    /// estimate is unknown.
+    fn estimated_total_rows(&self) -> Result<Option<RowCount>, TdError> {
+        let total = self
+            .estimated_input_rows()?
+            .into_values()
+            .try_fold(0, |acc, rows| rows.map(|rows| acc + *rows));
+        total.map(RowCount::try_from).transpose()
+    }
+}
+
+impl PlanEstimator for ExecutionGraph<ResolvedVersion> {
+    fn estimated_input_rows(&self) -> Result<HashMap<&FunctionNode, Option<RowCount>>, TdError> {
+        let mut estimates: HashMap<_, _> = self
+            .triggered_functions()
+            .into_iter()
+            .chain(std::iter::once(self.manual_trigger_function()))
+            .map(|function| (function, Some(0)))
+            .collect();
+
+        for (function, _, edge) in self.function_version_requirements() {
+            let GraphEdge::Dependency { versions, .. } = edge else {
+                continue;
+            };
+            let rows = versions.inner.iter().flatten().try_fold(0, |acc, version| {
+                version
+                    .with_data_row_count
+                    .as_ref()
+                    .map(|rows| acc + **rows)
+            });
+            let estimate = estimates.entry(function).or_insert(Some(0));
+            *estimate = estimate.zip(rows).map(|(estimate, rows)| estimate + rows);
+        }
+
+        estimates
+            .into_iter()
+            .map(|(function, rows)| Ok((function, rows.map(RowCount::try_from).transpose()?)))
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::collections::HashSet;
     use ta_execution::test_utils::graph::test_graph;
-    use td_error::TdError;
+    use td_objects::dxo::table_data_version::ExecutionTableDataVersionRead;
     use td_objects::test_utils::graph::{
         FUNCTION_NAMES, FUNCTIONS, TABLE_NAMES, function_node, table_node,
     };
+    use td_objects::types::basic::{
+        ExecutionId, FunctionRunId, FunctionRunStatus, TableDataVersionId, TransactionId,
+        TriggeredOn, UserId,
+    };
+    use td_objects::types::composed::TableVersions;
 
     #[tokio::test]
     async fn test_functions() -> Result<(), TdError> {
@@ -331,4 +389,72 @@ mod tests {
         assert_eq!(new_graph.tables().len(), TABLE_NAMES.len());
         Ok(())
     }
+
+    async fn resolved_graph(
+        row_count: Option<i64>,
+    ) -> Result<ExecutionGraph<ResolvedVersion>, TdError> {
+        let (graph, _) = test_graph().await;
+        graph
+            .versioned(|table, _, _| async move {
+                // Seeded table stats, as stored at write time.
+                let version = ExecutionTableDataVersionRead::builder()
+                    .id(TableDataVersionId::default())
+                    .collection_id(table.collection_id)
+                    .table_id(table.table_id)
+                    .name(table.name.clone())
+                    .table_version_id(table.table_version_id)
+                    .function_version_id(table.function_version_id)
+                    .has_data(None)
+                    .execution_id(ExecutionId::default())
+                    .transaction_id(TransactionId::default())
+                    .function_run_id(FunctionRunId::default())
+                    .function_param_pos(None)
+                    .triggered_on(TriggeredOn::now())
+                    .triggered_by_id(UserId::default())
+                    .started_on(None)
+                    .ended_on(None)
+                    .status(FunctionRunStatus::Committed)
+                    .with_data_table_data_version_id(None)
+                    .with_data_row_count(row_count.map(RowCount::try_from).transpose()?)
+                    .build()?;
+                ResolvedVersion::builder()
+                    .inner(vec![Some(version)])
+                    .original(TableVersions::try_from("HEAD")?)
+                    .build()
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_estimated_rows() -> Result<(), TdError> {
+        let graph = resolved_graph(Some(42)).await?;
+
+        // function_0 has no dependencies, function_1 depends on table_0.
+        let estimates = graph.estimated_input_rows()?;
+        assert_eq!(estimates.len(), FUNCTION_NAMES.len());
+        assert_eq!(
+            estimates[&function_node(&FUNCTION_NAMES[0])],
+            Some(RowCount::try_from(0)?)
+        );
+        assert_eq!(
+            estimates[&function_node(&FUNCTION_NAMES[1])],
+            Some(RowCount::try_from(42)?)
+        );
+        assert_eq!(graph.estimated_total_rows()?, Some(RowCount::try_from(42)?));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_estimated_rows_unknown() -> Result<(), TdError> {
+        let graph = resolved_graph(None).await?;
+
+        let estimates = graph.estimated_input_rows()?;
+        assert_eq!(
+            estimates[&function_node(&FUNCTION_NAMES[0])],
+            Some(RowCount::try_from(0)?)
+        );
+        assert_eq!(estimates[&function_node(&FUNCTION_NAMES[1])], None);
+        assert_eq!(graph.estimated_total_rows()?, None);
+        Ok(())
+    }
 }
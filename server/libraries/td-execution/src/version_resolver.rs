@@ -22,6 +22,8 @@ pub enum VersionResolverError {
     FixedTableDataVersionsNotFound(DisplayVec<TableDataVersionId>) = 0,
     #[error("In a range, the left version must be older than the right version.")]
     InvalidRange(Versions),
+    #[error("Expected the latest {0} table data versions, but only {1} exist.")]
+    NotEnoughVersions(usize, usize),
 }
 
 /// Struct to resolve table data versions. It will resolve relative and fixed versions, using
@@ -334,6 +336,56 @@ impl<'a> VersionResolver<'a> {
     }
 }
 
+/// Struct to resolve the latest `count` table data versions window, newest to oldest, for any
+/// triggered_on. Fewer than `count` existing versions is an error, unless `allow_partial` is set.
+#[derive(Debug, Builder)]
+#[builder(build_fn(error = "TdError"))]
+pub struct LatestVersionsResolver<'a> {
+    table_id: &'a TableId,
+    count: usize,
+    triggered_on: &'a TriggeredOn,
+    #[builder(default = "false")]
+    allow_partial: bool,
+}
+
+impl<'a> LatestVersionsResolver<'a> {
+    pub fn builder() -> LatestVersionsResolverBuilder<'a> {
+        LatestVersionsResolverBuilder::default()
+    }
+
+    pub async fn resolve(
+        &self,
+        queries: &DaoQueries,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<ActiveTableDataVersionDB>, TdError> {
+        if self.count == 0 {
+            return Ok(vec![]);
+        }
+
+        // The window is the HEAD range of the given size.
+        let versions = Versions::Range(Version::Head(-(self.count as isize - 1)), Version::Head(0));
+        let found: Vec<_> = VersionResolver::builder()
+            .table_id(self.table_id)
+            .versions(&versions)
+            .triggered_on(self.triggered_on)
+            .build()?
+            .resolve(queries, conn)
+            .await?
+            .into_iter()
+            .rev()
+            .flatten()
+            .collect();
+
+        if found.len() < self.count && !self.allow_partial {
+            Err(VersionResolverError::NotEnoughVersions(
+                self.count,
+                found.len(),
+            ))?
+        }
+        Ok(found)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1957,4 +2009,74 @@ mod tests {
         ));
         Ok(())
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_resolve_latest_window(db: DbPool) -> Result<(), TdError> {
+        let table_name = TableNameDto::try_from("joaquin")?;
+        let table_data_versions =
+            seed_table_data_versions(&db, HashMap::from([(&table_name, 5)])).await;
+        let table_data_versions = table_data_versions.get(&table_name).unwrap();
+        let table_id = table_data_versions[0].table_id;
+
+        let mut conn = db.acquire().await.unwrap();
+        let triggered_on = TriggeredOn::now();
+        let versions_found = LatestVersionsResolver::builder()
+            .table_id(&table_id)
+            .count(3)
+            .triggered_on(&triggered_on)
+            .build()?
+            .resolve(&DaoQueries::default(), &mut conn)
+            .await?;
+
+        // Newest to oldest.
+        let ids: Vec<_> = versions_found.iter().map(|v| v.id).collect();
+        let expected: Vec<_> = table_data_versions
+            .iter()
+            .rev()
+            .take(3)
+            .map(|v| v.id)
+            .collect();
+        assert_eq!(ids, expected);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_resolve_latest_window_partial(db: DbPool) -> Result<(), TdError> {
+        let table_name = TableNameDto::try_from("joaquin")?;
+        let table_data_versions =
+            seed_table_data_versions(&db, HashMap::from([(&table_name, 2)])).await;
+        let table_data_versions = table_data_versions.get(&table_name).unwrap();
+        let table_id = table_data_versions[0].table_id;
+
+        let mut conn = db.acquire().await.unwrap();
+        let triggered_on = TriggeredOn::now();
+
+        // Not enough versions.
+        let err = LatestVersionsResolver::builder()
+            .table_id(&table_id)
+            .count(3)
+            .triggered_on(&triggered_on)
+            .build()?
+            .resolve(&DaoQueries::default(), &mut conn)
+            .await
+            .unwrap_err();
+        let err = err.domain_err::<VersionResolverError>();
+        assert!(matches!(err, VersionResolverError::NotEnoughVersions(3, 2)));
+
+        // Partial window allowed.
+        let versions_found = LatestVersionsResolver::builder()
+            .table_id(&table_id)
+            .count(3)
+            .triggered_on(&triggered_on)
+            .allow_partial(true)
+            .build()?
+            .resolve(&DaoQueries::default(), &mut conn)
+            .await?;
+        let ids: Vec<_> = versions_found.iter().map(|v| v.id).collect();
+        let expected: Vec<_> = table_data_versions.iter().rev().map(|v| v.id).collect();
+        assert_eq!(ids, expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,121 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[td_type::dxo]
+mod definitions {
+    use crate::dxo::crudl::RequestContext;
+    use crate::types::basic::{
+        ApiKeyHash, ApiKeyId, ApiKeyName, ApiKeyPrefix, ApiKeyScopeId, ApiKeyScoped, ApiKeyStatus,
+        ApiKeyToken, AtTime, EntityId, EntityName, PermissionEntityType, PermissionType, RoleId,
+        RoleName, UserEnabled, UserHasRole, UserId, UserName,
+    };
+
+    /// Long-lived key authenticating requests as the user and role it was issued for.
+    ///
+    /// Only the hash of the key is stored, the key itself is returned once, when issued.
+    #[td_type::Dao]
+    #[dao(sql_table = "api_keys")]
+    #[td_type(updater(try_from = RequestContext, skip_all))]
+    pub struct ApiKeyDB {
+        #[td_type(extractor)]
+        #[builder(default)]
+        pub id: ApiKeyId,
+        pub name: ApiKeyName,
+        pub prefix: ApiKeyPrefix,
+        pub hash: ApiKeyHash,
+        #[td_type(updater(try_from = RequestContext, field = "user_id"))]
+        pub user_id: UserId,
+        #[td_type(updater(try_from = RequestContext, field = "role_id"))]
+        pub role_id: RoleId,
+        #[td_type(updater(try_from = RequestContext, field = "time"))]
+        pub created_on: AtTime,
+        #[builder(default = "ApiKeyStatus::Active")]
+        pub status: ApiKeyStatus,
+        #[td_type(updater(try_from = RequestContext, field = "time"))]
+        pub status_change_on: AtTime,
+        /// If the key is restricted to its [`ApiKeyScopeDB`]s, none meaning no permissions.
+        #[builder(default)]
+        pub scoped: ApiKeyScoped,
+    }
+
+    #[td_type::Dao]
+    #[dao(sql_table = "api_keys__with_names")]
+    #[inherits(ApiKeyDB)]
+    pub struct ApiKeyDBWithNames {
+        pub user_name: UserName,
+        pub user_enabled: UserEnabled,
+        pub role_name: RoleName,
+        pub user_has_role: UserHasRole,
+    }
+
+    #[td_type::Dao]
+    #[dao(sql_table = "api_keys")]
+    pub struct ApiKeyRevokeDB {
+        #[td_type(setter)]
+        pub status_change_on: AtTime,
+        #[builder(default = "ApiKeyStatus::Revoked")]
+        pub status: ApiKeyStatus,
+    }
+
+    /// Permission an API key is restricted to, it must be a permission of the key role.
+    #[td_type::Dao]
+    #[dao(sql_table = "api_key_scopes")]
+    pub struct ApiKeyScopeDB {
+        #[builder(default)]
+        pub id: ApiKeyScopeId,
+        #[td_type(extractor)]
+        pub api_key_id: ApiKeyId,
+        pub permission_type: PermissionType,
+        pub entity_type: PermissionEntityType,
+        pub entity_id: EntityId,
+    }
+
+    #[td_type::Dto]
+    pub struct ApiKeyCreate {
+        #[td_type(extractor)]
+        pub name: ApiKeyName,
+        /// Permissions to restrict the key to, if not given the key has all the role permissions.
+        #[td_type(extractor)]
+        #[builder(default)]
+        pub scope: Option<Vec<ApiKeyScopeCreate>>,
+    }
+
+    #[td_type::Dto]
+    pub struct ApiKeyScopeCreate {
+        pub permission_type: PermissionType,
+        pub entity_name: Option<EntityName>, // None means ALL
+    }
+
+    /// Issued API key, the only time the key is available.
+    #[td_type::Dto]
+    pub struct ApiKeyCreated {
+        pub id: ApiKeyId,
+        pub name: ApiKeyName,
+        pub prefix: ApiKeyPrefix,
+        /// Key to authenticate with, as `Authorization: ApiKey <api_key>`.
+        pub api_key: ApiKeyToken,
+        pub role_id: RoleId,
+        pub created_on: AtTime,
+    }
+
+    #[td_type::Dto]
+    #[dto(list(on = ApiKeyDB))]
+    #[td_type(builder(try_from = ApiKeyDB))]
+    pub struct ApiKey {
+        #[dto(list(pagination_by = "+", filter))]
+        pub id: ApiKeyId,
+        #[dto(list(filter, filter_like, order_by))]
+        pub name: ApiKeyName,
+        #[dto(list(filter))]
+        pub prefix: ApiKeyPrefix,
+        pub user_id: UserId,
+        #[dto(list(filter))]
+        pub role_id: RoleId,
+        #[dto(list(order_by))]
+        pub created_on: AtTime,
+        #[dto(list(filter))]
+        pub status: ApiKeyStatus,
+        pub status_change_on: AtTime,
+    }
+}
@@ -0,0 +1,33 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[td_type::dxo]
+mod definitions {
+    use crate::types::basic::{
+        AtTime, AuditAction, AuditEntity, AuditLogId, AuditOutcome, ErrorCode, RequestId, RoleId,
+        ServiceName, UserId,
+    };
+
+    /// Invocation of a service changing entities. The `audit_log` table is append only.
+    #[td_type::Dao]
+    #[dao(sql_table = "audit_log")]
+    pub struct AuditLogDB {
+        #[builder(default)]
+        pub id: AuditLogId,
+        #[builder(default)]
+        pub request_id: Option<RequestId>,
+        #[builder(default)]
+        pub user_id: Option<UserId>,
+        #[builder(default)]
+        pub role_id: Option<RoleId>,
+        pub action: AuditAction,
+        pub service: ServiceName,
+        #[builder(default)]
+        pub entity: Option<AuditEntity>,
+        pub outcome: AuditOutcome,
+        #[builder(default)]
+        pub error_code: Option<ErrorCode>,
+        pub audited_on: AtTime,
+    }
+}
@@ -5,7 +5,10 @@
 #[td_type::dxo]
 mod definitions {
     use crate::dxo::crudl::RequestContext;
-    use crate::types::basic::{AtTime, BundleHash, BundleId, CollectionId, UserId};
+    use crate::types::basic::{
+        AtTime, BundleHash, BundleId, BundleRefCount, BundleSize, BundleUploadId, CollectionId,
+        DataLocation, StorageVersion, UserId,
+    };
 
     #[td_type::Dao]
     #[dao(sql_table = "bundles")]
@@ -23,9 +26,61 @@ mod definitions {
         pub created_by_id: UserId,
     }
 
+    /// Stored content of function bundles. Bundles with identical content in a collection share
+    /// one blob, stored at the location of the bundle that first uploaded it.
+    #[td_type::Dao]
+    #[dao(sql_table = "bundle_blobs")]
+    pub struct BundleBlobDB {
+        #[td_type(extractor)]
+        pub id: BundleId,
+        pub collection_id: CollectionId,
+        #[td_type(extractor)]
+        pub hash: BundleHash,
+        #[builder(default)]
+        pub ref_count: BundleRefCount,
+        pub data_location: DataLocation,
+        pub storage_version: StorageVersion,
+    }
+
+    #[td_type::Dao]
+    #[dao(sql_table = "bundle_blobs")]
+    pub struct UpdateBundleBlobDB {
+        pub ref_count: BundleRefCount,
+    }
+
     #[td_type::Dto]
     #[td_type(builder(try_from = BundleDB))]
     pub struct Bundle {
         pub id: BundleId,
     }
+
+    /// Chunked upload of a function bundle, pending to be committed.
+    #[td_type::Dao]
+    #[dao(
+        sql_table = "bundle_uploads",
+        audit(created_on = "created_on", created_by = "created_by_id")
+    )]
+    pub struct BundleUploadDB {
+        #[td_type(extractor)]
+        pub id: BundleUploadId,
+        pub collection_id: CollectionId,
+        pub created_on: AtTime,
+        pub created_by_id: UserId,
+        pub expires_on: AtTime,
+    }
+
+    #[td_type::Dto]
+    #[td_type(builder(try_from = BundleUploadDB))]
+    pub struct BundleUpload {
+        pub id: BundleUploadId,
+        pub expires_on: AtTime,
+    }
+
+    /// Commit of a chunked upload, with the expected size and, optionally, the expected hash
+    /// (sha256) of the bundle.
+    #[td_type::Dto]
+    pub struct BundleUploadCommit {
+        pub size: BundleSize,
+        pub hash: Option<BundleHash>,
+    }
 }
@@ -5,8 +5,10 @@
 #[td_type::dxo]
 mod definitions {
     use crate::dxo::crudl::RequestContext;
+    use crate::dxo::function::{Function, FunctionRegister};
     use crate::types::basic::{
-        AtTime, CollectionId, CollectionName, Description, UserId, UserName,
+        AtTime, CollectionDefinitionVersion, CollectionId, CollectionName, Description,
+        ImportConflict, ToCollectionName, UserId, UserName,
     };
     use td_common::id::Id;
 
@@ -115,10 +117,32 @@ mod definitions {
     #[inherits(CollectionDBWithNames)]
     pub struct CollectionRead {
         #[dto(list(pagination_by = "+"))]
+        #[td_type(extractor)]
         pub id: CollectionId,
         #[dto(list(filter, filter_like, order_by))]
         pub name: CollectionName,
         #[dto(list(filter, filter_like, order_by))]
         pub description: Description,
     }
+
+    /// Definition of a collection, its functions (with their tables, dependencies and triggers)
+    /// and the collections it grants read access to. Ids are not part of it, so it can be
+    /// imported in another instance.
+    #[td_type::Dto]
+    #[derive(PartialEq)]
+    pub struct CollectionDefinition {
+        pub version: CollectionDefinitionVersion,
+        pub name: CollectionName,
+        pub description: Description,
+        pub functions: Vec<FunctionRegister>,
+        pub inter_collection_permissions: Vec<ToCollectionName>,
+    }
+
+    #[td_type::Dto]
+    pub struct CollectionImport {
+        #[td_type(extractor)]
+        pub collection: CollectionRead,
+        pub functions: Vec<Function>,
+        pub conflicts: Vec<ImportConflict>,
+    }
 }
@@ -2,7 +2,8 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use crate::types::basic::{AccessTokenId, AtTime, RoleId, UserId};
+use crate::sql::list::Cursor;
+use crate::types::basic::{AccessTokenId, ApiKeyScoped, AtTime, RequestId, RoleId, UserId};
 use serde::{Deserialize, Serialize};
 use serde_valid::Validate;
 use sqlx::Error;
@@ -28,6 +29,10 @@ pub struct RequestContext {
     /// The time the request was made.
     #[td_type(extractor)]
     pub time: AtTime,
+    /// The ID of the API request, if any.
+    pub request_id: Option<RequestId>,
+    /// If the access token is restricted to its scope, as scoped API keys are.
+    pub scoped: ApiKeyScoped,
 }
 
 impl RequestContext {
@@ -41,6 +46,42 @@ impl RequestContext {
             user_id: user_id.into(),
             role_id: role_id.into(),
             time: AtTime::default(),
+            request_id: None,
+            scoped: ApiKeyScoped::default(),
+        }
+    }
+
+    /// Sets the ID of the API request.
+    pub fn with_request_id(self, request_id: RequestId) -> Self {
+        Self {
+            request_id: Some(request_id),
+            ..self
+        }
+    }
+
+    /// Sets if the access token is restricted to its scope.
+    pub fn with_scoped(self, scoped: ApiKeyScoped) -> Self {
+        Self { scoped, ..self }
+    }
+}
+
+/// Audit context filling the `#[dao(audit(...))]` columns of Daos, with their builder `audit`.
+///
+/// The columns are filled when building the Dao rather than by an insert hook, so the Dao
+/// returned by a service is the one inserted, and all inserts, whatever the query, get them.
+#[td_type::Dlo]
+pub struct AuditContext {
+    /// The ID of the user creating the entity.
+    pub created_by: UserId,
+    /// The time the entity was created.
+    pub created_on: AtTime,
+}
+
+impl From<&RequestContext> for AuditContext {
+    fn from(request_context: &RequestContext) -> Self {
+        Self {
+            created_by: request_context.user_id,
+            created_on: request_context.time.clone(),
         }
     }
 }
@@ -135,15 +176,15 @@ pub struct ListParams {
     #[builder(default)]
     #[serde(alias = "search", default)]
     pub filter: Vec<String>,
-    /// The sort order of the result list.
+    /// The sort order of the result list, comma separated `<NAME>+/-` fields.
     #[builder(default)]
     #[serde(alias = "order-by", default)]
     pub order_by: Option<String>,
-    /// The previous value for pagination.
+    /// The previous value for pagination, or a pagination cursor (without `pagination_id`).
     #[builder(default)]
     #[serde(default)]
     pub previous: Option<String>,
-    /// The next value for pagination.
+    /// The next value for pagination, or a pagination cursor (without `pagination_id`).
     #[builder(default)]
     #[serde(default)]
     pub next: Option<String>,
@@ -151,6 +192,10 @@ pub struct ListParams {
     #[builder(default)]
     #[serde(default)]
     pub pagination_id: Option<String>,
+    /// The fields, comma separated, to include in each item of the result list (all if not set).
+    #[builder(default)]
+    #[serde(default)]
+    pub fields: Option<String>,
 }
 
 impl Default for ListParams {
@@ -162,10 +207,26 @@ impl Default for ListParams {
             previous: None,
             next: None,
             pagination_id: None,
+            fields: None,
         }
     }
 }
 
+impl ListParams {
+    /// Returns the fields selected to include in each item of the result list, `None` if all
+    /// (not set or empty).
+    pub fn selected_fields(&self) -> Option<Vec<&str>> {
+        let fields: Vec<_> = self
+            .fields
+            .as_deref()?
+            .split(',')
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .collect();
+        (!fields.is_empty()).then_some(fields)
+    }
+}
+
 /// Request to list entities.
 #[td_type::Dlo]
 pub struct ListRequest<N: Clone> {
@@ -257,21 +318,39 @@ pub enum CrudlErrorX {
     InternalError(String) = 5002,
 }
 
+/// List parameters effectively applied to a list operation, after parsing and normalization.
+#[td_type::Dto]
+pub struct AppliedListParams {
+    /// The page size applied.
+    pub len: usize,
+    /// The parsed filters, as `<NAME><OPERATOR><VALUE>`, sorted.
+    pub filter: Vec<String>,
+    /// The effective sort order, as comma separated `<NAME>+/-`. The natural order if none was
+    /// requested.
+    pub order_by: String,
+}
+
 /// Response for list operations.
 ///
 /// Besides the data, it includes the [`ListParams`] used for the list operation,
 /// the offset and length of the result and a flag indicating if there are more results or not.
+/// If available, it also includes the [`AppliedListParams`] as interpreted from the request.
 #[td_type::Dto]
 pub struct ListResponse<LL: Clone> {
     /// The list parameters of the request.
     pub list_params: ListParams,
+    /// The list parameters as applied, after parsing and normalization.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub applied: Option<AppliedListParams>,
     /// The length of the result list.
     pub len: usize,
     #[builder(setter(custom))]
     /// The data of the result list.
     pub data: Vec<LL>,
 
-    // Pagination info to go to previous page
+    // Pagination info to go to previous page, the previous/next values are set only when
+    // ordering by a single column (the cursors must be used when ordering by multiple columns)
 
     //#[builder(private)] NOTE: we cannot do set this because list_status! macro generates a
     //                          concrete class and tries to define the builder with a pub setter.
@@ -287,6 +366,16 @@ pub struct ListResponse<LL: Clone> {
     pub next: Option<String>,
     //#[builder(private)] NOTE: same same
     pub next_pagination_id: Option<String>,
+
+    // Pagination cursors, the previous/next value and pagination ID as a single token
+    /// The cursor to go to the previous page, to be given as the `previous` list param.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_cursor: Option<String>,
+    /// The cursor to go to the next page, to be given as the `next` list param.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl<LL: Clone> ListResponseBuilder<LL> {
@@ -297,29 +386,60 @@ impl<LL: Clone> ListResponseBuilder<LL> {
         self
     }
 
-    /// Sets info to paginate to previous page
+    /// Sets info to paginate to previous page, the previous values are the ordered by columns
+    /// values. The unique id breaking the ties of the order, if any, is carried by the cursor only.
     pub fn previous_page(
         &mut self,
-        previous: Option<String>,
+        previous: Option<Vec<String>>,
         previous_pagination_id: Option<String>,
+        previous_tiebreaker_id: Option<String>,
     ) -> &mut Self {
-        self.previous = Some(previous);
+        self.previous_cursor = Some(cursor(
+            &previous,
+            &previous_pagination_id,
+            previous_tiebreaker_id,
+        ));
+        self.previous = Some(single_value(previous));
         self.previous_pagination_id = Some(previous_pagination_id);
         self
     }
 
-    /// Sets info to paginate to next page
+    /// Sets info to paginate to next page, the next values are the ordered by columns values.
+    /// The unique id breaking the ties of the order, if any, is carried by the cursor only.
     pub fn next_page(
         &mut self,
-        next: Option<String>,
+        next: Option<Vec<String>>,
         next_pagination_id: Option<String>,
+        next_tiebreaker_id: Option<String>,
     ) -> &mut Self {
-        self.next = Some(next);
+        self.next_cursor = Some(cursor(&next, &next_pagination_id, next_tiebreaker_id));
+        self.next = Some(single_value(next));
         self.next_pagination_id = Some(next_pagination_id);
         self
     }
 }
 
+/// The cursor values are the ordered by columns values, followed by the tiebreaker id if any.
+fn cursor(
+    values: &Option<Vec<String>>,
+    pagination_id: &Option<String>,
+    tiebreaker_id: Option<String>,
+) -> Option<String> {
+    match (values, pagination_id) {
+        (Some(values), Some(pagination_id)) => {
+            let values: Vec<String> = values.iter().cloned().chain(tiebreaker_id).collect();
+            Some(Cursor::encode(&values, pagination_id))
+        }
+        _ => None,
+    }
+}
+
+fn single_value(values: Option<Vec<String>>) -> Option<String> {
+    values
+        .filter(|values| values.len() == 1)
+        .and_then(|mut values| values.pop())
+}
+
 /// Crudl helper function to handle SQL create errors.
 pub fn handle_create_error(e: Error) -> CrudlErrorX {
     match e {
@@ -358,7 +478,7 @@ pub fn handle_select_error(e: Error) -> CrudlErrorX {
 
 /// Converts a SQL error into an app error.
 pub fn handle_sql_err(err: Error) -> TdError {
-    TdError::new(DbError::SqlError(err))
+    TdError::new(DbError::from_sql(err))
 }
 
 pub fn handle_create_unique_err<AlreadyExisting, DbErr>(
@@ -10,8 +10,9 @@ mod definitions {
     use crate::execution::graph::{FunctionNode, GraphEdge, TableNode};
     use crate::types::basic::{
         AtTime, CollectionId, CollectionName, Dot, ExecutionId, ExecutionName, ExecutionStatus,
-        FunctionName, FunctionRunStatus, FunctionVersionId, StatusCount, TableDataVersionId,
-        TableName, TableVersionId, TransactionId, TriggeredOn, UserId, UserName,
+        FunctionName, FunctionRunStatus, FunctionVersionId, RowCount, StatusCount,
+        TableDataVersionId, TableName, TableVersionId, TransactionId, TriggeredOn, UserId,
+        UserName,
     };
     use crate::types::composed::TableVersions;
     use crate::types::status_count::FunctionRunStatusCount;
@@ -163,6 +164,9 @@ mod definitions {
             GraphEdge<ResolvedVersionResponse>,
         )>,
         pub relations_info: HashMap<TableDataVersionId, ExecutionTableDataVersionRead>,
+        // estimates info, None if unknown (no table stats available)
+        pub estimated_input_rows: HashMap<FunctionVersionId, Option<RowCount>>,
+        pub estimated_total_rows: Option<RowCount>,
     }
 
     impl ExecutionResponseBuilder {
@@ -7,15 +7,16 @@ mod definitions {
     use crate::dxo::crudl::RequestContext;
     use crate::types::basic::{
         AtTime, BundleId, CollectionId, CollectionName, Connector, DataLocation, Decorator,
-        Description, FunctionId, FunctionName, FunctionRuntimeValues, FunctionStatus,
-        FunctionVersionId, ReuseFrozen, Snippet, StorageVersion, TableName, TableNameDto, UserId,
-        UserName,
+        Description, FunctionId, FunctionName, FunctionRuntimeValues, FunctionStatus, FunctionTag,
+        FunctionVersionId, ReuseFrozen, Snippet, SnippetChanged, SnippetLanguage, StorageVersion,
+        TableName, TableNameDto, UserId, UserName,
     };
     use crate::types::composed::{
         TableDependency, TableDependencyDto, TableTrigger, TableTriggerDto,
     };
 
     #[td_type::Dto]
+    #[derive(PartialEq)]
     pub struct FunctionRegister {
         #[td_type(extractor)]
         pub name: FunctionName,
@@ -35,10 +36,20 @@ mod definitions {
         pub runtime_values: FunctionRuntimeValues,
         #[td_type(extractor)]
         pub reuse_frozen_tables: ReuseFrozen,
+        /// Tags of the function, replacing the current ones if given (kept if not given).
+        #[builder(default)]
+        #[serde(default)]
+        #[td_type(extractor)]
+        pub tags: Option<Vec<FunctionTag>>,
     }
 
     pub type FunctionUpdate = FunctionRegister;
 
+    #[td_type::Dto]
+    pub struct FunctionRegisterBatch {
+        pub functions: Vec<FunctionRegister>,
+    }
+
     #[td_type::Dao]
     #[derive(Eq, PartialEq)]
     #[dao(
@@ -94,6 +105,13 @@ mod definitions {
         pub status: FunctionStatus,
     }
 
+    /// Status of a function version, updated as is to guard updates of the function.
+    #[td_type::Dao]
+    #[dao(sql_table = "functions")]
+    pub struct UpdateFunctionStatusDB {
+        pub status: FunctionStatus,
+    }
+
     #[td_type::Dao]
     #[dao(sql_table = "functions__with_names")]
     #[inherits(FunctionDB)]
@@ -135,6 +153,48 @@ mod definitions {
         pub defined_by: UserName,
     }
 
+    #[td_type::Dto]
+    #[td_type(builder(try_from = FunctionDBWithNames, skip_all))]
+    pub struct FunctionSnippet {
+        #[td_type(builder(include))]
+        pub id: FunctionVersionId,
+        #[td_type(builder(include))]
+        pub collection: CollectionName,
+        #[td_type(builder(include))]
+        pub name: FunctionName,
+        #[td_type(builder(include))]
+        pub decorator: Decorator,
+        #[td_type(setter)]
+        pub language: SnippetLanguage,
+        #[td_type(builder(include))]
+        pub snippet: Snippet,
+    }
+
+    #[td_type::Dto]
+    pub struct FunctionDependencyChange {
+        pub from: TableDependency,
+        pub to: TableDependency,
+    }
+
+    #[td_type::Dto]
+    pub struct FunctionDiff {
+        pub from: FunctionVersionId,
+        pub to: FunctionVersionId,
+        pub added_tables: Vec<TableName>,
+        pub removed_tables: Vec<TableName>,
+        pub added_dependencies: Vec<TableDependency>,
+        pub removed_dependencies: Vec<TableDependency>,
+        pub changed_dependencies: Vec<FunctionDependencyChange>,
+        pub added_triggers: Vec<TableTrigger>,
+        pub removed_triggers: Vec<TableTrigger>,
+        pub snippet_changed: SnippetChanged,
+    }
+
+    #[td_type::Dto]
+    pub struct FunctionBatch {
+        pub functions: Vec<Function>,
+    }
+
     #[td_type::Dto]
     #[td_type(builder(try_from = Function))]
     #[inherits(Function)]
@@ -0,0 +1,17 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[td_type::dxo]
+mod definitions {
+    use crate::types::basic::{FunctionId, FunctionTag};
+
+    /// Tag of a function. Tags are kept by function id, so they apply to all its versions.
+    #[td_type::Dao]
+    #[dao(sql_table = "function_tags")]
+    pub struct FunctionTagDB {
+        #[td_type(extractor)]
+        pub function_id: FunctionId,
+        pub tag: FunctionTag,
+    }
+}
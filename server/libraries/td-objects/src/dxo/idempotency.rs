@@ -0,0 +1,34 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[td_type::dxo]
+mod definitions {
+    use crate::types::basic::{
+        AtTime, IdempotencyKey, RequestHash, ResponseBody, ResponseStatus, UserId,
+    };
+
+    /// Response of a create request, stored to be replayed on retries with the same
+    /// idempotency key. The key is reserved before running the request, without response until
+    /// the request completes.
+    #[td_type::Dao]
+    #[dao(sql_table = "idempotency_keys")]
+    pub struct IdempotencyKeyDB {
+        pub idempotency_key: IdempotencyKey,
+        pub user_id: UserId,
+        pub request_hash: RequestHash,
+        pub response_status: Option<ResponseStatus>,
+        pub response_body: Option<ResponseBody>,
+        pub created_on: AtTime,
+        pub expires_on: AtTime,
+    }
+
+    /// Response stored for a reserved idempotency key once its request completes.
+    #[td_type::Dao]
+    #[dao(sql_table = "idempotency_keys")]
+    pub struct IdempotencyKeyResponseDB {
+        pub response_status: Option<ResponseStatus>,
+        pub response_body: Option<ResponseBody>,
+        pub expires_on: AtTime,
+    }
+}
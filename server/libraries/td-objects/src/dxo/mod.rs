@@ -2,6 +2,8 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+pub mod api_key;
+pub mod audit_log;
 pub mod auth;
 pub mod bundle;
 pub mod collection;
@@ -11,8 +13,10 @@ pub mod execution;
 pub mod function;
 pub mod function_requirement;
 pub mod function_run;
+pub mod function_tag;
 pub mod function_upload;
 pub mod global_status;
+pub mod idempotency;
 pub mod inter_collection_access;
 pub mod inter_collection_permission;
 pub mod permission;
@@ -23,8 +27,10 @@ pub mod synchrotron;
 pub mod system;
 pub mod table;
 pub mod table_data_version;
+pub mod table_stats;
 pub mod transaction;
 pub mod trigger;
 pub mod user;
 pub mod user_role;
+pub mod webhook;
 pub mod worker;
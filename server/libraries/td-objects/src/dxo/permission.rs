@@ -7,8 +7,8 @@ mod definitions {
     use crate::dxo::crudl::RequestContext;
     use crate::dxo::role::RoleDB;
     use crate::types::basic::{
-        AtTime, EntityId, EntityName, Fixed, PermissionEntityType, PermissionId, PermissionType,
-        RoleId, RoleName, UserId, UserName,
+        AtTime, EntityId, EntityName, Fixed, PermissionChangeToken, PermissionEntityType,
+        PermissionId, PermissionType, RoleId, RoleName, UserId, UserName,
     };
 
     #[td_type::Dao]
@@ -72,4 +72,14 @@ mod definitions {
         #[dto(list(filter, filter_like))] // TODO should we allow order by on nullable??
         pub entity: Option<EntityName>,
     }
+
+    /// A change of permissions, its ID is a monotonically increasing token.
+    #[td_type::Dao]
+    #[dao(sql_table = "permission_changes")]
+    pub struct PermissionChangeDB {
+        #[dao(read_only)]
+        #[builder(default)]
+        pub id: PermissionChangeToken,
+        pub changed_on: AtTime,
+    }
 }
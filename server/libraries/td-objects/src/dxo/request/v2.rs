@@ -4,10 +4,11 @@
 
 use crate::dxo::request::{Location, Locations};
 use crate::types::basic::{
-    CollectionId, CollectionName, ColumnCount, DependencyPos, ExecutionId, ExecutionName,
-    FunctionName, FunctionRunId, FunctionVersionId, InputIdx, PartitionFileName, PartitionName,
-    RowCount, SchemaHash, TableDataVersionId, TableFunctionParamPos, TableId, TableName,
-    TableVersionId, TransactionId, TriggeredOnMillis, VersionPos,
+    CollectionId, CollectionName, ColumnCount, ColumnMaxValue, ColumnMinValue, DependencyPos,
+    ExecutionId, ExecutionName, FunctionName, FunctionRunId, FunctionVersionId, InputIdx,
+    NullCount, PartitionFileName, PartitionName, RowCount, SchemaFieldName, SchemaHash,
+    TableDataVersionId, TableFunctionParamPos, TableId, TableName, TableVersionId, TransactionId,
+    TriggeredOnMillis, VersionPos,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -197,6 +198,26 @@ pub struct TableInfo {
     pub column_count: ColumnCount,
     pub row_count: RowCount,
     pub schema_hash: SchemaHash,
+    /// Statistics of the columns, computed by the worker while writing the table. Workers
+    /// that don't compute them don't report them.
+    #[builder(default)]
+    #[serde(default)]
+    pub column_stats: Option<Vec<ColumnStats>>,
+}
+
+/// Min and max values are stored as floats, integers beyond 2^53 lose precision.
+#[td_type::Dto]
+pub struct ColumnStats {
+    pub name: SchemaFieldName,
+    pub null_count: NullCount,
+    /// Only for numeric columns with non-null values.
+    #[builder(default)]
+    #[serde(default)]
+    pub min: Option<ColumnMinValue>,
+    /// Only for numeric columns with non-null values.
+    #[builder(default)]
+    #[serde(default)]
+    pub max: Option<ColumnMaxValue>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
@@ -407,6 +428,7 @@ mod tests {
                         column_count: ColumnCount::try_from(1i64)?,
                         row_count: RowCount::try_from(2i64)?,
                         schema_hash: SchemaHash::try_from("hash")?,
+                        column_stats: None,
                     },
                 },
                 WrittenTableV2::NoData {
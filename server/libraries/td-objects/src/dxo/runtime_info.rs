@@ -2,7 +2,11 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use crate::types::basic::{BuildManifest, PythonVersion, TabsdataVersion};
+use crate::types::basic::{
+    BuildManifest, BuildTimestamp, Edition, GitCommit, PythonVersion, TabsdataVersion,
+};
+use td_common::about;
+use td_error::TdError;
 
 #[td_type::Dto]
 pub struct ServerVersion {
@@ -24,9 +28,32 @@ pub struct PythonVersions {
     pub versions: Vec<PythonVersion>,
 }
 
+/// Build metadata of the running server, see [`about::BuildInfo`].
+#[td_type::Dto]
+pub struct BuildInfo {
+    pub version: TabsdataVersion,
+    pub git_commit: Option<GitCommit>,
+    pub build_timestamp: BuildTimestamp,
+    pub edition: Edition,
+}
+
+impl TryFrom<&about::BuildInfo> for BuildInfo {
+    type Error = TdError;
+
+    fn try_from(build_info: &about::BuildInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            version: TabsdataVersion::try_from(build_info.version)?,
+            git_commit: build_info.git_commit.map(GitCommit::try_from).transpose()?,
+            build_timestamp: BuildTimestamp::try_from(build_info.build_timestamp)?,
+            edition: Edition::try_from(build_info.edition)?,
+        })
+    }
+}
+
 #[td_type::Dto]
 pub struct RuntimeInfo {
     pub version: TabsdataVersion,
+    pub build_info: BuildInfo,
     pub build_manifest: BuildManifest,
     pub python_versions: Vec<PythonVersion>,
 }
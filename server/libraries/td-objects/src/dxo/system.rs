@@ -11,5 +11,14 @@ pub struct ApiStatus {
 #[td_type::typed_enum]
 pub enum HealthStatus {
     OK,
+    /// The database could not be opened for writing, only reads are served.
+    DatabaseReadOnly,
     DatabaseError(String),
 }
+
+/// Roles and their permissions cached by the authorization context.
+#[td_type::Dto]
+pub struct AuthzCacheStatus {
+    pub roles: usize,
+    pub permissions: usize,
+}
@@ -90,6 +90,7 @@ mod definitions {
         pub ended_on: Option<AtTime>,
         pub status: FunctionRunStatus,
         pub with_data_table_data_version_id: Option<TableDataVersionId>,
+        pub with_data_row_count: Option<RowCount>,
     }
 
     #[td_type::Dao]
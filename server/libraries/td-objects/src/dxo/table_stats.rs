@@ -0,0 +1,28 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[td_type::dxo]
+mod definitions {
+    use crate::types::basic::{
+        ColumnMaxValue, ColumnMinValue, ColumnPos, NullCount, RowCount, SchemaFieldName,
+        TableDataVersionId,
+    };
+
+    /// Statistics of a column of a table data version, as reported when the table was written.
+    /// Min and max values are floats (`REAL`), integers beyond 2^53 are approximated.
+    #[td_type::Dao]
+    #[dao(sql_table = "table_stats")]
+    pub struct TableStatsDB {
+        #[td_type(extractor)]
+        pub table_data_version_id: TableDataVersionId,
+        pub column_name: SchemaFieldName,
+        pub column_pos: ColumnPos,
+        pub row_count: RowCount,
+        pub null_count: NullCount,
+        #[builder(default)]
+        pub min_value: Option<ColumnMinValue>,
+        #[builder(default)]
+        pub max_value: Option<ColumnMaxValue>,
+    }
+}
@@ -35,6 +35,8 @@ mod definitions {
     #[dao(sql_table = "users_roles__with_names")]
     #[inherits(UserRoleDB)]
     pub struct UserRoleDBWithNames {
+        #[td_type(extractor)]
+        pub id: UserRoleId,
         #[td_type(extractor)]
         pub role_id: RoleId,
 
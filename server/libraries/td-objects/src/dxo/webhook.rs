@@ -0,0 +1,97 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[td_type::dxo]
+mod definitions {
+    use crate::dxo::crudl::RequestContext;
+    use crate::types::basic::{
+        AtTime, CollectionId, FunctionId, FunctionName, FunctionRunId, UserId, WebhookAttempts,
+        WebhookDeliveryError, WebhookDeliveryId, WebhookDeliveryStatus, WebhookId, WebhookPayload,
+        WebhookSecret, WebhookUrl,
+    };
+
+    /// Webhook notified when the function runs of a collection, or of one of its functions,
+    /// complete or fail.
+    #[td_type::Dao]
+    #[dao(sql_table = "function_run_webhooks")]
+    #[td_type(updater(try_from = RequestContext, skip_all))]
+    pub struct WebhookDB {
+        #[td_type(extractor)]
+        #[builder(default)]
+        pub id: WebhookId,
+        #[td_type(setter, extractor)]
+        pub collection_id: CollectionId,
+        #[builder(default)]
+        pub function_id: Option<FunctionId>,
+        pub url: WebhookUrl,
+        pub secret: WebhookSecret,
+        #[td_type(updater(try_from = RequestContext, field = "time"))]
+        pub created_on: AtTime,
+        #[td_type(updater(try_from = RequestContext, field = "user_id"))]
+        pub created_by_id: UserId,
+    }
+
+    #[td_type::Dto]
+    pub struct WebhookCreate {
+        pub url: WebhookUrl,
+        /// Shared secret the payloads are signed with.
+        pub secret: WebhookSecret,
+        /// Function to be notified of, all the functions of the collection if not given.
+        pub function: Option<FunctionName>,
+    }
+
+    #[td_type::Dto]
+    #[dto(list(on = WebhookDB))]
+    #[td_type(builder(try_from = WebhookDB))]
+    pub struct Webhook {
+        #[dto(list(pagination_by = "+", filter))]
+        pub id: WebhookId,
+        pub collection_id: CollectionId,
+        #[dto(list(filter))]
+        pub function_id: Option<FunctionId>,
+        #[dto(list(filter, filter_like, order_by))]
+        pub url: WebhookUrl,
+        pub created_on: AtTime,
+        pub created_by_id: UserId,
+    }
+
+    /// Notification of a function run to a webhook, delivered by the scheduler.
+    #[td_type::Dao]
+    #[dao(sql_table = "function_run_webhook_deliveries")]
+    pub struct WebhookDeliveryDB {
+        #[td_type(extractor)]
+        #[builder(default)]
+        pub id: WebhookDeliveryId,
+        pub webhook_id: WebhookId,
+        pub function_run_id: FunctionRunId,
+        pub payload: WebhookPayload,
+        #[builder(default = WebhookDeliveryStatus::Pending)]
+        pub status: WebhookDeliveryStatus,
+        pub attempts: WebhookAttempts,
+        pub next_attempt_on: AtTime,
+        #[builder(default)]
+        pub last_error: Option<WebhookDeliveryError>,
+        pub created_on: AtTime,
+        #[builder(default)]
+        pub delivered_on: Option<AtTime>,
+    }
+
+    #[td_type::Dao]
+    #[dao(sql_table = "function_run_webhook_deliveries__to_deliver")]
+    #[inherits(WebhookDeliveryDB)]
+    pub struct WebhookDeliveryToDeliverDB {
+        pub url: WebhookUrl,
+        pub secret: WebhookSecret,
+    }
+
+    #[td_type::Dao]
+    #[dao(sql_table = "function_run_webhook_deliveries")]
+    pub struct UpdateWebhookDeliveryDB {
+        pub status: WebhookDeliveryStatus,
+        pub attempts: WebhookAttempts,
+        pub next_attempt_on: AtTime,
+        pub last_error: Option<WebhookDeliveryError>,
+        pub delivered_on: Option<AtTime>,
+    }
+}
@@ -0,0 +1,211 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use td_error::{TdError, td_error};
+use utoipa::ToSchema;
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+const OPENAPI_REF_PREFIX: &str = "#/components/schemas/";
+const DEFS_REF_PREFIX: &str = "#/$defs/";
+
+#[td_error]
+pub enum JsonSchemaError {
+    #[error("Unresolved schema reference '{0}' in the schema of '{1}'")]
+    UnresolvedReference(String, String) = 0,
+
+    #[error("Could not serialize the schema of '{0}': {1}")]
+    SerializationError(String, #[source] serde_json::Error) = 5000,
+}
+
+/// Returns the JSON Schema of a DTO, as documented in the OpenAPI spec, as a self-contained
+/// document.
+///
+/// The OpenAPI `$ref`s to other component schemas are inlined. Recursive references can't be
+/// inlined, they are kept as `$ref`s into the `$defs` of the document instead.
+pub fn json_schema<T: ToSchema>() -> Result<Value, TdError> {
+    let name = T::name().to_string();
+    let serialization_error = |e| JsonSchemaError::SerializationError(name.clone(), e);
+
+    let mut components = Vec::new();
+    T::schemas(&mut components);
+    let components = components
+        .into_iter()
+        .map(|(component, schema)| Ok((component, serde_json::to_value(schema)?)))
+        .collect::<Result<HashMap<_, _>, serde_json::Error>>()
+        .map_err(serialization_error)?;
+    let schema = serde_json::to_value(T::schema()).map_err(serialization_error)?;
+
+    let mut resolver = RefResolver {
+        dto: &name,
+        components: &components,
+        expanding: vec![],
+        recursive: BTreeSet::new(),
+    };
+    let mut document = match resolver.resolve(schema)? {
+        Value::Object(document) => document,
+        other => Map::from_iter([("allOf".to_string(), Value::Array(vec![other]))]),
+    };
+
+    // Recursive references are resolved into $defs, which can have recursive references too.
+    let mut defs = Map::new();
+    while let Some(component) = resolver
+        .recursive
+        .iter()
+        .find(|component| !defs.contains_key(*component))
+        .cloned()
+    {
+        resolver.expanding = vec![component.clone()];
+        let schema = resolver.component(&component)?.clone();
+        defs.insert(component, resolver.resolve(schema)?);
+    }
+
+    document.insert(
+        "$schema".to_string(),
+        Value::String(JSON_SCHEMA_DIALECT.to_string()),
+    );
+    document
+        .entry("title")
+        .or_insert_with(|| Value::String(name.clone()));
+    if !defs.is_empty() {
+        document.insert("$defs".to_string(), Value::Object(defs));
+    }
+    Ok(Value::Object(document))
+}
+
+struct RefResolver<'a> {
+    dto: &'a str,
+    components: &'a HashMap<String, Value>,
+    /// Components being inlined, from the outermost one.
+    expanding: Vec<String>,
+    /// Components referenced from within themselves.
+    recursive: BTreeSet<String>,
+}
+
+impl RefResolver<'_> {
+    fn component(&self, component: &str) -> Result<&Value, JsonSchemaError> {
+        self.components.get(component).ok_or_else(|| {
+            JsonSchemaError::UnresolvedReference(component.to_string(), self.dto.to_string())
+        })
+    }
+
+    fn resolve(&mut self, value: Value) -> Result<Value, JsonSchemaError> {
+        match value {
+            Value::Object(mut object) => {
+                let reference = match object.get("$ref") {
+                    Some(Value::String(reference)) => Some(reference.clone()),
+                    _ => None,
+                };
+                if let Some(reference) = reference {
+                    object.remove("$ref");
+                    let component = reference
+                        .strip_prefix(OPENAPI_REF_PREFIX)
+                        .ok_or_else(|| {
+                            JsonSchemaError::UnresolvedReference(
+                                reference.clone(),
+                                self.dto.to_string(),
+                            )
+                        })?
+                        .to_string();
+
+                    let resolved = if self.expanding.contains(&component) {
+                        self.recursive.insert(component.clone());
+                        Value::Object(Map::from_iter([(
+                            "$ref".to_string(),
+                            Value::String(format!("{DEFS_REF_PREFIX}{component}")),
+                        )]))
+                    } else {
+                        let schema = self.component(&component)?.clone();
+                        self.expanding.push(component);
+                        let resolved = self.resolve(schema);
+                        self.expanding.pop();
+                        resolved?
+                    };
+
+                    // keywords next to the $ref (e.g. description) take precedence
+                    return Ok(match resolved {
+                        Value::Object(mut resolved) => {
+                            for (keyword, value) in object {
+                                resolved.insert(keyword, self.resolve(value)?);
+                            }
+                            Value::Object(resolved)
+                        }
+                        resolved => resolved,
+                    });
+                }
+
+                let resolved = object
+                    .into_iter()
+                    .map(|(keyword, value)| Ok((keyword, self.resolve(value)?)))
+                    .collect::<Result<Map<_, _>, JsonSchemaError>>()?;
+                Ok(Value::Object(resolved))
+            }
+            Value::Array(values) => {
+                let resolved = values
+                    .into_iter()
+                    .map(|value| self.resolve(value))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Value::Array(resolved))
+            }
+            value => Ok(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dxo::function::FunctionRegister;
+
+    fn contains_ref(value: &Value) -> bool {
+        match value {
+            Value::Object(object) => {
+                object.contains_key("$ref") || object.values().any(contains_ref)
+            }
+            Value::Array(values) => values.iter().any(contains_ref),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_function_register_json_schema() -> Result<(), TdError> {
+        let schema = json_schema::<FunctionRegister>()?;
+
+        assert_eq!(schema["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(schema["title"], "FunctionRegister");
+        assert_eq!(schema["type"], "object");
+        assert!(!contains_ref(&schema), "unexpected $ref in {schema:#}");
+
+        let required = schema["required"].as_array().unwrap();
+        for field in [
+            "name",
+            "description",
+            "bundle_id",
+            "snippet",
+            "decorator",
+            "reuse_frozen_tables",
+        ] {
+            assert!(
+                required.contains(&Value::String(field.to_string())),
+                "expected '{field}' to be required in {required:?}"
+            );
+        }
+        for field in ["connector", "dependencies", "triggers", "tables", "tags"] {
+            assert!(
+                !required.contains(&Value::String(field.to_string())),
+                "expected '{field}' to be optional in {required:?}"
+            );
+        }
+
+        let properties = &schema["properties"];
+        assert_eq!(properties["name"]["type"], "string");
+        assert_eq!(properties["description"]["type"], "string");
+        assert_eq!(properties["snippet"]["type"], "string");
+        assert_eq!(properties["decorator"]["type"], "string");
+        assert!(properties["decorator"]["enum"].is_array());
+        assert_eq!(properties["reuse_frozen_tables"]["type"], "boolean");
+        Ok(())
+    }
+}
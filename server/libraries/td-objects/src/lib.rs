@@ -9,6 +9,7 @@ pub mod all_the_tuples;
 
 pub mod dxo;
 pub mod execution;
+pub mod json_schema;
 pub mod parse;
 pub mod rest_urls;
 pub mod sql;
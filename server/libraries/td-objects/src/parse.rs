@@ -36,11 +36,13 @@ pub fn parser(
     }
 }
 
-const VERSIONS_MARKER_REGEX: &str = "[^/@]+";
+const VERSIONS_MARKER_REGEX: &str = "[^/@\\[\\]]+";
 const VERSIONED_TABLE_PATTERN: &str = concat!(
-    "^((?P<collection>[^/]+)/)?(?P<table>[^@]+)(@(?P<versions>",
+    "^((?P<collection>[^/]+)/)?(?P<table>[^@\\[\\]]+)(@(?P<versions>",
     VERSIONS_MARKER_REGEX,
-    "))?$"
+    ")|\\[(?P<bracket_versions>",
+    VERSIONS_MARKER_REGEX,
+    ")\\])?$"
 );
 
 pub fn parse_versioned_table_ref<T, E>(
@@ -58,7 +60,10 @@ where
         Some(captures) => {
             let collection = captures.name("collection").map(|m| m.as_str());
             let table = captures.name("table").unwrap().as_str();
-            let versions = captures.name("versions").map(|m| m.as_str());
+            let versions = captures
+                .name("versions")
+                .or_else(|| captures.name("bracket_versions"))
+                .map(|m| m.as_str());
             if let Some(versions) = versions {
                 let versions = parse_versions(versions)?;
                 (collection, table, versions)
@@ -68,7 +73,8 @@ where
         }
         None => Err(ParserError::CouldNotParse(
             s.clone(),
-            "a table dependency, a [<COLLECTION>/]<TABLE>[@<VERSIONS>] with \
+            "a table dependency, a [<COLLECTION>/]<TABLE>[@<VERSIONS>] or \
+[<COLLECTION>/]<TABLE>[[<VERSIONS>]] with \
 <COLLECTION> and <NAME> being a [_A-Za-z0-9] word of up to 100 characters each \
 and <VERSIONS> being a single version, a range of versions or a list of versions"
                 .to_string(),
@@ -278,13 +284,105 @@ pub fn parse_email(s: impl Into<String>) -> Result<String, TdError> {
     )
 }
 
+/// Only checks the URL is an absolute http or https URL, the address it resolves to is checked
+/// when delivering webhooks.
+pub fn parse_webhook_url(s: impl Into<String>) -> Result<String, TdError> {
+    let s = s.into();
+    match url::Url::parse(&s) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(s),
+        _ => Err(ParserError::CouldNotParse(
+            s,
+            "a webhook URL, an absolute http or https URL".to_string(),
+        ))?,
+    }
+}
+
+/// A field parse error, with the path of the field that failed (`parent.child` if nested).
+#[derive(Debug)]
+pub struct ParseError {
+    pub field: String,
+    pub error: TdError,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.error)
+    }
+}
+
+/// Collects the parse errors of all fields, see [`parse_all`].
+#[derive(Debug, Default)]
+pub struct ParseAll {
+    prefix: Option<String>,
+    errors: Vec<ParseError>,
+}
+
+impl ParseAll {
+    /// Returns the parsed value, or `None` recording the error with the field path.
+    pub fn field<T, E: Into<TdError>>(&mut self, field: &str, result: Result<T, E>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.errors.push(ParseError {
+                    field: self.path(field),
+                    error: error.into(),
+                });
+                None
+            }
+        }
+    }
+
+    /// Parses a nested struct, prefixing the paths of its field errors with `field`.
+    pub fn nested<T>(
+        &mut self,
+        field: &str,
+        f: impl FnOnce(&mut ParseAll) -> Option<T>,
+    ) -> Option<T> {
+        let mut nested = ParseAll {
+            prefix: Some(self.path(field)),
+            errors: vec![],
+        };
+        let value = f(&mut nested);
+        self.errors.extend(nested.errors);
+        value
+    }
+
+    fn path(&self, field: &str) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}.{field}"),
+            None => field.to_string(),
+        }
+    }
+}
+
+/// Parses all fields, returning all their errors at once instead of only the first one. The
+/// given function parses each field with [`ParseAll::field`], and builds the value with `?`.
+pub fn parse_all<T>(f: impl FnOnce(&mut ParseAll) -> Option<T>) -> Result<T, Vec<ParseError>> {
+    let mut parse_all = ParseAll::default();
+    match f(&mut parse_all) {
+        Some(value) if parse_all.errors.is_empty() => Ok(value),
+        _ => Err(parse_all.errors),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::table_ref::Version;
-    use crate::types::basic::TableNameDto;
+    use crate::types::basic::{FunctionName, TableName, TableNameDto};
     use td_common::id;
 
+    #[test]
+    fn test_parse_webhook_url() {
+        assert!(parse_webhook_url("http://localhost:8080/hook").is_ok());
+        assert!(parse_webhook_url("https://example.com/hooks/runs?token=a").is_ok());
+
+        assert!(parse_webhook_url("").is_err());
+        assert!(parse_webhook_url("example.com/hook").is_err());
+        assert!(parse_webhook_url("ftp://example.com/hook").is_err());
+        assert!(parse_webhook_url("file:///tmp/hook").is_err());
+    }
+
     #[test]
     fn test_parse_name() {
         let name = parse_name("abc".to_string(), "test").unwrap();
@@ -371,6 +469,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_versioned_table_ref_forms() {
+        let table_ref = parse_versioned_table_ref::<TableNameDto, _>("abc").unwrap();
+        assert_eq!(table_ref.collection, None);
+        assert_eq!(table_ref.table, TableNameDto::try_from("abc").unwrap());
+        assert_eq!(table_ref.versions, Versions::None);
+
+        let table_ref = parse_versioned_table_ref::<TableNameDto, _>("xyz/abc@HEAD~1").unwrap();
+        assert_eq!(
+            table_ref.collection,
+            Some(CollectionName::try_from("xyz").unwrap())
+        );
+        assert_eq!(table_ref.table, TableNameDto::try_from("abc").unwrap());
+        assert_eq!(table_ref.versions, Versions::Single(Version::Head(-1)));
+
+        let table_ref = parse_versioned_table_ref::<TableNameDto, _>("xyz/abc[HEAD~1]").unwrap();
+        assert_eq!(
+            table_ref.collection,
+            Some(CollectionName::try_from("xyz").unwrap())
+        );
+        assert_eq!(table_ref.table, TableNameDto::try_from("abc").unwrap());
+        assert_eq!(table_ref.versions, Versions::Single(Version::Head(-1)));
+
+        let table_ref = parse_versioned_table_ref::<TableNameDto, _>("abc[HEAD^..HEAD]").unwrap();
+        assert_eq!(table_ref.collection, None);
+        assert_eq!(
+            table_ref.versions,
+            Versions::Range(Version::Head(-1), Version::Head(0))
+        );
+
+        assert!(parse_versioned_table_ref::<TableNameDto, _>("abc[HEAD").is_err());
+        assert!(parse_versioned_table_ref::<TableNameDto, _>("abc[]").is_err());
+        assert!(parse_versioned_table_ref::<TableNameDto, _>("abc@HEAD[HEAD]").is_err());
+    }
+
+    #[test]
+    fn test_parse_versioned_table_ref_invalid_version() {
+        let err = parse_versioned_table_ref::<TableNameDto, _>("xyz/abc[HEAD~]").unwrap_err();
+        assert_eq!(
+            err.domain_err::<ParserError>().to_string(),
+            "Could not parse 'HEAD~', expected: <VERSIONS> being a single version, a range of \
+versions or a list of versions"
+        );
+    }
+
     #[test]
     fn test_parse_version() {
         let version = parse_version("HEAD").unwrap();
@@ -558,4 +701,61 @@ mod tests {
             assert!(parsed.is_err());
         });
     }
+
+    #[derive(Debug)]
+    struct Source {
+        table: TableName,
+        function: FunctionName,
+    }
+
+    #[derive(Debug)]
+    struct Form {
+        collection: CollectionName,
+        source: Source,
+    }
+
+    fn parse_form(collection: &str, table: &str, function: &str) -> Result<Form, Vec<ParseError>> {
+        parse_all(|p| {
+            let collection = p.field("collection", CollectionName::try_from(collection));
+            let source = p.nested("source", |p| {
+                let table = p.field("table", TableName::try_from(table));
+                let function = p.field("function", FunctionName::try_from(function));
+                Some(Source {
+                    table: table?,
+                    function: function?,
+                })
+            });
+            Some(Form {
+                collection: collection?,
+                source: source?,
+            })
+        })
+    }
+
+    #[test]
+    fn test_parse_all_ok() {
+        let form = parse_form("collection", "table", "function").unwrap();
+        assert_eq!(form.collection.to_string(), "collection");
+        assert_eq!(form.source.table.to_string(), "table");
+        assert_eq!(form.source.function.to_string(), "function");
+    }
+
+    #[test]
+    fn test_parse_all_collects_errors() {
+        let errors = parse_form("bad collection", "table", "bad function").unwrap_err();
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, vec!["collection", "source.function"]);
+        assert!(errors[0].to_string().starts_with("collection: "));
+        assert!(
+            errors[0]
+                .to_string()
+                .contains("Could not parse 'bad collection'")
+        );
+        assert!(errors[1].to_string().starts_with("source.function: "));
+        assert!(
+            errors[1]
+                .to_string()
+                .contains("Could not parse 'bad function'")
+        );
+    }
 }
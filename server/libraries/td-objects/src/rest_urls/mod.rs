@@ -3,11 +3,14 @@
 //
 
 pub mod params;
+pub mod reverse;
 
 use crate::types::basic::{
-    AtTime, CollectionIdName, ExecutionIdName, FunctionIdName, FunctionRunId,
-    InterCollectionPermissionIdName, LogsCastNumber, PermissionIdName, RoleIdName, SampleLen,
-    SampleOffset, Sql, TableIdName, TransactionIdName, UserIdName, WorkerIdName,
+    ApiKeyId, AtTime, BundleChunkOffset, BundleUploadId, Cascade, CollectionIdName,
+    ExecutionIdName, ExpectedFunctionVersionId, FromFunctionVersionId, FunctionIdName,
+    FunctionRunId, FunctionTag, InterCollectionPermissionIdName, LogsCastNumber, PermissionIdName,
+    RoleIdName, SampleLen, SampleOffset, Sql, TableIdName, TagMatch, ToCollectionName,
+    ToFunctionVersionId, TransactionIdName, UserIdName, WebhookId, WorkerIdName,
 };
 use constcat::concat;
 use td_common::logging::LOG_EXTENSION;
@@ -52,6 +55,18 @@ pub const AUTH_ROLE_CHANGE: &str = url!(AUTH, "/role_change");
 pub const AUTH_LOGOUT: &str = url!(AUTH, "/logout");
 pub const AUTH_USER_INFO: &str = url!(AUTH, "/info");
 pub const AUTH_PASSWORD_CHANGE: &str = url!(AUTH, "/password_change");
+pub const AUTH_API_KEYS: &str = url!(AUTH, "/api_keys");
+pub const AUTH_API_KEY: &str = url!(AUTH_API_KEYS, "/{api_key}");
+
+#[td_type::UrlParam]
+pub struct ApiKeyParam {
+    #[td_type(extractor)]
+    api_key: ApiKeyId,
+}
+
+pub const LIST_API_KEYS: &str = url!(AUTH_API_KEYS);
+pub const CREATE_API_KEY: &str = url!(AUTH_API_KEYS);
+pub const REVOKE_API_KEY: &str = url!(AUTH_API_KEY, "/revoke");
 
 pub const CERT_DOWNLOAD: &str = url!("/ssl-cert");
 
@@ -135,6 +150,15 @@ pub const GET_COLLECTION: &str = url!(COLLECTION);
 pub const CREATE_COLLECTION: &str = url!(COLLECTIONS);
 pub const UPDATE_COLLECTION: &str = url!(COLLECTION);
 pub const DELETE_COLLECTION: &str = url!(COLLECTION);
+pub const EXPORT_COLLECTION: &str = url!(COLLECTION, "/export");
+pub const IMPORT_COLLECTION: &str = url!("/collection-import");
+
+#[td_type::QueryParam]
+pub struct CascadeParam {
+    #[td_type(extractor)]
+    #[serde(default)]
+    cascade: Cascade,
+}
 
 #[td_type::UrlParam]
 pub struct InterCollectionPermissionParam {
@@ -146,10 +170,21 @@ pub struct InterCollectionPermissionParam {
 
 pub const INTER_COLLECTION_PERMISSIONS: &str = url!(COLLECTION, "/inter-collection-permissions");
 pub const INTER_COLLECTION_PERMISSION: &str = url!(INTER_COLLECTION_PERMISSIONS, "/{permission}");
+pub const INTER_COLLECTION_PERMISSION_TO: &str =
+    url!(INTER_COLLECTION_PERMISSIONS, "/to/{to_collection}");
+
+#[td_type::UrlParam]
+pub struct InterCollectionPermissionToParam {
+    #[td_type(extractor)]
+    collection: CollectionIdName,
+    #[td_type(extractor)]
+    to_collection: ToCollectionName,
+}
 
 pub const LIST_INTER_COLLECTION_PERMISSIONS: &str = url!(INTER_COLLECTION_PERMISSIONS);
 pub const CREATE_INTER_COLLECTION_PERMISSION: &str = url!(INTER_COLLECTION_PERMISSIONS);
 pub const DELETE_INTER_COLLECTION_PERMISSION: &str = url!(INTER_COLLECTION_PERMISSION);
+pub const REVOKE_INTER_COLLECTION_PERMISSION: &str = url!(INTER_COLLECTION_PERMISSION_TO);
 
 // Functions
 pub const FUNCTIONS: &str = url!(COLLECTION, "/functions");
@@ -164,13 +199,42 @@ pub struct FunctionParam {
 }
 
 pub const FUNCTION_CREATE: &str = url!(FUNCTIONS);
+pub const FUNCTION_CREATE_BATCH: &str = url!(COLLECTION, "/functions-batch");
 pub const FUNCTION_GET: &str = url!(FUNCTION);
+pub const FUNCTION_SNIPPET: &str = url!(FUNCTION, "/snippet");
+pub const FUNCTION_DIFF: &str = url!(FUNCTION, "/diff");
 pub const FUNCTION_DELETE: &str = url!(FUNCTION);
 pub const FUNCTION_LIST_BY_COLL: &str = url!(FUNCTIONS);
 pub const FUNCTION_LIST: &str = url!("/functions");
 pub const FUNCTION_UPDATE: &str = url!(FUNCTION);
 pub const FUNCTION_UPLOAD: &str = url!(COLLECTION, "/function-bundle-upload");
 
+// Chunked function bundle uploads
+pub const FUNCTION_BUNDLE_UPLOADS: &str = url!(COLLECTION, "/function-bundle-uploads");
+pub const FUNCTION_BUNDLE_UPLOAD: &str = url!(FUNCTION_BUNDLE_UPLOADS, "/{upload}");
+
+#[td_type::UrlParam]
+pub struct BundleUploadParam {
+    #[td_type(extractor)]
+    collection: CollectionIdName,
+    #[td_type(extractor)]
+    upload: BundleUploadId,
+}
+
+#[td_type::UrlParam]
+pub struct BundleUploadChunkParam {
+    #[td_type(extractor)]
+    collection: CollectionIdName,
+    #[td_type(extractor)]
+    upload: BundleUploadId,
+    #[td_type(extractor)]
+    offset: BundleChunkOffset,
+}
+
+pub const FUNCTION_UPLOAD_START: &str = url!(FUNCTION_BUNDLE_UPLOADS);
+pub const FUNCTION_UPLOAD_CHUNK: &str = url!(FUNCTION_BUNDLE_UPLOAD, "/chunks/{offset}");
+pub const FUNCTION_UPLOAD_COMMIT: &str = url!(FUNCTION_BUNDLE_UPLOAD, "/commit");
+
 pub const FUNCTION_HISTORY: &str = url!(FUNCTION, "/history");
 pub const FUNCTION_EXECUTE: &str = url!(FUNCTION, "/execute");
 
@@ -182,6 +246,36 @@ pub struct AtTimeParam {
     at: AtTime,
 }
 
+/// Tags to list functions by, functions with any (default) or all of them. No filter if empty.
+/// Tags are not versioned, functions listed at a past time are matched by their current tags.
+#[td_type::QueryParam]
+pub struct FunctionTagsParam {
+    #[td_type(extractor)]
+    #[serde(alias = "tag")]
+    tags: Vec<FunctionTag>,
+    #[td_type(extractor)]
+    #[builder(default)]
+    #[serde(default)]
+    tag_match: TagMatch,
+}
+
+/// Function version an update is based on. When given, the update fails if the function has
+/// been updated since (optimistic concurrency control).
+#[td_type::QueryParam]
+pub struct FunctionExpectedVersionParam {
+    #[td_type(extractor)]
+    #[serde(default)]
+    expected_version: Option<ExpectedFunctionVersionId>,
+}
+
+#[td_type::QueryParam]
+pub struct FunctionDiffParam {
+    #[td_type(extractor)]
+    from: FromFunctionVersionId,
+    #[td_type(extractor)]
+    to: ToFunctionVersionId,
+}
+
 #[td_type::QueryParam]
 pub struct SampleOffsetLenParam {
     #[td_type(extractor)]
@@ -239,6 +333,7 @@ pub const TABLE_DELETE: &str = url!(TABLE);
 // Server status
 pub const SERVER_STATUS: &str = url!("/status");
 pub const RUNTIME_INFO: &str = url!("/runtime-info");
+pub const AUTHZ_CACHE_REFRESH: &str = url!("/authz-cache/refresh");
 
 // Executions
 pub const EXECUTIONS: &str = url!("/executions");
@@ -392,3 +487,20 @@ pub struct FunctionRunParam {
 
 pub const FUNCTION_RUN_GET: &str = url!(FUNCTION_RUN);
 pub const FUNCTION_RUN_LIST: &str = url!("/function_runs");
+pub const FUNCTION_RUN_EVENTS: &str = url!(FUNCTION_RUN, "/events");
+
+// Function run webhooks
+pub const FUNCTION_RUN_WEBHOOKS: &str = url!(COLLECTION, "/function-run-webhooks");
+pub const FUNCTION_RUN_WEBHOOK: &str = url!(FUNCTION_RUN_WEBHOOKS, "/{webhook}");
+
+#[td_type::UrlParam]
+pub struct WebhookParam {
+    #[td_type(extractor)]
+    collection: CollectionIdName,
+    #[td_type(extractor)]
+    webhook: WebhookId,
+}
+
+pub const LIST_FUNCTION_RUN_WEBHOOKS: &str = url!(FUNCTION_RUN_WEBHOOKS);
+pub const CREATE_FUNCTION_RUN_WEBHOOK: &str = url!(FUNCTION_RUN_WEBHOOKS);
+pub const DELETE_FUNCTION_RUN_WEBHOOK: &str = url!(FUNCTION_RUN_WEBHOOK);
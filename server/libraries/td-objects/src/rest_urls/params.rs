@@ -3,12 +3,14 @@
 //
 
 use crate::rest_urls::{
-    AtTimeParam, CollectionParam, FileFormat, FileFormatParam, FunctionParam, SampleOffsetLenParam,
-    SqlParam, TableParam,
+    AtTimeParam, CascadeParam, CollectionParam, FileFormat, FileFormatParam, FunctionDiffParam,
+    FunctionExpectedVersionParam, FunctionParam, FunctionTagsParam, SampleOffsetLenParam, SqlParam,
+    TableParam,
 };
 use crate::types::basic::{
-    AtTime, CollectionIdName, FunctionIdName, SampleLen, SampleOffset, SchemaFieldName,
-    SchemaFieldType, Sql, TableIdName,
+    AtTime, Cascade, CollectionIdName, ExpectedFunctionVersionId, FromFunctionVersionId,
+    FunctionIdName, FunctionTag, SampleLen, SampleOffset, SchemaFieldName, SchemaFieldType, Sql,
+    TableIdName, TagMatch, ToFunctionVersionId,
 };
 use polars::prelude::Field;
 use td_error::TdError;
@@ -30,6 +32,23 @@ impl CollectionAtName {
     }
 }
 
+#[td_type::Dlo]
+pub struct CollectionCascadeName {
+    #[td_type(extractor)]
+    collection: CollectionIdName,
+    #[td_type(extractor)]
+    cascade: Cascade,
+}
+
+impl CollectionCascadeName {
+    pub fn new(collection: CollectionParam, cascade: CascadeParam) -> Self {
+        Self {
+            collection: collection.collection.clone(),
+            cascade: cascade.cascade.clone(),
+        }
+    }
+}
+
 #[td_type::Dlo]
 pub struct FunctionAtIdName {
     #[td_type(extractor)]
@@ -50,6 +69,89 @@ impl FunctionAtIdName {
     }
 }
 
+#[td_type::Dlo]
+pub struct FunctionDiffName {
+    #[td_type(extractor)]
+    collection: CollectionIdName,
+    #[td_type(extractor)]
+    function: FunctionIdName,
+    #[td_type(extractor)]
+    from: FromFunctionVersionId,
+    #[td_type(extractor)]
+    to: ToFunctionVersionId,
+}
+
+impl FunctionDiffName {
+    pub fn new(function: FunctionParam, diff: FunctionDiffParam) -> Self {
+        Self {
+            collection: function.collection.clone(),
+            function: function.function.clone(),
+            from: diff.from.clone(),
+            to: diff.to.clone(),
+        }
+    }
+}
+
+#[td_type::Dlo]
+pub struct FunctionExpectedVersionName {
+    #[td_type(extractor)]
+    collection: CollectionIdName,
+    #[td_type(extractor)]
+    function: FunctionIdName,
+    #[td_type(extractor)]
+    expected_version: Option<ExpectedFunctionVersionId>,
+}
+
+impl FunctionExpectedVersionName {
+    pub fn new(function: FunctionParam, expected_version: FunctionExpectedVersionParam) -> Self {
+        Self {
+            collection: function.collection.clone(),
+            function: function.function.clone(),
+            expected_version: expected_version.expected_version.clone(),
+        }
+    }
+}
+
+impl From<FunctionParam> for FunctionExpectedVersionName {
+    fn from(function: FunctionParam) -> Self {
+        Self {
+            collection: function.collection,
+            function: function.function,
+            expected_version: None,
+        }
+    }
+}
+
+#[td_type::Dlo]
+pub struct FunctionListName {
+    #[td_type(extractor)]
+    at: AtTime,
+    #[td_type(extractor)]
+    tags: Vec<FunctionTag>,
+    #[td_type(extractor)]
+    tag_match: TagMatch,
+}
+
+impl FunctionListName {
+    pub fn new(at: AtTimeParam, tags: FunctionTagsParam) -> Self {
+        Self {
+            at: at.at,
+            tags: tags.tags,
+            tag_match: tags.tag_match,
+        }
+    }
+}
+
+impl From<AtTimeParam> for FunctionListName {
+    fn from(at: AtTimeParam) -> Self {
+        Self {
+            at: at.at,
+            tags: vec![],
+            tag_match: TagMatch::default(),
+        }
+    }
+}
+
 #[td_type::Dlo]
 pub struct TableAtIdName {
     #[td_type(extractor)]
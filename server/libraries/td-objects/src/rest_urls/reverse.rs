@@ -0,0 +1,170 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Reverse routing, building canonical URLs from typed URL params, so clients do not need to
+//! format URLs by hand.
+
+use crate::rest_urls::{
+    AUTH_API_KEY, ApiKeyParam, COLLECTION, CollectionParam, EXECUTION, ExecutionParam, FUNCTION,
+    FUNCTION_RUN, FUNCTION_RUN_WEBHOOK, FunctionParam, FunctionRunIdParam, FunctionRunParam,
+    INTER_COLLECTION_PERMISSION, InterCollectionPermissionParam, PERMISSION, ROLE, RoleParam,
+    RolePermissionParam, TABLE, TRANSACTION, TableParam, TransactionParam, UPDATE_FUNCTION_RUN,
+    USER, USER_ROLE, UserParam, UserRoleParam, WORKER, WebhookParam, WorkerParam,
+};
+use serde::Serialize;
+use serde_json::Value;
+use td_error::{TdError, td_error};
+
+#[td_error]
+enum ReverseUrlError {
+    #[error("URL '{0}' requires param '{1}', which is missing")]
+    MissingParam(String, String) = 0,
+    #[error("URL '{0}' has an unterminated param")]
+    UnterminatedParam(String) = 1,
+    #[error("Could not serialize URL params: {0}")]
+    InvalidParams(#[source] serde_json::Error) = 5000,
+}
+
+/// URL params with a canonical URL (relative to the API base URL).
+pub trait CanonicalUrl: Serialize {
+    const URL: &'static str;
+
+    /// Returns the canonical URL path for these params, with each param URL encoded.
+    fn url(&self) -> Result<String, TdError> {
+        reverse_url(Self::URL, self)
+    }
+}
+
+/// Replaces each `{param}` in the `url` template with the URL encoded value of the `params` field
+/// with the same name.
+pub fn reverse_url(url: &str, params: &impl Serialize) -> Result<String, TdError> {
+    let params = serde_json::to_value(params).map_err(ReverseUrlError::InvalidParams)?;
+
+    let mut reversed = String::with_capacity(url.len());
+    let mut rest = url;
+    while let Some(start) = rest.find('{') {
+        reversed.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            Err(ReverseUrlError::UnterminatedParam(url.to_string()))?
+        };
+        let name = &rest[start + 1..start + end];
+        let value = match params.get(name) {
+            Some(Value::String(value)) => value.clone(),
+            Some(Value::Null) | None => Err(ReverseUrlError::MissingParam(
+                url.to_string(),
+                name.to_string(),
+            ))?,
+            Some(value) => value.to_string(),
+        };
+        reversed.push_str(&encode_segment(&value));
+        rest = &rest[start + end + 1..];
+    }
+    reversed.push_str(rest);
+    Ok(reversed)
+}
+
+/// Percent encodes all but the unreserved characters (RFC 3986) of a path segment.
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+macro_rules! canonical_url {
+    ($($param:ty => $url:expr),* $(,)?) => {
+        $(
+            impl CanonicalUrl for $param {
+                const URL: &'static str = $url;
+            }
+        )*
+    };
+}
+
+canonical_url! {
+    FunctionRunIdParam => UPDATE_FUNCTION_RUN,
+    UserParam => USER,
+    RoleParam => ROLE,
+    RolePermissionParam => PERMISSION,
+    UserRoleParam => USER_ROLE,
+    CollectionParam => COLLECTION,
+    InterCollectionPermissionParam => INTER_COLLECTION_PERMISSION,
+    FunctionParam => FUNCTION,
+    TableParam => TABLE,
+    ExecutionParam => EXECUTION,
+    TransactionParam => TRANSACTION,
+    WorkerParam => WORKER,
+    FunctionRunParam => FUNCTION_RUN,
+    WebhookParam => FUNCTION_RUN_WEBHOOK,
+    ApiKeyParam => AUTH_API_KEY,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::basic::{CollectionId, CollectionIdName, IdOrName};
+
+    #[test]
+    fn test_function_param_url() -> Result<(), TdError> {
+        let param = FunctionParam::builder()
+            .try_collection("collection")?
+            .try_function("function")?
+            .build()?;
+        assert_eq!(param.url()?, "/collections/collection/functions/function");
+        Ok(())
+    }
+
+    #[test]
+    fn test_collection_param_id_url() -> Result<(), TdError> {
+        let collection = CollectionIdName::from_id(CollectionId::default());
+        let param = CollectionParam::builder()
+            .collection(collection.clone())
+            .build()?;
+        assert_eq!(param.url()?, format!("/collections/{collection}"));
+        assert!(param.url()?.starts_with("/collections/~"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_url_encoding() -> Result<(), TdError> {
+        #[derive(Serialize)]
+        struct Params {
+            collection: String,
+            function: String,
+        }
+
+        let params = Params {
+            collection: "my collection".to_string(),
+            function: "a/b?c#d%".to_string(),
+        };
+        assert_eq!(
+            reverse_url(FUNCTION, &params)?,
+            "/collections/my%20collection/functions/a%2Fb%3Fc%23d%25"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_url_missing_param() {
+        #[derive(Serialize)]
+        struct Params {
+            collection: String,
+        }
+
+        let params = Params {
+            collection: "collection".to_string(),
+        };
+        let err = reverse_url(FUNCTION, &params).unwrap_err();
+        assert!(matches!(
+            err.domain_err::<ReverseUrlError>(),
+            ReverseUrlError::MissingParam(_, name) if name == "function"
+        ));
+    }
+}
@@ -28,6 +28,14 @@ pub trait CteQueries<'a, E> {
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError>
     where
         D: DataAccessObject + Versioned + States<S>;
+
+    /// Latest version of each entity in state `S` matching the where-clause, without time bound.
+    fn select_latest_versions<const S: u8, D>(
+        &self,
+        e: &'a E,
+    ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError>
+    where
+        D: DataAccessObject + Versioned + States<S>;
 }
 
 impl<'a, Q, E> CteQueries<'a, E> for Q
@@ -105,6 +113,16 @@ where
         trace!("find_active_versions: sql: {}", query_builder.sql());
         Ok(query_builder)
     }
+
+    fn select_latest_versions<const S: u8, D>(
+        &self,
+        e: &'a E,
+    ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError>
+    where
+        D: DataAccessObject + Versioned + States<S>,
+    {
+        self.select_versions_at::<S, D>(None, e)
+    }
 }
 
 /// CTEs to find the latest versions of objects at a given time.
@@ -794,6 +812,52 @@ mod tests {
         Ok(())
     }
 
+    #[td_test::test(sqlx(fixture = "test_cte"))]
+    #[tokio::test]
+    async fn test_select_latest_versions(db: DbPool) -> Result<(), TdError> {
+        let new = TestDaoBuilder::default()
+            .id(TestId::try_from("0000000000000000000000000S")?)
+            // Same partition ID as 04
+            .partition_id(TestPartition::try_from("0")?)
+            .status(TestStatus::try_from("A")?)
+            .defined_on(AtTime::try_from(
+                "2025-04-02T08:19:55.543+00:00"
+                    .parse::<DateTime<Utc>>()
+                    .unwrap(),
+            )?)
+            .build()?;
+        DaoQueries::default()
+            .insert::<TestDao>(&new)?
+            .build()
+            .execute(&db)
+            .await
+            .unwrap();
+
+        // Only the latest version of each partition, 1 is deleted
+        let mut query_builder =
+            DaoQueries::default().select_latest_versions::<{ TestDao::Active }, TestDao>(&())?;
+        let result: Vec<TestDao> = query_builder.build_query_as().fetch_all(&db).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0], new);
+
+        // Regardless of state, latest of each partition, order by id DESC
+        let mut query_builder =
+            DaoQueries::default().select_latest_versions::<{ TestDao::All }, TestDao>(&())?;
+        let result: Vec<TestDao> = query_builder.build_query_as().fetch_all(&db).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0], new);
+        assert_eq!(result[1], FIXTURE_DAOS[2]);
+
+        // Filtered by partition
+        let by = TestPartition::try_from("1")?;
+        let mut query_builder =
+            DaoQueries::default().select_latest_versions::<{ TestDao::All }, TestDao>(&by)?;
+        let result: Vec<TestDao> = query_builder.build_query_as().fetch_all(&db).await.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0], FIXTURE_DAOS[2]);
+        Ok(())
+    }
+
     #[td_test::test(sqlx(fixture = "test_cte"))]
     #[tokio::test]
     async fn test_select_versions_at_defined_on_04_08_fetch_one(db: DbPool) {
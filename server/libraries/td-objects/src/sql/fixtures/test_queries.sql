@@ -21,3 +21,18 @@ SELECT '00000000000000000000000008',
        'luigi',
        '6789'
 ;
+
+create table test_read_only_table
+(
+    id          TEXT primary key,
+    name        TEXT    not null,
+    modified_on INTEGER not null default 42
+);
+
+create table test_audit_table
+(
+    id            TEXT primary key,
+    name          TEXT      not null,
+    created_on    TIMESTAMP not null,
+    created_by_id TEXT      not null
+);
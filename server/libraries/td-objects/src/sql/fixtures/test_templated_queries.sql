@@ -0,0 +1,17 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+create table test_table
+(
+    id          TEXT primary key,
+    name        TEXT    not null,
+    modified_on INTEGER not null
+);
+
+
+INSERT INTO test_table
+SELECT '00000000000000000000000004',
+       '${name}',
+       '1234'
+;
@@ -2,15 +2,26 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use crate::dxo::crudl::ListParams;
+use crate::dxo::crudl::{AppliedListParams, ListParams};
 use crate::parse::IDENTIFIER_PATTERN;
+use crate::sql::UNIQUE_ID_FIELD;
 use crate::types::basic::LikeFilter;
-use crate::types::{ListQuery, SqlEntity};
+use crate::types::{DataAccessObject, ListQuery, SqlEntity};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, SecondsFormat, Utc};
 use itertools::Itertools;
 use regex::Regex;
+use ring::hmac;
+use ring::rand::SystemRandom;
+use serde::Deserialize;
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+use std::fmt::{Display, Formatter};
 use std::marker::PhantomData;
 use std::str::FromStr;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, OnceLock};
+use td_common::datetime::IntoRelativeDateTimeUtc;
 use td_error::{TdError, td_error};
 
 #[td_error]
@@ -21,23 +32,114 @@ pub enum ListError {
     InvalidCondition(String, String) = 1,
     #[error("Undefined field: {0}")]
     UndefinedField(String) = 2,
-    #[error("Undefined filter: {0}")]
-    UndefinedFilter(String) = 3,
-    #[error("Undefined like filter: {0}")]
-    UndefinedLikeFilter(String) = 4,
-    #[error("Undefined order by: {0}")]
-    UndefinedOrderBy(String) = 5,
+    #[error("Undefined filter field '{0}', filter fields are: [{1}]")]
+    UndefinedFilter(String, String) = 3,
+    #[error("Undefined like filter field '{0}', like filter fields are: [{1}]")]
+    UndefinedLikeFilter(String, String) = 4,
+    #[error("Undefined order by field '{0}', order by fields are: [{1}]")]
+    UndefinedOrderBy(String, String) = 5,
     #[error("Previous and Next parameters cannot be used together")]
     PreviousAndNext = 6,
     #[error("Natural Id must be use in pagination with Previous or Next parameters")]
     MissingPaginationParams = 7,
-    #[error("Invalid between condition '{0}', it must be <NAME>:btw:<min>::<max>")]
+    #[error(
+        "Invalid between condition '{0}', it must be <NAME>:btw:<min>::<max>, with at most one empty bound"
+    )]
     InvalidBetweenCondition(String) = 8,
+    #[error("Invalid pagination cursor: {0}")]
+    InvalidCursor(String) = 9,
+    #[error("Undefined select field: {0}")]
+    UndefinedSelectField(String) = 10,
+    #[error("Duplicate order by field: {0}")]
+    DuplicateOrderBy(String) = 11,
+    #[error(
+        "Pagination values do not match the order by fields, expected {0} values but got {1} (use the pagination cursor)"
+    )]
+    InvalidPaginationValues(usize, usize) = 12,
 
     #[error("Error computing SQL entity value: {0}")]
     InvalidSqlEntity(#[source] TdError) = 5000,
 }
 
+/// Key cursors are signed with, see [`Cursor::init_secret`].
+static CURSOR_KEY: OnceLock<hmac::Key> = OnceLock::new();
+
+/// Opaque pagination cursor, combining the order-by column values and the pagination ID of the
+/// row to paginate from into a single URL safe token.
+///
+/// The token is signed (HMAC-SHA256) with a server secret, so only cursors issued by the server
+/// are accepted.
+pub struct Cursor;
+
+impl Cursor {
+    const SIGNATURE_LEN: usize = 32;
+
+    /// Sets the server secret cursors are signed with. It must be set before any cursor is
+    /// encoded or decoded, otherwise a random secret is used (and cursors do not survive
+    /// restarts). Returns `false` if the secret was already set.
+    pub fn init_secret(secret: &[u8]) -> bool {
+        // the key is derived, the secret may be used for other purposes
+        let derived = hmac::sign(
+            &hmac::Key::new(hmac::HMAC_SHA256, secret),
+            b"pagination-cursor",
+        );
+        CURSOR_KEY
+            .set(hmac::Key::new(hmac::HMAC_SHA256, derived.as_ref()))
+            .is_ok()
+    }
+
+    fn key() -> &'static hmac::Key {
+        CURSOR_KEY.get_or_init(|| {
+            // random key generation only fails if the system random source is unavailable
+            hmac::Key::generate(hmac::HMAC_SHA256, &SystemRandom::new()).unwrap()
+        })
+    }
+
+    /// Encodes the order-by column values and the pagination ID into a cursor.
+    pub fn encode(order_values: &[String], id: &str) -> String {
+        Self::encode_with(Self::key(), order_values, id)
+    }
+
+    /// Decodes a cursor into the order-by column values and the pagination ID.
+    pub fn decode(cursor: &str) -> Result<(Vec<String>, String), ListError> {
+        Self::decode_with(Self::key(), cursor)
+    }
+
+    fn encode_with(key: &hmac::Key, order_values: &[String], id: &str) -> String {
+        // (order_values, id) serialization is infallible
+        let mut token = serde_json::to_vec(&(order_values, id)).unwrap();
+        let signature = hmac::sign(key, &token);
+        token.extend_from_slice(signature.as_ref());
+        URL_SAFE_NO_PAD.encode(token)
+    }
+
+    fn decode_with(key: &hmac::Key, cursor: &str) -> Result<(Vec<String>, String), ListError> {
+        let invalid = || ListError::InvalidCursor(cursor.to_string());
+        let token = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        if token.len() < Self::SIGNATURE_LEN {
+            Err(invalid())?
+        }
+        let (payload, signature) = token.split_at(token.len() - Self::SIGNATURE_LEN);
+        hmac::verify(key, payload, signature).map_err(|_| invalid())?;
+        let (order_values, id): (CursorValues, String) =
+            serde_json::from_slice(payload).map_err(|_| invalid())?;
+        let order_values = match order_values {
+            CursorValues::Single(value) => vec![value],
+            CursorValues::Multiple(values) => values,
+        };
+        Ok((order_values, id))
+    }
+}
+
+/// Order-by column values of a cursor, cursors ordered by a single column (before ordering by
+/// multiple columns was supported) have a single value.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CursorValues {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Order {
     Asc(String),
@@ -111,6 +213,15 @@ impl Order {
     }
 }
 
+impl Display for Order {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Order::Asc(field) => write!(f, "{field}+"),
+            Order::Desc(field) => write!(f, "{field}-"),
+        }
+    }
+}
+
 impl FromStr for Order {
     type Err = ListError;
     fn from_str(value: &str) -> Result<Self, Self::Err> {
@@ -123,6 +234,7 @@ pub enum Condition<D> {
     Eq(String, Box<dyn SqlEntity>),
     Ne(String, Box<dyn SqlEntity>),
     Lk(String, Box<LikeFilter>),
+    Nlk(String, Box<LikeFilter>),
     Gt(String, Box<dyn SqlEntity>),
     Ge(String, Box<dyn SqlEntity>),
     Lt(String, Box<dyn SqlEntity>),
@@ -146,7 +258,9 @@ impl<D: ListQuery + Eq> PartialEq for Condition<D> {
                     && min1.as_display() == min2.as_display()
                     && max1.as_display() == max2.as_display()
             }
-            (Lk(f1, v1), Lk(f2, v2)) => f1 == f2 && v1.as_display() == v2.as_display(),
+            (Lk(f1, v1), Lk(f2, v2)) | (Nlk(f1, v1), Nlk(f2, v2)) => {
+                f1 == f2 && v1.as_display() == v2.as_display()
+            }
             (Phantom(_), Phantom(_)) => true,
             _ => false,
         }
@@ -154,84 +268,151 @@ impl<D: ListQuery + Eq> PartialEq for Condition<D> {
 }
 impl<D: ListQuery + Eq> Eq for Condition<D> {}
 
+const EQ: &str = ":eq:";
+const NE: &str = ":ne:";
+const GT: &str = ":gt:";
+const GE: &str = ":ge:";
+const LT: &str = ":lt:";
+const LE: &str = ":le:";
+const LK: &str = ":lk:";
+const NLK: &str = ":nlk:";
+const BTW: &str = ":btw:";
+
+const OPERATORS: &str = constcat::concat!(
+    EQ, "|", NE, "|", GT, "|", GE, "|", LT, "|", LE, "|", LK, "|", NLK, "|", BTW
+);
+const CONDITION_PATTERN: &str = constcat::concat!(
+    "^(?<field>",
+    IDENTIFIER_PATTERN,
+    ")(?<operator>(",
+    OPERATORS,
+    "))(?<value>(.*))$"
+);
+
+static CONDITION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(CONDITION_PATTERN).unwrap());
+
 impl<D: ListQuery> Condition<D> {
-    fn parse(s: &str) -> Result<Self, TdError> {
-        const EQ: &str = ":eq:";
-        const NE: &str = ":ne:";
-        const GT: &str = ":gt:";
-        const GE: &str = ":ge:";
-        const LT: &str = ":lt:";
-        const LE: &str = ":le:";
-        const LK: &str = ":lk:";
-        const BTW: &str = ":btw:";
-
-        const OPERATORS: &str = constcat::concat!(
-            EQ, "|", NE, "|", GT, "|", GE, "|", LT, "|", LE, "|", LK, "|", BTW
-        );
-        const CONDITION_PATTERN: &str = constcat::concat!(
-            "^(?<field>",
-            IDENTIFIER_PATTERN,
-            ")(?<operator>(",
-            OPERATORS,
-            "))(?<value>(.*))$"
-        );
+    /// Checks the field of a condition is a filter field (a like filter field for like operators),
+    /// before parsing its value. Malformed conditions are left to [`Condition::parse`].
+    fn check_field(s: &str) -> Result<(), ListError> {
+        if let Some(captures) = CONDITION_REGEX.captures(s) {
+            let field = captures.name("field").unwrap().as_str();
+            let operator = captures.name("operator").unwrap().as_str();
+            let (fields, undefined): (_, fn(String, String) -> ListError) = match operator {
+                LK | NLK => (D::filter_by_like_fields(), ListError::UndefinedLikeFilter),
+                _ => (D::filter_by_fields(), ListError::UndefinedFilter),
+            };
+            if !fields.contains(&field) {
+                Err(undefined(field.to_string(), fields.join(", ")))?
+            }
+        }
+        Ok(())
+    }
+
+    /// Maps the value of a condition to the type of its field. Values of timestamp fields not
+    /// valid for them that are relative timestamp expressions (e.g. `-7d`) are resolved against
+    /// the current time, so timestamp fields can be filtered with them.
+    fn filter_value(field: &str, value: &str) -> Result<Box<dyn SqlEntity>, TdError> {
+        let now = Utc::now();
+        let sql_value = match D::map_sql_entity_value(field, value) {
+            Err(err) if Self::is_timestamp_field(field, now) => {
+                match value.relative_datetime_utc(now)? {
+                    Some(resolved) => {
+                        let resolved = resolved.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+                        D::map_sql_entity_value(field, &resolved).map_err(|_| err)?
+                    }
+                    None => Err(err)?,
+                }
+            }
+            Err(err) => Err(err)?,
+            Ok(sql_value) => sql_value,
+        };
+        Ok(sql_value.ok_or(ListError::UndefinedField(field.to_string()))?)
+    }
 
-        static CONDITION_REGEX: LazyLock<Regex> =
-            LazyLock::new(|| Regex::new(CONDITION_PATTERN).unwrap());
+    /// If the field is a timestamp, mapping a timestamp value to its type.
+    fn is_timestamp_field(field: &str, now: DateTime<Utc>) -> bool {
+        let now = now.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        matches!(
+            D::map_sql_entity_value(field, &now),
+            Ok(Some(sql_value)) if sql_value.is_timestamp()
+        )
+    }
 
+    fn parse(s: &str) -> Result<Self, TdError> {
         if let Some(captures) = CONDITION_REGEX.captures(s) {
             let field = captures.name("field").unwrap().as_str().to_string();
             let operator = captures.name("operator").unwrap().as_str().to_string();
             let value = captures.name("value").unwrap().as_str().to_string();
             let condition = match operator.as_str() {
                 EQ => {
-                    let sql_value = D::map_sql_entity_value(&field, &value)?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
+                    let sql_value = Self::filter_value(&field, &value)?;
                     Self::Eq(field, sql_value)
                 }
                 NE => {
-                    let sql_value = D::map_sql_entity_value(&field, &value)?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
+                    let sql_value = Self::filter_value(&field, &value)?;
                     Self::Ne(field, sql_value)
                 }
                 GT => {
-                    let sql_value = D::map_sql_entity_value(&field, &value)?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
+                    let sql_value = Self::filter_value(&field, &value)?;
                     Self::Gt(field, sql_value)
                 }
                 GE => {
-                    let sql_value = D::map_sql_entity_value(&field, &value)?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
+                    let sql_value = Self::filter_value(&field, &value)?;
                     Self::Ge(field, sql_value)
                 }
                 LT => {
-                    let sql_value = D::map_sql_entity_value(&field, &value)?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
+                    let sql_value = Self::filter_value(&field, &value)?;
                     Self::Lt(field, sql_value)
                 }
                 LE => {
-                    let sql_value = D::map_sql_entity_value(&field, &value)?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
+                    let sql_value = Self::filter_value(&field, &value)?;
                     Self::Le(field, sql_value)
                 }
                 LK => {
                     // check that it exists, but do not convert to type, as it is a LIKE filter
                     if !D::filter_by_like_fields().contains(&field.as_str()) {
-                        Err(ListError::UndefinedLikeFilter(field.clone()))?
+                        Err(ListError::UndefinedLikeFilter(
+                            field.clone(),
+                            D::filter_by_like_fields().join(", "),
+                        ))?
                     }
                     let converted = Box::new(Self::convert_to_like_pattern(&value).try_into()?);
                     Self::Lk(field, converted)
                 }
+                NLK => {
+                    // same as LIKE, the field must be a like filter
+                    if !D::filter_by_like_fields().contains(&field.as_str()) {
+                        Err(ListError::UndefinedLikeFilter(
+                            field.clone(),
+                            D::filter_by_like_fields().join(", "),
+                        ))?
+                    }
+                    let converted = Box::new(Self::convert_to_like_pattern(&value).try_into()?);
+                    Self::Nlk(field, converted)
+                }
                 BTW => {
                     let min_max = value.split("::").collect::<Vec<_>>();
                     if min_max.len() != 2 {
                         Err(ListError::InvalidBetweenCondition(s.to_string()))?
                     }
-                    let sql_min = D::map_sql_entity_value(&field, min_max[0])?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
-                    let sql_max = D::map_sql_entity_value(&field, min_max[1])?
-                        .ok_or(ListError::UndefinedField(field.clone()))?;
-                    Self::Btw(field, sql_min, sql_max)
+                    // an empty bound makes the range open-ended on that side
+                    match (min_max[0], min_max[1]) {
+                        ("", "") => Err(ListError::InvalidBetweenCondition(s.to_string()))?,
+                        (min, "") => {
+                            let sql_min = Self::filter_value(&field, min)?;
+                            Self::Ge(field, sql_min)
+                        }
+                        ("", max) => {
+                            let sql_max = Self::filter_value(&field, max)?;
+                            Self::Le(field, sql_max)
+                        }
+                        (min, max) => {
+                            let sql_min = Self::filter_value(&field, min)?;
+                            let sql_max = Self::filter_value(&field, max)?;
+                            Self::Btw(field, sql_min, sql_max)
+                        }
+                    }
                 }
                 _ => Err(ListError::InvalidCondition(
                     OPERATORS.to_string(),
@@ -252,6 +433,7 @@ impl<D: ListQuery> Condition<D> {
             Condition::Eq(field, _) => field,
             Condition::Ne(field, _) => field,
             Condition::Lk(field, _) => field,
+            Condition::Nlk(field, _) => field,
             Condition::Gt(field, _) => field,
             Condition::Ge(field, _) => field,
             Condition::Lt(field, _) => field,
@@ -293,6 +475,7 @@ impl<D: ListQuery> Condition<D> {
             Condition::Eq(_, value) => vec![&**value],
             Condition::Ne(_, value) => vec![&**value],
             Condition::Lk(_, value) => vec![&**value],
+            Condition::Nlk(_, value) => vec![&**value],
             Condition::Gt(_, value) => vec![&**value],
             Condition::Ge(_, value) => vec![&**value],
             Condition::Lt(_, value) => vec![&**value],
@@ -307,6 +490,7 @@ impl<D: ListQuery> Condition<D> {
             Condition::Eq(_, _) => "=",
             Condition::Ne(_, _) => "!=",
             Condition::Lk(_, _) => "LIKE",
+            Condition::Nlk(_, _) => "NOT LIKE",
             Condition::Gt(_, _) => ">",
             Condition::Ge(_, _) => ">=",
             Condition::Lt(_, _) => "<",
@@ -324,7 +508,7 @@ impl<D: ListQuery> Condition<D> {
             | Condition::Ge(_, _)
             | Condition::Lt(_, _)
             | Condition::Le(_, _) => "",
-            Condition::Lk(_, _) => r#"ESCAPE '\'"#,
+            Condition::Lk(_, _) | Condition::Nlk(_, _) => r#"ESCAPE '\'"#,
             Condition::Btw(_, _, _) => "AND",
             Condition::Phantom(_) => unreachable!(),
         }
@@ -335,6 +519,7 @@ impl<D: ListQuery> Condition<D> {
             Condition::Eq(_, _)
             | Condition::Ne(_, _)
             | Condition::Lk(_, _)
+            | Condition::Nlk(_, _)
             | Condition::Gt(_, _)
             | Condition::Ge(_, _)
             | Condition::Lt(_, _)
@@ -345,6 +530,25 @@ impl<D: ListQuery> Condition<D> {
     }
 }
 
+impl<D> Display for Condition<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Condition::Eq(field, value) => write!(f, "{field}:eq:{}", value.as_display()),
+            Condition::Ne(field, value) => write!(f, "{field}:ne:{}", value.as_display()),
+            Condition::Lk(field, value) => write!(f, "{field}:lk:{}", value.as_display()),
+            Condition::Nlk(field, value) => write!(f, "{field}:nlk:{}", value.as_display()),
+            Condition::Gt(field, value) => write!(f, "{field}:gt:{}", value.as_display()),
+            Condition::Ge(field, value) => write!(f, "{field}:ge:{}", value.as_display()),
+            Condition::Lt(field, value) => write!(f, "{field}:lt:{}", value.as_display()),
+            Condition::Le(field, value) => write!(f, "{field}:le:{}", value.as_display()),
+            Condition::Btw(field, min, max) => {
+                write!(f, "{field}:btw:{}::{}", min.as_display(), max.as_display())
+            }
+            Condition::Phantom(_) => unreachable!(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct OrConditions<D>(pub Vec<Condition<D>>);
 
@@ -404,63 +608,147 @@ where
     }
 }
 
+/// Keyset pagination, with the values of the ordered by columns (in order), the pagination ID
+/// and, if it breaks the ties of the order, the unique id of the row to paginate from. The
+/// unique id is only given by pagination cursors.
 pub enum Pagination {
-    Previous(Box<dyn SqlEntity>, Box<dyn SqlEntity>),
-    Next(Box<dyn SqlEntity>, Box<dyn SqlEntity>),
+    Previous(
+        Vec<Box<dyn SqlEntity>>,
+        Box<dyn SqlEntity>,
+        Option<TiebreakerId>,
+    ),
+    Next(
+        Vec<Box<dyn SqlEntity>>,
+        Box<dyn SqlEntity>,
+        Option<TiebreakerId>,
+    ),
 }
 
 impl Pagination {
-    pub fn column_value(&self) -> &dyn SqlEntity {
+    pub fn column_values(&self) -> &[Box<dyn SqlEntity>] {
         match self {
-            Pagination::Previous(column_value, _) => &**column_value,
-            Pagination::Next(column_value, _) => &**column_value,
+            Pagination::Previous(column_values, _, _) => column_values,
+            Pagination::Next(column_values, _, _) => column_values,
         }
     }
 
     pub fn pagination_id(&self) -> &dyn SqlEntity {
         match self {
-            Pagination::Previous(_, pagination_id) => &**pagination_id,
-            Pagination::Next(_, pagination_id) => &**pagination_id,
+            Pagination::Previous(_, pagination_id, _) => &**pagination_id,
+            Pagination::Next(_, pagination_id, _) => &**pagination_id,
+        }
+    }
+
+    pub fn tiebreaker_id(&self) -> Option<&TiebreakerId> {
+        match self {
+            Pagination::Previous(_, _, tiebreaker_id) => tiebreaker_id.as_ref(),
+            Pagination::Next(_, _, tiebreaker_id) => tiebreaker_id.as_ref(),
         }
     }
 }
 
+/// Unique id of a row, as stored, breaking the ties of the list order in pagination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TiebreakerId(String);
+
+impl TiebreakerId {
+    /// Reads the unique id of a listed row.
+    pub fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        row.try_get(UNIQUE_ID_FIELD).map(Self)
+    }
+}
+
+impl SqlEntity for TiebreakerId {
+    fn push_bind<'a>(&'a self, builder: &mut sqlx::QueryBuilder<'a, sqlx::Sqlite>) {
+        builder.push_bind(&self.0);
+    }
+
+    fn push_bind_unseparated<'a>(
+        &'a self,
+        builder: &mut sqlx::query_builder::Separated<'_, 'a, sqlx::Sqlite, &str>,
+    ) {
+        builder.push_bind_unseparated(&self.0);
+    }
+
+    fn as_display(&self) -> String {
+        self.0.clone()
+    }
+
+    fn from_display(s: impl ToString) -> Result<Self, TdError> {
+        Ok(Self(s.to_string()))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
 pub struct ListQueryParams<D: ListQuery> {
     pub len: usize,
     pub conditions: AndConditions<D>,
     pub natural_order: Order,
-    pub order: Option<Order>,
+    /// The requested order, by one or more fields, empty if none was requested.
+    pub order: Vec<Order>,
     pub pagination: Option<Pagination>,
 }
 
+impl<D: ListQuery> ListQueryParams<D> {
+    /// Returns the order of the list, the requested order or the natural order if none was
+    /// requested. Pagination values are given for these fields.
+    pub fn ordered_by(&self) -> &[Order] {
+        ordered_by(&self.order, &self.natural_order)
+    }
+
+    /// Returns if the unique id breaks the ties of the order, as the list is not ordered by it.
+    /// It is then the last column of the order, and its value is the last one of the cursors.
+    pub fn id_tiebreaker(&self) -> bool {
+        id_tiebreaker::<D>(&self.order, &self.natural_order)
+    }
+
+    /// Returns the list parameters as applied, after parsing and normalization. Filters are
+    /// sorted, and the natural order is returned if no order was requested.
+    pub fn applied(&self) -> AppliedListParams {
+        let filter = self
+            .conditions
+            .conditions()
+            .iter()
+            .flat_map(OrConditions::conditions)
+            .map(ToString::to_string)
+            .sorted()
+            .collect();
+        let order_by = self.ordered_by().iter().join(",");
+        AppliedListParams {
+            len: self.len,
+            filter,
+            order_by,
+        }
+    }
+}
+
 impl<D: ListQuery> TryFrom<&ListParams> for ListQueryParams<D> {
     type Error = TdError;
     fn try_from(value: &ListParams) -> Result<Self, Self::Error> {
+        // fields are checked before parsing the values, to report undefined fields as such
+        value
+            .filter
+            .iter()
+            .map(String::as_str)
+            .try_for_each(Condition::<D>::check_field)?;
         let conditions = value
             .filter
             .iter()
             .map(String::as_str)
             .map(Condition::parse)
             .collect::<Result<Vec<Condition<D>>, _>>()?;
-        conditions
-            .iter()
-            .map(|c| match c {
-                Condition::Lk(field, _) => {
-                    if !D::filter_by_like_fields().contains(&field.as_str()) {
-                        Err(ListError::UndefinedLikeFilter(field.to_string()))
-                    } else {
-                        Ok(())
-                    }
-                }
-                c => {
-                    if !D::filter_by_fields().contains(&c.field()) {
-                        Err(ListError::UndefinedFilter(c.field().to_string()))
-                    } else {
-                        Ok(())
-                    }
-                }
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+
+        if let Some(field) = value
+            .selected_fields()
+            .into_iter()
+            .flatten()
+            .find(|field| !D::select_fields().contains(field))
+        {
+            Err(ListError::UndefinedSelectField(field.to_string()))?;
+        }
 
         let or_condition_groups = conditions
             .into_iter()
@@ -473,22 +761,30 @@ impl<D: ListQuery> TryFrom<&ListParams> for ListQueryParams<D> {
             .into();
 
         let order = match &value.order_by {
-            Some(o) => {
-                let o = o.parse()?;
-                match o {
-                    Order::Asc(field) | Order::Desc(field)
-                        if !D::order_by_fields().contains(&field.as_str()) =>
-                    {
-                        Err(ListError::UndefinedOrderBy(field.to_string()))
-                    }
-                    _ => Ok(Some(o)),
-                }
-            }
-            None => Ok(None),
-        }?;
+            Some(order_by) => order_by
+                .split(',')
+                .map(str::trim)
+                .map(Order::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+        if let Some(field) = order
+            .iter()
+            .map(Order::field)
+            .find(|field| !D::order_by_fields().contains(field))
+        {
+            Err(ListError::UndefinedOrderBy(
+                field.to_string(),
+                D::order_by_fields().join(", "),
+            ))?
+        }
+        if let Some(field) = order.iter().map(Order::field).duplicates().next() {
+            Err(ListError::DuplicateOrderBy(field.to_string()))?
+        }
 
+        // The natural order follows the direction of the first order-by field.
         let default_pagination_order = Order::parse(D::pagination_by())?;
-        let natural_order = match &order {
+        let natural_order = match order.first() {
             Some(o) => match o {
                 Order::Asc(o) if o != D::pagination_by() => {
                     Order::asc(default_pagination_order.field())
@@ -501,27 +797,68 @@ impl<D: ListQuery> TryFrom<&ListParams> for ListQueryParams<D> {
             None => default_pagination_order,
         };
 
-        // Column value applies to order-by column, or natural-order-by column if order-by is empty.
-        let pagination = match (&value.previous, &value.next, &value.pagination_id) {
+        // A previous or next value without pagination ID is a cursor, with all of them, and the
+        // unique id last if it breaks the ties of the order. A pagination ID alone is missing the
+        // previous or next value. Without cursor there is no unique id to break the ties.
+        let cursor = |cursor: &str| -> Result<_, ListError> {
+            let (mut column_values, pagination_id) = Cursor::decode(cursor)?;
+            let tiebreaker_id = match id_tiebreaker::<D>(&order, &natural_order) {
+                true => column_values.pop().map(TiebreakerId),
+                false => None,
+            };
+            Ok((column_values, pagination_id, tiebreaker_id))
+        };
+        let (previous, next, pagination_id, tiebreaker_id) =
+            match (&value.previous, &value.next, &value.pagination_id) {
+                (Some(previous), None, None) => {
+                    let (column_values, pagination_id, tiebreaker_id) = cursor(previous)?;
+                    (
+                        Some(column_values),
+                        None,
+                        Some(pagination_id),
+                        tiebreaker_id,
+                    )
+                }
+                (None, Some(next), None) => {
+                    let (column_values, pagination_id, tiebreaker_id) = cursor(next)?;
+                    (
+                        None,
+                        Some(column_values),
+                        Some(pagination_id),
+                        tiebreaker_id,
+                    )
+                }
+                (previous, next, pagination_id) => (
+                    previous.clone().map(|v| vec![v]),
+                    next.clone().map(|v| vec![v]),
+                    pagination_id.clone(),
+                    None,
+                ),
+            };
+
+        // Column values apply to order-by columns, or natural-order-by column if order-by is empty.
+        let pagination = match (&previous, &next, &pagination_id) {
             (Some(_), Some(_), _) => Err(ListError::PreviousAndNext),
-            (Some(_), _, None) => Err(ListError::MissingPaginationParams),
-            (_, Some(_), None) => Err(ListError::MissingPaginationParams),
             (None, None, Some(_)) => Err(ListError::MissingPaginationParams),
-            (Some(column_value), None, Some(pagination_id)) => {
-                let column_value = order
-                    .as_ref()
-                    .unwrap_or(&natural_order)
-                    .value_sql_entity::<D>(column_value)?;
+            (Some(column_values), None, Some(pagination_id)) => {
+                let column_values =
+                    column_sql_entities::<D>(ordered_by(&order, &natural_order), column_values)?;
                 let pagination_id = natural_order.value_sql_entity::<D>(pagination_id)?;
-                Ok(Some(Pagination::Previous(column_value, pagination_id)))
+                Ok(Some(Pagination::Previous(
+                    column_values,
+                    pagination_id,
+                    tiebreaker_id,
+                )))
             }
-            (None, Some(column_value), Some(pagination_id)) => {
-                let column_value = order
-                    .as_ref()
-                    .unwrap_or(&natural_order)
-                    .value_sql_entity::<D>(column_value)?;
+            (None, Some(column_values), Some(pagination_id)) => {
+                let column_values =
+                    column_sql_entities::<D>(ordered_by(&order, &natural_order), column_values)?;
                 let pagination_id = natural_order.value_sql_entity::<D>(pagination_id)?;
-                Ok(Some(Pagination::Next(column_value, pagination_id)))
+                Ok(Some(Pagination::Next(
+                    column_values,
+                    pagination_id,
+                    tiebreaker_id,
+                )))
             }
             _ => Ok(None),
         }?;
@@ -536,11 +873,48 @@ impl<D: ListQuery> TryFrom<&ListParams> for ListQueryParams<D> {
     }
 }
 
+fn ordered_by<'a>(order: &'a [Order], natural_order: &'a Order) -> &'a [Order] {
+    if order.is_empty() {
+        std::slice::from_ref(natural_order)
+    } else {
+        order
+    }
+}
+
+fn id_tiebreaker<D: ListQuery>(order: &[Order], natural_order: &Order) -> bool {
+    let ordered_by_id = std::iter::once(natural_order)
+        .chain(order)
+        .any(|o| D::map_dao_field(o.field()) == UNIQUE_ID_FIELD);
+    !ordered_by_id && <D::Dao as DataAccessObject>::fields().contains(&UNIQUE_ID_FIELD)
+}
+
+fn column_sql_entities<D: ListQuery>(
+    ordered_by: &[Order],
+    column_values: &[String],
+) -> Result<Vec<Box<dyn SqlEntity>>, ListError> {
+    if ordered_by.len() != column_values.len() {
+        Err(ListError::InvalidPaginationValues(
+            ordered_by.len(),
+            column_values.len(),
+        ))?
+    }
+    ordered_by
+        .iter()
+        .zip(column_values)
+        .map(|(order, value)| order.value_sql_entity::<D>(value))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::dxo::crudl::ListParamsBuilder;
+    use crate::sql::{DaoQueries, ListBy, NoListFilter};
+    use crate::types::basic::AtTime;
+    use chrono::{DateTime, TimeDelta, Utc};
     use std::any::Any;
+    use td_common::datetime::IntoDateTimeError;
+    use td_error::ApiError;
 
     #[td_type::Dao]
     struct TestDao {
@@ -629,6 +1003,165 @@ mod tests {
             Condition::<TestDto>::parse("a:lk:A").unwrap(),
             Condition::Lk("a".to_string(), Box::new("A".try_into().unwrap()))
         );
+        assert_eq!(
+            Condition::<TestDto>::parse("a:nlk:A").unwrap(),
+            Condition::Nlk("a".to_string(), Box::new("A".try_into().unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_condition_parse_between() {
+        assert_eq!(
+            Condition::<TestDto>::parse("a:btw:A::B").unwrap(),
+            Condition::Btw(
+                "a".to_string(),
+                Box::new("A".to_string()),
+                Box::new("B".to_string())
+            )
+        );
+        assert!(Condition::<TestDto>::parse("a:btw:A").is_err());
+        assert!(Condition::<TestDto>::parse("a:btw:A::B::C").is_err());
+    }
+
+    #[test]
+    fn test_condition_parse_between_lower_open() {
+        assert_eq!(
+            Condition::<TestDto>::parse("a:btw:::B").unwrap(),
+            Condition::Le("a".to_string(), Box::new("B".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_condition_parse_between_upper_open() {
+        assert_eq!(
+            Condition::<TestDto>::parse("a:btw:A::").unwrap(),
+            Condition::Ge("a".to_string(), Box::new("A".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_condition_parse_between_empty() {
+        let err = Condition::<TestDto>::parse("a:btw:::").unwrap_err();
+        assert!(matches!(
+            err.domain_err::<ListError>(),
+            ListError::InvalidBetweenCondition(_)
+        ));
+    }
+
+    #[td_type::Dao]
+    #[dao(sql_table = "test_time_table")]
+    struct TestTimeDao {
+        id: String,
+        modified_on: AtTime,
+    }
+
+    #[td_type::Dto]
+    #[dto(list(on = TestTimeDao))]
+    #[td_type(builder(try_from = TestTimeDao))]
+    struct TestTimeDto {
+        #[dto(list(pagination_by = "+"))]
+        id: String,
+        #[dto(list(filter))]
+        modified_on: AtTime,
+    }
+
+    fn at_time(value: &dyn SqlEntity) -> DateTime<Utc> {
+        **value.as_any().downcast_ref::<AtTime>().unwrap()
+    }
+
+    #[test]
+    fn test_condition_parse_relative_time() -> Result<(), TdError> {
+        let before = Utc::now() - TimeDelta::days(7);
+        let condition = Condition::<TestTimeDto>::parse("modified_on:gt:-7d")?;
+        let after = Utc::now() - TimeDelta::days(7);
+
+        let Condition::Gt(field, value) = &condition else {
+            panic!("expected a greater than condition, got {condition}");
+        };
+        assert_eq!(field, "modified_on");
+        let resolved = at_time(&**value);
+        assert!(before <= resolved && resolved <= after);
+
+        // values of non timestamp fields are not resolved
+        let condition = Condition::<TestTimeDto>::parse("id:eq:-7d")?;
+        assert_eq!(condition.values()[0].as_display(), "-7d");
+        Ok(())
+    }
+
+    #[test]
+    fn test_condition_parse_between_relative_time() -> Result<(), TdError> {
+        let before = Utc::now();
+        let condition = Condition::<TestTimeDto>::parse("modified_on:btw:-30d::now")?;
+        let after = Utc::now();
+
+        let Condition::Btw(_, min, max) = &condition else {
+            panic!("expected a between condition, got {condition}");
+        };
+        let (min, max) = (at_time(&**min), at_time(&**max));
+        assert!(before - TimeDelta::days(30) <= min && min <= after - TimeDelta::days(30));
+        assert!(before <= max && max <= after);
+        Ok(())
+    }
+
+    #[test]
+    fn test_condition_parse_invalid_relative_time() {
+        let err = Condition::<TestTimeDto>::parse("modified_on:gt:-7x").unwrap_err();
+        assert!(matches!(
+            err.domain_err::<IntoDateTimeError>(),
+            IntoDateTimeError::InvalidRelativeTimestamp(_)
+        ));
+    }
+
+    #[test]
+    fn test_condition_parse_relative_time_non_timestamp_field() {
+        #[td_type::typed(i64)]
+        struct TestCount;
+
+        #[td_type::Dao]
+        #[dao(sql_table = "test_count_table")]
+        struct TestCountDao {
+            id: String,
+            count: TestCount,
+        }
+
+        #[td_type::Dto]
+        #[dto(list(on = TestCountDao))]
+        #[td_type(builder(try_from = TestCountDao))]
+        struct TestCountDto {
+            #[dto(list(pagination_by = "+"))]
+            id: String,
+            #[dto(list(filter))]
+            count: TestCount,
+        }
+
+        // values of non timestamp fields fail as invalid for the field, not as relative timestamps
+        for value in ["nowhere", "-7x", "-7d"] {
+            let err = Condition::<TestCountDto>::parse(&format!("count:eq:{value}")).unwrap_err();
+            let source = std::error::Error::source(&err).unwrap();
+            assert!(source.downcast_ref::<IntoDateTimeError>().is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_query_relative_time_binds_timestamp() -> Result<(), TdError> {
+        let list_params = ListParamsBuilder::default()
+            .filter(vec!["modified_on:gt:-7d".to_string()])
+            .build()?;
+        let query_params = ListQueryParams::<TestTimeDto>::try_from(&list_params)?;
+        let query_builder = DaoQueries::default()
+            .list_by::<TestTimeDto, NoListFilter>(&query_params, &(), &())
+            .await?;
+        assert_eq!(
+            query_builder.sql(),
+            "SELECT id, modified_on FROM test_time_table WHERE (modified_on > ?) ORDER BY id ASC LIMIT ?"
+        );
+
+        // the relative expression is bound as the concrete timestamp it resolved to
+        let condition = &query_params.conditions.conditions()[0].conditions()[0];
+        let bound = condition.values()[0].as_display();
+        assert_ne!(bound, "-7d");
+        assert!(AtTime::from_display(&bound).is_ok());
+        Ok(())
     }
 
     #[test]
@@ -690,7 +1223,7 @@ mod tests {
         let res: Result<ListQueryParams<Def>, TdError> = (&list_params).try_into();
         let err = res.err().unwrap();
         let err = err.domain_err::<ListError>();
-        assert!(matches!(err, ListError::UndefinedField(_)));
+        assert!(matches!(err, ListError::UndefinedFilter(_, _)));
         let list_params = ListParamsBuilder::default()
             .len(0usize)
             .filter(vec!["likex:lk:LIKE".to_string()])
@@ -700,7 +1233,7 @@ mod tests {
         let res: Result<ListQueryParams<Def>, TdError> = (&list_params).try_into();
         let err = res.err().unwrap();
         let err = err.domain_err::<ListError>();
-        assert!(matches!(err, ListError::UndefinedLikeFilter(_)));
+        assert!(matches!(err, ListError::UndefinedLikeFilter(_, _)));
         let list_params = ListParamsBuilder::default()
             .len(0usize)
             .order_by("orderx".to_string())
@@ -709,7 +1242,296 @@ mod tests {
         let res: Result<ListQueryParams<Def>, TdError> = (&list_params).try_into();
         let err = res.err().unwrap();
         let err = err.domain_err::<ListError>();
-        assert!(matches!(err, ListError::UndefinedOrderBy(_)));
+        assert!(matches!(err, ListError::UndefinedOrderBy(_, _)));
+    }
+
+    #[td_type::Dao]
+    struct FieldsDao {
+        id: String,
+    }
+
+    #[td_type::Dto]
+    #[dto(list(on = FieldsDao))]
+    #[td_type(builder(try_from = FieldsDao))]
+    #[derive(Eq, PartialEq)]
+    struct FieldsDto {
+        #[dto(list(pagination_by = "+", filter))]
+        id: String,
+        #[dto(list(order_by, filter))]
+        #[td_type(builder(field = "id"))]
+        name: String,
+    }
+
+    #[test]
+    fn test_list_query_multiple_order_by() -> Result<(), TdError> {
+        let list_params = ListParamsBuilder::default()
+            .order_by("name-, id+".to_string())
+            .build()
+            .unwrap();
+        let list_query: ListQueryParams<FieldsDto> = (&list_params).try_into()?;
+        assert_eq!(
+            list_query.order,
+            vec![Order::desc("name"), Order::asc("id")]
+        );
+        // the natural order follows the direction of the first order-by field
+        assert_eq!(list_query.natural_order, Order::desc("id"));
+        assert_eq!(list_query.applied().order_by, "name-,id+");
+
+        // pagination values are given for all order-by fields
+        let cursor = Cursor::encode(&["A".to_string(), "B".to_string()], "B");
+        let list_params = ListParamsBuilder::default()
+            .order_by("name-,id+".to_string())
+            .next(cursor)
+            .build()
+            .unwrap();
+        let list_query: ListQueryParams<FieldsDto> = (&list_params).try_into()?;
+        let Some(Pagination::Next(column_values, _, _)) = list_query.pagination else {
+            panic!("expected next pagination");
+        };
+        let column_values = column_values
+            .iter()
+            .map(|v| v.as_display())
+            .collect::<Vec<_>>();
+        assert_eq!(column_values, vec!["A", "B"]);
+
+        let list_params = ListParamsBuilder::default()
+            .order_by("name-,id+".to_string())
+            .next("A".to_string())
+            .pagination_id("B".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<FieldsDto>, TdError> = (&list_params).try_into();
+        assert!(matches!(
+            res.err().unwrap().domain_err::<ListError>(),
+            ListError::InvalidPaginationValues(2, 1)
+        ));
+
+        let list_params = ListParamsBuilder::default()
+            .order_by("name-,name+".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<FieldsDto>, TdError> = (&list_params).try_into();
+        assert!(matches!(
+            res.err().unwrap().domain_err::<ListError>(),
+            ListError::DuplicateOrderBy(field) if field == "name"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_query_undefined_filter_field() {
+        let list_params = ListParamsBuilder::default()
+            .filter(vec!["bogus:eq:value".to_string()])
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<FieldsDto>, TdError> = (&list_params).try_into();
+        let err = res.err().unwrap();
+        assert_eq!(err.api_error(), ApiError::InputError);
+        let err = err.domain_err::<ListError>();
+        assert!(
+            matches!(err, ListError::UndefinedFilter(field, fields) if field == "bogus" && fields == "id, name")
+        );
+        assert_eq!(
+            err.to_string(),
+            "Undefined filter field 'bogus', filter fields are: [id, name]"
+        );
+    }
+
+    #[test]
+    fn test_list_query_undefined_order_field() {
+        let list_params = ListParamsBuilder::default()
+            .order_by("bogus-".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<FieldsDto>, TdError> = (&list_params).try_into();
+        let err = res.err().unwrap();
+        assert_eq!(err.api_error(), ApiError::InputError);
+        let err = err.domain_err::<ListError>();
+        assert!(
+            matches!(err, ListError::UndefinedOrderBy(field, fields) if field == "bogus" && fields == "id, name")
+        );
+        assert_eq!(
+            err.to_string(),
+            "Undefined order by field 'bogus', order by fields are: [id, name]"
+        );
+    }
+
+    #[test]
+    fn test_list_query_select_fields() {
+        let list_params = ListParamsBuilder::default()
+            .fields("a".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<TestDto>, TdError> = (&list_params).try_into();
+        assert!(res.is_ok());
+
+        let list_params = ListParamsBuilder::default()
+            .fields("a,bogus".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<TestDto>, TdError> = (&list_params).try_into();
+        let err = res.err().unwrap();
+        let err = err.domain_err::<ListError>();
+        assert!(matches!(err, ListError::UndefinedSelectField(field) if field == "bogus"));
+
+        // empty selects all fields
+        let list_params = ListParamsBuilder::default()
+            .fields(" , ".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(list_params.selected_fields(), None);
+        let res: Result<ListQueryParams<TestDto>, TdError> = (&list_params).try_into();
+        assert!(res.is_ok());
+    }
+
+    #[td_type::Dao]
+    struct TestRenamedDao {
+        a_field: String,
+        b_field: String,
+    }
+
+    #[td_type::Dto]
+    #[dto(list(on = TestRenamedDao))]
+    #[td_type(builder(try_from = TestRenamedDao))]
+    #[serde(rename_all = "camelCase")]
+    struct TestRenamedDto {
+        #[dto(list(pagination_by = "+"))]
+        a_field: String,
+        #[serde(rename = "other")]
+        b_field: String,
+    }
+
+    #[test]
+    fn test_list_query_select_serialized_fields() {
+        assert_eq!(TestRenamedDto::select_fields(), &["aField", "other"]);
+
+        let list_params = ListParamsBuilder::default()
+            .fields("aField,b_field".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<TestRenamedDto>, TdError> = (&list_params).try_into();
+        let err = res.err().unwrap();
+        let err = err.domain_err::<ListError>();
+        assert!(matches!(err, ListError::UndefinedSelectField(field) if field == "b_field"));
+    }
+
+    #[test]
+    fn test_cursor_round_trip() -> Result<(), TdError> {
+        let cursor = Cursor::encode(
+            &["a value/with?symbols".to_string(), "B".to_string()],
+            "00000000000000000000000004",
+        );
+        assert!(
+            cursor
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+        let (order_values, id) = Cursor::decode(&cursor)?;
+        assert_eq!(order_values, vec!["a value/with?symbols", "B"]);
+        assert_eq!(id, "00000000000000000000000004");
+        Ok(())
+    }
+
+    #[test]
+    fn test_cursor_single_value() -> Result<(), TdError> {
+        // cursors issued before ordering by multiple columns have a single order-by value
+        let mut token = serde_json::to_vec(&("A", "ID")).unwrap();
+        token.extend_from_slice(hmac::sign(Cursor::key(), &token).as_ref());
+        let cursor = URL_SAFE_NO_PAD.encode(token);
+        let (order_values, id) = Cursor::decode(&cursor)?;
+        assert_eq!(order_values, vec!["A"]);
+        assert_eq!(id, "ID");
+        Ok(())
+    }
+
+    #[test]
+    fn test_cursor_invalid() {
+        let cursor = Cursor::encode(&["value".to_string()], "id");
+
+        // malformed
+        for invalid in ["", "not a cursor", &cursor[..cursor.len() - 2]] {
+            let err = Cursor::decode(invalid).unwrap_err();
+            assert!(matches!(err, ListError::InvalidCursor(_)));
+        }
+
+        // tampered, a valid payload with the signature of another one
+        let mut token = URL_SAFE_NO_PAD.decode(&cursor).unwrap();
+        let len = token.len();
+        token[len - Cursor::SIGNATURE_LEN - 1] ^= 1;
+        let tampered = URL_SAFE_NO_PAD.encode(token);
+        let err = Cursor::decode(&tampered).unwrap_err();
+        assert!(matches!(err, ListError::InvalidCursor(_)));
+    }
+
+    #[test]
+    fn test_cursor_other_secret() {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, b"secret");
+        let other_key = hmac::Key::new(hmac::HMAC_SHA256, b"other secret");
+        let cursor = Cursor::encode_with(&key, &["value".to_string()], "id");
+        assert!(Cursor::decode_with(&key, &cursor).is_ok());
+
+        // a well formed cursor, signed with another secret
+        let err = Cursor::decode_with(&other_key, &cursor).unwrap_err();
+        assert!(matches!(err, ListError::InvalidCursor(_)));
+    }
+
+    #[test]
+    fn test_pagination_id_without_previous_or_next() {
+        let list_params = ListParamsBuilder::default()
+            .pagination_id("ID".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<TestDto>, TdError> = (&list_params).try_into();
+        let err = res.err().unwrap();
+        let err = err.domain_err::<ListError>();
+        assert!(matches!(err, ListError::MissingPaginationParams));
+    }
+
+    #[test]
+    fn test_list_query_cursor() -> Result<(), TdError> {
+        let cursor = Cursor::encode(&["A".to_string()], "ID");
+
+        let list_params = ListParamsBuilder::default()
+            .next(cursor.clone())
+            .build()
+            .unwrap();
+        let list_query: ListQueryParams<TestDto> = (&list_params).try_into()?;
+        let Some(Pagination::Next(column_values, pagination_id, _)) = list_query.pagination else {
+            panic!("expected next pagination");
+        };
+        assert_eq!(column_values.len(), 1);
+        assert_eq!(column_values[0].as_display(), "A");
+        assert_eq!(pagination_id.as_display(), "ID");
+
+        let list_params = ListParamsBuilder::default()
+            .previous(cursor)
+            .build()
+            .unwrap();
+        let list_query: ListQueryParams<TestDto> = (&list_params).try_into()?;
+        assert!(matches!(
+            list_query.pagination,
+            Some(Pagination::Previous(..))
+        ));
+
+        // legacy fields
+        let list_params = ListParamsBuilder::default()
+            .next("A".to_string())
+            .pagination_id("ID".to_string())
+            .build()
+            .unwrap();
+        let list_query: ListQueryParams<TestDto> = (&list_params).try_into()?;
+        assert!(matches!(list_query.pagination, Some(Pagination::Next(..))));
+
+        let list_params = ListParamsBuilder::default()
+            .next("A".to_string())
+            .build()
+            .unwrap();
+        let res: Result<ListQueryParams<TestDto>, TdError> = (&list_params).try_into();
+        assert!(matches!(
+            res.err().unwrap().domain_err::<ListError>(),
+            ListError::InvalidCursor(_)
+        ));
+        Ok(())
     }
 
     #[test]
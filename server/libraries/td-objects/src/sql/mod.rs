@@ -6,10 +6,12 @@ pub mod cte;
 pub mod list;
 pub mod recursive;
 
+use crate::dxo::crudl::handle_sql_err;
 use crate::sql::cte::{LATEST_VERSIONS_CTE, ranked_versions_at, select_ranked_versions_at};
 use crate::sql::list::{ListQueryParams, Order, Pagination};
 use crate::types::{AsDynSqlEntities, DataAccessObject, ListQuery, SqlEntity, States, Versioned};
 use async_trait::async_trait;
+use sqlx::{Execute, Row};
 use std::ops::Deref;
 use td_error::TdError;
 use tracing::trace;
@@ -61,7 +63,7 @@ where
         dao: &'a D,
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError> {
         let table = D::sql_table();
-        let fields = D::fields();
+        let fields = D::writable_fields();
         let sql = format!("INSERT INTO {} ({}) ", table, fields.join(", "));
 
         let query_builder = dao.values_query_builder(sql, fields);
@@ -119,6 +121,14 @@ pub trait SelectBy<'a, E> {
         &self,
         where_: &'a E,
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError>;
+
+    /// Same as [`SelectBy::select_by`], but selecting from `table` (a view or a table with the
+    /// same columns as the DAO) instead of the DAO table.
+    fn select_by_on<D: DataAccessObject>(
+        &self,
+        table: &str,
+        where_: &'a E,
+    ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError>;
 }
 
 impl<'a, Q, E> SelectBy<'a, E> for Q
@@ -130,7 +140,14 @@ where
         &self,
         where_: &'a E,
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError> {
-        let table = D::sql_table();
+        self.select_by_on::<D>(D::sql_table(), where_)
+    }
+
+    fn select_by_on<D: DataAccessObject>(
+        &self,
+        table: &str,
+        where_: &'a E,
+    ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError> {
         let fields = D::fields();
         let sql = format!("SELECT {} FROM {}", fields.join(", "), table);
         let mut query_builder = sqlx::QueryBuilder::new(sql);
@@ -147,6 +164,14 @@ pub trait FindBy<'a, E> {
         &self,
         where_: &'a [E],
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError>;
+
+    /// Same as [`FindBy::find_by`], but finding in `table` (a view or a table with the same
+    /// columns as the DAO) instead of the DAO table.
+    fn find_by_on<D: DataAccessObject>(
+        &self,
+        table: &str,
+        where_: &'a [E],
+    ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError>;
 }
 
 impl<'a, Q, E> FindBy<'a, E> for Q
@@ -158,7 +183,14 @@ where
         &self,
         where_: &'a [E],
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError> {
-        let table = D::sql_table();
+        self.find_by_on::<D>(D::sql_table(), where_)
+    }
+
+    fn find_by_on<D: DataAccessObject>(
+        &self,
+        table: &str,
+        where_: &'a [E],
+    ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError> {
         let fields = D::fields();
         let sql = format!("SELECT {} FROM {}", fields.join(", "), table);
         let mut query_builder = sqlx::QueryBuilder::new(sql);
@@ -193,6 +225,25 @@ impl ListFilterGenerator for NoListFilter {
     }
 }
 
+/// Combines two [`ListFilterGenerator`]s, AND-ing their where clauses. Nest it to combine more.
+#[derive(Debug, Clone)]
+pub struct AndFilter<L, R>(pub L, pub R);
+
+impl<L, R> ListFilterGenerator for AndFilter<L, R>
+where
+    L: ListFilterGenerator,
+    R: ListFilterGenerator,
+{
+    fn where_clause<'a, D: DataAccessObject>(
+        &'a self,
+        with_where: bool,
+        query_builder: &mut sqlx::QueryBuilder<'a, sqlx::Sqlite>,
+    ) -> Result<bool, TdError> {
+        let with_where = self.0.where_clause::<D>(with_where, query_builder)?;
+        self.1.where_clause::<D>(with_where, query_builder)
+    }
+}
+
 #[async_trait]
 pub trait ListBy<'a, E> {
     async fn list_by<T, F>(
@@ -358,6 +409,9 @@ where
     }
 }
 
+/// Column holding the unique id of the entities.
+pub(crate) const UNIQUE_ID_FIELD: &str = "id";
+
 fn query_params_where<'a, T>(
     with_where: bool,
     query_params: &'a ListQueryParams<T>,
@@ -418,55 +472,68 @@ where
             with_where = true;
         }
 
-        query_builder.push("(");
-
-        let pagination_field = query_params
-            .order
-            .as_ref()
-            .unwrap_or(&query_params.natural_order);
-        let range_operator = match (pagination_field, pagination) {
-            (Order::Asc(_), Pagination::Previous(_, _)) => "<",
-            (Order::Asc(_), Pagination::Next(_, _)) => ">",
-            (Order::Desc(_), Pagination::Previous(_, _)) => ">",
-            (Order::Desc(_), Pagination::Next(_, _)) => "<",
+        let range_operator = |order: &Order| match (order, pagination) {
+            (Order::Asc(_), Pagination::Previous(..)) => "<",
+            (Order::Asc(_), Pagination::Next(..)) => ">",
+            (Order::Desc(_), Pagination::Previous(..)) => ">",
+            (Order::Desc(_), Pagination::Next(..)) => "<",
         };
 
-        if matches!(pagination, Pagination::Previous(_, _)) {
-            natural_order = natural_order.invert();
-            order = order.map(|o| o.invert());
-        }
-
-        // field OP value
-        query_builder.push(format!(
-            "{} {} ",
-            T::map_dao_field(pagination_field.field()),
-            range_operator
+        // Keyset pagination over the ordered by columns, with the natural order and, if it breaks
+        // the ties of the order, the unique id as tiebreakers:
+        // (k1 OP v1 OR (k1 = v1 AND k2 OP v2) OR ... OR (k1 = v1 AND ... AND id OP last_id))
+        let natural_operator = range_operator(&natural_order);
+        let mut keys: Vec<(String, &str, &dyn SqlEntity)> = query_params
+            .ordered_by()
+            .iter()
+            .zip(pagination.column_values())
+            .map(|(field, value)| {
+                (
+                    T::map_dao_field(field.field()),
+                    range_operator(field),
+                    value.as_ref(),
+                )
+            })
+            .collect();
+        keys.push((
+            T::map_dao_field(natural_order.field()),
+            natural_operator,
+            pagination.pagination_id(),
         ));
-        pagination.column_value().push_bind(query_builder);
+        if let Some(tiebreaker_id) = pagination.tiebreaker_id() {
+            keys.push((UNIQUE_ID_FIELD.to_string(), natural_operator, tiebreaker_id));
+        }
 
-        query_builder.push(" OR ");
         query_builder.push("(");
-
-        // field = value
-        query_builder.push(format!("{} = ", T::map_dao_field(pagination_field.field())));
-        pagination.column_value().push_bind(query_builder);
-
-        // natural_field OP value
-        query_builder.push(format!(
-            " AND {} {} ",
-            T::map_dao_field(natural_order.field()),
-            range_operator
-        ));
-        pagination.pagination_id().push_bind(query_builder);
+        for (i, (field, operator, value)) in keys.iter().enumerate() {
+            if i > 0 {
+                query_builder.push(" OR (");
+            }
+            // key = value, for the previous keys
+            for (previous_field, _, previous_value) in &keys[..i] {
+                query_builder.push(format!("{previous_field} = "));
+                previous_value.push_bind(query_builder);
+                query_builder.push(" AND ");
+            }
+            // key OP value
+            query_builder.push(format!("{field} {operator} "));
+            value.push_bind(query_builder);
+            if i > 0 {
+                query_builder.push(")");
+            }
+        }
         query_builder.push(")");
 
-        query_builder.push(")");
+        if matches!(pagination, Pagination::Previous(..)) {
+            natural_order = natural_order.invert();
+            order = order.iter().map(Order::invert).collect();
+        }
     }
 
     query_builder.push(" ORDER BY ");
     let mut separated = query_builder.separated(", ");
 
-    if let Some(order) = order {
+    for order in &order {
         separated.push(format!(
             "{} {}",
             T::map_dao_field(order.field()),
@@ -480,6 +547,11 @@ where
         natural_order.direction()
     ));
 
+    // The unique id is the ultimate tiebreaker, to guarantee a total order.
+    if query_params.id_tiebreaker() {
+        separated.push(format!("{} {}", UNIQUE_ID_FIELD, natural_order.direction()));
+    }
+
     query_builder
         .push(" LIMIT ")
         .push_bind(query_params.len as i64);
@@ -513,7 +585,7 @@ where
         where_: &'a E,
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError> {
         let table = U::sql_table();
-        let fields = U::fields();
+        let fields = U::writable_fields();
         let sql = format!("UPDATE {} SET ", table);
         let mut query_builder = dao.tuples_query_builder(sql, fields);
         gen_where_clause::<D, E>(&mut query_builder, std::slice::from_ref(where_))?;
@@ -527,7 +599,7 @@ where
         where_: &'a [E],
     ) -> Result<sqlx::QueryBuilder<'a, sqlx::Sqlite>, TdError> {
         let table = D::sql_table();
-        let fields = U::fields();
+        let fields = U::writable_fields();
         let sql = format!("UPDATE {} SET ", table);
         let mut query_builder = dao.tuples_query_builder(sql, fields);
         if where_.is_empty() {
@@ -566,11 +638,97 @@ where
     }
 }
 
+/// SQL of a query builder with its `?` placeholders numbered (`?1`, `?2`, ...) as the values
+/// bound to them, which are not included, so it can be logged or attached to errors without
+/// disclosing them. Numbered placeholders are valid SQLite, the SQL can be run as is.
+pub fn explain(query_builder: &sqlx::QueryBuilder<'_, sqlx::Sqlite>) -> String {
+    let sql = query_builder.sql();
+    let mut explained = String::with_capacity(sql.len());
+    let mut placeholder = 0;
+    let mut in_literal = false;
+    for c in sql.chars() {
+        explained.push(c);
+        match c {
+            '\'' => in_literal = !in_literal,
+            '?' if !in_literal => {
+                placeholder += 1;
+                explained.push_str(&placeholder.to_string());
+            }
+            _ => {}
+        }
+    }
+    explained
+}
+
+/// Debugging aid rendering the SQL of a built query with its bound values annotated, each `?`
+/// placeholder followed by the SQL literal of its value, as quoted by SQLite. The values are the
+/// ones bound to the query builder, so they always match its placeholders. It consumes the query
+/// builder bindings, so the query builder must be reset before being reused.
+pub async fn explain_with_values<'e, X>(
+    executor: X,
+    query_builder: &mut sqlx::QueryBuilder<'_, sqlx::Sqlite>,
+) -> Result<String, TdError>
+where
+    X: sqlx::SqliteExecutor<'e>,
+{
+    let mut query = query_builder.build();
+    let sql = query.sql().to_string();
+    let arguments = query
+        .take_arguments()
+        .map_err(|e| handle_sql_err(sqlx::Error::Encode(e)))?
+        .unwrap_or_default();
+    let len = sqlx::Arguments::len(&arguments);
+    if len == 0 {
+        return Ok(sql);
+    }
+
+    let quote_sql = format!("SELECT {}", vec!["quote(?)"; len].join(", "));
+    let row = sqlx::query_with(&quote_sql, arguments)
+        .fetch_one(executor)
+        .await
+        .map_err(handle_sql_err)?;
+    let mut values = (0..len).map(|i| row.try_get::<String, _>(i));
+
+    let mut explained = String::with_capacity(sql.len());
+    for c in sql.chars() {
+        explained.push(c);
+        if c == '?'
+            && let Some(value) = values.next()
+        {
+            explained.push_str(&format!(" /* {} */", value.map_err(handle_sql_err)?));
+        }
+    }
+    Ok(explained)
+}
+
+/// Debugging aid returning SQLite's query plan of a built query, one `detail` per plan row
+/// (e.g. `SEARCH functions USING INDEX ...` or `SCAN functions`). It consumes the query
+/// builder bindings, so the query builder must be reset before being reused.
+pub async fn explain_query_plan<'e, X>(
+    executor: X,
+    query_builder: &mut sqlx::QueryBuilder<'_, sqlx::Sqlite>,
+) -> Result<Vec<String>, TdError>
+where
+    X: sqlx::SqliteExecutor<'e>,
+{
+    let mut query = query_builder.build();
+    let sql = format!("EXPLAIN QUERY PLAN {}", query.sql());
+    let arguments = query
+        .take_arguments()
+        .map_err(|e| handle_sql_err(sqlx::Error::Encode(e)))?
+        .unwrap_or_default();
+    let plan: Vec<(i64, i64, i64, String)> = sqlx::query_as_with(&sql, arguments)
+        .fetch_all(executor)
+        .await
+        .map_err(handle_sql_err)?;
+    Ok(plan.into_iter().map(|(_, _, _, detail)| detail).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dxo::crudl::ListParams;
-    use sqlx::Execute;
+    use crate::dxo::crudl::{AuditContext, ListParams, RequestContext};
+    use crate::types::basic::{AccessTokenId, AtTime, RoleId, UserId};
     use td_database::sql::DbPool;
     use td_error::TdError;
     use td_type::Dao;
@@ -643,6 +801,100 @@ mod tests {
             Ok(())
         }
 
+        #[td_test::test(sqlx(fixture = "test_queries"))]
+        #[tokio::test]
+        async fn test_dao_insert_read_only(db: DbPool) -> Result<(), TdError> {
+            #[Dao]
+            #[dao(sql_table = "test_read_only_table")]
+            struct ReadOnlyTestDao {
+                id: TestId,
+                name: TestName,
+                #[dao(read_only)]
+                modified_on: TestModifiedOn,
+            }
+
+            let dao = ReadOnlyTestDao::builder()
+                .id(TestId::try_from("")?)
+                .try_name("bowser")?
+                .try_modified_on(123)?
+                .build()?;
+
+            let mut query_builder = DaoQueries::default().insert(&dao)?;
+            let query = query_builder.build();
+
+            let query_str = query.sql();
+            assert_eq!(
+                query_str,
+                "INSERT INTO test_read_only_table (id, name) VALUES (?, ?)"
+            );
+
+            let result = query.execute(&db).await.unwrap();
+            assert_eq!(result.rows_affected(), 1);
+
+            // Read-only fields are still populated on select, with the DB value.
+            let mut query_builder =
+                DaoQueries::default().select_by::<ReadOnlyTestDao>(&dao.name)?;
+            let db_data: Vec<ReadOnlyTestDao> =
+                query_builder.build_query_as().fetch_all(&db).await.unwrap();
+            assert_eq!(db_data.len(), 1);
+            assert_eq!(db_data[0].name, dao.name);
+            assert_eq!(db_data[0].modified_on, TestModifiedOn::try_from(42)?);
+            Ok(())
+        }
+
+        #[td_test::test(sqlx(fixture = "test_queries"))]
+        #[tokio::test]
+        async fn test_dao_insert_audited(db: DbPool) -> Result<(), TdError> {
+            #[Dao]
+            #[dao(
+                sql_table = "test_audit_table",
+                audit(created_on = "created_on", created_by = "created_by_id")
+            )]
+            struct AuditTestDao {
+                id: TestId,
+                name: TestName,
+                #[builder(default)]
+                created_on: Option<AtTime>,
+                created_by_id: UserId,
+            }
+
+            let request_context =
+                RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+            let audit = AuditContext::from(&request_context);
+
+            // Audit fields not explicitly set are populated from the audit context.
+            let dao = AuditTestDao::builder()
+                .id(TestId::try_from("")?)
+                .try_name("bowser")?
+                .audit(&audit)
+                .build()?;
+            assert_eq!(dao.created_on, Some(request_context.time.clone()));
+            assert_eq!(dao.created_by_id, UserId::admin());
+
+            // Audit fields explicitly set are kept.
+            let created_by_id = UserId::default();
+            let explicit = AuditTestDao::builder()
+                .id(TestId::try_from("")?)
+                .try_name("peach")?
+                .created_by_id(created_by_id)
+                .audit(&audit)
+                .build()?;
+            assert_eq!(explicit.created_on, Some(request_context.time.clone()));
+            assert_eq!(explicit.created_by_id, created_by_id);
+
+            let mut query_builder = DaoQueries::default().insert(&dao)?;
+            let result = query_builder.build().execute(&db).await.unwrap();
+            assert_eq!(result.rows_affected(), 1);
+
+            let mut query_builder = DaoQueries::default().select_by::<AuditTestDao>(&dao.name)?;
+            let db_data: Vec<AuditTestDao> =
+                query_builder.build_query_as().fetch_all(&db).await.unwrap();
+            assert_eq!(db_data.len(), 1);
+            assert_eq!(db_data[0].created_on, dao.created_on);
+            assert_eq!(db_data[0].created_by_id, dao.created_by_id);
+            Ok(())
+        }
+
         #[td_test::test(sqlx(fixture = "test_queries"))]
         #[tokio::test]
         async fn test_dao_select_by(db: DbPool) -> Result<(), TdError> {
@@ -709,6 +961,121 @@ mod tests {
             Ok(())
         }
 
+        #[td_test::test(sqlx(fixture = "test_queries"))]
+        #[tokio::test]
+        async fn test_dao_select_by_on_view(db: DbPool) -> Result<(), TdError> {
+            sqlx::query(
+                "CREATE VIEW test_table_view AS SELECT * FROM test_table WHERE modified_on > 5000",
+            )
+            .execute(&db)
+            .await
+            .unwrap();
+
+            let mut query_builder =
+                DaoQueries::default().select_by_on::<TestDao>("test_table_view", &())?;
+            let query = query_builder.build_query_as();
+
+            let query_str = query.sql();
+            assert_eq!(
+                query_str,
+                "SELECT id, name, modified_on FROM test_table_view ORDER BY 1 DESC"
+            );
+
+            let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0], FIXTURE_DAOS[1]);
+
+            let by = [TestName::try_from("mario")?];
+            let mut query_builder =
+                DaoQueries::default().find_by_on::<TestDao>("test_table_view", &by)?;
+            let query = query_builder.build_query_as();
+            assert_eq!(
+                query.sql(),
+                "SELECT id, name, modified_on FROM test_table_view WHERE (name = ?)"
+            );
+            let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+            assert!(result.is_empty());
+            Ok(())
+        }
+
+        #[test]
+        fn test_dao_explain_select_by_where() -> Result<(), TdError> {
+            let by = (
+                TestId::try_from("00000000000000000000000004")?,
+                TestName::try_from("mario")?,
+            );
+            let query_builder = DaoQueries::default().select_by::<TestDao>(&by)?;
+
+            let explained = explain(&query_builder);
+            assert!(explained.contains("FROM test_table"));
+            assert!(explained.contains("WHERE (id = ?1 AND name = ?2)"));
+            assert!(!explained.contains("mario"));
+            Ok(())
+        }
+
+        #[td_test::test(sqlx)]
+        #[tokio::test]
+        async fn test_dao_explain_with_values_select_by_where(db: DbPool) -> Result<(), TdError> {
+            let by = TestName::try_from("mario")?;
+            let mut query_builder = DaoQueries::default().select_by::<TestDao>(&by)?;
+
+            let explained = explain_with_values(&db, &mut query_builder).await?;
+            assert!(explained.contains("FROM test_table"));
+            assert!(explained.contains("WHERE (name = ? /* 'mario' */)"));
+            Ok(())
+        }
+
+        #[test]
+        fn test_and_filter() -> Result<(), TdError> {
+            struct EqFilter(&'static str, TestName);
+
+            impl ListFilterGenerator for EqFilter {
+                fn where_clause<'a, D: DataAccessObject>(
+                    &'a self,
+                    with_where: bool,
+                    query_builder: &mut sqlx::QueryBuilder<'a, sqlx::Sqlite>,
+                ) -> Result<bool, TdError> {
+                    query_builder.push(if with_where { " AND " } else { " WHERE " });
+                    query_builder.push(format!("{} = ", self.0));
+                    query_builder.push_bind(&self.1);
+                    Ok(true)
+                }
+            }
+
+            let filter = AndFilter(
+                EqFilter("name", TestName::try_from("mario")?),
+                EqFilter("id", TestName::try_from("00000000000000000000000004")?),
+            );
+
+            let mut query_builder = sqlx::QueryBuilder::new("SELECT * FROM test_table");
+            let with_where = filter.where_clause::<TestDao>(false, &mut query_builder)?;
+            assert!(with_where);
+            assert_eq!(
+                query_builder.sql(),
+                "SELECT * FROM test_table WHERE name = ? AND id = ?"
+            );
+
+            // Appends to an existing where clause
+            let mut query_builder = sqlx::QueryBuilder::new("SELECT * FROM test_table WHERE 1 = 1");
+            filter.where_clause::<TestDao>(true, &mut query_builder)?;
+            assert_eq!(
+                query_builder.sql(),
+                "SELECT * FROM test_table WHERE 1 = 1 AND name = ? AND id = ?"
+            );
+            Ok(())
+        }
+
+        #[td_test::test(sqlx(fixture = "test_templated_queries", vars(name = "peach")))]
+        #[tokio::test]
+        async fn test_dao_select_by_templated_fixture(db: DbPool) -> Result<(), TdError> {
+            let by = TestName::try_from("peach")?;
+            let mut query_builder = DaoQueries::default().select_by::<TestDao>(&by)?;
+            let result: Vec<TestDao> = query_builder.build_query_as().fetch_all(&db).await.unwrap();
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].name, by);
+            Ok(())
+        }
+
         #[td_test::test(sqlx(fixture = "test_queries"))]
         #[tokio::test]
         async fn test_dao_select_by_where_tuple(db: DbPool) -> Result<(), TdError> {
@@ -1082,9 +1449,9 @@ mod tests {
             let query_str = query.sql();
             assert!(
                 query_str
-                    == r#"SELECT id, name, modified_on FROM test_table WHERE (name = ?) AND (modified_on > ?) AND (name LIKE ? ESCAPE '\' ) AND (name < ? OR (name = ? AND modified_on < ?)) ORDER BY name DESC, modified_on DESC LIMIT ?"#
+                    == r#"SELECT id, name, modified_on FROM test_table WHERE (name = ?) AND (modified_on > ?) AND (name LIKE ? ESCAPE '\' ) AND (name < ? OR (name = ? AND modified_on < ?)) ORDER BY name DESC, modified_on DESC, id DESC LIMIT ?"#
                     || query_str
-                        == r#"SELECT id, name, modified_on FROM test_table WHERE (name = ?) AND (name LIKE ? ESCAPE '\' ) AND (modified_on > ?) AND (name < ? OR (name = ? AND modified_on < ?)) ORDER BY name DESC, modified_on DESC LIMIT ?"#
+                        == r#"SELECT id, name, modified_on FROM test_table WHERE (name = ?) AND (name LIKE ? ESCAPE '\' ) AND (modified_on > ?) AND (name < ? OR (name = ? AND modified_on < ?)) ORDER BY name DESC, modified_on DESC, id DESC LIMIT ?"#
             );
 
             let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
@@ -1148,7 +1515,7 @@ mod tests {
             let query_str = query.sql();
             assert_eq!(
                 query_str,
-                "SELECT id, name, modified_on FROM test_table ORDER BY modified_on DESC LIMIT ?"
+                "SELECT id, name, modified_on FROM test_table ORDER BY modified_on DESC, id DESC LIMIT ?"
             );
 
             let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
@@ -1185,7 +1552,7 @@ mod tests {
             let query_str = query.sql();
             assert_eq!(
                 query_str,
-                "SELECT id, name, modified_on FROM test_table ORDER BY name ASC, modified_on ASC LIMIT ?"
+                "SELECT id, name, modified_on FROM test_table ORDER BY name ASC, modified_on ASC, id ASC LIMIT ?"
             );
 
             let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
@@ -1223,7 +1590,7 @@ mod tests {
             let query_str = query.sql();
             assert_eq!(
                 query_str,
-                "SELECT id, name, modified_on FROM test_table ORDER BY name ASC, modified_on ASC LIMIT ?"
+                "SELECT id, name, modified_on FROM test_table ORDER BY name ASC, modified_on ASC, id ASC LIMIT ?"
             );
 
             let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
@@ -1233,6 +1600,71 @@ mod tests {
             Ok(())
         }
 
+        #[td_test::test(sqlx(fixture = "test_list_queries"))]
+        #[tokio::test]
+        async fn test_dao_list_order_by_multiple(db: DbPool) -> Result<(), TdError> {
+            #[Dto]
+            #[dto(list(on = TestDao))]
+            #[td_type(builder(try_from = TestDao))]
+            struct TestDto {
+                #[dto(list(order_by))]
+                id: TestId,
+                #[dto(list(order_by))]
+                name: TestName,
+                #[dto(list(pagination_by = "+"))]
+                modified_on: TestModifiedOn,
+            }
+
+            let list_params = ListParamsBuilder::default()
+                .order_by("name+,id-".to_string())
+                .build()?;
+            let list_query_params = ListQueryParams::<TestDto>::try_from(&list_params)?;
+            let mut query_builder = DaoQueries::default()
+                .list_by::<TestDto, NoListFilter>(&list_query_params, &(), &())
+                .await?;
+            let query = query_builder.build_query_as();
+
+            let query_str = query.sql();
+            assert_eq!(
+                query_str,
+                "SELECT id, name, modified_on FROM test_table ORDER BY name ASC, id DESC, modified_on ASC LIMIT ?"
+            );
+
+            let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+            let mut expected = FIXTURE_DAOS.clone();
+            expected.sort_by(|a, b| {
+                a.name
+                    .cmp(&b.name)
+                    .then(b.id.to_string().cmp(&a.id.to_string()))
+            });
+            assert_eq!(result, expected);
+
+            // next page, after the first row
+            let cursor = crate::sql::list::Cursor::encode(
+                &["A".to_string(), "0000000000000000000000000C".to_string()],
+                "3",
+            );
+            let list_params = ListParamsBuilder::default()
+                .order_by("name+,id-".to_string())
+                .next(cursor)
+                .build()?;
+            let list_query_params = ListQueryParams::<TestDto>::try_from(&list_params)?;
+            let mut query_builder = DaoQueries::default()
+                .list_by::<TestDto, NoListFilter>(&list_query_params, &(), &())
+                .await?;
+            let query = query_builder.build_query_as();
+
+            let query_str = query.sql();
+            assert_eq!(
+                query_str,
+                "SELECT id, name, modified_on FROM test_table WHERE (name > ? OR (name = ? AND id < ?) OR (name = ? AND id = ? AND modified_on > ?)) ORDER BY name ASC, id DESC, modified_on ASC LIMIT ?"
+            );
+
+            let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+            assert_eq!(result, expected[1..]);
+            Ok(())
+        }
+
         #[td_test::test(sqlx(fixture = "test_list_queries"))]
         #[tokio::test]
         async fn test_dao_list_filter(db: DbPool) -> Result<(), TdError> {
@@ -1269,6 +1701,52 @@ mod tests {
             Ok(())
         }
 
+        #[td_test::test(sqlx(fixture = "test_list_queries"))]
+        #[tokio::test]
+        async fn test_explain_query_plan_list_filter(db: DbPool) -> Result<(), TdError> {
+            #[Dto]
+            #[dto(list(on = TestDao))]
+            #[td_type(builder(try_from = TestDao))]
+            struct TestDto {
+                id: TestId,
+                #[dto(list(filter))]
+                name: TestName,
+                #[dto(list(pagination_by = "+"))]
+                modified_on: TestModifiedOn,
+            }
+
+            let list_params = ListParamsBuilder::default()
+                .filter(vec!["name:eq:A".to_string()])
+                .build()?;
+            let list_query_params = ListQueryParams::<TestDto>::try_from(&list_params)?;
+            let plan = async || -> Result<Vec<String>, TdError> {
+                let mut query_builder = DaoQueries::default()
+                    .list_by::<TestDto, NoListFilter>(&list_query_params, &(), &())
+                    .await?;
+                explain_query_plan(&db, &mut query_builder).await
+            };
+
+            // Without an index on the filtered column, the whole table is scanned.
+            assert!(
+                plan()
+                    .await?
+                    .iter()
+                    .any(|d| d.starts_with("SCAN test_table"))
+            );
+
+            sqlx::query("CREATE INDEX test_table_name ON test_table (name, modified_on)")
+                .execute(&db)
+                .await
+                .unwrap();
+            let plan = plan().await?;
+            assert!(
+                plan.iter()
+                    .any(|d| d.starts_with("SEARCH test_table USING INDEX test_table_name"))
+            );
+            assert!(!plan.iter().any(|d| d.starts_with("SCAN test_table")));
+            Ok(())
+        }
+
         #[td_test::test(sqlx(fixture = "test_list_queries"))]
         #[tokio::test]
         async fn test_dao_list_filter_like(db: DbPool) -> Result<(), TdError> {
@@ -1341,6 +1819,103 @@ mod tests {
             Ok(())
         }
 
+        #[td_test::test(sqlx(fixture = "test_list_queries"))]
+        #[tokio::test]
+        async fn test_dao_list_filter_not_equal(db: DbPool) -> Result<(), TdError> {
+            #[Dto]
+            #[dto(list(on = TestDao))]
+            #[td_type(builder(try_from = TestDao))]
+            struct TestDto {
+                #[dto(list(pagination_by = "+"))]
+                id: TestId,
+                #[dto(list(filter))]
+                name: TestName,
+                modified_on: TestModifiedOn,
+            }
+
+            let list_params = ListParamsBuilder::default()
+                .filter(vec!["name:ne:A".to_string()])
+                .build()?;
+            let list_query_params = ListQueryParams::<TestDto>::try_from(&list_params)?;
+            let mut query_builder = DaoQueries::default()
+                .list_by::<TestDto, NoListFilter>(&list_query_params, &(), &())
+                .await?;
+            let query = query_builder.build_query_as();
+
+            let query_str = query.sql();
+            assert_eq!(
+                query_str,
+                "SELECT id, name, modified_on FROM test_table WHERE (name != ?) ORDER BY id ASC LIMIT ?"
+            );
+
+            let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0], FIXTURE_DAOS[0]);
+            assert_eq!(result[1], FIXTURE_DAOS[3]);
+            Ok(())
+        }
+
+        #[td_test::test(sqlx(fixture = "test_list_queries"))]
+        #[tokio::test]
+        async fn test_dao_list_filter_not_like(db: DbPool) -> Result<(), TdError> {
+            #[Dto]
+            #[dto(list(on = TestDao))]
+            #[td_type(builder(try_from = TestDao))]
+            struct TestDto {
+                #[dto(list(pagination_by = "+", filter))]
+                id: TestId,
+                #[dto(list(filter_like))]
+                name: TestName,
+                modified_on: TestModifiedOn,
+            }
+
+            // OR within the same field, AND across fields
+            let list_params = ListParamsBuilder::default()
+                .filter(vec![
+                    "name:nlk:A".to_string(),
+                    "name:nlk:B".to_string(),
+                    "id:gt:00000000000000000000000000".to_string(),
+                ])
+                .build()?;
+            let list_query_params = ListQueryParams::<TestDto>::try_from(&list_params)?;
+            let mut query_builder = DaoQueries::default()
+                .list_by::<TestDto, NoListFilter>(&list_query_params, &(), &())
+                .await?;
+            let query = query_builder.build_query_as();
+
+            let query_str = query.sql();
+            assert!(
+                query_str
+                    == r#"SELECT id, name, modified_on FROM test_table WHERE (name NOT LIKE ? ESCAPE '\'  OR name NOT LIKE ? ESCAPE '\' ) AND (id > ?) ORDER BY id ASC LIMIT ?"#
+                    || query_str
+                        == r#"SELECT id, name, modified_on FROM test_table WHERE (id > ?) AND (name NOT LIKE ? ESCAPE '\'  OR name NOT LIKE ? ESCAPE '\' ) ORDER BY id ASC LIMIT ?"#
+            );
+
+            let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+            assert_eq!(result, *FIXTURE_DAOS);
+
+            let list_params = ListParamsBuilder::default()
+                .filter(vec!["name:nlk:A".to_string()])
+                .build()?;
+            let list_query_params = ListQueryParams::<TestDto>::try_from(&list_params)?;
+            let mut query_builder = DaoQueries::default()
+                .list_by::<TestDto, NoListFilter>(&list_query_params, &(), &())
+                .await?;
+            let query = query_builder.build_query_as();
+
+            let query_str = query.sql();
+            assert_eq!(
+                query_str,
+                "SELECT id, name, modified_on FROM test_table WHERE (name NOT LIKE ? ESCAPE '\\' ) ORDER BY id ASC LIMIT ?"
+            );
+
+            let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+            assert_eq!(result.len(), 2);
+            assert_eq!(result[0], FIXTURE_DAOS[0]);
+            assert_eq!(result[1], FIXTURE_DAOS[3]);
+            Ok(())
+        }
+
         #[td_test::test(sqlx(fixture = "test_list_queries"))]
         #[tokio::test]
         async fn test_dao_list_filter_between(db: DbPool) -> Result<(), TdError> {
@@ -1377,6 +1952,49 @@ mod tests {
             Ok(())
         }
 
+        #[td_test::test(sqlx(fixture = "test_list_queries"))]
+        #[tokio::test]
+        async fn test_dao_list_id_tiebreaker(db: DbPool) -> Result<(), TdError> {
+            #[Dto]
+            #[dto(list(on = TestDao))]
+            #[td_type(builder(try_from = TestDao))]
+            struct TestDto {
+                id: TestId,
+                #[dto(list(pagination_by = "-"))]
+                name: TestName,
+                modified_on: TestModifiedOn,
+            }
+
+            let list_params = ListParamsBuilder::default().build()?;
+            let list_query_params = ListQueryParams::<TestDto>::try_from(&list_params)?;
+
+            // names are not unique, the id breaks the ties
+            for _ in 0..3 {
+                let mut query_builder = DaoQueries::default()
+                    .list_by::<TestDto, NoListFilter>(&list_query_params, &(), &())
+                    .await?;
+                let query = query_builder.build_query_as();
+
+                let query_str = query.sql();
+                assert_eq!(
+                    query_str,
+                    "SELECT id, name, modified_on FROM test_table ORDER BY name DESC, id DESC LIMIT ?"
+                );
+
+                let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
+                assert_eq!(
+                    result,
+                    vec![
+                        FIXTURE_DAOS[3].clone(),
+                        FIXTURE_DAOS[0].clone(),
+                        FIXTURE_DAOS[2].clone(),
+                        FIXTURE_DAOS[1].clone(),
+                    ]
+                );
+            }
+            Ok(())
+        }
+
         #[td_test::test(sqlx(fixture = "test_list_queries"))]
         #[tokio::test]
         async fn test_dao_list_len(db: DbPool) -> Result<(), TdError> {
@@ -1862,7 +2480,7 @@ mod tests {
             let query_str = query.sql();
             assert_eq!(
                 query_str,
-                "SELECT id, name, modified_on FROM test_table WHERE (name = ?) ORDER BY name DESC, modified_on DESC LIMIT ?"
+                "SELECT id, name, modified_on FROM test_table WHERE (name = ?) ORDER BY name DESC, modified_on DESC, id DESC LIMIT ?"
             );
 
             let result: Vec<TestDao> = query.fetch_all(&db).await.unwrap();
@@ -4,11 +4,12 @@
 
 use axum::body::Body;
 use axum::response::{IntoResponse, Response};
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use futures::Stream;
 use std::borrow::Cow;
 use std::pin::Pin;
-use td_error::TdError;
+use std::task::{Context, Poll, ready};
+use td_error::{TdError, td_error};
 use utoipa::openapi::Schema;
 use utoipa::{PartialSchema, ToSchema};
 
@@ -52,3 +53,140 @@ impl ToSchema for BoxedSyncStream {
         "Data".into()
     }
 }
+
+#[td_error]
+enum StreamError {
+    #[error("Line exceeds the maximum of {0} bytes")]
+    LineTooLong(usize) = 0,
+}
+
+/// Splits a byte stream into lines (without the trailing `\n`), buffering at most
+/// `max_line_bytes` plus a chunk. The inner stream is only polled when no complete line is
+/// buffered. A line longer than `max_line_bytes` yields an error and ends the stream. A final
+/// line without a trailing `\n` is yielded at EOF.
+pub fn line_stream(bytes: BoxedSyncStream, max_line_bytes: usize) -> BoxedSyncStream {
+    BoxedSyncStream::new(LineStream {
+        inner: bytes.into_inner(),
+        buffer: BytesMut::new(),
+        scanned: 0,
+        max_line_bytes,
+        eof: false,
+        failed: false,
+    })
+}
+
+struct LineStream {
+    inner: Pin<Box<dyn Stream<Item = Result<Bytes, TdError>> + Send + Sync + 'static>>,
+    buffer: BytesMut,
+    // Bytes of the buffer already known not to contain a newline.
+    scanned: usize,
+    max_line_bytes: usize,
+    eof: bool,
+    failed: bool,
+}
+
+impl LineStream {
+    fn fail(&mut self, err: TdError) -> Poll<Option<Result<Bytes, TdError>>> {
+        self.failed = true;
+        self.buffer.clear();
+        Poll::Ready(Some(Err(err)))
+    }
+}
+
+impl Stream for LineStream {
+    type Item = Result<Bytes, TdError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.failed {
+                return Poll::Ready(None);
+            }
+
+            if let Some(pos) = this.buffer[this.scanned..].iter().position(|b| *b == b'\n') {
+                let pos = this.scanned + pos;
+                if pos > this.max_line_bytes {
+                    return this.fail(StreamError::LineTooLong(this.max_line_bytes).into());
+                }
+                let mut line = this.buffer.split_to(pos + 1);
+                line.truncate(pos);
+                this.scanned = 0;
+                return Poll::Ready(Some(Ok(line.freeze())));
+            }
+            this.scanned = this.buffer.len();
+
+            if this.buffer.len() > this.max_line_bytes {
+                return this.fail(StreamError::LineTooLong(this.max_line_bytes).into());
+            }
+
+            if this.eof {
+                if this.buffer.is_empty() {
+                    return Poll::Ready(None);
+                }
+                this.scanned = 0;
+                return Poll::Ready(Some(Ok(this.buffer.split().freeze())));
+            }
+
+            match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => this.buffer.extend_from_slice(&chunk),
+                Some(Err(err)) => return this.fail(err),
+                None => this.eof = true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    fn chunks(chunks: &[&'static str]) -> BoxedSyncStream {
+        let chunks: Vec<_> = chunks
+            .iter()
+            .map(|c| Ok::<_, TdError>(Bytes::from_static(c.as_bytes())))
+            .collect();
+        BoxedSyncStream::new(futures::stream::iter(chunks))
+    }
+
+    async fn lines(stream: BoxedSyncStream) -> Vec<Result<String, TdError>> {
+        stream
+            .into_inner()
+            .map(|line| line.map(|line| String::from_utf8(line.to_vec()).unwrap()))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_line_stream_across_chunks() {
+        let stream = line_stream(chunks(&["fir", "st\nsec", "ond\n", "\nthi", "rd"]), 10);
+        let lines: Vec<_> = lines(stream)
+            .await
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(lines, vec!["first", "second", "", "third"]);
+    }
+
+    #[tokio::test]
+    async fn test_line_stream_line_too_long() {
+        let stream = line_stream(chunks(&["short\nvery", "_long_line", "\nnext\n"]), 10);
+        let lines = lines(stream).await;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].as_ref().unwrap(), "short");
+        let err = lines[1].as_ref().unwrap_err();
+        assert!(matches!(
+            err.domain_err::<StreamError>(),
+            StreamError::LineTooLong(10)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_line_stream_never_terminated() {
+        // Buffering is bounded even if a newline never comes.
+        let stream = line_stream(chunks(&["0123456789", "0123456789", "0123456789"]), 15);
+        let lines = lines(stream).await;
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].is_err());
+    }
+}
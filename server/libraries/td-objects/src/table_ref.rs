@@ -137,6 +137,13 @@ pub struct VersionedTableRef<T> {
     pub versions: Versions,
 }
 
+impl<T> VersionedTableRef<T> {
+    /// Returns the collection of the reference, or the given current collection if not set.
+    pub fn collection_or<'a>(&'a self, current: &'a CollectionName) -> &'a CollectionName {
+        self.collection.as_ref().unwrap_or(current)
+    }
+}
+
 impl<T, E> ComposedString for VersionedTableRef<T>
 where
     T: Display + TryFrom<String, Error = E>,
@@ -264,6 +271,16 @@ mod tests {
         assert_eq!(table.to_string(), table);
     }
 
+    #[test]
+    fn test_versioned_table_ref_collection_or() {
+        let current = CollectionName::try_from("current").unwrap();
+        let table = VersionedTableRef::<TableName>::parse("table[HEAD]").unwrap();
+        assert_eq!(table.collection_or(&current), &current);
+        let table = VersionedTableRef::<TableName>::parse("collection/table[HEAD]").unwrap();
+        assert_eq!(table.collection_or(&current).to_string(), "collection");
+        assert_eq!(table.to_string(), "collection/table@HEAD");
+    }
+
     #[test]
     fn test_table_ref_to_string() {
         let table = TableRef::<TableName>::parse("table").unwrap();
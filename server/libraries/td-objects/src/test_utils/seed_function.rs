@@ -10,10 +10,50 @@ use crate::dxo::table::{TableDBBuilder, TableDBWithNames};
 use crate::dxo::trigger::TriggerDBBuilder;
 use crate::sql::{DaoQueries, Insert, SelectBy};
 use crate::types::basic::{
-    AccessTokenId, DataLocation, DependencyPos, DependencyStatus, RoleId, StorageVersion,
-    TableFunctionParamPos, TableId, TableName, TableStatus, TriggerStatus, UserId,
+    AccessTokenId, BundleId, DataLocation, Decorator, DependencyPos, DependencyStatus,
+    FunctionRuntimeValues, RoleId, StorageVersion, TableFunctionParamPos, TableId, TableName,
+    TableNameDto, TableStatus, TriggerStatus, UserId,
 };
+use crate::types::composed::{TableDependencyDto, TableTriggerDto};
 use td_database::sql::DbPool;
+use td_error::TdError;
+
+/// Registration of a publisher function with the given tables, dependencies and triggers. The
+/// triggers are always explicit, no triggers if empty (instead of all its dependencies).
+pub fn function_register(
+    name: &str,
+    tables: &[&str],
+    dependencies: &[&str],
+    triggers: &[&str],
+) -> Result<FunctionRegister, TdError> {
+    FunctionRegister::builder()
+        .try_name(name)?
+        .try_description(format!("{name} description"))?
+        .bundle_id(BundleId::default())
+        .try_snippet(format!("{name} snippet"))?
+        .decorator(Decorator::Publisher)
+        .tables(Some(
+            tables
+                .iter()
+                .map(|t| TableNameDto::try_from(*t))
+                .collect::<Result<_, _>>()?,
+        ))
+        .dependencies(Some(
+            dependencies
+                .iter()
+                .map(|t| TableDependencyDto::try_from(*t))
+                .collect::<Result<_, _>>()?,
+        ))
+        .triggers(Some(
+            triggers
+                .iter()
+                .map(|t| TableTriggerDto::try_from(*t))
+                .collect::<Result<_, _>>()?,
+        ))
+        .runtime_values(FunctionRuntimeValues::default())
+        .reuse_frozen_tables(false)
+        .build()
+}
 
 pub async fn seed_function(
     db: &DbPool,
@@ -175,7 +215,7 @@ mod tests {
     use super::*;
     use crate::sql::SelectBy;
     use crate::test_utils::seed_collection::seed_collection;
-    use crate::types::basic::{BundleId, CollectionName, Decorator, UserId};
+    use crate::types::basic::CollectionName;
     use td_security::ENCODED_ID_SYSTEM;
 
     #[td_test::test(sqlx)]
@@ -0,0 +1,165 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Audit log of create, update and delete requests, and of password changes.
+//!
+//! The requests implement [`AuditedRequest`], recording the requester, the action, the service,
+//! the id of the changed entity and the outcome, with the error code of failures, in the
+//! `audit_log` table.
+
+use crate::dxo::audit_log::AuditLogDB;
+use crate::dxo::auth::PasswordChange;
+use crate::dxo::crudl::{
+    CreateRequest, DeleteRequest, RequestContext, UpdateRequest, handle_sql_err,
+};
+use crate::sql::{DaoQueries, Insert};
+use crate::types::basic::{AtTime, AuditAction, AuditEntity, AuditOutcome, ErrorCode, ServiceName};
+use futures::future::BoxFuture;
+use td_error::TdError;
+use td_tower::audit::{AuditWriter, AuditedRequest};
+use td_tower::extractors::{Connection, FromHandler, IntoMutSqlConnection, SrvCtx};
+
+async fn insert_audit_log(
+    writer: AuditWriter<'_>,
+    context: Option<&RequestContext>,
+    action: AuditAction,
+    service: &str,
+    entity: Option<String>,
+    outcome: Result<(), &TdError>,
+) -> Result<(), TdError> {
+    let (outcome, error_code) = match outcome {
+        Ok(()) => (AuditOutcome::Success, None),
+        Err(err) => (
+            AuditOutcome::Failure,
+            Some(ErrorCode::try_from(err.code())?),
+        ),
+    };
+    let audit_log = AuditLogDB::builder()
+        .request_id(context.and_then(|context| context.request_id.clone()))
+        .user_id(context.map(|context| context.user_id))
+        .role_id(context.map(|context| context.role_id))
+        .action(action)
+        .service(ServiceName::try_from(service)?)
+        .entity(entity.map(AuditEntity::try_from).transpose()?)
+        .outcome(outcome)
+        .error_code(error_code)
+        .audited_on(AtTime::now())
+        .build()?;
+
+    match writer {
+        // in the service transaction, with its queries
+        AuditWriter::Service(handler) => {
+            let Connection(connection) = Connection::from_handler(handler)?;
+            let SrvCtx(queries) = SrvCtx::<DaoQueries>::from_handler(handler)?;
+
+            let mut conn = connection.lock().await;
+            let conn = conn.get_mut_connection()?;
+            queries
+                .insert(&audit_log)?
+                .build()
+                .execute(&mut *conn)
+                .await
+                .map_err(handle_sql_err)?;
+        }
+        // the service handler, with its queries, is gone with the service
+        AuditWriter::Pool(db) => {
+            DaoQueries::default()
+                .insert(&audit_log)?
+                .build()
+                .execute(db)
+                .await
+                .map_err(handle_sql_err)?;
+        }
+    }
+    Ok(())
+}
+
+impl<N, C> AuditedRequest for CreateRequest<N, C>
+where
+    N: Clone + Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+{
+    fn audit<'a>(
+        &'a self,
+        writer: AuditWriter<'a>,
+        service: &'static str,
+        entity: Option<String>,
+        outcome: Result<(), &'a TdError>,
+    ) -> BoxFuture<'a, Result<(), TdError>> {
+        Box::pin(insert_audit_log(
+            writer,
+            Some(&self.context),
+            AuditAction::Create,
+            service,
+            entity,
+            outcome,
+        ))
+    }
+}
+
+impl<N, U> AuditedRequest for UpdateRequest<N, U>
+where
+    N: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+{
+    fn audit<'a>(
+        &'a self,
+        writer: AuditWriter<'a>,
+        service: &'static str,
+        entity: Option<String>,
+        outcome: Result<(), &'a TdError>,
+    ) -> BoxFuture<'a, Result<(), TdError>> {
+        Box::pin(insert_audit_log(
+            writer,
+            Some(&self.context),
+            AuditAction::Update,
+            service,
+            entity,
+            outcome,
+        ))
+    }
+}
+
+impl<N> AuditedRequest for DeleteRequest<N>
+where
+    N: Clone + Send + Sync + 'static,
+{
+    fn audit<'a>(
+        &'a self,
+        writer: AuditWriter<'a>,
+        service: &'static str,
+        entity: Option<String>,
+        outcome: Result<(), &'a TdError>,
+    ) -> BoxFuture<'a, Result<(), TdError>> {
+        Box::pin(insert_audit_log(
+            writer,
+            Some(&self.context),
+            AuditAction::Delete,
+            service,
+            entity,
+            outcome,
+        ))
+    }
+}
+
+/// Password changes are done without a session, there is no requester. The changed entity is
+/// the user changing its password.
+impl AuditedRequest for PasswordChange {
+    fn audit<'a>(
+        &'a self,
+        writer: AuditWriter<'a>,
+        service: &'static str,
+        entity: Option<String>,
+        outcome: Result<(), &'a TdError>,
+    ) -> BoxFuture<'a, Result<(), TdError>> {
+        Box::pin(insert_audit_log(
+            writer,
+            None,
+            AuditAction::Update,
+            service,
+            entity,
+            outcome,
+        ))
+    }
+}
@@ -121,7 +121,9 @@ use crate::dxo::collection::CollectionDB;
 use crate::dxo::crudl::{RequestContext, handle_sql_err};
 use crate::dxo::inter_collection_access::InterCollectionAccess;
 use crate::sql::{DaoQueries, FindBy};
-use crate::types::basic::{CollectionId, RoleId, ToCollectionId, UserId};
+use crate::types::basic::{
+    AccessTokenId, CollectionId, EntityId, PermissionType, RoleId, ToCollectionId, UserId,
+};
 use crate::types::visible_collections::VisibleCollections;
 use async_trait::async_trait;
 use itertools::Itertools;
@@ -147,6 +149,18 @@ pub trait AuthzContextT: Send + Sync {
         role: &RoleId,
     ) -> Result<Option<Arc<Vec<Permission>>>, TdError>;
 
+    /// Return the permissions the given access token is scoped to (API keys can be restricted to
+    /// a subset of the permissions of their role).
+    ///
+    /// If the access token is not scoped it returns [`None`].
+    async fn access_token_permissions(
+        &self,
+        _conn: &mut SqliteConnection,
+        _access_token: &AccessTokenId,
+    ) -> Result<Option<Arc<Vec<Permission>>>, TdError> {
+        Ok(None)
+    }
+
     /// Return the collection permissions for a given role.
     ///
     /// If the role has an [`AuthzEntity::All`] on a permission, any specific [`AuthzEntity::On(<collection>`]
@@ -449,6 +463,30 @@ pub enum Permission {
 }
 
 impl Permission {
+    /// Return the permission for a stored permission type and entity.
+    pub fn try_from_entity(
+        permission_type: &PermissionType,
+        entity_id: &EntityId,
+    ) -> Result<Self, TdError> {
+        let authz_entity = || -> Result<AuthzEntity<CollectionId>, TdError> {
+            if entity_id.is_all_entities() {
+                Ok(AuthzEntity::All)
+            } else {
+                Ok(AuthzEntity::On(CollectionId::try_from(entity_id)?))
+            }
+        };
+
+        let perm = match permission_type {
+            PermissionType::SysAdmin => Permission::SysAdmin,
+            PermissionType::SecAdmin => Permission::SecAdmin,
+            PermissionType::CollectionAdmin => Permission::CollectionAdmin(authz_entity()?),
+            PermissionType::CollectionDev => Permission::CollectionDev(authz_entity()?),
+            PermissionType::CollectionExec => Permission::CollectionExec(authz_entity()?),
+            PermissionType::CollectionRead => Permission::CollectionRead(authz_entity()?),
+        };
+        Ok(perm)
+    }
+
     /// Return if the permission is a collection permission.
     pub fn is_on_collection(&self) -> bool {
         matches!(
@@ -811,6 +849,36 @@ fn augment_with_wildcards(required_permissions: HashSet<Permission>) -> HashSet<
     with_wildcards
 }
 
+/// Return the permissions of a role restricted to the given scope.
+///
+/// A scope permission is kept if the role has it, or has the same permission on all collections.
+/// A scope permission on all collections keeps the role permissions of the same type on specific
+/// collections.
+pub fn scoped_permissions(
+    role_permissions: &[Permission],
+    scope: &[Permission],
+) -> Vec<Permission> {
+    let mut permissions = Vec::new();
+    for scope_perm in scope {
+        for role_perm in role_permissions
+            .iter()
+            .filter(|p| p.is_same_type(scope_perm))
+        {
+            let perm = if role_perm == scope_perm || role_perm.is_on_all_collections() {
+                scope_perm
+            } else if scope_perm.is_on_all_collections() {
+                role_perm
+            } else {
+                continue;
+            };
+            if !permissions.contains(perm) {
+                permissions.push(perm.clone());
+            }
+        }
+    }
+    permissions
+}
+
 /// Service Authorization enforcer.
 pub struct Authz<
     AC: AuthzContextT,
@@ -851,7 +919,10 @@ impl<
     ///
     /// The user role is obtained from the [`RequestContext`] in the service context.
     ///
-    /// The role permissions are from the [`AuthzContextT`] in the service context.
+    /// The role permissions are from the [`AuthzContextT`] in the service context. If the access
+    /// token of the request is scoped, only the role permissions within the scope are considered,
+    /// and requester (user and role) permissions do not apply. A token marked as scoped in the
+    /// [`RequestContext`] without a known scope has no permissions.
     pub async fn check(
         SrvCtx(authz_context): SrvCtx<AC>,
         Connection(conn): Connection,
@@ -892,10 +963,23 @@ impl<
         if required_permissions.is_empty() {
             Ok(())
         } else {
-            if let Some(role_permissions) = authz_context
-                .role_permissions(conn, &request_context.role_id)
+            // A scoped access token whose scope is not known has no permissions.
+            let access_token_permissions = match authz_context
+                .access_token_permissions(conn, &request_context.access_token_id)
                 .await?
             {
+                None if *request_context.scoped => Some(Arc::new(Vec::new())),
+                access_token_permissions => access_token_permissions,
+            };
+            let role_permissions = authz_context
+                .role_permissions(conn, &request_context.role_id)
+                .await?
+                .map(|role_permissions| match &access_token_permissions {
+                    Some(scope) => Arc::new(scoped_permissions(&role_permissions, scope)),
+                    None => role_permissions,
+                });
+
+            if let Some(role_permissions) = role_permissions {
                 for perm in role_permissions.deref() {
                     if required_permissions.contains(perm) {
                         return Ok(());
@@ -921,6 +1005,11 @@ impl<
                 }
             }
 
+            // scoped access tokens only have the permissions of their scope
+            if access_token_permissions.is_some() {
+                Err(AuthzError::Forbidden(scope.to_string()))?
+            }
+
             // scope: User, SystemOrUserId, Requester required permission
             let user_id = request_context.user_id;
             if required_permissions.contains(&Permission::User(AuthzEntity::On(user_id))) {
@@ -1033,7 +1122,9 @@ mod tests {
         CollExec, CollRead, InterColl, InterCollRead, NoPermissions, Permission, Requester,
         SecAdmin, SysAdmin,
     };
-    use crate::types::basic::{AccessTokenId, CollectionId, RoleId, ToCollectionId, UserId};
+    use crate::types::basic::{
+        AccessTokenId, ApiKeyScoped, CollectionId, RoleId, ToCollectionId, UserId,
+    };
     use async_trait::async_trait;
     use sqlx::SqliteConnection;
     use std::collections::HashMap;
@@ -1132,6 +1223,7 @@ mod tests {
             HashMap<CollectionId, Arc<Vec<ToCollectionId>>>,
         inter_collections_permissions_key_can_read_value:
             HashMap<ToCollectionId, Arc<Vec<CollectionId>>>,
+        access_token_permissions_map: HashMap<AccessTokenId, Arc<Vec<Permission>>>,
     }
 
     impl AuthzContextForTest {
@@ -1145,6 +1237,16 @@ mod tests {
             self
         }
 
+        pub fn add_access_token_permissions(
+            mut self,
+            access_token: impl Into<AccessTokenId>,
+            permissions: impl Into<Vec<Permission>>,
+        ) -> Self {
+            self.access_token_permissions_map
+                .insert(access_token.into(), Arc::new(permissions.into()));
+            self
+        }
+
         pub fn remove_permissions(mut self, role: &RoleId) -> Self {
             self.role_permissions_map.remove(role);
             self
@@ -1181,6 +1283,7 @@ mod tests {
                 role_permissions_map: HashMap::new(),
                 inter_collections_permissions_value_can_read_key: HashMap::new(),
                 inter_collections_permissions_key_can_read_value: HashMap::new(),
+                access_token_permissions_map: HashMap::new(),
             }
             .add_permissions(
                 RoleId::sys_admin(),
@@ -1221,6 +1324,17 @@ mod tests {
             Ok(self.role_permissions_map.get(role).map(Arc::clone))
         }
 
+        async fn access_token_permissions(
+            &self,
+            _conn: &mut SqliteConnection,
+            access_token: &AccessTokenId,
+        ) -> Result<Option<Arc<Vec<Permission>>>, TdError> {
+            Ok(self
+                .access_token_permissions_map
+                .get(access_token)
+                .map(Arc::clone))
+        }
+
         async fn inter_collections_permissions_value_can_read_key(
             &self,
             _conn: &mut SqliteConnection,
@@ -2253,6 +2367,199 @@ mod tests {
         .await;
     }
 
+    #[test]
+    fn test_scoped_permissions() {
+        let c0 = CollectionId::default();
+        let c1 = CollectionId::default();
+        let role = [
+            Permission::SecAdmin,
+            Permission::CollectionDev(AuthzEntity::All),
+            Permission::CollectionRead(AuthzEntity::On(c0)),
+            Permission::CollectionRead(AuthzEntity::On(c1)),
+        ];
+
+        // same permission
+        assert_eq!(
+            scoped_permissions(&role, &[Permission::SecAdmin]),
+            vec![Permission::SecAdmin]
+        );
+        // not in role
+        assert!(scoped_permissions(&role, &[Permission::SysAdmin]).is_empty());
+        assert!(
+            scoped_permissions(&role, &[Permission::CollectionExec(AuthzEntity::On(c0))])
+                .is_empty()
+        );
+        // role on all collections, scope on one
+        assert_eq!(
+            scoped_permissions(&role, &[Permission::CollectionDev(AuthzEntity::On(c0))]),
+            vec![Permission::CollectionDev(AuthzEntity::On(c0))]
+        );
+        // role on some collections, scope on all
+        assert_eq!(
+            scoped_permissions(&role, &[Permission::CollectionRead(AuthzEntity::All)]),
+            vec![
+                Permission::CollectionRead(AuthzEntity::On(c0)),
+                Permission::CollectionRead(AuthzEntity::On(c1)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scoped_access_token() {
+        let collection = CollectionId::default();
+        let access_token = AccessTokenId::default();
+
+        let authz_context = AuthzContextForTest::default().add_access_token_permissions(
+            access_token,
+            [Permission::CollectionRead(AuthzEntity::On(collection))],
+        );
+        let authz_context = Arc::new(authz_context);
+        let scope = Arc::new(AuthzScope::Collection(AuthzEntity::On(collection)));
+
+        // without the scoped token the role can read and write
+        let request_context = Arc::new(RequestContext::with(
+            AccessTokenId::default(),
+            UserId::default(),
+            RoleId::user(),
+        ));
+        assert_ok(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<CollRead>::new(),
+        )
+        .await;
+        assert_ok(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<CollDev>::new(),
+        )
+        .await;
+
+        // with the scoped token it can only read
+        let request_context = Arc::new(RequestContext::with(
+            access_token,
+            UserId::default(),
+            RoleId::user(),
+        ));
+        assert_ok(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<CollRead>::new(),
+        )
+        .await;
+        assert_error(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<CollDev>::new(),
+            AuthzError::Forbidden("".to_string()),
+        )
+        .await;
+
+        // and only the collection of the scope
+        let scope = Arc::new(AuthzScope::Collection(AuthzEntity::On(
+            CollectionId::default(),
+        )));
+        assert_error(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<CollRead>::new(),
+            AuthzError::Forbidden("".to_string()),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_scoped_access_token_without_scopes() {
+        let collection = CollectionId::default();
+        let access_token = AccessTokenId::default();
+
+        let authz_context =
+            AuthzContextForTest::default().add_access_token_permissions(access_token, vec![]);
+        let authz_context = Arc::new(authz_context);
+        let scope = Arc::new(AuthzScope::Collection(AuthzEntity::On(collection)));
+
+        let request_context = Arc::new(RequestContext::with(
+            access_token,
+            UserId::default(),
+            RoleId::user(),
+        ));
+        assert_error(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<CollRead>::new(),
+            AuthzError::Forbidden("".to_string()),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_scoped_access_token_without_known_scope() {
+        let user = UserId::default();
+        let collection = CollectionId::default();
+
+        // the scope of the access token is not in the authz context
+        let authz_context = Arc::new(AuthzContextForTest::default());
+        let scope = Arc::new(AuthzScope::Collection(AuthzEntity::On(collection)));
+
+        let request_context = RequestContext::with(AccessTokenId::default(), user, RoleId::user());
+        assert_ok(
+            &authz_context,
+            &Arc::new(request_context.clone()),
+            &scope,
+            Authz::<CollRead>::new(),
+        )
+        .await;
+
+        // marked as scoped it has no permissions, neither of the role nor of the requester
+        let request_context = Arc::new(request_context.with_scoped(ApiKeyScoped::from(true)));
+        assert_error(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<CollRead>::new(),
+            AuthzError::Forbidden("".to_string()),
+        )
+        .await;
+        let scope = Arc::new(AuthzScope::User(AuthzEntity::On(user)));
+        assert_error(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<Requester>::new(),
+            AuthzError::Forbidden("".to_string()),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_scoped_access_token_no_requester_permissions() {
+        let user = UserId::default();
+        let access_token = AccessTokenId::default();
+
+        let authz_context = AuthzContextForTest::default().add_access_token_permissions(
+            access_token,
+            [Permission::CollectionRead(AuthzEntity::All)],
+        );
+        let authz_context = Arc::new(authz_context);
+        let scope = Arc::new(AuthzScope::User(AuthzEntity::On(user)));
+
+        let request_context = Arc::new(RequestContext::with(access_token, user, RoleId::user()));
+        assert_error(
+            &authz_context,
+            &request_context,
+            &scope,
+            Authz::<Requester>::new(),
+            AuthzError::Forbidden("".to_string()),
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn test_role_collections_permissions() {
         let db = td_database::test_utils::db().await.unwrap();
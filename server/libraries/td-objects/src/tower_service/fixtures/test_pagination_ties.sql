@@ -0,0 +1,18 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+create table bar
+(
+    id   TEXT primary key,
+    kind TEXT not null,
+    name TEXT not null
+);
+
+
+INSERT INTO bar values ('0', 'x', 'a');
+INSERT INTO bar values ('1', 'y', 'a');
+INSERT INTO bar values ('2', 'x', 'a');
+INSERT INTO bar values ('3', 'x', 'b');
+INSERT INTO bar values ('4', 'y', 'a');
+INSERT INTO bar values ('5', 'x', 'a');
@@ -2,6 +2,8 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+pub mod audit;
 pub mod authz;
 pub mod from;
 pub mod sql;
+pub mod trace;
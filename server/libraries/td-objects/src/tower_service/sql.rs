@@ -6,15 +6,20 @@ use crate::dxo::crudl::{
     ListParams, ListRequest, ListResponse, ListResponseBuilder, handle_sql_err,
 };
 use crate::sql::cte::CteQueries;
-use crate::sql::list::ListQueryParams;
+use crate::sql::list::{ListQueryParams, TiebreakerId};
 use crate::sql::{
-    DaoQueries, DeleteBy, FindBy, Insert, ListBy, ListFilterGenerator, SelectBy, UpdateBy,
+    DaoQueries, DeleteBy, FindBy, Insert, ListBy, ListFilterGenerator, SelectBy, UpdateBy, explain,
 };
 use crate::types::{AsDynSqlEntities, DataAccessObject, ListQuery, States, Versioned};
 use async_trait::async_trait;
+use sqlx::FromRow;
+use sqlx::sqlite::SqliteRow;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::ops::Deref;
+use td_database::sql::{DbError, is_disk_full};
 use td_error::{TdError, td_error};
+use td_tower::deadline;
 use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
 
 #[td_error]
@@ -32,10 +37,30 @@ pub enum SqlError {
     UpdateError(String, String, String, #[source] sqlx::Error) = 4,
     #[error("Could not delete entity with [{0}] [{1}] in '{2}': {3}")]
     DeleteError(String, String, String, #[source] sqlx::Error) = 5,
-    #[error("Could not find entity in '{0}': {1}")]
-    FindError(String, #[source] sqlx::Error) = 6,
+    #[error("Could not find entity in '{0}' with query [{1}]: {2}")]
+    FindError(String, String, #[source] sqlx::Error) = 6,
     #[error("Could not update entity in '{0}': {1}")]
     UpdateAllError(String, #[source] sqlx::Error) = 7,
+    #[error("Could not list entities in '{0}' with query [{1}]: {2}")]
+    ListError(String, String, #[source] sqlx::Error) = 8,
+}
+
+/// Maps an error of a write, to [`DbError::DiskFull`] if the database disk is full, so it is
+/// reported as an internal error, and with the given function otherwise.
+fn write_err(e: sqlx::Error, f: impl FnOnce(sqlx::Error) -> Result<TdError, TdError>) -> TdError {
+    if is_disk_full(&e) {
+        DbError::from_sql(e).into()
+    } else {
+        f(e).unwrap_or_else(|e| e)
+    }
+}
+
+/// Runs a read query within the request deadline, if any, aborting it once the deadline is
+/// reached.
+async fn within_deadline<T>(operation: &str, f: impl Future<Output = T>) -> Result<T, TdError> {
+    deadline::within_deadline(operation, f)
+        .await
+        .map_err(TdError::from)
 }
 
 pub fn formatted_entity<D, E>(entities: &E) -> Result<(String, String, String), TdError>
@@ -77,10 +102,11 @@ pub async fn insert<D: DataAccessObject>(
         .execute(&mut *conn)
         .await
         .map_err(|e| {
-            formatted_entity::<D, _>(&())
-                .map(|(_, _, table)| SqlError::InsertError(table, e).into())
-        })
-        .map_err(|e| e.unwrap_or_else(|e| e))?;
+            write_err(e, |e| {
+                formatted_entity::<D, _>(&())
+                    .map(|(_, _, table)| SqlError::InsertError(table, e).into())
+            })
+        })?;
     Ok(())
 }
 
@@ -99,10 +125,11 @@ pub async fn insert_vec<D: DataAccessObject>(
             .execute(&mut *conn)
             .await
             .map_err(|e| {
-                formatted_entity::<D, _>(&())
-                    .map(|(_, _, table)| SqlError::InsertError(table, e).into())
-            })
-            .map_err(|e| e.unwrap_or_else(|e| e))?;
+                write_err(e, |e| {
+                    formatted_entity::<D, _>(&())
+                        .map(|(_, _, table)| SqlError::InsertError(table, e).into())
+                })
+            })?;
     }
     Ok(())
 }
@@ -343,12 +370,11 @@ where
         let conn = conn.get_mut_connection()?;
 
         let by = by.as_slice();
-        let result = queries
-            .find_by::<D>(by)?
-            .build_query_as()
-            .fetch_all(&mut *conn)
-            .await
-            .map_err(|e| SqlError::FindError(D::sql_table().to_string(), e))?;
+        let mut query_builder = queries.find_by::<D>(by)?;
+        let sql = explain(&query_builder);
+        let result = within_deadline("find", query_builder.build_query_as().fetch_all(&mut *conn))
+            .await?
+            .map_err(|e| SqlError::FindError(D::sql_table().to_string(), sql, e))?;
 
         Ok(result)
     }
@@ -366,12 +392,11 @@ where
         let conn = conn.get_mut_connection()?;
 
         let by = by.deref();
-        let result = queries
-            .find_versions_at::<S, D>(Some(&*natural_order_by), by)?
-            .build_query_as()
-            .fetch_all(&mut *conn)
-            .await
-            .map_err(|e| TdError::from(SqlError::FindError(D::sql_table().to_string(), e)))?;
+        let mut query_builder = queries.find_versions_at::<S, D>(Some(&*natural_order_by), by)?;
+        let sql = explain(&query_builder);
+        let result = within_deadline("find", query_builder.build_query_as().fetch_all(&mut *conn))
+            .await?
+            .map_err(|e| SqlError::FindError(D::sql_table().to_string(), sql, e))?;
 
         Ok(result)
     }
@@ -559,11 +584,12 @@ where
             .execute(&mut *conn)
             .await
             .map_err(|e| {
-                formatted_entity::<D, _>(by).and_then(|(columns, values, table)| {
-                    Err(SqlError::UpdateError(columns, values, table, e))?
+                write_err(e, |e| {
+                    formatted_entity::<D, _>(by).and_then(|(columns, values, table)| {
+                        Err(SqlError::UpdateError(columns, values, table, e))?
+                    })
                 })
-            })
-            .map_err(|e| e.unwrap_or_else(|e| e))?;
+            })?;
 
         Ok(())
     }
@@ -588,7 +614,11 @@ where
             .build()
             .execute(&mut *conn)
             .await
-            .map_err(|e| TdError::from(SqlError::UpdateAllError(D::sql_table().to_string(), e)))?;
+            .map_err(|e| {
+                write_err(e, |e| {
+                    Ok(SqlError::UpdateAllError(D::sql_table().to_string(), e).into())
+                })
+            })?;
 
         Ok(())
     }
@@ -660,41 +690,18 @@ where
         let query_params = ListQueryParams::<T>::try_from(&request.list_params)?;
 
         let by = by.deref();
-        let result: Vec<T::Dao> = queries
+        let mut query_builder = queries
             .list_by::<T, F>(&query_params, &list_filter_generator, by)
-            .await?
-            .build_query_as()
-            .persistent(true)
-            .fetch_all(&mut *conn)
-            .await
-            .map_err(|e| {
-                formatted_entity::<T::Dao, _>(by).and_then(|(columns, values, table)| {
-                    Err(SqlError::SelectError(columns, values, table, e))?
-                })
-            })
-            .map_err(|e| e.unwrap_or_else(|e| e))?;
-
-        let mut result = result
-            .iter()
-            .map(T::try_from_dao)
-            .collect::<Result<Vec<T>, TdError>>()?;
-
-        if request.list_params.previous.is_some() {
-            result.reverse();
-        }
-
-        let (previous, previous_pagination_id) =
-            compute_previous(&request.list_params, &query_params, &result);
-        let (next, next_pagination_id) = compute_next(&request.list_params, &query_params, &result);
-
-        let list_response = ListResponseBuilder::default()
-            .list_params(request.list_params.clone())
-            .data(result)
-            .previous_page(previous, previous_pagination_id)
-            .next_page(next, next_pagination_id)
-            .build()?;
+            .await?;
+        let sql = explain(&query_builder);
+        let rows = within_deadline(
+            "list",
+            query_builder.build().persistent(true).fetch_all(&mut *conn),
+        )
+        .await?
+        .map_err(|e| SqlError::ListError(T::Dao::sql_table().to_string(), sql, e))?;
 
-        Ok(list_response)
+        list_response(&request.list_params, &query_params, &rows)
     }
 
     async fn list_at<N, F, const S: u8, T>(
@@ -717,46 +724,23 @@ where
         let query_params = ListQueryParams::<T>::try_from(&request.list_params)?;
 
         let by = by.deref();
-        let result: Vec<T::Dao> = queries
+        let mut query_builder = queries
             .list_by_at::<T, S, F>(
                 &query_params,
                 Some(&*natural_order_by),
                 &list_filter_generator,
                 by,
             )
-            .await?
-            .build_query_as()
-            .persistent(true)
-            .fetch_all(&mut *conn)
-            .await
-            .map_err(|e| {
-                formatted_entity::<T::Dao, _>(by).and_then(|(columns, values, table)| {
-                    Err(SqlError::SelectError(columns, values, table, e))?
-                })
-            })
-            .map_err(|e| e.unwrap_or_else(|e| e))?;
-
-        let mut result = result
-            .iter()
-            .map(T::try_from_dao)
-            .collect::<Result<Vec<T>, TdError>>()?;
-
-        if request.list_params.previous.is_some() {
-            result.reverse();
-        }
-
-        let (previous, previous_pagination_id) =
-            compute_previous(&request.list_params, &query_params, &result);
-        let (next, next_pagination_id) = compute_next(&request.list_params, &query_params, &result);
-
-        let list_response = ListResponseBuilder::default()
-            .list_params(request.list_params.clone())
-            .data(result)
-            .previous_page(previous, previous_pagination_id)
-            .next_page(next, next_pagination_id)
-            .build()?;
+            .await?;
+        let sql = explain(&query_builder);
+        let rows = within_deadline(
+            "list",
+            query_builder.build().persistent(true).fetch_all(&mut *conn),
+        )
+        .await?
+        .map_err(|e| SqlError::ListError(T::Dao::sql_table().to_string(), sql, e))?;
 
-        Ok(list_response)
+        list_response(&request.list_params, &query_params, &rows)
     }
 
     async fn list_versions_at<N, F, const S: u8, T>(
@@ -779,46 +763,23 @@ where
         let query_params = ListQueryParams::<T>::try_from(&request.list_params)?;
 
         let by = by.deref();
-        let result: Vec<T::Dao> = queries
+        let mut query_builder = queries
             .list_versions_by_at::<T, S, F>(
                 &query_params,
                 Some(&*natural_order_by),
                 &list_filter_generator,
                 by,
             )
-            .await?
-            .build_query_as()
-            .persistent(true)
-            .fetch_all(&mut *conn)
-            .await
-            .map_err(|e| {
-                formatted_entity::<T::Dao, _>(by).and_then(|(columns, values, table)| {
-                    Err(SqlError::SelectError(columns, values, table, e))?
-                })
-            })
-            .map_err(|e| e.unwrap_or_else(|e| e))?;
-
-        let mut result = result
-            .iter()
-            .map(T::try_from_dao)
-            .collect::<Result<Vec<T>, TdError>>()?;
-
-        if request.list_params.previous.is_some() {
-            result.reverse();
-        }
-
-        let (previous, previous_pagination_id) =
-            compute_previous(&request.list_params, &query_params, &result);
-        let (next, next_pagination_id) = compute_next(&request.list_params, &query_params, &result);
-
-        let list_response = ListResponseBuilder::default()
-            .list_params(request.list_params.clone())
-            .data(result)
-            .previous_page(previous, previous_pagination_id)
-            .next_page(next, next_pagination_id)
-            .build()?;
+            .await?;
+        let sql = explain(&query_builder);
+        let rows = within_deadline(
+            "list",
+            query_builder.build().persistent(true).fetch_all(&mut *conn),
+        )
+        .await?
+        .map_err(|e| SqlError::ListError(T::Dao::sql_table().to_string(), sql, e))?;
 
-        Ok(list_response)
+        list_response(&request.list_params, &query_params, &rows)
     }
 }
 
@@ -826,8 +787,8 @@ where
 fn compute_previous<T: ListQuery>(
     list_params: &ListParams,
     query_params: &ListQueryParams<T>,
-    result: &[T],
-) -> (Option<String>, Option<String>) {
+    result: &[(T, Option<TiebreakerId>)],
+) -> (Option<Vec<String>>, Option<String>, Option<String>) {
     let first = match (&list_params.previous, &list_params.next, result.first()) {
         (None, None, _) => None,
         (None, Some(_), Some(first)) => Some(first),
@@ -836,51 +797,86 @@ fn compute_previous<T: ListQuery>(
         (None, Some(_), None) => None,
     };
     match first {
-        None => (None, None),
-        Some(first) => {
-            let order = query_params
-                .order
-                .as_ref()
-                .unwrap_or(&query_params.natural_order)
-                .field()
-                .to_string();
-            let order = Some(order);
-            (
-                first.order_by_str_value(&order),
-                Some(first.pagination_value()),
-            )
-        }
+        None => (None, None, None),
+        Some((first, first_id)) => (
+            pagination_values(query_params, first),
+            Some(first.pagination_value()),
+            first_id.as_ref().map(TiebreakerId::as_display),
+        ),
     }
 }
 
+/// Builds the list response with the listed rows, and the pagination info of its first and last
+/// rows.
+fn list_response<T: ListQuery>(
+    list_params: &ListParams,
+    query_params: &ListQueryParams<T>,
+    rows: &[SqliteRow],
+) -> Result<ListResponse<T>, TdError> {
+    let mut result = rows
+        .iter()
+        .map(|row| {
+            let dao = T::Dao::from_row(row).map_err(handle_sql_err)?;
+            let tiebreaker_id = match query_params.id_tiebreaker() {
+                true => Some(TiebreakerId::from_row(row).map_err(handle_sql_err)?),
+                false => None,
+            };
+            Ok((T::try_from_dao(&dao)?, tiebreaker_id))
+        })
+        .collect::<Result<Vec<_>, TdError>>()?;
+
+    if list_params.previous.is_some() {
+        result.reverse();
+    }
+
+    let (previous, previous_pagination_id, previous_tiebreaker_id) =
+        compute_previous(list_params, query_params, &result);
+    let (next, next_pagination_id, next_tiebreaker_id) =
+        compute_next(list_params, query_params, &result);
+
+    let list_response = ListResponseBuilder::default()
+        .list_params(list_params.clone())
+        .applied(Some(query_params.applied()))
+        .data(result.into_iter().map(|(item, _)| item).collect())
+        .previous_page(previous, previous_pagination_id, previous_tiebreaker_id)
+        .next_page(next, next_pagination_id, next_tiebreaker_id)
+        .build()?;
+
+    Ok(list_response)
+}
+
 /// Determine next info for listing pagination
 fn compute_next<T: ListQuery>(
     list_params: &ListParams,
     query_params: &ListQueryParams<T>,
-    result: &[T],
-) -> (Option<String>, Option<String>) {
+    result: &[(T, Option<TiebreakerId>)],
+) -> (Option<Vec<String>>, Option<String>, Option<String>) {
     match (result.len() < list_params.len, result.last()) {
         // If the result length is less than the requested length, no more pages => no next page
-        (true, _) => (None, None),
+        (true, _) => (None, None, None),
         // not result data => no next page
-        (false, None) => (None, None),
+        (false, None) => (None, None, None),
         // result length eq requested length and result data => use the last data item to get next info
-        (false, Some(last)) => {
-            let order = query_params
-                .order
-                .as_ref()
-                .unwrap_or(&query_params.natural_order)
-                .field()
-                .to_string();
-            let order = Some(order);
-            (
-                last.order_by_str_value(&order),
-                Some(last.pagination_value()),
-            )
-        }
+        (false, Some((last, last_id))) => (
+            pagination_values(query_params, last),
+            Some(last.pagination_value()),
+            last_id.as_ref().map(TiebreakerId::as_display),
+        ),
     }
 }
 
+/// Values of the ordered by columns of an item, to paginate from it.
+fn pagination_values<T: ListQuery>(
+    query_params: &ListQueryParams<T>,
+    item: &T,
+) -> Option<Vec<String>> {
+    query_params
+        .ordered_by()
+        .iter()
+        .map(|order| item.order_by_str_value(&Some(order.field().to_string())))
+        .collect()
+}
+
 #[async_trait]
 pub trait SqlDeleteService<E> {
     async fn delete<D>(
@@ -915,11 +911,12 @@ where
             .execute(&mut *conn)
             .await
             .map_err(|e| {
-                formatted_entity::<D, _>(by).and_then(|(columns, values, table)| {
-                    Err(SqlError::DeleteError(columns, values, table, e))?
+                write_err(e, |e| {
+                    formatted_entity::<D, _>(by).and_then(|(columns, values, table)| {
+                        Err(SqlError::DeleteError(columns, values, table, e))?
+                    })
                 })
-            })
-            .map_err(|e| e.unwrap_or_else(|e| e))?;
+            })?;
 
         Ok(())
     }
@@ -995,6 +992,34 @@ mod tests {
         Ok(())
     }
 
+    #[td_test::test(sqlx(fixture = "test_tower"))]
+    #[tokio::test]
+    async fn test_insert_disk_full(db: DbPool) -> Result<(), TdError> {
+        let transaction = db.begin().await.unwrap();
+        let transaction = ConnectionType::Transaction(transaction).into();
+        let connection = Connection::new(transaction);
+        {
+            let mut conn = connection.0.lock().await;
+            let conn = conn.get_mut_connection()?;
+            // The maximum is capped to the current size of the database, so it cannot grow.
+            sqlx::query("PRAGMA max_page_count = 1")
+                .execute(conn)
+                .await
+                .unwrap();
+        }
+
+        let dao = Input::new(FooDao {
+            id: FooId::try_from("final boss")?,
+            name: FooName::try_from("bowser".repeat(16 * 1024))?,
+        });
+        let err = insert(connection, SrvCtx::new(DaoQueries::default()), dao)
+            .await
+            .unwrap_err();
+        assert!(matches!(err.domain_err::<DbError>(), DbError::DiskFull(_)));
+        assert_eq!(err.api_error(), td_error::ApiError::InternalError);
+        Ok(())
+    }
+
     #[td_test::test(sqlx(fixture = "test_tower"))]
     #[tokio::test]
     async fn test_select_by(db: DbPool) -> Result<(), TdError> {
@@ -1273,6 +1298,129 @@ mod tests {
         Ok(())
     }
 
+    #[Dto]
+    #[derive(Eq, PartialEq)]
+    #[dto(list(on = FooDao))]
+    #[td_type(builder(try_from = FooDao))]
+    struct FooDtoFilter {
+        #[dto(list(pagination_by = "+"))]
+        id: FooId,
+        #[dto(list(filter, order_by))]
+        name: FooName,
+    }
+
+    #[td_test::test(sqlx(fixture = "test_tower"))]
+    #[tokio::test]
+    async fn test_list_applied_params(db: DbPool) -> Result<(), TdError> {
+        let transaction = db.begin().await.unwrap();
+        let transaction = ConnectionType::Transaction(transaction).into();
+        let connection = Connection::new(transaction);
+
+        let list_request = RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )
+        .list(
+            (),
+            ListParams::builder()
+                .len(10usize)
+                .filter(vec!["name:eq:mario".to_string()])
+                .order_by(Some("name-".to_string()))
+                .build()?,
+        );
+
+        let list = By::<()>::list::<(), NoListFilter, FooDtoFilter>(
+            connection,
+            SrvCtx::new(DaoQueries::default()),
+            Input::new(list_request),
+            Input::new(()),
+            Input::new(()),
+        )
+        .await?;
+        assert_eq!(list.len, 1);
+        assert_eq!(list.data[0].name, MARIO.name);
+
+        let applied = list.applied.unwrap();
+        assert_eq!(applied.len, 10);
+        assert_eq!(applied.filter, vec!["name:eq:mario".to_string()]);
+        assert_eq!(applied.order_by, "name-");
+        Ok(())
+    }
+
+    #[td_test::test(sqlx(fixture = "test_tower"))]
+    #[tokio::test]
+    async fn test_find_error_has_sql(db: DbPool) -> Result<(), TdError> {
+        let mut transaction = db.begin().await.unwrap();
+        sqlx::query("DROP TABLE foo")
+            .execute(&mut *transaction)
+            .await
+            .unwrap();
+        let transaction = ConnectionType::Transaction(transaction).into();
+        let connection = Connection::new(transaction);
+
+        let err = By::<FooName>::find::<FooDao>(
+            connection,
+            SrvCtx::new(DaoQueries::default()),
+            Input::new(vec![FooName::try_from("mario")?]),
+        )
+        .await
+        .unwrap_err();
+
+        let SqlError::FindError(table, sql, _) = err.domain_err::<SqlError>() else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(table, "foo");
+        assert!(sql.starts_with("SELECT id, name FROM foo WHERE"));
+        // the SQL template has placeholders, not the bound values
+        assert!(!sql.contains("mario"));
+        assert!(err.to_string().contains(sql));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx(fixture = "test_tower"))]
+    #[tokio::test]
+    async fn test_list_error_has_sql(db: DbPool) -> Result<(), TdError> {
+        let mut transaction = db.begin().await.unwrap();
+        sqlx::query("DROP TABLE foo")
+            .execute(&mut *transaction)
+            .await
+            .unwrap();
+        let transaction = ConnectionType::Transaction(transaction).into();
+        let connection = Connection::new(transaction);
+
+        let list_request = RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )
+        .list(
+            (),
+            ListParams::builder()
+                .filter(vec!["name:eq:mario".to_string()])
+                .build()?,
+        );
+
+        let err = By::<()>::list::<(), NoListFilter, FooDtoFilter>(
+            connection,
+            SrvCtx::new(DaoQueries::default()),
+            Input::new(list_request),
+            Input::new(()),
+            Input::new(()),
+        )
+        .await
+        .unwrap_err();
+
+        let SqlError::ListError(table, sql, _) = err.domain_err::<SqlError>() else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(table, "foo");
+        assert!(sql.contains("FROM foo"));
+        assert!(!sql.contains("mario"));
+        assert!(err.to_string().contains(sql));
+        Ok(())
+    }
+
     #[td_test::test(sqlx(fixture = "test_tower"))]
     #[tokio::test]
     async fn test_delete(db: DbPool) -> Result<(), TdError> {
@@ -1349,6 +1497,7 @@ mod tests {
                 .name(Name::try_from("d")?)
                 .build()?,
         ];
+        let rows: Vec<_> = data.iter().map(|dto| (dto.clone(), None)).collect();
 
         // default list params with no data
         let list_params = ListParams::builder()
@@ -1358,7 +1507,7 @@ mod tests {
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
             compute_previous::<MyDto>(&list_params, &list_query_params, &[]),
-            (None, None)
+            (None, None, None)
         );
 
         // default list params with data
@@ -1368,8 +1517,8 @@ mod tests {
             .unwrap();
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
-            compute_previous::<MyDto>(&list_params, &list_query_params, &data),
-            (None, None)
+            compute_previous::<MyDto>(&list_params, &list_query_params, &rows),
+            (None, None, None)
         );
 
         // previous list params with no data
@@ -1381,7 +1530,7 @@ mod tests {
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
             compute_previous::<MyDto>(&list_params, &list_query_params, &[]),
-            (None, None)
+            (None, None, None)
         );
 
         // previous list params with data
@@ -1392,10 +1541,13 @@ mod tests {
             .build()?;
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
-            compute_previous::<MyDto>(&list_params, &list_query_params, &data[0..1]),
+            compute_previous::<MyDto>(&list_params, &list_query_params, &rows[0..1]),
             (
-                data[0].order_by_str_value(&Some("name".to_string())),
-                Some(data[0].pagination_value())
+                data[0]
+                    .order_by_str_value(&Some("name".to_string()))
+                    .map(|v| vec![v]),
+                Some(data[0].pagination_value()),
+                None
             )
         );
         Ok(())
@@ -1421,6 +1573,7 @@ mod tests {
                 .name(Name::try_from("d")?)
                 .build()?,
         ];
+        let rows: Vec<_> = data.iter().map(|dto| (dto.clone(), None)).collect();
 
         // default list params with no data
         let list_params = ListParams::builder()
@@ -1430,7 +1583,7 @@ mod tests {
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
             compute_next::<MyDto>(&list_params, &list_query_params, &[]),
-            (None, None)
+            (None, None, None)
         );
 
         // default list params with less data than requested
@@ -1441,8 +1594,8 @@ mod tests {
             .unwrap();
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
-            compute_next::<MyDto>(&list_params, &list_query_params, &data),
-            (None, None)
+            compute_next::<MyDto>(&list_params, &list_query_params, &rows),
+            (None, None, None)
         );
 
         // default list params with exact data
@@ -1453,10 +1606,13 @@ mod tests {
             .unwrap();
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
-            compute_next::<MyDto>(&list_params, &list_query_params, &data),
+            compute_next::<MyDto>(&list_params, &list_query_params, &rows),
             (
-                data[3].order_by_str_value(&Some("name".to_string())),
-                Some(data[3].pagination_value())
+                data[3]
+                    .order_by_str_value(&Some("name".to_string()))
+                    .map(|v| vec![v]),
+                Some(data[3].pagination_value()),
+                None
             )
         );
 
@@ -1469,7 +1625,7 @@ mod tests {
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
             compute_next::<MyDto>(&list_params, &list_query_params, &[]),
-            (None, None)
+            (None, None, None)
         );
 
         // next list params with less data than requested
@@ -1481,8 +1637,8 @@ mod tests {
             .build()?;
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
-            compute_next::<MyDto>(&list_params, &list_query_params, &data),
-            (None, None)
+            compute_next::<MyDto>(&list_params, &list_query_params, &rows),
+            (None, None, None)
         );
 
         // next list params with same amount of data than requested
@@ -1494,10 +1650,13 @@ mod tests {
             .build()?;
         let list_query_params = ListQueryParams::<MyDto>::try_from(&list_params)?;
         assert_eq!(
-            compute_next::<MyDto>(&list_params, &list_query_params, &data[2..]),
+            compute_next::<MyDto>(&list_params, &list_query_params, &rows[2..]),
             (
-                data[3].order_by_str_value(&Some("name".to_string())),
-                Some(data[3].pagination_value())
+                data[3]
+                    .order_by_str_value(&Some("name".to_string()))
+                    .map(|v| vec![v]),
+                Some(data[3].pagination_value()),
+                None
             )
         );
         Ok(())
@@ -1758,4 +1917,115 @@ mod tests {
 
         Ok(())
     }
+
+    #[td_type::typed(string)]
+    struct BarKind;
+
+    #[Dao]
+    #[dao(sql_table = "bar")]
+    struct BarDao {
+        id: FooId,
+        kind: BarKind,
+        name: FooName,
+    }
+
+    #[Dto]
+    #[dto(list(on = BarDao))]
+    #[td_type(builder(try_from = BarDao))]
+    struct BarDto {
+        id: FooId,
+        #[dto(list(order_by))]
+        kind: BarKind,
+        #[dto(list(pagination_by = "+", order_by))]
+        name: FooName,
+    }
+
+    #[td_test::test(sqlx(fixture = "test_pagination_ties"))]
+    #[tokio::test]
+    async fn test_list_pagination_ties(db: DbPool) -> Result<(), TdError> {
+        async fn list(db: &DbPool, params: ListParams) -> ListResponse<BarDto> {
+            let request = RequestContext::with(
+                AccessTokenId::default(),
+                UserId::admin(),
+                RoleId::sys_admin(),
+            )
+            .list((), params);
+            let connection =
+                Connection::new(ConnectionType::PoolConnection(db.acquire().await.unwrap()).into());
+            By::<()>::list::<(), NoListFilter, BarDto>(
+                connection,
+                SrvCtx::new(DaoQueries::default()),
+                Input::new(request),
+                Input::new(()),
+                Input::new(()),
+            )
+            .await
+            .unwrap()
+        }
+
+        fn ids(res: &ListResponse<BarDto>) -> Vec<String> {
+            res.data.iter().map(|dto| dto.id.to_string()).collect()
+        }
+
+        // pages of 2 rows, following the next cursors and then the previous cursors back
+        async fn pages(
+            db: &DbPool,
+            order_by: Option<String>,
+        ) -> Result<(Vec<Vec<String>>, Vec<Vec<String>>), TdError> {
+            let params = |previous: Option<String>, next: Option<String>| {
+                ListParams::builder()
+                    .len(2usize)
+                    .order_by(order_by.clone())
+                    .previous(previous)
+                    .next(next)
+                    .build()
+            };
+
+            let mut res = list(db, params(None, None)?).await;
+            let mut forward = vec![ids(&res)];
+            while let Some(next) = res.next_cursor.clone() {
+                let next_res = list(db, params(None, Some(next))?).await;
+                if next_res.data.is_empty() {
+                    break;
+                }
+                res = next_res;
+                forward.push(ids(&res));
+            }
+
+            let mut backward = vec![];
+            while let Some(previous) = res.previous_cursor.clone() {
+                res = list(db, params(Some(previous), None)?).await;
+                if res.data.is_empty() {
+                    break;
+                }
+                backward.insert(0, ids(&res));
+            }
+            Ok((forward, backward))
+        }
+
+        // natural order only, all but one row share the same name
+        let (forward, backward) = pages(&db, None).await?;
+        assert_eq!(
+            forward,
+            vec![vec!["0", "1"], vec!["2", "4"], vec!["5", "3"]]
+        );
+        assert_eq!(backward, forward[..2]);
+
+        // multiple columns, rows sharing the same kind and name
+        let (forward, backward) = pages(&db, Some("kind".to_string())).await?;
+        assert_eq!(
+            forward,
+            vec![vec!["0", "2"], vec!["5", "3"], vec!["1", "4"]]
+        );
+        assert_eq!(backward, forward[..2]);
+
+        let (forward, backward) = pages(&db, Some("kind-".to_string())).await?;
+        assert_eq!(
+            forward,
+            vec![vec!["4", "1"], vec!["3", "5"], vec!["2", "0"]]
+        );
+        assert_eq!(backward, forward[..2]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,70 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Request id of the service requests, recorded in the spans of the services serving them. It
+//! is the request id of the [`RequestContext`] of the requests, requests without it have none.
+
+use crate::dxo::auth::{Login, PasswordChange};
+use crate::dxo::crudl::{
+    CreateRequest, DeleteRequest, ListRequest, ReadRequest, RequestContext, UpdateRequest,
+};
+use td_tower::trace::TracedRequest;
+
+fn request_id(context: &RequestContext) -> Option<String> {
+    context
+        .request_id
+        .as_ref()
+        .map(|request_id| request_id.to_string())
+}
+
+impl<N, C> TracedRequest for CreateRequest<N, C>
+where
+    N: Clone + Send + Sync + 'static,
+    C: Clone + Send + Sync + 'static,
+{
+    fn request_id(&self) -> Option<String> {
+        request_id(&self.context)
+    }
+}
+
+impl<N, U> TracedRequest for UpdateRequest<N, U>
+where
+    N: Clone + Send + Sync + 'static,
+    U: Clone + Send + Sync + 'static,
+{
+    fn request_id(&self) -> Option<String> {
+        request_id(&self.context)
+    }
+}
+
+impl<N> TracedRequest for DeleteRequest<N>
+where
+    N: Clone + Send + Sync + 'static,
+{
+    fn request_id(&self) -> Option<String> {
+        request_id(&self.context)
+    }
+}
+
+impl<N> TracedRequest for ReadRequest<N>
+where
+    N: Clone + Send + Sync + 'static,
+{
+    fn request_id(&self) -> Option<String> {
+        request_id(&self.context)
+    }
+}
+
+impl<N> TracedRequest for ListRequest<N>
+where
+    N: Clone + Send + Sync + 'static,
+{
+    fn request_id(&self) -> Option<String> {
+        request_id(&self.context)
+    }
+}
+
+impl TracedRequest for Login {}
+
+impl TracedRequest for PasswordChange {}
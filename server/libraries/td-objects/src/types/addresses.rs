@@ -28,6 +28,36 @@ impl NonEmptyAddresses {
             ))
         }
     }
+
+    /// Removes duplicated addresses and checks no two addresses collide on the same port, which
+    /// would fail when binding them. Addresses collide if they share port and family and either
+    /// of them is unspecified (i.e. `0.0.0.0` or `[::]`). Port `0` (ephemeral) addresses are kept
+    /// as they are.
+    pub fn validated(self) -> Result<Self, TdError> {
+        let mut validated: Vec<SocketAddr> = Vec::with_capacity(self.0.len());
+        for address in self.0 {
+            if address.port() != 0 {
+                if validated.contains(&address) {
+                    continue;
+                }
+                if let Some(conflict) = validated.iter().find(|other| {
+                    other.port() == address.port()
+                        && other.is_ipv4() == address.is_ipv4()
+                        && (other.ip().is_unspecified() || address.ip().is_unspecified())
+                }) {
+                    return Err(api_error!(
+                        ApiError::InputError,
+                        "Addresses [{}] and [{}] conflict on port {}",
+                        conflict,
+                        address,
+                        address.port()
+                    ));
+                }
+            }
+            validated.push(address);
+        }
+        Self::from_vec(validated)
+    }
 }
 
 impl Deref for NonEmptyAddresses {
@@ -38,6 +68,8 @@ impl Deref for NonEmptyAddresses {
     }
 }
 
+/// Addresses of the API server. By default `127.0.0.1:2457`, IPv4 loopback only: IPv6 is not
+/// bound unless an IPv6 address, such as `[::1]:2457`, is configured.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ApiServerAddresses(pub NonEmptyAddresses);
 
@@ -65,6 +97,8 @@ impl Deref for ApiServerAddresses {
     }
 }
 
+/// Addresses of the internal server. By default `127.0.0.1:2458`, IPv4 loopback only: IPv6 is
+/// not bound unless an IPv6 address, such as `[::1]:2458`, is configured.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InternalServerAddresses(pub NonEmptyAddresses);
 
@@ -91,3 +125,71 @@ impl Default for InternalServerAddresses {
         )]))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv6Addr;
+
+    fn address(ip: impl Into<std::net::IpAddr>, port: u16) -> SocketAddr {
+        SocketAddr::new(ip.into(), port)
+    }
+
+    #[test]
+    fn test_validated_dedup() {
+        let addresses = NonEmptyAddresses::new(nonempty![
+            address(Ipv4Addr::LOCALHOST, 2457),
+            address(Ipv4Addr::LOCALHOST, 2457),
+            address(Ipv4Addr::LOCALHOST, 2458),
+        ])
+        .validated()
+        .unwrap();
+        assert_eq!(
+            addresses,
+            NonEmptyAddresses::new(nonempty![
+                address(Ipv4Addr::LOCALHOST, 2457),
+                address(Ipv4Addr::LOCALHOST, 2458),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_validated_conflict() {
+        let err = NonEmptyAddresses::new(nonempty![
+            address(Ipv4Addr::LOCALHOST, 2457),
+            address(Ipv4Addr::UNSPECIFIED, 2457),
+        ])
+        .validated()
+        .unwrap_err();
+        assert_eq!(err.api_error(), ApiError::InputError);
+        assert!(err.to_string().contains("conflict on port 2457"));
+    }
+
+    #[test]
+    fn test_validated_no_conflict() {
+        // Different family, different port or different specified ips.
+        let addresses = nonempty![
+            address(Ipv4Addr::UNSPECIFIED, 2457),
+            address(Ipv6Addr::UNSPECIFIED, 2457),
+            address(Ipv4Addr::LOCALHOST, 2458),
+            address(Ipv4Addr::new(10, 0, 0, 1), 2458),
+        ];
+        let validated = NonEmptyAddresses::new(addresses.clone())
+            .validated()
+            .unwrap();
+        assert_eq!(validated, NonEmptyAddresses::new(addresses));
+    }
+
+    #[test]
+    fn test_validated_ephemeral() {
+        let addresses = nonempty![
+            address(Ipv4Addr::LOCALHOST, 0),
+            address(Ipv4Addr::LOCALHOST, 0),
+            address(Ipv4Addr::UNSPECIFIED, 0),
+        ];
+        let validated = NonEmptyAddresses::new(addresses.clone())
+            .validated()
+            .unwrap();
+        assert_eq!(validated, NonEmptyAddresses::new(addresses));
+    }
+}
@@ -2,6 +2,12 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+#[td_type::typed(bool(default = false))]
+pub struct ApiKeyScoped;
+
+#[td_type::typed(bool(default = false))]
+pub struct Cascade;
+
 #[td_type::typed(bool)]
 pub struct DataChanged;
 
@@ -36,6 +42,9 @@ pub struct ReuseFrozen;
 #[td_type::typed(bool)]
 pub struct SelfDependency;
 
+#[td_type::typed(bool(default = false))]
+pub struct SnippetChanged;
+
 #[td_type::typed(bool)]
 pub struct SysAdmin;
 
@@ -45,5 +54,8 @@ pub struct System;
 #[td_type::typed(bool(default = true))]
 pub struct UserEnabled;
 
+#[td_type::typed(bool)]
+pub struct UserHasRole;
+
 #[td_type::typed(bool)]
 pub struct Versioned;
@@ -0,0 +1,9 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[td_type::typed(f64)]
+pub struct ColumnMaxValue;
+
+#[td_type::typed(f64)]
+pub struct ColumnMinValue;
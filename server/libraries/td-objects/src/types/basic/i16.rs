@@ -2,6 +2,9 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+#[td_type::typed(i16(min = 1))]
+pub struct CollectionDefinitionVersion;
+
 #[td_type::typed(i16)]
 pub struct ExecutionLimit;
 
@@ -10,3 +13,9 @@ pub struct ExecutionTry;
 
 #[td_type::typed(i16(min = 1))]
 pub struct LogsCastNumber;
+
+#[td_type::typed(i16(min = 100, max = 599))]
+pub struct ResponseStatus;
+
+#[td_type::typed(i16(min = 0))]
+pub struct WebhookAttempts;
@@ -2,6 +2,12 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+#[td_type::typed(i32(min = 0, default = 0))]
+pub struct BundleRefCount;
+
+#[td_type::typed(i32(min = 0, default = 0))]
+pub struct ColumnPos;
+
 #[td_type::typed(i32(default = 0))]
 pub struct DependencyPos;
 
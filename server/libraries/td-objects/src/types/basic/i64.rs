@@ -7,9 +7,21 @@ use crate::types::basic::AtTime;
 #[td_type::typed(i64)]
 pub struct AccessTokenExpiration;
 
+#[td_type::typed(i64(min = 0, default = 0))]
+pub struct BundleChunkOffset;
+
+#[td_type::typed(i64(min = 0))]
+pub struct BundleSize;
+
 #[td_type::typed(i64)]
 pub struct ColumnCount;
 
+#[td_type::typed(i64(min = 0, default = 0))]
+pub struct NullCount;
+
+#[td_type::typed(i64(min = 0, default = 0))]
+pub struct PermissionChangeToken;
+
 #[td_type::typed(i64)]
 pub struct RowCount;
 
@@ -7,7 +7,7 @@ use td_security::{
     ID_ALL_ENTITIES, ID_ROLE_SEC_ADMIN, ID_ROLE_SYS_ADMIN, ID_ROLE_USER, ID_USER_ADMIN,
 };
 
-#[td_type::typed(id)]
+#[td_type::typed(id, try_from = ApiKeyId)]
 pub struct AccessTokenId;
 
 impl AccessTokenId {
@@ -19,9 +19,21 @@ impl AccessTokenId {
     }
 }
 
+#[td_type::typed(id)]
+pub struct ApiKeyId;
+
+#[td_type::typed(id)]
+pub struct ApiKeyScopeId;
+
+#[td_type::typed(id)]
+pub struct AuditLogId;
+
 #[td_type::typed(id)]
 pub struct BundleId;
 
+#[td_type::typed(id)]
+pub struct BundleUploadId;
+
 #[td_type::typed(id, try_from = EntityId, try_from = FromCollectionId, try_from = ToCollectionId)]
 pub struct CollectionId;
 
@@ -57,16 +69,27 @@ impl EntityId {
 #[td_type::typed(id)]
 pub struct ExecutionId;
 
+#[td_type::typed(id, try_from = FunctionVersionId)]
+pub struct ExpectedFunctionVersionId;
+
 #[td_type::typed(id, try_from = CollectionId)]
 pub struct FromCollectionId;
 
+#[td_type::typed(id, try_from = FunctionVersionId)]
+pub struct FromFunctionVersionId;
+
 #[td_type::typed(id)]
 pub struct FunctionId;
 
 #[td_type::typed(id)]
 pub struct FunctionRunId;
 
-#[td_type::typed(id)]
+#[td_type::typed(
+    id,
+    try_from = ExpectedFunctionVersionId,
+    try_from = FromFunctionVersionId,
+    try_from = ToFunctionVersionId
+)]
 pub struct FunctionVersionId;
 
 #[td_type::typed(id)]
@@ -116,6 +139,9 @@ pub struct TableVersionId;
 #[td_type::typed(id, try_from = CollectionId)]
 pub struct ToCollectionId;
 
+#[td_type::typed(id, try_from = FunctionVersionId)]
+pub struct ToFunctionVersionId;
+
 #[td_type::typed(id)]
 pub struct TransactionId;
 
@@ -137,5 +163,11 @@ impl UserId {
 #[td_type::typed(id)]
 pub struct UserRoleId;
 
+#[td_type::typed(id)]
+pub struct WebhookDeliveryId;
+
+#[td_type::typed(id)]
+pub struct WebhookId;
+
 #[td_type::typed(id)]
 pub struct WorkerId;
@@ -3,6 +3,7 @@
 //
 
 mod bool;
+mod f64;
 mod i16;
 mod i32;
 mod i64;
@@ -14,6 +15,7 @@ mod typed_enum;
 
 // Re-export the types so they are all accessible under basic::*
 pub use bool::*;
+pub use f64::*;
 pub use i16::*;
 pub use i32::*;
 pub use i64::*;
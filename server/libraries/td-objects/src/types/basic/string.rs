@@ -5,7 +5,7 @@
 use crate::dxo::table::TableDBRead;
 use crate::parse::{
     DATA_LOCATION_REGEX, parse_collection, parse_email, parse_entity, parse_execution,
-    parse_function, parse_role, parse_table, parse_user,
+    parse_function, parse_role, parse_table, parse_user, parse_webhook_url,
 };
 use std::fmt::Debug;
 use td_security::{ADMIN_USER, SEC_ADMIN_ROLE, SYS_ADMIN_ROLE, USER_ROLE};
@@ -13,9 +13,27 @@ use td_security::{ADMIN_USER, SEC_ADMIN_ROLE, SYS_ADMIN_ROLE, USER_ROLE};
 #[td_type::typed(string)]
 pub struct AccessToken;
 
+#[td_type::typed(string)]
+pub struct ApiKeyHash;
+
+#[td_type::typed(string(min_len = 1, max_len = 100))]
+pub struct ApiKeyName;
+
+#[td_type::typed(string(min_len = 8, max_len = 8))]
+pub struct ApiKeyPrefix;
+
+#[td_type::typed(string)]
+pub struct ApiKeyToken;
+
+#[td_type::typed(string)]
+pub struct AuditEntity;
+
 #[td_type::typed(string(default = "<unavailable>"))]
 pub struct BuildManifest;
 
+#[td_type::typed(string)]
+pub struct BuildTimestamp;
+
 #[td_type::typed(string)]
 pub struct BundleHash;
 
@@ -37,9 +55,15 @@ pub struct Dot;
 #[td_type::typed(string(parser = parse_email))]
 pub struct Email;
 
+#[td_type::typed(string)]
+pub struct Edition;
+
 #[td_type::typed(string(parser = parse_entity))]
 pub struct EntityName;
 
+#[td_type::typed(string)]
+pub struct ErrorCode;
+
 #[td_type::typed(string)]
 pub struct ExecutionError;
 
@@ -52,11 +76,23 @@ pub struct FullName;
 #[td_type::typed(string(parser = parse_function))]
 pub struct FunctionName;
 
-// JSON blob with `version`, `envs` & `secrets` top entries.
+// JSON blob with `version`, `envs`, `secrets`, `limits` & `priority` top entries.
 // info used in decorator.
 #[td_type::typed(string(max_len = 4096, default = "{}"))]
 pub struct FunctionRuntimeValues;
 
+#[td_type::typed(string(min_len = 1, max_len = 100))]
+pub struct FunctionTag;
+
+#[td_type::typed(string)]
+pub struct GitCommit;
+
+#[td_type::typed(string(min_len = 1, max_len = 255))]
+pub struct IdempotencyKey;
+
+#[td_type::typed(string)]
+pub struct ImportConflict;
+
 #[td_type::typed(string)]
 pub struct LikeFilter;
 
@@ -90,6 +126,15 @@ pub struct PythonVersion;
 #[td_type::typed(string)]
 pub struct RefreshToken;
 
+#[td_type::typed(string)]
+pub struct RequestHash;
+
+#[td_type::typed(string(min_len = 1, max_len = 255))]
+pub struct RequestId;
+
+#[td_type::typed(string)]
+pub struct ResponseBody;
+
 #[td_type::typed(string(parser = parse_role))]
 pub struct RoleName;
 
@@ -116,6 +161,9 @@ pub struct SchemaFieldType;
 #[td_type::typed(string)]
 pub struct SchemaHash;
 
+#[td_type::typed(string)]
+pub struct ServiceName;
+
 #[td_type::typed(string(min_len = 0, max_len = 4096))]
 pub struct Snippet;
 
@@ -175,3 +223,15 @@ impl UserName {
         Self(ADMIN_USER.to_string())
     }
 }
+
+#[td_type::typed(string)]
+pub struct WebhookDeliveryError;
+
+#[td_type::typed(string)]
+pub struct WebhookPayload;
+
+#[td_type::typed(string(min_len = 16, max_len = 255))]
+pub struct WebhookSecret;
+
+#[td_type::typed(string(parser = parse_webhook_url))]
+pub struct WebhookUrl;
@@ -4,6 +4,34 @@
 
 use td_common::execution_status::WorkerCallbackStatus;
 
+#[td_type::typed_enum]
+pub enum ApiKeyStatus {
+    #[typed_enum(rename = "A")]
+    Active,
+    #[typed_enum(rename = "R")]
+    Revoked,
+}
+
+/// Kind of change of an audited service.
+#[td_type::typed_enum]
+pub enum AuditAction {
+    #[typed_enum(rename = "C")]
+    Create,
+    #[typed_enum(rename = "U")]
+    Update,
+    #[typed_enum(rename = "D")]
+    Delete,
+}
+
+/// Outcome of an audited service.
+#[td_type::typed_enum]
+pub enum AuditOutcome {
+    #[typed_enum(rename = "S")]
+    Success,
+    #[typed_enum(rename = "F")]
+    Failure,
+}
+
 #[td_type::typed_enum]
 pub enum Decorator {
     #[typed_enum(rename = "P")]
@@ -188,6 +216,15 @@ pub enum SessionStatus {
     InvalidUserDisabled,
 }
 
+/// Source language of a function snippet, for display purposes.
+#[td_type::typed_enum]
+pub enum SnippetLanguage {
+    #[typed_enum(rename = "python")]
+    Python,
+    #[typed_enum(rename = "text")]
+    Text,
+}
+
 #[td_type::typed_enum]
 pub enum TableStatus {
     #[typed_enum(rename = "A")]
@@ -198,6 +235,17 @@ pub enum TableStatus {
     Deleted,
 }
 
+/// How the tags of a tag filter are matched, functions with any or with all of them.
+#[td_type::typed_enum]
+#[derive(Default)]
+pub enum TagMatch {
+    #[default]
+    #[typed_enum(rename = "any")]
+    Any,
+    #[typed_enum(rename = "all")]
+    All,
+}
+
 /// Represents the status of a transaction. Note transactions are atomic status wise.
 /// So final status (e.g., Committed, Canceled, Yanked) means all function runs within the transaction
 /// do have the same status.
@@ -247,6 +295,16 @@ pub enum TriggerStatus {
     Deleted,
 }
 
+#[td_type::typed_enum]
+pub enum WebhookDeliveryStatus {
+    #[typed_enum(rename = "P")]
+    Pending,
+    #[typed_enum(rename = "D")]
+    Delivered,
+    #[typed_enum(rename = "L")]
+    DeadLetter,
+}
+
 #[td_type::typed_enum]
 pub enum WorkerMessageStatus {
     #[typed_enum(rename = "L")]
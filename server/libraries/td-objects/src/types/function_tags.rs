@@ -0,0 +1,89 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::dxo::function_tag::FunctionTagDB;
+use crate::rest_urls::params::FunctionListName;
+use crate::sql::ListFilterGenerator;
+use crate::types::DataAccessObject;
+use crate::types::basic::{FunctionId, FunctionTag, TagMatch};
+use itertools::Itertools;
+use sqlx::{QueryBuilder, Sqlite};
+use td_error::TdError;
+
+/// Filters listed functions by their tags, keeping the functions with any or with all of the
+/// tags. It does not filter if there are no tags.
+///
+/// Tags are not versioned, functions listed at a past time are filtered by their current tags.
+#[derive(Debug, Clone)]
+pub struct FunctionTagsFilter {
+    tags: Vec<FunctionTag>,
+    tag_match: TagMatch,
+}
+
+impl FunctionTagsFilter {
+    pub fn new(tags: Vec<FunctionTag>, tag_match: TagMatch) -> Self {
+        // duplicated tags would not be matched by ALL, as tags are counted
+        let tags = tags.into_iter().unique().collect();
+        Self { tags, tag_match }
+    }
+
+    pub fn tags(&self) -> &[FunctionTag] {
+        &self.tags
+    }
+
+    pub fn tag_match(&self) -> &TagMatch {
+        &self.tag_match
+    }
+}
+
+impl TryFrom<&FunctionListName> for FunctionTagsFilter {
+    type Error = TdError;
+
+    fn try_from(name: &FunctionListName) -> Result<Self, TdError> {
+        Ok(Self::new(
+            Vec::<FunctionTag>::from(name),
+            TagMatch::from(name),
+        ))
+    }
+}
+
+impl ListFilterGenerator for FunctionTagsFilter {
+    fn where_clause<'a, D: DataAccessObject>(
+        &'a self,
+        with_where: bool,
+        query_builder: &mut QueryBuilder<'a, Sqlite>,
+    ) -> Result<bool, TdError> {
+        if self.tags.is_empty() {
+            return Ok(with_where);
+        }
+
+        let mut with_where = with_where;
+        if with_where {
+            query_builder.push(" AND ");
+        } else {
+            query_builder.push(" WHERE ");
+            with_where = true;
+        }
+
+        let field = D::sql_field_for_type(std::any::TypeId::of::<FunctionId>())?;
+        query_builder.push(format!(
+            "{field} IN (SELECT function_id FROM {} WHERE tag IN (",
+            FunctionTagDB::sql_table()
+        ));
+        let mut separated = query_builder.separated(", ");
+        for tag in &self.tags {
+            separated.push_bind(tag);
+        }
+        query_builder.push(")");
+        if matches!(self.tag_match, TagMatch::All) {
+            query_builder.push(format!(
+                " GROUP BY function_id HAVING COUNT(tag) = {}",
+                self.tags.len()
+            ));
+        }
+        query_builder.push(")");
+
+        Ok(with_where)
+    }
+}
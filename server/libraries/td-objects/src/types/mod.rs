@@ -10,6 +10,7 @@ pub mod basic;
 
 pub mod addresses;
 pub mod composed;
+pub mod function_tags;
 pub mod option;
 pub mod status_count;
 pub mod visible_collections;
@@ -42,6 +43,11 @@ pub trait SqlEntity: Debug + Send + Sync {
     fn type_id(&self) -> TypeId {
         self.as_any().type_id()
     }
+
+    /// If it is a timestamp, which can be given as a relative expression (e.g. `-7d`) in filters.
+    fn is_timestamp(&self) -> bool {
+        false
+    }
 }
 
 impl<T> SqlEntity for &T
@@ -73,6 +79,10 @@ where
     fn as_any(&self) -> &dyn std::any::Any {
         (**self).as_any()
     }
+
+    fn is_timestamp(&self) -> bool {
+        (**self).is_timestamp()
+    }
 }
 
 pub trait AsDynSqlEntities: Send + Sync {
@@ -150,6 +160,8 @@ pub trait DataAccessObject:
     fn order_by() -> &'static str;
     fn fields() -> &'static [&'static str];
     fn immutable_fields() -> &'static [&'static str];
+    /// Fields used in inserts and updates, excluding `#[dao(read_only)]` ones maintained by the DB.
+    fn writable_fields() -> &'static [&'static str];
     fn sql_field_for_type(type_id: TypeId) -> Result<&'static str, td_error::TdError>;
     fn values_query_builder(
         &self,
@@ -197,6 +209,8 @@ pub trait ListQuery: DataTransferObject + Clone + Send + Sync {
     fn order_by_str_value(&self, ordered_by_field: &Option<String>) -> Option<String>;
     fn filter_by_fields() -> &'static [&'static str];
     fn filter_by_like_fields() -> &'static [&'static str];
+    /// Fields that can be selected to be included in the list result items.
+    fn select_fields() -> &'static [&'static str];
 }
 
 pub trait Extractor<T> {
@@ -48,4 +48,8 @@ where
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn is_timestamp(&self) -> bool {
+        self.as_ref().is_some_and(T::is_timestamp)
+    }
 }
@@ -3,6 +3,7 @@
 //
 
 mod test_id_name;
+mod test_query_param;
 mod test_td_types;
 mod test_type_builder;
 mod test_typed_types;
@@ -0,0 +1,33 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+#[cfg(test)]
+mod tests {
+    #[td_type::QueryParam]
+    struct TagsParam {
+        tag: Vec<String>,
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_repeated_key() {
+        let param: TagsParam = serde_html_form::from_str("tag=a&tag=b&name=foo").unwrap();
+        assert_eq!(param.tag, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(param.name, Some("foo".to_string()));
+    }
+
+    #[test]
+    fn test_absent_keys() {
+        let param: TagsParam = serde_html_form::from_str("").unwrap();
+        assert!(param.tag.is_empty());
+        assert_eq!(param.name, None);
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let param = TagsParam::builder().build().unwrap();
+        assert!(param.tag.is_empty());
+        assert_eq!(param.name, None);
+    }
+}
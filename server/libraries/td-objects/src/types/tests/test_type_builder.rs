@@ -66,6 +66,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dto_field_defaults() -> Result<(), serde_json::Error> {
+        #[Dto]
+        struct DefaultsDto {
+            name: String,
+            #[dto(default = "\"standard\"")]
+            kind: String,
+            #[dto(default = "10")]
+            len: i64,
+        }
+
+        let dto: DefaultsDto = serde_json::from_str(r#"{"name":"dto"}"#)?;
+        assert_eq!(dto.name, "dto");
+        assert_eq!(dto.kind, "standard");
+        assert_eq!(dto.len, 10);
+
+        let dto: DefaultsDto = serde_json::from_str(r#"{"name":"dto","kind":"custom","len":5}"#)?;
+        assert_eq!(dto.kind, "custom");
+        assert_eq!(dto.len, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dto_field_defaults_generic() -> Result<(), serde_json::Error> {
+        #[Dto]
+        struct GenericDefaultsDto<T: Clone> {
+            value: T,
+            #[dto(default = "10")]
+            len: i64,
+        }
+
+        let dto: GenericDefaultsDto<String> = serde_json::from_str(r#"{"value":"dto"}"#)?;
+        assert_eq!(dto.value, "dto");
+        assert_eq!(dto.len, 10);
+        Ok(())
+    }
+
     #[test]
     fn test_dlo() -> Result<(), td_error::TdError> {
         #[Dlo]
@@ -81,6 +118,61 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dlo_flatten() -> Result<(), td_error::TdError> {
+        #[Dlo]
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct ChildDlo {
+            id: i64,
+            description: Option<String>,
+        }
+
+        #[Dlo]
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct ParentDlo {
+            name: String,
+            #[dlo(flatten)]
+            child: ChildDlo,
+        }
+
+        let parent = ParentDlo::builder()
+            .name("parent")
+            .with_child(|child| child.id(1234).description(Some("desc".to_string())))
+            .build()?;
+        assert_eq!(parent.child.id, 1234);
+
+        let json = serde_json::to_value(&parent)?;
+        assert_eq!(
+            json,
+            serde_json::json!({"name": "parent", "id": 1234, "description": "desc"})
+        );
+        let parent: ParentDlo = serde_json::from_value(json)?;
+        assert_eq!(parent.name, "parent");
+        assert_eq!(parent.child.id, 1234);
+        assert_eq!(parent.child.description, Some("desc".to_string()));
+
+        // The nested DLO can be set as a whole too, and its fields are required.
+        let child = ChildDlo::builder()
+            .id(1)
+            .description(None::<String>)
+            .build()?;
+        let parent = ParentDlo::builder().name("parent").child(child).build()?;
+        assert_eq!(parent.child.id, 1);
+        let parent = parent
+            .to_builder()
+            .with_child(|child| child.id(2))
+            .build()?;
+        assert_eq!(parent.child.id, 2);
+        assert!(
+            ParentDlo::builder()
+                .name("parent")
+                .with_child(|child| child.id(1))
+                .build()
+                .is_err()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_to_builder() {
         let modified = chrono::DateTime::<chrono::Utc>::default();
@@ -358,6 +358,45 @@ mod tests {
         assert!(TypedString::parse("12345").is_ok());
     }
 
+    #[test]
+    fn test_string_pattern() {
+        #[td_type::typed(string(max_len = 5, pattern = "^[0-9]+$"))]
+        struct TypedString;
+
+        assert!(TypedString::parse("12345").is_ok());
+        assert!(matches!(
+            TypedString::parse("123456"),
+            Err(TypedStringError::MaxLen(_, 5))
+        ));
+        assert!(matches!(
+            TypedString::parse("12a"),
+            Err(TypedStringError::Regex(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_string_error_context() {
+        #[td_type::typed(string(max_len = 3))]
+        struct TypedName;
+
+        let err = TypedName::try_from("1234").unwrap_err();
+        assert!(err.to_string().contains("TypedName"));
+        assert!(err.to_string().contains("'1234'"));
+    }
+
+    #[test]
+    fn test_numeric_error_context() {
+        #[td_type::typed(i64(min = 0, max = 100))]
+        struct TypedPercent;
+
+        assert!(TypedPercent::try_from(0i64).is_ok());
+        assert!(TypedPercent::try_from(100i64).is_ok());
+        let err = TypedPercent::try_from(-1i64).unwrap_err();
+        assert!(err.to_string().contains("TypedPercent"));
+        let err = TypedPercent::try_from(101i64).unwrap_err();
+        assert!(err.to_string().contains("TypedPercent"));
+    }
+
     #[td_error]
     enum ParsingError {
         #[error("parse error: {0}, {1}")]
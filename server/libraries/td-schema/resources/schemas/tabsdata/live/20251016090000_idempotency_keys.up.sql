@@ -0,0 +1,19 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+CREATE TABLE idempotency_keys
+(
+    idempotency_key TEXT      NOT NULL,
+    user_id         TEXT      NOT NULL,
+    request_hash    TEXT      NOT NULL,
+    -- NULL while the request holding the key is in progress
+    response_status INTEGER,
+    response_body   TEXT,
+    created_on      TIMESTAMP NOT NULL,
+    expires_on      TIMESTAMP NOT NULL,
+
+    PRIMARY KEY (idempotency_key, user_id)
+);
+
+CREATE INDEX idempotency_keys___expires_on___idx ON idempotency_keys (expires_on);
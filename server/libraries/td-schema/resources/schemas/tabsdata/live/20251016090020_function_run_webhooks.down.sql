@@ -0,0 +1,7 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+DROP VIEW function_run_webhook_deliveries__to_deliver;
+DROP TABLE function_run_webhook_deliveries;
+DROP TABLE function_run_webhooks;
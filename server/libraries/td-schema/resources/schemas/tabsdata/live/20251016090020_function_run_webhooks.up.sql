@@ -0,0 +1,49 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- Function run webhooks (table)
+
+CREATE TABLE function_run_webhooks
+(
+    id            TEXT PRIMARY KEY,
+    collection_id TEXT      NOT NULL,
+    function_id   TEXT      NULL, -- NULL for all the functions of the collection
+    url           TEXT      NOT NULL,
+    secret        TEXT      NOT NULL,
+    created_on    TIMESTAMP NOT NULL,
+    created_by_id TEXT      NOT NULL,
+
+    FOREIGN KEY (collection_id) REFERENCES collections (id)
+);
+
+CREATE INDEX function_run_webhooks___collection_id___idx ON function_run_webhooks (collection_id);
+
+-- Function run webhook deliveries (table & __to_deliver view)
+
+CREATE TABLE function_run_webhook_deliveries
+(
+    id              TEXT PRIMARY KEY,
+    webhook_id      TEXT      NOT NULL,
+    function_run_id TEXT      NOT NULL,
+    payload         TEXT      NOT NULL,
+    status          TEXT      NOT NULL, -- P (pending), D (delivered), L (dead letter)
+    attempts        INTEGER   NOT NULL,
+    next_attempt_on TIMESTAMP NOT NULL,
+    last_error      TEXT      NULL,
+    created_on      TIMESTAMP NOT NULL,
+    delivered_on    TIMESTAMP NULL,
+
+    FOREIGN KEY (webhook_id) REFERENCES function_run_webhooks (id) ON DELETE CASCADE,
+    FOREIGN KEY (function_run_id) REFERENCES function_runs (id)
+);
+
+CREATE INDEX function_run_webhook_deliveries___status___idx ON function_run_webhook_deliveries (status);
+
+CREATE VIEW function_run_webhook_deliveries__to_deliver AS
+SELECT d.*,
+       w.url    AS url,
+       w.secret AS secret
+FROM function_run_webhook_deliveries d
+         JOIN function_run_webhooks w ON d.webhook_id = w.id
+WHERE d.status = 'P';
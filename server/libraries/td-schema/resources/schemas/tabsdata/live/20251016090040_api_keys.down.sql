@@ -0,0 +1,6 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+DROP VIEW api_keys__with_names;
+DROP TABLE api_keys;
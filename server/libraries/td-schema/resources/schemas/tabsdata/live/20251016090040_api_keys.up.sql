@@ -0,0 +1,36 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- API keys (table & __with_names view)
+
+CREATE TABLE api_keys
+(
+    id               TEXT PRIMARY KEY,
+    name             TEXT        NOT NULL,
+    prefix           TEXT UNIQUE NOT NULL,
+    hash             TEXT        NOT NULL, -- SHA-256 of the key, the key itself is never stored
+    user_id          TEXT        NOT NULL,
+    role_id          TEXT        NOT NULL,
+    created_on       TIMESTAMP   NOT NULL,
+    status           TEXT        NOT NULL, -- A (active), R (revoked)
+    status_change_on TIMESTAMP   NOT NULL,
+
+    FOREIGN KEY (user_id) REFERENCES users (id),
+    FOREIGN KEY (role_id) REFERENCES roles (id)
+);
+
+CREATE INDEX api_keys___user_id___idx ON api_keys (user_id);
+
+CREATE VIEW api_keys__with_names AS
+SELECT k.*,
+       u.name    AS user_name,
+       u.enabled AS user_enabled,
+       r.name    AS role_name,
+       EXISTS (SELECT 1
+               FROM users_roles ur
+               WHERE ur.user_id = k.user_id
+                 AND ur.role_id = k.role_id) AS user_has_role -- the role may be removed from the user
+FROM api_keys k
+         JOIN users u ON k.user_id = u.id
+         JOIN roles r ON k.role_id = r.id;
@@ -0,0 +1,7 @@
+--
+--  Copyright 2025 Tabs Data Inc.
+--
+
+UPDATE tabsdata_system
+SET value = '5'
+WHERE name = 'db_version';
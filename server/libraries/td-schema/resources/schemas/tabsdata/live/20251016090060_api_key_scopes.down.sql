@@ -0,0 +1,8 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+DROP TABLE api_key_scopes;
+
+ALTER TABLE api_keys
+    DROP COLUMN scoped;
@@ -0,0 +1,23 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- API key scopes, the subset of the role permissions an API key is restricted to.
+-- Only API keys not marked as scoped have all the permissions of their role, a scoped API key
+-- without scopes has no permissions.
+
+ALTER TABLE api_keys
+    ADD COLUMN scoped BOOLEAN NOT NULL DEFAULT false;
+
+CREATE TABLE api_key_scopes
+(
+    id              TEXT PRIMARY KEY,
+    api_key_id      TEXT NOT NULL,
+    permission_type TEXT NOT NULL, -- same as permissions.permission_type
+    entity_type     TEXT NOT NULL, -- same as permissions.entity_type
+    entity_id       TEXT NOT NULL, -- 00000000000000000000000204 means ALL
+
+    FOREIGN KEY (api_key_id) REFERENCES api_keys (id) ON DELETE CASCADE
+);
+
+CREATE INDEX api_key_scopes___api_key_id___idx ON api_key_scopes (api_key_id);
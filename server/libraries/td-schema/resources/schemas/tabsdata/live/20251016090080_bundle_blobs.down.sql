@@ -0,0 +1,5 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+DROP TABLE bundle_blobs;
@@ -0,0 +1,22 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- Function bundle blobs, the stored content of the function bundles.
+-- Bundles with identical content in a collection share one blob, stored at the location of the
+-- bundle that first uploaded it. The blob is released once no function version uses it, and
+-- purged, from the database and from storage, afterwards by the scheduler.
+
+CREATE TABLE bundle_blobs
+(
+    id              TEXT PRIMARY KEY, -- bundle the blob is stored for
+    collection_id   TEXT    NOT NULL,
+    hash            TEXT    NOT NULL,
+    ref_count       INTEGER NOT NULL, -- current function versions using the blob
+    data_location   TEXT    NOT NULL,
+    storage_version TEXT    NOT NULL,
+
+    FOREIGN KEY (collection_id) REFERENCES collections (id)
+);
+
+CREATE UNIQUE INDEX bundle_blobs___collection_id__hash___idx ON bundle_blobs (collection_id, hash);
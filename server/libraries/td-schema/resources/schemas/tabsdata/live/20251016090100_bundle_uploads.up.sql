@@ -0,0 +1,19 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- Chunked function bundle uploads, pending to be committed.
+-- The chunks are stored as they are uploaded, and assembled into the bundle on commit.
+
+CREATE TABLE bundle_uploads
+(
+    id            TEXT PRIMARY KEY,
+    collection_id TEXT      NOT NULL,
+    created_on    TIMESTAMP NOT NULL,
+    created_by_id TEXT      NOT NULL,
+    expires_on    TIMESTAMP NOT NULL,
+
+    FOREIGN KEY (collection_id) REFERENCES collections (id)
+);
+
+CREATE INDEX bundle_uploads___expires_on___idx ON bundle_uploads (expires_on);
@@ -0,0 +1,12 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- Permission changes, the latest change ID is the token nodes poll to invalidate their
+-- authorization caches when permissions are changed by other nodes.
+
+CREATE TABLE permission_changes
+(
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    changed_on TIMESTAMP NOT NULL
+);
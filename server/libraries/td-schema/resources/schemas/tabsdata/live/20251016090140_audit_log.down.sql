@@ -0,0 +1,8 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+DROP TRIGGER audit_log_no_delete;
+DROP TRIGGER audit_log_no_update;
+DROP INDEX audit_log_user_id_idx;
+DROP TABLE audit_log;
@@ -0,0 +1,38 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- Audit log of the services changing entities. Changes are written in the transaction of the
+-- change, failures once it is rolled back. The user and role are not known for services without
+-- a session (password changes), the entity is not known for failures. It is append only, updates
+-- and deletes are rejected so it is a tamper-evident record of who changed what.
+
+CREATE TABLE audit_log
+(
+    id          TEXT PRIMARY KEY,
+    request_id  TEXT,
+    user_id     TEXT,
+    role_id     TEXT,
+    action      TEXT      NOT NULL,
+    service     TEXT      NOT NULL,
+    entity      TEXT,
+    outcome     TEXT      NOT NULL,
+    error_code  TEXT,
+    audited_on  TIMESTAMP NOT NULL
+);
+
+CREATE INDEX audit_log_user_id_idx ON audit_log (user_id, audited_on);
+
+CREATE TRIGGER audit_log_no_update
+    BEFORE UPDATE
+    ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append only');
+END;
+
+CREATE TRIGGER audit_log_no_delete
+    BEFORE DELETE
+    ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append only');
+END;
@@ -0,0 +1,6 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+DROP INDEX function_tags___tag___idx;
+DROP TABLE function_tags;
@@ -0,0 +1,16 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- Free-form tags of functions, to organize them and list them by tag.
+-- Tags are kept by function id, so they carry over across the function versions.
+
+CREATE TABLE function_tags
+(
+    function_id TEXT NOT NULL,
+    tag         TEXT NOT NULL,
+
+    PRIMARY KEY (function_id, tag)
+);
+
+CREATE INDEX function_tags___tag___idx ON function_tags (tag);
@@ -0,0 +1,19 @@
+--
+-- Copyright 2025 Tabs Data Inc.
+--
+
+-- Basic statistics of the columns of table data versions, as reported by the worker when
+-- writing the table. Min and max values are only available for numeric columns.
+
+CREATE TABLE table_stats
+(
+    table_data_version_id TEXT    NOT NULL,
+    column_name           TEXT    NOT NULL,
+    column_pos            INTEGER NOT NULL,
+    row_count             INTEGER NOT NULL,
+    null_count            INTEGER NOT NULL,
+    min_value             REAL,
+    max_value             REAL,
+
+    PRIMARY KEY (table_data_version_id, column_name)
+);
@@ -5,6 +5,16 @@
 mod base;
 mod v1;
 mod v2;
+mod v3;
+mod v4;
+mod v5;
+mod v6;
+mod v7;
+mod v8;
+mod v9;
+mod v10;
+mod v11;
+mod v12;
 
 use sqlx::migrate::Migrator;
 use sqlx::sqlite::SqliteRow;
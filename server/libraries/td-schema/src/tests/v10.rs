@@ -0,0 +1,52 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::tests::run_migration_test;
+use sqlx::SqlitePool;
+
+#[tokio::test]
+async fn test_audit_log() {
+    let target_version = 10;
+
+    async fn tables(pool: &SqlitePool) -> Vec<String> {
+        let tables: Vec<(String,)> = sqlx::query_as(
+            "SELECT name FROM sqlite_master WHERE type IN ('table', 'view') AND name LIKE 'audit_log%' ORDER BY name",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        tables.into_iter().map(|(name,)| name).collect()
+    }
+
+    async fn pre_migration(pool: &SqlitePool) {
+        assert!(
+            tables(pool).await.is_empty(),
+            "Did not expect audit log table before migration"
+        );
+    }
+
+    async fn post_migration(pool: &SqlitePool) {
+        assert_eq!(
+            tables(pool).await,
+            vec!["audit_log"],
+            "Expected audit log table after migration"
+        );
+
+        // the audit log is append only
+        sqlx::query(
+            "INSERT INTO audit_log VALUES ('a', NULL, 'u', 'r', 'C', 's', 'e', 'S', NULL, '2025-01-01')",
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        let res = sqlx::query("UPDATE audit_log SET outcome = 'F'")
+            .execute(pool)
+            .await;
+        assert!(res.is_err(), "Expected audit log updates to be rejected");
+        let res = sqlx::query("DELETE FROM audit_log").execute(pool).await;
+        assert!(res.is_err(), "Expected audit log deletes to be rejected");
+    }
+
+    run_migration_test(target_version, pre_migration, post_migration).await;
+}
@@ -0,0 +1,38 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::tests::run_migration_test;
+use sqlx::SqlitePool;
+
+#[tokio::test]
+async fn test_function_tags() {
+    let target_version = 11;
+
+    async fn tables(pool: &SqlitePool) -> Vec<String> {
+        let tables: Vec<(String,)> = sqlx::query_as(
+            "SELECT name FROM sqlite_master WHERE type IN ('table', 'view') AND name LIKE 'function_tags%' ORDER BY name",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        tables.into_iter().map(|(name,)| name).collect()
+    }
+
+    async fn pre_migration(pool: &SqlitePool) {
+        assert!(
+            tables(pool).await.is_empty(),
+            "Did not expect function tags table before migration"
+        );
+    }
+
+    async fn post_migration(pool: &SqlitePool) {
+        assert_eq!(
+            tables(pool).await,
+            vec!["function_tags"],
+            "Expected function tags table after migration"
+        );
+    }
+
+    run_migration_test(target_version, pre_migration, post_migration).await;
+}
@@ -0,0 +1,37 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::tests::run_migration_test;
+use sqlx::SqlitePool;
+
+#[tokio::test]
+async fn test_idempotency_keys() {
+    let target_version = 3;
+
+    async fn table_exists(pool: &SqlitePool) -> bool {
+        let tables: Vec<(String,)> = sqlx::query_as(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'idempotency_keys'",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap();
+        !tables.is_empty()
+    }
+
+    async fn pre_migration(pool: &SqlitePool) {
+        assert!(
+            !table_exists(pool).await,
+            "Did not expect 'idempotency_keys' table before migration"
+        );
+    }
+
+    async fn post_migration(pool: &SqlitePool) {
+        assert!(
+            table_exists(pool).await,
+            "Expected 'idempotency_keys' table after migration"
+        );
+    }
+
+    run_migration_test(target_version, pre_migration, post_migration).await;
+}
@@ -2,7 +2,8 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use argon2::{Algorithm, Argon2, Params, PasswordHasher, Version};
+use crate::password::{Argon2PasswordHasher, PasswordHasher, Pbkdf2PasswordHasher};
+use argon2::{Algorithm, Argon2, Params, Version};
 use derive_builder::Builder;
 use getset::Getters;
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,16 @@ pub struct PasswordHashingConfig {
     time_cost: usize,
     parallelism_cost: usize,
     kdf_len: usize,
+    /// PBKDF2 iterations, used only by the `pbkdf2-sha256` algorithm.
+    #[serde(default = "default_iterations")]
+    iterations: usize,
+}
+
+/// PBKDF2-HMAC-SHA256 iterations recommended by OWASP.
+const DEFAULT_PBKDF2_ITERATIONS: usize = 600_000;
+
+fn default_iterations() -> usize {
+    DEFAULT_PBKDF2_ITERATIONS
 }
 
 impl PasswordHashingConfig {
@@ -37,15 +48,25 @@ impl Default for PasswordHashingConfig {
             time_cost: 2,
             parallelism_cost: 1,
             kdf_len: 32,
+            iterations: DEFAULT_PBKDF2_ITERATIONS,
         }
     }
 }
 
 impl PasswordHashingConfig {
-    pub fn password_hasher(&self) -> impl PasswordHasher + use<> {
-        Argon2::new(
+    /// Algorithm selecting the PBKDF2 password hasher, for FIPS-constrained deployments.
+    pub const PBKDF2_SHA256: &'static str = "pbkdf2-sha256";
+
+    pub fn password_hasher(&self) -> Box<dyn PasswordHasher> {
+        if self.algorithm == Self::PBKDF2_SHA256 {
+            return Box::new(Pbkdf2PasswordHasher::new(
+                self.iterations as u32,
+                self.kdf_len,
+            ));
+        }
+        Box::new(Argon2PasswordHasher::new(Argon2::new(
             Algorithm::from_str(&self.algorithm)
-                .expect("Invalid configuration: unknown password hashing algorithm. Valid values: argon2d, argon2i, argon2id (default)"),
+                .expect("Invalid configuration: unknown password hashing algorithm. Valid values: argon2d, argon2i, argon2id (default), pbkdf2-sha256"),
             Version::try_from(self.version as u32)
                 .expect("Invalid configuration: unknown password hashing version. Valid values: 16, 19 (default)"),
             Params::new(
@@ -55,7 +76,7 @@ impl PasswordHashingConfig {
                 None,
             )
             .unwrap(),
-        )
+        )))
     }
 }
 
@@ -72,5 +93,6 @@ mod tests {
         assert_eq!(*config.time_cost(), 2);
         assert_eq!(*config.parallelism_cost(), 1);
         assert_eq!(*config.kdf_len(), 32);
+        assert_eq!(*config.iterations(), 600_000);
     }
 }
@@ -3,9 +3,10 @@
 //
 
 use crate::config::PasswordHashingConfig;
-use argon2::password_hash::SaltString;
+use argon2::Argon2;
 use argon2::password_hash::rand_core::OsRng;
-use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+use argon2::password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier as _, SaltString};
+use pbkdf2::Pbkdf2;
 use td_error::{TdError, td_error};
 
 #[td_error]
@@ -14,14 +15,126 @@ pub enum Error {
     PasswordLengthViolation(usize),
 }
 
-// Verifies the password hash.
+/// Algorithms passwords can be hashed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordHashAlgorithm {
+    Argon2,
+    /// PBKDF2-HMAC-SHA256, for FIPS-constrained deployments.
+    Pbkdf2,
+}
+
+impl PasswordHashAlgorithm {
+    /// Returns the algorithm a PHC string was hashed with, from its `$<id>$` prefix.
+    pub fn of(phc_str: &str) -> Option<Self> {
+        let id = phc_str.strip_prefix('$')?.split('$').next()?;
+        if id.starts_with("argon2") {
+            Some(PasswordHashAlgorithm::Argon2)
+        } else if id.starts_with("pbkdf2") {
+            Some(PasswordHashAlgorithm::Pbkdf2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Hashes passwords into PHC strings, and verifies passwords against them.
+pub trait PasswordHasher: Send + Sync {
+    fn algorithm(&self) -> PasswordHashAlgorithm;
+
+    fn hash(&self, password: &str) -> String;
+
+    /// Verifies the password against a PHC string hashed with this hasher's algorithm, the
+    /// parameters encoded in the PHC string are used to verify it.
+    fn verify(&self, phc_str: &str, password: &str) -> bool;
+}
+
+#[derive(Default)]
+pub struct Argon2PasswordHasher(Argon2<'static>);
+
+impl Argon2PasswordHasher {
+    pub fn new(argon2: Argon2<'static>) -> Self {
+        Self(argon2)
+    }
+}
+
+impl PasswordHasher for Argon2PasswordHasher {
+    fn algorithm(&self) -> PasswordHashAlgorithm {
+        PasswordHashAlgorithm::Argon2
+    }
+
+    fn hash(&self, password: &str) -> String {
+        self.0
+            .hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string()
+    }
+
+    fn verify(&self, phc_str: &str, password: &str) -> bool {
+        match PasswordHash::new(phc_str) {
+            Ok(parsed_hash) => self
+                .0
+                .verify_password(password.as_bytes(), &parsed_hash)
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+pub struct Pbkdf2PasswordHasher(pbkdf2::Params);
+
+impl Pbkdf2PasswordHasher {
+    pub fn new(rounds: u32, output_length: usize) -> Self {
+        Self(pbkdf2::Params {
+            rounds,
+            output_length,
+        })
+    }
+}
+
+impl Default for Pbkdf2PasswordHasher {
+    fn default() -> Self {
+        Self(pbkdf2::Params::default())
+    }
+}
+
+impl PasswordHasher for Pbkdf2PasswordHasher {
+    fn algorithm(&self) -> PasswordHashAlgorithm {
+        PasswordHashAlgorithm::Pbkdf2
+    }
+
+    fn hash(&self, password: &str) -> String {
+        Pbkdf2
+            .hash_password_customized(
+                password.as_bytes(),
+                Some(pbkdf2::Algorithm::Pbkdf2Sha256.ident()),
+                None,
+                self.0,
+                &SaltString::generate(&mut OsRng),
+            )
+            .unwrap()
+            .to_string()
+    }
+
+    fn verify(&self, phc_str: &str, password: &str) -> bool {
+        match PasswordHash::new(phc_str) {
+            Ok(parsed_hash) => Pbkdf2
+                .verify_password(password.as_bytes(), &parsed_hash)
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+// Verifies the password hash, with the algorithm it was hashed with.
 pub fn verify_password(phc_str: &str, password: &str) -> bool {
-    match PasswordHash::new(phc_str) {
-        // the values encode in the PHC string are used to configure the verifier
-        Ok(parsed_hash) => Argon2::default()
-            .verify_password(password.as_bytes(), &parsed_hash)
-            .is_ok(),
-        Err(_) => false,
+    match PasswordHashAlgorithm::of(phc_str) {
+        Some(PasswordHashAlgorithm::Argon2) => {
+            Argon2PasswordHasher::default().verify(phc_str, password)
+        }
+        Some(PasswordHashAlgorithm::Pbkdf2) => {
+            Pbkdf2PasswordHasher::default().verify(phc_str, password)
+        }
+        None => false,
     }
 }
 
@@ -37,17 +150,16 @@ pub fn create_password_hash(
     password: &str,
 ) -> String {
     // creates a PHC string
-    password_hashing_config
-        .password_hasher()
-        .hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
-        .unwrap()
-        .to_string()
+    password_hashing_config.password_hasher().hash(password)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::config::PasswordHashingConfig;
-    use crate::password::{create_password_hash, verify_password};
+    use crate::password::{
+        Argon2PasswordHasher, PasswordHashAlgorithm, PasswordHasher, Pbkdf2PasswordHasher,
+        create_password_hash, verify_password,
+    };
 
     #[test]
     fn test_password_hash_with_custom_config() {
@@ -72,4 +184,61 @@ mod tests {
         let hash = create_password_hash(&config, password);
         assert!(verify_password(&hash, password));
     }
+
+    #[test]
+    fn test_password_hash_with_pbkdf2_config() {
+        let config = PasswordHashingConfig::builder()
+            .algorithm("pbkdf2-sha256")
+            .iterations(1000usize)
+            .kdf_len(32usize)
+            .build()
+            .unwrap();
+        let password = "password";
+        let hash = create_password_hash(&config, password);
+        assert!(hash.starts_with("$pbkdf2-sha256$i=1000,l=32$"));
+        assert!(verify_password(&hash, password));
+        assert!(!verify_password(&hash, "wrong password"));
+    }
+
+    #[test]
+    fn test_password_hashers_verify_own_hashes() {
+        let hashers: [Box<dyn PasswordHasher>; 2] = [
+            Box::new(Argon2PasswordHasher::default()),
+            Box::new(Pbkdf2PasswordHasher::new(1000, 32)),
+        ];
+        for hasher in hashers {
+            let hash = hasher.hash("password");
+            assert!(hasher.verify(&hash, "password"));
+            assert!(!hasher.verify(&hash, "wrong password"));
+            assert_eq!(PasswordHashAlgorithm::of(&hash), Some(hasher.algorithm()));
+        }
+    }
+
+    #[test]
+    fn test_password_hash_algorithm_of_stored_hash() {
+        let argon2 = Argon2PasswordHasher::default().hash("password");
+        let pbkdf2 = Pbkdf2PasswordHasher::new(1000, 32).hash("password");
+        assert_eq!(
+            PasswordHashAlgorithm::of(&argon2),
+            Some(PasswordHashAlgorithm::Argon2)
+        );
+        assert_eq!(
+            PasswordHashAlgorithm::of(&pbkdf2),
+            Some(PasswordHashAlgorithm::Pbkdf2)
+        );
+        assert_eq!(
+            PasswordHashAlgorithm::of("$scrypt$ln=16,r=8,p=1$salt$hash"),
+            None
+        );
+        assert_eq!(PasswordHashAlgorithm::of("password"), None);
+
+        // Stored hashes are verified with the algorithm they were hashed with.
+        assert!(verify_password(&argon2, "password"));
+        assert!(verify_password(&pbkdf2, "password"));
+        assert!(!Argon2PasswordHasher::default().verify(&pbkdf2, "password"));
+        assert!(!verify_password(
+            "$scrypt$ln=16,r=8,p=1$salt$hash",
+            "password"
+        ));
+    }
 }
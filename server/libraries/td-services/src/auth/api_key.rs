@@ -0,0 +1,151 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! API keys, long-lived credentials for service-to-service callers, sent as
+//! `Authorization: ApiKey <key>` instead of a JWT access token.
+//!
+//! Keys are `td_<prefix>_<secret>`. The prefix identifies the key, and only the SHA-256 hash of
+//! the whole key is stored, so keys cannot be recovered once issued.
+
+use crate::auth::AuthError;
+use ring::rand::{SecureRandom, SystemRandom};
+use sha2::{Digest, Sha256};
+use sqlx::SqliteConnection;
+use td_error::TdError;
+use td_objects::dxo::api_key::ApiKeyDBWithNames;
+use td_objects::dxo::crudl::handle_select_error;
+use td_objects::sql::{DaoQueries, SelectBy};
+use td_objects::types::basic::{ApiKeyHash, ApiKeyPrefix, ApiKeyStatus, ApiKeyToken};
+use tracing::debug;
+
+/// Authorization scheme of API keys.
+pub const API_KEY_SCHEME: &str = "ApiKey";
+
+const API_KEY_PREFIX: &str = "td";
+const API_KEY_PREFIX_BYTES: usize = 4;
+const API_KEY_SECRET_BYTES: usize = 24;
+
+fn random_hex(len: usize) -> Result<String, TdError> {
+    let mut bytes = vec![0u8; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| AuthError::InternalError("Could not generate API key".to_string()))?;
+    Ok(hex::encode(bytes))
+}
+
+/// Generates a new API key.
+pub fn generate_api_key() -> Result<ApiKeyToken, TdError> {
+    let prefix = random_hex(API_KEY_PREFIX_BYTES)?;
+    let secret = random_hex(API_KEY_SECRET_BYTES)?;
+    ApiKeyToken::try_from(format!("{API_KEY_PREFIX}_{prefix}_{secret}"))
+}
+
+/// Hash an API key is stored with.
+pub fn api_key_hash(api_key: &ApiKeyToken) -> Result<ApiKeyHash, TdError> {
+    let hash = Sha256::digest(api_key.as_bytes());
+    ApiKeyHash::try_from(hex::encode(&hash[..]))
+}
+
+/// Prefix of an API key, if it is well formed.
+pub fn api_key_prefix(api_key: &ApiKeyToken) -> Option<ApiKeyPrefix> {
+    let mut parts = api_key.split('_');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(API_KEY_PREFIX), Some(prefix), Some(secret), None) if !secret.is_empty() => {
+            ApiKeyPrefix::try_from(prefix).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Compares two hashes in constant time.
+fn hash_eq(a: &ApiKeyHash, b: &ApiKeyHash) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Returns the active API key matching the given key, for an enabled user still having the role
+/// the key was issued for.
+///
+/// Any failure, an unknown, revoked or mismatching key, a disabled user or a removed role, is an
+/// [`AuthError::AuthenticationFailed`].
+pub async fn authenticate_api_key(
+    queries: &DaoQueries,
+    conn: &mut SqliteConnection,
+    api_key: &ApiKeyToken,
+) -> Result<ApiKeyDBWithNames, TdError> {
+    let prefix = api_key_prefix(api_key).ok_or(AuthError::AuthenticationFailed)?;
+    let found: Option<ApiKeyDBWithNames> = queries
+        .select_by::<ApiKeyDBWithNames>(&prefix)?
+        .build_query_as()
+        .fetch_optional(conn)
+        .await
+        .map_err(handle_select_error)?;
+
+    let hash = api_key_hash(api_key)?;
+    match found {
+        Some(found)
+            if hash_eq(&found.hash, &hash)
+                && found.status == ApiKeyStatus::Active
+                && *found.user_enabled
+                && *found.user_has_role =>
+        {
+            Ok(found)
+        }
+        _ => {
+            debug!("API key with prefix {prefix} could not be authenticated");
+            Err(AuthError::AuthenticationFailed)?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_api_key() -> Result<(), TdError> {
+        let api_key = generate_api_key()?;
+        let prefix = api_key_prefix(&api_key).unwrap();
+        assert!(api_key.starts_with(&format!("td_{prefix}_")));
+
+        let other = generate_api_key()?;
+        assert_ne!(api_key_prefix(&other).unwrap(), prefix);
+        assert_ne!(other, api_key);
+        Ok(())
+    }
+
+    #[test]
+    fn test_api_key_hash() -> Result<(), TdError> {
+        let api_key = generate_api_key()?;
+        let hash = api_key_hash(&api_key)?;
+        assert_eq!(hash.len(), 64);
+        assert!(!hash.contains(api_key.as_str()));
+        assert!(hash_eq(&hash, &api_key_hash(&api_key)?));
+
+        let other = generate_api_key()?;
+        assert!(!hash_eq(&hash, &api_key_hash(&other)?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_api_key_prefix_malformed() -> Result<(), TdError> {
+        assert_eq!(api_key_prefix(&ApiKeyToken::try_from("secret")?), None);
+        assert_eq!(
+            api_key_prefix(&ApiKeyToken::try_from("xx_0123abcd_secret")?),
+            None
+        );
+        assert_eq!(
+            api_key_prefix(&ApiKeyToken::try_from("td_0123abcd_")?),
+            None
+        );
+        assert_eq!(
+            api_key_prefix(&ApiKeyToken::try_from("td_012_secret")?),
+            None
+        );
+        Ok(())
+    }
+}
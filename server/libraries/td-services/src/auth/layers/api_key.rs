@@ -0,0 +1,129 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::auth::AuthError;
+use crate::auth::api_key::{api_key_hash, api_key_prefix, generate_api_key};
+use std::ops::Deref;
+use td_authz::AuthzContext;
+use td_error::TdError;
+use td_objects::dxo::api_key::{
+    ApiKeyCreate, ApiKeyCreated, ApiKeyDB, ApiKeyDBBuilder, ApiKeyScopeDB,
+};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{RequestContext, handle_sql_err};
+use td_objects::sql::{DaoQueries, SelectBy};
+use td_objects::tower_service::authz::{AuthzContextT, Permission, scoped_permissions};
+use td_objects::types::basic::{ApiKeyScoped, ApiKeyToken, CollectionName, EntityId};
+use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
+
+pub async fn create_api_key() -> Result<ApiKeyToken, TdError> {
+    generate_api_key()
+}
+
+pub async fn update_api_key_db_builder(
+    Input(api_key): Input<ApiKeyToken>,
+    Input(create): Input<ApiKeyCreate>,
+    Input(builder): Input<ApiKeyDBBuilder>,
+) -> Result<ApiKeyDBBuilder, TdError> {
+    let prefix = api_key_prefix(&api_key)
+        .ok_or_else(|| AuthError::InternalError("Malformed API key".to_string()))?;
+
+    // only the hash of the key is stored
+    let mut builder = builder.deref().clone();
+    builder
+        .name(create.name.clone())
+        .prefix(prefix)
+        .hash(api_key_hash(&api_key)?)
+        .scoped(ApiKeyScoped::from(create.scope.is_some()));
+    Ok(builder)
+}
+
+/// Builds the scope of the API key, which must be within the permissions of the requester.
+///
+/// Keys issued with a scoped key must be scoped too, to a subset of the issuing key scope.
+pub async fn build_api_key_scopes(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(authz_context): SrvCtx<AuthzContext>,
+    Input(request_context): Input<RequestContext>,
+    Input(create): Input<ApiKeyCreate>,
+    Input(api_key_db): Input<ApiKeyDB>,
+) -> Result<Vec<ApiKeyScopeDB>, TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let requester_scope = authz_context
+        .access_token_permissions(conn, &request_context.access_token_id)
+        .await?;
+    let scope = match &create.scope {
+        Some(scope) if scope.is_empty() => Err(AuthError::EmptyApiKeyScope)?,
+        Some(scope) => scope,
+        None if requester_scope.is_some() => Err(AuthError::ApiKeyScopeNotGranted(
+            "all role permissions".to_string(),
+        ))?,
+        None => return Ok(vec![]),
+    };
+
+    let role_permissions = authz_context
+        .role_permissions(conn, &request_context.role_id)
+        .await?
+        .unwrap_or_default();
+    let granted = match &requester_scope {
+        Some(requester_scope) => scoped_permissions(&role_permissions, requester_scope),
+        None => role_permissions.to_vec(),
+    };
+
+    let mut scopes = vec![];
+    for scope_create in scope {
+        let permission_type = &scope_create.permission_type;
+        let entity_id = if let Some(entity_name) = &scope_create.entity_name {
+            let collection_name = CollectionName::try_from(entity_name.to_string())?;
+            let collection: CollectionDB = queries
+                .select_by::<CollectionDB>(&(&collection_name))?
+                .build_query_as()
+                .fetch_one(&mut *conn)
+                .await
+                .map_err(handle_sql_err)?;
+            EntityId::try_from(collection.id)?
+        } else {
+            EntityId::all_entities()
+        };
+
+        let permission = Permission::try_from_entity(permission_type, &entity_id)?;
+        if scoped_permissions(&granted, std::slice::from_ref(&permission)) != [permission] {
+            Err(AuthError::ApiKeyScopeNotGranted(format!(
+                "{permission_type} on {}",
+                scope_create
+                    .entity_name
+                    .as_ref()
+                    .map(|name| name.to_string())
+                    .unwrap_or_else(|| "all collections".to_string())
+            )))?
+        }
+
+        let scope_db = ApiKeyScopeDB::builder()
+            .api_key_id(api_key_db.id)
+            .permission_type(permission_type.clone())
+            .entity_type(permission_type.on_entity_type())
+            .entity_id(entity_id)
+            .build()?;
+        scopes.push(scope_db);
+    }
+    Ok(scopes)
+}
+
+pub async fn api_key_created(
+    Input(api_key_db): Input<ApiKeyDB>,
+    Input(api_key): Input<ApiKeyToken>,
+) -> Result<ApiKeyCreated, TdError> {
+    let created = ApiKeyCreated::builder()
+        .id(api_key_db.id)
+        .name(api_key_db.name.clone())
+        .prefix(api_key_db.prefix.clone())
+        .api_key(api_key.deref().clone())
+        .role_id(api_key_db.role_id)
+        .created_on(api_key_db.created_on)
+        .build()?;
+    Ok(created)
+}
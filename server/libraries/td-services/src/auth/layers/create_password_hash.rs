@@ -2,9 +2,6 @@
 // Copyright 2025. Tabs Data Inc.
 //
 
-use argon2::PasswordHasher;
-use argon2::password_hash::SaltString;
-use argon2::password_hash::rand_core::OsRng;
 use std::ops::Deref;
 use td_error::TdError;
 use td_objects::types::basic::PasswordHash;
@@ -17,12 +14,7 @@ pub async fn create_password_hash<P: Deref<Target = String>>(
 ) -> Result<PasswordHash, TdError> {
     let hash = password_hashing_config
         .password_hasher()
-        .hash_password(
-            password.deref().as_bytes(),
-            &SaltString::generate(&mut OsRng),
-        )
-        .unwrap()
-        .to_string();
+        .hash(password.deref());
 
     PasswordHash::try_from(hash)
 }
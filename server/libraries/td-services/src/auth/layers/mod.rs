@@ -2,6 +2,7 @@
 // Copyright 2025. Tabs Data Inc.
 //
 
+pub mod api_key;
 pub mod assert_current_password;
 pub mod assert_no_password_change_required;
 pub mod assert_user_enabled;
@@ -2,6 +2,7 @@
 // Copyright 2025. Tabs Data Inc.
 //
 
+pub mod api_key;
 pub mod jwt;
 pub mod layers;
 pub mod services;
@@ -11,6 +12,12 @@ use td_error::td_error;
 
 #[td_error]
 pub enum AuthError {
+    #[error("API key scope cannot be empty")]
+    EmptyApiKeyScope = 0,
+
+    #[error("API key scope not granted: {0}")]
+    ApiKeyScopeNotGranted(String) = 3000,
+
     #[error("Authentication failed")]
     AuthenticationFailed = 4000,
     #[error("Missing Authorization Header")]
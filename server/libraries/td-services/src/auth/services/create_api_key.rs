@@ -0,0 +1,283 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::auth::layers::api_key::{
+    api_key_created, build_api_key_scopes, create_api_key, update_api_key_db_builder,
+};
+use ta_services::factory::service_factory;
+use td_authz::{AuthzContext, refresh_authz_context};
+use td_objects::dxo::api_key::{
+    ApiKeyCreate, ApiKeyCreated, ApiKeyDB, ApiKeyDBBuilder, ApiKeyScopeDB,
+};
+use td_objects::dxo::crudl::{CreateRequest, RequestContext};
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::from::{
+    BuildService, ExtractDataService, ExtractService, UpdateService, With, builder,
+};
+use td_objects::tower_service::sql::{insert, insert_vec};
+use td_objects::types::basic::ApiKeyId;
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = CreateApiKeyService,
+    request = CreateRequest<(), ApiKeyCreate>,
+    response = ApiKeyCreated,
+    connection = TransactionProvider,
+    audit = ApiKeyId,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<CreateRequest<(), ApiKeyCreate>>::extract::<RequestContext>),
+        from_fn(With::<CreateRequest<(), ApiKeyCreate>>::extract_data::<ApiKeyCreate>),
+        // generate API key
+        from_fn(create_api_key),
+        // create API key DAO, for the requester user and role
+        from_fn(builder::<ApiKeyDBBuilder>),
+        from_fn(With::<RequestContext>::update::<ApiKeyDBBuilder, _>),
+        from_fn(update_api_key_db_builder),
+        from_fn(With::<ApiKeyDBBuilder>::build::<ApiKeyDB, _>),
+        // insert DAO in DB
+        from_fn(insert::<ApiKeyDB>),
+        from_fn(With::<ApiKeyDB>::extract::<ApiKeyId>),
+        // insert API key scope, if any, and refresh permissions
+        from_fn(build_api_key_scopes),
+        from_fn(insert_vec::<ApiKeyScopeDB>),
+        from_fn(refresh_authz_context),
+        // create DTO response, with the API key
+        from_fn(api_key_created),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthError;
+    use crate::auth::api_key::{api_key_hash, api_key_prefix};
+    use std::sync::Arc;
+    use ta_services::service::TdService;
+    use td_authz::Authz;
+    use td_database::sql::DbPool;
+    use td_error::{TdError, assert_service_error};
+    use td_objects::dxo::api_key::ApiKeyScopeCreate;
+    use td_objects::sql::SelectBy;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::tower_service::authz::{
+        AuthzEntity, AuthzError, AuthzRequirements, AuthzScope, CollDev, CollRead,
+    };
+    use td_objects::types::basic::{
+        AccessTokenId, ApiKeyName, ApiKeyStatus, CollectionId, CollectionName, EntityName,
+        PermissionType, RoleId, UserId,
+    };
+    use td_tower::ctx_service::RawOneshot;
+    use td_tower::extractors::{Connection, ConnectionType, Input, SrvCtx};
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_create_api_key(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        CreateApiKeyService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<CreateRequest<(), ApiKeyCreate>, ApiKeyCreated>(&[
+                type_of_val(&With::<CreateRequest<(), ApiKeyCreate>>::extract::<RequestContext>),
+                type_of_val(&With::<CreateRequest<(), ApiKeyCreate>>::extract_data::<ApiKeyCreate>),
+                // generate API key
+                type_of_val(&create_api_key),
+                // create API key DAO, for the requester user and role
+                type_of_val(&builder::<ApiKeyDBBuilder>),
+                type_of_val(&With::<RequestContext>::update::<ApiKeyDBBuilder, _>),
+                type_of_val(&update_api_key_db_builder),
+                type_of_val(&With::<ApiKeyDBBuilder>::build::<ApiKeyDB, _>),
+                // insert DAO in DB
+                type_of_val(&insert::<ApiKeyDB>),
+                type_of_val(&With::<ApiKeyDB>::extract::<ApiKeyId>),
+                // insert API key scope, if any, and refresh permissions
+                type_of_val(&build_api_key_scopes),
+                type_of_val(&insert_vec::<ApiKeyScopeDB>),
+                type_of_val(&refresh_authz_context),
+                // create DTO response, with the API key
+                type_of_val(&api_key_created),
+            ]);
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_create_api_key(db: DbPool) -> Result<(), TdError> {
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                (),
+                ApiKeyCreate::builder()
+                    .name(ApiKeyName::try_from("ci")?)
+                    .build()?,
+            );
+        let created = CreateApiKeyService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert_eq!(created.name.as_str(), "ci");
+        assert_eq!(created.role_id, RoleId::user());
+        assert_eq!(
+            api_key_prefix(&created.api_key),
+            Some(created.prefix.clone())
+        );
+
+        let stored: ApiKeyDB = DaoQueries::default()
+            .select_by::<ApiKeyDB>(&created.id)?
+            .build_query_as()
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(stored.user_id, UserId::admin());
+        assert_eq!(stored.role_id, RoleId::user());
+        assert_eq!(stored.prefix, created.prefix);
+        assert_eq!(stored.status, ApiKeyStatus::Active);
+
+        // only the hash of the key is stored
+        assert_eq!(stored.hash, api_key_hash(&created.api_key)?);
+        assert_ne!(stored.hash.as_str(), created.api_key.as_str());
+        assert!(!stored.hash.contains(created.api_key.as_str()));
+        Ok(())
+    }
+
+    fn read_only_scope(collection: &str) -> Result<Vec<ApiKeyScopeCreate>, TdError> {
+        Ok(vec![
+            ApiKeyScopeCreate::builder()
+                .permission_type(PermissionType::CollectionRead)
+                .entity_name(Some(EntityName::try_from(collection)?))
+                .build()?,
+        ])
+    }
+
+    async fn check<C: AuthzRequirements>(
+        db: &DbPool,
+        request_context: &RequestContext,
+        collection_id: &CollectionId,
+    ) -> Result<(), TdError> {
+        let conn = db.acquire().await.unwrap();
+        let conn = Connection::new(ConnectionType::PoolConnection(conn).into());
+        Authz::<C>::check(
+            SrvCtx(Arc::new(AuthzContext::default())),
+            conn,
+            Input(Arc::new(request_context.clone())),
+            Input(Arc::new(AuthzScope::Collection(AuthzEntity::On(
+                *collection_id,
+            )))),
+        )
+        .await
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_create_read_only_scoped_api_key(db: DbPool) -> Result<(), TdError> {
+        let collection =
+            seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+
+        // the user role can read and write in the collection
+        let context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        check::<CollRead>(&db, &context, &collection.id).await?;
+        check::<CollDev>(&db, &context, &collection.id).await?;
+
+        let request = context.create(
+            (),
+            ApiKeyCreate::builder()
+                .name(ApiKeyName::try_from("ci")?)
+                .scope(Some(read_only_scope("c0")?))
+                .build()?,
+        );
+        let created = CreateApiKeyService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        // the read-only key can read, but not write
+        let key_context = RequestContext::with(
+            AccessTokenId::try_from(created.id)?,
+            UserId::admin(),
+            RoleId::user(),
+        );
+        check::<CollRead>(&db, &key_context, &collection.id).await?;
+        let err = check::<CollDev>(&db, &key_context, &collection.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.domain_err::<AuthzError>(),
+            AuthzError::Forbidden(_)
+        ));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_create_scoped_api_key_not_granted(db: DbPool) -> Result<(), TdError> {
+        seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+        let context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+
+        // the user role has no system permissions
+        let service = CreateApiKeyService::with_defaults(db.clone())
+            .service()
+            .await;
+        let request = context.clone().create(
+            (),
+            ApiKeyCreate::builder()
+                .name(ApiKeyName::try_from("ci")?)
+                .scope(Some(vec![
+                    ApiKeyScopeCreate::builder()
+                        .permission_type(PermissionType::SysAdmin)
+                        .entity_name(None)
+                        .build()?,
+                ]))
+                .build()?,
+        );
+        assert_service_error(service, request, |err| match err {
+            AuthError::ApiKeyScopeNotGranted(_) => {}
+            other => panic!("Expected 'ApiKeyScopeNotGranted', got {other:?}"),
+        })
+        .await;
+
+        // a scoped key cannot issue keys with more permissions than its scope
+        let request = context.create(
+            (),
+            ApiKeyCreate::builder()
+                .name(ApiKeyName::try_from("ci")?)
+                .scope(Some(read_only_scope("c0")?))
+                .build()?,
+        );
+        let created = CreateApiKeyService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        let key_context = RequestContext::with(
+            AccessTokenId::try_from(created.id)?,
+            UserId::admin(),
+            RoleId::user(),
+        );
+
+        let service = CreateApiKeyService::with_defaults(db.clone())
+            .service()
+            .await;
+        let request = key_context.create(
+            (),
+            ApiKeyCreate::builder()
+                .name(ApiKeyName::try_from("ci-unscoped")?)
+                .build()?,
+        );
+        assert_service_error(service, request, |err| match err {
+            AuthError::ApiKeyScopeNotGranted(_) => {}
+            other => panic!("Expected 'ApiKeyScopeNotGranted', got {other:?}"),
+        })
+        .await;
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use ta_services::factory::service_factory;
+use td_objects::dxo::api_key::ApiKey;
+use td_objects::dxo::crudl::{ListRequest, ListResponse, RequestContext};
+use td_objects::sql::{DaoQueries, NoListFilter};
+use td_objects::tower_service::from::{ExtractService, With};
+use td_objects::tower_service::sql::{By, SqlListService};
+use td_objects::types::basic::UserId;
+use td_tower::default_services::ConnectionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = ListApiKeysService,
+    request = ListRequest<()>,
+    response = ListResponse<ApiKey>,
+    connection = ConnectionProvider,
+    context = DaoQueries,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<ListRequest<()>>::extract::<RequestContext>),
+        // list API keys of the requester user
+        from_fn(With::<RequestContext>::extract::<UserId>),
+        from_fn(By::<UserId>::list::<(), NoListFilter, ApiKey>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::services::create_api_key::CreateApiKeyService;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::api_key::ApiKeyCreate;
+    use td_objects::dxo::crudl::ListParams;
+    use td_objects::types::basic::{AccessTokenId, ApiKeyName, RoleId};
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_list_api_keys(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        ListApiKeysService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<ListRequest<()>, ListResponse<ApiKey>>(&[
+                type_of_val(&With::<ListRequest<()>>::extract::<RequestContext>),
+                // list API keys of the requester user
+                type_of_val(&With::<RequestContext>::extract::<UserId>),
+                type_of_val(&By::<UserId>::list::<(), NoListFilter, ApiKey>),
+            ]);
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_list_api_keys(db: DbPool) -> Result<(), TdError> {
+        let context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        for name in ["k0", "k1"] {
+            let request = context.clone().create(
+                (),
+                ApiKeyCreate::builder()
+                    .name(ApiKeyName::try_from(name)?)
+                    .build()?,
+            );
+            CreateApiKeyService::with_defaults(db.clone())
+                .service()
+                .await
+                .raw_oneshot(request)
+                .await?;
+        }
+
+        let request = context.list((), ListParams::default());
+        let response = ListApiKeysService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert_eq!(response.data.len(), 2);
+        assert!(response.data.iter().all(|k| k.user_id == UserId::admin()));
+
+        // other users do not see them
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::default(), RoleId::user())
+                .list((), ListParams::default());
+        let response = ListApiKeysService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert!(response.data.is_empty());
+        Ok(())
+    }
+}
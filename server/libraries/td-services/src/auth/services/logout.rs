@@ -23,6 +23,7 @@ use td_tower::layers;
     request = UpdateRequest<(), ()>,
     response = (),
     connection = TransactionProvider,
+    audit = AccessTokenId,
     context = DaoQueries,
     context = Sessions,
 )]
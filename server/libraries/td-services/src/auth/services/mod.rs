@@ -3,19 +3,25 @@
 //
 
 use crate::auth::services::cert_download::CertDownloadService;
+use crate::auth::services::create_api_key::CreateApiKeyService;
+use crate::auth::services::list_api_keys::ListApiKeysService;
 use crate::auth::services::login::LoginService;
 use crate::auth::services::logout::LogoutService;
 use crate::auth::services::password_change::PasswordChangeService;
 use crate::auth::services::refresh::RefreshService;
+use crate::auth::services::revoke_api_key::RevokeApiKeyService;
 use crate::auth::services::role_change::RoleChangeService;
 use crate::auth::services::user_info::UserInfoService;
 use ta_services::factory::ServiceFactory;
 
 mod cert_download;
+mod create_api_key;
+mod list_api_keys;
 mod login;
 mod logout;
 mod password_change;
 mod refresh;
+mod revoke_api_key;
 mod role_change;
 mod user_info;
 
@@ -28,6 +34,9 @@ pub struct AuthServices {
     pub role_change: RoleChangeService,
     pub password_change: PasswordChangeService,
     pub cert_download: CertDownloadService,
+    pub create_api_key: CreateApiKeyService,
+    pub list_api_keys: ListApiKeysService,
+    pub revoke_api_key: RevokeApiKeyService,
 }
 
 #[cfg(test)]
@@ -34,6 +34,7 @@ use tower::util::MapErrLayer;
     request = PasswordChange,
     response = (),
     connection = TransactionProvider,
+    audit = UserId,
     context = DaoQueries,
     context = PasswordHashingConfig,
     context = Sessions,
@@ -96,8 +97,11 @@ mod tests {
     use ta_services::service::TdService;
     use td_database::sql::DbPool;
     use td_error::assert_service_error;
+    use td_objects::dxo::audit_log::AuditLogDB;
     use td_objects::dxo::auth::{Login, PasswordChange};
-    use td_objects::types::basic::{Password, RoleName, SessionStatus};
+    use td_objects::dxo::crudl::handle_sql_err;
+    use td_objects::sql::SelectBy;
+    use td_objects::types::basic::{AuditOutcome, Password, RoleName, SessionStatus};
     use td_tower::ctx_service::RawOneshot;
 
     #[cfg(feature = "test_tower_metadata")]
@@ -231,6 +235,19 @@ mod tests {
             other => panic!("Expected 'AuthenticationFailed', got {other:?}"),
         })
         .await;
+
+        // the failed password change is audited, without requester
+        let audit_logs: Vec<AuditLogDB> = DaoQueries::default()
+            .select_by::<AuditLogDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(audit_logs.len(), 1);
+        assert_eq!(audit_logs[0].service.as_str(), "PasswordChangeService");
+        assert_eq!(audit_logs[0].user_id, None);
+        assert_eq!(audit_logs[0].outcome, AuditOutcome::Failure);
+        assert!(audit_logs[0].error_code.is_some());
         Ok(())
     }
 }
@@ -31,6 +31,7 @@ use td_tower::layers;
     request = UpdateRequest<(), RefreshToken>,
     response = TokenResponseX,
     connection = TransactionProvider,
+    audit = AccessTokenId,
     context = DaoQueries,
     context = JwtConfig,
     context = Sessions,
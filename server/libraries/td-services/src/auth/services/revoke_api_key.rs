@@ -0,0 +1,218 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use ta_services::factory::service_factory;
+use td_objects::dxo::api_key::{ApiKeyDB, ApiKeyRevokeDB, ApiKeyRevokeDBBuilder};
+use td_objects::dxo::crudl::{RequestContext, UpdateRequest};
+use td_objects::rest_urls::ApiKeyParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::from::{
+    BuildService, DefaultService, ExtractNameService, ExtractService, SetService, With, combine,
+};
+use td_objects::tower_service::sql::{By, SqlSelectService, SqlUpdateService};
+use td_objects::types::basic::{ApiKeyId, AtTime, UserId};
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = RevokeApiKeyService,
+    request = UpdateRequest<ApiKeyParam, ()>,
+    response = (),
+    connection = TransactionProvider,
+    audit = ApiKeyId,
+    context = DaoQueries,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<UpdateRequest<ApiKeyParam, ()>>::extract::<RequestContext>),
+        from_fn(With::<UpdateRequest<ApiKeyParam, ()>>::extract_name::<ApiKeyParam>),
+        from_fn(With::<RequestContext>::extract::<UserId>),
+        from_fn(With::<RequestContext>::extract::<AtTime>),
+        // find API key of the requester user
+        from_fn(With::<ApiKeyParam>::extract::<ApiKeyId>),
+        from_fn(combine::<ApiKeyId, UserId>),
+        from_fn(By::<(ApiKeyId, UserId)>::select::<ApiKeyDB>),
+        // revoke API key
+        from_fn(With::<ApiKeyRevokeDBBuilder>::default),
+        from_fn(With::<AtTime>::set::<ApiKeyRevokeDBBuilder>),
+        from_fn(With::<ApiKeyRevokeDBBuilder>::build::<ApiKeyRevokeDB, _>),
+        from_fn(By::<ApiKeyId>::update::<ApiKeyRevokeDB, ApiKeyDB>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthError;
+    use crate::auth::api_key::authenticate_api_key;
+    use crate::auth::services::create_api_key::CreateApiKeyService;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::{TdError, assert_service_error};
+    use td_objects::dxo::api_key::{ApiKeyCreate, ApiKeyCreated};
+    use td_objects::dxo::user_role::UserRoleDB;
+    use td_objects::sql::DeleteBy;
+    use td_objects::test_utils::seed_user::seed_user;
+    use td_objects::test_utils::seed_user_role::seed_user_role;
+    use td_objects::tower_service::sql::SqlError;
+    use td_objects::types::basic::{
+        AccessTokenId, ApiKeyName, ApiKeyToken, RoleId, RoleName, UserEnabled, UserName,
+    };
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_revoke_api_key(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        RevokeApiKeyService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<UpdateRequest<ApiKeyParam, ()>, ()>(&[
+                type_of_val(&With::<UpdateRequest<ApiKeyParam, ()>>::extract::<RequestContext>),
+                type_of_val(&With::<UpdateRequest<ApiKeyParam, ()>>::extract_name::<ApiKeyParam>),
+                type_of_val(&With::<RequestContext>::extract::<UserId>),
+                type_of_val(&With::<RequestContext>::extract::<AtTime>),
+                // find API key of the requester user
+                type_of_val(&With::<ApiKeyParam>::extract::<ApiKeyId>),
+                type_of_val(&combine::<ApiKeyId, UserId>),
+                type_of_val(&By::<(ApiKeyId, UserId)>::select::<ApiKeyDB>),
+                // revoke API key
+                type_of_val(&With::<ApiKeyRevokeDBBuilder>::default),
+                type_of_val(&With::<AtTime>::set::<ApiKeyRevokeDBBuilder>),
+                type_of_val(&With::<ApiKeyRevokeDBBuilder>::build::<ApiKeyRevokeDB, _>),
+                type_of_val(&By::<ApiKeyId>::update::<ApiKeyRevokeDB, ApiKeyDB>),
+            ]);
+    }
+
+    async fn create_api_key(
+        db: &DbPool,
+        context: &RequestContext,
+    ) -> Result<ApiKeyCreated, TdError> {
+        let request = context.clone().create(
+            (),
+            ApiKeyCreate::builder()
+                .name(ApiKeyName::try_from("ci")?)
+                .build()?,
+        );
+        CreateApiKeyService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_revoke_api_key(db: DbPool) -> Result<(), TdError> {
+        let queries = DaoQueries::default();
+        let context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        let created = create_api_key(&db, &context).await?;
+
+        // a valid key authenticates as the user and role it was issued for
+        let mut conn = db.acquire().await.unwrap();
+        let authenticated = authenticate_api_key(&queries, &mut conn, &created.api_key).await?;
+        assert_eq!(authenticated.id, created.id);
+        assert_eq!(authenticated.user_id, UserId::admin());
+        assert_eq!(authenticated.user_name, UserName::admin());
+        assert_eq!(authenticated.role_id, RoleId::user());
+        assert_eq!(authenticated.role_name, RoleName::user());
+
+        // a key with a valid prefix and a wrong secret does not
+        let forged = ApiKeyToken::try_from(format!("td_{}_{}", created.prefix, "0".repeat(48)))?;
+        let err = authenticate_api_key(&queries, &mut conn, &forged)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.domain_err::<AuthError>(),
+            AuthError::AuthenticationFailed
+        ));
+
+        let request = context.update(ApiKeyParam::builder().api_key(created.id).build()?, ());
+        RevokeApiKeyService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        // a revoked key is rejected
+        let err = authenticate_api_key(&queries, &mut conn, &created.api_key)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.domain_err::<AuthError>(),
+            AuthError::AuthenticationFailed
+        ));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_api_key_of_removed_role_or_disabled_user(db: DbPool) -> Result<(), TdError> {
+        let queries = DaoQueries::default();
+        let user = seed_user(&db, &UserName::try_from("ci")?, &UserEnabled::from(true)).await;
+        let user_role = seed_user_role(&db, &user.id, &RoleId::user()).await;
+        let context = RequestContext::with(AccessTokenId::default(), user.id, RoleId::user());
+        let created = create_api_key(&db, &context).await?;
+
+        let mut conn = db.acquire().await.unwrap();
+        authenticate_api_key(&queries, &mut conn, &created.api_key).await?;
+
+        // a key of a role the user no longer has is rejected
+        queries
+            .delete_by::<UserRoleDB>(&user_role.id)?
+            .build()
+            .execute(&db)
+            .await
+            .unwrap();
+        let err = authenticate_api_key(&queries, &mut conn, &created.api_key)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.domain_err::<AuthError>(),
+            AuthError::AuthenticationFailed
+        ));
+
+        // a key of a disabled user is rejected, even with the role
+        seed_user_role(&db, &user.id, &RoleId::user()).await;
+        authenticate_api_key(&queries, &mut conn, &created.api_key).await?;
+        sqlx::query("UPDATE users SET enabled = false WHERE id = ?1")
+            .bind(user.id)
+            .execute(&db)
+            .await
+            .unwrap();
+        let err = authenticate_api_key(&queries, &mut conn, &created.api_key)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.domain_err::<AuthError>(),
+            AuthError::AuthenticationFailed
+        ));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_revoke_api_key_of_other_user(db: DbPool) -> Result<(), TdError> {
+        let context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        let created = create_api_key(&db, &context).await?;
+
+        let service = RevokeApiKeyService::with_defaults(db.clone())
+            .service()
+            .await;
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::default(), RoleId::user())
+                .update(ApiKeyParam::builder().api_key(created.id).build()?, ());
+        assert_service_error(service, request, |err| match err {
+            SqlError::CouldNotFindEntity(_, _, _) => {}
+            other => panic!("Expected 'CouldNotFindEntity', got {other:?}"),
+        })
+        .await;
+        Ok(())
+    }
+}
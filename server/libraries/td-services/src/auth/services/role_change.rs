@@ -35,6 +35,7 @@ use tower::util::MapErrLayer;
     request = UpdateRequest<(), RoleChange>,
     response = TokenResponseX,
     connection = TransactionProvider,
+    audit = AccessTokenId,
     context = DaoQueries,
     context = JwtConfig,
     context = Sessions,
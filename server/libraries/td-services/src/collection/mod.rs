@@ -3,11 +3,36 @@
 //
 
 use td_error::td_error;
+use td_objects::types::basic::{
+    BundleId, CollectionDefinitionVersion, CollectionName, FunctionName,
+};
+use td_storage::StorageError;
 
 pub mod service;
 
+/// Version of the collection definitions exported, the only one that can be imported.
+pub const COLLECTION_DEFINITION_VERSION: i16 = 1;
+
 #[td_error]
 pub enum CollectionError {
     #[error("The collection update request has nothing to update")]
     UpdateRequestHasNothingToUpdate = 0,
+    #[error(
+        "Collection definition version {0} is not supported, it must be {COLLECTION_DEFINITION_VERSION}"
+    )]
+    UnsupportedDefinitionVersion(CollectionDefinitionVersion) = 1,
+    #[error(
+        "Collection '{0}' has dependents, delete it with cascade to delete or freeze them too: {1}"
+    )]
+    CollectionHasDependents(CollectionName, String) = 2,
+
+    #[error(
+        "Bundle '{0}' of function '{1}' was not found, the function must be registered again with its bundle"
+    )]
+    BundleNotFound(BundleId, FunctionName) = 1000,
+
+    #[error("Invalid storage version: {0}")]
+    InvalidStorageVersion(String) = 5000,
+    #[error("Function bundle copy failed: {0}")]
+    BundleCopyFailed(#[source] StorageError) = 5001,
 }
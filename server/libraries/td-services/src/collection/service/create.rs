@@ -18,17 +18,23 @@ use td_objects::tower_service::sql::{By, SqlSelectService, insert};
 use td_objects::types::basic::CollectionId;
 use td_tower::default_services::TransactionProvider;
 use td_tower::from_fn::from_fn;
-use td_tower::layers;
+use td_tower::{layer, layers};
 
 #[service_factory(
     name = CreateCollectionService,
     request = CreateRequest<(), CollectionCreate>,
     response = CollectionRead,
     connection = TransactionProvider,
+    audit = CollectionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
 fn service() {
+    layers!(create_collection())
+}
+
+#[layer]
+pub fn create_collection() {
     layers!(
         from_fn(With::<CreateRequest<(), CollectionCreate>>::extract::<RequestContext>),
         from_fn(AuthzOn::<System>::set),
@@ -2,7 +2,9 @@
 // Copyright 2024 Tabs Data Inc.
 //
 
-use crate::collection::service::layer::delete::{build_deleted_functions, build_deleted_tables};
+use crate::collection::service::layer::delete::{
+    build_deleted_functions, build_deleted_tables, check_collection_dependents,
+};
 use crate::table::layers::delete::{
     build_deleted_dependencies, build_deleted_triggers, build_frozen_functions,
 };
@@ -14,7 +16,7 @@ use td_objects::dxo::dependency::DependencyDB;
 use td_objects::dxo::function::FunctionDB;
 use td_objects::dxo::table::TableDB;
 use td_objects::dxo::trigger::TriggerDB;
-use td_objects::rest_urls::CollectionParam;
+use td_objects::rest_urls::params::CollectionCascadeName;
 use td_objects::sql::DaoQueries;
 use td_objects::tower_service::authz::{AuthzOn, SysAdmin, System};
 use td_objects::tower_service::from::{
@@ -24,29 +26,37 @@ use td_objects::tower_service::from::{
 use td_objects::tower_service::sql::{
     By, SqlFindService, SqlSelectAllService, SqlSelectService, SqlUpdateService, insert_vec,
 };
-use td_objects::types::basic::{AtTime, CollectionId, CollectionIdName, FunctionId, TableId};
+use td_objects::types::basic::{
+    AtTime, Cascade, CollectionId, CollectionIdName, FunctionId, TableId,
+};
 use td_tower::default_services::TransactionProvider;
 use td_tower::from_fn::from_fn;
 use td_tower::layers;
 
 #[service_factory(
     name = DeleteCollectionService,
-    request = DeleteRequest<CollectionParam>,
+    request = DeleteRequest<CollectionCascadeName>,
     response = (),
     connection = TransactionProvider,
+    audit = CollectionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
 fn service() {
     layers!(
-        from_fn(With::<DeleteRequest<CollectionParam>>::extract::<RequestContext>),
+        from_fn(With::<DeleteRequest<CollectionCascadeName>>::extract::<RequestContext>),
         from_fn(AuthzOn::<System>::set),
         from_fn(Authz::<SysAdmin>::check),
-        from_fn(With::<DeleteRequest<CollectionParam>>::extract_name::<CollectionParam>),
+        from_fn(
+            With::<DeleteRequest<CollectionCascadeName>>::extract_name::<CollectionCascadeName>
+        ),
         from_fn(With::<RequestContext>::extract::<AtTime>),
         // Get collection
-        from_fn(With::<CollectionParam>::extract::<CollectionIdName>),
+        from_fn(With::<CollectionCascadeName>::extract::<CollectionIdName>),
         from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        // Refuse to delete a collection with dependents, unless cascading
+        from_fn(With::<CollectionCascadeName>::extract::<Cascade>),
+        from_fn(check_collection_dependents),
         // Build deleted collection
         from_fn(With::<CollectionDB>::convert_to::<CollectionDeleteDBBuilder, _>),
         from_fn(With::<RequestContext>::update::<CollectionDeleteDBBuilder, _>),
@@ -87,20 +97,21 @@ fn service() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::collection::CollectionError;
     use ta_services::service::TdService;
     use td_database::sql::DbPool;
-    use td_error::TdError;
+    use td_error::{TdError, assert_service_error};
     use td_objects::dxo::collection::CollectionCreateDB;
     use td_objects::dxo::crudl::RequestContext;
     use td_objects::dxo::dependency::DependencyDBWithNames;
     use td_objects::dxo::function::{FunctionDBWithNames, FunctionRegister};
     use td_objects::dxo::table::TableDBWithNames;
     use td_objects::dxo::trigger::TriggerDBWithNames;
-    use td_objects::rest_urls::CollectionParam;
+    use td_objects::rest_urls::{CascadeParam, CollectionParam};
     use td_objects::sql::cte::CteQueries;
     use td_objects::sql::{DaoQueries, SelectBy};
     use td_objects::test_utils::seed_collection::seed_collection;
-    use td_objects::test_utils::seed_function::seed_function;
+    use td_objects::test_utils::seed_function::{function_register, seed_function};
     use td_objects::types::basic::{
         AccessTokenId, BundleId, CollectionName, Decorator, DependencyStatus, FunctionName,
         FunctionRuntimeValues, FunctionStatus, RoleId, TableName, TableNameDto, TableStatus,
@@ -117,15 +128,18 @@ mod tests {
         DeleteCollectionService::with_defaults(db)
             .metadata()
             .await
-            .assert_service::<DeleteRequest<CollectionParam>, ()>(&[
-                type_of_val(&With::<DeleteRequest<CollectionParam>>::extract::<RequestContext>),
+            .assert_service::<DeleteRequest<CollectionCascadeName>, ()>(&[
+                type_of_val(&With::<DeleteRequest<CollectionCascadeName>>::extract::<RequestContext>),
                 type_of_val(&AuthzOn::<System>::set),
                 type_of_val(&Authz::<SysAdmin>::check),
-                type_of_val(&With::<DeleteRequest<CollectionParam>>::extract_name::<CollectionParam>),
+                type_of_val(&With::<DeleteRequest<CollectionCascadeName>>::extract_name::<CollectionCascadeName>),
                 type_of_val(&With::<RequestContext>::extract::<AtTime>),
                 // Get collection
-                type_of_val(&With::<CollectionParam>::extract::<CollectionIdName>),
+                type_of_val(&With::<CollectionCascadeName>::extract::<CollectionIdName>),
                 type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                // Refuse to delete a collection with dependents, unless cascading
+                type_of_val(&With::<CollectionCascadeName>::extract::<Cascade>),
+                type_of_val(&check_collection_dependents),
                 // Build deleted collection
                 type_of_val(&With::<CollectionDB>::convert_to::<CollectionDeleteDBBuilder, _>),
                 type_of_val(&With::<RequestContext>::update::<CollectionDeleteDBBuilder, _>),
@@ -163,6 +177,25 @@ mod tests {
             ]);
     }
 
+    fn delete_request(
+        name: &CollectionName,
+        cascade: bool,
+    ) -> Result<DeleteRequest<CollectionCascadeName>, TdError> {
+        Ok(RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )
+        .delete(CollectionCascadeName::new(
+            CollectionParam::builder()
+                .try_collection(name.to_string())?
+                .build()?,
+            CascadeParam::builder()
+                .cascade(Cascade::from(cascade))
+                .build()?,
+        )))
+    }
+
     #[td_test::test(sqlx)]
     #[tokio::test]
     async fn test_delete_collection(db: DbPool) -> Result<(), TdError> {
@@ -203,16 +236,7 @@ mod tests {
             .build()?;
         let _ = seed_function(&db, &collection, &create).await;
 
-        let request = RequestContext::with(
-            AccessTokenId::default(),
-            UserId::admin(),
-            RoleId::sys_admin(),
-        )
-        .delete(
-            CollectionParam::builder()
-                .try_collection(name.to_string())?
-                .build()?,
-        );
+        let request = delete_request(&name, true)?;
 
         DeleteCollectionService::with_defaults(db.clone())
             .service()
@@ -326,16 +350,7 @@ mod tests {
             .build()?;
         let _ = seed_function(&db, &collection_1, &create).await;
 
-        let request = RequestContext::with(
-            AccessTokenId::default(),
-            UserId::admin(),
-            RoleId::sys_admin(),
-        )
-        .delete(
-            CollectionParam::builder()
-                .try_collection(name_c_0.to_string())?
-                .build()?,
-        );
+        let request = delete_request(&name_c_0, true)?;
 
         DeleteCollectionService::with_defaults(db.clone())
             .service()
@@ -379,4 +394,105 @@ mod tests {
 
         Ok(())
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_delete_empty_collection(db: DbPool) -> Result<(), TdError> {
+        let name = CollectionName::try_from("c")?;
+        let _ = seed_collection(&db, &name, &UserId::admin()).await;
+
+        DeleteCollectionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(delete_request(&name, false)?)
+            .await?;
+
+        let found: Vec<CollectionDB> = DaoQueries::default()
+            .select_by::<CollectionDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 0);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_delete_collection_with_functions_refused(db: DbPool) -> Result<(), TdError> {
+        let name = CollectionName::try_from("c")?;
+        let collection = seed_collection(&db, &name, &UserId::admin()).await;
+        let create = function_register("function_0", &["table_0"], &[], &[])?;
+        let _ = seed_function(&db, &collection, &create).await;
+
+        let service = DeleteCollectionService::with_defaults(db.clone())
+            .service()
+            .await;
+        assert_service_error(service, delete_request(&name, false)?, |err| match err {
+            CollectionError::CollectionHasDependents(collection, dependents) => {
+                assert_eq!(collection, &name);
+                assert!(dependents.contains("function_0"));
+            }
+            other => panic!("Expected 'CollectionHasDependents', got {other:?}"),
+        })
+        .await;
+
+        // Assert nothing was deleted
+        let found: Vec<CollectionDB> = DaoQueries::default()
+            .select_by::<CollectionDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        let found: Vec<FunctionDBWithNames> = DaoQueries::default()
+            .select_versions_at::<{ FunctionDBWithNames::Available }, FunctionDBWithNames>(
+                None,
+                &(),
+            )?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert!(matches!(found[0].status, FunctionStatus::Active));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_delete_collection_with_downstream_refused(db: DbPool) -> Result<(), TdError> {
+        let name_c_0 = CollectionName::try_from("c_0")?;
+        let collection_0 = seed_collection(&db, &name_c_0, &UserId::admin()).await;
+        let create = function_register("function_0", &["table_0"], &[], &[])?;
+        let _ = seed_function(&db, &collection_0, &create).await;
+
+        let name_c_1 = CollectionName::try_from("c_1")?;
+        let collection_1 = seed_collection(&db, &name_c_1, &UserId::admin()).await;
+        let create = function_register(
+            "function_1",
+            &["table_1"],
+            &["c_0/table_0"],
+            &["c_0/table_0"],
+        )?;
+        let _ = seed_function(&db, &collection_1, &create).await;
+
+        let service = DeleteCollectionService::with_defaults(db.clone())
+            .service()
+            .await;
+        assert_service_error(
+            service,
+            delete_request(&name_c_0, false)?,
+            |err| match err {
+                CollectionError::CollectionHasDependents(collection, dependents) => {
+                    assert_eq!(collection, &name_c_0);
+                    assert!(dependents.contains("function_0"));
+                    assert!(dependents.contains("c_1/function_1"));
+                }
+                other => panic!("Expected 'CollectionHasDependents', got {other:?}"),
+            },
+        )
+        .await;
+        Ok(())
+    }
 }
@@ -0,0 +1,41 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::collection::service::layer::definition::export_collection;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::collection::{CollectionDB, CollectionDefinition};
+use td_objects::dxo::crudl::{ReadRequest, RequestContext};
+use td_objects::rest_urls::CollectionParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin};
+use td_objects::tower_service::from::{ExtractNameService, ExtractService, With};
+use td_objects::tower_service::sql::{By, SqlSelectService};
+use td_objects::types::basic::{AtTime, CollectionId, CollectionIdName};
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = ExportCollectionService,
+    request = ReadRequest<CollectionParam>,
+    response = CollectionDefinition,
+    connection = TransactionProvider,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<ReadRequest<CollectionParam>>::extract::<RequestContext>),
+        from_fn(With::<ReadRequest<CollectionParam>>::extract_name::<CollectionParam>),
+        from_fn(With::<CollectionParam>::extract::<CollectionIdName>),
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        // check requester is coll_admin for the collection
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin>::check),
+        from_fn(With::<RequestContext>::extract::<AtTime>),
+        from_fn(export_collection),
+    )
+}
@@ -0,0 +1,305 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::collection::service::layer::definition::import_collection;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::collection::{CollectionDefinition, CollectionImport, CollectionRead};
+use td_objects::dxo::crudl::{CreateRequest, RequestContext};
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, SysAdmin, System};
+use td_objects::tower_service::from::{ExtractDataService, ExtractService, With};
+use td_objects::types::basic::CollectionId;
+use td_storage::Storage;
+use td_storage::quota::StorageQuota;
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = ImportCollectionService,
+    request = CreateRequest<(), CollectionDefinition>,
+    response = CollectionImport,
+    connection = TransactionProvider,
+    audit = CollectionId,
+    context = DaoQueries,
+    context = AuthzContext,
+    context = Storage,
+    context = StorageQuota,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<CreateRequest<(), CollectionDefinition>>::extract::<RequestContext>),
+        from_fn(AuthzOn::<System>::set),
+        from_fn(Authz::<SysAdmin>::check),
+        from_fn(
+            With::<CreateRequest<(), CollectionDefinition>>::extract_data::<CollectionDefinition>
+        ),
+        from_fn(import_collection),
+        from_fn(With::<CollectionImport>::extract::<CollectionRead>),
+        from_fn(With::<CollectionRead>::extract::<CollectionId>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+    use crate::collection::COLLECTION_DEFINITION_VERSION;
+    use crate::collection::CollectionError;
+    use crate::collection::service::export::ExportCollectionService;
+    use crate::function::services::register_batch::RegisterFunctionBatchService;
+    use crate::function::services::upload::UploadFunctionService;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use ta_services::factory::ServiceFactory;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::bundle::BundleBlobDB;
+    use td_objects::dxo::crudl::handle_sql_err;
+    use td_objects::dxo::function::FunctionRegisterBatch;
+    use td_objects::dxo::function_upload::FunctionUpload;
+    use td_objects::rest_urls::CollectionParam;
+    use td_objects::sql::SelectBy;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_function::function_register;
+    use td_objects::test_utils::seed_inter_collection_permission::seed_inter_collection_permission;
+    use td_objects::types::basic::{
+        AccessTokenId, CollectionDefinitionVersion, CollectionId, CollectionName, DataLocation,
+        RoleId, ToCollectionId, ToCollectionName, UserId,
+    };
+    use td_storage::location::StorageLocation;
+    use td_test::sqlx::SqlxTestSetup;
+    use td_test::{TestSetup, TestSetupExecution};
+    use td_tower::ctx_service::RawOneshot;
+
+    fn context() -> RequestContext {
+        RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )
+    }
+
+    async fn export(db: &DbPool) -> Result<CollectionDefinition, TdError> {
+        let request = context().read(
+            CollectionParam::builder()
+                .try_collection("collection")?
+                .build()?,
+        );
+        ExportCollectionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    async fn import(
+        db: &DbPool,
+        definition: CollectionDefinition,
+    ) -> Result<CollectionImport, TdError> {
+        let request = context().create((), definition);
+        ImportCollectionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    async fn fresh_db() -> DbPool {
+        match SqlxTestSetup::new(None, vec![]).setup().await {
+            TestSetupExecution::Run(db) => db,
+            TestSetupExecution::Skip => unreachable!(),
+        }
+    }
+
+    async fn bundle_blob(
+        db: &DbPool,
+        collection_id: &CollectionId,
+    ) -> Result<BundleBlobDB, TdError> {
+        DaoQueries::default()
+            .select_by::<BundleBlobDB>(collection_id)?
+            .build_query_as()
+            .fetch_one(db)
+            .await
+            .map_err(handle_sql_err)
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_export_import_round_trip(db: DbPool) -> Result<(), TdError> {
+        let collection = seed_collection(
+            &db,
+            &CollectionName::try_from("collection")?,
+            &UserId::admin(),
+        )
+        .await;
+        let other =
+            seed_collection(&db, &CollectionName::try_from("other")?, &UserId::admin()).await;
+        seed_inter_collection_permission(&db, &collection.id, &ToCollectionId::try_from(other.id)?)
+            .await;
+
+        let batch = FunctionRegisterBatch::builder()
+            .functions(vec![
+                function_register("first", &["first_table"], &["second_table"], &[])?,
+                function_register("second", &["second_table"], &[], &["first_table"])?,
+            ])
+            .build()?;
+        let request = context().create(
+            CollectionParam::builder()
+                .try_collection("collection")?
+                .build()?,
+            batch,
+        );
+        RegisterFunctionBatchService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let definition = export(&db).await?;
+        assert_eq!(*definition.version, COLLECTION_DEFINITION_VERSION);
+        assert_eq!(definition.functions.len(), 2);
+        assert_eq!(definition.inter_collection_permissions.len(), 1);
+
+        // import into a fresh instance, where the other collection exists
+        let target = fresh_db().await;
+        seed_collection(
+            &target,
+            &CollectionName::try_from("other")?,
+            &UserId::admin(),
+        )
+        .await;
+
+        // the bundles are not in the fresh instance
+        let err = import(&target, definition.clone()).await.err().unwrap();
+        assert!(matches!(
+            err.domain_err(),
+            CollectionError::BundleNotFound(_, _)
+        ));
+
+        // without the functions it is imported
+        let mut definition = definition;
+        definition.functions.clear();
+        let imported = import(&target, definition.clone()).await?;
+        assert_eq!(imported.collection.name, definition.name);
+        assert_ne!(imported.collection.id, collection.id);
+        assert!(imported.functions.is_empty());
+        assert!(imported.conflicts.is_empty());
+
+        // and exporting it again gives the same definition
+        assert_eq!(export(&target).await?, definition);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_import_conflicts(db: DbPool) -> Result<(), TdError> {
+        let definition = CollectionDefinition::builder()
+            .version(CollectionDefinitionVersion::try_from(
+                COLLECTION_DEFINITION_VERSION,
+            )?)
+            .try_name("collection")?
+            .try_description("imported")?
+            .functions(vec![])
+            .inter_collection_permissions(vec![ToCollectionName::try_from("missing")?])
+            .build()?;
+
+        // the permission to a missing collection is reported
+        let imported = import(&db, definition.clone()).await?;
+        assert!(imported.functions.is_empty());
+        assert_eq!(imported.conflicts.len(), 1);
+
+        // the collection already exists
+        assert!(import(&db, definition.clone()).await.is_err());
+
+        // the bundle of the function is missing
+        let mut missing_bundle = definition.clone();
+        missing_bundle.name = CollectionName::try_from("missing_bundle")?;
+        missing_bundle.functions = vec![function_register("function", &["table"], &[], &[])?];
+        let err = import(&db, missing_bundle).await.err().unwrap();
+        assert!(matches!(
+            err.domain_err(),
+            CollectionError::BundleNotFound(_, _)
+        ));
+
+        let mut unsupported = definition;
+        unsupported.version =
+            CollectionDefinitionVersion::try_from(COLLECTION_DEFINITION_VERSION + 1)?;
+        let err = import(&db, unsupported).await.err().unwrap();
+        assert!(matches!(
+            err.domain_err(),
+            CollectionError::UnsupportedDefinitionVersion(_)
+        ));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_import_copies_bundles(db: DbPool) -> Result<(), TdError> {
+        let services = Context::with_defaults(db.clone());
+        let collection = seed_collection(
+            &db,
+            &CollectionName::try_from("collection")?,
+            &UserId::admin(),
+        )
+        .await;
+        let collection_param = CollectionParam::builder()
+            .try_collection("collection")?
+            .build()?;
+
+        // two functions sharing a bundle
+        let upload = FunctionUpload::new(
+            Request::builder()
+                .body(Body::new("TEXT".to_string()))
+                .unwrap(),
+        );
+        let bundle = UploadFunctionService::build(&services)
+            .service()
+            .await
+            .raw_oneshot(context().create(collection_param.clone(), upload))
+            .await?;
+        let mut functions = vec![
+            function_register("first", &["first_table"], &[], &[])?,
+            function_register("second", &["second_table"], &[], &[])?,
+        ];
+        functions.iter_mut().for_each(|f| f.bundle_id = bundle.id);
+        let batch = FunctionRegisterBatch::builder()
+            .functions(functions)
+            .build()?;
+        RegisterFunctionBatchService::build(&services)
+            .service()
+            .await
+            .raw_oneshot(context().create(collection_param, batch))
+            .await?;
+
+        // imported as another collection in the same instance
+        let mut definition = export(&db).await?;
+        definition.name = CollectionName::try_from("copy")?;
+        let imported = ImportCollectionService::build(&services)
+            .service()
+            .await
+            .raw_oneshot(context().create((), definition))
+            .await?;
+        assert!(imported.conflicts.is_empty());
+
+        // the copy has its own bundle, shared by its functions
+        let source = bundle_blob(&db, &collection.id).await?;
+        let copy = bundle_blob(&db, &imported.collection.id).await?;
+        assert_eq!(source.id, bundle.id);
+        assert_ne!(copy.id, source.id);
+        assert_eq!(copy.hash, source.hash);
+        assert_eq!(*source.ref_count, 2);
+        assert_eq!(*copy.ref_count, 2);
+
+        let (location, _) = StorageLocation::current()
+            .builder(&DataLocation::default())
+            .collection(&imported.collection.id)
+            .function(&copy.id)
+            .build();
+        assert!(services.storage.exists(&location).await?);
+        Ok(())
+    }
+}
@@ -0,0 +1,408 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::collection::service::create::create_collection;
+use crate::collection::{COLLECTION_DEFINITION_VERSION, CollectionError};
+use crate::function::layers::register::{
+    SYSTEM_INPUT_TABLE_DEPENDENCY_PREFIXES, SYSTEM_OUTPUT_TABLE_NAMES_PREFIXES,
+};
+use crate::function::layers::upload::store_bundle;
+use crate::function::services::read::read_function_with_tables;
+use crate::function::services::register_batch::register_functions;
+use crate::inter_coll_permission::services::create::create_inter_collection_permission;
+use sqlx::SqliteConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+use td_authz::AuthzContext;
+use td_error::TdError;
+use td_objects::dxo::collection::{
+    CollectionCreate, CollectionDB, CollectionDefinition, CollectionImport, CollectionRead,
+};
+use td_objects::dxo::crudl::{CreateRequest, RequestContext, handle_sql_err};
+use td_objects::dxo::function::{
+    FunctionDB, FunctionDBWithNames, FunctionRegister, FunctionWithTables,
+};
+use td_objects::dxo::inter_collection_permission::{
+    InterCollectionPermissionCreate, InterCollectionPermissionDBWithNames,
+};
+use td_objects::rest_urls::CollectionParam;
+use td_objects::sql::cte::CteQueries;
+use td_objects::sql::{DaoQueries, SelectBy};
+use td_objects::table_ref::{TableRef, VersionedTableRef};
+use td_objects::types::basic::{
+    AtTime, BundleId, CollectionDefinitionVersion, CollectionName, DataLocation, ImportConflict,
+    StorageVersion, TableNameDto, ToCollectionName,
+};
+use td_objects::types::composed::{TableDependencyDto, TableTriggerDto};
+use td_storage::Storage;
+use td_storage::location::StorageLocation;
+use td_storage::quota::StorageQuota;
+use td_tower::extractors::{Connection, FromHandler, Input, IntoMutSqlConnection, ReqCtx, SrvCtx};
+use td_tower::handler::Handler;
+use td_tower::service;
+use tower::ServiceExt;
+
+/// Handler for a service layer run as part of another service, sharing its connection.
+fn inner_handler<R: Send + Sync + 'static>(
+    request: R,
+    queries: &Arc<DaoQueries>,
+    authz_context: &Arc<AuthzContext>,
+    connection: &Connection,
+    req_ctx: &ReqCtx,
+) -> Handler {
+    let mut handler = Handler::new();
+    handler.insert(Input(Arc::new(())));
+    handler.insert(Input(Arc::new(request)));
+    handler.insert(req_ctx.clone());
+    handler.insert(SrvCtx(queries.clone()));
+    handler.insert(SrvCtx(authz_context.clone()));
+    handler.insert(connection.clone());
+    handler
+}
+
+/// References to tables of the collection itself are left without collection, so the
+/// definition does not depend on the collection name.
+fn table_collection(
+    collection: &CollectionName,
+    table_collection: &Option<CollectionName>,
+) -> Option<CollectionName> {
+    table_collection
+        .as_ref()
+        .filter(|table_collection| *table_collection != collection)
+        .cloned()
+}
+
+/// Registration of an exported function. System tables, added on registration, are left out.
+fn function_definition(
+    collection: &CollectionName,
+    function: &FunctionWithTables,
+) -> Result<FunctionRegister, TdError> {
+    let mut tables = function
+        .tables
+        .iter()
+        .filter(|table| {
+            !SYSTEM_OUTPUT_TABLE_NAMES_PREFIXES
+                .iter()
+                .any(|prefix| table.starts_with(prefix))
+        })
+        .map(|table| TableNameDto::try_from(table.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    tables.sort_by_key(|table| table.to_string());
+
+    let mut dependencies = function
+        .dependencies
+        .iter()
+        .filter(|dependency| {
+            !SYSTEM_INPUT_TABLE_DEPENDENCY_PREFIXES
+                .iter()
+                .any(|prefix| dependency.table.starts_with(prefix))
+        })
+        .map(|dependency| {
+            let dependency = VersionedTableRef::new(
+                table_collection(collection, &dependency.collection),
+                &dependency.table,
+                dependency.versions.clone(),
+            );
+            TableDependencyDto::try_from(dependency.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    dependencies.sort_by_key(|dependency| dependency.to_string());
+
+    let mut triggers = function
+        .triggers
+        .iter()
+        .map(|trigger| {
+            let trigger = TableRef::new(
+                table_collection(collection, &trigger.collection),
+                &trigger.table,
+            );
+            TableTriggerDto::try_from(trigger.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    triggers.sort_by_key(|trigger| trigger.to_string());
+
+    FunctionRegister::builder()
+        .name(function.name.clone())
+        .description(function.description.clone())
+        .bundle_id(function.bundle_id)
+        .snippet(function.snippet.clone())
+        .decorator(function.decorator.clone())
+        .connector(function.connector.clone())
+        .dependencies(Some(dependencies))
+        .triggers(Some(triggers))
+        .tables(Some(tables))
+        .runtime_values(function.runtime_values.clone())
+        .reuse_frozen_tables(false)
+        .build()
+}
+
+/// Copies the bundles of the imported functions into the imported collection, so they do not
+/// share the bundles of the collection they were exported from. Functions with the same bundle
+/// share its copy.
+///
+/// Bundles not found (i.e. exported from another instance) fail the import, as the functions
+/// cannot run without them.
+async fn import_bundles(
+    conn: &mut SqliteConnection,
+    queries: &DaoQueries,
+    storage: &Storage,
+    quota: &StorageQuota,
+    collection: &CollectionRead,
+    functions: &mut [FunctionRegister],
+) -> Result<(), TdError> {
+    let mut copies = HashMap::new();
+    for function in functions.iter_mut() {
+        let source = function.bundle_id;
+        let copy = match copies.get(&source) {
+            Some(copy) => *copy,
+            None => {
+                let copy = copy_bundle(conn, queries, storage, quota, collection, &source).await?;
+                copies.insert(source, copy);
+                copy
+            }
+        };
+        match copy {
+            Some(bundle_id) => function.bundle_id = bundle_id,
+            None => Err(CollectionError::BundleNotFound(
+                source,
+                function.name.clone(),
+            ))?,
+        }
+    }
+    Ok(())
+}
+
+/// Copies a bundle into the collection, returning the id of its copy, `None` if not found.
+async fn copy_bundle(
+    conn: &mut SqliteConnection,
+    queries: &DaoQueries,
+    storage: &Storage,
+    quota: &StorageQuota,
+    collection: &CollectionRead,
+    bundle_id: &BundleId,
+) -> Result<Option<BundleId>, TdError> {
+    let source: Option<FunctionDB> = queries
+        .select_by::<FunctionDB>(bundle_id)?
+        .build_query_as()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    let Some(source) = source else {
+        return Ok(None);
+    };
+
+    let storage_location = StorageLocation::try_from(&source.storage_version)
+        .map_err(CollectionError::InvalidStorageVersion)?;
+    let (location, _) = storage_location
+        .builder(&source.data_location)
+        .collection(&source.collection_id)
+        .function(bundle_id)
+        .build();
+    if !storage
+        .exists(&location)
+        .await
+        .map_err(CollectionError::BundleCopyFailed)?
+    {
+        return Ok(None);
+    }
+    let bytes = storage
+        .read(&location)
+        .await
+        .map_err(CollectionError::BundleCopyFailed)?;
+
+    let blob = store_bundle(
+        conn,
+        queries,
+        storage,
+        quota,
+        &BundleId::default(),
+        &StorageVersion::default(),
+        &DataLocation::default(),
+        &collection.id,
+        &collection.name,
+        bytes,
+    )
+    .await?;
+    Ok(Some(blob.id))
+}
+
+/// Exports the definition of a collection, with its active functions at the request time.
+pub async fn export_collection(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    connection: Connection,
+    req_ctx: ReqCtx,
+    Input(collection): Input<CollectionDB>,
+    Input(at): Input<AtTime>,
+) -> Result<CollectionDefinition, TdError> {
+    let (mut functions, permissions) = {
+        let mut conn = connection.lock().await;
+        let conn = conn.get_mut_connection()?;
+
+        let functions: Vec<FunctionDBWithNames> = queries
+            .select_versions_at::<{ FunctionDBWithNames::Active }, FunctionDBWithNames>(
+                Some(&*at),
+                &collection.id,
+            )?
+            .build_query_as()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+        let permissions: Vec<InterCollectionPermissionDBWithNames> = queries
+            .select_by::<InterCollectionPermissionDBWithNames>(&collection.id)?
+            .build_query_as()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+        (functions, permissions)
+    };
+    functions.sort_by_key(|function| function.name.to_string());
+
+    let mut definitions = Vec::with_capacity(functions.len());
+    for function in functions {
+        let mut handler = Handler::new();
+        handler.insert(Input(Arc::new(())));
+        handler.insert(Input(Arc::new(function)));
+        handler.insert(Input(at.clone()));
+        handler.insert(req_ctx.clone());
+        handler.insert(SrvCtx(queries.clone()));
+        handler.insert(connection.clone());
+        let handler = service!(read_function_with_tables())
+            .oneshot(handler)
+            .await?;
+        let Input(function) = Input::<FunctionWithTables>::from_handler(&handler)?;
+        definitions.push(function_definition(&collection.name, &function)?);
+    }
+
+    let mut inter_collection_permissions = permissions
+        .iter()
+        .map(|permission| ToCollectionName::try_from(permission.to_collection.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    inter_collection_permissions.sort_by_key(|to_collection| to_collection.to_string());
+
+    CollectionDefinition::builder()
+        .version(CollectionDefinitionVersion::try_from(
+            COLLECTION_DEFINITION_VERSION,
+        )?)
+        .name(collection.name.clone())
+        .description(collection.description.clone())
+        .functions(definitions)
+        .inter_collection_permissions(inter_collection_permissions)
+        .build()
+}
+
+/// Imports a collection definition, creating the collection, its functions and the
+/// inter collection permissions it grants.
+///
+/// Everything gets new ids, function bundles are copied into the collection (see
+/// [`import_bundles`]). Permissions granted to collections that do not exist are not created,
+/// and are reported as conflicts. Any other conflict, like an existing collection with the same
+/// name or a missing function bundle, fails the import.
+#[allow(clippy::too_many_arguments)]
+pub async fn import_collection(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(authz_context): SrvCtx<AuthzContext>,
+    SrvCtx(storage): SrvCtx<Storage>,
+    SrvCtx(quota): SrvCtx<StorageQuota>,
+    connection: Connection,
+    req_ctx: ReqCtx,
+    Input(context): Input<RequestContext>,
+    Input(definition): Input<CollectionDefinition>,
+) -> Result<CollectionImport, TdError> {
+    if *definition.version != COLLECTION_DEFINITION_VERSION {
+        Err(CollectionError::UnsupportedDefinitionVersion(
+            definition.version.clone(),
+        ))?
+    }
+
+    // Collection
+    let create = CollectionCreate::builder()
+        .name(definition.name.clone())
+        .description(definition.description.clone())
+        .build()?;
+    let request: CreateRequest<(), CollectionCreate> = context.as_ref().clone().create((), create);
+    let created = service!(create_collection())
+        .oneshot(inner_handler(
+            request,
+            &queries,
+            &authz_context,
+            &connection,
+            &req_ctx,
+        ))
+        .await?;
+    let Input(collection) = Input::<CollectionRead>::from_handler(&created)?;
+    let collection_param = CollectionParam::builder()
+        .try_collection(collection.name.as_str())?
+        .build()?;
+
+    // Inter collection permissions
+    let mut conflicts = Vec::new();
+    for to_collection in &definition.inter_collection_permissions {
+        let exists = {
+            let mut conn = connection.lock().await;
+            let conn = conn.get_mut_connection()?;
+            let found: Option<CollectionDB> = queries
+                .select_by::<CollectionDB>(&CollectionName::try_from(to_collection.to_string())?)?
+                .build_query_as()
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(handle_sql_err)?;
+            found.is_some()
+        };
+        if !exists {
+            conflicts.push(ImportConflict::try_from(format!(
+                "Collection '{to_collection}' does not exist, its inter collection permission was not created"
+            ))?);
+            continue;
+        }
+
+        let create = InterCollectionPermissionCreate::builder()
+            .to_collection(to_collection.clone())
+            .build()?;
+        let request: CreateRequest<CollectionParam, InterCollectionPermissionCreate> = context
+            .as_ref()
+            .clone()
+            .create(collection_param.clone(), create);
+        service!(create_inter_collection_permission())
+            .oneshot(inner_handler(
+                request,
+                &queries,
+                &authz_context,
+                &connection,
+                &req_ctx,
+            ))
+            .await?;
+    }
+
+    // Functions, with their own copy of the bundles
+    let mut functions = definition.functions.clone();
+    {
+        let mut conn = connection.lock().await;
+        let conn = conn.get_mut_connection()?;
+        import_bundles(
+            conn,
+            &queries,
+            &storage,
+            &quota,
+            &collection,
+            &mut functions,
+        )
+        .await?;
+    }
+    let functions = register_functions(
+        queries,
+        authz_context,
+        connection,
+        req_ctx,
+        &context,
+        &collection_param,
+        &functions,
+    )
+    .await?;
+
+    CollectionImport::builder()
+        .collection(collection.as_ref().clone())
+        .functions(functions)
+        .conflicts(conflicts)
+        .build()
+}
@@ -2,13 +2,115 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::collection::CollectionError;
+use itertools::Itertools;
 use std::ops::Deref;
 use td_error::TdError;
-use td_objects::dxo::crudl::RequestContext;
-use td_objects::dxo::function::{FunctionDB, FunctionDBBuilder};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{RequestContext, handle_sql_err};
+use td_objects::dxo::dependency::DependencyDBWithNames;
+use td_objects::dxo::function::{FunctionDB, FunctionDBBuilder, FunctionDBWithNames};
 use td_objects::dxo::table::{TableDB, TableDBBuilder};
-use td_objects::types::basic::{FunctionStatus, FunctionVersionId, TableStatus, TableVersionId};
-use td_tower::extractors::Input;
+use td_objects::sql::DaoQueries;
+use td_objects::sql::cte::CteQueries;
+use td_objects::types::basic::{
+    AtTime, Cascade, FunctionStatus, FunctionVersionId, TableStatus, TableVersionId,
+};
+use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
+
+/// Refuses to delete a collection with dependents unless the deletion cascades. Dependents are
+/// the functions in the collection and the functions in other collections depending on its tables.
+pub async fn check_collection_dependents(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Connection(connection): Connection,
+    Input(cascade): Input<Cascade>,
+    Input(at_time): Input<AtTime>,
+    Input(collection): Input<CollectionDB>,
+) -> Result<(), TdError> {
+    if **cascade {
+        return Ok(());
+    }
+
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let functions: Vec<FunctionDBWithNames> = queries
+        .select_versions_at::<{ FunctionDBWithNames::Available }, FunctionDBWithNames>(
+            Some(at_time.deref()),
+            &collection.id,
+        )?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+
+    let tables: Vec<TableDB> = queries
+        .select_versions_at::<{ TableDB::Available }, TableDB>(
+            Some(at_time.deref()),
+            &collection.id,
+        )?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    let table_ids = tables.iter().map(|t| t.table_id).collect::<Vec<_>>();
+
+    let downstream_functions: Vec<FunctionDBWithNames> = if table_ids.is_empty() {
+        vec![]
+    } else {
+        let dependencies: Vec<DependencyDBWithNames> = queries
+            .find_versions_at::<{ DependencyDBWithNames::Active }, DependencyDBWithNames>(
+                Some(at_time.deref()),
+                &table_ids,
+            )?
+            .build_query_as()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+        let function_ids = dependencies
+            .iter()
+            .filter(|d| d.collection_id != collection.id)
+            .map(|d| d.function_id)
+            .unique()
+            .collect::<Vec<_>>();
+        if function_ids.is_empty() {
+            vec![]
+        } else {
+            queries
+                .find_versions_at::<{ FunctionDBWithNames::Active }, FunctionDBWithNames>(
+                    Some(at_time.deref()),
+                    &function_ids,
+                )?
+                .build_query_as()
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(handle_sql_err)?
+        }
+    };
+
+    let mut dependents = vec![];
+    if !functions.is_empty() {
+        let names = functions.iter().map(|f| f.name.to_string()).join(", ");
+        dependents.push(format!("functions [{names}]"));
+    }
+    if !downstream_functions.is_empty() {
+        let names = downstream_functions
+            .iter()
+            .map(|f| format!("{}/{}", f.collection, f.name))
+            .join(", ");
+        dependents.push(format!(
+            "functions in other collections depending on its tables [{names}]"
+        ));
+    }
+    if dependents.is_empty() {
+        Ok(())
+    } else {
+        Err(CollectionError::CollectionHasDependents(
+            collection.name.clone(),
+            dependents.join(", "),
+        ))?
+    }
+}
 
 pub async fn build_deleted_functions(
     Input(request_context): Input<RequestContext>,
@@ -2,5 +2,6 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+pub(crate) mod definition;
 pub(crate) mod delete;
 pub(crate) mod update;
@@ -4,6 +4,8 @@
 
 use crate::collection::service::create::CreateCollectionService;
 use crate::collection::service::delete::DeleteCollectionService;
+use crate::collection::service::export::ExportCollectionService;
+use crate::collection::service::import::ImportCollectionService;
 use crate::collection::service::list::ListCollectionsService;
 use crate::collection::service::read::ReadCollectionService;
 use crate::collection::service::update::UpdateCollectionService;
@@ -11,6 +13,8 @@ use ta_services::factory::ServiceFactory;
 
 mod create;
 mod delete;
+mod export;
+mod import;
 mod layer;
 mod list;
 mod read;
@@ -26,4 +30,6 @@ pub struct CollectionServices {
     pub update: UpdateCollectionService,
     pub delete: DeleteCollectionService,
     pub list: ListCollectionsService,
+    pub export: ExportCollectionService,
+    pub import: ImportCollectionService,
 }
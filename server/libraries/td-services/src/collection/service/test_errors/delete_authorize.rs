@@ -7,10 +7,11 @@ use ta_services::service::TdService;
 use td_database::sql::DbPool;
 use td_error::assert_service_error;
 use td_objects::dxo::crudl::RequestContext;
-use td_objects::rest_urls::CollectionParam;
+use td_objects::rest_urls::params::CollectionCascadeName;
+use td_objects::rest_urls::{CascadeParam, CollectionParam};
 use td_objects::test_utils::seed_collection::seed_collection;
 use td_objects::tower_service::authz::AuthzError;
-use td_objects::types::basic::{AccessTokenId, CollectionName, RoleId, UserId};
+use td_objects::types::basic::{AccessTokenId, Cascade, CollectionName, RoleId, UserId};
 
 #[td_test::test(sqlx)]
 #[tokio::test]
@@ -23,13 +24,17 @@ async fn test_not_allowed_to_delete_collection(db: DbPool) {
         .await;
 
     let request = RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user())
-        .delete(
+        .delete(CollectionCascadeName::new(
             CollectionParam::builder()
                 .try_collection(name.to_string())
                 .unwrap()
                 .build()
                 .unwrap(),
-        );
+            CascadeParam::builder()
+                .cascade(Cascade::default())
+                .build()
+                .unwrap(),
+        ));
 
     assert_service_error(service, request, |err| match err {
         AuthzError::Forbidden(_) => {}
@@ -30,6 +30,7 @@ use td_tower::layers;
     request = UpdateRequest<CollectionParam, CollectionUpdate>,
     response = CollectionRead,
     connection = TransactionProvider,
+    audit = CollectionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
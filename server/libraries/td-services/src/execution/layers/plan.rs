@@ -2,6 +2,7 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::function::layers::upload::{UploadError, check_storage_quota, collection_locations};
 use itertools::{Either, Itertools};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
@@ -9,12 +10,13 @@ use std::ops::Deref;
 use ta_execution::graphs::ExecutionGraph;
 use ta_execution::transaction::{TransactionMap, TransactionMapper};
 use td_error::TdError;
-use td_execution::planner::ExecutionPlanner;
+use td_execution::planner::{ExecutionPlanner, PlanEstimator};
 use td_execution::version_resolver::VersionResolver;
 use td_objects::dxo::crudl::handle_sql_err;
 use td_objects::dxo::execution::{
     ExecutionDB, ExecutionResponse, FunctionNodeResponseBuilder, TableNodeResponseBuilder,
 };
+use td_objects::dxo::function::FunctionDB;
 use td_objects::dxo::function_requirement::FunctionRequirementDB;
 use td_objects::dxo::function_run::{FunctionRunDB, FunctionRunDBBuilder, UpdateFunctionRunDB};
 use td_objects::dxo::table_data_version::{
@@ -22,12 +24,58 @@ use td_objects::dxo::table_data_version::{
 };
 use td_objects::dxo::transaction::{TransactionDB, TransactionDBBuilder};
 use td_objects::execution::graph::{GraphEdge, ResolvedVersion};
-use td_objects::sql::{DaoQueries, FindBy, UpdateBy};
+use td_objects::sql::{DaoQueries, FindBy, SelectBy, UpdateBy};
 use td_objects::table_ref::Versions;
 use td_objects::types::basic::{Dot, FunctionRunStatus, InputIdx, Trigger, VersionPos};
+use td_storage::Storage;
+use td_storage::location::StorageLocation;
+use td_storage::quota::StorageQuota;
 use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
 use te_execution::transaction::TransactionBy;
 
+/// Fails if a collection the execution writes tables to is over its [`StorageQuota`].
+///
+/// Table data is written by the function runs, after the execution is planned, so its size is
+/// not known here. It is counted in the collection usage once written, and a collection over
+/// its quota does not get new executions writing to it.
+pub async fn assert_storage_quota(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(storage): SrvCtx<Storage>,
+    SrvCtx(quota): SrvCtx<StorageQuota>,
+    Connection(connection): Connection,
+    Input(template): Input<ExecutionGraph<Versions>>,
+) -> Result<(), TdError> {
+    let functions: HashMap<_, _> = template
+        .output_tables()
+        .into_iter()
+        .filter(|(f, _, _)| quota.quota(&f.collection).is_some())
+        .map(|(f, _, _)| (&f.collection, &f.function_version_id))
+        .collect();
+    if functions.is_empty() {
+        return Ok(());
+    }
+
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+    for (collection, function_version_id) in functions {
+        let function: FunctionDB = queries
+            .select_by::<FunctionDB>(function_version_id)?
+            .build_query_as()
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+        let storage_location = StorageLocation::try_from(&function.storage_version)
+            .map_err(UploadError::InvalidStorageVersion)?;
+        let locations = collection_locations(
+            &storage_location,
+            &function.data_location,
+            &function.collection_id,
+        );
+        check_storage_quota(&storage, &quota, &locations, collection, 0).await?;
+    }
+    Ok(())
+}
+
 pub async fn build_transaction_map(
     SrvCtx(transaction_by): SrvCtx<TransactionBy>,
     Input(execution): Input<ExecutionDB>,
@@ -85,9 +133,12 @@ pub async fn build_function_runs(
         .trigger(Trigger::Manual)
         .build()?;
 
+    // function runs are created in execution order, the same on every planning of the graph
+    let triggered_functions = template.triggered_functions();
     let dependency_function_runs = template
-        .triggered_functions()
-        .iter()
+        .execution_order()?
+        .into_iter()
+        .filter(|f| triggered_functions.contains(f))
         .map(|f| {
             let transaction = transaction_map.get(&transaction_by.key(f)?)?;
             function_run_builder
@@ -413,6 +464,14 @@ pub async fn build_response(
         })
         .collect::<Result<Vec<_>, TdError>>()?;
 
+    // Estimates info
+    let estimated_input_rows = plan
+        .estimated_input_rows()?
+        .into_iter()
+        .map(|(f, rows)| (f.function_version_id, rows))
+        .collect::<HashMap<_, _>>();
+    let estimated_total_rows = plan.estimated_total_rows()?;
+
     let triggered_on = &execution.triggered_on;
     let dot = Dot::try_from(plan.dot().to_string())?;
 
@@ -431,6 +490,8 @@ pub async fn build_response(
         .user_tables(user_tables)
         .relations(relations)
         .relations_info(relations_info)
+        .estimated_input_rows(estimated_input_rows)
+        .estimated_total_rows(estimated_total_rows)
         .build()?;
     Ok(response)
 }
@@ -13,10 +13,11 @@ use td_objects::dxo::function_run::{
 use td_objects::dxo::request::FunctionOutput;
 use td_objects::dxo::request::v2::WrittenTableV2;
 use td_objects::dxo::table_data_version::{TableDataVersionDB, UpdateTableDataVersionDB};
+use td_objects::dxo::table_stats::TableStatsDB;
 use td_objects::dxo::worker::{CallbackRequest, UpdateWorkerDB, WorkerDB};
 use td_objects::sql::recursive::RecursiveQueries;
-use td_objects::sql::{DaoQueries, SelectBy, UpdateBy};
-use td_objects::types::basic::{FunctionRunId, FunctionRunStatus, WorkerId};
+use td_objects::sql::{DaoQueries, DeleteBy, Insert, SelectBy, UpdateBy};
+use td_objects::types::basic::{ColumnPos, FunctionRunId, FunctionRunStatus, WorkerId};
 use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, ReqCtx, SrvCtx};
 
 #[td_error]
@@ -288,6 +289,46 @@ pub async fn update_table_data_version_status(
                                 .await
                                 .map_err(handle_sql_err)?;
                             assert_one(res)?;
+
+                            if let Some((info, column_stats)) = has_data.and_then(|info| {
+                                info.column_stats.as_ref().map(|stats| (info, stats))
+                            }) {
+                                let table_data_version: TableDataVersionDB = queries
+                                    .select_by::<TableDataVersionDB>(&(
+                                        &*function_run_id,
+                                        table_name,
+                                    ))?
+                                    .build_query_as()
+                                    .fetch_one(&mut *conn)
+                                    .await
+                                    .map_err(handle_sql_err)?;
+
+                                // Reported stats replace any previous ones of the version.
+                                queries
+                                    .delete_by::<TableStatsDB>(&table_data_version.id)?
+                                    .build()
+                                    .execute(&mut *conn)
+                                    .await
+                                    .map_err(handle_sql_err)?;
+
+                                for (pos, column) in column_stats.iter().enumerate() {
+                                    let stats = TableStatsDB::builder()
+                                        .table_data_version_id(table_data_version.id)
+                                        .column_name(column.name.clone())
+                                        .column_pos(ColumnPos::try_from(pos as i32)?)
+                                        .row_count(info.row_count.clone())
+                                        .null_count(column.null_count.clone())
+                                        .min_value(column.min.clone())
+                                        .max_value(column.max.clone())
+                                        .build()?;
+                                    queries
+                                        .insert(&stats)?
+                                        .build()
+                                        .execute(&mut *conn)
+                                        .await
+                                        .map_err(handle_sql_err)?;
+                                }
+                            }
                             Ok::<_, TdError>(())
                         }
                     })
@@ -5,6 +5,7 @@
 use crate::execution::layers::update_status::{
     update_function_run_status, update_table_data_version_status, update_worker_status,
 };
+use crate::function_run::layers::webhook::enqueue_function_run_webhooks;
 use ta_services::factory::service_factory;
 use td_objects::dxo::crudl::UpdateRequest;
 use td_objects::dxo::function_run::{
@@ -29,6 +30,7 @@ use td_tower::layers;
     request = UpdateRequest<FunctionRunIdParam, CallbackRequest>,
     response = (),
     connection = TransactionProvider,
+    audit = FunctionRunId,
     context = DaoQueries,
 )]
 fn service() {
@@ -60,6 +62,8 @@ fn service() {
         from_fn(update_function_run_status),
         // Update table data versions status.
         from_fn(update_table_data_version_status),
+        // Notify webhooks of completed and failed function runs.
+        from_fn(enqueue_function_run_webhooks),
     )
 }
 
@@ -78,13 +82,14 @@ mod tests {
     use td_objects::dxo::crudl::{RequestContext, handle_sql_err};
     use td_objects::dxo::function_run::FunctionRunDBWithNames;
     use td_objects::dxo::request::FunctionOutput;
-    use td_objects::dxo::request::v2::{FunctionOutputV2, TableInfo, WrittenTableV2};
+    use td_objects::dxo::request::v2::{ColumnStats, FunctionOutputV2, TableInfo, WrittenTableV2};
     use td_objects::dxo::table_data_version::TableDataVersionDBWithNames;
+    use td_objects::dxo::table_stats::TableStatsDB;
     use td_objects::sql::SelectBy;
     use td_objects::types::basic::{
-        AccessTokenId, CollectionName, ColumnCount, ExecutionStatus, FunctionName,
-        FunctionRunStatus, RoleId, RowCount, SchemaHash, TableName, TableNameDto,
-        TransactionStatus, UserId, WorkerId,
+        AccessTokenId, CollectionName, ColumnCount, ColumnMaxValue, ColumnMinValue,
+        ExecutionStatus, FunctionName, FunctionRunStatus, NullCount, RoleId, RowCount,
+        SchemaFieldName, SchemaHash, TableName, TableNameDto, TransactionStatus, UserId, WorkerId,
     };
     use td_objects::types::composed::TableDependencyDto;
     use td_tower::ctx_service::RawOneshot;
@@ -128,6 +133,8 @@ mod tests {
                 type_of_val(&update_function_run_status),
                 // Update table data versions status.
                 type_of_val(&update_table_data_version_status),
+                // Notify webhooks of completed and failed function runs.
+                type_of_val(&enqueue_function_run_webhooks),
             ]);
     }
 
@@ -585,4 +592,118 @@ mod tests {
 
         Ok(())
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_callback_table_stats(db: DbPool) -> Result<(), TdError> {
+        // Stats of the table a: [3, null, -2, 5], b: ["x", "y", null, "z"], c: [null x 4]
+        let column_stats = vec![
+            ColumnStats::builder()
+                .name(SchemaFieldName::try_from("a")?)
+                .null_count(NullCount::try_from(1i64)?)
+                .min(Some(ColumnMinValue::try_from(-2.0)?))
+                .max(Some(ColumnMaxValue::try_from(5.0)?))
+                .build()?,
+            ColumnStats::builder()
+                .name(SchemaFieldName::try_from("b")?)
+                .null_count(NullCount::try_from(1i64)?)
+                .build()?,
+            ColumnStats::builder()
+                .name(SchemaFieldName::try_from("c")?)
+                .null_count(NullCount::try_from(4i64)?)
+                .build()?,
+        ];
+
+        test_callback(
+            db.clone(),
+            vec![TestExecution {
+                expected_status: ExecutionStatus::Finished,
+                transactions: vec![TestTransaction {
+                    expected_status: TransactionStatus::Committed,
+                    functions: vec![TestFunction {
+                        collection: CollectionName::try_from("c_0")?,
+                        name: FunctionName::try_from("f_0")?,
+                        dependencies: vec![],
+                        tables: vec![
+                            TableNameDto::try_from("t_0")?,
+                            TableNameDto::try_from("t_1")?,
+                        ],
+                        initial_status: FunctionRunStatus::Running,
+                        expected_status: FunctionRunStatus::Committed,
+                    }],
+                }],
+            }],
+            "f_0",
+            WorkerCallbackStatus::Done,
+            Some(FunctionOutput::V2(
+                FunctionOutputV2::builder()
+                    .output(vec![
+                        WrittenTableV2::Data {
+                            table: TableName::try_from("t_0")?,
+                            info: TableInfo::builder()
+                                .column_count(ColumnCount::try_from(3i64)?)
+                                .row_count(RowCount::try_from(4i64)?)
+                                .schema_hash(SchemaHash::try_from("hash")?)
+                                .column_stats(Some(column_stats))
+                                .build()?,
+                        },
+                        // Without stats, as reported by workers not computing them.
+                        WrittenTableV2::Data {
+                            table: TableName::try_from("t_1")?,
+                            info: TableInfo::builder()
+                                .column_count(ColumnCount::try_from(1i64)?)
+                                .row_count(RowCount::try_from(2i64)?)
+                                .schema_hash(SchemaHash::try_from("hash")?)
+                                .build()?,
+                        },
+                    ])
+                    .build()?,
+            )),
+        )
+        .await?;
+
+        let queries = DaoQueries::default();
+        let table_stats = async |table: &str| -> Result<Vec<TableStatsDB>, TdError> {
+            let table_data_version: TableDataVersionDBWithNames = queries
+                .select_by::<TableDataVersionDBWithNames>(&(&TableName::try_from(table)?))?
+                .build_query_as()
+                .fetch_one(&db)
+                .await
+                .map_err(handle_sql_err)?;
+            let mut table_stats: Vec<TableStatsDB> = queries
+                .select_by::<TableStatsDB>(&table_data_version.id)?
+                .build_query_as()
+                .fetch_all(&db)
+                .await
+                .map_err(handle_sql_err)?;
+            table_stats.sort_by_key(|stats| stats.column_pos.clone());
+            Ok(table_stats)
+        };
+
+        let stats = table_stats("t_0").await?;
+        let stats: Vec<_> = stats
+            .iter()
+            .map(|s| {
+                (
+                    s.column_name.as_str(),
+                    *s.column_pos,
+                    *s.row_count,
+                    *s.null_count,
+                    s.min_value.as_ref().map(|v| **v),
+                    s.max_value.as_ref().map(|v| **v),
+                )
+            })
+            .collect();
+        assert_eq!(
+            stats,
+            vec![
+                ("a", 0, 4, 1, Some(-2.0), Some(5.0)),
+                ("b", 1, 4, 1, None, None),
+                ("c", 2, 4, 4, None, None),
+            ]
+        );
+
+        assert!(table_stats("t_1").await?.is_empty());
+        Ok(())
+    }
 }
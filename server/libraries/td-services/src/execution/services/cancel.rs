@@ -23,6 +23,7 @@ use td_tower::layers;
     request = UpdateRequest<ExecutionParam, ()>,
     response = (),
     connection = TransactionProvider,
+    audit = ExecutionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -3,8 +3,8 @@
 //
 
 use crate::execution::layers::plan::{
-    build_execution_plan, build_function_requirements, build_function_runs, build_response,
-    build_table_data_versions, build_transaction_map, build_transactions,
+    assert_storage_quota, build_execution_plan, build_function_requirements, build_function_runs,
+    build_response, build_table_data_versions, build_transaction_map, build_transactions,
     update_initial_function_run_status,
 };
 use crate::execution::layers::template::{
@@ -35,8 +35,10 @@ use td_objects::tower_service::from::{
 };
 use td_objects::tower_service::sql::{By, SqlSelectService, insert, insert_vec};
 use td_objects::types::basic::{
-    AtTime, CollectionId, CollectionIdName, FunctionId, FunctionIdName,
+    AtTime, CollectionId, CollectionIdName, ExecutionId, FunctionId, FunctionIdName,
 };
+use td_storage::Storage;
+use td_storage::quota::StorageQuota;
 use td_tower::default_services::TransactionProvider;
 use td_tower::from_fn::from_fn;
 use td_tower::layers;
@@ -47,9 +49,12 @@ use te_execution::transaction::TransactionBy;
     request = CreateRequest<FunctionParam, ExecutionRequest>,
     response = ExecutionResponse,
     connection = TransactionProvider,
+    audit = ExecutionId,
     context = DaoQueries,
     context = AuthzContext,
     context = TransactionBy,
+    context = Storage,
+    context = StorageQuota,
 )]
 fn service() {
     layers!(
@@ -92,6 +97,8 @@ fn service() {
         from_fn(With::<TriggerDBWithNames>::vec_convert_to::<InterCollectionAccessBuilder, _>),
         from_fn(With::<InterCollectionAccessBuilder>::vec_build::<InterCollectionAccess, _>),
         from_fn(Authz::<InterColl>::check_inter_collection),
+        // Check collections written to are not over their storage quota
+        from_fn(assert_storage_quota),
         // Create execution plan.
         // Build execution
         from_fn(With::<FunctionDBWithNames>::convert_to::<ExecutionDBBuilder, _>),
@@ -99,6 +106,7 @@ fn service() {
         from_fn(With::<ExecutionRequest>::update::<ExecutionDBBuilder, _>),
         from_fn(With::<ExecutionDBBuilder>::build::<ExecutionDB, _>),
         from_fn(insert::<ExecutionDB>),
+        from_fn(With::<ExecutionDB>::extract::<ExecutionId>),
         // Build transactions
         from_fn(build_transaction_map),
         from_fn(With::<ExecutionDB>::convert_to::<TransactionDBBuilder, _>),
@@ -211,6 +219,8 @@ pub(crate) mod tests {
                     type_of_val(&With::<TriggerDBWithNames>::vec_convert_to::<InterCollectionAccessBuilder, _>),
                     type_of_val(&With::<InterCollectionAccessBuilder>::vec_build::<InterCollectionAccess, _>),
                     type_of_val(&Authz::<InterColl>::check_inter_collection),
+                    // Check collections written to are not over their storage quota
+                    type_of_val(&assert_storage_quota),
                     // Create execution plan.
                     // Build execution
                     type_of_val(&With::<FunctionDBWithNames>::convert_to::<ExecutionDBBuilder, _>),
@@ -218,6 +228,7 @@ pub(crate) mod tests {
                     type_of_val(&With::<ExecutionRequest>::update::<ExecutionDBBuilder, _>),
                     type_of_val(&With::<ExecutionDBBuilder>::build::<ExecutionDB, _>),
                     type_of_val(&insert::<ExecutionDB>),
+                    type_of_val(&With::<ExecutionDB>::extract::<ExecutionId>),
                     // Build transactions
                     type_of_val(&build_transaction_map),
                     type_of_val(&With::<ExecutionDB>::convert_to::<TransactionDBBuilder, _>),
@@ -802,4 +813,81 @@ pub(crate) mod tests {
         assert_eq!(function_run.status, expected_initial_status);
         Ok(())
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_execute_storage_quota(db: DbPool) -> Result<(), TdError> {
+        use crate::Context;
+        use crate::function::layers::upload::UploadError;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+        use td_storage::location::StorageLocation;
+
+        let collection_name = CollectionName::try_from("collection")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+
+        let create = FunctionRegister::builder()
+            .try_name("function_0")?
+            .try_description("foo description")?
+            .bundle_id(BundleId::default())
+            .try_snippet("foo snippet")?
+            .decorator(Decorator::Publisher)
+            .dependencies(None)
+            .triggers(None)
+            .tables(vec![TableNameDto::try_from("table_0")?])
+            .runtime_values(FunctionRuntimeValues::try_from("foo runtime values")?)
+            .reuse_frozen_tables(false)
+            .build()?;
+        let function = seed_function(&db, &collection, &create).await;
+
+        let mut context = Context::with_defaults(db.clone());
+        context.storage_quota = Arc::new(StorageQuota::new(
+            None,
+            HashMap::from([(collection_name.clone(), 10)]),
+        ));
+
+        let execute = || {
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                FunctionParam::builder()
+                    .try_collection(collection.name.to_string())
+                    .unwrap()
+                    .try_function("function_0")
+                    .unwrap()
+                    .build()
+                    .unwrap(),
+                ExecutionRequest::builder().name(None).build().unwrap(),
+            )
+        };
+
+        // table data written by previous executions counts in the collection usage
+        let (collection_location, _) = StorageLocation::try_from(&function.storage_version)
+            .unwrap()
+            .builder(&function.data_location)
+            .collection(&collection.id)
+            .build();
+        let table_data = collection_location.child("table_data")?;
+        context.storage.write(&table_data, vec![0; 10]).await?;
+
+        // at the quota
+        ExecuteFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(execute())
+            .await?;
+
+        // over the quota
+        context.storage.write(&table_data, vec![0; 11]).await?;
+        let err = ExecuteFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(execute())
+            .await
+            .unwrap_err();
+        let err = err.domain_err::<UploadError>();
+        assert!(matches!(
+            err,
+            UploadError::StorageQuotaExceeded(name, 0, 11, 10) if *name == collection_name
+        ));
+        Ok(())
+    }
 }
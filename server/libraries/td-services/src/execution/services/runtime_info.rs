@@ -6,10 +6,11 @@ use crate::execution::layers::runtime_info::runtime_info;
 use getset::Getters;
 use serde::de::DeserializeOwned;
 use ta_services::factory::service_factory;
+use td_common::about::build_info;
 use td_common::server::{EtcContent, etc_service};
 use td_error::{TdError, td_error};
 use td_objects::dxo::crudl::ReadRequest;
-use td_objects::dxo::runtime_info::{PythonVersions, RuntimeInfo, ServerVersion};
+use td_objects::dxo::runtime_info::{BuildInfo, PythonVersions, RuntimeInfo, ServerVersion};
 use td_objects::types::basic::{BuildManifest, TabsdataVersion};
 use td_tower::from_fn::from_fn;
 use td_tower::layers;
@@ -35,6 +36,7 @@ impl RuntimeContext {
     pub async fn new() -> Result<Self, TdError> {
         let info = RuntimeInfo::builder()
             .version(server_version().await?)
+            .build_info(BuildInfo::try_from(build_info())?)
             .build_manifest(build_manifest().await?)
             .python_versions(valid_python_versions().await?.versions.clone())
             .build()?;
@@ -48,6 +50,7 @@ impl Default for RuntimeContext {
         RuntimeContext {
             info: RuntimeInfo::builder()
                 .version(TabsdataVersion::try_from("-unknown-").unwrap())
+                .build_info(BuildInfo::try_from(build_info()).unwrap())
                 .build_manifest(BuildManifest::default())
                 .python_versions(vec![])
                 .build()
@@ -116,6 +119,7 @@ async fn build_manifest() -> Result<BuildManifest, TdError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use td_objects::types::basic::Edition;
 
     #[cfg(feature = "test_tower_metadata")]
     #[tokio::test]
@@ -137,6 +141,15 @@ mod tests {
     async fn test_runtime_info() {
         let res = RuntimeContext::new().await;
         assert!(res.is_ok());
-        assert!(res.unwrap().info().python_versions.is_empty());
+        let res = res.unwrap();
+        assert!(res.info().python_versions.is_empty());
+        assert_eq!(
+            res.info().build_info.version,
+            TabsdataVersion::try_from(build_info().version).unwrap()
+        );
+        assert_eq!(
+            res.info().build_info.edition,
+            Edition::try_from(build_info().edition).unwrap()
+        );
     }
 }
@@ -0,0 +1,167 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use std::ops::Deref;
+use td_error::{TdError, td_error};
+use td_objects::dxo::bundle::{BundleBlobDB, BundleDB, UpdateBundleBlobDB};
+use td_objects::dxo::crudl::handle_sql_err;
+use td_objects::dxo::function::FunctionDBWithNames;
+use td_objects::sql::{DaoQueries, DeleteBy, Insert, SelectBy, UpdateBy};
+use td_objects::types::basic::{BundleId, BundleRefCount};
+use td_storage::Storage;
+use td_storage::location::StorageLocation;
+use td_tower::extractors::{Connection, Input, SrvCtx};
+use tracing::warn;
+
+#[td_error]
+enum BundleError {
+    #[error("Invalid storage version: {0}")]
+    InvalidStorageVersion(String) = 5000,
+}
+
+/// Inserts the uploaded bundle, pending to be registered, unless the same bundle (a bundle with
+/// identical content) is already pending.
+pub async fn insert_pending_bundle(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Input(bundle): Input<BundleDB>,
+) -> Result<BundleDB, TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let pending: Option<BundleDB> = queries
+        .select_by::<BundleDB>(&bundle.id)?
+        .build_query_as()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    if let Some(pending) = pending {
+        return Ok(pending);
+    }
+
+    queries
+        .insert(bundle.deref())?
+        .build()
+        .execute(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    Ok(bundle.deref().clone())
+}
+
+/// Adds a reference to the blob of the bundle of a new function version. Bundles without a blob
+/// (uploaded before blobs were tracked) are ignored.
+pub async fn reference_bundle_blob(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Input(bundle_id): Input<BundleId>,
+) -> Result<(), TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let blob: Option<BundleBlobDB> = queries
+        .select_by::<BundleBlobDB>(bundle_id.deref())?
+        .build_query_as()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    if let Some(blob) = blob {
+        let update = UpdateBundleBlobDB::builder()
+            .ref_count(BundleRefCount::try_from(*blob.ref_count + 1)?)
+            .build()?;
+        queries
+            .update_by::<_, BundleBlobDB>(&update, &blob.id)?
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+    }
+    Ok(())
+}
+
+/// Releases the reference to the blob of the bundle of a replaced or deleted function version.
+///
+/// The blob is released, but not deleted, once no function version uses it. Storage is not
+/// transactional, so released blobs are purged by [`purge_released_bundle_blobs`] after the
+/// release is committed.
+pub async fn release_bundle_blob(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Input(function): Input<FunctionDBWithNames>,
+) -> Result<(), TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let blob: Option<BundleBlobDB> = queries
+        .select_by::<BundleBlobDB>(&function.bundle_id)?
+        .build_query_as()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    if let Some(blob) = blob {
+        let update = UpdateBundleBlobDB::builder()
+            .ref_count(BundleRefCount::try_from((*blob.ref_count - 1).max(0))?)
+            .build()?;
+        queries
+            .update_by::<_, BundleBlobDB>(&update, &blob.id)?
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+    }
+    Ok(())
+}
+
+/// Condition of the blobs no function version uses and no upload of is pending to be registered.
+const RELEASED_BUNDLE_BLOBS: &str =
+    "ref_count = 0 AND NOT EXISTS (SELECT 1 FROM bundles WHERE bundles.id = bundle_blobs.id)";
+
+/// Purges the released bundle blobs, see [`release_bundle_blob`].
+///
+/// Each blob is deleted from the database, only if still released, before deleting it from
+/// storage. A blob referenced again meanwhile is kept, and a blob in the database is never
+/// missing from storage. Blobs that fail to be deleted from storage are left behind.
+pub async fn purge_released_bundle_blobs(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(storage): SrvCtx<Storage>,
+) -> Result<(), TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let mut query = queries.select_by::<BundleBlobDB>(&())?;
+    query.push(" WHERE ");
+    query.push(RELEASED_BUNDLE_BLOBS);
+    let blobs: Vec<BundleBlobDB> = query
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+
+    for blob in blobs {
+        let mut query = queries.delete_by::<BundleBlobDB>(&blob.id)?;
+        query.push(" AND ");
+        query.push(RELEASED_BUNDLE_BLOBS);
+        let deleted = query
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?
+            .rows_affected();
+        if deleted == 0 {
+            continue;
+        }
+
+        let storage_location = StorageLocation::try_from(&blob.storage_version)
+            .map_err(BundleError::InvalidStorageVersion)?;
+        let (location, _) = storage_location
+            .builder(&blob.data_location)
+            .collection(&blob.collection_id)
+            .function(&blob.id)
+            .build();
+        if let Err(e) = storage.delete(&location).await {
+            warn!("Could not delete the released function bundle blob {location}: {e}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,310 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::upload::{
+    UploadError, bundle_hash, check_storage_quota, collection_locations, read_function_upload,
+    store_bundle,
+};
+use std::time::Duration;
+use td_common::time::UniqueUtc;
+use td_error::TdError;
+use td_objects::dxo::bundle::{BundleBlobDB, BundleUploadCommit, BundleUploadDB};
+use td_objects::dxo::crudl::{AuditContext, RequestContext, handle_sql_err};
+use td_objects::dxo::function_upload::FunctionUpload;
+use td_objects::sql::{DaoQueries, DeleteBy, Insert, SelectBy};
+use td_objects::types::basic::{
+    AtTime, BundleChunkOffset, BundleId, BundleUploadId, CollectionId, CollectionName,
+    DataLocation, StorageVersion,
+};
+use td_storage::location::StorageLocation;
+use td_storage::quota::StorageQuota;
+use td_storage::{SPath, Storage};
+use td_tower::extractors::{Connection, Input, SrvCtx};
+use tracing::{debug, warn};
+
+/// Time a chunked upload can take, from its start to its commit.
+pub const BUNDLE_UPLOAD_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Extension of the stored chunks of a chunked upload.
+const CHUNK_EXTENSION: &str = "part";
+
+/// Location of a chunked upload, holding its chunks.
+fn upload_location(
+    storage_version: &StorageVersion,
+    data_location: &DataLocation,
+    collection_id: &CollectionId,
+    upload_id: &BundleUploadId,
+) -> Result<SPath, UploadError> {
+    let storage_location =
+        StorageLocation::try_from(storage_version).map_err(UploadError::InvalidStorageVersion)?;
+    let (location, _) = storage_location
+        .builder(data_location)
+        .collection(collection_id)
+        .upload(upload_id)
+        .build();
+    Ok(location)
+}
+
+/// Returns the stored chunks of a chunked upload, with their offsets, sorted by offset.
+async fn upload_chunks(
+    storage: &Storage,
+    location: &SPath,
+) -> Result<Vec<(i64, SPath)>, UploadError> {
+    let mut chunks = storage
+        .list(location)
+        .await
+        .map_err(UploadError::BundleUploadChunkReadFailed)?
+        .into_iter()
+        .filter(|chunk| chunk.extension() == Some(CHUNK_EXTENSION))
+        .map(|chunk| {
+            chunk
+                .last_element()
+                .and_then(|name| name.strip_suffix(&format!(".{CHUNK_EXTENSION}")))
+                .and_then(|offset| offset.parse::<i64>().ok())
+                .map(|offset| (offset, chunk.clone()))
+                .ok_or(UploadError::InvalidBundleChunk(chunk))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    chunks.sort_by_key(|(offset, _)| *offset);
+    Ok(chunks)
+}
+
+/// Deletes the stored chunks of a chunked upload. Failures are logged, as leftover chunks do not
+/// affect other uploads.
+async fn delete_upload_chunks(storage: &Storage, location: &SPath) {
+    let chunks = match upload_chunks(storage, location).await {
+        Ok(chunks) => chunks
+            .into_iter()
+            .map(|(_, chunk)| chunk)
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            warn!("Could not list the chunks of function bundle upload {location}: {e}");
+            return;
+        }
+    };
+    match storage.delete_all(&chunks).await {
+        Ok(results) => {
+            for (chunk, result) in results {
+                if let Err(e) = result {
+                    warn!(
+                        "Could not delete chunk {chunk} of function bundle upload {location}: {e}"
+                    );
+                }
+            }
+        }
+        Err(e) => warn!("Could not delete the chunks of function bundle upload {location}: {e}"),
+    }
+}
+
+/// Starts a chunked upload of a function bundle in the collection, purging the expired uploads
+/// of the collection.
+pub async fn start_bundle_upload(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(storage): SrvCtx<Storage>,
+    Input(request_context): Input<RequestContext>,
+    Input(storage_version): Input<StorageVersion>,
+    Input(data_location): Input<DataLocation>,
+    Input(collection_id): Input<CollectionId>,
+) -> Result<BundleUploadDB, TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let now = AtTime::now();
+    let uploads: Vec<BundleUploadDB> = queries
+        .select_by::<BundleUploadDB>(&*collection_id)?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    for upload in uploads
+        .iter()
+        .filter(|upload| upload.expires_on.timestamp_millis() <= now.timestamp_millis())
+    {
+        queries
+            .delete_by::<BundleUploadDB>(&upload.id)?
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+        let location =
+            upload_location(&storage_version, &data_location, &collection_id, &upload.id)?;
+        delete_upload_chunks(&storage, &location).await;
+        debug!("Purged expired function bundle upload {}", upload.id);
+    }
+
+    let upload = BundleUploadDB::builder()
+        .id(BundleUploadId::default())
+        .collection_id(*collection_id)
+        .audit(&AuditContext::from(&*request_context))
+        .expires_on((UniqueUtc::now_millis() + BUNDLE_UPLOAD_TTL).try_into()?)
+        .build()?;
+    queries
+        .insert(&upload)?
+        .build()
+        .execute(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    Ok(upload)
+}
+
+/// Selects a chunked upload of the collection, failing if it does not exist or has expired.
+pub async fn select_bundle_upload(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Input(collection_id): Input<CollectionId>,
+    Input(upload_id): Input<BundleUploadId>,
+) -> Result<BundleUploadDB, TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let upload: Option<BundleUploadDB> = queries
+        .select_by::<BundleUploadDB>(&(&*collection_id, &*upload_id))?
+        .build_query_as()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    let upload = upload.ok_or(UploadError::BundleUploadNotFound(*upload_id))?;
+    if upload.expires_on.timestamp_millis() <= AtTime::now().timestamp_millis() {
+        Err(UploadError::BundleUploadExpired(*upload_id))?;
+    }
+    Ok(upload)
+}
+
+/// Stores a chunk of a chunked upload, at its offset. Storing a chunk again at the same offset
+/// replaces it, so failed chunks can be retried.
+///
+/// The size of the upload so far, this chunk included, must fit in the collection storage quota,
+/// so uploads cannot fill the storage with chunks before being committed.
+#[allow(clippy::too_many_arguments)]
+pub async fn write_bundle_upload_chunk(
+    SrvCtx(storage): SrvCtx<Storage>,
+    SrvCtx(quota): SrvCtx<StorageQuota>,
+    Input(storage_version): Input<StorageVersion>,
+    Input(data_location): Input<DataLocation>,
+    Input(collection_name): Input<CollectionName>,
+    Input(upload): Input<BundleUploadDB>,
+    Input(offset): Input<BundleChunkOffset>,
+    Input(request): Input<FunctionUpload>,
+) -> Result<(), TdError> {
+    let bytes = read_function_upload(&request).await?;
+    let storage_location =
+        StorageLocation::try_from(&*storage_version).map_err(UploadError::InvalidStorageVersion)?;
+    let (location, _) = storage_location
+        .builder(&data_location)
+        .collection(&upload.collection_id)
+        .upload(&upload.id)
+        .chunk(&offset)
+        .build();
+
+    // held until the chunk is written, so concurrent writes cannot exceed the quota together
+    let _quota_lock = quota.lock(&collection_name).await;
+    if quota.quota(&collection_name).is_some() {
+        // the chunk being replaced does not count
+        if storage
+            .exists(&location)
+            .await
+            .map_err(UploadError::BundleUploadChunkReadFailed)?
+        {
+            storage
+                .delete(&location)
+                .await
+                .map_err(UploadError::FunctionBundleSaveFailed)?;
+        }
+        let upload_location = upload_location(
+            &storage_version,
+            &data_location,
+            &upload.collection_id,
+            &upload.id,
+        )?;
+        let uploaded = storage
+            .usage(&upload_location)
+            .await
+            .map_err(|e| UploadError::StorageUsageCheckFailed(collection_name.clone(), e))?;
+        check_storage_quota(
+            &storage,
+            &quota,
+            &collection_locations(&storage_location, &data_location, &upload.collection_id),
+            &collection_name,
+            uploaded + bytes.len() as u64,
+        )
+        .await?;
+    }
+
+    storage
+        .write(&location, bytes)
+        .await
+        .map_err(UploadError::FunctionBundleSaveFailed)?;
+    Ok(())
+}
+
+/// Assembles the chunks of a chunked upload into a function bundle, validating its size and,
+/// if given, its hash, and stores it (see [`store_bundle`]). The chunks are deleted once the
+/// bundle is stored.
+#[allow(clippy::too_many_arguments)]
+pub async fn commit_bundle_upload(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(storage): SrvCtx<Storage>,
+    SrvCtx(quota): SrvCtx<StorageQuota>,
+    Input(bundle_id): Input<BundleId>,
+    Input(storage_version): Input<StorageVersion>,
+    Input(data_location): Input<DataLocation>,
+    Input(collection_id): Input<CollectionId>,
+    Input(collection_name): Input<CollectionName>,
+    Input(upload): Input<BundleUploadDB>,
+    Input(commit): Input<BundleUploadCommit>,
+) -> Result<BundleBlobDB, TdError> {
+    let location = upload_location(&storage_version, &data_location, &collection_id, &upload.id)?;
+
+    let mut bytes = Vec::new();
+    for (offset, chunk) in upload_chunks(&storage, &location).await? {
+        let expected = bytes.len() as i64;
+        if offset > expected {
+            Err(UploadError::MissingBundleChunk(upload.id, expected))?;
+        } else if offset < expected {
+            Err(UploadError::OverlappingBundleChunk(upload.id, offset))?;
+        }
+        bytes.extend(
+            storage
+                .read(&chunk)
+                .await
+                .map_err(UploadError::BundleUploadChunkReadFailed)?,
+        );
+    }
+
+    let size = bytes.len() as i64;
+    if size < *commit.size {
+        // the last chunks are missing
+        Err(UploadError::MissingBundleChunk(upload.id, size))?;
+    } else if size > *commit.size {
+        Err(UploadError::BundleSizeMismatch(commit.size, size))?;
+    }
+    if let Some(hash) = &commit.hash {
+        let uploaded = bundle_hash(&bytes)?;
+        if *hash != uploaded {
+            Err(UploadError::BundleHashMismatch(hash.clone(), uploaded))?;
+        }
+    }
+
+    let blob = {
+        let mut conn = connection.lock().await;
+        let conn = conn.get_mut_connection()?;
+        store_bundle(
+            conn,
+            &queries,
+            &storage,
+            &quota,
+            &bundle_id,
+            &storage_version,
+            &data_location,
+            &collection_id,
+            &collection_name,
+            bytes,
+        )
+        .await?
+    };
+    delete_upload_chunks(&storage, &location).await;
+    Ok(blob)
+}
@@ -0,0 +1,226 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::read::create_table_dependencies;
+use itertools::Itertools;
+use sqlx::SqliteConnection;
+use std::collections::HashMap;
+use std::ops::Deref;
+use td_error::{TdError, td_error};
+use td_objects::dxo::crudl::handle_sql_err;
+use td_objects::dxo::dependency::DependencyDBRead;
+use td_objects::dxo::function::{
+    FunctionDB, FunctionDBWithNames, FunctionDependencyChange, FunctionDiff,
+};
+use td_objects::dxo::table::{TableDBRead, TableDBWithNames};
+use td_objects::dxo::trigger::TriggerDBWithNames;
+use td_objects::sql::cte::CteQueries;
+use td_objects::sql::{DaoQueries, SelectBy};
+use td_objects::types::basic::{
+    AtTime, DependencyPos, FromFunctionVersionId, FunctionName, FunctionVersionId, Snippet,
+    SnippetChanged, TableId, TableName, ToFunctionVersionId,
+};
+use td_objects::types::composed::{TableDependency, TableTrigger};
+use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
+
+#[td_error]
+pub enum DiffFunctionError {
+    #[error("Function version '{0}' is not a version of function '{1}'")]
+    NotAFunctionVersion(FunctionVersionId, FunctionName) = 0,
+}
+
+/// Tables, dependencies (with their position) and triggers of a function version, as defined
+/// with it.
+struct FunctionVersionContent {
+    snippet: Snippet,
+    tables: Vec<TableName>,
+    dependencies: Vec<(DependencyPos, TableDependency)>,
+    triggers: Vec<TableTrigger>,
+}
+
+/// Diffs two versions of a function. Each version is read at its own definition time, and
+/// dependencies on the same table, at the same position, with different versions are reported
+/// as changed. A function can depend on the same table more than once, at different positions.
+pub async fn diff_function_versions(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Connection(connection): Connection,
+    Input(function): Input<FunctionDBWithNames>,
+    Input(from): Input<FromFunctionVersionId>,
+    Input(to): Input<ToFunctionVersionId>,
+) -> Result<FunctionDiff, TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let from_id = FunctionVersionId::try_from(from.deref())?;
+    let to_id = FunctionVersionId::try_from(to.deref())?;
+    let from_content = read_version_content(&queries, &mut *conn, &function, &from_id).await?;
+    let to_content = read_version_content(&queries, &mut *conn, &function, &to_id).await?;
+
+    let (added_tables, removed_tables) = added_removed(&from_content.tables, &to_content.tables);
+    let (added_triggers, removed_triggers) =
+        added_removed(&from_content.triggers, &to_content.triggers);
+
+    let dependency_key = |(pos, d): &(DependencyPos, TableDependency)| {
+        (d.collection.clone(), d.table.clone(), pos.clone())
+    };
+    let from_dependencies = from_content
+        .dependencies
+        .iter()
+        .map(|d| (dependency_key(d), &d.1))
+        .collect::<HashMap<_, _>>();
+    let to_dependencies = to_content
+        .dependencies
+        .iter()
+        .map(|d| (dependency_key(d), &d.1))
+        .collect::<HashMap<_, _>>();
+    let added_dependencies = to_content
+        .dependencies
+        .iter()
+        .filter(|d| !from_dependencies.contains_key(&dependency_key(d)))
+        .map(|(_, d)| d.clone())
+        .collect();
+    let removed_dependencies = from_content
+        .dependencies
+        .iter()
+        .filter(|d| !to_dependencies.contains_key(&dependency_key(d)))
+        .map(|(_, d)| d.clone())
+        .collect();
+    let changed_dependencies = to_content
+        .dependencies
+        .iter()
+        .filter_map(|to| {
+            let from = from_dependencies.get(&dependency_key(to))?;
+            let to = &to.1;
+            (from.versions != to.versions).then(|| FunctionDependencyChange {
+                from: (*from).clone(),
+                to: to.clone(),
+            })
+        })
+        .collect();
+
+    Ok(FunctionDiff {
+        from: from_id,
+        to: to_id,
+        added_tables,
+        removed_tables,
+        added_dependencies,
+        removed_dependencies,
+        changed_dependencies,
+        added_triggers,
+        removed_triggers,
+        snippet_changed: SnippetChanged::from(from_content.snippet != to_content.snippet),
+    })
+}
+
+async fn read_version_content(
+    queries: &DaoQueries,
+    conn: &mut SqliteConnection,
+    function: &FunctionDBWithNames,
+    version_id: &FunctionVersionId,
+) -> Result<FunctionVersionContent, TdError> {
+    let version: FunctionDB = queries
+        .select_by::<FunctionDB>(version_id)?
+        .build_query_as()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?
+        .filter(|v| v.function_id == function.function_id)
+        .ok_or_else(|| {
+            DiffFunctionError::NotAFunctionVersion(*version_id, function.name.clone())
+        })?;
+    let at_time = version.defined_on.clone();
+
+    let tables: Vec<TableDBRead> = queries
+        .select_versions_at::<{ TableDBRead::Active }, TableDBRead>(
+            Some(&at_time),
+            &version.function_id,
+        )?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    let tables = tables.into_iter().map(|t| t.name).collect();
+
+    let triggers: Vec<TriggerDBWithNames> = queries
+        .select_versions_at::<{ TriggerDBWithNames::Active }, TriggerDBWithNames>(
+            Some(&at_time),
+            &version.function_id,
+        )?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    let trigger_tables = find_tables_at(
+        queries,
+        conn,
+        &at_time,
+        triggers.iter().map(|t| t.trigger_by_table_id),
+    )
+    .await?;
+    let triggers = trigger_tables
+        .iter()
+        .map(TableTrigger::try_from)
+        .collect::<Result<_, _>>()?;
+
+    let dependencies: Vec<DependencyDBRead> = queries
+        .select_versions_at::<{ DependencyDBRead::Active }, DependencyDBRead>(
+            Some(&at_time),
+            &version.function_id,
+        )?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    let dependency_tables = find_tables_at(
+        queries,
+        conn,
+        &at_time,
+        dependencies.iter().map(|d| d.table_id),
+    )
+    .await?;
+    let dependencies = dependencies
+        .iter()
+        .sorted_by_key(|d| d.dep_pos.clone())
+        .flat_map(|d| {
+            create_table_dependencies(std::slice::from_ref(d), &dependency_tables)
+                .into_iter()
+                .map(|t| (d.dep_pos.clone(), t))
+        })
+        .collect();
+
+    Ok(FunctionVersionContent {
+        snippet: version.snippet,
+        tables,
+        dependencies,
+        triggers,
+    })
+}
+
+async fn find_tables_at(
+    queries: &DaoQueries,
+    conn: &mut SqliteConnection,
+    at_time: &AtTime,
+    table_ids: impl Iterator<Item = TableId>,
+) -> Result<Vec<TableDBWithNames>, TdError> {
+    let table_ids = table_ids.unique().collect::<Vec<_>>();
+    if table_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let tables = queries
+        .find_versions_at::<{ TableDBWithNames::Available }, TableDBWithNames>(
+            Some(at_time),
+            &table_ids,
+        )?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    Ok(tables)
+}
+
+fn added_removed<T: Clone + PartialEq>(from: &[T], to: &[T]) -> (Vec<T>, Vec<T>) {
+    let added = to.iter().filter(|t| !from.contains(t)).cloned().collect();
+    let removed = from.iter().filter(|t| !to.contains(t)).cloned().collect();
+    (added, removed)
+}
@@ -31,9 +31,13 @@ use td_tower::extractors::Input;
 use td_tower::from_fn::from_fn;
 use td_tower::{layer, layers};
 
+pub mod bundle;
+pub mod bundle_upload;
 pub mod delete;
+pub mod diff;
 pub mod read;
 pub mod register;
+pub mod tags;
 pub mod update;
 pub mod upload;
 
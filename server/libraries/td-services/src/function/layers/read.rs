@@ -6,8 +6,10 @@ use std::collections::HashMap;
 use std::ops::Deref;
 use td_error::TdError;
 use td_objects::dxo::dependency::DependencyDBRead;
+use td_objects::dxo::function::FunctionDBWithNames;
 use td_objects::dxo::table::TableDBWithNames;
 use td_objects::table_ref::VersionedTableRef;
+use td_objects::types::basic::SnippetLanguage;
 use td_objects::types::composed::TableDependency;
 use td_tower::extractors::Input;
 
@@ -15,12 +17,19 @@ pub async fn vec_create_table_dependency(
     Input(dependencies): Input<Vec<DependencyDBRead>>,
     Input(tables): Input<Vec<TableDBWithNames>>,
 ) -> Result<Vec<TableDependency>, TdError> {
+    Ok(create_table_dependencies(&dependencies, &tables))
+}
+
+pub fn create_table_dependencies(
+    dependencies: &[DependencyDBRead],
+    tables: &[TableDBWithNames],
+) -> Vec<TableDependency> {
     let tables = tables
         .iter()
         .map(|t| (t.table_id, t))
         .collect::<HashMap<_, _>>();
 
-    let table_deps = dependencies
+    dependencies
         .iter()
         .filter_map(|d| {
             let table = tables.get(&d.table_id)?;
@@ -31,6 +40,25 @@ pub async fn vec_create_table_dependency(
                 versions,
             )))
         })
-        .collect::<Vec<_>>();
-    Ok(table_deps)
+        .collect()
+}
+
+/// Function snippets are the Python source of the decorated function. Anything not looking like
+/// Python source (e.g. a placeholder) is shown as plain text.
+pub async fn detect_snippet_language(
+    Input(function): Input<FunctionDBWithNames>,
+) -> Result<SnippetLanguage, TdError> {
+    let python = function.snippet.lines().map(str::trim_start).any(|line| {
+        line.starts_with('@')
+            || line.starts_with("def ")
+            || line.starts_with("async def ")
+            || line.starts_with("import ")
+            || line.starts_with("from ")
+    });
+    let language = if python {
+        SnippetLanguage::Python
+    } else {
+        SnippetLanguage::Text
+    };
+    Ok(language)
 }
@@ -0,0 +1,58 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use itertools::Itertools;
+use std::ops::Deref;
+use td_error::TdError;
+use td_objects::dxo::crudl::handle_sql_err;
+use td_objects::dxo::function_tag::FunctionTagDB;
+use td_objects::sql::{AndFilter, DaoQueries, DeleteBy, Insert};
+use td_objects::types::basic::{FunctionId, FunctionTag};
+use td_objects::types::function_tags::FunctionTagsFilter;
+use td_objects::types::visible_collections::VisibleFunctionsCollections;
+use td_tower::extractors::{Connection, Input, SrvCtx};
+
+/// Replaces the tags of a function with the given ones. Tags are kept as they are if not given.
+pub async fn register_function_tags(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Input(function_id): Input<FunctionId>,
+    Input(tags): Input<Option<Vec<FunctionTag>>>,
+) -> Result<(), TdError> {
+    let Some(tags) = tags.deref() else {
+        return Ok(());
+    };
+
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    queries
+        .delete_by::<FunctionTagDB>(function_id.deref())?
+        .build()
+        .execute(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+
+    for tag in tags.iter().unique() {
+        let function_tag = FunctionTagDB::builder()
+            .function_id(function_id.deref().clone())
+            .tag(tag.clone())
+            .build()?;
+        queries
+            .insert(&function_tag)?
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+    }
+    Ok(())
+}
+
+/// Lists the visible functions that also match the requested tags.
+pub async fn visible_functions_with_tags(
+    Input(visible): Input<VisibleFunctionsCollections>,
+    Input(tags): Input<FunctionTagsFilter>,
+) -> Result<AndFilter<VisibleFunctionsCollections, FunctionTagsFilter>, TdError> {
+    Ok(AndFilter(visible.deref().clone(), tags.deref().clone()))
+}
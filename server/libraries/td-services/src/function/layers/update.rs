@@ -5,18 +5,32 @@
 use std::ops::Deref;
 use td_error::{TdError, td_error};
 use td_objects::dxo::crudl::handle_sql_err;
-use td_objects::dxo::function::{FunctionDBWithNames, FunctionUpdate};
-use td_objects::sql::DaoQueries;
+use td_objects::dxo::function::{
+    FunctionDB, FunctionDBWithNames, FunctionUpdate, UpdateFunctionStatusDB,
+};
 use td_objects::sql::cte::CteQueries;
-use td_objects::types::basic::{AtTime, CollectionId, CollectionName, FunctionName};
+use td_objects::sql::{DaoQueries, UpdateBy};
+use td_objects::types::basic::{
+    AtTime, CollectionId, CollectionName, ExpectedFunctionVersionId, FunctionName,
+    FunctionVersionId,
+};
 use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
 
 #[td_error]
-enum UpdateFunctionError {
+pub enum UpdateFunctionError {
     #[error("Function '{0}' already exists in collection '{1}'")]
     FunctionAlreadyExists(FunctionName, CollectionName) = 0,
+
+    #[error("Function '{0}' was updated concurrently: version '{1}' expected, current is '{2}'")]
+    VersionConflict(FunctionName, FunctionVersionId, FunctionVersionId) = 8000,
+    #[error("Function '{0}' was updated concurrently: version '{1}' is no longer the current one")]
+    UpdatedConcurrently(FunctionName, FunctionVersionId) = 8001,
 }
 
+/// Condition of the function version being the current (latest) version of its function.
+const CURRENT_FUNCTION_VERSION: &str = "id = (SELECT latest.id FROM functions latest \
+    WHERE latest.function_id = functions.function_id ORDER BY latest.defined_on DESC LIMIT 1)";
+
 pub async fn assert_function_name_not_exists(
     Connection(connection): Connection,
     SrvCtx(queries): SrvCtx<DaoQueries>,
@@ -50,3 +64,55 @@ pub async fn assert_function_name_not_exists(
 
     Ok(())
 }
+
+/// Refuses the update if the current function version, read within the update transaction, is
+/// not the expected one (i.e. the function was updated since the version the update is based on).
+pub async fn assert_expected_function_version(
+    Input(expected_version): Input<Option<ExpectedFunctionVersionId>>,
+    Input(function): Input<FunctionDBWithNames>,
+) -> Result<(), TdError> {
+    if let Some(expected_version) = expected_version.deref() {
+        let expected_version = FunctionVersionId::try_from(expected_version)?;
+        if expected_version != function.id {
+            Err(UpdateFunctionError::VersionConflict(
+                function.name.clone(),
+                expected_version,
+                function.id,
+            ))?
+        }
+    }
+    Ok(())
+}
+
+/// Guards the update against concurrent updates of the function. The function version the update
+/// is based on is updated as is, only if it still is the current version of the function. This
+/// write, within the update transaction, makes concurrent updates based on the same version
+/// conflict instead of both creating a new version.
+pub async fn guard_function_version(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Input(function): Input<FunctionDBWithNames>,
+) -> Result<(), TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let update = UpdateFunctionStatusDB::builder()
+        .status(function.status.clone())
+        .build()?;
+    let mut query = queries.update_by::<_, FunctionDB>(&update, &function.id)?;
+    query.push(" AND ");
+    query.push(CURRENT_FUNCTION_VERSION);
+    let updated = query
+        .build()
+        .execute(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?
+        .rows_affected();
+    if updated == 0 {
+        Err(UpdateFunctionError::UpdatedConcurrently(
+            function.name.clone(),
+            function.id,
+        ))?
+    }
+    Ok(())
+}
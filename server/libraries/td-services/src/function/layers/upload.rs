@@ -4,17 +4,50 @@
 
 use futures::TryStreamExt;
 use sha2::{Digest, Sha256};
+use sqlx::SqliteConnection;
+use std::path::{Path, PathBuf};
+use td_common::os::available_space;
 use td_error::{TdError, td_error};
+use td_objects::dxo::bundle::BundleBlobDB;
+use td_objects::dxo::crudl::handle_sql_err;
 use td_objects::dxo::function_upload::FunctionUpload;
-use td_objects::types::basic::{BundleHash, BundleId, CollectionId, DataLocation, StorageVersion};
+use td_objects::sql::{DaoQueries, Insert, SelectBy};
+use td_objects::types::basic::{
+    BundleHash, BundleId, BundleSize, BundleUploadId, CollectionId, CollectionName, DataLocation,
+    StorageVersion,
+};
 use td_storage::location::StorageLocation;
-use td_storage::{Storage, StorageError};
-use td_tower::extractors::{Input, SrvCtx};
+use td_storage::quota::StorageQuota;
+use td_storage::{SPath, Storage, StorageError};
+use td_tower::extractors::{Connection, Input, SrvCtx};
 use tokio::io::BufWriter;
 use tokio_util::io::StreamReader;
+use url::Url;
+
+/// Space that must remain free in a local filesystem after writing a function bundle to it.
+const UPLOAD_FREE_SPACE_MARGIN: u64 = 128 * 1024 * 1024; // 128MB
 
 #[td_error]
-enum UploadError {
+pub enum UploadError {
+    #[error("Function bundle upload '{0}' has expired")]
+    BundleUploadExpired(BundleUploadId) = 0,
+    #[error("Function bundle upload '{0}' is missing the chunk at offset {1}")]
+    MissingBundleChunk(BundleUploadId, i64) = 1,
+    #[error("Function bundle upload '{0}' has overlapping chunks at offset {1}")]
+    OverlappingBundleChunk(BundleUploadId, i64) = 2,
+    #[error("Function bundle size mismatch: {0} bytes expected, {1} bytes uploaded")]
+    BundleSizeMismatch(BundleSize, i64) = 3,
+    #[error("Function bundle hash mismatch: '{0}' expected, '{1}' uploaded")]
+    BundleHashMismatch(BundleHash, BundleHash) = 4,
+
+    #[error("Function bundle upload '{0}' not found")]
+    BundleUploadNotFound(BundleUploadId) = 1000,
+
+    #[error(
+        "Storage quota exceeded for collection '{0}': {1} bytes required, {2} bytes used of a {3} bytes quota"
+    )]
+    StorageQuotaExceeded(CollectionName, u64, u64, u64) = 2000,
+
     #[error("Invalid storage version: {0}")]
     InvalidStorageVersion(String) = 5000,
     #[error("Function bundle upload failed")]
@@ -23,16 +56,104 @@ enum UploadError {
     FunctionBundleBufferingFailed(#[from] std::io::Error) = 5002,
     #[error("Function bundle save failed: {0}")]
     FunctionBundleSaveFailed(#[from] StorageError) = 5003,
+    #[error(
+        "Not enough storage space for function bundle: {0} bytes required, {1} bytes available (keeping a {2} bytes margin)"
+    )]
+    InsufficientStorageSpace(u64, u64, u64) = 5004,
+    #[error("Could not determine available storage space at {0}: {1}")]
+    AvailableSpaceCheckFailed(String, #[source] std::io::Error) = 5005,
+    #[error("Could not determine storage usage of collection '{0}': {1}")]
+    StorageUsageCheckFailed(CollectionName, #[source] StorageError) = 5006,
+    #[error("Invalid function bundle upload chunk: {0}")]
+    InvalidBundleChunk(SPath) = 5007,
+    #[error("Function bundle upload chunk read failed: {0}")]
+    BundleUploadChunkReadFailed(#[source] StorageError) = 5008,
 }
 
-pub async fn upload_function_write_to_storage(
-    SrvCtx(storage): SrvCtx<Storage>,
-    Input(bundle_id): Input<BundleId>,
-    Input(storage_version): Input<StorageVersion>,
-    Input(data_location): Input<DataLocation>,
-    Input(collection_id): Input<CollectionId>,
-    Input(request): Input<FunctionUpload>,
-) -> Result<BundleHash, TdError> {
+/// Returns the closest existing ancestor (or the path itself) of a local file URI, `None` if
+/// the URI is not a local file.
+fn local_existing_dir(uri: &Url) -> Option<PathBuf> {
+    if uri.scheme() != "file" {
+        return None;
+    }
+    let path = uri.to_file_path().ok()?;
+    path.ancestors().find(|p| p.exists()).map(Path::to_path_buf)
+}
+
+/// Fails fast if writing `size` bytes to `uri` would leave less than [`UPLOAD_FREE_SPACE_MARGIN`]
+/// bytes free. Only local file storage is checked, object stores are assumed to have room.
+fn check_available_space(uri: &Url, size: u64) -> Result<(), UploadError> {
+    if let Some(dir) = local_existing_dir(uri) {
+        let available = available_space(&dir).map_err(|e| {
+            UploadError::AvailableSpaceCheckFailed(dir.to_string_lossy().to_string(), e)
+        })?;
+        if size > available.saturating_sub(UPLOAD_FREE_SPACE_MARGIN) {
+            return Err(UploadError::InsufficientStorageSpace(
+                size,
+                available,
+                UPLOAD_FREE_SPACE_MARGIN,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Locations of everything stored for a collection: its table data and its function bundles,
+/// which are not stored under the collection data location but in their own folder.
+pub(crate) fn collection_locations(
+    storage_location: &StorageLocation,
+    data_location: &DataLocation,
+    collection_id: &CollectionId,
+) -> Vec<SPath> {
+    let (collection_location, _) = storage_location
+        .builder(data_location)
+        .collection(collection_id)
+        .build();
+    let (bundle_location, _) = storage_location
+        .builder(data_location)
+        .collection(collection_id)
+        .function(&BundleId::default())
+        .build();
+    let mut locations = vec![collection_location];
+    locations.extend(bundle_location.parent());
+    locations
+}
+
+/// Fails if storing `size` more bytes in the collection would exceed its [`StorageQuota`]. The
+/// collection usage is the size of everything stored under the given collection locations.
+///
+/// Writers must hold the [`StorageQuota::lock`] of the collection across the check and their
+/// write.
+pub(crate) async fn check_storage_quota(
+    storage: &Storage,
+    quota: &StorageQuota,
+    collection_locations: &[SPath],
+    collection_name: &CollectionName,
+    size: u64,
+) -> Result<(), UploadError> {
+    if quota.quota(collection_name).is_none() {
+        return Ok(());
+    }
+    let mut usage = 0;
+    for location in collection_locations {
+        usage += storage
+            .usage(location)
+            .await
+            .map_err(|e| UploadError::StorageUsageCheckFailed(collection_name.clone(), e))?;
+    }
+    match quota.exceeded(collection_name, usage, size) {
+        Some(quota) => Err(UploadError::StorageQuotaExceeded(
+            collection_name.clone(),
+            size,
+            usage,
+            quota,
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Buffers the whole body of a function bundle upload.
+pub(crate) async fn read_function_upload(request: &FunctionUpload) -> Result<Vec<u8>, UploadError> {
     let stream = request
         .stream()
         .await
@@ -46,21 +167,141 @@ pub async fn upload_function_write_to_storage(
     tokio::io::copy(&mut body_reader, &mut buffer)
         .await
         .map_err(UploadError::FunctionBundleBufferingFailed)?; //cannot easily test this error
-    let bytes = buffer.into_inner();
-    let hash = hex::encode(&Sha256::digest(&bytes)[..]);
+    Ok(buffer.into_inner())
+}
+
+/// Hash (sha256) of the content of a function bundle.
+pub(crate) fn bundle_hash(bytes: &[u8]) -> Result<BundleHash, TdError> {
+    BundleHash::try_from(&hex::encode(&Sha256::digest(bytes)[..]))
+}
+
+/// Stores a function bundle and returns its blob.
+///
+/// Bundles are content addressed within a collection: if a bundle with identical content is
+/// already stored, its blob is returned and nothing is written.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn store_bundle(
+    conn: &mut SqliteConnection,
+    queries: &DaoQueries,
+    storage: &Storage,
+    quota: &StorageQuota,
+    bundle_id: &BundleId,
+    storage_version: &StorageVersion,
+    data_location: &DataLocation,
+    collection_id: &CollectionId,
+    collection_name: &CollectionName,
+    bytes: Vec<u8>,
+) -> Result<BundleBlobDB, TdError> {
+    let hash = bundle_hash(&bytes)?;
+
+    let blob: Option<BundleBlobDB> = queries
+        .select_by::<BundleBlobDB>(&(collection_id, &hash))?
+        .build_query_as()
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    if let Some(blob) = blob {
+        return Ok(blob);
+    }
 
     let storage_location =
-        StorageLocation::try_from(&*storage_version).map_err(UploadError::InvalidStorageVersion)?;
+        StorageLocation::try_from(storage_version).map_err(UploadError::InvalidStorageVersion)?;
     let (location, _) = storage_location
-        .builder(&data_location)
-        .collection(&collection_id)
-        .function(&bundle_id)
+        .builder(data_location)
+        .collection(collection_id)
+        .function(bundle_id)
         .build();
 
+    let (uri, _) = storage
+        .to_external_uri(&location)
+        .map_err(UploadError::FunctionBundleSaveFailed)?;
+    check_available_space(&uri, bytes.len() as u64)?;
+    // held until the bundle is written, so concurrent writes cannot exceed the quota together
+    let _quota_lock = quota.lock(collection_name).await;
+    check_storage_quota(
+        storage,
+        quota,
+        &collection_locations(&storage_location, data_location, collection_id),
+        collection_name,
+        bytes.len() as u64,
+    )
+    .await?;
+
+    // inserted before writing, so a failed write does not leave the blob behind
+    let blob = BundleBlobDB::builder()
+        .id(*bundle_id)
+        .collection_id(*collection_id)
+        .hash(hash)
+        .data_location(data_location.clone())
+        .storage_version(storage_version.clone())
+        .build()?;
+    queries
+        .insert(&blob)?
+        .build()
+        .execute(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+
     storage
         .write(&location, bytes)
         .await
         .map_err(UploadError::FunctionBundleSaveFailed)?; //cannot easily test this error
 
-    BundleHash::try_from(&hash)
+    Ok(blob)
+}
+
+/// Writes the uploaded function bundle to storage and returns its blob, see [`store_bundle`].
+#[allow(clippy::too_many_arguments)]
+pub async fn upload_function_write_to_storage(
+    Connection(connection): Connection,
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(storage): SrvCtx<Storage>,
+    SrvCtx(quota): SrvCtx<StorageQuota>,
+    Input(bundle_id): Input<BundleId>,
+    Input(storage_version): Input<StorageVersion>,
+    Input(data_location): Input<DataLocation>,
+    Input(collection_id): Input<CollectionId>,
+    Input(collection_name): Input<CollectionName>,
+    Input(request): Input<FunctionUpload>,
+) -> Result<BundleBlobDB, TdError> {
+    let bytes = read_function_upload(&request).await?;
+
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+    store_bundle(
+        conn,
+        &queries,
+        &storage,
+        &quota,
+        &bundle_id,
+        &storage_version,
+        &data_location,
+        &collection_id,
+        &collection_name,
+        bytes,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testdir::testdir;
+
+    #[test]
+    fn test_check_available_space() {
+        let dir = testdir!();
+        let uri = Url::from_file_path(dir.join("a").join("bundle")).unwrap();
+        assert!(check_available_space(&uri, 0).is_ok());
+        assert!(matches!(
+            check_available_space(&uri, u64::MAX),
+            Err(UploadError::InsufficientStorageSpace(..))
+        ));
+    }
+
+    #[test]
+    fn test_check_available_space_skips_object_stores() {
+        let uri = Url::parse("s3://bucket/bundle").unwrap();
+        assert!(check_available_space(&uri, u64::MAX).is_ok());
+    }
 }
@@ -2,5 +2,5 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-mod layers;
+pub(crate) mod layers;
 pub mod services;
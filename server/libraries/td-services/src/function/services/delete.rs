@@ -2,6 +2,7 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::function::layers::bundle::release_bundle_blob;
 use crate::function::layers::delete::build_deleted_function_version;
 use crate::function::layers::{
     SKIP_AUTHZ, register_dependencies, register_tables, register_triggers,
@@ -12,6 +13,7 @@ use td_objects::dxo::collection::CollectionDB;
 use td_objects::dxo::crudl::{DeleteRequest, RequestContext};
 use td_objects::dxo::dependency::DependencyDB;
 use td_objects::dxo::function::{FunctionDB, FunctionDBBuilder, FunctionDBWithNames};
+use td_objects::dxo::function_tag::FunctionTagDB;
 use td_objects::dxo::table::TableDB;
 use td_objects::dxo::trigger::TriggerDBWithNames;
 use td_objects::rest_urls::FunctionParam;
@@ -21,7 +23,9 @@ use td_objects::tower_service::from::{
     DefaultService, ExtractNameService, ExtractService, TryIntoService, UpdateService, With,
     combine,
 };
-use td_objects::tower_service::sql::{By, SqlSelectAllService, SqlSelectService, insert};
+use td_objects::tower_service::sql::{
+    By, SqlDeleteService, SqlSelectAllService, SqlSelectService, insert,
+};
 use td_objects::types::basic::{
     AtTime, CollectionId, CollectionIdName, CollectionName, FunctionId, FunctionIdName,
     FunctionVersionId, ReuseFrozen, TableNameDto,
@@ -36,6 +40,7 @@ use td_tower::layers;
     request = DeleteRequest<FunctionParam>,
     response = (),
     connection = TransactionProvider,
+    audit = FunctionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -70,6 +75,10 @@ fn service() {
         from_fn(With::<RequestContext>::update::<FunctionDBBuilder, _>),
         from_fn(build_deleted_function_version),
         from_fn(insert::<FunctionDB>),
+        // Delete the function tags, deleted functions are not listed by tag.
+        from_fn(By::<FunctionId>::delete::<FunctionTagDB>),
+        // Release the bundle blob of the deleted version
+        from_fn(release_bundle_blob),
         // Register associations
         // Find previous versions.
         from_fn(By::<FunctionId>::select_all_versions::<{ TableDB::Active }, TableDB>),
@@ -104,11 +113,11 @@ mod tests {
     use td_objects::dxo::crudl::handle_sql_err;
     use td_objects::dxo::function::{FunctionBuilder, FunctionRegister};
     use td_objects::rest_urls::CollectionParam;
-    use td_objects::sql::SelectBy;
+    use td_objects::sql::{Insert, SelectBy};
     use td_objects::test_utils::seed_collection::seed_collection;
     use td_objects::test_utils::seed_function::seed_function;
     use td_objects::types::basic::{
-        AccessTokenId, BundleId, Decorator, FunctionRuntimeValues, RoleId, UserId,
+        AccessTokenId, BundleId, Decorator, FunctionRuntimeValues, FunctionTag, RoleId, UserId,
     };
     use td_tower::ctx_service::RawOneshot;
 
@@ -162,6 +171,10 @@ mod tests {
                 type_of_val(&With::<RequestContext>::update::<FunctionDBBuilder, _>),
                 type_of_val(&build_deleted_function_version),
                 type_of_val(&insert::<FunctionDB>),
+                // Delete the function tags, deleted functions are not listed by tag.
+                type_of_val(&By::<FunctionId>::delete::<FunctionTagDB>),
+                // Release the bundle blob of the deleted version
+                type_of_val(&release_bundle_blob),
                 // Register associations
                 // Find previous versions.
                 type_of_val(&By::<FunctionId>::select_all_versions::<{ TableDB::Active }, TableDB>),
@@ -512,4 +525,58 @@ mod tests {
         )
         .await
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_delete_function_tags(db: DbPool) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("cofnig")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+
+        let create = FunctionRegister::builder()
+            .try_name("joaquin_workout")?
+            .try_description("function_foo description")?
+            .bundle_id(BundleId::default())
+            .try_snippet("function_foo snippet")?
+            .decorator(Decorator::Publisher)
+            .dependencies(None)
+            .triggers(None)
+            .tables(None)
+            .runtime_values(FunctionRuntimeValues::try_from("mock runtime values")?)
+            .reuse_frozen_tables(false)
+            .build()?;
+
+        let created_function = seed_function(&db, &collection, &create).await;
+        let function_tag = FunctionTagDB::builder()
+            .function_id(created_function.function_id)
+            .tag(FunctionTag::try_from("workout")?)
+            .build()?;
+        DaoQueries::default()
+            .insert(&function_tag)?
+            .build()
+            .execute(&db)
+            .await
+            .map_err(handle_sql_err)?;
+
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).delete(
+                FunctionParam::builder()
+                    .try_collection(format!("~{}", collection.id))?
+                    .try_function("joaquin_workout")?
+                    .build()?,
+            );
+        DeleteFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let function_tags: Vec<FunctionTagDB> = DaoQueries::default()
+            .select_by::<FunctionTagDB>(&created_function.function_id)?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert!(function_tags.is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,287 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::diff::diff_function_versions;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::crudl::{ReadRequest, RequestContext};
+use td_objects::dxo::function::{FunctionDBWithNames, FunctionDiff};
+use td_objects::rest_urls::params::FunctionDiffName;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin, CollDev, CollExec, CollRead};
+use td_objects::tower_service::from::{ExtractNameService, ExtractService, With, combine};
+use td_objects::tower_service::sql::{By, SqlSelectService};
+use td_objects::types::basic::{
+    AtTime, CollectionId, CollectionIdName, FromFunctionVersionId, FunctionIdName,
+    ToFunctionVersionId,
+};
+use td_tower::default_services::ConnectionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = FunctionDiffService,
+    request = ReadRequest<FunctionDiffName>,
+    response = FunctionDiff,
+    connection = ConnectionProvider,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<ReadRequest<FunctionDiffName>>::extract::<RequestContext>),
+        from_fn(With::<ReadRequest<FunctionDiffName>>::extract_name::<FunctionDiffName>),
+        // Extract from request.
+        from_fn(With::<FunctionDiffName>::extract::<CollectionIdName>),
+        from_fn(With::<FunctionDiffName>::extract::<FunctionIdName>),
+        from_fn(With::<FunctionDiffName>::extract::<FromFunctionVersionId>),
+        from_fn(With::<FunctionDiffName>::extract::<ToFunctionVersionId>),
+        from_fn(combine::<CollectionIdName, FunctionIdName>),
+        // Read function version
+        from_fn(With::<RequestContext>::extract::<AtTime>),
+        from_fn(
+            By::<(CollectionIdName, FunctionIdName)>::select_version::<
+                { FunctionDBWithNames::Available },
+                FunctionDBWithNames,
+            >
+        ),
+        // check requester is coll_admin or coll_dev for the function's collection
+        from_fn(With::<FunctionDBWithNames>::extract::<CollectionId>),
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin, CollDev, CollExec, CollRead>::check),
+        // Diff both function versions
+        from_fn(diff_function_versions),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::layers::diff::DiffFunctionError;
+    use crate::function::services::update::UpdateFunctionService;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::{TdError, assert_service_error};
+    use td_objects::dxo::function::{FunctionDependencyChange, FunctionRegister, FunctionUpdate};
+    use td_objects::rest_urls::{FunctionDiffParam, FunctionParam};
+    use td_objects::table_ref::VersionedTableRef;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_function::seed_function;
+    use td_objects::types::basic::{
+        AccessTokenId, BundleId, CollectionName, Decorator, FunctionRuntimeValues,
+        FunctionVersionId, RoleId, TableNameDto, UserId,
+    };
+    use td_objects::types::composed::{TableDependency, TableDependencyDto};
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_function_diff(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        FunctionDiffService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<ReadRequest<FunctionDiffName>, FunctionDiff>(&[
+                type_of_val(&With::<ReadRequest<FunctionDiffName>>::extract::<RequestContext>),
+                type_of_val(
+                    &With::<ReadRequest<FunctionDiffName>>::extract_name::<FunctionDiffName>,
+                ),
+                // Extract from request.
+                type_of_val(&With::<FunctionDiffName>::extract::<CollectionIdName>),
+                type_of_val(&With::<FunctionDiffName>::extract::<FunctionIdName>),
+                type_of_val(&With::<FunctionDiffName>::extract::<FromFunctionVersionId>),
+                type_of_val(&With::<FunctionDiffName>::extract::<ToFunctionVersionId>),
+                type_of_val(&combine::<CollectionIdName, FunctionIdName>),
+                // Read function version
+                type_of_val(&With::<RequestContext>::extract::<AtTime>),
+                type_of_val(
+                    &By::<(CollectionIdName, FunctionIdName)>::select_version::<
+                        { FunctionDBWithNames::Available },
+                        FunctionDBWithNames,
+                    >,
+                ),
+                // check requester is coll_admin or coll_dev for the function's collection
+                type_of_val(&With::<FunctionDBWithNames>::extract::<CollectionId>),
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<CollAdmin, CollDev, CollExec, CollRead>::check),
+                // Diff both function versions
+                type_of_val(&diff_function_versions),
+            ]);
+    }
+
+    fn function_register(dependencies: &[&str]) -> Result<FunctionRegister, TdError> {
+        let register = FunctionRegister::builder()
+            .try_name("joaquin_workout")?
+            .try_description("function_foo description")?
+            .bundle_id(BundleId::default())
+            .try_snippet("function_foo snippet")?
+            .decorator(Decorator::Publisher)
+            .dependencies(Some(
+                dependencies
+                    .iter()
+                    .map(|d| TableDependencyDto::try_from(*d))
+                    .collect::<Result<_, _>>()?,
+            ))
+            .triggers(None)
+            .tables(Some(vec![TableNameDto::try_from("table")?]))
+            .runtime_values(FunctionRuntimeValues::default())
+            .reuse_frozen_tables(false)
+            .build()?;
+        Ok(register)
+    }
+
+    fn diff_request(
+        from: &FunctionVersionId,
+        to: &FunctionVersionId,
+    ) -> Result<ReadRequest<FunctionDiffName>, TdError> {
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).read(
+                FunctionDiffName::new(
+                    FunctionParam::builder()
+                        .try_collection("cofnig")?
+                        .try_function("joaquin_workout")?
+                        .build()?,
+                    FunctionDiffParam::builder()
+                        .from(FromFunctionVersionId::try_from(from)?)
+                        .to(ToFunctionVersionId::try_from(to)?)
+                        .build()?,
+                ),
+            );
+        Ok(request)
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_diff_changed_dependency(db: DbPool) -> Result<(), TdError> {
+        let collection =
+            seed_collection(&db, &CollectionName::try_from("cofnig")?, &UserId::admin()).await;
+
+        let create = function_register(&["table@HEAD~1"])?;
+        let created_function = seed_function(&db, &collection, &create).await;
+
+        let update: FunctionUpdate = function_register(&["table@HEAD~2"])?;
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).update(
+                FunctionParam::builder()
+                    .try_collection("cofnig")?
+                    .try_function("joaquin_workout")?
+                    .build()?,
+                update,
+            );
+        let updated_function = UpdateFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let response = FunctionDiffService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(diff_request(&created_function.id, &updated_function.id)?)
+            .await?;
+
+        assert_eq!(response.from, created_function.id);
+        assert_eq!(response.to, updated_function.id);
+        assert!(response.added_tables.is_empty());
+        assert!(response.removed_tables.is_empty());
+        assert!(response.added_dependencies.is_empty());
+        assert!(response.removed_dependencies.is_empty());
+        assert!(response.added_triggers.is_empty());
+        assert!(response.removed_triggers.is_empty());
+        assert!(!*response.snippet_changed);
+
+        let expected = |dependency: &str| -> Result<TableDependency, TdError> {
+            let dependency = TableDependency::try_from(TableDependencyDto::try_from(dependency)?)?;
+            Ok(TableDependency::new(VersionedTableRef::new(
+                Some(collection.name.clone()),
+                dependency.table.clone(),
+                dependency.versions.clone(),
+            )))
+        };
+        assert_eq!(
+            response.changed_dependencies,
+            vec![FunctionDependencyChange {
+                from: expected("table@HEAD~1")?,
+                to: expected("table@HEAD~2")?,
+            }]
+        );
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_diff_repeated_table_dependency(db: DbPool) -> Result<(), TdError> {
+        let collection =
+            seed_collection(&db, &CollectionName::try_from("cofnig")?, &UserId::admin()).await;
+
+        let create = function_register(&["table@HEAD~1", "table@HEAD~2"])?;
+        let created_function = seed_function(&db, &collection, &create).await;
+
+        let update: FunctionUpdate = function_register(&["table@HEAD~1", "table@HEAD~3"])?;
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).update(
+                FunctionParam::builder()
+                    .try_collection("cofnig")?
+                    .try_function("joaquin_workout")?
+                    .build()?,
+                update,
+            );
+        let updated_function = UpdateFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let response = FunctionDiffService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(diff_request(&created_function.id, &updated_function.id)?)
+            .await?;
+
+        // only the second dependency on the table changed
+        assert!(response.added_dependencies.is_empty());
+        assert!(response.removed_dependencies.is_empty());
+        let expected = |dependency: &str| -> Result<TableDependency, TdError> {
+            let dependency = TableDependency::try_from(TableDependencyDto::try_from(dependency)?)?;
+            Ok(TableDependency::new(VersionedTableRef::new(
+                Some(collection.name.clone()),
+                dependency.table.clone(),
+                dependency.versions.clone(),
+            )))
+        };
+        assert_eq!(
+            response.changed_dependencies,
+            vec![FunctionDependencyChange {
+                from: expected("table@HEAD~2")?,
+                to: expected("table@HEAD~3")?,
+            }]
+        );
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_diff_not_a_function_version(db: DbPool) -> Result<(), TdError> {
+        let collection =
+            seed_collection(&db, &CollectionName::try_from("cofnig")?, &UserId::admin()).await;
+        let create = function_register(&["table@HEAD~1"])?;
+        let created_function = seed_function(&db, &collection, &create).await;
+
+        let unknown = FunctionVersionId::default();
+        let service = FunctionDiffService::with_defaults(db.clone())
+            .service()
+            .await;
+        assert_service_error(
+            service,
+            diff_request(&created_function.id, &unknown)?,
+            |err| match err {
+                DiffFunctionError::NotAFunctionVersion(id, _) => assert_eq!(*id, unknown),
+            },
+        )
+        .await;
+        Ok(())
+    }
+}
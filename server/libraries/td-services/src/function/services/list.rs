@@ -2,16 +2,18 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::function::layers::tags::visible_functions_with_tags;
 use ta_services::factory::service_factory;
 use td_authz::{Authz, AuthzContext};
 use td_objects::dxo::crudl::{ListRequest, ListResponse, RequestContext};
 use td_objects::dxo::function::{Function, FunctionDBWithNames};
-use td_objects::rest_urls::AtTimeParam;
-use td_objects::sql::DaoQueries;
+use td_objects::rest_urls::params::FunctionListName;
+use td_objects::sql::{AndFilter, DaoQueries};
 use td_objects::tower_service::authz::{CollAdmin, CollDev, CollExec, CollRead};
 use td_objects::tower_service::from::{ExtractNameService, ExtractService, TryIntoService, With};
 use td_objects::tower_service::sql::{By, SqlListService};
 use td_objects::types::basic::AtTime;
+use td_objects::types::function_tags::FunctionTagsFilter;
 use td_objects::types::visible_collections::{VisibleCollections, VisibleFunctionsCollections};
 use td_tower::default_services::ConnectionProvider;
 use td_tower::from_fn::from_fn;
@@ -19,7 +21,7 @@ use td_tower::layers;
 
 #[service_factory(
     name = FunctionListService,
-    request = ListRequest<AtTimeParam>,
+    request = ListRequest<FunctionListName>,
     response = ListResponse<Function>,
     connection = ConnectionProvider,
     context = DaoQueries,
@@ -27,18 +29,21 @@ use td_tower::layers;
 )]
 fn service() {
     layers!(
-        from_fn(With::<ListRequest<AtTimeParam>>::extract::<RequestContext>),
-        from_fn(With::<ListRequest<AtTimeParam>>::extract_name::<AtTimeParam>),
-        from_fn(With::<AtTimeParam>::extract::<AtTime>),
+        from_fn(With::<ListRequest<FunctionListName>>::extract::<RequestContext>),
+        from_fn(With::<ListRequest<FunctionListName>>::extract_name::<FunctionListName>),
+        from_fn(With::<FunctionListName>::extract::<AtTime>),
         // get allowed collections
         from_fn(Authz::<CollAdmin, CollDev, CollExec, CollRead>::visible_collections),
         // convert them to allowed function collections
         from_fn(With::<VisibleCollections>::convert_to::<VisibleFunctionsCollections, _>),
+        // filter them by the requested tags
+        from_fn(With::<FunctionListName>::convert_to::<FunctionTagsFilter, _>),
+        from_fn(visible_functions_with_tags),
         // list
         from_fn(
             By::<()>::list_versions_at::<
-                AtTimeParam,
-                VisibleFunctionsCollections,
+                FunctionListName,
+                AndFilter<VisibleFunctionsCollections, FunctionTagsFilter>,
                 { FunctionDBWithNames::Available },
                 Function,
             >
@@ -50,21 +55,22 @@ fn service() {
 mod tests {
     use super::*;
     use crate::function::services::delete::DeleteFunctionService;
+    use crate::function::services::register::RegisterFunctionService;
     use crate::function::services::update::UpdateFunctionService;
     use ta_services::service::TdService;
     use td_database::sql::DbPool;
     use td_error::TdError;
     use td_objects::dxo::crudl::{ListParams, ListParamsBuilder};
     use td_objects::dxo::function::{FunctionRegister, FunctionUpdate};
-    use td_objects::rest_urls::FunctionParam;
+    use td_objects::rest_urls::{AtTimeParam, CollectionParam, FunctionParam, FunctionTagsParam};
     use td_objects::test_utils::seed_collection::seed_collection;
-    use td_objects::test_utils::seed_function::seed_function;
+    use td_objects::test_utils::seed_function::{function_register, seed_function};
     use td_objects::test_utils::seed_role::seed_role;
     use td_objects::test_utils::seed_user::seed_user;
     use td_objects::test_utils::seed_user_role::seed_user_role;
     use td_objects::types::basic::{
-        AccessTokenId, BundleId, CollectionName, Decorator, Description, FunctionName, RoleId,
-        RoleName, UserEnabled, UserId, UserName,
+        AccessTokenId, BundleId, CollectionName, Decorator, Description, FunctionName, FunctionTag,
+        RoleId, RoleName, TagMatch, UserEnabled, UserId, UserName,
     };
     use td_tower::ctx_service::RawOneshot;
 
@@ -77,21 +83,26 @@ mod tests {
         FunctionListService::with_defaults(db)
             .metadata()
             .await
-            .assert_service::<ListRequest<AtTimeParam>, ListResponse<Function>>(&[
-                type_of_val(&With::<ListRequest<AtTimeParam>>::extract::<RequestContext>),
-                type_of_val(&With::<ListRequest<AtTimeParam>>::extract_name::<AtTimeParam>),
-                type_of_val(&With::<AtTimeParam>::extract::<AtTime>),
+            .assert_service::<ListRequest<FunctionListName>, ListResponse<Function>>(&[
+                type_of_val(&With::<ListRequest<FunctionListName>>::extract::<RequestContext>),
+                type_of_val(
+                    &With::<ListRequest<FunctionListName>>::extract_name::<FunctionListName>,
+                ),
+                type_of_val(&With::<FunctionListName>::extract::<AtTime>),
                 // get allowed collections
                 type_of_val(&Authz::<CollAdmin, CollDev, CollExec, CollRead>::visible_collections),
                 // convert them to allowed function collections
                 type_of_val(
                     &With::<VisibleCollections>::convert_to::<VisibleFunctionsCollections, _>,
                 ),
+                // filter them by the requested tags
+                type_of_val(&With::<FunctionListName>::convert_to::<FunctionTagsFilter, _>),
+                type_of_val(&visible_functions_with_tags),
                 // list
                 type_of_val(
                     &By::<()>::list_versions_at::<
-                        AtTimeParam,
-                        VisibleFunctionsCollections,
+                        FunctionListName,
+                        AndFilter<VisibleFunctionsCollections, FunctionTagsFilter>,
                         { FunctionDBWithNames::Available },
                         Function,
                     >,
@@ -411,4 +422,174 @@ mod tests {
         assert_eq!(data.len(), 0);
         Ok(())
     }
+
+    async fn register_tagged(
+        db: &DbPool,
+        collection: &CollectionName,
+        name: &str,
+        tags: &[&str],
+    ) -> Result<(), TdError> {
+        let mut create = function_register(name, &[], &[], &[])?;
+        create.tags = Some(
+            tags.iter()
+                .map(|t| FunctionTag::try_from(*t))
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                CollectionParam::builder()
+                    .try_collection(collection.as_str())?
+                    .build()?,
+                create,
+            );
+        RegisterFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_tagged(
+        db: &DbPool,
+        tags: &[&str],
+        tag_match: TagMatch,
+    ) -> Result<Vec<FunctionName>, TdError> {
+        let tags = FunctionTagsParam::builder()
+            .tags(
+                tags.iter()
+                    .map(|t| FunctionTag::try_from(*t))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .tag_match(tag_match)
+            .build()?;
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).list(
+                FunctionListName::new(AtTimeParam::builder().at(AtTime::now()).build()?, tags),
+                ListParamsBuilder::default()
+                    .order_by("name".to_string())
+                    .build()
+                    .unwrap(),
+            );
+        let response = FunctionListService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        Ok(response.data.into_iter().map(|f| f.name).collect())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_list_functions_by_tags(db: DbPool) -> Result<(), TdError> {
+        let collection = CollectionName::try_from("collection")?;
+        seed_collection(&db, &collection, &UserId::admin()).await;
+
+        register_tagged(&db, &collection, "function_1", &["etl", "daily"]).await?;
+        register_tagged(&db, &collection, "function_2", &["etl"]).await?;
+        register_tagged(&db, &collection, "function_3", &["daily", "ml"]).await?;
+        register_tagged(&db, &collection, "function_4", &[]).await?;
+
+        let names = |names: &[&str]| -> Result<Vec<FunctionName>, TdError> {
+            names.iter().map(|n| FunctionName::try_from(*n)).collect()
+        };
+
+        // no tags, no filter
+        assert_eq!(
+            list_tagged(&db, &[], TagMatch::Any).await?,
+            names(&["function_1", "function_2", "function_3", "function_4"])?
+        );
+
+        // any of the tags
+        assert_eq!(
+            list_tagged(&db, &["etl"], TagMatch::Any).await?,
+            names(&["function_1", "function_2"])?
+        );
+        assert_eq!(
+            list_tagged(&db, &["etl", "ml"], TagMatch::Any).await?,
+            names(&["function_1", "function_2", "function_3"])?
+        );
+        assert_eq!(list_tagged(&db, &["missing"], TagMatch::Any).await?, vec![]);
+
+        // all the tags
+        assert_eq!(
+            list_tagged(&db, &["etl", "daily"], TagMatch::All).await?,
+            names(&["function_1"])?
+        );
+        assert_eq!(
+            list_tagged(&db, &["daily", "daily"], TagMatch::All).await?,
+            names(&["function_1", "function_3"])?
+        );
+        assert_eq!(
+            list_tagged(&db, &["etl", "ml"], TagMatch::All).await?,
+            vec![]
+        );
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_list_functions_by_updated_tags(db: DbPool) -> Result<(), TdError> {
+        let collection = CollectionName::try_from("collection")?;
+        seed_collection(&db, &collection, &UserId::admin()).await;
+
+        register_tagged(&db, &collection, "function_1", &["etl", "daily"]).await?;
+        register_tagged(&db, &collection, "function_2", &["etl"]).await?;
+
+        // tags are kept on updates without tags
+        let update = FunctionUpdate::builder()
+            .try_name("function_2")?
+            .try_description("function_foo description")?
+            .bundle_id(BundleId::default())
+            .try_snippet("function_foo snippet")?
+            .decorator(Decorator::Publisher)
+            .dependencies(None)
+            .triggers(None)
+            .tables(None)
+            .try_runtime_values("mock runtime values")?
+            .reuse_frozen_tables(false)
+            .build()?;
+        let function = FunctionParam::builder()
+            .try_collection(collection.as_str())?
+            .try_function("function_2")?
+            .build()?;
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user())
+                .update(function.clone(), update.clone());
+        UpdateFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert_eq!(
+            list_tagged(&db, &["etl"], TagMatch::Any).await?,
+            vec![
+                FunctionName::try_from("function_1")?,
+                FunctionName::try_from("function_2")?
+            ]
+        );
+
+        // and replaced on updates with tags
+        let mut update = update;
+        update.tags = Some(vec![
+            FunctionTag::try_from("daily")?,
+            FunctionTag::try_from("etl")?,
+        ]);
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user())
+                .update(function, update);
+        UpdateFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert_eq!(
+            list_tagged(&db, &["etl", "daily"], TagMatch::All).await?,
+            vec![
+                FunctionName::try_from("function_1")?,
+                FunctionName::try_from("function_2")?
+            ]
+        );
+        Ok(())
+    }
 }
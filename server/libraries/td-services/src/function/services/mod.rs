@@ -3,36 +3,54 @@
 //
 
 use crate::function::services::delete::DeleteFunctionService;
+use crate::function::services::diff::FunctionDiffService;
 use crate::function::services::history::FunctionHistoryService;
 use crate::function::services::list::FunctionListService;
 use crate::function::services::list_by_collection::FunctionListByCollectionService;
 use crate::function::services::read::ReadFunctionService;
+use crate::function::services::read_snippet::ReadFunctionSnippetService;
 use crate::function::services::register::RegisterFunctionService;
+use crate::function::services::register_batch::RegisterFunctionBatchService;
 use crate::function::services::update::UpdateFunctionService;
 use crate::function::services::upload::UploadFunctionService;
+use crate::function::services::upload_chunk::UploadFunctionChunkService;
+use crate::function::services::upload_commit::CommitFunctionUploadService;
+use crate::function::services::upload_start::StartFunctionUploadService;
 use getset::Getters;
 use ta_services::factory::ServiceFactory;
 
 pub(crate) mod delete;
+pub(crate) mod diff;
 pub(crate) mod history;
 pub(crate) mod list;
 pub(crate) mod list_by_collection;
 pub(crate) mod read;
+pub(crate) mod read_snippet;
 pub(crate) mod register;
+pub(crate) mod register_batch;
 pub(crate) mod update;
 pub(crate) mod upload;
+pub(crate) mod upload_chunk;
+pub(crate) mod upload_commit;
+pub(crate) mod upload_start;
 
 #[derive(ServiceFactory, Getters)]
 #[getset(get = "pub")]
 pub struct FunctionServices {
     pub register: RegisterFunctionService,
+    pub register_batch: RegisterFunctionBatchService,
     pub upload: UploadFunctionService,
+    pub upload_start: StartFunctionUploadService,
+    pub upload_chunk: UploadFunctionChunkService,
+    pub upload_commit: CommitFunctionUploadService,
     pub read_version: ReadFunctionService,
+    pub read_snippet: ReadFunctionSnippetService,
     pub list_by_collection: FunctionListByCollectionService,
     pub list: FunctionListService,
     pub update: UpdateFunctionService,
     pub delete: DeleteFunctionService,
     pub history: FunctionHistoryService,
+    pub diff: FunctionDiffService,
 }
 
 #[cfg(test)]
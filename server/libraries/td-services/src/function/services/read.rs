@@ -26,7 +26,7 @@ use td_objects::types::basic::{
 use td_objects::types::composed::{TableDependency, TableTrigger};
 use td_tower::default_services::ConnectionProvider;
 use td_tower::from_fn::from_fn;
-use td_tower::layers;
+use td_tower::{layer, layers};
 
 #[service_factory(
     name = ReadFunctionService,
@@ -56,6 +56,14 @@ fn service() {
         from_fn(With::<FunctionDBWithNames>::extract::<CollectionId>),
         from_fn(AuthzOn::<CollectionId>::set),
         from_fn(Authz::<CollAdmin, CollDev, CollExec, CollRead>::check),
+        read_function_with_tables(),
+    )
+}
+
+/// Reads the tables, triggers and dependencies of a function version, at the request time.
+#[layer]
+pub fn read_function_with_tables() {
+    layers!(
         // Read function with tables, triggers and dependencies
         from_fn(With::<FunctionDBWithNames>::extract::<FunctionId>),
         // Convert to function read
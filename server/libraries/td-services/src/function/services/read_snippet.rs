@@ -0,0 +1,173 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::read::detect_snippet_language;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::crudl::{ReadRequest, RequestContext};
+use td_objects::dxo::function::{FunctionDBWithNames, FunctionSnippet, FunctionSnippetBuilder};
+use td_objects::rest_urls::FunctionParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin, CollDev, CollExec, CollRead};
+use td_objects::tower_service::from::{
+    BuildService, ExtractNameService, ExtractService, SetService, TryIntoService, With, combine,
+};
+use td_objects::tower_service::sql::{By, SqlSelectService};
+use td_objects::types::basic::{
+    AtTime, CollectionId, CollectionIdName, FunctionIdName, SnippetLanguage,
+};
+use td_tower::default_services::ConnectionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = ReadFunctionSnippetService,
+    request = ReadRequest<FunctionParam>,
+    response = FunctionSnippet,
+    connection = ConnectionProvider,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<ReadRequest<FunctionParam>>::extract::<RequestContext>),
+        from_fn(With::<ReadRequest<FunctionParam>>::extract_name::<FunctionParam>),
+        // Extract from request.
+        from_fn(With::<FunctionParam>::extract::<CollectionIdName>),
+        from_fn(With::<FunctionParam>::extract::<FunctionIdName>),
+        from_fn(combine::<CollectionIdName, FunctionIdName>),
+        // Read function version
+        from_fn(With::<RequestContext>::extract::<AtTime>),
+        from_fn(
+            By::<(CollectionIdName, FunctionIdName)>::select_version::<
+                { FunctionDBWithNames::Available },
+                FunctionDBWithNames,
+            >
+        ),
+        // check requester is coll_admin or coll_dev for the function's collection
+        from_fn(With::<FunctionDBWithNames>::extract::<CollectionId>),
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin, CollDev, CollExec, CollRead>::check),
+        // Build snippet, with its language
+        from_fn(With::<FunctionDBWithNames>::convert_to::<FunctionSnippetBuilder, _>),
+        from_fn(detect_snippet_language),
+        from_fn(With::<SnippetLanguage>::set::<FunctionSnippetBuilder>),
+        from_fn(With::<FunctionSnippetBuilder>::build::<FunctionSnippet, _>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::function::FunctionRegister;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_function::seed_function;
+    use td_objects::types::basic::{
+        AccessTokenId, BundleId, CollectionName, Decorator, FunctionRuntimeValues, RoleId,
+        TableNameDto, UserId,
+    };
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_read_function_snippet(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        ReadFunctionSnippetService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<ReadRequest<FunctionParam>, FunctionSnippet>(&[
+                type_of_val(&With::<ReadRequest<FunctionParam>>::extract::<RequestContext>),
+                type_of_val(&With::<ReadRequest<FunctionParam>>::extract_name::<FunctionParam>),
+                // Extract from request.
+                type_of_val(&With::<FunctionParam>::extract::<CollectionIdName>),
+                type_of_val(&With::<FunctionParam>::extract::<FunctionIdName>),
+                type_of_val(&combine::<CollectionIdName, FunctionIdName>),
+                // Read function version
+                type_of_val(&With::<RequestContext>::extract::<AtTime>),
+                type_of_val(
+                    &By::<(CollectionIdName, FunctionIdName)>::select_version::<
+                        { FunctionDBWithNames::Available },
+                        FunctionDBWithNames,
+                    >,
+                ),
+                // check requester is coll_admin or coll_dev for the function's collection
+                type_of_val(&With::<FunctionDBWithNames>::extract::<CollectionId>),
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<CollAdmin, CollDev, CollExec, CollRead>::check),
+                // Build snippet, with its language
+                type_of_val(&With::<FunctionDBWithNames>::convert_to::<FunctionSnippetBuilder, _>),
+                type_of_val(&detect_snippet_language),
+                type_of_val(&With::<SnippetLanguage>::set::<FunctionSnippetBuilder>),
+                type_of_val(&With::<FunctionSnippetBuilder>::build::<FunctionSnippet, _>),
+            ]);
+    }
+
+    async fn read_snippet(
+        db: &DbPool,
+        decorator: Decorator,
+        snippet: &str,
+    ) -> Result<FunctionSnippet, TdError> {
+        let collection =
+            seed_collection(db, &CollectionName::try_from("cofnig")?, &UserId::admin()).await;
+
+        let create = FunctionRegister::builder()
+            .try_name("joaquin_workout")?
+            .try_description("function_foo description")?
+            .bundle_id(BundleId::default())
+            .try_snippet(snippet)?
+            .decorator(decorator)
+            .dependencies(None)
+            .triggers(None)
+            .tables(Some(vec![TableNameDto::try_from("table")?]))
+            .runtime_values(FunctionRuntimeValues::default())
+            .reuse_frozen_tables(false)
+            .build()?;
+        let function = seed_function(db, &collection, &create).await;
+
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).read(
+                FunctionParam::builder()
+                    .try_collection("cofnig")?
+                    .try_function("joaquin_workout")?
+                    .build()?,
+            );
+        let response = ReadFunctionSnippetService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        assert_eq!(response.id, function.id);
+        assert_eq!(response.collection, collection.name);
+        assert_eq!(response.name, function.name);
+        Ok(response)
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_read_snippet(db: DbPool) -> Result<(), TdError> {
+        let snippet = "@td.publisher(tables=\"table\")\ndef joaquin_workout():\n    return None\n";
+        let response = read_snippet(&db, Decorator::Publisher, snippet).await?;
+        assert_eq!(response.snippet.as_str(), snippet);
+        assert_eq!(response.decorator, Decorator::Publisher);
+        assert_eq!(response.language, SnippetLanguage::Python);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_read_snippet_text(db: DbPool) -> Result<(), TdError> {
+        let snippet = "function_foo snippet";
+        let response = read_snippet(&db, Decorator::Publisher, snippet).await?;
+        assert_eq!(response.snippet.as_str(), snippet);
+        assert_eq!(response.decorator, Decorator::Publisher);
+        assert_eq!(response.language, SnippetLanguage::Text);
+        Ok(())
+    }
+}
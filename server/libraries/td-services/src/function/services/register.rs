@@ -2,7 +2,9 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::function::layers::bundle::reference_bundle_blob;
 use crate::function::layers::register::{data_location, validate_tables_do_not_exist};
+use crate::function::layers::tags::register_function_tags;
 use crate::function::layers::{
     DO_AUTHZ, check_private_tables, register_dependencies, register_tables, register_triggers,
 };
@@ -29,22 +31,32 @@ use td_objects::tower_service::sql::{
 };
 use td_objects::types::basic::{
     AtTime, BundleId, CollectionId, CollectionIdName, CollectionName, DataLocation, FunctionId,
-    FunctionName, ReuseFrozen, StorageVersion, TableNameDto,
+    FunctionName, FunctionTag, ReuseFrozen, StorageVersion, TableNameDto,
 };
 use td_objects::types::composed::{TableDependencyDto, TableTriggerDto};
 use td_tower::default_services::TransactionProvider;
 use td_tower::from_fn::from_fn;
-use td_tower::layers;
+use td_tower::{layer, layers};
 
 #[service_factory(
     name = RegisterFunctionService,
     request = CreateRequest<CollectionParam, FunctionRegister>,
     response = Function,
     connection = TransactionProvider,
+    audit = FunctionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
 fn service() {
+    layers!(
+        register_function_version(),
+        register_function_associations(),
+    )
+}
+
+/// Registers the function version and its tables.
+#[layer]
+pub fn register_function_version() {
     layers!(
         from_fn(
             With::<CreateRequest<CollectionParam, FunctionRegister>>::extract::<RequestContext>
@@ -92,6 +104,7 @@ fn service() {
         // Remove from bundles
         from_fn(With::<FunctionDB>::extract::<BundleId>),
         from_fn(By::<BundleId>::delete::<BundleDB>),
+        from_fn(reference_bundle_blob),
         // Register associations
         // Extract new function id
         from_fn(With::<FunctionDB>::extract::<FunctionId>),
@@ -112,6 +125,18 @@ fn service() {
         from_fn(With::<FunctionRegister>::extract::<ReuseFrozen>),
         // And register new ones
         register_tables(),
+        // Replace tags
+        from_fn(With::<FunctionRegister>::extract::<Option<Vec<FunctionTag>>>),
+        from_fn(register_function_tags),
+    )
+}
+
+/// Registers the dependencies and triggers of a function registered by
+/// [`register_function_version`], which can reference tables of other functions registered
+/// in the same transaction.
+#[layer]
+pub fn register_function_associations() {
+    layers!(
         register_dependencies::<_, DO_AUTHZ>(),
         register_triggers::<_, DO_AUTHZ>(),
         // Response
@@ -130,6 +155,7 @@ mod tests {
     use td_database::sql::DbPool;
     use td_error::TdError;
     use td_execution::version_resolver::VersionResolverError;
+    use td_objects::dxo::audit_log::AuditLogDB;
     use td_objects::dxo::crudl::handle_sql_err;
     use td_objects::sql::SelectBy;
     use td_objects::test_utils::seed_collection::seed_collection;
@@ -138,7 +164,8 @@ mod tests {
     use td_objects::tower_service::authz::AuthzError;
     use td_objects::tower_service::sql::SqlError;
     use td_objects::types::basic::{
-        AccessTokenId, Decorator, FunctionRuntimeValues, RoleId, ToCollectionId, UserId,
+        AccessTokenId, AuditAction, AuditOutcome, Decorator, FunctionRuntimeValues, RequestId,
+        RoleId, ToCollectionId, UserId,
     };
     use td_tower::ctx_service::RawOneshot;
 
@@ -217,6 +244,7 @@ mod tests {
                 // Remove from bundles
                 type_of_val(&With::<FunctionDB>::extract::<BundleId>),
                 type_of_val(&By::<BundleId>::delete::<BundleDB>),
+                type_of_val(&reference_bundle_blob),
                 // Register associations
                 // Extract new function id
                 type_of_val(&With::<FunctionDB>::extract::<FunctionId>),
@@ -243,6 +271,9 @@ mod tests {
                 type_of_val(&insert_vec::<TableDB>),
                 type_of_val(&build_tables_trigger_versions),
                 type_of_val(&insert_vec::<TriggerDB>),
+                // Replace tags
+                type_of_val(&With::<FunctionRegister>::extract::<Option<Vec<FunctionTag>>>),
+                type_of_val(&register_function_tags),
                 // Insert into dependency_versions(sql) current function table dependencies status=Active.
                 type_of_val(&With::<FunctionDB>::convert_to::<DependencyDBBuilder, _>),
                 type_of_val(&With::<RequestContext>::update::<DependencyDBBuilder, _>),
@@ -1166,4 +1197,82 @@ mod tests {
 
         Ok(())
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_register_audit_log(db: DbPool) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("cofnig")?;
+        seed_collection(&db, &collection_name, &UserId::admin()).await;
+
+        let create = FunctionRegister::builder()
+            .try_name("function_foo")?
+            .try_description("function_foo description")?
+            .bundle_id(BundleId::default())
+            .try_snippet("function_foo snippet")?
+            .decorator(Decorator::Publisher)
+            .dependencies(None)
+            .triggers(None)
+            .tables(None)
+            .runtime_values(FunctionRuntimeValues::try_from("mock runtime values")?)
+            .reuse_frozen_tables(false)
+            .build()?;
+
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user())
+                .with_request_id(RequestId::try_from("request-0")?)
+                .create(
+                    CollectionParam::builder()
+                        .try_collection(collection_name.as_str())?
+                        .build()?,
+                    create,
+                );
+        let provider = RegisterFunctionService::with_defaults(db.clone());
+        let function = provider
+            .service()
+            .await
+            .raw_oneshot(request.clone())
+            .await?;
+
+        // registering it again fails, and the failure is recorded too
+        let err = provider
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+            .unwrap_err();
+
+        let audit_logs: Vec<AuditLogDB> = DaoQueries::default()
+            .select_by::<AuditLogDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(audit_logs.len(), 2);
+        let audit_log = &audit_logs[0];
+        assert_eq!(audit_log.user_id, Some(UserId::admin()));
+        assert_eq!(audit_log.role_id, Some(RoleId::user()));
+        assert_eq!(audit_log.action, AuditAction::Create);
+        assert_eq!(audit_log.service.as_str(), "RegisterFunctionService");
+        assert_eq!(
+            audit_log.entity.as_ref().map(|e| e.to_string()),
+            Some(function.function_id.to_string())
+        );
+        assert_eq!(audit_log.outcome, AuditOutcome::Success);
+        assert_eq!(audit_log.error_code, None);
+        assert_eq!(
+            audit_log.request_id,
+            Some(RequestId::try_from("request-0")?)
+        );
+
+        let audit_log = &audit_logs[1];
+        assert_eq!(audit_log.user_id, Some(UserId::admin()));
+        assert_eq!(audit_log.action, AuditAction::Create);
+        assert_eq!(audit_log.entity, None);
+        assert_eq!(audit_log.outcome, AuditOutcome::Failure);
+        assert_eq!(
+            audit_log.error_code.as_ref().map(|c| c.to_string()),
+            Some(err.code().to_string())
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,233 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::services::register::{
+    register_function_associations, register_function_version,
+};
+use std::sync::Arc;
+use ta_services::factory::service_factory;
+use td_authz::AuthzContext;
+use td_error::TdError;
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{CreateRequest, RequestContext};
+use td_objects::dxo::function::{Function, FunctionBatch, FunctionRegister, FunctionRegisterBatch};
+use td_objects::rest_urls::CollectionParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::from::{
+    ExtractDataService, ExtractNameService, ExtractService, With,
+};
+use td_objects::tower_service::sql::{By, SqlSelectService};
+use td_objects::types::basic::{CollectionId, CollectionIdName};
+use td_tower::default_services::TransactionProvider;
+use td_tower::extractors::{Connection, FromHandler, Input, ReqCtx, SrvCtx};
+use td_tower::from_fn::from_fn;
+use td_tower::handler::Handler;
+use td_tower::{layers, service};
+use tower::ServiceExt;
+
+#[service_factory(
+    name = RegisterFunctionBatchService,
+    request = CreateRequest<CollectionParam, FunctionRegisterBatch>,
+    response = FunctionBatch,
+    connection = TransactionProvider,
+    audit = CollectionId,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(
+            With::<CreateRequest<CollectionParam, FunctionRegisterBatch>>::extract::<RequestContext>
+        ),
+        from_fn(
+            With::<CreateRequest<CollectionParam, FunctionRegisterBatch>>::extract_name::<
+                CollectionParam,
+            >
+        ),
+        from_fn(
+            With::<CreateRequest<CollectionParam, FunctionRegisterBatch>>::extract_data::<
+                FunctionRegisterBatch,
+            >
+        ),
+        from_fn(register_batch),
+        // Collection of the functions, the audited entity
+        from_fn(With::<CollectionParam>::extract::<CollectionIdName>),
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+    )
+}
+
+async fn register_batch(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(authz_context): SrvCtx<AuthzContext>,
+    connection: Connection,
+    req_ctx: ReqCtx,
+    Input(context): Input<RequestContext>,
+    Input(collection): Input<CollectionParam>,
+    Input(batch): Input<FunctionRegisterBatch>,
+) -> Result<FunctionBatch, TdError> {
+    let functions = register_functions(
+        queries,
+        authz_context,
+        connection,
+        req_ctx,
+        &context,
+        &collection,
+        &batch.functions,
+    )
+    .await?;
+    Ok(FunctionBatch::builder().functions(functions).build()?)
+}
+
+/// Registers functions of a collection in the given connection transaction, so either all or
+/// none of them are registered.
+///
+/// The versions and tables of all the functions are registered before any of their
+/// dependencies and triggers, so functions can depend on, or be triggered by, tables of other
+/// functions registered with them.
+pub(crate) async fn register_functions(
+    queries: Arc<DaoQueries>,
+    authz_context: Arc<AuthzContext>,
+    connection: Connection,
+    req_ctx: ReqCtx,
+    context: &RequestContext,
+    collection: &CollectionParam,
+    functions: &[FunctionRegister],
+) -> Result<Vec<Function>, TdError> {
+    // Same handler the register service gets, sharing the transaction.
+    let handler = |function: FunctionRegister| {
+        let request: CreateRequest<CollectionParam, FunctionRegister> =
+            context.clone().create(collection.clone(), function);
+        let mut handler = Handler::new();
+        handler.insert(Input(Arc::new(())));
+        handler.insert(Input(Arc::new(request)));
+        handler.insert(req_ctx.clone());
+        handler.insert(SrvCtx(queries.clone()));
+        handler.insert(SrvCtx(authz_context.clone()));
+        handler.insert(connection.clone());
+        handler
+    };
+
+    let mut registered = Vec::with_capacity(functions.len());
+    for function in functions {
+        let handler = service!(register_function_version())
+            .oneshot(handler(function.clone()))
+            .await?;
+        registered.push(handler);
+    }
+
+    let mut functions = Vec::with_capacity(registered.len());
+    for handler in registered {
+        let handler = service!(register_function_associations())
+            .oneshot(handler)
+            .await?;
+        let Input(function) = Input::<Function>::from_handler(&handler)?;
+        functions.push(function.as_ref().clone());
+    }
+    Ok(functions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::layers::register::RegisterFunctionError;
+    use crate::function::services::tests::assert_register;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_objects::dxo::collection::CollectionDB;
+    use td_objects::dxo::crudl::handle_sql_err;
+    use td_objects::dxo::function::FunctionDB;
+    use td_objects::sql::SelectBy;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_function::function_register;
+    use td_objects::types::basic::{AccessTokenId, CollectionName, RoleId, UserId};
+    use td_tower::ctx_service::RawOneshot;
+
+    async fn register(
+        db: &DbPool,
+        functions: Vec<FunctionRegister>,
+    ) -> Result<FunctionBatch, TdError> {
+        let batch = FunctionRegisterBatch::builder()
+            .functions(functions)
+            .build()?;
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                CollectionParam::builder()
+                    .try_collection("collection")?
+                    .build()?,
+                batch,
+            );
+        RegisterFunctionBatchService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_register_batch_with_cross_references(db: DbPool) -> Result<(), TdError> {
+        let collection = seed_collection(
+            &db,
+            &CollectionName::try_from("collection")?,
+            &UserId::admin(),
+        )
+        .await;
+
+        // 'first' depends on a table of 'second', which is triggered by a table of 'first'.
+        let first = function_register("first", &["first_table"], &["second_table"], &[])?;
+        let second = function_register("second", &["second_table"], &[], &["first_table"])?;
+        let response = register(&db, vec![first.clone(), second.clone()]).await?;
+
+        assert_eq!(response.functions.len(), 2);
+        assert_register(
+            &db,
+            &UserId::admin(),
+            &collection,
+            &first,
+            &response.functions[0],
+        )
+        .await?;
+        assert_register(
+            &db,
+            &UserId::admin(),
+            &collection,
+            &second,
+            &response.functions[1],
+        )
+        .await?;
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_register_batch_rollback(db: DbPool) -> Result<(), TdError> {
+        let collection: CollectionDB = seed_collection(
+            &db,
+            &CollectionName::try_from("collection")?,
+            &UserId::admin(),
+        )
+        .await;
+
+        // 'second' depends on a table no function in the collection or the batch has.
+        let first = function_register("first", &["first_table"], &[], &[])?;
+        let second = function_register("second", &["second_table"], &["missing_table"], &[])?;
+        let err = register(&db, vec![first, second]).await.err().unwrap();
+        assert!(matches!(
+            err.domain_err(),
+            RegisterFunctionError::DependencyTableDoesNotExist(_)
+        ));
+
+        // 'first' was registered in the same transaction, it was rolled back too.
+
+        let functions: Vec<FunctionDB> = DaoQueries::default()
+            .select_by::<FunctionDB>(&collection.id)?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert!(functions.is_empty());
+        Ok(())
+    }
+}
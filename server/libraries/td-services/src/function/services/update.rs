@@ -2,8 +2,12 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::function::layers::bundle::{reference_bundle_blob, release_bundle_blob};
 use crate::function::layers::register::{data_location, validate_tables_do_not_exist};
-use crate::function::layers::update::assert_function_name_not_exists;
+use crate::function::layers::tags::register_function_tags;
+use crate::function::layers::update::{
+    assert_expected_function_version, assert_function_name_not_exists, guard_function_version,
+};
 use crate::function::layers::{
     DO_AUTHZ, check_private_tables, register_dependencies, register_tables, register_triggers,
 };
@@ -18,7 +22,7 @@ use td_objects::dxo::function::{
 };
 use td_objects::dxo::table::TableDB;
 use td_objects::dxo::trigger::TriggerDBWithNames;
-use td_objects::rest_urls::FunctionParam;
+use td_objects::rest_urls::params::FunctionExpectedVersionName;
 use td_objects::sql::DaoQueries;
 use td_objects::tower_service::authz::{AuthzOn, CollAdmin, CollDev};
 use td_objects::tower_service::from::{
@@ -29,8 +33,9 @@ use td_objects::tower_service::sql::{
     By, SqlDeleteService, SqlSelectAllService, SqlSelectService, insert,
 };
 use td_objects::types::basic::{
-    AtTime, BundleId, CollectionId, CollectionIdName, CollectionName, DataLocation, FunctionId,
-    FunctionIdName, FunctionVersionId, ReuseFrozen, StorageVersion, TableNameDto,
+    AtTime, BundleId, CollectionId, CollectionIdName, CollectionName, DataLocation,
+    ExpectedFunctionVersionId, FunctionId, FunctionIdName, FunctionTag, FunctionVersionId,
+    ReuseFrozen, StorageVersion, TableNameDto,
 };
 use td_objects::types::composed::{TableDependencyDto, TableTriggerDto};
 use td_tower::default_services::TransactionProvider;
@@ -39,24 +44,34 @@ use td_tower::layers;
 
 #[service_factory(
     name = UpdateFunctionService,
-    request = UpdateRequest<FunctionParam, FunctionUpdate>,
+    request = UpdateRequest<FunctionExpectedVersionName, FunctionUpdate>,
     response = Function,
     connection = TransactionProvider,
+    audit = FunctionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
 fn service() {
     layers!(
-        from_fn(With::<UpdateRequest<FunctionParam, FunctionUpdate>>::extract::<RequestContext>),
         from_fn(
-            With::<UpdateRequest<FunctionParam, FunctionUpdate>>::extract_name::<FunctionParam>
+            With::<UpdateRequest<FunctionExpectedVersionName, FunctionUpdate>>::extract::<
+                RequestContext,
+            >
+        ),
+        from_fn(
+            With::<UpdateRequest<FunctionExpectedVersionName, FunctionUpdate>>::extract_name::<
+                FunctionExpectedVersionName,
+            >
         ),
         from_fn(
-            With::<UpdateRequest<FunctionParam, FunctionUpdate>>::extract_data::<FunctionUpdate>
+            With::<UpdateRequest<FunctionExpectedVersionName, FunctionUpdate>>::extract_data::<
+                FunctionUpdate,
+            >
         ),
         // Extract collection and current function from request.
-        from_fn(With::<FunctionParam>::extract::<CollectionIdName>),
-        from_fn(With::<FunctionParam>::extract::<FunctionIdName>),
+        from_fn(With::<FunctionExpectedVersionName>::extract::<CollectionIdName>),
+        from_fn(With::<FunctionExpectedVersionName>::extract::<FunctionIdName>),
+        from_fn(With::<FunctionExpectedVersionName>::extract::<Option<ExpectedFunctionVersionId>>),
         // Get collection. Extract collection id and name.
         from_fn(By::<CollectionIdName>::select::<CollectionDB>),
         from_fn(With::<CollectionDB>::extract::<CollectionId>),
@@ -77,6 +92,10 @@ fn service() {
         // not change, but function version id does.
         from_fn(With::<FunctionDBWithNames>::extract::<FunctionId>),
         from_fn(With::<FunctionDBWithNames>::extract::<FunctionVersionId>),
+        // Refuse the update if the function was updated since the expected version, or
+        // concurrently.
+        from_fn(assert_expected_function_version),
+        from_fn(guard_function_version),
         // If function has a new name, check new name does not exist in collection.
         from_fn(assert_function_name_not_exists),
         // Get location and storage version.
@@ -95,6 +114,9 @@ fn service() {
         // Remove from bundles
         from_fn(With::<FunctionDB>::extract::<BundleId>),
         from_fn(By::<BundleId>::delete::<BundleDB>),
+        // Move the bundle blob reference from the previous version to the new one
+        from_fn(reference_bundle_blob),
+        from_fn(release_bundle_blob),
         // Register associations
         // Find previous versions
         from_fn(By::<FunctionId>::select_all_versions::<{ TableDB::Available }, TableDB>),
@@ -118,6 +140,9 @@ fn service() {
         from_fn(With::<FunctionUpdate>::extract::<ReuseFrozen>),
         // And register new ones
         register_tables(),
+        // Replace tags
+        from_fn(With::<FunctionUpdate>::extract::<Option<Vec<FunctionTag>>>),
+        from_fn(register_function_tags),
         register_dependencies::<_, DO_AUTHZ>(),
         register_triggers::<_, DO_AUTHZ>(),
         // Response
@@ -133,17 +158,18 @@ fn service() {
 mod tests {
     use super::*;
     use crate::function::layers::register::RegisterFunctionError;
+    use crate::function::layers::update::UpdateFunctionError;
     use crate::function::services::register::RegisterFunctionService;
     use crate::function::services::tests::{assert_register, assert_update};
     use std::collections::HashMap;
     use std::ops::Deref;
     use ta_services::service::TdService;
     use td_database::sql::DbPool;
-    use td_error::TdError;
+    use td_error::{ApiError, TdError, assert_service_error};
     use td_objects::dxo::crudl::handle_sql_err;
     use td_objects::dxo::function::FunctionRegister;
     use td_objects::dxo::trigger::TriggerDB;
-    use td_objects::rest_urls::CollectionParam;
+    use td_objects::rest_urls::{CollectionParam, FunctionExpectedVersionParam, FunctionParam};
     use td_objects::sql::SelectBy;
     use td_objects::sql::cte::CteQueries;
     use td_objects::sql::recursive::RecursiveQueries;
@@ -155,6 +181,7 @@ mod tests {
         ToCollectionId, UserId,
     };
     use td_tower::ctx_service::RawOneshot;
+    use td_tower::extractors::{Connection, ConnectionType, Input, SrvCtx};
 
     #[cfg(feature = "test_tower_metadata")]
     #[td_test::test(sqlx)]
@@ -177,17 +204,20 @@ mod tests {
 
         use td_tower::metadata::type_of_val;
 
+        type UpdateFunctionRequest = UpdateRequest<FunctionExpectedVersionName, FunctionUpdate>;
+
         UpdateFunctionService::with_defaults(db)
             .metadata()
             .await
-            .assert_service::<UpdateRequest<FunctionParam, FunctionUpdate>, Function>(
+            .assert_service::<UpdateFunctionRequest, Function>(
             &[
-                type_of_val(&With::<UpdateRequest<FunctionParam, FunctionUpdate>>::extract::<RequestContext>),
-                type_of_val(&With::<UpdateRequest<FunctionParam, FunctionUpdate>>::extract_name::<FunctionParam>),
-                type_of_val(&With::<UpdateRequest<FunctionParam, FunctionUpdate>>::extract_data::<FunctionUpdate>),
+                type_of_val(&With::<UpdateFunctionRequest>::extract::<RequestContext>),
+                type_of_val(&With::<UpdateFunctionRequest>::extract_name::<FunctionExpectedVersionName>),
+                type_of_val(&With::<UpdateFunctionRequest>::extract_data::<FunctionUpdate>),
                 // Extract collection and current function from request.
-                type_of_val(&With::<FunctionParam>::extract::<CollectionIdName>),
-                type_of_val(&With::<FunctionParam>::extract::<FunctionIdName>),
+                type_of_val(&With::<FunctionExpectedVersionName>::extract::<CollectionIdName>),
+                type_of_val(&With::<FunctionExpectedVersionName>::extract::<FunctionIdName>),
+                type_of_val(&With::<FunctionExpectedVersionName>::extract::<Option<ExpectedFunctionVersionId>>),
                 // Get collection. Extract collection id and name.
                 type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
                 type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
@@ -207,6 +237,10 @@ mod tests {
                 // not change, but function version id does.
                 type_of_val(&With::<FunctionDBWithNames>::extract::<FunctionId>),
                 type_of_val(&With::<FunctionDBWithNames>::extract::<FunctionVersionId>),
+                // Refuse the update if the function was updated since the expected version, or
+                // concurrently.
+                type_of_val(&assert_expected_function_version),
+                type_of_val(&guard_function_version),
                 // If function has a new name, check new name does not exist in collection.
                 type_of_val(&assert_function_name_not_exists),
                 // Get location and storage version.
@@ -225,6 +259,9 @@ mod tests {
                 // Remove from bundles
                 type_of_val(&With::<FunctionDB>::extract::<BundleId>),
                 type_of_val(&By::<BundleId>::delete::<BundleDB>),
+                // Move the bundle blob reference from the previous version to the new one
+                type_of_val(&reference_bundle_blob),
+                type_of_val(&release_bundle_blob),
                 // Register associations
                 // Find previous versions
                 type_of_val(&By::<FunctionId>::select_all_versions::<{ TableDB::Available }, TableDB>),
@@ -253,6 +290,9 @@ mod tests {
                 type_of_val(&insert_vec::<TableDB>),
                 type_of_val(&build_tables_trigger_versions),
                 type_of_val(&insert_vec::<TriggerDB>),
+                // Replace tags
+                type_of_val(&With::<FunctionUpdate>::extract::<Option<Vec<FunctionTag>>>),
+                type_of_val(&register_function_tags),
                 // Insert into dependency_versions(sql) current function table dependencies status=Active.
                 type_of_val(&With::<FunctionDB>::convert_to::<DependencyDBBuilder, _>),
                 type_of_val(&With::<RequestContext>::update::<DependencyDBBuilder, _>),
@@ -1712,4 +1752,147 @@ mod tests {
         assert_eq!(dependency_function[0].name, dependant.name);
         Ok(())
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_update_concurrent_same_base_version(db: DbPool) -> Result<(), TdError> {
+        let collection =
+            seed_collection(&db, &CollectionName::try_from("cofnig")?, &UserId::admin()).await;
+
+        let update = |description: &str| -> Result<FunctionUpdate, TdError> {
+            let update = FunctionUpdate::builder()
+                .try_name("joaquin_workout")?
+                .try_description(description)?
+                .bundle_id(BundleId::default())
+                .try_snippet("function_foo snippet")?
+                .decorator(Decorator::Publisher)
+                .dependencies(None)
+                .triggers(None)
+                .tables(Some(vec![TableNameDto::try_from("table")?]))
+                .runtime_values(FunctionRuntimeValues::default())
+                .reuse_frozen_tables(false)
+                .build()?;
+            Ok(update)
+        };
+        let base_function =
+            seed_function(&db, &collection, &update("function_foo description")?).await;
+
+        // Both updates are based on the same function version.
+        let request = |description: &str| -> Result<_, TdError> {
+            let name = FunctionExpectedVersionName::new(
+                FunctionParam::builder()
+                    .try_collection("cofnig")?
+                    .try_function("joaquin_workout")?
+                    .build()?,
+                FunctionExpectedVersionParam::builder()
+                    .expected_version(Some(ExpectedFunctionVersionId::try_from(
+                        &base_function.id,
+                    )?))
+                    .build()?,
+            );
+            let request =
+                RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user())
+                    .update(name, update(description)?);
+            Ok(request)
+        };
+
+        let first = UpdateFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request("first update")?)
+            .await?;
+        assert_ne!(first.id, base_function.id);
+
+        let service = UpdateFunctionService::with_defaults(db.clone())
+            .service()
+            .await;
+        assert_service_error(service, request("second update")?, |err| match err {
+            UpdateFunctionError::VersionConflict(name, expected, current) => {
+                assert_eq!(name, &base_function.name);
+                assert_eq!(expected, &base_function.id);
+                assert_eq!(current, &first.id);
+            }
+            _ => panic!("Unexpected error: {err:?}"),
+        })
+        .await;
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_update_guard_function_version(db: DbPool) -> Result<(), TdError> {
+        let collection =
+            seed_collection(&db, &CollectionName::try_from("cofnig")?, &UserId::admin()).await;
+
+        let update = FunctionUpdate::builder()
+            .try_name("joaquin_workout")?
+            .try_description("function_foo description")?
+            .bundle_id(BundleId::default())
+            .try_snippet("function_foo snippet")?
+            .decorator(Decorator::Publisher)
+            .dependencies(None)
+            .triggers(None)
+            .tables(Some(vec![TableNameDto::try_from("table")?]))
+            .runtime_values(FunctionRuntimeValues::default())
+            .reuse_frozen_tables(false)
+            .build()?;
+        let base_function = seed_function(&db, &collection, &update).await;
+
+        let queries = DaoQueries::default();
+        let select = async |id: &FunctionVersionId| -> Result<FunctionDBWithNames, TdError> {
+            let function = queries
+                .select_by::<FunctionDBWithNames>(id)?
+                .build_query_as()
+                .fetch_one(&db)
+                .await
+                .map_err(handle_sql_err)?;
+            Ok(function)
+        };
+        let guard = async |function: FunctionDBWithNames| -> Result<(), TdError> {
+            let connection = db.acquire().await.unwrap();
+            let connection = ConnectionType::PoolConnection(connection).into();
+            guard_function_version(
+                Connection(connection),
+                SrvCtx::new(queries.clone()),
+                Input::new(function),
+            )
+            .await
+        };
+
+        // The current version guards the update.
+        let base = select(&base_function.id).await?;
+        guard(base.clone()).await?;
+
+        // Once the function is updated, the previous version does not.
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).update(
+                FunctionExpectedVersionName::new(
+                    FunctionParam::builder()
+                        .try_collection("cofnig")?
+                        .try_function("joaquin_workout")?
+                        .build()?,
+                    FunctionExpectedVersionParam::builder()
+                        .expected_version(None)
+                        .build()?,
+                ),
+                update,
+            );
+        let updated = UpdateFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let err = guard(base).await.unwrap_err();
+        match err.domain_err::<UpdateFunctionError>() {
+            UpdateFunctionError::UpdatedConcurrently(name, version) => {
+                assert_eq!(name, &base_function.name);
+                assert_eq!(version, &base_function.id);
+            }
+            err => panic!("Unexpected error: {err:?}"),
+        }
+        assert_eq!(err.api_error(), ApiError::Conflict);
+        guard(select(&updated.id).await?).await?;
+        Ok(())
+    }
 }
@@ -2,11 +2,12 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::function::layers::bundle::insert_pending_bundle;
 use crate::function::layers::register::data_location;
 use crate::function::layers::upload::upload_function_write_to_storage;
 use ta_services::factory::service_factory;
 use td_authz::{Authz, AuthzContext};
-use td_objects::dxo::bundle::{Bundle, BundleBuilder, BundleDB, BundleDBBuilder};
+use td_objects::dxo::bundle::{Bundle, BundleBlobDB, BundleBuilder, BundleDB, BundleDBBuilder};
 use td_objects::dxo::collection::CollectionDB;
 use td_objects::dxo::crudl::{CreateRequest, RequestContext};
 use td_objects::dxo::function_upload::FunctionUpload;
@@ -17,11 +18,12 @@ use td_objects::tower_service::from::{
     BuildService, DefaultService, ExtractDataService, ExtractNameService, ExtractService,
     SetService, TryIntoService, With,
 };
-use td_objects::tower_service::sql::{By, SqlSelectService, insert};
+use td_objects::tower_service::sql::{By, SqlSelectService};
 use td_objects::types::basic::{
-    BundleHash, BundleId, CollectionId, CollectionIdName, StorageVersion,
+    BundleHash, BundleId, CollectionId, CollectionIdName, CollectionName, StorageVersion,
 };
 use td_storage::Storage;
+use td_storage::quota::StorageQuota;
 use td_tower::default_services::TransactionProvider;
 use td_tower::from_fn::from_fn;
 use td_tower::layers;
@@ -31,9 +33,11 @@ use td_tower::layers;
     request = CreateRequest<CollectionParam, FunctionUpload>,
     response = Bundle,
     connection = TransactionProvider,
+    audit = BundleId,
     context = DaoQueries,
     context = AuthzContext,
     context = Storage,
+    context = StorageQuota,
 )]
 fn service() {
     layers!(
@@ -49,6 +53,7 @@ fn service() {
         // Extract collection
         from_fn(By::<CollectionIdName>::select::<CollectionDB>),
         from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        from_fn(With::<CollectionDB>::extract::<CollectionName>),
         // check requester is coll_admin or coll_dev for the function's collection
         from_fn(AuthzOn::<CollectionId>::set),
         from_fn(Authz::<CollAdmin, CollDev>::check),
@@ -58,13 +63,16 @@ fn service() {
         // Write to storage with new bundle id.
         from_fn(With::<BundleId>::default),
         from_fn(upload_function_write_to_storage),
+        // Bundles with identical content share the blob, and its bundle id.
+        from_fn(With::<BundleBlobDB>::extract::<BundleId>),
+        from_fn(With::<BundleBlobDB>::extract::<BundleHash>),
         // Build BundleDB
         from_fn(With::<RequestContext>::convert_to::<BundleDBBuilder, _>),
         from_fn(With::<BundleId>::set::<BundleDBBuilder>),
         from_fn(With::<CollectionId>::set::<BundleDBBuilder>),
         from_fn(With::<BundleHash>::set::<BundleDBBuilder>),
         from_fn(With::<BundleDBBuilder>::build::<BundleDB, _>),
-        from_fn(insert::<BundleDB>),
+        from_fn(insert_pending_bundle),
         // Build response
         from_fn(With::<BundleDB>::convert_to::<BundleBuilder, _>),
         from_fn(With::<BundleBuilder>::build::<Bundle, _>),
@@ -74,7 +82,8 @@ fn service() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Context;
+    use crate::scheduler::services::ScheduleServices;
+    use crate::{Context, SchedulerContext};
     use axum::body::Body;
     use axum::extract::Request;
     use sha2::{Digest, Sha256};
@@ -85,7 +94,7 @@ mod tests {
     use td_objects::dxo::crudl::handle_sql_err;
     use td_objects::sql::SelectBy;
     use td_objects::test_utils::seed_collection::seed_collection;
-    use td_objects::types::basic::{AccessTokenId, CollectionName, DataLocation, RoleId, UserId};
+    use td_objects::types::basic::{AccessTokenId, DataLocation, RoleId, UserId};
     use td_storage::location::StorageLocation;
     use td_tower::ctx_service::RawOneshot;
 
@@ -119,6 +128,7 @@ mod tests {
                 // Extract collection
                 type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
                 type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionName>),
                 // check requester is coll_admin or coll_dev for the function's collection
                 type_of_val(&AuthzOn::<CollectionId>::set),
                 type_of_val(&Authz::<CollAdmin, CollDev>::check),
@@ -128,13 +138,16 @@ mod tests {
                 // Write to storage with new bundle id.
                 type_of_val(&With::<BundleId>::default),
                 type_of_val(&upload_function_write_to_storage),
+                // Bundles with identical content share the blob, and its bundle id.
+                type_of_val(&With::<BundleBlobDB>::extract::<BundleId>),
+                type_of_val(&With::<BundleBlobDB>::extract::<BundleHash>),
                 // Build BundleDB
                 type_of_val(&With::<RequestContext>::convert_to::<BundleDBBuilder, _>),
                 type_of_val(&With::<BundleId>::set::<BundleDBBuilder>),
                 type_of_val(&With::<CollectionId>::set::<BundleDBBuilder>),
                 type_of_val(&With::<BundleHash>::set::<BundleDBBuilder>),
                 type_of_val(&With::<BundleDBBuilder>::build::<BundleDB, _>),
-                type_of_val(&insert::<BundleDB>),
+                type_of_val(&insert_pending_bundle),
                 // Build response
                 type_of_val(&With::<BundleDB>::convert_to::<BundleBuilder, _>),
                 type_of_val(&With::<BundleBuilder>::build::<Bundle, _>),
@@ -197,4 +210,284 @@ mod tests {
 
         Ok(())
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_upload_storage_quota(db: DbPool) -> Result<(), TdError> {
+        use crate::function::layers::upload::UploadError;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let collection_name = CollectionName::try_from("quota")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+
+        let mut context = Context::with_defaults(db.clone());
+        context.storage_quota = Arc::new(StorageQuota::new(
+            None,
+            HashMap::from([(collection_name.clone(), 10)]),
+        ));
+
+        let upload = |payload: &str| {
+            let request = Request::builder()
+                .body(Body::new(payload.to_string()))
+                .unwrap();
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                CollectionParam::builder()
+                    .try_collection(format!("{}", collection.name))
+                    .unwrap()
+                    .build()
+                    .unwrap(),
+                FunctionUpload::new(request),
+            )
+        };
+
+        // under the quota
+        let bundle = UploadFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(upload("123456"))
+            .await?;
+
+        // exceeding the quota
+        let err = UploadFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(upload("1234567"))
+            .await
+            .unwrap_err();
+        let err = err.domain_err::<UploadError>();
+        assert!(matches!(
+            err,
+            UploadError::StorageQuotaExceeded(name, 7, 6, 10) if *name == collection_name
+        ));
+
+        // the rejected bundle is neither in the db nor in storage
+        let queries = DaoQueries::default();
+        let bundle_db: Vec<BundleDB> = queries
+            .select_by::<BundleDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(bundle_db.len(), 1);
+        assert_eq!(bundle_db[0].id, bundle.id);
+
+        let (bundle_location, _) = StorageLocation::current()
+            .builder(&DataLocation::default())
+            .collection(&collection.id)
+            .function(&bundle.id)
+            .build();
+        let bundles_location = bundle_location.parent().unwrap();
+        assert_eq!(context.storage.usage(&bundles_location).await?, 6);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_upload_deduplicated_bundle(db: DbPool) -> Result<(), TdError> {
+        use crate::function::services::delete::DeleteFunctionService;
+        use crate::function::services::register::RegisterFunctionService;
+        use td_objects::rest_urls::FunctionParam;
+        use td_objects::test_utils::seed_function::function_register;
+
+        let collection_name = CollectionName::try_from("dedup")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+        let context = Context::with_defaults(db.clone());
+        let request_context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        let collection_param = CollectionParam::builder()
+            .try_collection(format!("{}", collection.name))?
+            .build()?;
+
+        // upload the same bundle twice
+        let mut bundles = vec![];
+        for _ in 0..2 {
+            let request = Request::builder()
+                .body(Body::new("TEXT".to_string()))
+                .unwrap();
+            let request = request_context
+                .clone()
+                .create(collection_param.clone(), FunctionUpload::new(request));
+            let bundle = UploadFunctionService::build(&context)
+                .service()
+                .await
+                .raw_oneshot(request)
+                .await?;
+            bundles.push(bundle);
+        }
+        assert_eq!(bundles[0].id, bundles[1].id);
+
+        // a single object is stored
+        let (bundle_location, _) = StorageLocation::current()
+            .builder(&DataLocation::default())
+            .collection(&collection.id)
+            .function(&bundles[0].id)
+            .build();
+        let bundles_location = bundle_location.parent().unwrap();
+        assert_eq!(context.storage.list(&bundles_location).await?.len(), 1);
+
+        // register two functions with it
+        for name in ["f0", "f1"] {
+            let mut create = function_register(name, &[], &[], &[])?;
+            create.bundle_id = bundles[0].id;
+            let request = request_context
+                .clone()
+                .create(collection_param.clone(), create);
+            RegisterFunctionService::build(&context)
+                .service()
+                .await
+                .raw_oneshot(request)
+                .await?;
+        }
+
+        let delete = |name: &str| {
+            request_context.clone().delete(
+                FunctionParam::builder()
+                    .try_collection(format!("{}", collection.name))
+                    .unwrap()
+                    .try_function(name)
+                    .unwrap()
+                    .build()
+                    .unwrap(),
+            )
+        };
+
+        // the shared blob is kept while a function uses it
+        DeleteFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(delete("f0"))
+            .await?;
+        assert!(context.storage.exists(&bundle_location).await?);
+
+        // and released with the last one, to be purged after the release is committed
+        DeleteFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(delete("f1"))
+            .await?;
+        assert!(context.storage.exists(&bundle_location).await?);
+
+        let queries = DaoQueries::default();
+        let blobs: Vec<BundleBlobDB> = queries
+            .select_by::<BundleBlobDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(*blobs[0].ref_count, 0);
+
+        let mut scheduler_context = SchedulerContext::with_defaults(db.clone());
+        scheduler_context.storage = context.storage.clone();
+        ScheduleServices::build(&scheduler_context)
+            .bundles()
+            .service()
+            .await
+            .raw_oneshot(())
+            .await?;
+        assert!(!context.storage.exists(&bundle_location).await?);
+
+        let blobs: Vec<BundleBlobDB> = queries
+            .select_by::<BundleBlobDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert!(blobs.is_empty());
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_release_bundle_blob_rollback(db: DbPool) -> Result<(), TdError> {
+        use crate::function::services::register::RegisterFunctionService;
+        use crate::function::services::update::UpdateFunctionService;
+        use td_objects::rest_urls::FunctionParam;
+        use td_objects::test_utils::seed_function::function_register;
+
+        let collection_name = CollectionName::try_from("rollback")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+        let context = Context::with_defaults(db.clone());
+        let request_context =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user());
+        let collection_param = CollectionParam::builder()
+            .try_collection(format!("{}", collection.name))?
+            .build()?;
+
+        let upload = |payload: &str| {
+            let request = Request::builder()
+                .body(Body::new(payload.to_string()))
+                .unwrap();
+            request_context
+                .clone()
+                .create(collection_param.clone(), FunctionUpload::new(request))
+        };
+
+        let bundle = UploadFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(upload("TEXT"))
+            .await?;
+        let mut create = function_register("f0", &[], &[], &[])?;
+        create.bundle_id = bundle.id;
+        RegisterFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(
+                request_context
+                    .clone()
+                    .create(collection_param.clone(), create),
+            )
+            .await?;
+
+        // an update to a new bundle failing after releasing the previous bundle blob
+        let new_bundle = UploadFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(upload("NEW TEXT"))
+            .await?;
+        let mut update = function_register("f0", &[], &["missing_table"], &[])?;
+        update.bundle_id = new_bundle.id;
+        let request = request_context.clone().update(
+            FunctionParam::builder()
+                .try_collection(format!("{}", collection.name))?
+                .try_function("f0")?
+                .build()?,
+            update,
+        );
+        assert!(
+            UpdateFunctionService::build(&context)
+                .service()
+                .await
+                .raw_oneshot(request)
+                .await
+                .is_err()
+        );
+
+        // rolls back the release, the blob is still in use and not purged
+        let mut scheduler_context = SchedulerContext::with_defaults(db.clone());
+        scheduler_context.storage = context.storage.clone();
+        ScheduleServices::build(&scheduler_context)
+            .bundles()
+            .service()
+            .await
+            .raw_oneshot(())
+            .await?;
+
+        let blob: BundleBlobDB = DaoQueries::default()
+            .select_by::<BundleBlobDB>(&bundle.id)?
+            .build_query_as()
+            .fetch_one(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(*blob.ref_count, 1);
+        let (bundle_location, _) = StorageLocation::current()
+            .builder(&DataLocation::default())
+            .collection(&collection.id)
+            .function(&bundle.id)
+            .build();
+        assert!(context.storage.exists(&bundle_location).await?);
+        Ok(())
+    }
 }
@@ -0,0 +1,177 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::bundle_upload::{select_bundle_upload, write_bundle_upload_chunk};
+use crate::function::layers::register::data_location;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::bundle::{BundleUpload, BundleUploadBuilder, BundleUploadDB};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{CreateRequest, RequestContext};
+use td_objects::dxo::function_upload::FunctionUpload;
+use td_objects::rest_urls::BundleUploadChunkParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin, CollDev};
+use td_objects::tower_service::from::{
+    BuildService, DefaultService, ExtractDataService, ExtractNameService, ExtractService,
+    TryIntoService, With,
+};
+use td_objects::tower_service::sql::{By, SqlSelectService};
+use td_objects::types::basic::{
+    BundleChunkOffset, BundleUploadId, CollectionId, CollectionIdName, CollectionName,
+    StorageVersion,
+};
+use td_storage::Storage;
+use td_storage::quota::StorageQuota;
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = UploadFunctionChunkService,
+    request = CreateRequest<BundleUploadChunkParam, FunctionUpload>,
+    response = BundleUpload,
+    connection = TransactionProvider,
+    audit = BundleUploadId,
+    context = DaoQueries,
+    context = AuthzContext,
+    context = Storage,
+    context = StorageQuota,
+)]
+fn service() {
+    layers!(
+        from_fn(
+            With::<CreateRequest<BundleUploadChunkParam, FunctionUpload>>::extract::<RequestContext>
+        ),
+        from_fn(
+            With::<CreateRequest<BundleUploadChunkParam, FunctionUpload>>::extract_name::<
+                BundleUploadChunkParam,
+            >
+        ),
+        from_fn(
+            With::<CreateRequest<BundleUploadChunkParam, FunctionUpload>>::extract_data::<
+                FunctionUpload,
+            >
+        ),
+        from_fn(With::<BundleUploadChunkParam>::extract::<CollectionIdName>),
+        from_fn(With::<BundleUploadChunkParam>::extract::<BundleUploadId>),
+        from_fn(With::<BundleUploadChunkParam>::extract::<BundleChunkOffset>),
+        // Extract collection
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        // check requester is coll_admin or coll_dev for the function's collection
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin, CollDev>::check),
+        from_fn(With::<CollectionDB>::extract::<CollectionName>),
+        // Get the pending upload, it must not have expired.
+        from_fn(select_bundle_upload),
+        // Get location and storage version.
+        from_fn(With::<StorageVersion>::default),
+        from_fn(data_location),
+        // Write the chunk to storage at its offset.
+        from_fn(write_bundle_upload_chunk),
+        // Build response
+        from_fn(With::<BundleUploadDB>::convert_to::<BundleUploadBuilder, _>),
+        from_fn(With::<BundleUploadBuilder>::build::<BundleUpload, _>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+    use crate::function::layers::upload::UploadError;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use ta_services::factory::ServiceFactory;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::types::basic::{AccessTokenId, RoleId, UserId};
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_upload_function_chunk(db: DbPool) -> Result<(), TdError> {
+        use td_tower::metadata::type_of_val;
+
+        UploadFunctionChunkService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<CreateRequest<BundleUploadChunkParam, FunctionUpload>, BundleUpload>(
+                &[
+                    type_of_val(
+                        &With::<CreateRequest<BundleUploadChunkParam, FunctionUpload>>::extract::<
+                            RequestContext,
+                        >,
+                    ),
+                    type_of_val(
+                        &With::<CreateRequest<BundleUploadChunkParam, FunctionUpload>>::extract_name::<
+                            BundleUploadChunkParam,
+                        >,
+                    ),
+                    type_of_val(
+                        &With::<CreateRequest<BundleUploadChunkParam, FunctionUpload>>::extract_data::<
+                            FunctionUpload,
+                        >,
+                    ),
+                    type_of_val(&With::<BundleUploadChunkParam>::extract::<CollectionIdName>),
+                    type_of_val(&With::<BundleUploadChunkParam>::extract::<BundleUploadId>),
+                    type_of_val(&With::<BundleUploadChunkParam>::extract::<BundleChunkOffset>),
+                    // Extract collection
+                    type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                    type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                    // check requester is coll_admin or coll_dev for the function's collection
+                    type_of_val(&AuthzOn::<CollectionId>::set),
+                    type_of_val(&Authz::<CollAdmin, CollDev>::check),
+                    type_of_val(&With::<CollectionDB>::extract::<CollectionName>),
+                    // Get the pending upload, it must not have expired.
+                    type_of_val(&select_bundle_upload),
+                    // Get location and storage version.
+                    type_of_val(&With::<StorageVersion>::default),
+                    type_of_val(&data_location),
+                    // Write the chunk to storage at its offset.
+                    type_of_val(&write_bundle_upload_chunk),
+                    // Build response
+                    type_of_val(&With::<BundleUploadDB>::convert_to::<BundleUploadBuilder, _>),
+                    type_of_val(&With::<BundleUploadBuilder>::build::<BundleUpload, _>),
+                ],
+            );
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_upload_chunk_not_found(db: DbPool) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("uploads")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+
+        let upload_id = BundleUploadId::default();
+        let request = Request::builder()
+            .body(Body::new("TEXT".to_string()))
+            .unwrap();
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                BundleUploadChunkParam::builder()
+                    .try_collection(format!("{}", collection.name))?
+                    .upload(upload_id)
+                    .offset(BundleChunkOffset::try_from(0)?)
+                    .build()?,
+                FunctionUpload::new(request),
+            );
+
+        let context = Context::with_defaults(db.clone());
+        let err = UploadFunctionChunkService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+            .unwrap_err();
+        let err = err.domain_err::<UploadError>();
+        assert!(matches!(err, UploadError::BundleUploadNotFound(id) if *id == upload_id));
+        Ok(())
+    }
+}
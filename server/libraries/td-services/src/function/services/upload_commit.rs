@@ -0,0 +1,384 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::bundle::insert_pending_bundle;
+use crate::function::layers::bundle_upload::{commit_bundle_upload, select_bundle_upload};
+use crate::function::layers::register::data_location;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::bundle::{
+    Bundle, BundleBlobDB, BundleBuilder, BundleDB, BundleDBBuilder, BundleUploadCommit,
+    BundleUploadDB,
+};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{CreateRequest, RequestContext};
+use td_objects::rest_urls::BundleUploadParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin, CollDev};
+use td_objects::tower_service::from::{
+    BuildService, DefaultService, ExtractDataService, ExtractNameService, ExtractService,
+    SetService, TryIntoService, With,
+};
+use td_objects::tower_service::sql::{By, SqlDeleteService, SqlSelectService};
+use td_objects::types::basic::{
+    BundleHash, BundleId, BundleUploadId, CollectionId, CollectionIdName, CollectionName,
+    StorageVersion,
+};
+use td_storage::Storage;
+use td_storage::quota::StorageQuota;
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = CommitFunctionUploadService,
+    request = CreateRequest<BundleUploadParam, BundleUploadCommit>,
+    response = Bundle,
+    connection = TransactionProvider,
+    audit = BundleId,
+    context = DaoQueries,
+    context = AuthzContext,
+    context = Storage,
+    context = StorageQuota,
+)]
+fn service() {
+    layers!(
+        from_fn(
+            With::<CreateRequest<BundleUploadParam, BundleUploadCommit>>::extract::<RequestContext>
+        ),
+        from_fn(
+            With::<CreateRequest<BundleUploadParam, BundleUploadCommit>>::extract_name::<
+                BundleUploadParam,
+            >
+        ),
+        from_fn(
+            With::<CreateRequest<BundleUploadParam, BundleUploadCommit>>::extract_data::<
+                BundleUploadCommit,
+            >
+        ),
+        from_fn(With::<BundleUploadParam>::extract::<CollectionIdName>),
+        from_fn(With::<BundleUploadParam>::extract::<BundleUploadId>),
+        // Extract collection
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        from_fn(With::<CollectionDB>::extract::<CollectionName>),
+        // check requester is coll_admin or coll_dev for the function's collection
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin, CollDev>::check),
+        // Get the pending upload, it must not have expired.
+        from_fn(select_bundle_upload),
+        // Get location and storage version.
+        from_fn(With::<StorageVersion>::default),
+        from_fn(data_location),
+        // Assemble the chunks and write to storage with new bundle id.
+        from_fn(With::<BundleId>::default),
+        from_fn(commit_bundle_upload),
+        // Bundles with identical content share the blob, and its bundle id.
+        from_fn(With::<BundleBlobDB>::extract::<BundleId>),
+        from_fn(With::<BundleBlobDB>::extract::<BundleHash>),
+        // Build BundleDB
+        from_fn(With::<RequestContext>::convert_to::<BundleDBBuilder, _>),
+        from_fn(With::<BundleId>::set::<BundleDBBuilder>),
+        from_fn(With::<CollectionId>::set::<BundleDBBuilder>),
+        from_fn(With::<BundleHash>::set::<BundleDBBuilder>),
+        from_fn(With::<BundleDBBuilder>::build::<BundleDB, _>),
+        from_fn(insert_pending_bundle),
+        // The upload is done.
+        from_fn(By::<BundleUploadId>::delete::<BundleUploadDB>),
+        // Build response
+        from_fn(With::<BundleDB>::convert_to::<BundleBuilder, _>),
+        from_fn(With::<BundleBuilder>::build::<Bundle, _>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+    use crate::function::layers::upload::UploadError;
+    use crate::function::services::upload_chunk::UploadFunctionChunkService;
+    use crate::function::services::upload_start::StartFunctionUploadService;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use sha2::{Digest, Sha256};
+    use ta_services::factory::ServiceFactory;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::bundle::BundleUpload;
+    use td_objects::dxo::crudl::handle_sql_err;
+    use td_objects::dxo::function_upload::FunctionUpload;
+    use td_objects::rest_urls::{BundleUploadChunkParam, CollectionParam};
+    use td_objects::sql::SelectBy;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::types::basic::{
+        AccessTokenId, BundleChunkOffset, BundleSize, DataLocation, RoleId, UserId,
+    };
+    use td_storage::location::StorageLocation;
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_commit_function_upload(db: DbPool) -> Result<(), TdError> {
+        use td_tower::metadata::type_of_val;
+
+        CommitFunctionUploadService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<CreateRequest<BundleUploadParam, BundleUploadCommit>, Bundle>(&[
+                type_of_val(
+                    &With::<CreateRequest<BundleUploadParam, BundleUploadCommit>>::extract::<
+                        RequestContext,
+                    >,
+                ),
+                type_of_val(
+                    &With::<CreateRequest<BundleUploadParam, BundleUploadCommit>>::extract_name::<
+                        BundleUploadParam,
+                    >,
+                ),
+                type_of_val(
+                    &With::<CreateRequest<BundleUploadParam, BundleUploadCommit>>::extract_data::<
+                        BundleUploadCommit,
+                    >,
+                ),
+                type_of_val(&With::<BundleUploadParam>::extract::<CollectionIdName>),
+                type_of_val(&With::<BundleUploadParam>::extract::<BundleUploadId>),
+                // Extract collection
+                type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionName>),
+                // check requester is coll_admin or coll_dev for the function's collection
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<CollAdmin, CollDev>::check),
+                // Get the pending upload, it must not have expired.
+                type_of_val(&select_bundle_upload),
+                // Get location and storage version.
+                type_of_val(&With::<StorageVersion>::default),
+                type_of_val(&data_location),
+                // Assemble the chunks and write to storage with new bundle id.
+                type_of_val(&With::<BundleId>::default),
+                type_of_val(&commit_bundle_upload),
+                // Bundles with identical content share the blob, and its bundle id.
+                type_of_val(&With::<BundleBlobDB>::extract::<BundleId>),
+                type_of_val(&With::<BundleBlobDB>::extract::<BundleHash>),
+                // Build BundleDB
+                type_of_val(&With::<RequestContext>::convert_to::<BundleDBBuilder, _>),
+                type_of_val(&With::<BundleId>::set::<BundleDBBuilder>),
+                type_of_val(&With::<CollectionId>::set::<BundleDBBuilder>),
+                type_of_val(&With::<BundleHash>::set::<BundleDBBuilder>),
+                type_of_val(&With::<BundleDBBuilder>::build::<BundleDB, _>),
+                type_of_val(&insert_pending_bundle),
+                // The upload is done.
+                type_of_val(&By::<BundleUploadId>::delete::<BundleUploadDB>),
+                // Build response
+                type_of_val(&With::<BundleDB>::convert_to::<BundleBuilder, _>),
+                type_of_val(&With::<BundleBuilder>::build::<Bundle, _>),
+            ]);
+        Ok(())
+    }
+
+    fn request_context() -> RequestContext {
+        RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user())
+    }
+
+    async fn start_upload(
+        context: &Context,
+        collection: &CollectionName,
+    ) -> Result<BundleUpload, TdError> {
+        let request = request_context().create(
+            CollectionParam::builder()
+                .try_collection(format!("{collection}"))?
+                .build()?,
+            (),
+        );
+        StartFunctionUploadService::build(context)
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    async fn upload_chunk(
+        context: &Context,
+        collection: &CollectionName,
+        upload: &BundleUpload,
+        offset: i64,
+        chunk: &str,
+    ) -> Result<BundleUpload, TdError> {
+        let request = Request::builder()
+            .body(Body::new(chunk.to_string()))
+            .unwrap();
+        let request = request_context().create(
+            BundleUploadChunkParam::builder()
+                .try_collection(format!("{collection}"))?
+                .upload(upload.id)
+                .offset(BundleChunkOffset::try_from(offset)?)
+                .build()?,
+            FunctionUpload::new(request),
+        );
+        UploadFunctionChunkService::build(context)
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    async fn commit_upload(
+        context: &Context,
+        collection: &CollectionName,
+        upload: &BundleUpload,
+        size: usize,
+        hash: Option<BundleHash>,
+    ) -> Result<Bundle, TdError> {
+        let request = request_context().create(
+            BundleUploadParam::builder()
+                .try_collection(format!("{collection}"))?
+                .upload(upload.id)
+                .build()?,
+            BundleUploadCommit::builder()
+                .size(BundleSize::try_from(size as i64)?)
+                .hash(hash)
+                .build()?,
+        );
+        CommitFunctionUploadService::build(context)
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_commit_chunked_upload(db: DbPool) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("chunked")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+        let context = Context::with_defaults(db.clone());
+
+        let chunks = ["FIRST-", "SECOND-", "THIRD"];
+        let payload = chunks.concat();
+
+        // chunks can be uploaded in any order
+        let upload = start_upload(&context, &collection_name).await?;
+        upload_chunk(&context, &collection_name, &upload, 13, chunks[2]).await?;
+        upload_chunk(&context, &collection_name, &upload, 0, chunks[0]).await?;
+        upload_chunk(&context, &collection_name, &upload, 6, chunks[1]).await?;
+
+        let hash = BundleHash::try_from(&hex::encode(&Sha256::digest(&payload)[..]))?;
+        let bundle = commit_upload(
+            &context,
+            &collection_name,
+            &upload,
+            payload.len(),
+            Some(hash.clone()),
+        )
+        .await?;
+
+        // Assert db
+        let queries = DaoQueries::default();
+        let bundle_db: Vec<BundleDB> = queries
+            .select_by::<BundleDB>(&bundle.id)?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(bundle_db.len(), 1);
+        assert_eq!(bundle_db[0].collection_id, collection.id);
+        assert_eq!(bundle_db[0].hash, hash);
+
+        let uploads: Vec<BundleUploadDB> = queries
+            .select_by::<BundleUploadDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert!(uploads.is_empty());
+
+        // Assert storage, the assembled bundle is stored and the chunks are gone
+        let (bundle_location, _) = StorageLocation::current()
+            .builder(&DataLocation::default())
+            .collection(&collection.id)
+            .function(&bundle.id)
+            .build();
+        let content = context.storage.read(&bundle_location).await?;
+        assert_eq!(String::from_utf8(content).unwrap(), payload);
+
+        let (upload_location, _) = StorageLocation::current()
+            .builder(&DataLocation::default())
+            .collection(&collection.id)
+            .upload(&upload.id)
+            .build();
+        assert!(context.storage.list(&upload_location).await?.is_empty());
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_commit_chunked_upload_missing_chunk(db: DbPool) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("chunked")?;
+        seed_collection(&db, &collection_name, &UserId::admin()).await;
+        let context = Context::with_defaults(db.clone());
+
+        let upload = start_upload(&context, &collection_name).await?;
+        upload_chunk(&context, &collection_name, &upload, 0, "FIRST-").await?;
+        upload_chunk(&context, &collection_name, &upload, 13, "THIRD").await?;
+
+        let err = commit_upload(&context, &collection_name, &upload, 18, None)
+            .await
+            .unwrap_err();
+        let err = err.domain_err::<UploadError>();
+        assert!(matches!(
+            err,
+            UploadError::MissingBundleChunk(id, 6) if *id == upload.id
+        ));
+
+        // the missing chunk can still be uploaded, and the upload committed
+        upload_chunk(&context, &collection_name, &upload, 6, "SECOND-").await?;
+        commit_upload(&context, &collection_name, &upload, 18, None).await?;
+
+        // a wrong size is rejected too
+        let upload = start_upload(&context, &collection_name).await?;
+        upload_chunk(&context, &collection_name, &upload, 0, "FIRST-").await?;
+        let err = commit_upload(&context, &collection_name, &upload, 5, None)
+            .await
+            .unwrap_err();
+        let err = err.domain_err::<UploadError>();
+        assert!(matches!(err, UploadError::BundleSizeMismatch(size, 6) if **size == 5));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_upload_chunk_storage_quota(db: DbPool) -> Result<(), TdError> {
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let collection_name = CollectionName::try_from("chunked")?;
+        seed_collection(&db, &collection_name, &UserId::admin()).await;
+        let mut context = Context::with_defaults(db.clone());
+        context.storage_quota = Arc::new(StorageQuota::new(
+            None,
+            HashMap::from([(collection_name.clone(), 10)]),
+        ));
+
+        let upload = start_upload(&context, &collection_name).await?;
+        upload_chunk(&context, &collection_name, &upload, 0, "FIRST-").await?;
+
+        // the upload so far does not fit in the quota
+        let err = upload_chunk(&context, &collection_name, &upload, 6, "SECOND-")
+            .await
+            .unwrap_err();
+        let err = err.domain_err::<UploadError>();
+        assert!(matches!(
+            err,
+            UploadError::StorageQuotaExceeded(name, 13, 0, 10) if *name == collection_name
+        ));
+
+        // a chunk stored again does not count twice
+        upload_chunk(&context, &collection_name, &upload, 0, "FIRST-").await?;
+        upload_chunk(&context, &collection_name, &upload, 6, "LAST").await?;
+        commit_upload(&context, &collection_name, &upload, 10, None).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::bundle_upload::start_bundle_upload;
+use crate::function::layers::register::data_location;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::bundle::{BundleUpload, BundleUploadBuilder, BundleUploadDB};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{CreateRequest, RequestContext};
+use td_objects::rest_urls::CollectionParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin, CollDev};
+use td_objects::tower_service::from::{
+    BuildService, DefaultService, ExtractNameService, ExtractService, TryIntoService, With,
+};
+use td_objects::tower_service::sql::{By, SqlSelectService};
+use td_objects::types::basic::{BundleUploadId, CollectionId, CollectionIdName, StorageVersion};
+use td_storage::Storage;
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = StartFunctionUploadService,
+    request = CreateRequest<CollectionParam, ()>,
+    response = BundleUpload,
+    connection = TransactionProvider,
+    audit = BundleUploadId,
+    context = DaoQueries,
+    context = AuthzContext,
+    context = Storage,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<CreateRequest<CollectionParam, ()>>::extract::<RequestContext>),
+        from_fn(With::<CreateRequest<CollectionParam, ()>>::extract_name::<CollectionParam>),
+        // Extract collection
+        from_fn(With::<CollectionParam>::extract::<CollectionIdName>),
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        // check requester is coll_admin or coll_dev for the function's collection
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin, CollDev>::check),
+        // Get location and storage version.
+        from_fn(With::<StorageVersion>::default),
+        from_fn(data_location),
+        // Start the upload, purging the expired ones.
+        from_fn(start_bundle_upload),
+        from_fn(With::<BundleUploadDB>::extract::<BundleUploadId>),
+        // Build response
+        from_fn(With::<BundleUploadDB>::convert_to::<BundleUploadBuilder, _>),
+        from_fn(With::<BundleUploadBuilder>::build::<BundleUpload, _>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+    use ta_services::factory::ServiceFactory;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::crudl::handle_sql_err;
+    use td_objects::sql::SelectBy;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::types::basic::{AccessTokenId, AtTime, CollectionName, RoleId, UserId};
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_start_function_upload(db: DbPool) -> Result<(), TdError> {
+        use td_tower::metadata::type_of_val;
+
+        StartFunctionUploadService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<CreateRequest<CollectionParam, ()>, BundleUpload>(&[
+                type_of_val(&With::<CreateRequest<CollectionParam, ()>>::extract::<RequestContext>),
+                type_of_val(
+                    &With::<CreateRequest<CollectionParam, ()>>::extract_name::<CollectionParam>,
+                ),
+                // Extract collection
+                type_of_val(&With::<CollectionParam>::extract::<CollectionIdName>),
+                type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                // check requester is coll_admin or coll_dev for the function's collection
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<CollAdmin, CollDev>::check),
+                // Get location and storage version.
+                type_of_val(&With::<StorageVersion>::default),
+                type_of_val(&data_location),
+                // Start the upload, purging the expired ones.
+                type_of_val(&start_bundle_upload),
+                type_of_val(&With::<BundleUploadDB>::extract::<BundleUploadId>),
+                // Build response
+                type_of_val(&With::<BundleUploadDB>::convert_to::<BundleUploadBuilder, _>),
+                type_of_val(&With::<BundleUploadBuilder>::build::<BundleUpload, _>),
+            ]);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_start_upload(db: DbPool) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("uploads")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                CollectionParam::builder()
+                    .try_collection(format!("{}", collection.name))?
+                    .build()?,
+                (),
+            );
+
+        let context = Context::with_defaults(db.clone());
+        let response = StartFunctionUploadService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert!(response.expires_on.timestamp_millis() > AtTime::now().timestamp_millis());
+
+        let queries = DaoQueries::default();
+        let uploads: Vec<BundleUploadDB> = queries
+            .select_by::<BundleUploadDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(uploads.len(), 1);
+        assert_eq!(uploads[0].id, response.id);
+        assert_eq!(uploads[0].collection_id, collection.id);
+        assert_eq!(uploads[0].created_by_id, UserId::admin());
+        Ok(())
+    }
+}
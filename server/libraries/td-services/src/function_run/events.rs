@@ -0,0 +1,161 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Status transitions of a function run, streamed by the apiserver as server-sent events.
+
+use futures::Stream;
+use std::future::Future;
+use std::time::Duration;
+use td_error::TdError;
+use td_objects::dxo::function_run::FunctionRun;
+use td_objects::types::basic::FunctionRunStatus;
+use tokio::time::Instant;
+
+/// Frequency the function run status is checked at after a transition.
+pub const FUNCTION_RUN_EVENTS_FREQUENCY: Duration = Duration::from_millis(500);
+
+/// Frequency the function run status is backed off to while it does not change.
+pub const FUNCTION_RUN_EVENTS_MAX_FREQUENCY: Duration = Duration::from_secs(10);
+
+/// Time a stream is kept open, clients reconnect to keep following the function run.
+pub const FUNCTION_RUN_EVENTS_MAX_WAIT: Duration = Duration::from_secs(30 * 60);
+
+/// Polling of the function run status while streaming its transitions.
+#[derive(Debug, Clone)]
+pub struct EventsPolling {
+    /// Delay between reads after a transition, doubled on every read without one.
+    pub frequency: Duration,
+    /// Maximum delay between reads.
+    pub max_frequency: Duration,
+    /// Time after which the stream ends, even if the function run has not finished.
+    pub max_wait: Duration,
+}
+
+impl Default for EventsPolling {
+    fn default() -> Self {
+        Self {
+            frequency: FUNCTION_RUN_EVENTS_FREQUENCY,
+            max_frequency: FUNCTION_RUN_EVENTS_MAX_FREQUENCY,
+            max_wait: FUNCTION_RUN_EVENTS_MAX_WAIT,
+        }
+    }
+}
+
+/// Status ending the stream of a function run. `Done` is not one of them, as the function run
+/// is still to be committed or canceled.
+pub fn is_final_event(status: &FunctionRunStatus) -> bool {
+    matches!(
+        status,
+        FunctionRunStatus::Committed
+            | FunctionRunStatus::Failed
+            | FunctionRunStatus::Canceled
+            | FunctionRunStatus::Yanked
+    )
+}
+
+/// Stream of the status transitions of a function run, read with the given function as
+/// configured by `polling`. The first item is the current status, and the stream ends after a
+/// final status, a read error or `polling.max_wait`.
+pub fn function_run_status_events<F, Fut>(
+    read: F,
+    polling: EventsPolling,
+) -> impl Stream<Item = Result<FunctionRun, TdError>> + Send
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<FunctionRun, TdError>> + Send,
+{
+    status_events(read, |function_run| &function_run.status, polling)
+}
+
+fn status_events<T, F, Fut, S>(
+    read: F,
+    status: S,
+    polling: EventsPolling,
+) -> impl Stream<Item = Result<T, TdError>> + Send
+where
+    T: Send,
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<T, TdError>> + Send,
+    S: Fn(&T) -> &FunctionRunStatus + Send + Sync + 'static,
+{
+    let deadline = Instant::now() + polling.max_wait;
+    // The state is the last streamed status, None once the stream is over.
+    futures::stream::unfold(
+        (read, status, Some(None::<FunctionRunStatus>)),
+        move |(read, status, last)| {
+            let polling = polling.clone();
+            async move {
+                let last = last?;
+                let mut delay = polling.frequency;
+                if last.is_some() {
+                    sleep_until(deadline, delay).await?;
+                }
+                loop {
+                    match read().await {
+                        Ok(item) if last.as_ref() == Some(status(&item)) => {}
+                        Ok(item) => {
+                            let next = (!is_final_event(status(&item)))
+                                .then(|| Some(status(&item).clone()));
+                            return Some((Ok(item), (read, status, next)));
+                        }
+                        Err(e) => return Some((Err(e), (read, status, None))),
+                    }
+                    delay = (delay * 2).min(polling.max_frequency);
+                    sleep_until(deadline, delay).await?;
+                }
+            }
+        },
+    )
+}
+
+/// Sleeps for the given delay, returning None instead if the deadline is reached first.
+async fn sleep_until(deadline: Instant, delay: Duration) -> Option<()> {
+    let wake_up = Instant::now() + delay;
+    if wake_up >= deadline {
+        tokio::time::sleep_until(deadline).await;
+        None
+    } else {
+        tokio::time::sleep_until(wake_up).await;
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_status_events_max_wait_and_backoff() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let read = {
+            let reads = reads.clone();
+            move || {
+                reads.fetch_add(1, Ordering::SeqCst);
+                async { Ok(FunctionRunStatus::Running) }
+            }
+        };
+        let polling = EventsPolling {
+            frequency: Duration::from_millis(10),
+            max_frequency: Duration::from_millis(40),
+            max_wait: Duration::from_millis(300),
+        };
+
+        let start = Instant::now();
+        let events: Vec<_> = status_events(read, |status| status, polling)
+            .collect()
+            .await;
+        let elapsed = start.elapsed();
+
+        // The current status only, and the stream ends at the maximum wait.
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Ok(FunctionRunStatus::Running)));
+        assert!(elapsed >= Duration::from_millis(300));
+        assert!(elapsed < Duration::from_secs(5));
+        // Without backoff it would have been read ~30 times, with it ~9.
+        assert!(reads.load(Ordering::SeqCst) < 15);
+    }
+}
@@ -1,3 +1,5 @@
 //
 // Copyright 2025 Tabs Data Inc.
 //
+
+pub(crate) mod webhook;
@@ -0,0 +1,250 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function_run::WebhookError;
+use crate::function_run::webhook::WebhookClient;
+use async_trait::async_trait;
+use futures::{StreamExt, stream};
+use std::ops::Deref;
+use td_common::time::UniqueUtc;
+use td_error::TdError;
+use td_objects::dxo::crudl::handle_sql_err;
+use td_objects::dxo::function::FunctionDB;
+use td_objects::dxo::function_run::{
+    FunctionRunBuilder, FunctionRunDBWithNames, UpdateFunctionRunDB,
+};
+use td_objects::dxo::webhook::{
+    UpdateWebhookDeliveryDB, WebhookCreate, WebhookDB, WebhookDBBuilder, WebhookDeliveryDB,
+    WebhookDeliveryToDeliverDB,
+};
+use td_objects::sql::cte::CteQueries;
+use td_objects::sql::{DaoQueries, Insert, SelectBy, UpdateBy};
+use td_objects::tower_service::from::With;
+use td_objects::types::basic::{
+    AtTime, CollectionId, FunctionRunId, FunctionRunStatus, WebhookAttempts, WebhookDeliveryError,
+    WebhookDeliveryStatus, WebhookPayload,
+};
+use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
+use tracing::warn;
+
+#[async_trait]
+pub trait UpdateCreateWebhookDBBuilder {
+    async fn update_create_webhook_db_builder(
+        queries: SrvCtx<DaoQueries>,
+        connection: Connection,
+        collection_id: Input<CollectionId>,
+        create: Input<WebhookCreate>,
+        builder: Input<WebhookDBBuilder>,
+    ) -> Result<WebhookDBBuilder, TdError>;
+}
+
+#[async_trait]
+impl UpdateCreateWebhookDBBuilder for With<WebhookCreate> {
+    async fn update_create_webhook_db_builder(
+        SrvCtx(queries): SrvCtx<DaoQueries>,
+        Connection(connection): Connection,
+        Input(collection_id): Input<CollectionId>,
+        Input(create): Input<WebhookCreate>,
+        Input(builder): Input<WebhookDBBuilder>,
+    ) -> Result<WebhookDBBuilder, TdError> {
+        let function_id = match &create.function {
+            Some(function) => {
+                let mut conn = connection.lock().await;
+                let conn = conn.get_mut_connection()?;
+                let found: Option<FunctionDB> = queries
+                    .select_versions_at::<{ FunctionDB::Active }, FunctionDB>(
+                        None,
+                        &(&*collection_id, function),
+                    )?
+                    .build_query_as()
+                    .fetch_optional(&mut *conn)
+                    .await
+                    .map_err(handle_sql_err)?;
+                match found {
+                    Some(found) => Some(found.function_id),
+                    None => Err(WebhookError::FunctionNotFound(function.clone()))?,
+                }
+            }
+            None => None,
+        };
+
+        let mut builder = builder.deref().clone();
+        builder
+            .url(create.url.clone())
+            .secret(create.secret.clone())
+            .function_id(function_id);
+        Ok(builder)
+    }
+}
+
+/// Enqueues the notification of a function run to the webhooks of its collection and function,
+/// if the update completed or failed it.
+pub async fn enqueue_function_run_webhooks(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Connection(connection): Connection,
+    Input(function_run_id): Input<FunctionRunId>,
+    Input(update): Input<UpdateFunctionRunDB>,
+) -> Result<(), TdError> {
+    if !matches!(
+        update.status,
+        FunctionRunStatus::Done | FunctionRunStatus::Failed
+    ) {
+        return Ok(());
+    }
+
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    // The update is a no-op for function runs already in a final status, like canceled ones.
+    let function_run: FunctionRunDBWithNames = queries
+        .select_by::<FunctionRunDBWithNames>(&*function_run_id)?
+        .build_query_as()
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    if !matches!(
+        function_run.status,
+        FunctionRunStatus::Done | FunctionRunStatus::Committed | FunctionRunStatus::Failed
+    ) {
+        return Ok(());
+    }
+
+    let webhooks: Vec<WebhookDB> = queries
+        .select_by::<WebhookDB>(&function_run.collection_id)?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+
+    let function: FunctionDB = queries
+        .select_by::<FunctionDB>(&function_run.function_version_id)?
+        .build_query_as()
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+
+    let payload = FunctionRunBuilder::try_from(&function_run)?.build()?;
+    let payload = serde_json::to_string(&payload).map_err(WebhookError::PayloadSerialization)?;
+    let payload = WebhookPayload::try_from(payload)?;
+
+    // Repeated callbacks of a function run notify each webhook once.
+    let delivered: Vec<WebhookDeliveryDB> = queries
+        .select_by::<WebhookDeliveryDB>(&function_run.id)?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+
+    let now = AtTime::now();
+    for webhook in webhooks.iter().filter(|webhook| {
+        webhook
+            .function_id
+            .is_none_or(|id| id == function.function_id)
+            && !delivered
+                .iter()
+                .any(|delivery| delivery.webhook_id == webhook.id)
+    }) {
+        let delivery = WebhookDeliveryDB::builder()
+            .webhook_id(webhook.id)
+            .function_run_id(function_run.id)
+            .payload(payload.clone())
+            .attempts(WebhookAttempts::try_from(0)?)
+            .next_attempt_on(now.clone())
+            .created_on(now.clone())
+            .build()?;
+        queries
+            .insert(&delivery)?
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+    }
+    Ok(())
+}
+
+/// Delivers the pending webhook notifications that are due. Failed deliveries are retried with
+/// an exponential backoff, and dead lettered once they reach the maximum attempts.
+pub async fn deliver_webhooks(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    SrvCtx(client): SrvCtx<WebhookClient>,
+    Connection(connection): Connection,
+) -> Result<(), TdError> {
+    let now = AtTime::now();
+    let deliveries: Vec<WebhookDeliveryToDeliverDB> = {
+        let mut conn = connection.lock().await;
+        let conn = conn.get_mut_connection()?;
+        queries
+            .select_by::<WebhookDeliveryToDeliverDB>(&())?
+            .build_query_as()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?
+    };
+
+    // The connection is not held while posting, webhooks can be slow to respond. Deliveries are
+    // posted concurrently, up to the client limit, and recorded as each of them finishes.
+    let client: &WebhookClient = &client;
+    let mut posted =
+        stream::iter(deliveries.iter().filter(|delivery| {
+            delivery.next_attempt_on.timestamp_millis() <= now.timestamp_millis()
+        }))
+        .map(|delivery| async move {
+            let result = client
+                .post(
+                    &delivery.url,
+                    &delivery.id,
+                    &delivery.secret,
+                    &delivery.payload,
+                )
+                .await;
+            (delivery, result)
+        })
+        .buffer_unordered(*client.max_concurrent_deliveries());
+
+    while let Some((delivery, result)) = posted.next().await {
+        let attempts = WebhookAttempts::try_from(*delivery.attempts + 1)?;
+        let update = match result {
+            Ok(()) => UpdateWebhookDeliveryDB::builder()
+                .status(WebhookDeliveryStatus::Delivered)
+                .attempts(attempts)
+                .next_attempt_on(delivery.next_attempt_on.clone())
+                .last_error(None)
+                .delivered_on(Some(AtTime::now()))
+                .build()?,
+            Err(error) => {
+                let status = if *attempts >= *client.max_attempts() {
+                    warn!(
+                        "Webhook delivery [{}] of function run [{}] dead lettered after {} attempts: {}",
+                        delivery.id, delivery.function_run_id, *attempts, error
+                    );
+                    WebhookDeliveryStatus::DeadLetter
+                } else {
+                    WebhookDeliveryStatus::Pending
+                };
+                UpdateWebhookDeliveryDB::builder()
+                    .status(status)
+                    .attempts(attempts.clone())
+                    .next_attempt_on(
+                        (UniqueUtc::now_millis() + client.retry_delay(*attempts)).try_into()?,
+                    )
+                    .last_error(Some(WebhookDeliveryError::try_from(error)?))
+                    .delivered_on(None)
+                    .build()?
+            }
+        };
+
+        let mut conn = connection.lock().await;
+        let conn = conn.get_mut_connection()?;
+        queries
+            .update_by::<_, WebhookDeliveryDB>(&update, &(&delivery.id))?
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+    }
+    Ok(())
+}
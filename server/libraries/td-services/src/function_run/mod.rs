@@ -2,5 +2,20 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-mod layers;
+use td_error::td_error;
+use td_objects::types::basic::FunctionName;
+
+pub mod events;
+pub(crate) mod layers;
 pub mod services;
+pub mod webhook;
+
+#[td_error]
+pub enum WebhookError {
+    #[error("Function [{0}] does not exist in the collection")]
+    FunctionNotFound(FunctionName) = 1000,
+    #[error("Could not serialize function run webhook payload: {0}")]
+    PayloadSerialization(#[from] serde_json::Error) = 5000,
+    #[error("Could not build the webhook HTTP client: {0}")]
+    Client(#[source] reqwest::Error) = 5001,
+}
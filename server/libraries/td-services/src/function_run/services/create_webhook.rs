@@ -0,0 +1,208 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function_run::layers::webhook::UpdateCreateWebhookDBBuilder;
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{CreateRequest, RequestContext};
+use td_objects::dxo::webhook::{
+    Webhook, WebhookBuilder, WebhookCreate, WebhookDB, WebhookDBBuilder,
+};
+use td_objects::rest_urls::CollectionParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin};
+use td_objects::tower_service::from::{
+    BuildService, ExtractDataService, ExtractNameService, ExtractService, SetService,
+    TryIntoService, UpdateService, With, builder,
+};
+use td_objects::tower_service::sql::{By, SqlSelectService, insert};
+use td_objects::types::basic::{CollectionId, CollectionIdName, WebhookId};
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = CreateWebhookService,
+    request = CreateRequest<CollectionParam, WebhookCreate>,
+    response = Webhook,
+    connection = TransactionProvider,
+    audit = WebhookId,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<CreateRequest<CollectionParam, WebhookCreate>>::extract::<RequestContext>),
+        from_fn(
+            With::<CreateRequest<CollectionParam, WebhookCreate>>::extract_name::<CollectionParam>
+        ),
+        from_fn(
+            With::<CreateRequest<CollectionParam, WebhookCreate>>::extract_data::<WebhookCreate>
+        ),
+        // find collection ID
+        from_fn(With::<CollectionParam>::extract::<CollectionIdName>),
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        // check requester is coll_admin for the collection
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin>::check),
+        // create webhook DAO
+        from_fn(builder::<WebhookDBBuilder>),
+        from_fn(With::<RequestContext>::update::<WebhookDBBuilder, _>),
+        from_fn(With::<CollectionId>::set::<WebhookDBBuilder>),
+        from_fn(With::<WebhookCreate>::update_create_webhook_db_builder),
+        from_fn(With::<WebhookDBBuilder>::build::<WebhookDB, _>),
+        // insert DAO in DB
+        from_fn(insert::<WebhookDB>),
+        from_fn(With::<WebhookDB>::extract::<WebhookId>),
+        // create DTO response
+        from_fn(With::<WebhookDB>::convert_to::<WebhookBuilder, _>),
+        from_fn(With::<WebhookBuilder>::build::<Webhook, _>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function_run::WebhookError;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::{TdError, assert_service_error};
+    use td_objects::dxo::function::FunctionRegister;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_function::seed_function;
+    use td_objects::tower_service::authz::AuthzError;
+    use td_objects::types::basic::{
+        AccessTokenId, BundleId, CollectionName, Decorator, FunctionName, FunctionRuntimeValues,
+        RoleId, UserId, WebhookSecret, WebhookUrl,
+    };
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_create_webhook_service(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        CreateWebhookService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<CreateRequest<CollectionParam, WebhookCreate>, Webhook>(&[
+                type_of_val(&With::<CreateRequest<CollectionParam, WebhookCreate>>::extract::<RequestContext>),
+                type_of_val(&With::<CreateRequest<CollectionParam, WebhookCreate>>::extract_name::<CollectionParam>),
+                type_of_val(&With::<CreateRequest<CollectionParam, WebhookCreate>>::extract_data::<WebhookCreate>),
+                // find collection ID
+                type_of_val(&With::<CollectionParam>::extract::<CollectionIdName>),
+                type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                // check requester is coll_admin for the collection
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<CollAdmin>::check),
+                // create webhook DAO
+                type_of_val(&builder::<WebhookDBBuilder>),
+                type_of_val(&With::<RequestContext>::update::<WebhookDBBuilder, _>),
+                type_of_val(&With::<CollectionId>::set::<WebhookDBBuilder>),
+                type_of_val(&With::<WebhookCreate>::update_create_webhook_db_builder),
+                type_of_val(&With::<WebhookDBBuilder>::build::<WebhookDB, _>),
+                // insert DAO in DB
+                type_of_val(&insert::<WebhookDB>),
+                type_of_val(&With::<WebhookDB>::extract::<WebhookId>),
+                // create DTO response
+                type_of_val(&With::<WebhookDB>::convert_to::<WebhookBuilder, _>),
+                type_of_val(&With::<WebhookBuilder>::build::<Webhook, _>),
+            ]);
+    }
+
+    fn create_request(
+        role: RoleId,
+        collection: &str,
+        function: Option<&str>,
+    ) -> Result<CreateRequest<CollectionParam, WebhookCreate>, TdError> {
+        let create = WebhookCreate::builder()
+            .url(WebhookUrl::try_from("https://example.com/hooks/runs")?)
+            .secret(WebhookSecret::try_from("0123456789abcdef")?)
+            .function(function.map(FunctionName::try_from).transpose()?)
+            .build()?;
+        let request = RequestContext::with(AccessTokenId::default(), UserId::admin(), role).create(
+            CollectionParam::builder()
+                .collection(CollectionIdName::try_from(collection)?)
+                .build()?,
+            create,
+        );
+        Ok(request)
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_create_webhook_ok(db: DbPool) -> Result<(), TdError> {
+        let collection =
+            seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+        let register = FunctionRegister::builder()
+            .try_name("f0")?
+            .try_description("description")?
+            .bundle_id(BundleId::default())
+            .try_snippet("snippet")?
+            .decorator(Decorator::Publisher)
+            .dependencies(None)
+            .triggers(None)
+            .tables(None)
+            .runtime_values(FunctionRuntimeValues::default())
+            .reuse_frozen_tables(false)
+            .build()?;
+        let function = seed_function(&db, &collection, &register).await;
+
+        let response = CreateWebhookService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(create_request(RoleId::sys_admin(), "c0", None)?)
+            .await?;
+        assert_eq!(response.collection_id, collection.id);
+        assert_eq!(response.function_id, None);
+        assert_eq!(response.url.as_str(), "https://example.com/hooks/runs");
+        assert_eq!(response.created_by_id, UserId::admin());
+
+        let response = CreateWebhookService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(create_request(RoleId::sys_admin(), "c0", Some("f0"))?)
+            .await?;
+        assert_eq!(response.function_id, Some(function.function_id));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_create_webhook_function_not_found(db: DbPool) -> Result<(), TdError> {
+        seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+
+        let service = CreateWebhookService::with_defaults(db.clone())
+            .service()
+            .await;
+        let request = create_request(RoleId::sys_admin(), "c0", Some("f0"))?;
+        assert_service_error(service, request, |err| match err {
+            WebhookError::FunctionNotFound(_) => {}
+            other => panic!("Expected 'FunctionNotFound', got {other:?}"),
+        })
+        .await;
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_create_webhook_authz_err(db: DbPool) -> Result<(), TdError> {
+        seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+
+        let service = CreateWebhookService::with_defaults(db.clone())
+            .service()
+            .await;
+        let request = create_request(RoleId::user(), "c0", None)?;
+        assert_service_error(service, request, |err| match err {
+            AuthzError::Forbidden(_) => {}
+            other => panic!("Expected 'Forbidden', got {other:?}"),
+        })
+        .await;
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{DeleteRequest, RequestContext};
+use td_objects::dxo::webhook::WebhookDB;
+use td_objects::rest_urls::WebhookParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin};
+use td_objects::tower_service::from::{ExtractNameService, ExtractService, With, combine};
+use td_objects::tower_service::sql::{By, SqlDeleteService, SqlSelectService};
+use td_objects::types::basic::{CollectionId, CollectionIdName, WebhookId};
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = DeleteWebhookService,
+    request = DeleteRequest<WebhookParam>,
+    response = (),
+    connection = TransactionProvider,
+    audit = WebhookId,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<DeleteRequest<WebhookParam>>::extract::<RequestContext>),
+        from_fn(With::<DeleteRequest<WebhookParam>>::extract_name::<WebhookParam>),
+        // find collection ID
+        from_fn(With::<WebhookParam>::extract::<CollectionIdName>),
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        // check requester is coll_admin for the collection
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin>::check),
+        // find webhook in the collection
+        from_fn(With::<WebhookParam>::extract::<WebhookId>),
+        from_fn(combine::<CollectionId, WebhookId>),
+        from_fn(By::<(CollectionId, WebhookId)>::select::<WebhookDB>),
+        // delete webhook, its pending deliveries are deleted with it
+        from_fn(By::<WebhookId>::delete::<WebhookDB>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function_run::services::create_webhook::CreateWebhookService;
+    use crate::function_run::services::list_webhooks::ListWebhooksService;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::crudl::ListParams;
+    use td_objects::dxo::webhook::WebhookCreate;
+    use td_objects::rest_urls::CollectionParam;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::types::basic::{
+        AccessTokenId, CollectionName, RoleId, UserId, WebhookSecret, WebhookUrl,
+    };
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_delete_webhook_service(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        DeleteWebhookService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<DeleteRequest<WebhookParam>, ()>(&[
+                type_of_val(&With::<DeleteRequest<WebhookParam>>::extract::<RequestContext>),
+                type_of_val(&With::<DeleteRequest<WebhookParam>>::extract_name::<WebhookParam>),
+                // find collection ID
+                type_of_val(&With::<WebhookParam>::extract::<CollectionIdName>),
+                type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                // check requester is coll_admin for the collection
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<CollAdmin>::check),
+                // find webhook in the collection
+                type_of_val(&With::<WebhookParam>::extract::<WebhookId>),
+                type_of_val(&combine::<CollectionId, WebhookId>),
+                type_of_val(&By::<(CollectionId, WebhookId)>::select::<WebhookDB>),
+                // delete webhook, its pending deliveries are deleted with it
+                type_of_val(&By::<WebhookId>::delete::<WebhookDB>),
+            ]);
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_delete_webhook(db: DbPool) -> Result<(), TdError> {
+        seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+        let context = RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        );
+        let collection = CollectionParam::builder()
+            .collection(CollectionIdName::try_from("c0")?)
+            .build()?;
+
+        let request = context.clone().create(
+            collection.clone(),
+            WebhookCreate::builder()
+                .url(WebhookUrl::try_from("https://example.com/hooks/runs")?)
+                .secret(WebhookSecret::try_from("0123456789abcdef")?)
+                .function(None)
+                .build()?,
+        );
+        let webhook = CreateWebhookService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let request = context.clone().delete(
+            WebhookParam::builder()
+                .collection(CollectionIdName::try_from("c0")?)
+                .webhook(webhook.id)
+                .build()?,
+        );
+        DeleteWebhookService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let request = context.list(collection, ListParams::default());
+        let response = ListWebhooksService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert!(response.data.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{ListRequest, ListResponse, RequestContext};
+use td_objects::dxo::webhook::Webhook;
+use td_objects::rest_urls::CollectionParam;
+use td_objects::sql::{DaoQueries, NoListFilter};
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin};
+use td_objects::tower_service::from::{ExtractNameService, ExtractService, With};
+use td_objects::tower_service::sql::{By, SqlListService, SqlSelectService};
+use td_objects::types::basic::{CollectionId, CollectionIdName};
+use td_tower::default_services::ConnectionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = ListWebhooksService,
+    request = ListRequest<CollectionParam>,
+    response = ListResponse<Webhook>,
+    connection = ConnectionProvider,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<ListRequest<CollectionParam>>::extract::<RequestContext>),
+        from_fn(With::<ListRequest<CollectionParam>>::extract_name::<CollectionParam>),
+        // find collection ID
+        from_fn(With::<CollectionParam>::extract::<CollectionIdName>),
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        // check requester is coll_admin for the collection
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<CollAdmin>::check),
+        // get list of webhooks
+        from_fn(By::<CollectionId>::list::<CollectionParam, NoListFilter, Webhook>),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function_run::services::create_webhook::CreateWebhookService;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::crudl::ListParams;
+    use td_objects::dxo::webhook::WebhookCreate;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::types::basic::{
+        AccessTokenId, CollectionName, RoleId, UserId, WebhookSecret, WebhookUrl,
+    };
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_list_webhooks_service(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        ListWebhooksService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<ListRequest<CollectionParam>, ListResponse<Webhook>>(&[
+                type_of_val(&With::<ListRequest<CollectionParam>>::extract::<RequestContext>),
+                type_of_val(&With::<ListRequest<CollectionParam>>::extract_name::<CollectionParam>),
+                // find collection ID
+                type_of_val(&With::<CollectionParam>::extract::<CollectionIdName>),
+                type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                // check requester is coll_admin for the collection
+                type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<CollAdmin>::check),
+                // get list of webhooks
+                type_of_val(&By::<CollectionId>::list::<CollectionParam, NoListFilter, Webhook>),
+            ]);
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_list_webhooks(db: DbPool) -> Result<(), TdError> {
+        seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+        seed_collection(&db, &CollectionName::try_from("c1")?, &UserId::admin()).await;
+
+        for collection in ["c0", "c0", "c1"] {
+            let request = RequestContext::with(
+                AccessTokenId::default(),
+                UserId::admin(),
+                RoleId::sys_admin(),
+            )
+            .create(
+                CollectionParam::builder()
+                    .collection(CollectionIdName::try_from(collection)?)
+                    .build()?,
+                WebhookCreate::builder()
+                    .url(WebhookUrl::try_from("https://example.com/hooks/runs")?)
+                    .secret(WebhookSecret::try_from("0123456789abcdef")?)
+                    .function(None)
+                    .build()?,
+            );
+            CreateWebhookService::with_defaults(db.clone())
+                .service()
+                .await
+                .raw_oneshot(request)
+                .await?;
+        }
+
+        let request = RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )
+        .list(
+            CollectionParam::builder()
+                .collection(CollectionIdName::try_from("c0")?)
+                .build()?,
+            ListParams::default(),
+        );
+        let response = ListWebhooksService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        assert_eq!(response.data.len(), 2);
+        Ok(())
+    }
+}
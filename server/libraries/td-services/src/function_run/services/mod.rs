@@ -2,15 +2,24 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::function_run::services::create_webhook::CreateWebhookService;
+use crate::function_run::services::delete_webhook::DeleteWebhookService;
 use crate::function_run::services::list::FunctionRunListService;
+use crate::function_run::services::list_webhooks::ListWebhooksService;
 use crate::function_run::services::read::FunctionRunReadService;
 use ta_services::factory::ServiceFactory;
 
+mod create_webhook;
+mod delete_webhook;
 mod list;
+mod list_webhooks;
 mod read;
 
 #[derive(ServiceFactory)]
 pub struct FunctionRunServices {
     pub list: FunctionRunListService,
     pub read: FunctionRunReadService,
+    pub create_webhook: CreateWebhookService,
+    pub list_webhooks: ListWebhooksService,
+    pub delete_webhook: DeleteWebhookService,
 }
@@ -0,0 +1,289 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Delivery of function run notifications to webhooks.
+//!
+//! Notifications are enqueued in the same transaction that completes or fails a function run,
+//! and delivered by the scheduler once committed. Each delivery is a JSON `POST` of the
+//! function run, signed with the webhook secret.
+//!
+//! Webhook URLs are only checked to be absolute http or https URLs when created. The addresses
+//! they resolve to are checked on every delivery, refusing private, loopback and link-local
+//! ones (unless allowed), so webhooks cannot reach internal services.
+
+use crate::function_run::WebhookError;
+use getset::Getters;
+use http::header::CONTENT_TYPE;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect::Policy;
+use ring::hmac;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use td_error::TdError;
+use td_objects::types::basic::{WebhookDeliveryId, WebhookPayload, WebhookSecret, WebhookUrl};
+use url::{Host, Url};
+
+/// Header with the signature of the payload, `sha256=<hex HMAC-SHA256 of the body>` keyed
+/// with the webhook secret.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Tabsdata-Signature";
+
+/// Header with the id of the delivery, the same on every attempt to deliver it.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-Tabsdata-Delivery";
+
+/// Signature of a webhook payload, sent in the [`WEBHOOK_SIGNATURE_HEADER`] header.
+pub fn webhook_signature(secret: &WebhookSecret, payload: &WebhookPayload) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let signature = hmac::sign(&key, payload.as_bytes());
+    format!("sha256={}", hex::encode(signature.as_ref()))
+}
+
+/// Returns if webhooks can be delivered to the address, public unicast addresses only.
+fn is_public_address(ip: &IpAddr) -> bool {
+    fn is_public_ipv4(ip: &Ipv4Addr) -> bool {
+        let [a, b, ..] = ip.octets();
+        !(ip.is_private()
+            || ip.is_loopback()
+            || ip.is_link_local()
+            || ip.is_unspecified()
+            || ip.is_broadcast()
+            || ip.is_multicast()
+            || ip.is_documentation()
+            // 0.0.0.0/8 (this network) and 100.64.0.0/10 (shared address space)
+            || a == 0
+            || (a == 100 && (b & 0xc0) == 64))
+    }
+
+    fn is_public_ipv6(ip: &Ipv6Addr) -> bool {
+        if let Some(ip) = ip.to_ipv4_mapped() {
+            return is_public_ipv4(&ip);
+        }
+        let first = ip.segments()[0];
+        !(ip.is_loopback()
+            || ip.is_unspecified()
+            || ip.is_multicast()
+            // fc00::/7 (unique local) and fe80::/10 (link-local)
+            || (first & 0xfe00) == 0xfc00
+            || (first & 0xffc0) == 0xfe80)
+    }
+
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => is_public_ipv6(ip),
+    }
+}
+
+/// Resolver refusing host names that resolve to non public addresses. The connection is made to
+/// the addresses checked, so the host cannot resolve to a different one afterwards.
+#[derive(Debug)]
+struct PublicAddressResolver;
+
+impl Resolve for PublicAddressResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_address(&addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(
+                    format!("Webhook host '{host}' does not resolve to a public address").into(),
+                );
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct WebhookClient {
+    client: reqwest::Client,
+    /// Attempts to deliver a notification before it is dead lettered.
+    max_attempts: i16,
+    /// Delay before the first retry, doubled on every following one.
+    retry_backoff: Duration,
+    /// If webhooks can be delivered to private, loopback and link-local addresses.
+    allow_private_addresses: bool,
+    /// Deliveries posted at the same time.
+    max_concurrent_deliveries: usize,
+}
+
+impl WebhookClient {
+    const TIMEOUT: Duration = Duration::from_secs(10);
+    const MAX_ATTEMPTS: i16 = 5;
+    const RETRY_BACKOFF: Duration = Duration::from_secs(30);
+    const MAX_CONCURRENT_DELIVERIES: usize = 8;
+
+    pub fn new(
+        timeout: Duration,
+        max_attempts: i16,
+        retry_backoff: Duration,
+        allow_private_addresses: bool,
+        max_concurrent_deliveries: usize,
+    ) -> Result<Self, TdError> {
+        // Redirects are not followed, they could lead to addresses not checked. Proxies are not
+        // used either, they would connect to the webhook without the addresses being checked.
+        let builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .redirect(Policy::none())
+            .no_proxy();
+        let builder = if allow_private_addresses {
+            builder
+        } else {
+            builder.dns_resolver(Arc::new(PublicAddressResolver))
+        };
+        // Falling back to a default client would skip the address checks.
+        let client = builder.build().map_err(WebhookError::Client)?;
+        Ok(Self {
+            client,
+            max_attempts,
+            retry_backoff,
+            allow_private_addresses,
+            max_concurrent_deliveries: max_concurrent_deliveries.max(1),
+        })
+    }
+
+    /// Client with the default timeout, attempts and backoff, delivering to public addresses
+    /// only.
+    pub fn try_default() -> Result<Self, TdError> {
+        Self::new(
+            Self::TIMEOUT,
+            Self::MAX_ATTEMPTS,
+            Self::RETRY_BACKOFF,
+            false,
+            Self::MAX_CONCURRENT_DELIVERIES,
+        )
+    }
+
+    /// Fails if the webhook URL host is an address webhooks cannot be delivered to. Host names
+    /// are checked when resolved, see [`PublicAddressResolver`].
+    fn check_url(&self, url: &WebhookUrl) -> Result<(), String> {
+        if self.allow_private_addresses {
+            return Ok(());
+        }
+        let url = Url::parse(url.as_str()).map_err(|e| e.to_string())?;
+        let ip = match url.host() {
+            Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+            Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+            _ => return Ok(()),
+        };
+        if is_public_address(&ip) {
+            Ok(())
+        } else {
+            Err(format!("Webhook address '{ip}' is not a public address"))
+        }
+    }
+
+    /// Delay before retrying a delivery that failed the given attempts.
+    pub fn retry_delay(&self, attempts: i16) -> Duration {
+        let exponent = (attempts - 1).clamp(0, 16) as u32;
+        self.retry_backoff * 2u32.pow(exponent)
+    }
+
+    /// Posts a payload to a webhook, failing if it does not respond with a success status.
+    pub async fn post(
+        &self,
+        url: &WebhookUrl,
+        delivery_id: &WebhookDeliveryId,
+        secret: &WebhookSecret,
+        payload: &WebhookPayload,
+    ) -> Result<(), String> {
+        self.check_url(url)?;
+        let response = self
+            .client
+            .post(url.as_str())
+            .header(CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_DELIVERY_HEADER, delivery_id.to_string())
+            .header(WEBHOOK_SIGNATURE_HEADER, webhook_signature(secret, payload))
+            .body(payload.to_string())
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Webhook responded with status {}",
+                response.status()
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_signature() -> Result<(), TdError> {
+        let secret = WebhookSecret::try_from("0123456789abcdef")?;
+        let payload = WebhookPayload::try_from(r#"{"id":"run"}"#)?;
+        assert_eq!(
+            webhook_signature(&secret, &payload),
+            "sha256=3322c435623de0797b338fff247849eda9f96493b84c17910facb63e630861ad"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_retry_delay() -> Result<(), TdError> {
+        let client =
+            WebhookClient::new(Duration::from_secs(1), 5, Duration::from_secs(30), false, 1)?;
+        assert_eq!(client.retry_delay(1), Duration::from_secs(30));
+        assert_eq!(client.retry_delay(2), Duration::from_secs(60));
+        assert_eq!(client.retry_delay(4), Duration::from_secs(240));
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_public_address() {
+        let public = ["93.184.216.34", "8.8.8.8", "2606:4700::1111"];
+        for ip in public {
+            assert!(is_public_address(&ip.parse().unwrap()), "{ip}");
+        }
+        let internal = [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ];
+        for ip in internal {
+            assert!(!is_public_address(&ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_refuses_internal_addresses() -> Result<(), TdError> {
+        let client = WebhookClient::try_default()?;
+        let delivery_id = WebhookDeliveryId::default();
+        let secret = WebhookSecret::try_from("0123456789abcdef")?;
+        let payload = WebhookPayload::try_from("{}")?;
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/hook",
+            "http://localhost/hook",
+        ] {
+            let url = WebhookUrl::try_from(url)?;
+            let err = client
+                .post(&url, &delivery_id, &secret, &payload)
+                .await
+                .unwrap_err();
+            assert!(err.contains("public address"), "{url}: {err}");
+        }
+        Ok(())
+    }
+}
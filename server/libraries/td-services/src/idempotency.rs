@@ -0,0 +1,295 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Storage of the responses of create requests with an idempotency key, so retries of the same
+//! request return the stored response instead of creating the resource again.
+
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+use td_common::time::UniqueUtc;
+use td_database::sql::DbPool;
+use td_error::{TdError, td_error};
+use td_objects::dxo::crudl::{
+    handle_create_error, handle_delete_error, handle_select_error, handle_update_error,
+};
+use td_objects::dxo::idempotency::{IdempotencyKeyDB, IdempotencyKeyResponseDB};
+use td_objects::sql::{DaoQueries, DeleteBy, Insert, SelectBy, UpdateBy};
+use td_objects::types::basic::{AtTime, IdempotencyKey, RequestHash, UserId};
+use td_tower::error::ConnectionError;
+use tracing::debug;
+
+/// Header with the idempotency key of a request.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Time stored responses are replayed for.
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Time a key is reserved for while its request is in progress, so keys of requests that never
+/// complete (i.e. cancelled) can be reserved again.
+pub const IDEMPOTENCY_KEY_LEASE: Duration = Duration::from_secs(5 * 60);
+
+#[td_error]
+pub enum IdempotencyError {
+    #[error("Invalid idempotency key: {0}")]
+    InvalidKey(String) = 0,
+    #[error("Could not read the request body: {0}")]
+    InvalidRequestBody(String) = 1,
+    #[error("Idempotency key '{0}' was already used with a different request")]
+    KeyReused(IdempotencyKey) = 2000,
+    #[error("A request with idempotency key '{0}' is still in progress")]
+    KeyInProgress(IdempotencyKey) = 2001,
+    #[error("Idempotency key '{0}' is not reserved, its response could not be stored")]
+    KeyNotReserved(IdempotencyKey) = 5000,
+}
+
+/// Hash identifying a request, its method, path and body.
+pub fn request_hash(method: &str, path: &str, body: &[u8]) -> Result<RequestHash, TdError> {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    RequestHash::try_from(hex::encode(&hasher.finalize()[..]))
+}
+
+/// Reserves the idempotency key of the user, purging the expired ones first. The key is inserted,
+/// without response, in its own write transaction before running the request, so concurrent
+/// requests with the same key cannot both run it.
+///
+/// Returns `None` if the key was reserved, or the existing key if it was already reserved, with
+/// its stored response or without it if its request is still in progress.
+pub async fn reserve_idempotency_key(
+    queries: &DaoQueries,
+    db: &DbPool,
+    idempotency_key: &IdempotencyKeyDB,
+) -> Result<Option<IdempotencyKeyDB>, TdError> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(ConnectionError::CannotBeginTransaction)?;
+
+    let now = AtTime::now();
+    let mut query = queries.delete_by::<IdempotencyKeyDB>(&())?;
+    query.push(" WHERE expires_on <= ");
+    query.push_bind(&now);
+    let purged = query
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(handle_delete_error)?
+        .rows_affected();
+    debug!("Purged {purged} expired idempotency keys");
+
+    let mut query = queries.insert(idempotency_key)?;
+    query.push(" ON CONFLICT (idempotency_key, user_id) DO NOTHING");
+    let reserved = query
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(handle_create_error)?
+        .rows_affected()
+        == 1;
+
+    let existing = if reserved {
+        None
+    } else {
+        let existing: IdempotencyKeyDB = queries
+            .select_by::<IdempotencyKeyDB>(&(
+                &idempotency_key.idempotency_key,
+                &idempotency_key.user_id,
+            ))?
+            .build_query_as()
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(handle_select_error)?;
+        Some(existing)
+    };
+
+    tx.commit()
+        .await
+        .map_err(ConnectionError::CannotCommitTransaction)?;
+    Ok(existing)
+}
+
+/// Stores the response of the request holding a reserved idempotency key, extending its
+/// expiration to replay it.
+pub async fn store_idempotency_response(
+    queries: &DaoQueries,
+    db: &DbPool,
+    idempotency_key: &IdempotencyKey,
+    user_id: &UserId,
+    response: &IdempotencyKeyResponseDB,
+) -> Result<(), TdError> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(ConnectionError::CannotBeginTransaction)?;
+    let updated = queries
+        .update_by::<_, IdempotencyKeyDB>(response, &(idempotency_key, user_id))?
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(handle_update_error)?
+        .rows_affected();
+    if updated != 1 {
+        Err(IdempotencyError::KeyNotReserved(idempotency_key.clone()))?;
+    }
+    tx.commit()
+        .await
+        .map_err(ConnectionError::CannotCommitTransaction)?;
+    Ok(())
+}
+
+/// Releases a reserved idempotency key, for requests whose response is not stored.
+pub async fn release_idempotency_key(
+    queries: &DaoQueries,
+    db: &DbPool,
+    idempotency_key: &IdempotencyKey,
+    user_id: &UserId,
+) -> Result<(), TdError> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(ConnectionError::CannotBeginTransaction)?;
+    queries
+        .delete_by::<IdempotencyKeyDB>(&(idempotency_key, user_id))?
+        .build()
+        .execute(&mut *tx)
+        .await
+        .map_err(handle_delete_error)?;
+    tx.commit()
+        .await
+        .map_err(ConnectionError::CannotCommitTransaction)?;
+    Ok(())
+}
+
+/// Expiration of an idempotency key stored now for the given time.
+pub fn idempotency_key_expiration(ttl: Duration) -> Result<AtTime, TdError> {
+    (UniqueUtc::now_millis() + ttl).try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use td_objects::types::basic::{ResponseBody, ResponseStatus};
+
+    fn reserved(key: &str, expires_on: AtTime) -> Result<IdempotencyKeyDB, TdError> {
+        IdempotencyKeyDB::builder()
+            .idempotency_key(IdempotencyKey::try_from(key)?)
+            .user_id(UserId::admin())
+            .request_hash(request_hash("POST", "/collections", b"{}")?)
+            .response_status(None)
+            .response_body(None)
+            .created_on(AtTime::now())
+            .expires_on(expires_on)
+            .build()
+    }
+
+    fn response() -> Result<IdempotencyKeyResponseDB, TdError> {
+        IdempotencyKeyResponseDB::builder()
+            .response_status(Some(ResponseStatus::try_from(201)?))
+            .response_body(Some(ResponseBody::try_from("{}")?))
+            .expires_on(idempotency_key_expiration(IDEMPOTENCY_KEY_TTL)?)
+            .build()
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_idempotency_key_reservation(db: DbPool) -> Result<(), TdError> {
+        let queries = DaoQueries::default();
+        let key = reserved("key", idempotency_key_expiration(IDEMPOTENCY_KEY_LEASE)?)?;
+
+        // the first request reserves the key, a second one finds it in progress
+        assert!(
+            reserve_idempotency_key(&queries, &db, &key)
+                .await?
+                .is_none()
+        );
+        let found = reserve_idempotency_key(&queries, &db, &key).await?.unwrap();
+        assert_eq!(found.request_hash, key.request_hash);
+        assert!(found.response_status.is_none());
+
+        // and once completed, it finds the stored response
+        let user_id = UserId::admin();
+        store_idempotency_response(&queries, &db, &key.idempotency_key, &user_id, &response()?)
+            .await?;
+        let found = reserve_idempotency_key(&queries, &db, &key).await?.unwrap();
+        assert_eq!(found.response_status, response()?.response_status);
+        assert_eq!(found.response_body, response()?.response_body);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_idempotency_key_concurrent_reservation(db: DbPool) -> Result<(), TdError> {
+        let queries = DaoQueries::default();
+        let key = reserved("key", idempotency_key_expiration(IDEMPOTENCY_KEY_LEASE)?)?;
+
+        let (first, second) = tokio::join!(
+            reserve_idempotency_key(&queries, &db, &key),
+            reserve_idempotency_key(&queries, &db, &key),
+        );
+        // exactly one of them reserves the key
+        assert_ne!(first?.is_none(), second?.is_none());
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_idempotency_key_release(db: DbPool) -> Result<(), TdError> {
+        let queries = DaoQueries::default();
+        let key = reserved("key", idempotency_key_expiration(IDEMPOTENCY_KEY_LEASE)?)?;
+        let user_id = UserId::admin();
+
+        assert!(
+            reserve_idempotency_key(&queries, &db, &key)
+                .await?
+                .is_none()
+        );
+        release_idempotency_key(&queries, &db, &key.idempotency_key, &user_id).await?;
+        assert!(
+            reserve_idempotency_key(&queries, &db, &key)
+                .await?
+                .is_none()
+        );
+
+        // the response of a released key cannot be stored
+        release_idempotency_key(&queries, &db, &key.idempotency_key, &user_id).await?;
+        let res =
+            store_idempotency_response(&queries, &db, &key.idempotency_key, &user_id, &response()?)
+                .await;
+        assert!(res.is_err());
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_idempotency_key_expiration(db: DbPool) -> Result<(), TdError> {
+        let queries = DaoQueries::default();
+
+        // expired keys are purged, so they can be reserved again
+        let expired = reserved("expired", AtTime::now())?;
+        assert!(
+            reserve_idempotency_key(&queries, &db, &expired)
+                .await?
+                .is_none()
+        );
+        assert!(
+            reserve_idempotency_key(&queries, &db, &expired)
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_request_hash() -> Result<(), TdError> {
+        let hash = request_hash("POST", "/collections", b"{}")?;
+        assert_eq!(hash, request_hash("POST", "/collections", b"{}")?);
+        assert_ne!(hash, request_hash("POST", "/collections", b"{ }")?);
+        assert_ne!(hash, request_hash("POST", "/roles", b"{}")?);
+        Ok(())
+    }
+}
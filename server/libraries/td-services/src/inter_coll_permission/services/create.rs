@@ -26,17 +26,23 @@ use td_objects::types::basic::{
 };
 use td_tower::default_services::TransactionProvider;
 use td_tower::from_fn::from_fn;
-use td_tower::layers;
+use td_tower::{layer, layers};
 
 #[service_factory(
     name = CreateInterCollectionPermissionService,
     request = CreateRequest<CollectionParam, InterCollectionPermissionCreate>,
     response = InterCollectionPermission,
     connection = TransactionProvider,
+    audit = InterCollectionPermissionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
 fn service() {
+    layers!(create_inter_collection_permission())
+}
+
+#[layer]
+pub fn create_inter_collection_permission() {
     layers!(
         from_fn(
             With::<CreateRequest<CollectionParam, InterCollectionPermissionCreate>>::extract::<
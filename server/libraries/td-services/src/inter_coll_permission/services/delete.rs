@@ -26,6 +26,7 @@ use td_tower::layers;
     request = DeleteRequest<InterCollectionPermissionParam>,
     response = (),
     connection = TransactionProvider,
+    audit = InterCollectionPermissionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
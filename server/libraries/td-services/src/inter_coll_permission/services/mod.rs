@@ -5,15 +5,18 @@
 use crate::inter_coll_permission::services::create::CreateInterCollectionPermissionService;
 use crate::inter_coll_permission::services::delete::DeleteInterCollectionPermissionService;
 use crate::inter_coll_permission::services::list::ListInterCollectionPermissionService;
+use crate::inter_coll_permission::services::revoke::RevokeInterCollectionPermissionService;
 use ta_services::factory::ServiceFactory;
 
 pub mod create;
 pub mod delete;
 pub mod list;
+pub mod revoke;
 
 #[derive(ServiceFactory)]
 pub struct InterCollectionPermissionServices {
     pub create: CreateInterCollectionPermissionService,
     pub delete: DeleteInterCollectionPermissionService,
     pub list: ListInterCollectionPermissionService,
+    pub revoke: RevokeInterCollectionPermissionService,
 }
@@ -0,0 +1,251 @@
+//
+// Copyright 2025. Tabs Data Inc.
+//
+
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext, refresh_authz_context};
+use td_objects::dxo::collection::CollectionDB;
+use td_objects::dxo::crudl::{DeleteRequest, RequestContext};
+use td_objects::dxo::inter_collection_permission::InterCollectionPermissionDB;
+use td_objects::rest_urls::InterCollectionPermissionToParam;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, CollAdmin, SecAdmin, System};
+use td_objects::tower_service::from::{ExtractNameService, ExtractService, TryIntoService, With};
+use td_objects::tower_service::sql::{By, SqlDeleteService, SqlSelectService};
+use td_objects::types::basic::{
+    CollectionId, CollectionIdName, CollectionName, FromCollectionId, InterCollectionPermissionId,
+    ToCollectionId, ToCollectionName,
+};
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = RevokeInterCollectionPermissionService,
+    request = DeleteRequest<InterCollectionPermissionToParam>,
+    response = (),
+    connection = TransactionProvider,
+    audit = InterCollectionPermissionId,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<DeleteRequest<InterCollectionPermissionToParam>>::extract::<RequestContext>),
+        from_fn(
+            With::<DeleteRequest<InterCollectionPermissionToParam>>::extract_name::<
+                InterCollectionPermissionToParam,
+            >
+        ),
+        // check requester is sec_admin or coll_admin, early pre-check
+        from_fn(AuthzOn::<System>::set),
+        from_fn(Authz::<SecAdmin, CollAdmin>::check),
+        // find collection ID for the FROM collection
+        from_fn(With::<InterCollectionPermissionToParam>::extract::<CollectionIdName>),
+        from_fn(By::<CollectionIdName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        from_fn(With::<CollectionId>::convert_to::<FromCollectionId, _>),
+        // check request is sec_admin or coll_admin for the FROM collection
+        from_fn(AuthzOn::<CollectionId>::set),
+        from_fn(Authz::<SecAdmin, CollAdmin>::check),
+        // find collection ID for the TO collection
+        from_fn(With::<InterCollectionPermissionToParam>::extract::<ToCollectionName>),
+        from_fn(With::<ToCollectionName>::convert_to::<CollectionName, _>),
+        from_fn(By::<CollectionName>::select::<CollectionDB>),
+        from_fn(With::<CollectionDB>::extract::<CollectionId>),
+        from_fn(With::<CollectionId>::convert_to::<ToCollectionId, _>),
+        // restore CollectionId from request name as we dropped it get the ToCollectionId
+        from_fn(With::<FromCollectionId>::convert_to::<CollectionId, _>),
+        // find permission for the pair, it fails if there is none
+        from_fn(By::<(CollectionId, ToCollectionId)>::select::<InterCollectionPermissionDB>),
+        // delete permission from DB
+        from_fn(With::<InterCollectionPermissionDB>::extract::<InterCollectionPermissionId>),
+        from_fn(By::<InterCollectionPermissionId>::delete::<InterCollectionPermissionDB>),
+        // refresh the inter collections authz cache
+        from_fn(refresh_authz_context),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Context;
+    use crate::inter_coll_permission::services::create::CreateInterCollectionPermissionService;
+    use ta_services::factory::ServiceFactory;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::{TdError, assert_service_error};
+    use td_objects::dxo::inter_collection_permission::InterCollectionPermissionCreate;
+    use td_objects::rest_urls::CollectionParam;
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::test_utils::seed_inter_collection_permission::get_inter_collection_permissions;
+    use td_objects::tower_service::authz::{AuthzContextT, AuthzError};
+    use td_objects::tower_service::sql::SqlError;
+    use td_objects::types::basic::{AccessTokenId, RoleId, UserId};
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_revoke_inter_collection_permission_service(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        RevokeInterCollectionPermissionService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<DeleteRequest<InterCollectionPermissionToParam>, ()>(&[
+                type_of_val(
+                    &With::<DeleteRequest<InterCollectionPermissionToParam>>::extract::<
+                        RequestContext,
+                    >,
+                ),
+                type_of_val(
+                    &With::<DeleteRequest<InterCollectionPermissionToParam>>::extract_name::<
+                        InterCollectionPermissionToParam,
+                    >,
+                ),
+                // check requester is sec_admin or coll_admin, early pre-check
+                type_of_val(&AuthzOn::<System>::set),
+                type_of_val(&Authz::<SecAdmin, CollAdmin>::check),
+                // find collection ID for the FROM collection
+                type_of_val(&With::<InterCollectionPermissionToParam>::extract::<CollectionIdName>),
+                type_of_val(&By::<CollectionIdName>::select::<CollectionDB>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                type_of_val(&With::<CollectionId>::convert_to::<FromCollectionId, _>),
+                // check request is sec_admin or coll_admin for the FROM collection
+                type_of_val(&AuthzOn::<CollectionId>::set),
+                type_of_val(&Authz::<SecAdmin, CollAdmin>::check),
+                // find collection ID for the TO collection
+                type_of_val(&With::<InterCollectionPermissionToParam>::extract::<ToCollectionName>),
+                type_of_val(&With::<ToCollectionName>::convert_to::<CollectionName, _>),
+                type_of_val(&By::<CollectionName>::select::<CollectionDB>),
+                type_of_val(&With::<CollectionDB>::extract::<CollectionId>),
+                type_of_val(&With::<CollectionId>::convert_to::<ToCollectionId, _>),
+                // restore CollectionId from request name as we dropped it get the ToCollectionId
+                type_of_val(&With::<FromCollectionId>::convert_to::<CollectionId, _>),
+                // find permission for the pair, it fails if there is none
+                type_of_val(
+                    &By::<(CollectionId, ToCollectionId)>::select::<InterCollectionPermissionDB>,
+                ),
+                // delete permission from DB
+                type_of_val(
+                    &With::<InterCollectionPermissionDB>::extract::<InterCollectionPermissionId>,
+                ),
+                type_of_val(
+                    &By::<InterCollectionPermissionId>::delete::<InterCollectionPermissionDB>,
+                ),
+                // refresh the inter collections authz cache
+                type_of_val(&refresh_authz_context),
+            ]);
+    }
+
+    fn revoke_request(
+        to_collection: &str,
+    ) -> Result<DeleteRequest<InterCollectionPermissionToParam>, TdError> {
+        Ok(RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sec_admin(),
+        )
+        .delete(
+            InterCollectionPermissionToParam::builder()
+                .collection(CollectionIdName::try_from("c0")?)
+                .to_collection(ToCollectionName::try_from(to_collection)?)
+                .build()?,
+        ))
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_revoke_permission_ok(db: DbPool) -> Result<(), TdError> {
+        let context = Context::with_defaults(db.clone());
+
+        let c0 = seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+        let c1 = seed_collection(&db, &CollectionName::try_from("c1")?, &UserId::admin()).await;
+        let c1_id = ToCollectionId::try_from(c1.id)?;
+
+        let request = RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sec_admin(),
+        )
+        .create(
+            CollectionParam::builder()
+                .collection(CollectionIdName::try_from("c0")?)
+                .build()?,
+            InterCollectionPermissionCreate::builder()
+                .try_to_collection("c1")?
+                .build()?,
+        );
+        CreateInterCollectionPermissionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+
+        let mut conn = db.acquire().await.unwrap();
+        let can_read = context
+            .auth_context
+            .inter_collections_permissions_value_can_read_key(&mut conn, &c0.id)
+            .await?;
+        assert_eq!(can_read.as_deref(), Some(&vec![c1_id]));
+
+        RevokeInterCollectionPermissionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(revoke_request("c1")?)
+            .await?;
+
+        let permissions = get_inter_collection_permissions(&db, &c0.id).await?;
+        assert!(permissions.is_empty());
+        let can_read = context
+            .auth_context
+            .inter_collections_permissions_value_can_read_key(&mut conn, &c0.id)
+            .await?;
+        assert!(!can_read.is_some_and(|can_read| can_read.contains(&c1_id)));
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_revoke_permission_not_found_err(db: DbPool) -> Result<(), TdError> {
+        let service = RevokeInterCollectionPermissionService::with_defaults(db.clone())
+            .service()
+            .await;
+
+        seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+        seed_collection(&db, &CollectionName::try_from("c1")?, &UserId::admin()).await;
+
+        assert_service_error(service, revoke_request("c1")?, |err| match err {
+            SqlError::CouldNotFindEntity(_, _, _) => {}
+            other => panic!("Expected 'CouldNotFindEntity', got {other:?}"),
+        })
+        .await;
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_revoke_permission_unauthz_err(db: DbPool) -> Result<(), TdError> {
+        let service = RevokeInterCollectionPermissionService::with_defaults(db.clone())
+            .service()
+            .await;
+
+        seed_collection(&db, &CollectionName::try_from("c0")?, &UserId::admin()).await;
+
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).delete(
+                InterCollectionPermissionToParam::builder()
+                    .collection(CollectionIdName::try_from("c0")?)
+                    .to_collection(ToCollectionName::try_from("c1")?)
+                    .build()?,
+            );
+
+        assert_service_error(service, request, |err| match err {
+            AuthzError::Forbidden(_) => {}
+            other => panic!("Expected 'Forbidden', got {other:?}"),
+        })
+        .await;
+        Ok(())
+    }
+}
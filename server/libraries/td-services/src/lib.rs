@@ -10,6 +10,7 @@ use crate::execution::services::ExecutionServices;
 use crate::execution::services::runtime_info::RuntimeContext;
 use crate::function::services::FunctionServices;
 use crate::function_run::services::FunctionRunServices;
+use crate::function_run::webhook::WebhookClient;
 use crate::inter_coll_permission::services::InterCollectionPermissionServices;
 use crate::permission::services::PermissionServices;
 use crate::role::services::RoleServices;
@@ -23,14 +24,15 @@ use crate::worker::services::WorkerServices;
 use axum::extract::FromRef;
 use std::path::PathBuf;
 use std::sync::Arc;
-use ta_services::factory::{FieldAccessors, ServiceFactory};
+use ta_services::factory::{FieldAccessor, FieldAccessors, ServiceFactory};
 use td_authz::AuthzContext;
 use td_common::server::FileWorkerMessageQueue;
-use td_database::sql::DbPool;
+use td_database::sql::{DbMode, DbPool};
 use td_objects::sql::DaoQueries;
 use td_objects::types::addresses::{ApiServerAddresses, InternalServerAddresses};
 use td_security::config::PasswordHashingConfig;
 use td_storage::Storage;
+use td_storage::quota::StorageQuota;
 use te_execution::transaction::TransactionBy;
 
 pub mod auth;
@@ -38,6 +40,7 @@ pub mod collection;
 pub mod execution;
 pub mod function;
 pub mod function_run;
+pub mod idempotency;
 pub mod inter_coll_permission;
 pub mod permission;
 pub mod role;
@@ -80,6 +83,14 @@ pub struct Context {
     pub storage: Arc<Storage>,
     pub runtime_context: Arc<RuntimeContext>,
     pub transaction_by: Arc<TransactionBy>,
+    pub storage_quota: Arc<StorageQuota>,
+}
+
+/// The database mode is taken from the context database, it is fixed when the database connects.
+impl FieldAccessor<Context> for Arc<DbMode> {
+    fn get_field(ctx: &Context) -> Self {
+        Arc::new(ctx.db.mode())
+    }
 }
 
 #[cfg(feature = "test-utils")]
@@ -97,6 +108,7 @@ impl Context {
             storage: Arc::new(Storage::default()),
             runtime_context: Arc::new(RuntimeContext::default()),
             transaction_by: Arc::new(TransactionBy::default()),
+            storage_quota: Arc::new(StorageQuota::default()),
         }
     }
 }
@@ -113,6 +125,7 @@ pub struct SchedulerContext {
     pub storage: Arc<Storage>,
     pub worker_queue: Arc<FileWorkerMessageQueue>,
     pub internal_addresses: Arc<InternalServerAddresses>,
+    pub webhook_client: Arc<WebhookClient>,
 }
 
 #[cfg(feature = "test-utils")]
@@ -124,6 +137,7 @@ impl SchedulerContext {
             storage: Arc::new(Storage::default()),
             worker_queue: Arc::new(FileWorkerMessageQueue::default()),
             internal_addresses: Arc::new(InternalServerAddresses::default()),
+            webhook_client: Arc::new(WebhookClient::try_default().unwrap()),
         }
     }
 }
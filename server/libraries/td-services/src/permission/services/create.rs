@@ -29,6 +29,7 @@ use td_tower::{layers, service};
     request = CreateRequest<RoleParam, PermissionCreate>,
     response = Permission,
     connection = TransactionProvider,
+    audit = PermissionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -29,6 +29,7 @@ use td_tower::{layers, service};
     request = DeleteRequest<RolePermissionParam>,
     response = (),
     connection = TransactionProvider,
+    audit = PermissionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -24,6 +24,7 @@ use td_tower::layers;
     request = CreateRequest<(), RoleCreate>,
     response = Role,
     connection = TransactionProvider,
+    audit = RoleId,
     context = DaoQueries,
     context = AuthzContext,
 )]
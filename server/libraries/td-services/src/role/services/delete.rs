@@ -24,6 +24,7 @@ use td_tower::layers;
     request = DeleteRequest<RoleParam>,
     response = (),
     connection = TransactionProvider,
+    audit = RoleId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -142,4 +142,79 @@ mod tests {
         assert_eq!(response.fixed, found.fixed);
         Ok(())
     }
+
+    #[cfg(not(feature = "test_tower_metadata"))]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_read_role_span(db: DbPool) -> Result<(), TdError> {
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+        use td_objects::types::basic::RequestId;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id};
+        use tracing::subscriber::set_default;
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::registry;
+
+        type RecordedSpan = (String, HashMap<String, String>);
+
+        #[derive(Clone, Default)]
+        struct SpanRecorder(Arc<Mutex<Vec<RecordedSpan>>>);
+
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanRecorder {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+                let mut fields = FieldsVisitor::default();
+                attrs.record(&mut fields);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((attrs.metadata().name().to_string(), fields.0));
+            }
+        }
+
+        #[derive(Default)]
+        struct FieldsVisitor(HashMap<String, String>);
+
+        impl Visit for FieldsVisitor {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{value:?}"));
+            }
+        }
+
+        let recorder = SpanRecorder::default();
+        let _guard = set_default(registry().with(recorder.clone()));
+
+        let request = RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sec_admin(),
+        )
+        .with_request_id(RequestId::try_from("read-role-request")?)
+        .read(
+            RoleParam::builder()
+                .role(RoleIdName::try_from(format!("~{}", RoleId::sec_admin()))?)
+                .build()?,
+        );
+        let service = ReadRoleService::with_defaults(db.clone()).service().await;
+        service.raw_oneshot(request).await?;
+
+        // a single span for the whole service, with its name and the id of its request
+        let spans: Vec<_> = recorder
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| name == "service")
+            .map(|(_, fields)| fields.clone())
+            .collect();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0]["service"], "ReadRoleService");
+        assert_eq!(spans[0]["request_id"], "read-role-request");
+        Ok(())
+    }
 }
@@ -26,6 +26,7 @@ use td_tower::layers;
     request = UpdateRequest<RoleParam, RoleUpdate>,
     response = Role,
     connection = TransactionProvider,
+    audit = RoleId,
     context = DaoQueries,
     context = AuthzContext,
 )]
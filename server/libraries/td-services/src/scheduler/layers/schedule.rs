@@ -4,18 +4,22 @@
 
 use http::Method;
 use itertools::{Either, Itertools};
+use serde::de::DeserializeOwned;
 use sqlx::SqliteConnection;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
+use ta_execution::transaction::TransactionMap;
+use td_common::os::ResourceLimits;
 use td_common::server::WorkerName::FUNCTION;
 use td_common::server::{
-    Callback, HttpCallbackBuilder, MessageAction, RequestMessagePayload,
+    Callback, HttpCallbackBuilder, MessageAction, MessagePriority, RequestMessagePayload,
     RequestMessagePayloadBuilder, SupervisorMessage, SupervisorMessagePayload, WorkerClass,
     WorkerMessageQueue,
 };
 use td_error::{TdError, td_error};
 use td_objects::dxo::crudl::handle_sql_err;
+use td_objects::dxo::function::FunctionDB;
 use td_objects::dxo::function_requirement::FunctionRequirementDBWithNames;
 use td_objects::dxo::function_run::{FunctionRunDB, FunctionRunToExecuteDB, UpdateFunctionRunDB};
 use td_objects::dxo::request::v2::{
@@ -23,17 +27,20 @@ use td_objects::dxo::request::v2::{
 };
 use td_objects::dxo::request::{EnvPrefix, FunctionInput, Location};
 use td_objects::dxo::table_data_version::TableDataVersionDBWithNames;
+use td_objects::dxo::transaction::{TransactionDB, TransactionValueBuilder};
 use td_objects::dxo::worker::{UpdateWorkerMessageStatusDB, WorkerDB};
 use td_objects::rest_urls::{BASE_URL, UPDATE_FUNCTION_RUN};
 use td_objects::sql::{DaoQueries, FindBy, SelectBy, UpdateBy};
 use td_objects::types::addresses::InternalServerAddresses;
 use td_objects::types::basic::{
-    FunctionRunId, FunctionRunStatus, WorkerId, WorkerMessageStatus, WorkerStatus,
+    FunctionRunId, FunctionRunStatus, FunctionRuntimeValues, TransactionId, WorkerId,
+    WorkerMessageStatus, WorkerStatus,
 };
 use td_storage::Storage;
 use td_storage::location::StorageLocation;
 use td_tower::extractors::{Connection, Input, IntoMutSqlConnection, SrvCtx};
-use tracing::{error, trace};
+use te_execution::transaction::TransactionBy;
+use tracing::{error, trace, warn};
 use url::Url;
 
 #[td_error]
@@ -44,6 +51,72 @@ pub enum ScheduleError {
     InvalidRequestMessagePayload(String) = 5001,
     #[error("Missing supervisor request message context")]
     MissingRequestContext = 5002,
+    #[error("Missing transaction [{0}] of a function run to execute")]
+    MissingTransaction(TransactionId) = 5003,
+}
+
+/// Filters the function runs ready to execute so runs sharing a transaction key are dispatched
+/// one at a time, preserving the atomicity of the transaction. A run is held back, remaining
+/// scheduled, while a run with its transaction key is in flight (run requested or running), or
+/// when a run with the same key was already picked in this round. Runs with different transaction
+/// keys are dispatched concurrently.
+pub async fn serialize_by_transaction_key(
+    SrvCtx(queries): SrvCtx<DaoQueries>,
+    Connection(connection): Connection,
+    Input(function_runs): Input<Vec<FunctionRunToExecuteDB>>,
+) -> Result<Vec<FunctionRunToExecuteDB>, TdError> {
+    let mut conn = connection.lock().await;
+    let conn = conn.get_mut_connection()?;
+
+    let in_flight_runs: Vec<FunctionRunDB> = queries
+        .find_by::<FunctionRunDB>(&[FunctionRunStatus::RunRequested, FunctionRunStatus::Running])?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?;
+
+    let transaction_ids = in_flight_runs
+        .iter()
+        .map(|fr| fr.transaction_id)
+        .chain(function_runs.iter().map(|fr| fr.transaction_id))
+        .unique()
+        .collect::<Vec<_>>();
+    let transactions: HashMap<TransactionId, TransactionDB> = queries
+        .find_by::<TransactionDB>(&transaction_ids)?
+        .build_query_as()
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(handle_sql_err)?
+        .into_iter()
+        .map(|t: TransactionDB| (t.id, t))
+        .collect();
+    let transaction_value = |transaction_id: &TransactionId| -> Result<_, TdError> {
+        let transaction = transactions
+            .get(transaction_id)
+            .ok_or(ScheduleError::MissingTransaction(*transaction_id))?;
+        Ok(TransactionValueBuilder::try_from(transaction)?.build()?)
+    };
+
+    // Keys are taken from the existing transactions, the mapper is not used to compute them.
+    let mut in_flight = TransactionMap::empty(TransactionBy::default());
+    for fr in in_flight_runs.iter() {
+        in_flight.insert(transaction_value(&fr.transaction_id)?);
+    }
+
+    let mut to_dispatch = Vec::with_capacity(function_runs.len());
+    for fr in function_runs.iter() {
+        let value = transaction_value(&fr.transaction_id)?;
+        let key = value.transaction_key.clone();
+        if in_flight.insert(value) {
+            to_dispatch.push(fr.clone());
+        } else {
+            trace!(
+                "Holding function run {} back, transaction key {} is in flight",
+                fr.id, key
+            );
+        }
+    }
+    Ok(to_dispatch)
 }
 
 pub async fn create_locked_workers<T: WorkerMessageQueue>(
@@ -306,6 +379,18 @@ pub async fn create_locked_workers<T: WorkerMessageQueue>(
                     .build()?;
                 let function_input = FunctionInput::V2(Box::new(function_input_v2));
 
+                // Resolve worker resource limits and priority from the function runtime values
+                let function: FunctionDB = queries
+                    .select_by::<FunctionDB>(&f.function_version_id)?
+                    .build_query_as()
+                    .fetch_one(&mut *conn)
+                    .await
+                    .map_err(handle_sql_err)?;
+                let limits: Option<ResourceLimits> =
+                    runtime_value(&function.runtime_values, "limits");
+                let priority: MessagePriority =
+                    runtime_value(&function.runtime_values, "priority").unwrap_or_default();
+
                 // Build message payload
                 // TODO ADD get_states to states
                 let message_payload: RequestMessagePayload<FunctionInput> =
@@ -316,6 +401,8 @@ pub async fn create_locked_workers<T: WorkerMessageQueue>(
                         .arguments(vec![])
                         .callback(callback)
                         .context(function_input)
+                        .limits(limits)
+                        .priority(priority)
                         .build()
                         .unwrap();
 
@@ -345,6 +432,23 @@ pub async fn create_locked_workers<T: WorkerMessageQueue>(
     Ok(res)
 }
 
+/// Value of the given top entry of the function runtime values, if any.
+/// Invalid values are logged and ignored, they must not block the scheduling of other functions.
+fn runtime_value<T: DeserializeOwned>(
+    runtime_values: &FunctionRuntimeValues,
+    entry: &str,
+) -> Option<T> {
+    let values: serde_json::Value = serde_json::from_str(runtime_values).ok()?;
+    let value = values.get(entry)?;
+    match serde_json::from_value(value.clone()) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("Ignoring invalid function runtime value '{entry}' ({value}): {e}");
+            None
+        }
+    }
+}
+
 // These layer should not fail for single messages errors, only for wider errors (system, connection, etc.).
 // All errors parsing or processing messages should be logged and the message should be removed from the queue.
 pub async fn unlock_workers<T: WorkerMessageQueue>(
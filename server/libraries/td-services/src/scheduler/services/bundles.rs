@@ -0,0 +1,129 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function::layers::bundle::purge_released_bundle_blobs;
+use ta_services::factory::service_factory;
+use td_objects::sql::DaoQueries;
+use td_storage::Storage;
+use td_tower::default_services::ConnectionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = ScheduleBundlesService,
+    request = (),
+    response = (),
+    connection = ConnectionProvider,
+    context = DaoQueries,
+    context = Storage,
+)]
+fn service() {
+    layers!(from_fn(purge_released_bundle_blobs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::services::upload::UploadFunctionService;
+    use crate::{Context, SchedulerContext};
+    use axum::body::Body;
+    use axum::extract::Request;
+    use ta_services::factory::ServiceFactory;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::bundle::{BundleBlobDB, BundleDB};
+    use td_objects::dxo::crudl::{RequestContext, handle_sql_err};
+    use td_objects::dxo::function_upload::FunctionUpload;
+    use td_objects::rest_urls::CollectionParam;
+    use td_objects::sql::{DeleteBy, SelectBy};
+    use td_objects::test_utils::seed_collection::seed_collection;
+    use td_objects::types::basic::{AccessTokenId, CollectionName, DataLocation, RoleId, UserId};
+    use td_storage::location::StorageLocation;
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_schedule_bundles(db: DbPool) -> Result<(), TdError> {
+        use td_tower::metadata::type_of_val;
+
+        ScheduleBundlesService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<(), ()>(&[type_of_val(&purge_released_bundle_blobs)]);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_purge_released_bundle_blobs(db: DbPool) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("purge")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+        let context = Context::with_defaults(db.clone());
+        let mut scheduler_context = SchedulerContext::with_defaults(db.clone());
+        scheduler_context.storage = context.storage.clone();
+
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                CollectionParam::builder()
+                    .try_collection(format!("{}", collection.name))?
+                    .build()?,
+                FunctionUpload::new(
+                    Request::builder()
+                        .body(Body::new("TEXT".to_string()))
+                        .unwrap(),
+                ),
+            );
+        let bundle = UploadFunctionService::build(&context)
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        let (bundle_location, _) = StorageLocation::current()
+            .builder(&DataLocation::default())
+            .collection(&collection.id)
+            .function(&bundle.id)
+            .build();
+
+        let queries = DaoQueries::default();
+
+        // a blob pending to be registered is kept
+        ScheduleBundlesService::build(&scheduler_context)
+            .service()
+            .await
+            .raw_oneshot(())
+            .await?;
+        let blobs: Vec<BundleBlobDB> = queries
+            .select_by::<BundleBlobDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(blobs.len(), 1);
+        assert!(context.storage.exists(&bundle_location).await?);
+
+        // and purged once released
+        queries
+            .delete_by::<BundleDB>(&bundle.id)?
+            .build()
+            .execute(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        ScheduleBundlesService::build(&scheduler_context)
+            .service()
+            .await
+            .raw_oneshot(())
+            .await?;
+        let blobs: Vec<BundleBlobDB> = queries
+            .select_by::<BundleBlobDB>(&())?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert!(blobs.is_empty());
+        assert!(!context.storage.exists(&bundle_location).await?);
+        Ok(())
+    }
+}
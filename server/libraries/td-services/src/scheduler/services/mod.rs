@@ -2,17 +2,23 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::scheduler::services::bundles::ScheduleBundlesService;
 use crate::scheduler::services::commit::ScheduleCommitService;
 use crate::scheduler::services::request::ScheduleRequestService;
+use crate::scheduler::services::webhooks::ScheduleWebhooksService;
 use getset::Getters;
 use ta_services::factory::ServiceFactory;
 
+mod bundles;
 mod commit;
 mod request;
+mod webhooks;
 
 #[derive(ServiceFactory, Getters)]
 #[getset(get = "pub")]
 pub struct ScheduleServices {
     request: ScheduleRequestService,
     commit: ScheduleCommitService,
+    webhooks: ScheduleWebhooksService,
+    bundles: ScheduleBundlesService,
 }
@@ -2,7 +2,7 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use crate::scheduler::layers::schedule::create_locked_workers;
+use crate::scheduler::layers::schedule::{create_locked_workers, serialize_by_transaction_key};
 use ta_services::factory::service_factory;
 use td_common::server::{FileWorkerMessageQueue, WorkerMessageQueue};
 use td_objects::dxo::function_run::{FunctionRunDB, FunctionRunToExecuteDB, UpdateFunctionRunDB};
@@ -47,6 +47,8 @@ where
         // Get all function runs that are ready to execute.
         // This is, with status scheduled and with all requirements done.
         from_fn(By::<()>::select_all::<FunctionRunToExecuteDB>),
+        // Hold back runs whose transaction key is already in flight.
+        from_fn(serialize_by_transaction_key),
         // Create a locked message for each function run.
         from_fn(create_locked_workers::<T>),
         // And insert generated messages.
@@ -68,6 +70,7 @@ mod tests {
     use td_common::server::SupervisorMessagePayload;
     use td_database::sql::DbPool;
     use td_error::TdError;
+    use td_objects::dxo::collection::CollectionDB;
     use td_objects::dxo::crudl::{RequestContext, handle_sql_err};
     use td_objects::dxo::execution::ExecutionRequest;
     use td_objects::dxo::function::FunctionRegister;
@@ -75,12 +78,13 @@ mod tests {
     use td_objects::dxo::request::{EnvPrefix, FunctionInput};
     use td_objects::dxo::table_data_version::TableDataVersionDBWithNames;
     use td_objects::rest_urls::FunctionParam;
-    use td_objects::sql::SelectBy;
+    use td_objects::sql::{SelectBy, UpdateBy};
     use td_objects::test_utils::seed_collection::seed_collection;
-    use td_objects::test_utils::seed_function::seed_function;
+    use td_objects::test_utils::seed_function::{function_register, seed_function};
     use td_objects::types::basic::{
-        AccessTokenId, BundleId, CollectionName, Decorator, ExecutionName, FunctionRuntimeValues,
-        RoleId, TableName, TableNameDto, UserId, WorkerMessageStatus,
+        AccessTokenId, AtTime, BundleId, CollectionName, Decorator, ExecutionName,
+        FunctionRunStatus, FunctionRuntimeValues, RoleId, TableName, TableNameDto, UserId,
+        WorkerMessageStatus,
     };
     use td_objects::types::composed::TableDependencyDto;
     use td_storage::SPath;
@@ -100,6 +104,8 @@ mod tests {
                 // Get all function runs that are ready to execute.
                 // This is, with status scheduled and with all requirements done.
                 type_of_val(&By::<()>::select_all::<FunctionRunToExecuteDB>),
+                // Hold back runs whose transaction key is already in flight.
+                type_of_val(&serialize_by_transaction_key),
                 // Create a locked message for each function run.
                 type_of_val(&create_locked_workers::<FileWorkerMessageQueue>),
                 // And insert generated messages.
@@ -299,4 +305,112 @@ mod tests {
 
         Ok(())
     }
+
+    async fn seed_publisher(
+        db: &DbPool,
+        collection: &CollectionDB,
+        function: &str,
+        table: &str,
+    ) -> Result<(), TdError> {
+        let create = function_register(function, &[table], &[], &[])?;
+        seed_function(db, collection, &create).await;
+        Ok(())
+    }
+
+    async fn execute(
+        db: &DbPool,
+        collection: &CollectionDB,
+        function: &str,
+    ) -> Result<(), TdError> {
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).create(
+                FunctionParam::builder()
+                    .try_collection(format!("{}", collection.name))?
+                    .try_function(function)?
+                    .build()?,
+                ExecutionRequest::builder().name(None).build()?,
+            );
+        ExecuteFunctionService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await?;
+        Ok(())
+    }
+
+    async fn schedule(context: &SchedulerContext) -> Result<usize, TdError> {
+        ScheduleRequestService::build(context)
+            .service()
+            .await
+            .oneshot(())
+            .await?;
+        Ok(context
+            .worker_queue
+            .locked_messages::<FunctionInput>()
+            .await
+            .len())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_schedule_request_same_transaction_key_sequential(
+        db: DbPool,
+    ) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("cofnig")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+        seed_publisher(&db, &collection, "function_1", "table_1").await?;
+
+        // Both executions have a transaction keyed by the same function.
+        execute(&db, &collection, "function_1").await?;
+        execute(&db, &collection, "function_1").await?;
+
+        // Only the first run is dispatched, the second one waits for it.
+        let context = SchedulerContext::with_defaults(db.clone());
+        assert_eq!(schedule(&context).await?, 1);
+        assert_eq!(schedule(&context).await?, 1);
+
+        // Once the first run is done, the second one is dispatched.
+        let queries = DaoQueries::default();
+        let in_flight: Vec<FunctionRunDB> = queries
+            .select_by::<FunctionRunDB>(&FunctionRunStatus::RunRequested)?
+            .build_query_as()
+            .fetch_all(&db)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(in_flight.len(), 1);
+        let done = UpdateFunctionRunDB {
+            started_on: None,
+            ended_on: Some(AtTime::now()),
+            status: FunctionRunStatus::Done,
+        };
+        queries
+            .update_by::<_, FunctionRunDB>(&done, &in_flight[0].id)?
+            .build()
+            .execute(&db)
+            .await
+            .map_err(handle_sql_err)?;
+
+        assert_eq!(schedule(&context).await?, 2);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_schedule_request_different_transaction_keys_parallel(
+        db: DbPool,
+    ) -> Result<(), TdError> {
+        let collection_name = CollectionName::try_from("cofnig")?;
+        let collection = seed_collection(&db, &collection_name, &UserId::admin()).await;
+        seed_publisher(&db, &collection, "function_1", "table_1").await?;
+        seed_publisher(&db, &collection, "function_2", "table_2").await?;
+
+        // Each execution has a transaction keyed by a different function.
+        execute(&db, &collection, "function_1").await?;
+        execute(&db, &collection, "function_2").await?;
+
+        // Both runs are dispatched together.
+        let context = SchedulerContext::with_defaults(db.clone());
+        assert_eq!(schedule(&context).await?, 2);
+        Ok(())
+    }
 }
@@ -0,0 +1,298 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use crate::function_run::layers::webhook::deliver_webhooks;
+use crate::function_run::webhook::WebhookClient;
+use ta_services::factory::service_factory;
+use td_objects::sql::DaoQueries;
+use td_tower::default_services::ConnectionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = ScheduleWebhooksService,
+    request = (),
+    response = (),
+    connection = ConnectionProvider,
+    context = DaoQueries,
+    context = WebhookClient,
+)]
+fn service() {
+    layers!(from_fn(deliver_webhooks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::layers::update_status::tests::{
+        TestExecution, TestFunction, TestTransaction, test_status_update,
+    };
+    use crate::execution::services::callback::ExecutionCallbackService;
+    use crate::function_run::webhook::{
+        WEBHOOK_DELIVERY_HEADER, WEBHOOK_SIGNATURE_HEADER, webhook_signature,
+    };
+    use axum::Router;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use ta_services::service::TdService;
+    use td_common::execution_status::WorkerCallbackStatus;
+    use td_common::server::{MessageAction, ResponseMessagePayloadBuilder, WorkerClass};
+    use td_common::status::ExitStatus;
+    use td_database::sql::DbPool;
+    use td_error::TdError;
+    use td_objects::dxo::crudl::{RequestContext, handle_sql_err};
+    use td_objects::dxo::function_run::FunctionRun;
+    use td_objects::dxo::webhook::{WebhookDB, WebhookDeliveryDB};
+    use td_objects::dxo::worker::CallbackRequest;
+    use td_objects::rest_urls::FunctionRunIdParam;
+    use td_objects::sql::{Insert, SelectBy};
+    use td_objects::types::basic::{
+        AccessTokenId, AtTime, CollectionName, ExecutionStatus, FunctionName, FunctionRunId,
+        FunctionRunStatus, RoleId, TableNameDto, TransactionStatus, UserId, WebhookDeliveryStatus,
+        WebhookPayload, WebhookSecret, WebhookUrl, WorkerId,
+    };
+    use td_tower::ctx_service::RawOneshot;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "0123456789abcdef";
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_schedule_webhooks(db: DbPool) -> Result<(), TdError> {
+        use td_tower::metadata::type_of_val;
+
+        ScheduleWebhooksService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<(), ()>(&[type_of_val(&deliver_webhooks)]);
+        Ok(())
+    }
+
+    /// Webhook endpoint responding with the given status, forwarding the requests it receives.
+    async fn webhook_sink(
+        status: StatusCode,
+    ) -> (WebhookUrl, mpsc::UnboundedReceiver<(HeaderMap, String)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/hooks",
+            post(move |headers: HeaderMap, body: String| {
+                let tx = tx.clone();
+                async move {
+                    let _ = tx.send((headers, body));
+                    status
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let url = WebhookUrl::try_from(format!("http://{address}/hooks")).unwrap();
+        (url, rx)
+    }
+
+    fn test_executions() -> Result<Vec<TestExecution>, TdError> {
+        Ok(vec![TestExecution {
+            expected_status: ExecutionStatus::Running,
+            transactions: vec![TestTransaction {
+                expected_status: TransactionStatus::Running,
+                functions: vec![
+                    TestFunction {
+                        collection: CollectionName::try_from("c_0")?,
+                        name: FunctionName::try_from("f_0")?,
+                        dependencies: vec![],
+                        tables: vec![TableNameDto::try_from("t_0")?],
+                        initial_status: FunctionRunStatus::Running,
+                        expected_status: FunctionRunStatus::Done,
+                    },
+                    TestFunction {
+                        collection: CollectionName::try_from("c_0")?,
+                        name: FunctionName::try_from("f_1")?,
+                        dependencies: vec![],
+                        tables: vec![TableNameDto::try_from("t_1")?],
+                        initial_status: FunctionRunStatus::Running,
+                        expected_status: FunctionRunStatus::Running,
+                    },
+                ],
+            }],
+        }])
+    }
+
+    /// Registers a webhook on the collection and completes the function run with a callback.
+    async fn complete_function_run(
+        db: &DbPool,
+        webhook: &WebhookDB,
+        function_run: FunctionRunId,
+    ) -> Result<(), TdError> {
+        DaoQueries::default()
+            .insert(webhook)?
+            .build()
+            .execute(db)
+            .await
+            .map_err(handle_sql_err)?;
+
+        let callback: CallbackRequest = ResponseMessagePayloadBuilder::default()
+            .id(WorkerId::default().to_string())
+            .class(WorkerClass::EPHEMERAL)
+            .worker("".to_string())
+            .action(MessageAction::Notify)
+            .start(123)
+            .end(Some(456))
+            .status(WorkerCallbackStatus::Done)
+            .execution(0)
+            .limit(None)
+            .error(None)
+            .exception_kind(None)
+            .exception_message(None)
+            .exception_error_code(None)
+            .exit_status(ExitStatus::Success.code())
+            .context(None)
+            .build()
+            .unwrap();
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user()).update(
+                FunctionRunIdParam::builder()
+                    .function_run_id(function_run)
+                    .build()?,
+                callback,
+            );
+        ExecutionCallbackService::with_defaults(db.clone())
+            .service()
+            .await
+            .raw_oneshot(request)
+            .await
+    }
+
+    async fn deliveries(db: &DbPool, function_run: &FunctionRunId) -> Vec<WebhookDeliveryDB> {
+        DaoQueries::default()
+            .select_by::<WebhookDeliveryDB>(function_run)
+            .unwrap()
+            .build_query_as()
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_schedule_webhooks(db: DbPool) -> Result<(), TdError> {
+        let (url, rx) = webhook_sink(StatusCode::OK).await;
+        let rx = Arc::new(tokio::sync::Mutex::new(rx));
+
+        test_status_update(db.clone(), &test_executions()?, |c, _, _, f| {
+            let db = db.clone();
+            let url = url.clone();
+            let rx = rx.clone();
+            let collection = c[&CollectionName::try_from("c_0").unwrap()].clone();
+            let (_, function_run) = f.iter().find(|(f, _)| f.name.as_str() == "f_0").unwrap();
+            let function_run = function_run.id;
+
+            async move {
+                let webhook = WebhookDB::builder()
+                    .collection_id(collection.id)
+                    .url(url)
+                    .secret(WebhookSecret::try_from(SECRET)?)
+                    .created_on(AtTime::now())
+                    .created_by_id(UserId::admin())
+                    .build()?;
+                complete_function_run(&db, &webhook, function_run).await?;
+
+                let enqueued = deliveries(&db, &function_run).await;
+                assert_eq!(enqueued.len(), 1);
+                assert_eq!(enqueued[0].status, WebhookDeliveryStatus::Pending);
+
+                // the test webhook listens on a loopback address
+                let client =
+                    WebhookClient::new(Duration::from_secs(1), 5, Duration::ZERO, true, 1)?;
+                ScheduleWebhooksService::new(
+                    db.clone(),
+                    Arc::new(DaoQueries::default()),
+                    Arc::new(client),
+                )
+                .service()
+                .await
+                .oneshot(())
+                .await?;
+
+                let (headers, body) = rx.lock().await.recv().await.unwrap();
+                let notified: FunctionRun = serde_json::from_str(&body).unwrap();
+                assert_eq!(notified.id, function_run);
+                assert_eq!(notified.status, FunctionRunStatus::Done);
+                assert_eq!(
+                    headers[WEBHOOK_SIGNATURE_HEADER].to_str().unwrap(),
+                    webhook_signature(
+                        &WebhookSecret::try_from(SECRET)?,
+                        &WebhookPayload::try_from(body.as_str())?
+                    )
+                );
+                assert_eq!(
+                    headers[WEBHOOK_DELIVERY_HEADER].to_str().unwrap(),
+                    enqueued[0].id.to_string()
+                );
+
+                let delivered = deliveries(&db, &function_run).await;
+                assert_eq!(delivered[0].status, WebhookDeliveryStatus::Delivered);
+                assert_eq!(*delivered[0].attempts, 1);
+                assert!(delivered[0].delivered_on.is_some());
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_schedule_webhooks_dead_letter(db: DbPool) -> Result<(), TdError> {
+        let (url, _rx) = webhook_sink(StatusCode::INTERNAL_SERVER_ERROR).await;
+
+        test_status_update(db.clone(), &test_executions()?, |c, _, _, f| {
+            let db = db.clone();
+            let url = url.clone();
+            let collection = c[&CollectionName::try_from("c_0").unwrap()].clone();
+            let (_, function_run) = f.iter().find(|(f, _)| f.name.as_str() == "f_0").unwrap();
+            let function_run = function_run.id;
+
+            async move {
+                let webhook = WebhookDB::builder()
+                    .collection_id(collection.id)
+                    .url(url)
+                    .secret(WebhookSecret::try_from(SECRET)?)
+                    .created_on(AtTime::now())
+                    .created_by_id(UserId::admin())
+                    .build()?;
+                complete_function_run(&db, &webhook, function_run).await?;
+
+                let client =
+                    WebhookClient::new(Duration::from_secs(1), 2, Duration::ZERO, true, 1)?;
+                let service = ScheduleWebhooksService::new(
+                    db.clone(),
+                    Arc::new(DaoQueries::default()),
+                    Arc::new(client),
+                );
+
+                service.service().await.oneshot(()).await?;
+                let retried = deliveries(&db, &function_run).await;
+                assert_eq!(retried[0].status, WebhookDeliveryStatus::Pending);
+                assert_eq!(*retried[0].attempts, 1);
+                assert!(retried[0].last_error.is_some());
+
+                service.service().await.oneshot(()).await?;
+                let dead = deliveries(&db, &function_run).await;
+                assert_eq!(dead[0].status, WebhookDeliveryStatus::DeadLetter);
+                assert_eq!(*dead[0].attempts, 2);
+                assert!(dead[0].delivered_on.is_none());
+
+                // Dead lettered deliveries are not attempted again.
+                service.service().await.oneshot(()).await?;
+                assert_eq!(*deliveries(&db, &function_run).await[0].attempts, 2);
+                Ok(())
+            }
+        })
+        .await
+    }
+}
@@ -3,17 +3,22 @@
 //
 
 use std::time::Instant;
+use td_database::sql::DbMode;
 use td_error::TdError;
 use td_objects::dxo::system::{ApiStatus, HealthStatus};
-use td_tower::extractors::{Connection, IntoMutSqlConnection};
+use td_tower::extractors::{Connection, IntoMutSqlConnection, SrvCtx};
 
-pub async fn database_status(Connection(connection): Connection) -> Result<ApiStatus, TdError> {
+pub async fn database_status(
+    SrvCtx(db_mode): SrvCtx<DbMode>,
+    Connection(connection): Connection,
+) -> Result<ApiStatus, TdError> {
     let start = Instant::now();
 
     let mut conn = connection.lock().await;
     let conn = conn.get_mut_connection()?;
 
     let database_status = match sqlx::Connection::ping(conn).await {
+        Ok(_) if *db_mode == DbMode::ReadOnly => HealthStatus::DatabaseReadOnly,
         Ok(_) => HealthStatus::OK,
         Err(e) => HealthStatus::DatabaseError(e.to_string()),
     };
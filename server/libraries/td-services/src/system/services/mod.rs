@@ -2,12 +2,15 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
+use crate::system::services::refresh_authz::RefreshAuthzService;
 use crate::system::services::status::StatusService;
 use ta_services::factory::ServiceFactory;
 
+mod refresh_authz;
 mod status;
 
 #[derive(ServiceFactory)]
 pub struct SystemServices {
     pub status: StatusService,
+    pub refresh_authz: RefreshAuthzService,
 }
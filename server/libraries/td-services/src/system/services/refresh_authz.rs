@@ -0,0 +1,148 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use ta_services::factory::service_factory;
+use td_authz::{Authz, AuthzContext, authz_cache_status, refresh_authz_context};
+use td_objects::dxo::crudl::{RequestContext, UpdateRequest};
+use td_objects::dxo::system::AuthzCacheStatus;
+use td_objects::sql::DaoQueries;
+use td_objects::tower_service::authz::{AuthzOn, SysAdmin, System};
+use td_objects::tower_service::from::{ExtractService, With};
+use td_tower::default_services::TransactionProvider;
+use td_tower::from_fn::from_fn;
+use td_tower::layers;
+
+#[service_factory(
+    name = RefreshAuthzService,
+    request = UpdateRequest<(), ()>,
+    response = AuthzCacheStatus,
+    connection = TransactionProvider,
+    context = DaoQueries,
+    context = AuthzContext,
+)]
+fn service() {
+    layers!(
+        from_fn(With::<UpdateRequest<(), ()>>::extract::<RequestContext>),
+        from_fn(AuthzOn::<System>::set),
+        from_fn(Authz::<SysAdmin>::check),
+        // refresh the permissions authz cache, as if permissions had changed
+        from_fn(refresh_authz_context),
+        from_fn(authz_cache_status),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use ta_services::service::TdService;
+    use td_database::sql::DbPool;
+    use td_error::{TdError, assert_service_error};
+    use td_objects::dxo::audit_log::AuditLogDB;
+    use td_objects::dxo::crudl::handle_sql_err;
+    use td_objects::sql::SelectBy;
+    use td_objects::test_utils::seed_permission::seed_permission;
+    use td_objects::test_utils::seed_role::seed_role;
+    use td_objects::tower_service::authz::{AuthzContextT, AuthzError};
+    use td_objects::types::basic::{
+        AccessTokenId, AuditAction, AuditOutcome, Description, PermissionType, RoleId, RoleName,
+        UserId,
+    };
+    use td_tower::ctx_service::RawOneshot;
+
+    #[cfg(feature = "test_tower_metadata")]
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_tower_metadata_refresh_authz(db: DbPool) {
+        use td_tower::metadata::type_of_val;
+
+        RefreshAuthzService::with_defaults(db)
+            .metadata()
+            .await
+            .assert_service::<UpdateRequest<(), ()>, AuthzCacheStatus>(&[
+                type_of_val(&With::<UpdateRequest<(), ()>>::extract::<RequestContext>),
+                type_of_val(&AuthzOn::<System>::set),
+                type_of_val(&Authz::<SysAdmin>::check),
+                type_of_val(&refresh_authz_context),
+                type_of_val(&authz_cache_status),
+            ]);
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_refresh_authz(db: DbPool) -> Result<(), TdError> {
+        let authz_context = Arc::new(AuthzContext::default());
+        let role = seed_role(
+            &db,
+            RoleName::try_from("r0")?,
+            Description::try_from("role")?,
+        )
+        .await;
+        let mut conn = db.acquire().await.unwrap();
+        assert!(
+            authz_context
+                .role_permissions(&mut conn, &role.id)
+                .await?
+                .is_none()
+        );
+        let before = authz_context.cache_status(&mut conn).await?;
+
+        // out-of-band change, the cache is not aware of it
+        seed_permission(&db, PermissionType::CollectionDev, None, None, &role).await;
+        assert!(
+            authz_context
+                .role_permissions(&mut conn, &role.id)
+                .await?
+                .is_none()
+        );
+
+        let request = RequestContext::with(
+            AccessTokenId::default(),
+            UserId::admin(),
+            RoleId::sys_admin(),
+        )
+        .update((), ());
+        let service = RefreshAuthzService::new(
+            db.clone(),
+            Arc::new(DaoQueries::default()),
+            authz_context.clone(),
+        )
+        .service()
+        .await;
+        let status = service.raw_oneshot(request).await?;
+        assert_eq!(status.roles, before.roles + 1);
+        assert_eq!(status.permissions, before.permissions + 1);
+
+        let permissions = authz_context.role_permissions(&mut conn, &role.id).await?;
+        assert_eq!(permissions.unwrap().len(), 1);
+
+        // the refresh is audited, without an entity
+        let audit_logs: Vec<AuditLogDB> = DaoQueries::default()
+            .select_by::<AuditLogDB>(&())?
+            .build_query_as()
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(handle_sql_err)?;
+        assert_eq!(audit_logs.len(), 1);
+        assert_eq!(audit_logs[0].service.as_str(), "RefreshAuthzService");
+        assert_eq!(audit_logs[0].action, AuditAction::Update);
+        assert_eq!(audit_logs[0].outcome, AuditOutcome::Success);
+        assert_eq!(audit_logs[0].entity, None);
+        Ok(())
+    }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_refresh_authz_not_allowed(db: DbPool) {
+        let request =
+            RequestContext::with(AccessTokenId::default(), UserId::admin(), RoleId::user())
+                .update((), ());
+        let service = RefreshAuthzService::with_defaults(db).service().await;
+        assert_service_error(service, request, |err| match err {
+            AuthzError::Forbidden(_) => {}
+            other => panic!("Expected 'Forbidden', got {other:?}"),
+        })
+        .await;
+    }
+}
@@ -4,6 +4,7 @@
 
 use crate::system::layers::status::database_status;
 use ta_services::factory::service_factory;
+use td_database::sql::DbMode;
 use td_objects::dxo::system::ApiStatus;
 use td_tower::default_services::ConnectionProvider;
 use td_tower::from_fn::from_fn;
@@ -14,6 +15,7 @@ use td_tower::layers;
     request = (),
     response = ApiStatus,
     connection = ConnectionProvider,
+    context = DbMode,
 )]
 fn service() {
     layers!(from_fn(database_status))
@@ -22,6 +24,7 @@ fn service() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
     use ta_services::service::TdService;
     use td_database::sql::DbPool;
     use td_objects::dxo::system::HealthStatus;
@@ -48,4 +51,15 @@ mod tests {
         assert!(matches!(response.status, HealthStatus::OK));
         assert!(response.latency_as_nanos > 0);
     }
+
+    #[td_test::test(sqlx)]
+    #[tokio::test]
+    async fn test_database_status_service_read_only(db: DbPool) {
+        let service = StatusService::new(db, Arc::new(DbMode::ReadOnly))
+            .service()
+            .await;
+        let response = service.oneshot(()).await.unwrap();
+
+        assert!(matches!(response.status, HealthStatus::DatabaseReadOnly));
+    }
 }
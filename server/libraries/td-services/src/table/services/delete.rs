@@ -36,6 +36,7 @@ use td_tower::layers;
     request = DeleteRequest<TableParam>,
     response = (),
     connection = TransactionProvider,
+    audit = TableId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -23,6 +23,7 @@ use td_tower::layers;
     request = UpdateRequest<TransactionParam, ()>,
     response = (),
     connection = TransactionProvider,
+    audit = TransactionId,
     context = DaoQueries,
     context = AuthzContext,
 )]
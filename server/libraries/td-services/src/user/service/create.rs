@@ -30,6 +30,7 @@ use td_tower::layers;
     request = CreateRequest<(), UserCreate>,
     response = UserRead,
     connection = TransactionProvider,
+    audit = UserId,
     context = DaoQueries,
     context = AuthzContext,
     context = PasswordHashingConfig,
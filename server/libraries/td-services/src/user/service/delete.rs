@@ -23,6 +23,7 @@ use td_tower::layers;
     request = DeleteRequest<UserParam>,
     response = (),
     connection = TransactionProvider,
+    audit = UserId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -32,6 +32,7 @@ use td_tower::layers;
     request = UpdateRequest<UserParam, UserUpdate>,
     response = UserRead,
     connection = TransactionProvider,
+    audit = UserId,
     context = DaoQueries,
     context = AuthzContext,
     context = PasswordHashingConfig,
@@ -28,6 +28,7 @@ use td_tower::layers;
     request = CreateRequest<RoleParam, UserRoleCreate>,
     response = UserRole,
     connection = TransactionProvider,
+    audit = UserRoleId,
     context = DaoQueries,
     context = AuthzContext,
 )]
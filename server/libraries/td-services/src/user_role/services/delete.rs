@@ -14,7 +14,7 @@ use td_objects::sql::DaoQueries;
 use td_objects::tower_service::authz::{AuthzOn, SecAdmin, System};
 use td_objects::tower_service::from::{ExtractNameService, ExtractService, With, combine};
 use td_objects::tower_service::sql::{By, SqlDeleteService, SqlSelectService};
-use td_objects::types::basic::{RoleId, RoleIdName, UserId, UserIdName};
+use td_objects::types::basic::{RoleId, RoleIdName, UserId, UserIdName, UserRoleId};
 use td_tower::default_services::TransactionProvider;
 use td_tower::from_fn::from_fn;
 use td_tower::layers;
@@ -24,6 +24,7 @@ use td_tower::layers;
     request = DeleteRequest<UserRoleParam>,
     response = (),
     connection = TransactionProvider,
+    audit = UserRoleId,
     context = DaoQueries,
     context = AuthzContext,
 )]
@@ -42,6 +43,7 @@ fn service() {
         from_fn(combine::<RoleId, UserId>),
         from_fn(By::<(RoleId, UserId)>::select::<UserRoleDBWithNames>),
         from_fn(assert_not_fixed),
+        from_fn(With::<UserRoleDBWithNames>::extract::<UserRoleId>),
         from_fn(By::<(RoleId, UserId)>::delete::<UserRoleDB>),
     )
 }
@@ -83,6 +85,7 @@ mod tests {
                 type_of_val(&combine::<RoleId, UserId>),
                 type_of_val(&By::<(RoleId, UserId)>::select::<UserRoleDBWithNames>),
                 type_of_val(&assert_not_fixed),
+                type_of_val(&With::<UserRoleDBWithNames>::extract::<UserRoleId>),
                 type_of_val(&By::<(RoleId, UserId)>::delete::<UserRoleDB>),
             ]);
     }
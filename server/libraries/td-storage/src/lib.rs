@@ -4,20 +4,27 @@
 
 use bytes::Bytes;
 use derive_builder::UninitializedFieldError;
+use futures_util::future::BoxFuture;
 use futures_util::stream::BoxStream;
 use itertools::Itertools;
 use object_store::path::Path;
 use regex::Regex;
 use std::fmt::{Debug, Display};
+use std::future::Future;
 use std::ops::Deref;
 use std::path::PathBuf;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use td_error::display_vec::DisplayVec;
 use td_error::td_error;
+use td_tower::deadline;
+use td_tower::error::DeadlineError;
 use tracing::{trace, warn};
 use url::Url;
 
 pub mod location;
 mod mount;
+pub mod quota;
 mod store;
 
 pub use mount::MountDef;
@@ -48,9 +55,15 @@ pub enum StorageError {
     AlreadyExists(String) = 8,
     #[error("Not found {0}")]
     NotFound(String) = 9,
+    #[error("Invalid paths: {0}")]
+    InvalidPaths(DisplayVec<StorageError>) = 10,
+    #[error("Mount {0} check timed out after {1}ms")]
+    MountCheckTimeout(String, u128) = 11,
 
     #[error("Error reading object store stream: {0}")]
     StreamError(#[source] object_store::Error) = 5000,
+    #[error("Storage operation aborted: {0}")]
+    DeadlineExceeded(#[source] DeadlineError) = 7000,
 }
 
 impl From<UninitializedFieldError> for StorageError {
@@ -59,6 +72,20 @@ impl From<UninitializedFieldError> for StorageError {
     }
 }
 
+/// Runs a read storage operation within the request deadline, if any.
+async fn within_deadline<T>(operation: &str, f: impl Future<Output = Result<T>>) -> Result<T> {
+    deadline::within_deadline(operation, f)
+        .await
+        .map_err(StorageError::DeadlineExceeded)?
+}
+
+/// Runs a storage operation with side effects if the request deadline, if any, is not past. It
+/// is not cancelled once started, not to leave it half done.
+async fn before_deadline<T>(operation: &str, f: impl Future<Output = Result<T>>) -> Result<T> {
+    deadline::check_deadline(operation).map_err(StorageError::DeadlineExceeded)?;
+    f.await
+}
+
 /// A path in storage.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct SPath(Path);
@@ -114,6 +141,22 @@ impl SPath {
         Ok(SPath(fs_path))
     }
 
+    /// Parse all the given paths, see [`SPath::parse`].
+    ///
+    /// Unlike parsing one at a time, all invalid paths are reported in a single
+    /// [`StorageError::InvalidPaths`] error.
+    pub fn parse_all<S: AsRef<str>>(paths: impl IntoIterator<Item = S>) -> Result<Vec<SPath>> {
+        let (paths, errors): (Vec<_>, Vec<_>) =
+            paths.into_iter().map(SPath::parse).partition_result();
+        if errors.is_empty() {
+            Ok(paths)
+        } else {
+            Err(StorageError::InvalidPaths(
+                DisplayVec::new(errors).with_separator("; "),
+            ))
+        }
+    }
+
     /// Return the last element of the path.
     ///
     /// Returns `None` if the path is root.
@@ -145,6 +188,16 @@ impl SPath {
         Self::assert_path_element_name(name)?;
         Ok(SPath(self.0.child(name)))
     }
+
+    /// Return the path elements remaining after removing the given base path.
+    ///
+    /// Returns `None` if the path is not the base path or a descendant of it. If the path is the
+    /// base path, the remainder is empty.
+    pub fn strip_prefix(&self, base: &SPath) -> Option<Vec<String>> {
+        self.0
+            .prefix_match(&base.0)
+            .map(|parts| parts.map(|part| part.as_ref().to_string()).collect())
+    }
 }
 
 impl Deref for SPath {
@@ -182,15 +235,97 @@ impl TryFrom<PathBuf> for SPath {
 /// Result type for storage operations.
 pub type Result<T> = std::result::Result<T, StorageError>;
 
+/// Records the latency and the errors of a storage operation, if the `metrics` feature is enabled.
+#[allow(unused_variables)]
+fn record_metrics<T>(op: &'static str, start: Instant, res: &Result<T>) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::histogram!("storage_operation_duration_seconds", "op" => op)
+            .record(start.elapsed().as_secs_f64());
+        if res.is_err() {
+            metrics::counter!("storage_operation_errors_total", "op" => op).increment(1);
+        }
+    }
+}
+
+/// Default timeout of each mount check.
+pub const MOUNT_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Health of a storage mount.
+#[derive(Debug)]
+pub struct MountHealth {
+    pub id: String,
+    pub path: String,
+    pub status: MountStatus,
+}
+
+impl MountHealth {
+    pub fn is_reachable(&self) -> bool {
+        matches!(self.status, MountStatus::Reachable)
+    }
+}
+
+#[derive(Debug)]
+pub enum MountStatus {
+    Reachable,
+    Unreachable(StorageError),
+}
+
+/// Storage mutation notified to the storage hooks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StorageEvent {
+    Write(SPath),
+    Delete(SPath),
+}
+
+impl StorageEvent {
+    pub fn path(&self) -> &SPath {
+        match self {
+            StorageEvent::Write(path) | StorageEvent::Delete(path) => path,
+        }
+    }
+}
+
+/// Hook invoked after each successful storage mutation (e.g. to invalidate a CDN).
+///
+/// Hooks run on a background task, not blocking the storage operation, and their errors are
+/// logged, not propagated.
+pub trait StorageHook: Debug + Send + Sync {
+    fn on_event(&self, event: StorageEvent) -> BoxFuture<'static, Result<()>>;
+}
+
 #[derive(Debug)]
 pub struct Storage {
     storage: MountsStorage,
+    hooks: Vec<Arc<dyn StorageHook>>,
 }
 
 impl Storage {
     pub fn from(mount_defs: Vec<MountDef>) -> Result<Self> {
         let storage = MountsStorage::from(mount_defs)?;
-        Ok(Self { storage })
+        Ok(Self {
+            storage,
+            hooks: vec![],
+        })
+    }
+
+    /// Adds a hook to be notified of the storage mutations.
+    pub fn with_hook(mut self, hook: Arc<dyn StorageHook>) -> Self {
+        self.hooks.push(hook);
+        self
+    }
+
+    fn notify(&self, event: StorageEvent) {
+        for hook in &self.hooks {
+            let hook = hook.clone();
+            let event = event.clone();
+            tokio::spawn(async move {
+                let path = event.path().clone();
+                if let Err(e) = hook.on_event(event).await {
+                    warn!("storage hook {:?} on {} error: {}", hook, path, e);
+                }
+            });
+        }
     }
 
     pub fn to_external_uri(&self, path: &SPath) -> Result<(Url, &MountDef)> {
@@ -202,8 +337,23 @@ impl Storage {
         res
     }
 
+    pub fn to_external_uris(&self, paths: &[SPath]) -> Result<Vec<Url>> {
+        let res = self.storage.to_external_uris(paths);
+        match &res {
+            Ok(uris) => trace!(
+                "to_external_uris({} paths) -> {} uris",
+                paths.len(),
+                uris.len()
+            ),
+            Err(e) => warn!("to_external_uris({} paths) error: {}", paths.len(), e),
+        }
+        res
+    }
+
     pub async fn exists(&self, path: &SPath) -> Result<bool> {
-        let res = self.storage.exists(path).await;
+        let start = Instant::now();
+        let res = within_deadline("exists", self.storage.exists(path)).await;
+        record_metrics("exists", start, &res);
         match &res {
             Ok(exists) => trace!("exists({}) -> {}", path, exists),
             Err(e) => warn!("exists({}) error: {}", path, e),
@@ -212,25 +362,55 @@ impl Storage {
     }
 
     pub async fn delete(&self, path: &SPath) -> Result<()> {
-        let res = self.storage.delete(path).await;
+        let start = Instant::now();
+        let res = before_deadline("delete", self.storage.delete(path)).await;
+        record_metrics("delete", start, &res);
         match &res {
-            Ok(_) => trace!("delete({}) -> ok", path),
+            Ok(_) => {
+                trace!("delete({}) -> ok", path);
+                self.notify(StorageEvent::Delete(path.clone()));
+            }
             Err(e) => warn!("delete({}) error: {}", path, e),
         }
         res
     }
 
+    pub async fn delete_all(&self, paths: &[SPath]) -> Result<Vec<(SPath, Result<()>)>> {
+        let start = Instant::now();
+        let res = before_deadline("delete_all", self.storage.delete_all(paths)).await;
+        record_metrics("delete_all", start, &res);
+        match &res {
+            Ok(results) => {
+                let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+                trace!("delete_all({} paths) -> {} failed", paths.len(), failed);
+                results
+                    .iter()
+                    .filter(|(_, r)| r.is_ok())
+                    .for_each(|(path, _)| self.notify(StorageEvent::Delete(path.clone())));
+            }
+            Err(e) => warn!("delete_all({} paths) error: {}", paths.len(), e),
+        }
+        res
+    }
+
     pub async fn write(&self, path: &SPath, data: Vec<u8>) -> Result<()> {
-        let res = self.storage.write(path, data).await;
+        let start = Instant::now();
+        let res = before_deadline("write", self.storage.write(path, data)).await;
+        record_metrics("write", start, &res);
         match &res {
-            Ok(_) => trace!("write({}) -> ok", path),
+            Ok(_) => {
+                trace!("write({}) -> ok", path);
+                self.notify(StorageEvent::Write(path.clone()));
+            }
             Err(e) => warn!("write({}) error: {}", path, e),
         }
         res
     }
 
     pub async fn read(&self, path: &SPath) -> Result<Vec<u8>> {
-        let res = self.storage.read(path).await;
+        let start = Instant::now();
+        let res = within_deadline("read", self.storage.read(path)).await;
+        record_metrics("read", start, &res);
         match &res {
             Ok(_) => trace!("read({}) -> ok", path),
             Err(e) => warn!("read({}) error: {}", path, e),
@@ -239,7 +419,9 @@ impl Storage {
     }
 
     pub async fn read_stream(&self, path: &SPath) -> Result<BoxStream<'static, Result<Bytes>>> {
-        let res = self.storage.read_stream(path).await;
+        let start = Instant::now();
+        let res = within_deadline("read_stream", self.storage.read_stream(path)).await;
+        record_metrics("read_stream", start, &res);
         match &res {
             Ok(_) => trace!("read_stream({}) -> ok", path),
             Err(e) => warn!("read_stream({}) error: {}", path, e),
@@ -248,13 +430,67 @@ impl Storage {
     }
 
     pub async fn list(&self, path: &SPath) -> Result<Vec<SPath>> {
-        let res = self.storage.list(path).await;
+        let start = Instant::now();
+        let res = within_deadline("list", self.storage.list(path)).await;
+        record_metrics("list", start, &res);
         match &res {
             Ok(_) => trace!("list({}) -> ok", path),
             Err(e) => warn!("list({}) error: {}", path, e),
         }
         res
     }
+
+    /// Returns a page of, at most, `limit` objects under the given path, starting after the given
+    /// page token, and the page token of the next page, `None` if it is the last page.
+    pub async fn list_page(
+        &self,
+        path: &SPath,
+        page_token: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<SPath>, Option<String>)> {
+        let start = Instant::now();
+        let res =
+            within_deadline("list_page", self.storage.list_page(path, page_token, limit)).await;
+        record_metrics("list_page", start, &res);
+        match &res {
+            Ok((files, next)) => trace!(
+                "list_page({}) -> {} more: {}",
+                path,
+                files.len(),
+                next.is_some()
+            ),
+            Err(e) => warn!("list_page({}) error: {}", path, e),
+        }
+        res
+    }
+
+    /// Returns the total size, in bytes, of the objects under the given path, within its mount.
+    pub async fn usage(&self, path: &SPath) -> Result<u64> {
+        let start = Instant::now();
+        let res = within_deadline("usage", self.storage.usage(path)).await;
+        record_metrics("usage", start, &res);
+        match &res {
+            Ok(usage) => trace!("usage({}) -> {}", path, usage),
+            Err(e) => warn!("usage({}) error: {}", path, e),
+        }
+        res
+    }
+    /// Checks all the mounts are reachable, concurrently and with [`MOUNT_CHECK_TIMEOUT`] each.
+    pub async fn check_mounts(&self) -> Vec<MountHealth> {
+        self.check_mounts_with_timeout(MOUNT_CHECK_TIMEOUT).await
+    }
+
+    /// Checks all the mounts are reachable, concurrently and with the given timeout each.
+    pub async fn check_mounts_with_timeout(&self, timeout: Duration) -> Vec<MountHealth> {
+        let health = self.storage.check_mounts(timeout).await;
+        for mount in &health {
+            match &mount.status {
+                MountStatus::Reachable => trace!("check_mounts({}) -> ok", mount.path),
+                MountStatus::Unreachable(e) => warn!("check_mounts({}) error: {}", mount.path, e),
+            }
+        }
+        health
+    }
 }
 
 #[cfg(feature = "test-utils")]
@@ -273,10 +509,15 @@ impl Default for Storage {
 
 #[cfg(test)]
 mod tests {
-    use crate::{MountDef, SPath, Storage};
+    use crate::{MountDef, MountStatus, SPath, Storage, StorageError, StorageEvent, StorageHook};
+    use futures_util::future::BoxFuture;
     use object_store::path::Path;
     use std::fs;
     use std::ops::Deref;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use td_tower::deadline::with_deadline;
+    use td_tower::error::DeadlineError;
     use testdir::testdir;
 
     #[test]
@@ -315,6 +556,28 @@ mod tests {
         assert!(SPath::parse("/a#b").is_err()); // invalid path element name
     }
 
+    #[test]
+    fn test_spath_parse_all() {
+        let paths = SPath::parse_all(["/", "/a", "/a/b.ext"]).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[2].to_string(), "/a/b.ext");
+
+        let err = SPath::parse_all(["/a", "a", "/b", "/a/", "/a#b"]).unwrap_err();
+        match err {
+            StorageError::InvalidPaths(errors) => {
+                let invalid = errors
+                    .iter()
+                    .map(|e| match e {
+                        StorageError::InvalidPath(path, _) => path.as_str(),
+                        _ => panic!("unexpected error: {e}"),
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(invalid, vec!["a", "/a/", "/a#b"]);
+            }
+            _ => panic!("unexpected error: {err}"),
+        }
+    }
+
     #[test]
     fn test_spath_deref_to_path() {
         assert_eq!(SPath::parse("/").unwrap().deref(), &Path::default());
@@ -325,6 +588,25 @@ mod tests {
         assert_eq!(SPath::parse("/foo").unwrap().to_string(), "/foo");
     }
 
+    #[test]
+    fn test_spath_strip_prefix() {
+        let base = SPath::parse("/a").unwrap();
+        assert_eq!(
+            SPath::parse("/a/b/c").unwrap().strip_prefix(&base),
+            Some(vec!["b".to_string(), "c".to_string()])
+        );
+        assert_eq!(
+            SPath::parse("/a/b")
+                .unwrap()
+                .strip_prefix(&SPath::parse("/").unwrap()),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(base.strip_prefix(&base), Some(vec![]));
+        assert_eq!(SPath::parse("/ab/c").unwrap().strip_prefix(&base), None);
+        assert_eq!(SPath::parse("/b/a").unwrap().strip_prefix(&base), None);
+        assert_eq!(SPath::parse("/").unwrap().strip_prefix(&base), None);
+    }
+
     #[tokio::test]
     async fn test_storage_api() {
         let test_dir = testdir!();
@@ -400,4 +682,326 @@ mod tests {
             &match3.replace("\\", "/")
         );
     }
+
+    #[test]
+    fn test_storage_to_external_uris() {
+        let test_dir = testdir!();
+        let mount1_dir = test_dir.join("mount1");
+        fs::create_dir(&mount1_dir).unwrap();
+        let mount2_dir = test_dir.join("mount2");
+        fs::create_dir(&mount2_dir).unwrap();
+
+        #[cfg(target_os = "windows")]
+        let (uri1, uri2) = (
+            format!("file:///{}", mount1_dir.to_string_lossy()),
+            format!("file:///{}", mount2_dir.to_string_lossy()),
+        );
+        #[cfg(not(target_os = "windows"))]
+        let (uri1, uri2) = (
+            format!("file://{}", mount1_dir.to_string_lossy()),
+            format!("file://{}", mount2_dir.to_string_lossy()),
+        );
+
+        let mount1 = MountDef::builder()
+            .id("id0")
+            .path("/")
+            .uri(&uri1)
+            .build()
+            .unwrap();
+        let mount2 = MountDef::builder()
+            .id("id1")
+            .path("/foo")
+            .uri(&uri2)
+            .build()
+            .unwrap();
+        let storage = Storage::from(vec![mount1, mount2]).unwrap();
+
+        let paths = ["/a.txt", "/foo", "/foo/b.txt", "/c.txt", "/foo/d/e.txt"]
+            .iter()
+            .map(|p| SPath::parse(p).unwrap())
+            .collect::<Vec<_>>();
+        let uris = storage.to_external_uris(&paths).unwrap();
+        let uris = uris.iter().map(|u| u.as_str()).collect::<Vec<_>>();
+        let expected = [
+            format!("{uri1}/a.txt"),
+            uri2.clone(),
+            format!("{uri2}/b.txt"),
+            format!("{uri1}/c.txt"),
+            format!("{uri2}/d/e.txt"),
+        ]
+        .map(|uri| uri.replace("\\", "/"));
+        assert_eq!(uris, expected);
+
+        // same as converting one at a time
+        for (path, uri) in paths.iter().zip(uris) {
+            assert_eq!(storage.to_external_uri(path).unwrap().0.as_str(), uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_check_mounts() {
+        let test_dir = testdir!();
+        let good_dir = test_dir.join("good");
+        fs::create_dir(&good_dir).unwrap();
+        let missing_dir = test_dir.join("missing");
+
+        #[cfg(target_os = "windows")]
+        let (good_uri, missing_uri) = (
+            format!("file:///{}", good_dir.to_string_lossy()),
+            format!("file:///{}", missing_dir.to_string_lossy()),
+        );
+        #[cfg(not(target_os = "windows"))]
+        let (good_uri, missing_uri) = (
+            format!("file://{}", good_dir.to_string_lossy()),
+            format!("file://{}", missing_dir.to_string_lossy()),
+        );
+
+        let good = MountDef::builder()
+            .id("good")
+            .path("/")
+            .uri(good_uri)
+            .build()
+            .unwrap();
+        let missing = MountDef::builder()
+            .id("missing")
+            .path("/missing")
+            .uri(missing_uri)
+            .build()
+            .unwrap();
+        let storage = Storage::from(vec![missing, good]).unwrap();
+
+        let health = storage.check_mounts().await;
+        assert_eq!(health.len(), 2);
+        assert_eq!(health[0].id, "good");
+        assert!(health[0].is_reachable());
+        assert_eq!(health[1].id, "missing");
+        assert!(!health[1].is_reachable());
+        assert!(matches!(
+            health[1].status,
+            MountStatus::Unreachable(StorageError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_storage_deadline() {
+        let test_dir = testdir!();
+
+        #[cfg(target_os = "windows")]
+        let uri = format!("file:///{}", test_dir.to_string_lossy());
+        #[cfg(not(target_os = "windows"))]
+        let uri = format!("file://{}", test_dir.to_string_lossy());
+
+        let mount = MountDef::builder()
+            .id("id")
+            .path("/")
+            .uri(uri)
+            .build()
+            .unwrap();
+        let storage = Storage::from(vec![mount]).unwrap();
+        let path = SPath::parse("/file.txt").unwrap();
+
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(500);
+        with_deadline(deadline, async {
+            // within the deadline, operations complete
+            storage.write(&path, b"data".to_vec()).await.unwrap();
+            assert_eq!(storage.read(&path).await.unwrap(), b"data");
+
+            // the request is slow, and storage operations are aborted once past the deadline
+            tokio::time::sleep_until(deadline).await;
+            let err = storage.read(&path).await.unwrap_err();
+            assert!(matches!(
+                err,
+                StorageError::DeadlineExceeded(DeadlineError::DeadlineExceeded(op)) if op == "read"
+            ));
+            let err = storage.write(&path, b"late".to_vec()).await.unwrap_err();
+            assert!(matches!(
+                err,
+                StorageError::DeadlineExceeded(DeadlineError::DeadlineExceeded(op)) if op == "write"
+            ));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_storage_usage() {
+        let test_dir = testdir!();
+
+        #[cfg(target_os = "windows")]
+        let uri = format!("file:///{}", test_dir.to_string_lossy());
+        #[cfg(not(target_os = "windows"))]
+        let uri = format!("file://{}", test_dir.to_string_lossy());
+
+        let mount = MountDef::builder()
+            .id("id")
+            .path("/")
+            .uri(uri)
+            .build()
+            .unwrap();
+        let storage = Storage::from(vec![mount]).unwrap();
+
+        let dir = SPath::parse("/dir").unwrap();
+        assert_eq!(storage.usage(&dir).await.unwrap(), 0);
+
+        storage
+            .write(&SPath::parse("/dir/a.txt").unwrap(), vec![0; 10])
+            .await
+            .unwrap();
+        storage
+            .write(&SPath::parse("/dir/sub/b.txt").unwrap(), vec![0; 5])
+            .await
+            .unwrap();
+        storage
+            .write(&SPath::parse("/other.txt").unwrap(), vec![0; 100])
+            .await
+            .unwrap();
+        assert_eq!(storage.usage(&dir).await.unwrap(), 15);
+        assert_eq!(
+            storage.usage(&SPath::parse("/").unwrap()).await.unwrap(),
+            115
+        );
+    }
+
+    #[tokio::test]
+    async fn test_storage_list_page() {
+        let test_dir = testdir!();
+
+        #[cfg(target_os = "windows")]
+        let uri = format!("file:///{}", test_dir.to_string_lossy());
+        #[cfg(not(target_os = "windows"))]
+        let uri = format!("file://{}", test_dir.to_string_lossy());
+
+        let mount = MountDef::builder()
+            .id("id")
+            .path("/")
+            .uri(uri)
+            .build()
+            .unwrap();
+        let storage = Storage::from(vec![mount]).unwrap();
+
+        let dir = SPath::parse("/dir").unwrap();
+        let mut expected = vec![];
+        for name in ["e.txt", "c.txt", "a.txt", "d.txt", "b.txt"] {
+            let path = dir.child(name).unwrap();
+            storage.write(&path, vec![0; 1]).await.unwrap();
+            expected.push(path);
+        }
+        // not directly under the listed path
+        storage
+            .write(&SPath::parse("/dir/sub/f.txt").unwrap(), vec![0; 1])
+            .await
+            .unwrap();
+        expected.sort();
+
+        let mut listed = vec![];
+        let mut pages = 0;
+        let mut page_token = None;
+        loop {
+            let (page, next) = storage.list_page(&dir, page_token, 2).await.unwrap();
+            assert!(page.len() <= 2);
+            listed.extend(page);
+            pages += 1;
+            match next {
+                Some(next) => page_token = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(listed, expected);
+
+        // page tokens are the path of the last object of the page
+        let (_, next) = storage.list_page(&dir, None, 2).await.unwrap();
+        assert_eq!(next.as_deref(), Some("/dir/b.txt"));
+        let (page, _) = storage.list_page(&dir, next, 2).await.unwrap();
+        assert_eq!(page, expected[2..4]);
+
+        // and must be objects of the listed path
+        let err = storage
+            .list_page(&dir, Some("/other/b.txt".to_string()), 2)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StorageError::InvalidPath(..)));
+    }
+
+    #[derive(Debug)]
+    struct RecordingHook(tokio::sync::mpsc::UnboundedSender<StorageEvent>);
+
+    impl StorageHook for RecordingHook {
+        fn on_event(&self, event: StorageEvent) -> BoxFuture<'static, crate::Result<()>> {
+            let sender = self.0.clone();
+            Box::pin(async move {
+                sender
+                    .send(event)
+                    .map_err(|e| StorageError::ConfigurationError(e.to_string()))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_hook_on_write() {
+        let test_dir = testdir!();
+        let mount_def = MountDef::builder()
+            .id("id")
+            .path("/")
+            .uri(td_test::file::mount_uri(&test_dir))
+            .build()
+            .unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let storage = Storage::from(vec![mount_def])
+            .unwrap()
+            .with_hook(Arc::new(RecordingHook(sender)));
+
+        let path = SPath::parse("/foo.txt").unwrap();
+        storage.write(&path, b"foo".to_vec()).await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap();
+        assert_eq!(event, Some(StorageEvent::Write(path)));
+    }
+
+    #[tokio::test]
+    async fn test_storage_hook_on_delete_all() {
+        let test_dir = testdir!();
+        let mount_def = MountDef::builder()
+            .id("id")
+            .path("/")
+            .uri(td_test::file::mount_uri(&test_dir))
+            .build()
+            .unwrap();
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let storage = Storage::from(vec![mount_def])
+            .unwrap()
+            .with_hook(Arc::new(RecordingHook(sender)));
+
+        let paths = ["/a.txt", "/b.txt", "/c.txt"]
+            .iter()
+            .map(|p| SPath::parse(p).unwrap())
+            .collect::<Vec<_>>();
+        for path in &paths[..2] {
+            storage.write(path, b"foo".to_vec()).await.unwrap();
+        }
+        for _ in &paths[..2] {
+            let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .unwrap();
+            assert!(matches!(event, Some(StorageEvent::Write(_))));
+        }
+
+        let results = storage.delete_all(&paths).await.unwrap();
+        assert!(results.iter().all(|(_, r)| r.is_ok()));
+
+        // Each deleted path is notified, whatever the order of the results.
+        let mut deleted = vec![];
+        for _ in &paths {
+            let event = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+                .await
+                .unwrap();
+            match event {
+                Some(StorageEvent::Delete(path)) => deleted.push(path),
+                event => panic!("unexpected event {event:?}"),
+            }
+        }
+        deleted.sort();
+        assert_eq!(deleted, paths);
+    }
 }
@@ -7,8 +7,8 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::ops::Deref;
 use td_objects::types::basic::{
-    BundleId, CollectionId, DataLocation, FunctionVersionId, Partition, StorageVersion,
-    TableDataVersionId, TableId, TableVersionId, TransactionId,
+    BundleChunkOffset, BundleId, BundleUploadId, CollectionId, DataLocation, FunctionVersionId,
+    Partition, StorageVersion, TableDataVersionId, TableId, TableVersionId, TransactionId,
 };
 
 /// The [`StorageLocation`] creates storage URIS for the different types of data tabsdata stores.
@@ -35,6 +35,7 @@ pub enum StorageLocation {
     /// * /LOCATION/c/COLLECTION/d/DATA_VERSION/t/TABLE/TABLE_VERSION.t
     /// * /LOCATION/c/COLLECTION/d/DATA_VERSION/t/TABLE/TABLE_VERSION/p/PARTITION.p
     /// * /bundles/c/COLLECTION/f/BUNDLE.tgz
+    /// * /bundles/c/COLLECTION/u/UPLOAD/OFFSET.part (pending chunked bundle upload parts)
     V2,
 }
 
@@ -87,6 +88,8 @@ struct LocationBuilderInfo {
     location: SPath,
     collection: Option<String>,
     bundle: Option<String>,
+    upload: Option<String>,
+    chunk: Option<String>,
     data_version: Option<String>,
     transaction: Option<String>,
     function_version: Option<String>,
@@ -221,6 +224,34 @@ impl FunctionBuilder {
     }
 }
 
+/// Builder for the pending chunked function bundle upload location.
+#[derive(Debug)]
+pub struct UploadBuilder {
+    info: LocationBuilderInfo,
+    version_builder: Box<dyn VersionLocationBuilder>,
+}
+
+impl UploadBuilder {
+    /// Set the upload for the upload location.
+    pub fn upload(&mut self, upload: &BundleUploadId) -> &mut Self {
+        self.info.upload = Some(upload.to_string());
+        self.info.chunk = None;
+        self
+    }
+
+    /// Set the chunk, by its offset, for the upload location.
+    pub fn chunk(&mut self, offset: &BundleChunkOffset) -> &mut Self {
+        // zero padded, so chunk parts list in offset order
+        self.info.chunk = Some(format!("{:020}", **offset));
+        self
+    }
+
+    /// Build the upload location.
+    pub fn build(&self) -> (SPath, StorageLocation) {
+        self.version_builder.build(&self.info, None)
+    }
+}
+
 /// Builder for the collection location.
 #[derive(Debug)]
 pub struct CollectionBuilder {
@@ -245,6 +276,16 @@ impl CollectionBuilder {
         builder
     }
 
+    /// Return an [`UploadBuilder`] based on the [`CollectionBuilder`]
+    pub fn upload(self, upload: &BundleUploadId) -> UploadBuilder {
+        let mut builder = UploadBuilder {
+            info: self.info,
+            version_builder: self.version_builder,
+        };
+        builder.upload(upload);
+        builder
+    }
+
     /// Return a [`DataBuilder`] based on the [`CollectionBuilder`]
     pub fn data(self, data_version: &TableDataVersionId) -> DataBuilder {
         let mut builder = DataBuilder {
@@ -427,6 +468,22 @@ impl VersionLocationBuilder for V2LocationBuilder {
                     .unwrap()
                     .child(&format!("{bundle}.tgz"))
                     .unwrap();
+            } else if let Some(upload) = &info.upload {
+                // Upload parts are stored at /bundles/c/COLLECTION/u/UPLOAD/OFFSET.part
+                path = SPath::default()
+                    .child("bundles")
+                    .unwrap()
+                    .child("c")
+                    .unwrap()
+                    .child(collection)
+                    .unwrap()
+                    .child("u")
+                    .unwrap()
+                    .child(upload)
+                    .unwrap();
+                if let Some(chunk) = &info.chunk {
+                    path = path.child(&format!("{chunk}.part")).unwrap();
+                }
             } else if let Some(data_version) = &info.data_version {
                 // function always is present if data is present
                 path = path.child("d").unwrap().child(data_version).unwrap();
@@ -469,8 +526,8 @@ mod tests {
     use super::*;
     use td_error::TdError;
     use td_objects::types::basic::{
-        BundleId, CollectionId, DataLocation, FunctionVersionId, Partition, TableDataVersionId,
-        TableId, TableVersionId, TransactionId,
+        BundleChunkOffset, BundleId, BundleUploadId, CollectionId, DataLocation, FunctionVersionId,
+        Partition, TableDataVersionId, TableId, TableVersionId, TransactionId,
     };
 
     #[test]
@@ -551,6 +608,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_upload_builder_v2() -> Result<(), TdError> {
+        let data_location = DataLocation::try_from("/L")?;
+        let collection = CollectionId::default();
+        let upload = BundleUploadId::default();
+        let mut builder = StorageLocation::V2
+            .builder(&data_location)
+            .collection(&collection)
+            .upload(&upload);
+        assert_eq!(
+            builder.build().0,
+            SPath::parse(format!("/bundles/c/{collection}/u/{upload}"))?
+        );
+
+        builder.chunk(&BundleChunkOffset::try_from(1024)?);
+        assert_eq!(
+            builder.build().0,
+            SPath::parse(format!(
+                "/bundles/c/{collection}/u/{upload}/00000000000000001024.part"
+            ))?
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_data_builder_v2() -> Result<(), TdError> {
         let data_location = DataLocation::try_from("/L")?;
@@ -5,22 +5,57 @@
 use super::{Result, SPath, StorageError};
 use bytes::Bytes;
 use derive_builder::Builder;
-use futures_util::TryStreamExt;
 use futures_util::stream::BoxStream;
+use futures_util::{StreamExt, TryStreamExt, stream};
+use object_store::limit::LimitStore;
 use object_store::path::{Path, PathPart};
 use object_store::{ObjectStore, PutPayload};
 #[cfg(target_os = "windows")]
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fmt::Debug;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::fmt::{Debug, Display};
 use std::sync::LazyLock;
 use td_common::absolute_path::AbsolutePath;
 use tracing::debug;
 use url::Url;
 
+/// Replacement of credentials and query string values in redacted URIs.
+const REDACTED: &str = "****";
+
+/// Object store client option for the timeout of each request.
+const TIMEOUT_OPTION: &str = "timeout";
+
+/// Return the URI with the password of its user info and the values of its query string
+/// redacted, so it can be logged. If the URI cannot be parsed and could contain credentials,
+/// it is fully redacted.
+fn redacted_uri(uri: &str) -> String {
+    match Url::parse(uri) {
+        Ok(mut url) => {
+            if url.password().is_some() {
+                let _ = url.set_password(Some(REDACTED));
+            }
+            if url.query().is_some() {
+                let keys = url
+                    .query_pairs()
+                    .map(|(key, _)| key.into_owned())
+                    .collect::<Vec<_>>();
+                url.query_pairs_mut()
+                    .clear()
+                    .extend_pairs(keys.iter().map(|key| (key, REDACTED)));
+            }
+            url.to_string()
+        }
+        Err(_) if uri.contains('@') || uri.contains('?') => REDACTED.to_string(),
+        Err(_) => uri.to_string(),
+    }
+}
+
 /// Definition of a mount.
-#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+///
+/// Its [`Debug`] and [`Display`] representations redact the credentials of the URI and the
+/// values of the options.
+#[derive(Clone, Serialize, Deserialize, Builder)]
 #[builder(
     setter(into, strip_option),
     build_fn(validate = "Self::validate", error = "StorageError")
@@ -45,6 +80,16 @@ pub struct MountDef {
     ///
     /// Google Cloud Storage: refer to https://docs.rs/object_store/0.11.0/object_store/gcp/enum.GoogleConfigKey.html
     options: Option<HashMap<String, String>>,
+
+    #[builder(default)]
+    /// Timeout of each request to the object store, as a duration (i.e. `30s`, `2m`). If not
+    /// set, the object store default is used. It takes precedence over the `timeout` option.
+    request_timeout: Option<String>,
+
+    #[builder(default)]
+    /// Maximum number of concurrent requests, and so of open connections, to the object store.
+    /// If not set, they are not limited.
+    max_connections: Option<usize>,
 }
 
 impl MountDef {
@@ -68,6 +113,57 @@ impl MountDef {
         static NO_OPTIONS: LazyLock<HashMap<String, String>> = LazyLock::new(HashMap::new);
         self.options.as_ref().unwrap_or(&NO_OPTIONS)
     }
+
+    pub fn request_timeout(&self) -> Option<&str> {
+        self.request_timeout.as_deref()
+    }
+
+    pub fn max_connections(&self) -> Option<usize> {
+        self.max_connections
+    }
+
+    /// Options to create the object store client of the mount, the mount [`options`] with the
+    /// request timeout, if set.
+    pub fn store_options(&self) -> HashMap<String, String> {
+        let mut options = self.options().clone();
+        if let Some(request_timeout) = &self.request_timeout {
+            options.insert(TIMEOUT_OPTION.to_string(), request_timeout.clone());
+        }
+        options
+    }
+}
+
+impl Debug for MountDef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MountDef")
+            .field("id", &self.id)
+            .field("path", &self.path)
+            .field("uri", &redacted_uri(&self.uri))
+            .field(
+                "options",
+                &self.options.as_ref().map(|options| {
+                    options
+                        .keys()
+                        .map(|key| (key, REDACTED))
+                        .collect::<BTreeMap<_, _>>()
+                }),
+            )
+            .field("request_timeout", &self.request_timeout)
+            .field("max_connections", &self.max_connections)
+            .finish()
+    }
+}
+
+impl Display for MountDef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} -> {}",
+            self.id,
+            self.path,
+            redacted_uri(&self.uri)
+        )
+    }
 }
 
 impl MountDefBuilder {
@@ -96,29 +192,36 @@ impl MountDefBuilder {
             let uri_str = self.uri.as_ref().unwrap();
             if uri_str.len() != uri_str.trim().len() {
                 return Err(StorageError::ConfigurationError(format!(
-                    "URI cannot have leading or trailing spaces: '{uri_str}'"
+                    "URI cannot have leading or trailing spaces: '{}'",
+                    redacted_uri(uri_str)
                 )));
             }
             #[cfg(not(target_os = "windows"))]
             if !uri_str.ends_with('/') {
                 return Err(StorageError::ConfigurationError(format!(
-                    "Invalid URI {uri_str}, must end with '/'"
+                    "Invalid URI {}, must end with '/'",
+                    redacted_uri(uri_str)
                 )));
             }
             #[cfg(target_os = "windows")]
             if !uri_str.ends_with('\\') && !uri_str.ends_with('/') {
                 return Err(StorageError::ConfigurationError(format!(
-                    "Invalid URI {uri_str}, must end with '\\' or '/'"
+                    "Invalid URI {}, must end with '\\' or '/'",
+                    redacted_uri(uri_str)
                 )));
             }
             let uri = Url::parse(uri_str).map_err(|e| {
-                StorageError::ConfigurationError(format!("Invalid URI {uri_str} : {e}"))
+                StorageError::ConfigurationError(format!(
+                    "Invalid URI {} : {e}",
+                    redacted_uri(uri_str)
+                ))
             })?;
             match uri.scheme() {
                 "file" => {
                     if !is_valid_file_scheme(uri_str) {
                         return Err(StorageError::ConfigurationError(format!(
-                            "Invalid file URI, path must be absolute: {uri_str}"
+                            "Invalid file URI, path must be absolute: {}",
+                            redacted_uri(uri_str)
                         )));
                     }
                 }
@@ -133,6 +236,18 @@ impl MountDefBuilder {
                 }
             }
         }
+        if let Some(Some(request_timeout)) = &self.request_timeout {
+            humantime::parse_duration(request_timeout).map_err(|e| {
+                StorageError::ConfigurationError(format!(
+                    "Invalid request timeout '{request_timeout}' : {e}"
+                ))
+            })?;
+        }
+        if let Some(Some(0)) = self.max_connections {
+            return Err(StorageError::ConfigurationError(
+                "Max connections must be greater than 0".to_string(),
+            ));
+        }
         Ok(())
     }
 }
@@ -155,6 +270,8 @@ impl From<&MountDef> for MountDefBuilder {
             path: Some(mount.path.clone()),
             uri: Some(mount.uri.clone()),
             options: Some(mount.options.clone()),
+            request_timeout: Some(mount.request_timeout.clone()),
+            max_connections: Some(mount.max_connections),
         }
     }
 }
@@ -234,7 +351,7 @@ impl Debug for Mount {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Mount")
             .field("mount_path", &format!("{}/", &self.mount_path))
-            .field("store_uri", &self.def.uri)
+            .field("store_uri", &redacted_uri(&self.def.uri))
             .finish()
     }
 }
@@ -259,7 +376,10 @@ impl Mount {
     /// Create a [`Mount`] with the given definition.
     pub fn new(def: MountDef) -> Result<Self> {
         let mut uri = Url::parse(&def.uri).unwrap();
-        let store = Self::create_store(&uri, def.options())?;
+        let mut store = Self::create_store(&uri, &def.store_options())?;
+        if let Some(max_connections) = def.max_connections {
+            store = Box::new(LimitStore::new(store, max_connections));
+        }
 
         let mount_path = SPath::parse(&def.path)?;
         let path_mapper_from_mount = PathMapperFromMount::new(mount_path.parts().count());
@@ -269,7 +389,11 @@ impl Mount {
         let path_mapper_to_uri = PathMapperToUri::new(&uri_path);
         let path_mapper_from_uri = PathMapperFromUri::new(uri_path.parts().count());
 
-        debug!("Mount, mount: {} uri: {}", &def.path, &def.uri);
+        debug!(
+            "Mount, mount: {} uri: {}",
+            &def.path,
+            redacted_uri(&def.uri)
+        );
 
         uri.set_path("");
         Ok(Mount {
@@ -337,6 +461,44 @@ impl Mount {
         }
     }
 
+    /// Deletes all the given paths, using the bulk delete of the object store if available.
+    ///
+    /// Returns the result of each distinct path deletion along with the path, in no particular
+    /// order.
+    pub async fn delete_all(&self, paths: &[SPath]) -> Vec<(SPath, Result<()>)> {
+        let mut results = Vec::with_capacity(paths.len());
+        let mut locations = HashMap::new();
+        for path in paths {
+            match self.to_external_path(&path.0) {
+                Ok(external_path) => {
+                    locations.insert(external_path, path.clone());
+                }
+                Err(e) => results.push((path.clone(), Err(e))),
+            }
+        }
+
+        // The bulk delete reports the deleted locations, but its errors can't always be told
+        // apart by location, so the locations it did not report are deleted one by one.
+        let deleted = self
+            .store
+            .delete_stream(
+                stream::iter(locations.keys().cloned().map(Ok).collect::<Vec<_>>()).boxed(),
+            )
+            .filter_map(|deleted| async move { deleted.ok() })
+            .collect::<Vec<_>>()
+            .await;
+        for external_path in deleted {
+            if let Some(path) = locations.remove(&external_path) {
+                results.push((path, Ok(())));
+            }
+        }
+        for path in locations.into_values() {
+            let result = self.delete(&path).await;
+            results.push((path, result));
+        }
+        results
+    }
+
     pub async fn write(&self, path: &SPath, data: Vec<u8>) -> Result<()> {
         if path == &self.mount_path {
             return Err(StorageError::InvalidPath(
@@ -392,6 +554,33 @@ impl Mount {
         }
     }
 
+    /// Checks the mount backend is reachable, listing the mount root.
+    pub async fn check(&self) -> Result<()> {
+        let external_path = self.to_external_path(&self.mount_path.0)?;
+        if self.uri_scheme_authority.scheme() == "file" {
+            // listing a missing directory in a file system is not an error, it must exist
+            let dir = Url::parse(&self.def.uri)
+                .ok()
+                .and_then(|uri| uri.to_file_path().ok())
+                .ok_or_else(|| {
+                    StorageError::InvalidPath(
+                        redacted_uri(&self.def.uri),
+                        "not a file path".to_string(),
+                    )
+                })?;
+            if !tokio::fs::metadata(&dir).await.is_ok_and(|m| m.is_dir()) {
+                return Err(StorageError::NotFound(redacted_uri(&self.def.uri)));
+            }
+        }
+        match self.store.list_with_delimiter(Some(&external_path)).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(StorageError::CouldNotReadFromObjectStore(
+                redacted_uri(&self.def.uri),
+                e,
+            )),
+        }
+    }
+
     pub async fn list(&self, path: &SPath) -> Result<Vec<SPath>> {
         let external_path = self.to_external_path(&path.0)?;
         match self.store.list_with_delimiter(Some(&external_path)).await {
@@ -410,19 +599,93 @@ impl Mount {
             )),
         }
     }
+
+    /// Returns a page of, at most, `limit` objects directly under the given path, after the
+    /// object of the given page token, and the page token of the next page, if there is one.
+    ///
+    /// Page tokens are the path, in the mount, of the last object of a page. Its external path is
+    /// the listing offset of the next page, so each page lists only the objects after it.
+    pub async fn list_page(
+        &self,
+        path: &SPath,
+        page_token: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<SPath>, Option<String>)> {
+        let external_path = self.to_external_path(&path.0)?;
+        let depth = external_path.parts().count() + 1;
+        let offset = page_token
+            .map(|token| {
+                let cursor = SPath::parse(&token)?;
+                if cursor.parent().as_ref() != Some(path) {
+                    return Err(StorageError::InvalidPath(
+                        token,
+                        format!("page token is not an object of '{path}'"),
+                    ));
+                }
+                self.to_external_path(&cursor.0)
+            })
+            .transpose()?;
+        let objects = match &offset {
+            Some(offset) => self.store.list_with_offset(Some(&external_path), offset),
+            None => self.store.list(Some(&external_path)),
+        }
+        .try_filter(|meta| futures_util::future::ready(meta.location.parts().count() == depth))
+        .map_ok(|meta| meta.location);
+
+        // one more than the page, to know if there is a next page
+        let limit = limit.max(1);
+        let mut page = if self.uri_scheme_authority.scheme() == "file" {
+            // local files are not listed in order, the first ones are kept in a bounded max-heap
+            objects
+                .try_fold(BinaryHeap::new(), |mut page, location| async move {
+                    page.push(location);
+                    if page.len() > limit + 1 {
+                        page.pop();
+                    }
+                    Ok(page)
+                })
+                .await
+                .map(BinaryHeap::into_sorted_vec)
+        } else {
+            // object stores list in order, so listing stops after the page
+            objects.take(limit + 1).try_collect::<Vec<_>>().await
+        }
+        .map_err(|e| StorageError::CouldNotReadFromObjectStore(path.to_string(), e))?;
+
+        let has_next_page = page.len() > limit;
+        page.truncate(limit);
+        let files = page
+            .iter()
+            .map(|location| Ok(SPath(self.to_mount_path(location)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let next_page_token = has_next_page
+            .then(|| files.last().map(ToString::to_string))
+            .flatten();
+        Ok((files, next_page_token))
+    }
+
+    /// Returns the total size, in bytes, of all the objects under the given path (recursively).
+    pub async fn usage(&self, path: &SPath) -> Result<u64> {
+        let external_path = self.to_external_path(&path.0)?;
+        self.store
+            .list(Some(&external_path))
+            .try_fold(0u64, |usage, meta| async move { Ok(usage + meta.size) })
+            .await
+            .map_err(|e| StorageError::CouldNotReadFromObjectStore(path.to_string(), e))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::mount::{Mount, PathMapper, PathMapperPrefixer, PathMapperTrimmer};
-    use crate::{MountDef, SPath, StorageError};
+    use crate::mount::{Mount, PathMapper, PathMapperPrefixer, PathMapperTrimmer, redacted_uri};
+    use crate::{MountDef, MountsStorage, SPath, StorageError};
     use bytes::Bytes;
     use futures_util::StreamExt;
     use object_store::path::Path;
     use std::collections::HashMap;
     use td_common::absolute_path::AbsolutePath;
     use td_test::reqs::{
-        AzureStorageWithAccountKeyReqs, GcpStorageWithServiceAccountKeyReqs,
+        AzureStorageWithAccountKeyReqs, GcpStorageWithServiceAccountKeyReqs, S3EmulatorReqs,
         S3WithAccessKeySecretKeyReqs, TestRequirements,
     };
     use testdir::testdir;
@@ -554,6 +817,106 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_redacted_uri() {
+        assert_eq!(
+            redacted_uri("s3://key:secret@bucket/prefix/"),
+            "s3://key:****@bucket/prefix/"
+        );
+        assert_eq!(
+            redacted_uri("az://container/prefix/?sv=2022&sig=secret"),
+            "az://container/prefix/?sv=****&sig=****"
+        );
+        assert_eq!(redacted_uri("gs://bucket/prefix/"), "gs://bucket/prefix/");
+        assert_eq!(redacted_uri("not a uri"), "not a uri");
+        assert_eq!(redacted_uri("key:secret@bucket"), "****");
+    }
+
+    #[test]
+    fn test_mount_def_redacted() {
+        let mount_def = MountDef::builder()
+            .id("id")
+            .path("/foo")
+            .uri("s3://key:secret@bucket/prefix")
+            .options(HashMap::from([(
+                "aws_secret_access_key".to_string(),
+                "secret".to_string(),
+            )]))
+            .build()
+            .unwrap();
+        let debug = format!("{mount_def:?}");
+        assert!(!debug.contains("secret@"));
+        assert!(debug.contains("s3://key:****@bucket/prefix/"));
+        assert!(debug.contains(r#""aws_secret_access_key": "****""#));
+        assert_eq!(
+            mount_def.to_string(),
+            "id /foo -> s3://key:****@bucket/prefix/"
+        );
+
+        let mount = Mount::new(mount_def).unwrap();
+        assert!(!format!("{mount:?}").contains("secret@"));
+        assert_eq!(
+            mount
+                .to_external_uri(&SPath::parse("/foo/bar.txt").unwrap())
+                .unwrap()
+                .as_str(),
+            "s3://key:secret@bucket/prefix/bar.txt"
+        );
+    }
+
+    #[test]
+    fn test_mount_def_store_options() {
+        let json_str = r#"{"id":"id","path":"/","uri":"s3://bucket/prefix/","options":{"aws_region":"us-east-2","timeout":"5s"},"request_timeout":"45s","max_connections":8}"#;
+        let mount_def: MountDef = serde_json::from_str(json_str).unwrap();
+        assert!(matches!(mount_def.validate(), Ok(())));
+        assert_eq!(mount_def.request_timeout(), Some("45s"));
+        assert_eq!(mount_def.max_connections(), Some(8));
+
+        let options = mount_def.store_options();
+        assert_eq!(options.len(), 2);
+        assert_eq!(options["aws_region"], "us-east-2");
+        assert_eq!(options["timeout"], "45s");
+        // the mount options are kept as they were given
+        assert_eq!(mount_def.options()["timeout"], "5s");
+
+        // the store requests are limited to the max connections
+        let mount = Mount::new(mount_def.clone()).unwrap();
+        assert_eq!(mount.def().store_options(), options);
+        assert!(mount.store.to_string().starts_with("LimitStore(8, "));
+        assert!(MountsStorage::from(vec![mount_def]).is_ok());
+
+        let mount_def = MountDef::builder()
+            .id("id")
+            .path("/")
+            .uri("s3://bucket/prefix")
+            .build()
+            .unwrap();
+        assert_eq!(mount_def.request_timeout(), None);
+        assert_eq!(mount_def.max_connections(), None);
+        assert!(mount_def.store_options().is_empty());
+        let mount = Mount::new(mount_def).unwrap();
+        assert!(!mount.store.to_string().starts_with("LimitStore"));
+
+        assert!(matches!(
+            MountDef::builder()
+                .id("id")
+                .path("/")
+                .uri("s3://bucket/prefix")
+                .request_timeout("soon")
+                .build(),
+            Err(StorageError::ConfigurationError(_))
+        ));
+        assert!(matches!(
+            MountDef::builder()
+                .id("id")
+                .path("/")
+                .uri("s3://bucket/prefix")
+                .max_connections(0usize)
+                .build(),
+            Err(StorageError::ConfigurationError(_))
+        ));
+    }
+
     async fn test_mount(
         uri: &Url,
         mount_path: &str,
@@ -760,6 +1123,37 @@ mod tests {
         test_aws_mount("/foo", &reqs).await;
     }
 
+    async fn test_s3_emulator_mount(path: &str, s3_info: &S3EmulatorReqs) {
+        let options = s3_info.options();
+
+        let uri = format!("{}/{}", s3_info.uri, s3_info.test_path().to_str().unwrap());
+        let uri = Url::parse(&uri).unwrap();
+
+        let object_store = object_store::parse_url_opts(&uri, &options).unwrap().0;
+
+        let mount_def = MountDef::builder()
+            .id("id")
+            .path(path)
+            .uri(uri.to_string())
+            .options(options)
+            .build()
+            .unwrap();
+
+        test_mount(&uri, path, object_store, Mount::new(mount_def).unwrap()).await;
+    }
+
+    #[td_test::test(when(reqs = S3EmulatorReqs, env_prefix= "s3e0"))]
+    #[tokio::test]
+    async fn test_s3_emulator_root_mount(reqs: S3EmulatorReqs) {
+        test_s3_emulator_mount("/", &reqs).await;
+    }
+
+    #[td_test::test(when(reqs = S3EmulatorReqs, env_prefix= "s3e0"))]
+    #[tokio::test]
+    async fn test_s3_emulator_non_root_mount(reqs: S3EmulatorReqs) {
+        test_s3_emulator_mount("/foo", &reqs).await;
+    }
+
     async fn test_azure_mount(path: &str, az_info: &AzureStorageWithAccountKeyReqs) {
         let configs = HashMap::from([
             (
@@ -0,0 +1,112 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use td_objects::types::basic::CollectionName;
+use tokio::sync::OwnedMutexGuard;
+
+/// Per collection storage quotas, in bytes.
+///
+/// Collections without an explicit quota get the default quota, no quota (unlimited) if the
+/// default is not set.
+///
+/// Clones share the per collection locks, see [`StorageQuota::lock`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageQuota {
+    #[serde(default)]
+    default: Option<u64>,
+    #[serde(default)]
+    collections: HashMap<CollectionName, u64>,
+    #[serde(skip)]
+    locks: Arc<Mutex<HashMap<CollectionName, Arc<tokio::sync::Mutex<()>>>>>,
+}
+
+impl PartialEq for StorageQuota {
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default && self.collections == other.collections
+    }
+}
+
+impl Eq for StorageQuota {}
+
+impl StorageQuota {
+    pub fn new(default: Option<u64>, collections: HashMap<CollectionName, u64>) -> Self {
+        Self {
+            default,
+            collections,
+            locks: Arc::default(),
+        }
+    }
+
+    /// Returns the quota of the given collection, `None` if unlimited.
+    pub fn quota(&self, collection: &CollectionName) -> Option<u64> {
+        self.collections.get(collection).copied().or(self.default)
+    }
+
+    /// Returns the quota of the given collection if storing `size` more bytes on top of the
+    /// current `usage` would exceed it, `None` if it fits.
+    pub fn exceeded(&self, collection: &CollectionName, usage: u64, size: u64) -> Option<u64> {
+        self.quota(collection)
+            .filter(|quota| usage.saturating_add(size) > *quota)
+    }
+
+    /// Locks the quota of the given collection. Writers must hold the lock from checking the
+    /// collection usage until their write is done, so concurrent writes cannot both fit the
+    /// quota on their own and exceed it together.
+    pub async fn lock(&self, collection: &CollectionName) -> OwnedMutexGuard<()> {
+        let lock = self
+            .locks
+            .lock()
+            .unwrap()
+            .entry(collection.clone())
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_quota() {
+        let c0 = CollectionName::try_from("c0").unwrap();
+        let c1 = CollectionName::try_from("c1").unwrap();
+
+        let unlimited = StorageQuota::default();
+        assert_eq!(unlimited.quota(&c0), None);
+        assert_eq!(unlimited.exceeded(&c0, u64::MAX, u64::MAX), None);
+
+        let quota = StorageQuota::new(Some(100), HashMap::from([(c1.clone(), 10)]));
+        assert_eq!(quota.quota(&c0), Some(100));
+        assert_eq!(quota.quota(&c1), Some(10));
+        assert_eq!(quota.exceeded(&c1, 5, 5), None);
+        assert_eq!(quota.exceeded(&c1, 5, 6), Some(10));
+        assert_eq!(quota.exceeded(&c0, 50, 50), None);
+        assert_eq!(quota.exceeded(&c0, 50, 51), Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_storage_quota_lock() {
+        let c0 = CollectionName::try_from("c0").unwrap();
+        let c1 = CollectionName::try_from("c1").unwrap();
+
+        let quota = StorageQuota::default();
+        let clone = quota.clone();
+        let guard = quota.lock(&c0).await;
+        // other collections are not locked
+        drop(clone.lock(&c1).await);
+        // the same collection is locked, also through clones
+        assert!(
+            tokio::time::timeout(std::time::Duration::from_millis(50), clone.lock(&c0))
+                .await
+                .is_err()
+        );
+        drop(guard);
+        drop(clone.lock(&c0).await);
+    }
+}
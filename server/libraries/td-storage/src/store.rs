@@ -2,13 +2,15 @@
 // Copyright 2024 Tabs Data Inc.
 //
 
-use super::{Result, SPath, StorageError};
+use super::{MountHealth, MountStatus, Result, SPath, StorageError};
 use crate::mount::{Mount, MountDef};
 use bytes::Bytes;
+use futures_util::future::join_all;
 use futures_util::stream::BoxStream;
 use itertools::Itertools;
 use object_store::path::{Path, PathPart};
 use std::collections::HashMap;
+use std::time::Duration;
 use url::Url;
 
 /// Persistent store based on Storage mounts.
@@ -80,6 +82,26 @@ impl MountsStorage {
         Ok((mount.to_external_uri(path)?, mount.def()))
     }
 
+    /// Converts all the paths to external URIs, failing on the first error. The mount lookup is
+    /// done once per distinct parent path.
+    pub fn to_external_uris(&self, paths: &[SPath]) -> Result<Vec<Url>> {
+        let mut parent_mounts: HashMap<SPath, &Mount> = HashMap::new();
+        paths
+            .iter()
+            .map(|path| {
+                // a path is either a mount path or in the same mount as its parent
+                let mount = match (self.mounts.get(path), path.parent()) {
+                    (Some(mount), _) => mount,
+                    (None, Some(parent)) => *parent_mounts
+                        .entry(parent)
+                        .or_insert_with_key(|parent| self.find_mount(parent)),
+                    (None, None) => self.find_mount(path),
+                };
+                mount.to_external_uri(path)
+            })
+            .collect()
+    }
+
     pub async fn exists(&self, path: &SPath) -> Result<bool> {
         let mount = self.find_mount(path);
         mount.exists(path).await
@@ -90,6 +112,28 @@ impl MountsStorage {
         mount.delete(path).await
     }
 
+    /// Deletes all the given paths, with a bulk delete per mount.
+    ///
+    /// Returns the result of each distinct path deletion along with the path, in no particular
+    /// order.
+    pub async fn delete_all(&self, paths: &[SPath]) -> Result<Vec<(SPath, Result<()>)>> {
+        let mut by_mount: HashMap<&SPath, (&Mount, Vec<SPath>)> = HashMap::new();
+        for path in paths {
+            let mount = self.find_mount(path);
+            by_mount
+                .entry(mount.mount_path())
+                .or_insert_with(|| (mount, vec![]))
+                .1
+                .push(path.clone());
+        }
+
+        let mut results = Vec::with_capacity(paths.len());
+        for (mount, mount_paths) in by_mount.into_values() {
+            results.extend(mount.delete_all(&mount_paths).await);
+        }
+        Ok(results)
+    }
+
     pub async fn write(&self, path: &SPath, data: Vec<u8>) -> Result<()> {
         let mount = self.find_mount(path);
         mount.write(path, data).await
@@ -109,6 +153,47 @@ impl MountsStorage {
         let mount = self.find_mount(path);
         mount.list(path).await
     }
+
+    pub async fn list_page(
+        &self,
+        path: &SPath,
+        page_token: Option<String>,
+        limit: usize,
+    ) -> Result<(Vec<SPath>, Option<String>)> {
+        let mount = self.find_mount(path);
+        mount.list_page(path, page_token, limit).await
+    }
+
+    pub async fn usage(&self, path: &SPath) -> Result<u64> {
+        let mount = self.find_mount(path);
+        mount.usage(path).await
+    }
+
+    /// Checks all the mounts concurrently, each one with the given timeout.
+    ///
+    /// Returns the health of each mount, sorted by mount path.
+    pub async fn check_mounts(&self, timeout: Duration) -> Vec<MountHealth> {
+        let checks = self
+            .mounts
+            .values()
+            .sorted_by(|a, b| a.mount_path().cmp(b.mount_path()))
+            .map(|mount| async move {
+                let status = match tokio::time::timeout(timeout, mount.check()).await {
+                    Ok(Ok(())) => MountStatus::Reachable,
+                    Ok(Err(e)) => MountStatus::Unreachable(e),
+                    Err(_) => MountStatus::Unreachable(StorageError::MountCheckTimeout(
+                        mount.def().id.clone(),
+                        timeout.as_millis(),
+                    )),
+                };
+                MountHealth {
+                    id: mount.def().id.clone(),
+                    path: mount.def().path.clone(),
+                    status,
+                }
+            });
+        join_all(checks).await
+    }
 }
 
 #[cfg(test)]
@@ -155,6 +240,66 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_store_delete_all() {
+        let test_dir = testdir!();
+        let mount1_dir = test_dir.join("mount1");
+        fs::create_dir(&mount1_dir).unwrap();
+        let mount2_dir = test_dir.join("mount2");
+        fs::create_dir(&mount2_dir).unwrap();
+
+        #[cfg(target_os = "windows")]
+        let uri1 = format!("file:///{}", mount1_dir.to_string_lossy());
+        #[cfg(not(target_os = "windows"))]
+        let uri1 = format!("file://{}", mount1_dir.to_string_lossy());
+
+        let mount1 = MountDef::builder()
+            .id("id0")
+            .path("/")
+            .uri(uri1)
+            .build()
+            .unwrap();
+
+        #[cfg(target_os = "windows")]
+        let uri2 = format!("file:///{}", mount2_dir.to_string_lossy());
+        #[cfg(not(target_os = "windows"))]
+        let uri2 = format!("file://{}", mount2_dir.to_string_lossy());
+
+        let mount2 = MountDef::builder()
+            .id("id1")
+            .path("/foo")
+            .uri(uri2)
+            .build()
+            .unwrap();
+        let store = super::MountsStorage::from(vec![mount1, mount2]).unwrap();
+
+        let existing = ["/a.txt", "/foo/b.txt", "/c.txt"]
+            .iter()
+            .map(|p| SPath::parse(p).unwrap())
+            .collect::<Vec<_>>();
+        for path in &existing {
+            store.write(path, vec![1]).await.unwrap();
+        }
+
+        let mut paths = existing.clone();
+        paths.insert(1, SPath::parse("/foo/not_found.txt").unwrap());
+        let results = store.delete_all(&paths).await.unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|(_, r)| r.is_ok()));
+        let mut deleted = results
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        deleted.sort();
+        let mut expected = paths.clone();
+        expected.sort();
+        assert_eq!(deleted, expected);
+
+        for path in &existing {
+            assert!(!store.exists(path).await.unwrap());
+        }
+    }
+
     #[tokio::test]
     async fn test_store() {
         let test_dir = testdir!();
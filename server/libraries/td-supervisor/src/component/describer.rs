@@ -12,6 +12,8 @@ use getset::{Getters, Setters};
 use std::fmt::{Debug, Display, Formatter};
 use std::path::PathBuf;
 use td_common::env::{EnvironmentError, to_absolute};
+use td_common::os::ResourceLimits;
+use td_common::server::SupervisorMessagePayload::SupervisorRequestMessagePayload;
 use td_common::server::{SupervisorMessage, WorkerClass};
 use thiserror::Error;
 
@@ -32,6 +34,14 @@ pub trait WorkerDescriber: Display + Debug {
     fn work(&self) -> &PathBuf;
     fn queue(&self) -> &PathBuf;
     fn etc(&self) -> &PathBuf;
+
+    /// Resource limits of the worker, set by the message that triggers it, if any.
+    fn limits(&self) -> Option<&ResourceLimits> {
+        match &self.message().as_ref()?.payload {
+            SupervisorRequestMessagePayload(payload) => payload.limits().as_ref(),
+            _ => None,
+        }
+    }
 }
 
 // Default worker describer.
@@ -351,8 +361,10 @@ mod tests {
     use std::fs::create_dir_all;
     use std::path::PathBuf;
     use td_common::env::{get_current_exe_name, get_current_exe_path};
-    use td_common::server::WorkerClass::REGULAR;
-    use td_common::server::{CONFIG_FOLDER, ETC_FOLDER, MSG_FOLDER, WORK_FOLDER};
+    use td_common::server::WorkerClass::{EPHEMERAL, REGULAR};
+    use td_common::server::{
+        CONFIG_FOLDER, ETC_FOLDER, MSG_FOLDER, MessageAction, RequestMessagePayload, WORK_FOLDER,
+    };
     use tempfile::tempdir;
 
     //noinspection DuplicatedCode
@@ -450,4 +462,52 @@ mod tests {
             .build();
         assert!(describer.is_ok());
     }
+
+    //noinspection DuplicatedCode
+    #[test]
+    fn test_limits() {
+        let workspace_folder = tempdir().unwrap();
+        let config_folder = workspace_folder.path().to_path_buf().join(CONFIG_FOLDER);
+        create_dir_all(&config_folder).expect("Error creating config folder");
+        let work_folder = workspace_folder.path().to_path_buf().join(WORK_FOLDER);
+        create_dir_all(&work_folder).expect("Error creating work folder");
+        let limits = ResourceLimits::builder().timeout(10u64).build().unwrap();
+        let mut payload = RequestMessagePayload::new(
+            EPHEMERAL,
+            "function".to_string(),
+            MessageAction::Start,
+            vec![],
+            None,
+            None,
+        );
+        payload.set_limits(Some(limits.clone()));
+        let message = SupervisorMessage {
+            id: "id".to_string(),
+            work: "0".to_string(),
+            file: work_folder.join("id.yaml"),
+            payload: SupervisorRequestMessagePayload(payload),
+        };
+        let describer = |message: Option<SupervisorMessage>| {
+            TabsDataWorkerDescriberBuilder::default()
+                .instance(PathBuf::from("."))
+                .class(EPHEMERAL)
+                .name(get_current_exe_name().unwrap())
+                .runtime(None)
+                .location(Relative)
+                .program(get_current_exe_path().expect("Error getting current running program"))
+                .set_state(None)
+                .get_states(vec![])
+                .arguments(vec![])
+                .markers(vec![])
+                .config(config_folder.clone())
+                .work(work_folder.clone())
+                .queue(work_folder.clone().join(MSG_FOLDER))
+                .etc(work_folder.clone().join(ETC_FOLDER))
+                .message(message)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(describer(Some(message)).limits(), Some(&limits));
+        assert_eq!(describer(None).limits(), None);
+    }
 }
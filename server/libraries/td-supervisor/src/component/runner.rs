@@ -20,9 +20,11 @@ use std::fmt::{Debug, Formatter};
 use std::fs::OpenOptions;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::time::Duration;
 use std::{env, fmt};
 use td_common::env::{check_flag_env, get_current_dir};
 use td_common::logging::LOG_LOCATION;
+use td_common::os::apply_resource_limits;
 use td_common::server::WorkerName::FUNCTION;
 use td_common::server::{
     ResponseMessagePayloadBuilderError, WORKER_ERR_FILE, WORKER_OUT_FILE, WorkerClass,
@@ -149,6 +151,11 @@ impl WorkerRunner for TabsDataWorkerRunner {
             .stdin(Stdio::piped())
             .stderr(err);
 
+        if let Some(limits) = worker.describer().limits() {
+            debug!("Applying worker resource limits: {:?}", limits);
+            apply_resource_limits(&mut command, limits);
+        }
+
         if worker.describer().set_state().is_some() {
             command.stdout(Stdio::piped());
         } else {
@@ -303,6 +310,16 @@ pub enum RunnerError {
     IOError(#[from] std::io::Error),
     #[error("Worker exited: {message}")]
     WorkerExited { message: String },
+    #[error("Worker '{worker}' exceeded its time limit of {timeout:?} and was terminated")]
+    WorkerTimedOut { worker: String, timeout: Duration },
+    #[error(
+        "Worker '{worker}' exceeded its memory limit of {limit} bytes, using {used} bytes, and was terminated"
+    )]
+    WorkerOutOfMemory {
+        worker: String,
+        used: u64,
+        limit: u64,
+    },
     #[error("An error occurred running instance operations: {0}")]
     InstanceFailure(#[from] InstanceError),
     #[error("Received void message for ephemeral controller. Ignoring request.")]
@@ -347,6 +364,14 @@ pub enum RunnerError {
     RuntimeEnvironmentCreationError(#[from] TdError),
 }
 
+impl RunnerError {
+    /// Whether the worker was terminated for exceeding its resource limits. Retrying it would
+    /// exceed them again.
+    pub fn is_resource_limit_exceeded(&self) -> bool {
+        matches!(self, WorkerTimedOut { .. } | WorkerOutOfMemory { .. })
+    }
+}
+
 pub fn check_show_env() -> bool {
     const TD_SHOW_ENV: &str = "TD_SHOW_ENV";
     check_flag_env(TD_SHOW_ENV)
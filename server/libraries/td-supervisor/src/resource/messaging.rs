@@ -9,7 +9,7 @@ use td_common::files::{
 };
 use td_common::server::{
     COMPLETE_FOLDER, ERROR_FOLDER, FAIL_FOLDER, ONGOING_FOLDER, PLANNED_FOLDER, PayloadType,
-    QUEUED_FOLDER, SupervisorMessage,
+    QUEUED_FOLDER, SupervisorMessage, sort_by_priority,
 };
 use tracing::{debug, error};
 
@@ -26,19 +26,24 @@ impl SupervisorMessageQueue {
     }
 
     pub fn planned_messages(&self) -> Vec<SupervisorMessage> {
-        get_files_in_folder_sorted_by_name(self.root.join(PLANNED_FOLDER), Some(YAML_EXTENSION))
-            .unwrap_or_else(|_| Vec::new())
-            .into_iter()
-            .filter_map(|file| {
-                match SupervisorMessage::try_from((file.clone(), PayloadType::Request)) {
-                    Ok(msg) => Some(msg),
-                    Err(e) => {
-                        error!("Failed to extract message from file {:?}: {:?}", file, e);
-                        None
-                    }
+        let mut messages: Vec<_> = get_files_in_folder_sorted_by_name(
+            self.root.join(PLANNED_FOLDER),
+            Some(YAML_EXTENSION),
+        )
+        .unwrap_or_else(|_| Vec::new())
+        .into_iter()
+        .filter_map(|file| {
+            match SupervisorMessage::try_from((file.clone(), PayloadType::Request)) {
+                Ok(msg) => Some(msg),
+                Err(e) => {
+                    error!("Failed to extract message from file {:?}: {:?}", file, e);
+                    None
                 }
-            })
-            .collect()
+            }
+        })
+        .collect();
+        sort_by_priority(&mut messages);
+        messages
     }
 
     pub fn error_messages(&self) -> Vec<SupervisorMessage> {
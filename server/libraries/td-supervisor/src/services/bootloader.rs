@@ -50,7 +50,8 @@ impl Config for Configuration {}
 #[derive(Debug, Clone, clap_derive::Parser)]
 #[command(
     name = "Tabsdata Boot Loader",
-    version = "1.5.1",
+    version = td_common::about::build_info().version,
+    long_version = td_common::about::long_version(),
     about = "Tabsdata Boot Loader",
     long_about = "Tabsdata's bootloader prepares the execution instance resources."
 )]
@@ -2,13 +2,14 @@
 // Copyright 2025 Tabs Data Inc.
 //
 
-use crate::component::describer::TabsDataWorkerDescriberBuilder;
+use crate::component::describer::{TabsDataWorkerDescriberBuilder, WorkerDescriber};
 use crate::component::notifier::execution;
 use crate::component::parameters::render;
 use crate::component::runner::RunnerError;
 use crate::component::runner::RunnerError::{
     DescriberFailure, IOError, InvalidMessageType, MissingStartDate, MissingStdOutError,
-    ReadStdOutError, StartNotificationError, VoidEphemeralMessage, WorkerExited,
+    ReadStdOutError, StartNotificationError, VoidEphemeralMessage, WorkerExited, WorkerOutOfMemory,
+    WorkerTimedOut,
 };
 use crate::component::tracker::{WorkerStatus, check_status, get_pid_path};
 use crate::launch::worker::{TabsDataWorker, Worker, notify};
@@ -56,7 +57,7 @@ use sysinfo::{Pid, Signal};
 use td_common::attach::check_nowait_env;
 use td_common::env::to_absolute;
 use td_common::execution_status::WorkerCallbackStatus;
-use td_common::os::terminate_process;
+use td_common::os::{LimitedExit, terminate_process, wait_with_limits};
 use td_common::server::SupervisorMessagePayload::{
     SupervisorExceptionMessagePayload, SupervisorRequestMessagePayload,
     SupervisorResponseMessagePayload,
@@ -397,7 +398,8 @@ impl<'de> Deserialize<'de> for ControllersConfig {
 #[derive(Debug, Clone, clap_derive::Parser)]
 #[command(
     name = "Tabsdata Supervisor",
-    version = "1.5.1",
+    version = td_common::about::build_info().version,
+    long_version = td_common::about::long_version(),
     about = "Tabsdata Supervisor",
     long_about = "Tabsdata supervisor that can manage workers using a configuration descriptor."
 )]
@@ -1317,16 +1319,7 @@ impl Supervisor {
         let end = Utc::now().timestamp_millis();
         let execution = execution(&message);
         let limit = worker.retries;
-        let (status, error) = match &result {
-            Ok(_) => (WorkerCallbackStatus::Done, None),
-            Err(e) => {
-                if execution <= limit {
-                    (WorkerCallbackStatus::Error, Some(format!("{e:?}")))
-                } else {
-                    (WorkerCallbackStatus::Failed, Some(format!("{e:?}")))
-                }
-            }
-        };
+        let (status, error) = callback_status(&result, execution, limit);
 
         let notify_answer = notify(
             worker_run,
@@ -1543,8 +1536,40 @@ impl Supervisor {
                         sleep(Duration::from_secs(5)).await;
                     }
                 } else {
-                    match child.wait().await {
-                        Ok(exit_status) => {
+                    match wait_with_limits(&mut child, describer.limits()).await {
+                        Ok(LimitedExit::TimedOut(timeout)) => {
+                            error!(
+                                "Class {} worker '{}' exceeded its time limit of {:?}",
+                                class.as_ref(),
+                                &describer.name(),
+                                timeout
+                            );
+                            return (
+                                Some(td_worker),
+                                Err(WorkerTimedOut {
+                                    worker: describer.name().clone(),
+                                    timeout,
+                                }),
+                            );
+                        }
+                        Ok(LimitedExit::OutOfMemory { used, limit }) => {
+                            error!(
+                                "Class {} worker '{}' exceeded its memory limit of {} bytes, using {} bytes",
+                                class.as_ref(),
+                                &describer.name(),
+                                limit,
+                                used
+                            );
+                            return (
+                                Some(td_worker),
+                                Err(WorkerOutOfMemory {
+                                    worker: describer.name().clone(),
+                                    used,
+                                    limit,
+                                }),
+                            );
+                        }
+                        Ok(LimitedExit::Exited(exit_status)) => {
                             if exit_status.success() {
                                 info!(
                                     "Class {} worker '{}' completed successfully!",
@@ -2090,6 +2115,22 @@ impl Supervisor {
     }
 }
 
+/// Status to notify for a finished worker run, with its error if it failed. Failed runs are
+/// retried up to the retries limit, unless they exceeded their resource limits.
+fn callback_status(
+    result: &Result<(), RunnerError>,
+    execution: u16,
+    limit: u16,
+) -> (WorkerCallbackStatus, Option<String>) {
+    match result {
+        Ok(_) => (WorkerCallbackStatus::Done, None),
+        Err(e) if execution <= limit && !e.is_resource_limit_exceeded() => {
+            (WorkerCallbackStatus::Error, Some(format!("{e:?}")))
+        }
+        Err(e) => (WorkerCallbackStatus::Failed, Some(format!("{e:?}"))),
+    }
+}
+
 fn setup(arguments: Arguments) -> (Option<PathBuf>, PathBuf) {
     let check_and_join = |option: Option<PathBuf>, profile: bool| {
         option.and_then(|mut p| {
@@ -2296,3 +2337,53 @@ pub fn start() {
         Some(instance_folder),
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_status() {
+        let exited = || {
+            Err(WorkerExited {
+                message: "exit code 1".to_string(),
+            })
+        };
+        let timed_out = || {
+            Err(WorkerTimedOut {
+                worker: "function".to_string(),
+                timeout: Duration::from_secs(10),
+            })
+        };
+        let out_of_memory = || {
+            Err(WorkerOutOfMemory {
+                worker: "function".to_string(),
+                used: 2048,
+                limit: 1024,
+            })
+        };
+
+        assert!(matches!(
+            callback_status(&Ok(()), 1, 3),
+            (WorkerCallbackStatus::Done, None)
+        ));
+        assert!(matches!(
+            callback_status(&exited(), 1, 3),
+            (WorkerCallbackStatus::Error, Some(_))
+        ));
+        assert!(matches!(
+            callback_status(&exited(), 4, 3),
+            (WorkerCallbackStatus::Failed, Some(_))
+        ));
+        // runs exceeding their time limit fail without retries
+        let (status, error) = callback_status(&timed_out(), 1, 3);
+        assert!(matches!(status, WorkerCallbackStatus::Failed));
+        assert!(error.unwrap().contains("WorkerTimedOut"));
+        // runs exceeding their memory limit fail without retries, reporting the memory limit
+        let (status, error) = callback_status(&out_of_memory(), 1, 3);
+        assert!(matches!(status, WorkerCallbackStatus::Failed));
+        let error = error.unwrap();
+        assert!(error.contains("WorkerOutOfMemory"));
+        assert!(error.contains("limit: 1024"));
+    }
+}
@@ -115,7 +115,8 @@ pub enum TabsCliError {
 #[derive(Debug, Clone, clap_derive::Parser)]
 #[command(
     name = "Tabsdata Server",
-    version = "1.5.1",
+    version = td_common::about::build_info().version,
+    long_version = td_common::about::long_version(),
     about = "Tabsdata Server",
     long_about = "Any Tabsdata instance can be managed with the available commands of this tool. \
                   These commands rely on file 'pid' to control the state of any instance."
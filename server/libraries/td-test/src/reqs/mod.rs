@@ -20,6 +20,7 @@ pub mod gcp_storage;
 pub mod mysql;
 pub mod oracle;
 pub mod postgres;
+pub mod s3_emulator;
 
 pub use aws_s3::S3WithAccessKeySecretKeyReqs;
 pub use azure_storage::AzureStorageWithAccountKeyReqs;
@@ -27,6 +28,7 @@ pub use gcp_storage::GcpStorageWithServiceAccountKeyReqs;
 pub use mysql::MySqlReqs;
 pub use oracle::OracleReqs;
 pub use postgres::PostgresReqs;
+pub use s3_emulator::S3EmulatorReqs;
 
 const TEST_SKIP_IF_NO_REQS: &str = "TD_TEST_SKIP_IF_NO_REQS";
 
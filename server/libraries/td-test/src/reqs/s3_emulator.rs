@@ -0,0 +1,123 @@
+//
+// Copyright 2025. Tabs Data Inc.
+//
+
+use crate::reqs::{S3WithAccessKeySecretKeyReqs, TestRequirements};
+use std::collections::HashMap;
+
+#[allow(dead_code)]
+/// Requirements for an S3 test against a local S3 compatible emulator (i.e. MinIO) using an
+/// endpoint, Access Key, Secret Key and Region.
+pub struct S3EmulatorReqs {
+    pub uri: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+    pub endpoint: String,
+    vars: HashMap<String, String>,
+}
+
+impl S3EmulatorReqs {
+    /// Object store options to access the emulator, plain HTTP and path style requests allowed.
+    pub fn options(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("aws_region".to_string(), self.region.clone()),
+            ("aws_access_key_id".to_string(), self.access_key.clone()),
+            ("aws_secret_access_key".to_string(), self.secret_key.clone()),
+            ("aws_endpoint".to_string(), self.endpoint.clone()),
+            ("aws_allow_http".to_string(), "true".to_string()),
+            (
+                "aws_virtual_hosted_style_request".to_string(),
+                "false".to_string(),
+            ),
+        ])
+    }
+}
+
+impl TestRequirements for S3EmulatorReqs {
+    fn keys() -> &'static [&'static str] {
+        &[
+            "s3_uri",
+            "s3_region",
+            "s3_access_key",
+            "s3_secret_key",
+            "s3_endpoint",
+        ]
+    }
+
+    fn new(vars: impl Into<HashMap<String, String>>) -> Self {
+        let vars = vars.into();
+        Self {
+            uri: vars["s3_uri"].clone(),
+            region: vars["s3_region"].clone(),
+            access_key: vars["s3_access_key"].clone(),
+            secret_key: vars["s3_secret_key"].clone(),
+            endpoint: vars["s3_endpoint"].clone(),
+            vars,
+        }
+    }
+
+    fn vars(&self) -> &HashMap<String, String> {
+        &self.vars
+    }
+}
+
+impl From<&S3EmulatorReqs> for S3WithAccessKeySecretKeyReqs {
+    fn from(reqs: &S3EmulatorReqs) -> Self {
+        S3WithAccessKeySecretKeyReqs::new(reqs.vars.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as td_test;
+
+    use crate::reqs::{S3EmulatorReqs, S3WithAccessKeySecretKeyReqs, TestRequirementsInEnv};
+    use std::collections::HashMap;
+    use testdir::testdir;
+
+    #[crate::test(when(reqs = S3EmulatorReqs, env_prefix= "s3_emulator_test_not_defined", do_not_fail_reqs=true))]
+    #[tokio::test]
+    async fn test_signature_s3_emulator_reqs(_s3: S3EmulatorReqs) {
+        panic!()
+    }
+
+    #[test]
+    fn test_s3_emulator_reqs() {
+        let vars: HashMap<String, String> = HashMap::from([
+            (
+                "TEST_DIR".to_string(),
+                testdir!().to_str().unwrap().to_string(),
+            ),
+            (
+                "TESTS_TIMESTAMP".to_string(),
+                "0000_00_00_00_00_00".to_string(),
+            ),
+            ("NS__S3_URI".to_string(), "u".to_string()),
+            ("NS__S3_REGION".to_string(), "r".to_string()),
+            ("NS__S3_ACCESS_KEY".to_string(), "ak".to_string()),
+            ("NS__S3_SECRET_KEY".to_string(), "sk".to_string()),
+            ("NS__S3_ENDPOINT".to_string(), "http://e".to_string()),
+        ]);
+        let reqs = TestRequirementsInEnv::resolve_test_run_variables::<S3EmulatorReqs>(
+            "test_s3_emulator_reqs",
+            &testdir!(),
+            "S3EmulatorReqs",
+            "ns",
+            &vars,
+            false,
+        )
+        .unwrap();
+        assert_eq!(reqs.uri, "u");
+        assert_eq!(reqs.region, "r");
+        assert_eq!(reqs.access_key, "ak");
+        assert_eq!(reqs.secret_key, "sk");
+        assert_eq!(reqs.endpoint, "http://e");
+        assert_eq!(reqs.options()["aws_endpoint"], "http://e");
+        assert_eq!(reqs.options()["aws_allow_http"], "true");
+
+        let s3 = S3WithAccessKeySecretKeyReqs::from(&reqs);
+        assert_eq!(s3.uri, "u");
+        assert_eq!(s3.secret_key, "sk");
+    }
+}
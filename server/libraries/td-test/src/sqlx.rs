@@ -10,15 +10,48 @@ use td_database::sql::{Db, DbPool, DbSchema};
 pub struct SqlxTestSetup<'a> {
     schema: Option<&'static DbSchema>,
     fixtures: Vec<&'a str>,
+    vars: Vec<(&'a str, &'a str)>,
 }
 
 impl<'a> SqlxTestSetup<'a> {
     #[allow(dead_code)]
     pub fn new(schema: Option<&'static DbSchema>, fixtures: Vec<&'a str>) -> Self {
-        Self { schema, fixtures }
+        Self {
+            schema,
+            fixtures,
+            vars: vec![],
+        }
+    }
+
+    /// Variables to substitute, as `${VAR}`, in the fixtures.
+    pub fn with_vars(mut self, vars: Vec<(&'a str, &'a str)>) -> Self {
+        self.vars = vars;
+        self
     }
 }
 
+/// Replaces every `${VAR}` in the fixture with its value, failing if any variable is not given.
+pub fn render_fixture(fixture: &str, vars: &[(&str, &str)]) -> Result<String, String> {
+    let mut rendered = String::with_capacity(fixture.len());
+    let mut rest = fixture;
+    while let Some(start) = rest.find("${") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("Unterminated fixture variable: {}", &rest[start..]))?;
+        let name = &after[..end];
+        let (_, value) = vars
+            .iter()
+            .find(|(var, _)| *var == name)
+            .ok_or_else(|| format!("Fixture variable '{name}' has no value"))?;
+        rendered.push_str(value);
+        rest = &after[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
 #[async_trait]
 impl TestSetup<DbPool> for SqlxTestSetup<'_> {
     /// Similar to [`sqlx::testing::setup_test_db`], but generating DbPool.
@@ -34,17 +67,47 @@ impl TestSetup<DbPool> for SqlxTestSetup<'_> {
 
         let rw_pool = Db::schema().rw_pool(&config).await.unwrap();
         let ro_pool = Db::schema().ro_connect(&config).await.unwrap();
+        schema.run(&rw_pool).await.unwrap();
         let db = DbPool {
             schema,
             ro_pool,
-            rw_pool,
+            rw_pool: Some(rw_pool),
         };
-        schema.run(&db.rw_pool).await.unwrap();
 
         for fixture in &self.fixtures {
-            db.execute(*fixture).await.unwrap();
+            let fixture = render_fixture(fixture, &self.vars).unwrap();
+            db.execute(fixture.as_str()).await.unwrap();
         }
 
         TestSetupExecution::Run(db)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_fixture() {
+        let fixture = "INSERT INTO t SELECT '${id}', '${name}', '${id}';";
+        let rendered = render_fixture(fixture, &[("id", "c1"), ("name", "mario")]).unwrap();
+        assert_eq!(rendered, "INSERT INTO t SELECT 'c1', 'mario', 'c1';");
+    }
+
+    #[test]
+    fn test_render_fixture_without_vars() {
+        let fixture = "INSERT INTO t SELECT 'c1';";
+        assert_eq!(render_fixture(fixture, &[]).unwrap(), fixture);
+    }
+
+    #[test]
+    fn test_render_fixture_missing_var() {
+        let err = render_fixture("SELECT '${id}'", &[("name", "mario")]).unwrap_err();
+        assert!(err.contains("'id'"));
+    }
+
+    #[test]
+    fn test_render_fixture_unterminated_var() {
+        assert!(render_fixture("SELECT '${id'", &[("id", "c1")]).is_err());
+    }
+}
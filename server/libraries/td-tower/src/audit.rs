@@ -0,0 +1,273 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Audit log of the services changing entities.
+//!
+//! Create, update and delete services, and the services declaring their `audit` entity, are
+//! audited by the `service_factory` macro with two layers, recording the outcome of the service
+//! with the [`AuditedRequest`] implementation of its request:
+//!
+//! - [`AuditProvider`] wraps the service layers, inside its transaction, and once they complete
+//!   it records the change, with the id of the changed entity if the service declares it. The
+//!   record is written in the service transaction, so it is committed or rolled back with the
+//!   change it records.
+//! - [`AuditFailureProvider`] wraps the connection provider, and if the service fails it records
+//!   the failure, with its error code, in a connection of its own once the service transaction
+//!   has been rolled back.
+
+use crate::extractors::{FromHandler, Input};
+use crate::handler::Handler;
+use futures_util::future::BoxFuture;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use td_database::sql::DbPool;
+use td_error::TdError;
+use tower::{Layer, Service};
+use tracing::error;
+
+/// Connection an audit record is written with.
+pub enum AuditWriter<'a> {
+    /// The connection of the service, found in its handler, in its transaction if it has one.
+    Service(&'a Handler),
+    /// A connection of its own, once the service connection has been released.
+    Pool(&'a DbPool),
+}
+
+/// Request of a service changing entities, recorded in the audit log.
+pub trait AuditedRequest: Send + Sync + 'static {
+    /// Records the outcome of the given service with this request, and the id of the entity it
+    /// changed, if known.
+    fn audit<'a>(
+        &'a self,
+        writer: AuditWriter<'a>,
+        service: &'static str,
+        entity: Option<String>,
+        outcome: Result<(), &'a TdError>,
+    ) -> BoxFuture<'a, Result<(), TdError>>;
+}
+
+/// Metadata runs of services have no request, there is nothing to record.
+impl AuditedRequest for () {
+    fn audit<'a>(
+        &'a self,
+        _writer: AuditWriter<'a>,
+        _service: &'static str,
+        _entity: Option<String>,
+        _outcome: Result<(), &'a TdError>,
+    ) -> BoxFuture<'a, Result<(), TdError>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Finds, in the handler of a completed service, the id of the entity it changed.
+pub type AuditedEntity = fn(&Handler) -> Option<String>;
+
+/// [`AuditedEntity`] of the services changing entities with ids of type `E`.
+pub fn entity<E: Display + Send + Sync + 'static>(handler: &Handler) -> Option<String> {
+    handler
+        .get::<Input<E>>()
+        .map(|Input(entity)| entity.to_string())
+}
+
+/// [`AuditedEntity`] of the services not declaring the entity they change.
+pub fn no_entity(_handler: &Handler) -> Option<String> {
+    None
+}
+
+/// AuditProvider is a layer wrapping AuditProviderService.
+pub struct AuditProvider<Req> {
+    service: &'static str,
+    entity: AuditedEntity,
+    phantom: PhantomData<Req>,
+}
+
+impl<Req> AuditProvider<Req> {
+    pub fn new(service: &'static str, entity: AuditedEntity) -> Self {
+        Self {
+            service,
+            entity,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Req> Clone for AuditProvider<Req> {
+    fn clone(&self) -> Self {
+        Self::new(self.service, self.entity)
+    }
+}
+
+impl<S, Req> Layer<S> for AuditProvider<Req> {
+    type Service = AuditProviderService<S, Req>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        AuditProviderService {
+            inner: service,
+            service: self.service,
+            entity: self.entity,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// AuditProviderService is a service that records in the audit log the change done by the
+/// services chain. Failing to record it fails the service, rolling back the change.
+pub struct AuditProviderService<S, Req> {
+    inner: S,
+    service: &'static str,
+    entity: AuditedEntity,
+    phantom: PhantomData<Req>,
+}
+
+impl<S: Clone, Req> Clone for AuditProviderService<S, Req> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            service: self.service,
+            entity: self.entity,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, Req> Service<Handler> for AuditProviderService<S, Req>
+where
+    S: Service<Handler, Response = Handler, Error = TdError> + Clone + Send + 'static,
+    S::Future: Send,
+    Req: AuditedRequest,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, handler: Handler) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let service = self.service;
+        let entity = self.entity;
+        Box::pin(async move {
+            let handler = inner.call(handler).await?;
+
+            let Input(request) = Input::<Req>::from_handler(&handler)?;
+            let entity = entity(&handler);
+            request
+                .audit(AuditWriter::Service(&handler), service, entity, Ok(()))
+                .await?;
+            Ok(handler)
+        })
+    }
+}
+
+/// AuditFailureProvider is a layer wrapping AuditFailureProviderService.
+pub struct AuditFailureProvider<Req> {
+    db: DbPool,
+    service: &'static str,
+    phantom: PhantomData<Req>,
+}
+
+impl<Req> AuditFailureProvider<Req> {
+    pub fn new(db: DbPool, service: &'static str) -> Self {
+        Self {
+            db,
+            service,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Req> Clone for AuditFailureProvider<Req> {
+    fn clone(&self) -> Self {
+        Self::new(self.db.clone(), self.service)
+    }
+}
+
+impl<S, Req> Layer<S> for AuditFailureProvider<Req> {
+    type Service = AuditFailureProviderService<S, Req>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        AuditFailureProviderService {
+            inner: service,
+            db: self.db.clone(),
+            service: self.service,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// AuditFailureProviderService is a service that records in the audit log the failures of the
+/// services chain. Failing to record them is logged, the service error is returned as is.
+pub struct AuditFailureProviderService<S, Req> {
+    inner: S,
+    db: DbPool,
+    service: &'static str,
+    phantom: PhantomData<Req>,
+}
+
+impl<S: Clone, Req> Clone for AuditFailureProviderService<S, Req> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            db: self.db.clone(),
+            service: self.service,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S, Req> Service<Handler> for AuditFailureProviderService<S, Req>
+where
+    S: Service<Handler, Response = Handler, Error = TdError> + Clone + Send + 'static,
+    S::Future: Send,
+    Req: AuditedRequest,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, handler: Handler) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let db = self.db.clone(); // this is not cloning the pool, just its arc
+        let service = self.service;
+        Box::pin(async move {
+            let request: Option<Arc<Req>> =
+                handler.get::<Input<Req>>().map(|Input(req)| req.clone());
+
+            let result = inner.call(handler).await;
+
+            if let Err(e) = &result {
+                match request {
+                    Some(request) => {
+                        let res = request
+                            .audit(AuditWriter::Pool(&db), service, None, Err(e))
+                            .await;
+                        if let Err(e) = res {
+                            error!("Could not record audit log of {service}: {e}");
+                        }
+                    }
+                    None => error!("Could not record audit log of {service}: request not found"),
+                }
+            }
+            result
+        })
+    }
+}
@@ -0,0 +1,133 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Request deadlines.
+//!
+//! A deadline is set for a request with [`with_deadline`] (by the API server, from the client
+//! timeout or the server default) and it is carried, task local, to everything the request runs.
+//! Reads against slow backends use [`within_deadline`] so they are aborted once the deadline is
+//! reached, instead of running past the client timeout. Operations with side effects (services,
+//! which may commit, and storage writes) use [`check_deadline`] instead, so they do not start
+//! past the deadline but, once started, they are not cancelled half way.
+
+use crate::error::DeadlineError;
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+/// Runs the given future with the given deadline. If there is already an earlier deadline, the
+/// earlier one applies.
+pub async fn with_deadline<F: Future>(deadline: Instant, f: F) -> F::Output {
+    let deadline = match current_deadline() {
+        Some(current) => current.min(deadline),
+        None => deadline,
+    };
+    DEADLINE.scope(deadline, f).await
+}
+
+/// Returns the deadline of the current task, if any.
+pub fn current_deadline() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+/// Returns the time left until the deadline of the current task, if any.
+pub fn remaining() -> Option<Duration> {
+    current_deadline().map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Fails with [`DeadlineError::DeadlineExceeded`] if the deadline of the current task, if any,
+/// is already past. Used before starting operations that must not be cancelled half way.
+pub fn check_deadline(operation: &str) -> Result<(), DeadlineError> {
+    match remaining() {
+        Some(remaining) if remaining.is_zero() => {
+            Err(DeadlineError::DeadlineExceeded(operation.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Runs the given operation within the deadline of the current task, if any. Only for
+/// operations without side effects, as they are cancelled if the deadline is reached.
+///
+/// Fails with [`DeadlineError::DeadlineExceeded`] if the deadline is reached, without starting
+/// the operation if the deadline is already past.
+pub async fn within_deadline<F: Future>(operation: &str, f: F) -> Result<F::Output, DeadlineError> {
+    check_deadline(operation)?;
+    match remaining() {
+        None => Ok(f.await),
+        Some(remaining) => tokio::time::timeout(remaining, f)
+            .await
+            .map_err(|_| DeadlineError::DeadlineExceeded(operation.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_within_deadline() {
+        // without deadline, operations are not limited
+        assert!(remaining().is_none());
+        let res = within_deadline("sleep", tokio::time::sleep(Duration::from_millis(10))).await;
+        assert!(res.is_ok());
+
+        let deadline = Instant::now() + Duration::from_millis(50);
+        with_deadline(deadline, async {
+            assert_eq!(current_deadline(), Some(deadline));
+            assert!(remaining().unwrap() <= Duration::from_millis(50));
+
+            let res = within_deadline("fast", async { 1 }).await;
+            assert_eq!(res.unwrap(), 1);
+
+            let res = within_deadline("slow", tokio::time::sleep(Duration::from_secs(10))).await;
+            assert!(matches!(res, Err(DeadlineError::DeadlineExceeded(op)) if op == "slow"));
+
+            // once the deadline is past, operations do not start, not even ready ones
+            let res = within_deadline("late", async { 1 }).await;
+            assert!(matches!(res, Err(DeadlineError::DeadlineExceeded(op)) if op == "late"));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_check_deadline() {
+        assert!(check_deadline("any").is_ok());
+
+        let deadline = Instant::now() + Duration::from_millis(50);
+        with_deadline(deadline, async {
+            assert!(check_deadline("before").is_ok());
+
+            // started operations are not cancelled, later ones do not start
+            tokio::time::sleep_until(deadline).await;
+            let err = check_deadline("after").unwrap_err();
+            assert!(matches!(err, DeadlineError::DeadlineExceeded(op) if op == "after"));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline_nested() {
+        let earlier = Instant::now() + Duration::from_secs(1);
+        let later = Instant::now() + Duration::from_secs(10);
+        with_deadline(earlier, async {
+            with_deadline(later, async {
+                assert_eq!(current_deadline(), Some(earlier));
+            })
+            .await;
+        })
+        .await;
+        with_deadline(later, async {
+            with_deadline(earlier, async {
+                assert_eq!(current_deadline(), Some(earlier));
+            })
+            .await;
+        })
+        .await;
+    }
+}
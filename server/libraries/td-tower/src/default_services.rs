@@ -6,9 +6,11 @@
 //! be created by composing these services with other layer.
 
 use crate::ctx_service::CtxResponse;
+use crate::deadline::{check_deadline, within_deadline};
 use crate::error::{ConnectionError, FromHandlerError};
 use crate::extractors::{Connection, ConnectionType, Input, ReqCtx, SrvCtx};
 use crate::handler::{Handler, IntoHandler};
+use crate::trace::TracedRequest;
 use std::any::type_name;
 use std::future::Future;
 use std::marker::PhantomData;
@@ -17,7 +19,7 @@ use std::sync::Arc;
 use td_database::sql::DbPool;
 use td_error::TdError;
 use tower::{Layer, Service};
-use tracing::{error, trace};
+use tracing::{Instrument, error, info_span, trace};
 
 /// Utility trait to ensure that the type is Send + Sync + 'static
 pub trait Share: Send + Sync + 'static {}
@@ -25,12 +27,24 @@ impl<T: Send + Sync + 'static> Share for T {}
 
 /// ServiceEntry is a layer wrapping InitService.
 pub struct ServiceEntry<Res> {
+    service: Option<&'static str>,
     phantom: PhantomData<Res>,
 }
 
+impl<Res> ServiceEntry<Res> {
+    /// Entry of the named service, the name is recorded in the span of its calls.
+    pub fn new(service: &'static str) -> Self {
+        ServiceEntry {
+            service: Some(service),
+            phantom: PhantomData,
+        }
+    }
+}
+
 impl<Res> Default for ServiceEntry<Res> {
     fn default() -> Self {
         ServiceEntry {
+            service: None,
             phantom: PhantomData,
         }
     }
@@ -39,6 +53,7 @@ impl<Res> Default for ServiceEntry<Res> {
 impl<Res> Clone for ServiceEntry<Res> {
     fn clone(&self) -> Self {
         ServiceEntry {
+            service: self.service,
             phantom: PhantomData,
         }
     }
@@ -50,6 +65,7 @@ impl<S, Res> Layer<S> for ServiceEntry<Res> {
     fn layer(&self, service: S) -> Self::Service {
         InitService {
             inner: service,
+            service: self.service,
             phantom: PhantomData,
         }
     }
@@ -58,9 +74,13 @@ impl<S, Res> Layer<S> for ServiceEntry<Res> {
 /// InitService will initialize the service with the handler. It will also extract the required type on the way out,
 /// conditioning the Response type of the whole service.
 ///
+/// The service call runs in a `service` span, with the service name and the request id, if any
+/// (see [`crate::trace`]).
+///
 /// With `test_tower_metadata` feature enabled, add the Metadata struct to the handler too.
 pub struct InitService<S, Res> {
     inner: S,
+    service: Option<&'static str>,
     phantom: PhantomData<Res>,
 }
 
@@ -71,6 +91,7 @@ where
     fn clone(&self) -> Self {
         InitService {
             inner: self.inner.clone(),
+            service: self.service,
             phantom: PhantomData,
         }
     }
@@ -80,7 +101,7 @@ impl<S, Req, Res> Service<Req> for InitService<S, Res>
 where
     S: Service<Handler, Response = Handler, Error = TdError> + Clone + Send + 'static,
     S::Future: Send + 'static,
-    Req: Send + Sync + 'static,
+    Req: TracedRequest,
     Res: Send + Sync + 'static,
 {
     type Response = CtxResponse<Res>;
@@ -99,61 +120,77 @@ where
         let clone = self.inner.clone();
         let mut inner = std::mem::replace(&mut self.inner, clone);
 
-        Box::pin(async move {
-            // Create handler with initial request
-            let mut handler = Handler::new();
-            // For convenience, we insert the () into the handler (unit type always present).
-            handler.insert(Input(Arc::new(())));
-            handler.insert(Input(Arc::new(req)));
-
-            // Also insert context
-            handler.insert(ReqCtx::default());
-
-            #[cfg(feature = "test_tower_metadata")]
-            {
-                use crate::metadata::{MetadataMutex, type_of};
+        let request_id = req.request_id();
+        let span = info_span!(
+            "service",
+            service = self.service,
+            request_id = request_id.as_deref(),
+        );
+        Box::pin(
+            async move {
+                // Create handler with initial request
+                let mut handler = Handler::new();
+                // For convenience, we insert the () into the handler (unit type always present).
+                handler.insert(Input(Arc::new(())));
+                handler.insert(Input(Arc::new(req)));
+
+                // Also insert context
+                handler.insert(ReqCtx::default());
+
+                #[cfg(feature = "test_tower_metadata")]
+                {
+                    use crate::metadata::{MetadataMutex, type_of};
+
+                    // Add metadata to handler
+                    let res_type_name = type_of::<ReqCtx>();
+                    let metadata = MetadataMutex::with_initial_types(&[res_type_name]);
+                    handler.insert(Input::new(metadata));
+                }
 
-                // Add metadata to handler
-                let res_type_name = type_of::<ReqCtx>();
-                let metadata = MetadataMutex::with_initial_types(&[res_type_name]);
-                handler.insert(Input::new(metadata));
+                // And send it to the next service, if the request deadline, if any, is not past.
+                // Once started the service is not cancelled, as it may be half way through side
+                // effects.
+                let res = match check_deadline(type_name::<Req>()) {
+                    Ok(()) => inner.call(handler).await,
+                    Err(e) => Err(TdError::from(e)),
+                };
+                let mut handler = match res {
+                    Ok(handler) => {
+                        trace!("Service completed successfully");
+                        Ok(handler)
+                    }
+                    Err(e) => {
+                        error!("{e}");
+                        Err(e)
+                    }
+                }?;
+
+                // Extract the response type from the handler (we don't check for context, just
+                // other input types layer might have created)
+                let res = handler
+                    .remove::<Input<Res>>()
+                    .ok_or(FromHandlerError::NotFound(String::from(type_name::<Res>())))?;
+                let res = Arc::try_unwrap(res.0).map_err(|_| {
+                    FromHandlerError::InternalError(String::from(type_name::<Res>()))
+                })?;
+                // Also get ctx
+                let ctx = handler
+                    .remove::<ReqCtx>()
+                    .ok_or(FromHandlerError::NotFound(String::from(
+                        type_name::<ReqCtx>(),
+                    )))?;
+                let ctx = ctx
+                    .arc()
+                    .lock()
+                    .await
+                    .take()
+                    .ok_or(FromHandlerError::NotFound(String::from(
+                        type_name::<ReqCtx>(),
+                    )))?;
+                Ok(CtxResponse::new(res, ctx))
             }
-
-            // And send it to the next service, awaiting the completion
-            let mut handler = match inner.call(handler).await {
-                Ok(handler) => {
-                    trace!("Service completed successfully");
-                    Ok(handler)
-                }
-                Err(e) => {
-                    error!("{e}");
-                    Err(e)
-                }
-            }?;
-
-            // Extract the response type from the handler (we don't check for context, just other
-            // input types layer might have created)
-            let res = handler
-                .remove::<Input<Res>>()
-                .ok_or(FromHandlerError::NotFound(String::from(type_name::<Res>())))?;
-            let res = Arc::try_unwrap(res.0)
-                .map_err(|_| FromHandlerError::InternalError(String::from(type_name::<Res>())))?;
-            // Also get ctx
-            let ctx = handler
-                .remove::<ReqCtx>()
-                .ok_or(FromHandlerError::NotFound(String::from(
-                    type_name::<ReqCtx>(),
-                )))?;
-            let ctx = ctx
-                .arc()
-                .lock()
-                .await
-                .take()
-                .ok_or(FromHandlerError::NotFound(String::from(
-                    type_name::<ReqCtx>(),
-                )))?;
-            Ok(CtxResponse::new(res, ctx))
-        })
+            .instrument(span),
+        )
     }
 }
 
@@ -304,10 +341,10 @@ where
 
         let db = self.db.clone(); // this is not cloning the pool, just its arc
         Box::pin(async move {
-            // Create connection
-            let connection = db
-                .acquire()
+            // Create connection, not waiting for one past the request deadline, if any
+            let connection = within_deadline("acquire connection", db.acquire())
                 .await
+                .map_err(TdError::from)?
                 .map_err(ConnectionError::CannotGetConnection)
                 .map_err(TdError::new)?;
             let connection = ConnectionType::PoolConnection(connection).into();
@@ -388,10 +425,13 @@ where
 
         let db = self.db.clone(); // this is not cloning the pool, just its arc
         Box::pin(async move {
-            // Create transaction
-            let transaction = db
-                .begin()
+            // Fail early with a clear error if the database is in read-only mode
+            db.read_write_pool().map_err(TdError::new)?;
+
+            // Create transaction, not waiting for a connection past the request deadline, if any
+            let transaction = within_deadline("begin transaction", db.begin())
                 .await
+                .map_err(TdError::from)?
                 .map_err(ConnectionError::CannotBeginTransaction)
                 .map_err(TdError::new)?;
             let transaction = ConnectionType::Transaction(transaction).into();
@@ -742,16 +782,111 @@ mod tests {
     use td_error::TdError;
     use tower::{ServiceBuilder, ServiceExt};
 
+    // Requests of the tests, without request id.
+    impl TracedRequest for i32 {}
+    impl TracedRequest for bool {}
+
     #[tokio::test]
     async fn test_init_service() {
         let init_service = InitService {
             inner: ServiceReturn,
+            service: None,
             phantom: PhantomData::<i32>,
         };
         let res = init_service.raw_oneshot(1).await;
         assert!(matches!(res, Ok(1)));
     }
 
+    #[cfg(not(feature = "test_tower_metadata"))]
+    #[tokio::test]
+    async fn test_init_service_span() {
+        use std::collections::HashMap;
+        use std::sync::Mutex;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id};
+        use tracing::subscriber::set_default;
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::registry;
+
+        type RecordedSpan = (String, HashMap<String, String>);
+
+        #[derive(Clone, Default)]
+        struct SpanRecorder(Arc<Mutex<Vec<RecordedSpan>>>);
+
+        impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanRecorder {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+                let mut fields = FieldsVisitor::default();
+                attrs.record(&mut fields);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((attrs.metadata().name().to_string(), fields.0));
+            }
+        }
+
+        #[derive(Default)]
+        struct FieldsVisitor(HashMap<String, String>);
+
+        impl Visit for FieldsVisitor {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{value:?}"));
+            }
+        }
+
+        struct TestRequest(i32);
+
+        impl TracedRequest for TestRequest {
+            fn request_id(&self) -> Option<String> {
+                Some("test-request".to_string())
+            }
+        }
+
+        async fn add_one(Input(req): Input<TestRequest>) -> Result<i32, TdError> {
+            Ok(req.0 + 1)
+        }
+
+        let recorder = SpanRecorder::default();
+        let _guard = set_default(registry().with(recorder.clone()));
+
+        // without name nor request id
+        let service = ServiceBuilder::new()
+            .layer(ServiceEntry::default())
+            .layer(from_fn(|Input(x): Input<i32>| async move {
+                Ok::<_, TdError>(*x + 1)
+            }))
+            .service(ServiceReturn);
+        let response: i32 = service.raw_oneshot(3).await.unwrap();
+        assert_eq!(response, 4);
+
+        // with name and request id, in a single span for all the service layers
+        let service = ServiceBuilder::new()
+            .layer(ServiceEntry::new("TestService"))
+            .layer(from_fn(add_one))
+            .layer(from_fn(|Input(x): Input<i32>| async move {
+                Ok::<_, TdError>(*x + 1)
+            }))
+            .service(ServiceReturn);
+        let response: i32 = service.raw_oneshot(TestRequest(3)).await.unwrap();
+        assert_eq!(response, 5);
+
+        let spans = recorder.0.lock().unwrap().clone();
+        let spans: Vec<_> = spans
+            .into_iter()
+            .filter(|(name, _)| name == "service")
+            .map(|(_, fields)| fields)
+            .collect();
+        assert_eq!(spans.len(), 2);
+        assert!(!spans[0].contains_key("service"));
+        assert!(!spans[0].contains_key("request_id"));
+        assert_eq!(spans[1]["service"], "TestService");
+        assert_eq!(spans[1]["request_id"], "test-request");
+    }
+
     #[cfg(feature = "test_tower_metadata")]
     #[tokio::test]
     async fn test_tower_metadata_init_service() {
@@ -759,6 +894,7 @@ mod tests {
 
         let init_service = InitService {
             inner: ServiceReturn,
+            service: None,
             phantom: PhantomData::<MetadataMutex>,
         };
 
@@ -807,6 +943,35 @@ mod tests {
         assert!(res);
     }
 
+    #[cfg(not(feature = "test_tower_metadata"))]
+    #[tokio::test]
+    async fn test_connection_provider_past_deadline() {
+        use crate::deadline::with_deadline;
+        use crate::error::DeadlineError;
+        use tokio::time::Instant;
+
+        let db = td_database::test_utils::db().await.unwrap();
+        let service = ServiceBuilder::new()
+            .layer(ConnectionProvider::new(db.clone()))
+            .service(ServiceReturn);
+        let res = with_deadline(Instant::now(), service.oneshot(Handler::new())).await;
+        let err = res.err().unwrap();
+        assert!(matches!(
+            err.domain_err::<DeadlineError>(),
+            DeadlineError::DeadlineExceeded(op) if op == "acquire connection"
+        ));
+
+        let service = ServiceBuilder::new()
+            .layer(TransactionProvider::new(db))
+            .service(ServiceReturn);
+        let res = with_deadline(Instant::now(), service.oneshot(Handler::new())).await;
+        let err = res.err().unwrap();
+        assert!(matches!(
+            err.domain_err::<DeadlineError>(),
+            DeadlineError::DeadlineExceeded(op) if op == "begin transaction"
+        ));
+    }
+
     #[tokio::test]
     async fn test_transaction_provider_layer() {
         let db = td_database::test_utils::db().await.unwrap();
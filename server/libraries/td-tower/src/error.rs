@@ -23,3 +23,9 @@ pub enum ConnectionError {
     #[error("Broken connection while processing Service request")]
     ConnectionLost = 5003,
 }
+
+#[td_error]
+pub enum DeadlineError {
+    #[error("Request deadline exceeded, '{0}' did not complete in time")]
+    DeadlineExceeded(String) = 7000,
+}
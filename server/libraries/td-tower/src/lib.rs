@@ -4,7 +4,9 @@
 
 pub use tm_tower::*;
 
+pub mod audit;
 pub mod ctx_service;
+pub mod deadline;
 pub mod default_services;
 pub mod error;
 pub mod extractors;
@@ -13,3 +15,4 @@ pub mod handler;
 pub mod metadata;
 pub mod service_macro;
 pub mod service_provider;
+pub mod trace;
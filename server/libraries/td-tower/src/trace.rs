@@ -0,0 +1,21 @@
+//
+// Copyright 2025 Tabs Data Inc.
+//
+
+//! Tracing of services.
+//!
+//! Each service call runs in a `service` span, opened by the
+//! [`ServiceEntry`](crate::default_services::ServiceEntry) layer, with the name of the service
+//! and, if known, the id of the request being served. The request id is given by the
+//! [`TracedRequest`] implementation of the service request.
+
+/// Request of a service, carrying the id of the request being served, if any.
+pub trait TracedRequest: Send + Sync + 'static {
+    /// Id of the request, recorded in the span of the service serving it.
+    fn request_id(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Services without request have no request id.
+impl TracedRequest for () {}